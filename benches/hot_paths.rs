@@ -0,0 +1,310 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
+use lib_dachshund::dachshund::algorithms::clustering::Clustering;
+use lib_dachshund::dachshund::algorithms::cnm_communities::{
+    CNMCommunities, Community, CommunityId,
+};
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::label_propagation::LabelPropagation;
+use lib_dachshund::dachshund::algorithms::leiden::Leiden;
+use lib_dachshund::dachshund::algorithms::partition_comparison::{
+    get_adjusted_rand_index, get_community_labels, get_normalized_mutual_information,
+};
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
+use lib_dachshund::dachshund::algorithms::triangles::Triangles;
+use lib_dachshund::dachshund::candidate::Candidate;
+use lib_dachshund::dachshund::graph_arena::GraphArena;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId, NodeLabel};
+use lib_dachshund::dachshund::node::{Node, NodeBase};
+use lib_dachshund::dachshund::scorer::Scorer;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::{
+    gen_planted_clique_edges, gen_planted_clique_undirected_graph, gen_test_transformer,
+    process_raw_vector,
+};
+use lib_dachshund::dachshund::transformer::Transformer;
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use rand::prelude::*;
+use roaring::RoaringBitmap;
+use std::collections::HashMap;
+
+// Benchmarks of the hot paths of searches and graph algorithms, which run on a stable
+// toolchain: `cargo bench --bench hot_paths`.
+
+// a random graph with an average degree of about 10, including a 20-clique.
+fn get_graph(n: i64) -> SimpleUndirectedGraph {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..20).collect();
+    let edges = gen_planted_clique_edges(n, 10.0 / n as f64, &clique, &mut rng);
+    SimpleUndirectedGraphBuilder {}.from_vector(edges).unwrap()
+}
+
+fn get_transformer() -> Transformer {
+    let typespec: Vec<Vec<String>> = ["published", "cited", "reviewed"]
+        .iter()
+        .map(|edge_type| {
+            vec![
+                "author".to_string(),
+                edge_type.to_string(),
+                "article".into(),
+            ]
+        })
+        .collect();
+    gen_test_transformer(typespec, "author".to_string()).unwrap()
+}
+
+// 500 authors and 500 articles, with ties of 3 types, each present with probability 0.1.
+fn get_typed_graph(transformer: &Transformer) -> TypedGraph {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut raw: Vec<String> = Vec::new();
+    for author in 0..500 {
+        for article in 500..1000 {
+            for edge_type in ["published", "cited", "reviewed"] {
+                if rng.gen::<f64>() < 0.1 {
+                    raw.push(format!(
+                        "0\t{author}\t{article}\tauthor\t{edge_type}\tarticle"
+                    ));
+                }
+            }
+        }
+    }
+    let rows = process_raw_vector(transformer, raw).unwrap();
+    transformer
+        .build_pruned_graph(GraphId::from(0), rows)
+        .unwrap()
+}
+
+// counts ties by iterating over edges, as a baseline for `NodeBase::count_ties_with_ids`.
+fn count_ties_by_edges(node: &Node, ids: &RoaringBitmap) -> usize {
+    node.edges
+        .iter()
+        .filter(|e| ids.contains(e.target_id))
+        .count()
+}
+
+fn bench_candidates(c: &mut Criterion) {
+    let transformer = get_transformer();
+    let graph = get_typed_graph(&transformer);
+    let scorer = Scorer::new(1, &transformer.search_problem);
+    // the first 50 authors and articles.
+    let core_labels: Vec<NodeLabel> = (0..50).map(NodeLabel::from).collect();
+    let non_core_labels: Vec<NodeLabel> = (500..550).map(NodeLabel::from).collect();
+    let candidate = Candidate::from_labels(&core_labels, &non_core_labels, &graph, &scorer)
+        .unwrap()
+        .unwrap();
+    c.bench_function("candidate_replicate", |b| {
+        b.iter(|| candidate.replicate(true))
+    });
+    // adding a node counts its ties with the candidate.
+    let node_id = graph.get_node_by_label(NodeLabel::from(50)).node_id;
+    c.bench_function("candidate_add_node", |b| {
+        b.iter(|| {
+            let mut candidate = candidate.replicate(true);
+            candidate.add_node(node_id).unwrap();
+            candidate
+        })
+    });
+    c.bench_function("node_count_ties_with_ids", |b| {
+        b.iter(|| {
+            graph
+                .get_nodes_iter()
+                .map(|node| node.count_ties_with_ids(&candidate.non_core_ids))
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("node_count_ties_by_edges", |b| {
+        b.iter(|| {
+            graph
+                .get_nodes_iter()
+                .map(|node| count_ties_by_edges(node, &candidate.non_core_ids))
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("candidate_score", |b| {
+        b.iter(|| {
+            let mut candidate = candidate.replicate(false);
+            scorer.score(&mut candidate).unwrap()
+        })
+    });
+}
+
+fn bench_graph_building(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..20).collect();
+    let mut edges = gen_planted_clique_edges(50000, 10.0 / 50000.0, &clique, &mut rng);
+    edges.sort_unstable();
+    c.bench_function("graph_from_vector_50000", |b| {
+        b.iter(|| {
+            SimpleUndirectedGraphBuilder {}
+                .from_vector(edges.clone())
+                .unwrap()
+        })
+    });
+    c.bench_function("graph_from_sorted_edges_50000", |b| {
+        b.iter(|| {
+            SimpleUndirectedGraphBuilder {}
+                .from_sorted_edges(&edges)
+                .unwrap()
+        })
+    });
+}
+
+fn bench_shortest_paths(c: &mut Criterion) {
+    let graph = get_graph(5000);
+    let source = NodeId::from(0);
+    c.bench_function("shortest_paths_bfs_5000", |b| {
+        b.iter(|| graph.get_shortest_paths_bfs(source))
+    });
+    // loading the graph into an arena is part of the cost.
+    let mut arena = GraphArena::new();
+    c.bench_function("arena_distances_5000", |b| {
+        b.iter(|| {
+            arena.load(&graph);
+            arena.compute_distances(source).unwrap();
+        })
+    });
+    c.bench_function("shortest_paths_5000", |b| {
+        b.iter(|| graph.get_shortest_paths(source, &None))
+    });
+    c.bench_function("weighted_shortest_paths_5000", |b| {
+        b.iter(|| graph.get_weighted_shortest_paths(source).unwrap())
+    });
+    let small_graph = get_graph(1000);
+    c.bench_function("shortest_paths_1000", |b| {
+        b.iter(|| small_graph.get_shortest_paths(source, &None))
+    });
+    let graph = get_graph(200);
+    c.bench_function("node_betweenness_200", |b| {
+        b.iter(|| graph.get_node_betweenness().unwrap())
+    });
+    c.bench_function("node_betweenness_brandes_200", |b| {
+        b.iter(|| graph.get_node_betweenness_brandes().unwrap())
+    });
+}
+
+fn bench_coreness(c: &mut Criterion) {
+    let graph = get_graph(5000);
+    c.bench_function("coreness_values_5000", |b| {
+        b.iter(|| graph.get_coreness_values())
+    });
+    let mut arena = GraphArena::new();
+    c.bench_function("arena_coreness_values_5000", |b| {
+        b.iter(|| {
+            arena.load(&graph);
+            arena.compute_coreness_values();
+        })
+    });
+    c.bench_function("k_cores_5000", |b| b.iter(|| graph.get_k_cores(5)));
+}
+
+// 1000 nodes planted in 20 communities of 50, tied with probability 0.2 within communities
+// and 0.002 across, along with the planted community of each node.
+fn get_planted_partition_graph() -> (SimpleUndirectedGraph, HashMap<NodeId, usize>) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..1000 {
+        for j in (i + 1)..1000 {
+            let p = if i / 50 == j / 50 { 0.2 } else { 0.002 };
+            if rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    let truth = (0..1000)
+        .map(|i| (NodeId::from(i), (i / 50) as usize))
+        .collect();
+    (
+        SimpleUndirectedGraphBuilder {}.from_vector(edges).unwrap(),
+        truth,
+    )
+}
+
+// prints how close communities are to the planted ones, so that the accuracy of each
+// algorithm can be compared along with its speed.
+fn print_accuracy(
+    name: &str,
+    communities: &HashMap<CommunityId, Community>,
+    truth: &HashMap<NodeId, usize>,
+) {
+    let labels = get_community_labels(communities);
+    eprintln!(
+        "{}: {} communities, NMI {:.3}, ARI {:.3}",
+        name,
+        communities.len(),
+        get_normalized_mutual_information(&labels, truth).unwrap(),
+        get_adjusted_rand_index(&labels, truth).unwrap(),
+    );
+}
+
+fn bench_communities(c: &mut Criterion) {
+    let graph = get_graph(1000);
+    c.bench_function("cnm_communities_1000", |b| {
+        b.iter(|| graph.get_cnm_communities())
+    });
+    let (graph, truth) = get_planted_partition_graph();
+    print_accuracy("leiden", &graph.get_leiden_communities().0, &truth);
+    c.bench_function("leiden_communities_planted_1000", |b| {
+        b.iter(|| graph.get_leiden_communities())
+    });
+    print_accuracy("cnm", &graph.get_cnm_communities().0, &truth);
+    c.bench_function("cnm_communities_planted_1000", |b| {
+        b.iter(|| graph.get_cnm_communities())
+    });
+    print_accuracy(
+        "label propagation",
+        &graph.get_label_propagation_communities(0, 100),
+        &truth,
+    );
+    c.bench_function("label_propagation_communities_planted_1000", |b| {
+        b.iter(|| graph.get_label_propagation_communities(0, 100))
+    });
+}
+
+fn bench_triangles(c: &mut Criterion) {
+    let k100 = SimpleUndirectedGraphBuilder {}
+        .get_complete_graph(100)
+        .unwrap();
+    c.bench_function("triangle_count_k100", |b| {
+        b.iter(|| {
+            k100.nodes
+                .keys()
+                .map(|id| k100.triangle_count(*id))
+                .sum::<usize>()
+        })
+    });
+    // dense enough for a bitset adjacency matrix.
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..50).collect();
+    let dense = gen_planted_clique_undirected_graph(1000, 0.1, &clique, &mut rng).unwrap();
+    c.bench_function("triangle_counts_dense_1000", |b| {
+        b.iter(|| dense.get_triangle_counts())
+    });
+    c.bench_function("avg_clustering_dense_1000", |b| {
+        b.iter(|| dense.get_avg_clustering())
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_candidates,
+    bench_graph_building,
+    bench_shortest_paths,
+    bench_coreness,
+    bench_communities,
+    bench_triangles
+);
+criterion_main!(benches);
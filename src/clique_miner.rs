@@ -8,14 +8,96 @@ extern crate clap;
 extern crate lib_dachshund;
 
 use std::io;
+use std::io::Write;
 
-use clap::{App, Arg, ArgMatches};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 
+use lib_dachshund::dachshund::batch_transformer::BatchTransformer;
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_format::{get_line_format_args, LineFormat};
 use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::quasi_clique_transformer::QuasiCliqueTransformer;
 use lib_dachshund::dachshund::transformer::Transformer;
-use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::transformer_base::{
+    install_interrupt_handler, open_rejects_file, TransformerBase, INTERRUPTED_EXIT_CODE,
+};
+
+// search arguments, shared by the typed (default) and unipartite searches.
+fn get_search_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("beam_size")
+            .short("b")
+            .long("beam_size")
+            .takes_value(true)
+            .default_value("20")
+            .help("Beam size (number of candidates considered at any point in the search"),
+        Arg::with_name("alpha")
+            .short("a")
+            .long("alpha")
+            .takes_value(true)
+            .default_value("0.1")
+            .help("Alpha ('cliqueness weight') used to indicate how much to weigh global \
+                   Beam size (number of candidates considered at any point in the search"),
+        Arg::with_name("global_thresh")
+            .short("g")
+            .long("global_thresh")
+            .takes_value(true)
+            .default_value("1.0")
+            .help("Global density threshold: min % of ties out of all possible ties \
+                    required for a clique to be considered valid for the purposes of \
+                    the search."),
+        Arg::with_name("local_thresh")
+            .short("l")
+            .long("local_thresh")
+            .takes_value(true)
+            .default_value("1.0")
+            .help("Local density threshold: min % of ties out of all possible ties \
+                    required for each core node, in order for a clique to be considered \
+                    valid for the purposes of the search."),
+        Arg::with_name("num_to_search")
+            .short("n")
+            .long("num_to_search")
+            .takes_value(true)
+            .default_value("10")
+            .help("Number of candidate nodes to consider (and score) for \
+                    each existing clique in the beam. Candidate nodes are ordered in \
+                    decreasing order of the # of ties to nodes currently in candidate."),
+        Arg::with_name("epochs")
+            .short("e")
+            .long("epochs")
+            .takes_value(true)
+            .default_value("200")
+            .help("Number of epochs for which to run each search"),
+        Arg::with_name("max_repeated_prior_scores")
+            .short("m")
+            .long("max_repeated_prior_scores")
+            .takes_value(true)
+            .default_value("3")
+            .help("Number of times for which the top prior score, if repeated, would trigger an early \
+                    stop in the search process."),
+        Arg::with_name("max_beam_memory")
+            .long("max_beam_memory")
+            .takes_value(true)
+            .help("Cap, in megabytes, on the estimated memory used by the candidates in the \
+                    beam. The beam keeps fewer candidates when they would not fit, and the \
+                    search stops with an error if not even one does."),
+        Arg::with_name("debug_mode")
+            .short("d")
+            .long("debug_mode")
+            .takes_value(true)
+            .default_value("false")
+            .help("Whether to run in debug mode (printing lots of useful messages about \
+                    candidates)."),
+        Arg::with_name("min_degree")
+            .long("min_degree")
+            .takes_value(true)
+            .default_value("1")
+            .help("Min degree for each node in each clique (nodes are pruned iteratively until \
+                    all candidate nodes have at least this degree w/r to all other nodes in the \
+                    graph"),
+    ]
+}
 
 fn get_command_line_args() -> ArgMatches<'static> {
     let matches: ArgMatches = App::new("Dachshund")
@@ -38,79 +120,198 @@ fn get_command_line_args() -> ArgMatches<'static> {
                  .short("ts")
                  .long("typespec")
                  .takes_value(true)
+                 .required_unless("typespecs")
+                 .conflicts_with("typespecs")
                  .help("JSON-encoded array of arrays representing Dachshund types. E.g.: \
-                       [[\"author\", \"works_at\", \"university\"], [\"author\", \"published_in\", \"journal\"]]"))
-        .arg(Arg::with_name("beam_size")
-                 .short("b")
-                 .long("beam_size")
-                 .takes_value(true)
-                 .help("Beam size (number of candidates considered at any point in the search"))
-        .arg(Arg::with_name("alpha")
-                 .short("a")
-                 .long("alpha")
-                 .takes_value(true)
-                 .help("Alpha ('cliqueness weight') used to indicate how much to weigh global \
-                       Beam size (number of candidates considered at any point in the search"))
-        .arg(Arg::with_name("global_thresh")
-                 .short("g")
-                 .long("global_thresh")
+                       [[\"author\", \"works_at\", \"university\"], [\"author\", \"published_in\", \"journal\"]]. \
+                       A fourth element weighs ties of that edge type in cliqueness (default 1.0), e.g.: \
+                       [\"author\", \"cited\", \"journal\", 0.25]"))
+        .arg(Arg::with_name("typespecs")
+                 .long("typespecs")
                  .takes_value(true)
-                 .help("Global density threshold: min % of ties out of all possible ties \
-                        required for a clique to be considered valid for the purposes of \
-                        the search."))
-        .arg(Arg::with_name("local_thresh")
-                 .short("l")
-                 .long("local_thresh")
+                 .help("JSON-encoded map from names to typespecs, to search the graphs of several \
+                        typespecs (e.g. one per relation family) built from the same input in one \
+                        pass. Output lines are prefixed with the name of their typespec. E.g.: \
+                        {\"publishing\": [[\"author\", \"published_in\", \"journal\"]], \
+                        \"employment\": [[\"author\", \"works_at\", \"university\"]]}"))
+        .args(&get_search_args())
+        .arg(Arg::with_name("non_core_local_thresh")
+                 .long("non_core_local_thresh")
                  .takes_value(true)
-                 .help("Local density threshold: min % of ties out of all possible ties \
-                        required for each node, in order for a clique to be considered \
-                        valid for the purposes of the search."))
-        .arg(Arg::with_name("num_to_search")
-                 .short("n")
-                 .long("num_to_search")
+                 .help("JSON-encoded map from non-core types to their local density threshold: \
+                        min % of ties with core nodes out of all possible ones required for each \
+                        non-core node of that type. E.g.: {\"journal\": 0.5}"))
+        .arg(Arg::with_name("edge_type_min_degree")
+                 .long("edge_type_min_degree")
                  .takes_value(true)
-                 .help("Number of candidate nodes to consider (and score) for \
-                        each existing clique in the beam. Candidate nodes are ordered in \
-                        decreasing order of the # of ties to nodes currently in candidate."))
-        .arg(Arg::with_name("epochs")
-                 .short("e")
-                 .long("epochs")
+                 .help("JSON-encoded map from edge types to the min number of neighbors each core \
+                        node must have through edges of that type, enforced (like min_degree) by \
+                        pruning nodes iteratively. E.g.: {\"published_at\": 2}"))
+        .arg(Arg::with_name("core_min_degree")
+                 .long("core_min_degree")
                  .takes_value(true)
-                 .help("Number of epochs for which to run each search"))
-        .arg(Arg::with_name("max_repeated_prior_scores")
-                 .short("m")
-                 .long("max_repeated_prior_scores")
+                 .help("Min degree of core nodes (alpha), overriding min_degree for them: graphs \
+                        are pruned to their (alpha, beta)-core, in which every core node has at \
+                        least alpha neighbors and every non-core node at least beta."))
+        .arg(Arg::with_name("non_core_min_degree")
+                 .long("non_core_min_degree")
                  .takes_value(true)
-                 .help("Number of times for which the top prior score, if repeated, would trigger an early \
-                        stop in the search process."))
-        .arg(Arg::with_name("debug_mode")
-                 .short("d")
-                 .long("debug_mode")
-                 .takes_value(true)
-                 .help("Whether to run in debug mode (printing lots of useful messages about \
-                        candidates (default = false)."))
+                 .help("Min degree of non-core nodes (beta), overriding min_degree for them (see \
+                        core_min_degree)."))
         .arg(Arg::with_name("long_format")
                  .long("long_format")
                  .takes_value(true)
+                 .default_value("false")
                  .help("Whether to print clique assignments in long format: \
-                        clique_id\tnode_id\tnode_type \
-                        (default = false)"))
+                        clique_id\tnode_id\tnode_type"))
+        .arg(Arg::with_name("output_format")
+                 .long("output_format")
+                 .takes_value(true)
+                 .possible_values(&["wide", "long", "dot", "stats"])
+                 .help("Format in which to print the top candidate of each graph, overriding \
+                        long_format: wide (the default), long, or dot, to print each graph in \
+                        Graphviz DOT format, with the candidate's core and non-core nodes filled \
+                        in different colors and the edges between them highlighted, or stats, to \
+                        print node and edge counts and node type mixing of each graph as JSON \
+                        instead of searching it."))
+        .arg(Arg::with_name("dense_block_seed")
+                 .long("dense_block_seed")
+                 .takes_value(true)
+                 .default_value("false")
+                 .help("Whether to seed the search of each graph with its densest block, as found \
+                        by Fraudar-style greedy peeling (with ties to popular non-core nodes \
+                        weighing less), in addition to random candidates."))
+        .arg(Arg::with_name("string_labels")
+                 .long("string_labels")
+                 .takes_value(true)
+                 .default_value("false")
+                 .help("Whether to read node ids as arbitrary strings (e.g. user names) rather \
+                        than integers. They are interned as they are read, and written back as \
+                        such in the output."))
         .arg(Arg::with_name("core_type")
                  .long("core_type")
                  .takes_value(true)
+                 .required(true)
                  .help("What the type of the core entity is"))
-        .arg(Arg::with_name("min_degree")
-                 .long("min_degree")
+        .arg(Arg::with_name("summary_file")
+                 .long("summary_file")
+                 .takes_value(true)
+                 .help("File to write run totals to, as JSON. If not provided, totals are printed \
+                        to stderr."))
+        .arg(Arg::with_name("rejects_file")
+                 .long("rejects_file")
+                 .takes_value(true)
+                 .help("File to write lines that cannot be parsed to (as line_num, error, line), \
+                        skipping them. If not provided, the run stops at the first such line."))
+        .arg(Arg::with_name("graph_markers")
+                 .long("graph_markers")
+                 .help("Bracket the output of each graph with (#begin, graph_id) and \
+                        (#end, graph_id, number of lines) rows, so that truncated output can be \
+                        told from complete output."))
+        .arg(Arg::with_name("dry_run")
+                 .long("dry_run")
                  .takes_value(true)
-                 .help("Min degree for each node in each clique (nodes are pruned iteratively until \
-                        all candidate nodes have at least this degree w/r to all other nodes in the \
-                        graph"))
+                 .help("If provided, parse the typespec and (at most) this many input lines, report \
+                        the types found, row counts per graph_id and any parse issues, then exit \
+                        without mining."))
+        .args(&get_line_format_args())
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(SubCommand::with_name("unipartite")
+                 .about("Finds quasi-cliques in unipartite graphs, given as graph_id\tsource_id\ttarget_id \
+                         rows. local_thresh is the min % of the other nodes of a quasi-clique each \
+                         node must be tied to (gamma, for gamma-quasi-cliques). Prints \
+                         graph_id\tsize\t[node ids]\tdensity\t[local densities] for each graph with \
+                         a conforming quasi-clique.")
+                 .args(&get_search_args())
+                 .arg(Arg::with_name("k_plex")
+                     .long("k_plex")
+                     .takes_value(true)
+                     .conflicts_with("k_club")
+                     .help("Find k-plexes (sets of nodes each tied to all but at most k of \
+                            them, itself included) instead of quasi-cliques meeting the global \
+                            and local thresholds."))
+                 .arg(Arg::with_name("k_club")
+                     .long("k_club")
+                     .takes_value(true)
+                     .help("Find k-clubs (sets of nodes any two of which are joined by a path \
+                            of at most k ties among them) instead of quasi-cliques meeting the \
+                            global and local thresholds.")))
         .get_matches();
     matches
 }
 
+// arguments whose resolved values (after applying defaults) are printed at startup.
+const CONFIG_ARGS: [&str; 24] = [
+    "typespec",
+    "typespecs",
+    "core_type",
+    "beam_size",
+    "alpha",
+    "global_thresh",
+    "local_thresh",
+    "non_core_local_thresh",
+    "num_to_search",
+    "epochs",
+    "max_repeated_prior_scores",
+    "max_beam_memory",
+    "min_degree",
+    "core_min_degree",
+    "non_core_min_degree",
+    "edge_type_min_degree",
+    "debug_mode",
+    "long_format",
+    "output_format",
+    "dense_block_seed",
+    "string_labels",
+    "delimiter",
+    "k_plex",
+    "k_club",
+];
+
+fn print_types(transformer: &Transformer) {
+    eprintln!("Core type: {}", transformer.core_type);
+    eprintln!("Non-core types: {}", transformer.non_core_types.join(", "));
+    eprintln!("Edge types: {}", transformer.edge_types.join(", "));
+}
+
+// a transformer for the typespec argument, a batch transformer for the typespecs one, or a
+// quasi-clique transformer for the unipartite subcommand.
+fn build_transformer(matches: ArgMatches) -> CLQResult<Box<dyn TransformerBase>> {
+    if let Some(unipartite_matches) = matches.subcommand_matches("unipartite") {
+        return Ok(Box::new(QuasiCliqueTransformer::from_argmatches(
+            unipartite_matches,
+        )?));
+    }
+    let dry_run = matches.is_present("dry_run");
+    if matches.is_present("typespecs") {
+        let transformer = BatchTransformer::from_argmatches(matches)?;
+        if dry_run {
+            for (name, typespec_transformer) in &transformer.transformers {
+                eprintln!("Typespec {name}:");
+                print_types(typespec_transformer);
+            }
+        }
+        return Ok(Box::new(transformer));
+    }
+    let transformer = Transformer::from_argmatches(matches)?;
+    if dry_run {
+        print_types(&transformer);
+    }
+    Ok(Box::new(transformer))
+}
+
 fn main() -> CLQResult<()> {
     let matches: ArgMatches = get_command_line_args();
+    install_interrupt_handler()?;
+    eprintln!("Running with the following configuration:");
+    let config_matches: &ArgMatches = matches.subcommand_matches("unipartite").unwrap_or(&matches);
+    for name in CONFIG_ARGS {
+        eprintln!(
+            "  {}: {}",
+            name,
+            config_matches.value_of(name).unwrap_or("none")
+        );
+    }
 
     let stdio: io::Stdin = io::stdin();
     let input: Input;
@@ -121,10 +322,56 @@ fn main() -> CLQResult<()> {
         input = Input::console(&stdio);
     }
 
-    let mut transformer = Transformer::from_argmatches(matches)?;
+    let input: Input = match LineFormat::from_argmatches(&matches) {
+        Ok(line_format) => input.with_line_format(line_format),
+        Err(err) => {
+            eprintln!("Invalid configuration: {err}");
+            std::process::exit(2);
+        }
+    };
+
+    let dry_run_lines: Option<usize> = match matches.value_of("dry_run").map(str::parse) {
+        None => None,
+        Some(Ok(max_lines)) => Some(max_lines),
+        Some(Err(err)) => {
+            eprintln!("Invalid configuration: Invalid value for argument dry_run ({err})");
+            std::process::exit(2);
+        }
+    };
+
+    let summary_file: Option<String> = matches.value_of("summary_file").map(str::to_owned);
+    let graph_markers: bool = matches.is_present("graph_markers");
+    let mut rejects = open_rejects_file(matches.value_of("rejects_file"))?;
+
+    // configuration errors are reported as such, rather than as a failed run.
+    let mut transformer: Box<dyn TransformerBase> = match build_transformer(matches) {
+        Ok(transformer) => transformer,
+        Err(err) => {
+            eprintln!("Invalid configuration: {err}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(max_lines) = dry_run_lines {
+        let report = transformer.dry_run(input, max_lines);
+        eprint!("{report}");
+        // parse issues make for a non-zero exit code, so that dry runs can gate jobs.
+        std::process::exit(if report.issues.is_empty() { 0 } else { 1 });
+    }
 
     let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
-    transformer.run(input, output)?;
+    let mut output: Output = Output::console(&mut dummy);
+    if graph_markers {
+        output = output.with_graph_markers();
+    }
+    let summary = transformer.run_with_rejects(
+        input,
+        output,
+        rejects.as_mut().map(|x| x as &mut dyn Write),
+    )?;
+    summary.write(summary_file.as_deref())?;
+    if summary.interrupted {
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
     Ok(())
 }
@@ -8,15 +8,19 @@ extern crate clap;
 extern crate lib_dachshund;
 
 use std::io;
+use std::io::Write;
 
 use clap::{App, Arg, ArgMatches};
 
 use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_format::{get_line_format_args, LineFormat};
 use lib_dachshund::dachshund::output::Output;
 use lib_dachshund::dachshund::strongly_connected_components_transformer::StronglyConnectedComponentsTransformer;
-use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::transformer_base::{
+    install_interrupt_handler, open_rejects_file, TransformerBase, INTERRUPTED_EXIT_CODE,
+};
 
 fn get_command_line_args() -> ArgMatches<'static> {
     let matches: ArgMatches =
@@ -35,20 +39,71 @@ fn get_command_line_args() -> ArgMatches<'static> {
             .arg(Arg::with_name("directed").short("d").help(
                 "Interpret input as directed graph and calculate strongly connected components.",
             ))
+            .arg(
+                Arg::with_name("summary_file")
+                    .long("summary_file")
+                    .takes_value(true)
+                    .help("File to write run totals to, as JSON. If not provided, totals are printed to stderr."),
+            )
+            .arg(
+                Arg::with_name("rejects_file")
+                    .long("rejects_file")
+                    .takes_value(true)
+                    .help("File to write lines that cannot be parsed to (as line_num, error, line), \
+                           skipping them. If not provided, the run stops at the first such line."),
+            )
+            .arg(
+                Arg::with_name("graph_markers")
+                    .long("graph_markers")
+                    .help("Bracket the output of each graph with (#begin, graph_id) and \
+                           (#end, graph_id, number of lines) rows, so that truncated output can be \
+                           told from complete output."),
+            )
+            .arg(
+                Arg::with_name("string_labels")
+                    .long("string_labels")
+                    .help("Read node ids as arbitrary strings (e.g. user names) rather than \
+                           integers, and write them back as such."),
+            )
+            .args(&get_line_format_args())
             .get_matches();
     matches
 }
 
 fn main() -> CLQResult<()> {
     let matches: ArgMatches = get_command_line_args();
+    install_interrupt_handler()?;
     let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
+    let input: Input =
+        Input::console(&stdio).with_line_format(LineFormat::from_argmatches(&matches)?);
     let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
-    if matches.is_present("directed") {
-        ConnectedComponentsTransformer::new().run(input, output)?;
+    let mut output: Output = Output::console(&mut dummy);
+    if matches.is_present("graph_markers") {
+        output = output.with_graph_markers();
+    }
+    let mut rejects = open_rejects_file(matches.value_of("rejects_file"))?;
+    let string_labels: bool = matches.is_present("string_labels");
+    let mut transformer: Box<dyn TransformerBase> = if matches.is_present("directed") {
+        let transformer = ConnectedComponentsTransformer::new();
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
     } else {
-        StronglyConnectedComponentsTransformer::new().run(input, output)?;
+        let transformer = StronglyConnectedComponentsTransformer::new();
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
     };
+    let summary = transformer.run_with_rejects(
+        input,
+        output,
+        rejects.as_mut().map(|x| x as &mut dyn Write),
+    )?;
+    summary.write(matches.value_of("summary_file"))?;
+    if summary.interrupted {
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
     Ok(())
 }
@@ -8,16 +8,22 @@ extern crate clap;
 extern crate lib_dachshund;
 
 use std::io;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::{App, Arg, ArgMatches};
 
 use lib_dachshund::dachshund::core_transformer::CoreTransformer;
-use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::input::Input;
 use lib_dachshund::dachshund::kpeak_transformer::KPeakTransformer;
+use lib_dachshund::dachshund::line_format::{get_line_format_args, LineFormat};
 use lib_dachshund::dachshund::output::Output;
-use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::transformer_base::{
+    install_interrupt_handler, open_rejects_file, TransformerBase, INTERRUPTED_EXIT_CODE,
+};
 use lib_dachshund::dachshund::weighted_core_transformer::WeightedCoreTransformer;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::TemporalDecay;
 
 fn get_command_line_args() -> ArgMatches<'static> {
     let matches: ArgMatches = App::new("Dachshund Core Miner")
@@ -43,26 +49,117 @@ fn get_command_line_args() -> ArgMatches<'static> {
                 .long("kpeaks")
                 .help("Calculates k-peak values and mountain assignments in graphs from stdin."),
         )
+        .arg(
+            Arg::with_name("half_life")
+                .long("half_life")
+                .takes_value(true)
+                .requires("weighted")
+                .help("With -w, read edge weights as interaction timestamps instead, weighting \
+                       each edge by the sum of its interactions, each decayed by half every \
+                       half_life time units before the reference time."),
+        )
+        .arg(
+            Arg::with_name("reference_time")
+                .long("reference_time")
+                .takes_value(true)
+                .requires("half_life")
+                .help("Time relative to which interactions are decayed (default: now, in \
+                       seconds since the Unix epoch)."),
+        )
+        .arg(
+            Arg::with_name("summary_file")
+                .long("summary_file")
+                .takes_value(true)
+                .help("File to write run totals to, as JSON. If not provided, totals are printed to stderr."),
+        )
+        .arg(
+            Arg::with_name("rejects_file")
+                .long("rejects_file")
+                .takes_value(true)
+                .help("File to write lines that cannot be parsed to (as line_num, error, line), \
+                       skipping them. If not provided, the run stops at the first such line."),
+        )
+        .arg(
+            Arg::with_name("graph_markers")
+                .long("graph_markers")
+                .help("Bracket the output of each graph with (#begin, graph_id) and \
+                       (#end, graph_id, number of lines) rows, so that truncated output can be \
+                       told from complete output."),
+        )
+        .arg(
+            Arg::with_name("string_labels")
+                .long("string_labels")
+                .help("Read node ids as arbitrary strings (e.g. user names) rather than \
+                       integers, and write them back as such."),
+        )
+        .args(&get_line_format_args())
         .get_matches();
     matches
 }
 
 fn main() -> CLQResult<()> {
     let matches: ArgMatches = get_command_line_args();
+    install_interrupt_handler()?;
     let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
+    let input: Input =
+        Input::console(&stdio).with_line_format(LineFormat::from_argmatches(&matches)?);
     let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
+    let mut output: Output = Output::console(&mut dummy);
+    if matches.is_present("graph_markers") {
+        output = output.with_graph_markers();
+    }
+    let mut rejects = open_rejects_file(matches.value_of("rejects_file"))?;
     assert!(
         !(matches.is_present("weighted") && matches.is_present("kpeaks")),
         "Input arguments include kpeaks and weighted. Cannot run kpeaks on weighted graph."
     );
-    if matches.is_present("weighted") {
-        WeightedCoreTransformer::new().run(input, output)?;
+    let decay: Option<TemporalDecay> = match matches.value_of("half_life") {
+        Some(half_life) => {
+            let reference_time: f64 = match matches.value_of("reference_time") {
+                Some(x) => x.parse()?,
+                None => SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|e| CLQError::from(e.to_string()))?
+                    .as_secs_f64(),
+            };
+            Some(TemporalDecay::new(reference_time, half_life.parse()?)?)
+        }
+        None => None,
+    };
+    let string_labels: bool = matches.is_present("string_labels");
+    let mut transformer: Box<dyn TransformerBase> = if let Some(decay) = decay {
+        let transformer = WeightedCoreTransformer::with_temporal_decay(decay);
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
+    } else if matches.is_present("weighted") {
+        let transformer = WeightedCoreTransformer::new();
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
     } else if matches.is_present("kpeaks") {
-        KPeakTransformer::new().run(input, output)?;
+        let transformer = KPeakTransformer::new();
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
     } else {
-        CoreTransformer::new().run(input, output)?;
+        let transformer = CoreTransformer::new();
+        match string_labels {
+            true => Box::new(transformer.with_string_labels()),
+            false => Box::new(transformer),
+        }
     };
+    let summary = transformer.run_with_rejects(
+        input,
+        output,
+        rejects.as_mut().map(|x| x as &mut dyn Write),
+    )?;
+    summary.write(matches.value_of("summary_file"))?;
+    if summary.interrupted {
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
     Ok(())
 }
@@ -4,10 +4,140 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::laplacian::{Laplacian, SparseLaplacian};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use nalgebra::DMatrix;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+// graphs with more nodes than this get their algebraic connectivity from the sparse
+// solver (see get_laplacian_eigenpairs) rather than a dense eigendecomposition.
+pub const MAX_DENSE_EIGEN_NODES: usize = 1000;
+// Ritz pairs are accepted once their residual norm is at most this fraction of the largest
+// Ritz value (an estimate of the norm of the Laplacian).
+const LANCZOS_TOLERANCE: f64 = 1e-10;
+// size of the Krylov basis grown between restarts, on top of 4 vectors per eigenpair.
+const LANCZOS_BASIS_SIZE: usize = 30;
+const MAX_LANCZOS_RESTARTS: usize = 1000;
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+// Orthogonalizes x against the constant vector and the orthonormal vectors of basis (twice,
+// for numerical stability), and normalizes it. None if (almost) nothing is left of x.
+fn orthonormalize(mut x: Vec<f64>, basis: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let initial_norm = dot(&x, &x).sqrt();
+    for _ in 0..2 {
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        x.iter_mut().for_each(|v| *v -= mean);
+        for b in basis {
+            let c = dot(&x, b);
+            x.iter_mut().zip(b).for_each(|(v, w)| *v -= c * w);
+        }
+    }
+    let norm = dot(&x, &x).sqrt();
+    if norm == 0.0 || norm <= 1e-10 * initial_norm {
+        return None;
+    }
+    Some(x.into_iter().map(|v| v / norm).collect())
+}
+
+// Linear combination of vectors, with the coefficients in the given column of coefficients.
+fn combine(vectors: &[Vec<f64>], coefficients: &DMatrix<f64>, column: usize) -> Vec<f64> {
+    let mut x = vec![0.0; vectors[0].len()];
+    for (i, v) in vectors.iter().enumerate() {
+        let c = coefficients[(i, column)];
+        x.iter_mut().zip(v).for_each(|(a, b)| *a += c * b);
+    }
+    x
+}
+
+// Smallest num_eigenpairs eigenvalues of laplacian on the vectors orthogonal to the constant
+// vector (leaving out the trivial eigenvalue 0), in increasing order, with unit eigenvectors.
+// Thick-restart Lanczos with full reorthogonalization: a Krylov basis is grown from a random
+// vector, the eigenpairs of the Laplacian restricted to it (Ritz pairs) are computed, and the
+// basis is shrunk to the best Ritz vectors and grown again, until the wanted Ritz pairs have
+// small enough residuals (or MAX_LANCZOS_RESTARTS restarts, returning the estimates so far).
+fn lanczos_smallest_eigenpairs<R: Rng>(
+    laplacian: &SparseLaplacian,
+    num_eigenpairs: usize,
+    rng: &mut R,
+) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = laplacian.num_rows();
+    let k = num_eigenpairs.min(n.saturating_sub(1));
+    if k == 0 {
+        return (Vec::new(), Vec::new());
+    }
+    let max_basis_size = (4 * k + LANCZOS_BASIS_SIZE).min(n - 1);
+    let num_kept = k + (max_basis_size - k) / 2;
+    let random_vector =
+        |rng: &mut R| -> Vec<f64> { (0..n).map(|_| rng.gen::<f64>() - 0.5).collect() };
+
+    let mut basis: Vec<Vec<f64>> = Vec::new();
+    // the Laplacian times each vector of basis.
+    let mut products: Vec<Vec<f64>> = Vec::new();
+    let mut next: Vec<f64> = random_vector(rng);
+    let mut num_restarts: usize = 0;
+    loop {
+        while basis.len() < max_basis_size {
+            let v = match orthonormalize(std::mem::take(&mut next), &basis) {
+                Some(v) => v,
+                // basis spans an invariant subspace: carry on from a new random vector.
+                None => match orthonormalize(random_vector(rng), &basis) {
+                    Some(v) => v,
+                    None => break,
+                },
+            };
+            next = laplacian.multiply(&v);
+            products.push(next.clone());
+            basis.push(v);
+        }
+
+        let m = basis.len();
+        let projected = DMatrix::from_fn(m, m, |i, j| {
+            (dot(&basis[i], &products[j]) + dot(&basis[j], &products[i])) / 2.0
+        });
+        let eigen = projected.symmetric_eigen();
+        let mut order: Vec<usize> = (0..m).collect();
+        order.sort_by(|a, b| {
+            eigen.eigenvalues[*a]
+                .partial_cmp(&eigen.eigenvalues[*b])
+                .unwrap()
+        });
+        let scale: f64 = eigen.eigenvalues.iter().fold(0.0, |a, x| a.max(x.abs()));
+        let ritz_vectors: Vec<Vec<f64>> = order[..num_kept.min(m)]
+            .iter()
+            .map(|column| combine(&basis, &eigen.eigenvectors, *column))
+            .collect();
+        let ritz_products: Vec<Vec<f64>> = order[..num_kept.min(m)]
+            .iter()
+            .map(|column| combine(&products, &eigen.eigenvectors, *column))
+            .collect();
+        let values: Vec<f64> = order[..k].iter().map(|x| eigen.eigenvalues[*x]).collect();
+        let converged = (0..k).all(|i| {
+            let residual: f64 = ritz_products[i]
+                .iter()
+                .zip(&ritz_vectors[i])
+                .map(|(a, b)| (a - values[i] * b).powi(2))
+                .sum();
+            residual.sqrt() <= LANCZOS_TOLERANCE * scale
+        });
+        // a basis of n - 1 vectors spans all vectors orthogonal to the constant one.
+        if converged || m == n - 1 || num_restarts == MAX_LANCZOS_RESTARTS {
+            return (values, ritz_vectors.into_iter().take(k).collect());
+        }
+
+        // the Krylov sequence carries on from the last product, orthogonalized against the
+        // whole basis before it is shrunk.
+        next = orthonormalize(next, &basis).unwrap_or_else(|| random_vector(rng));
+        basis = ritz_vectors;
+        products = ritz_products;
+        num_restarts += 1;
+    }
+}
 
 pub trait AlgebraicConnectivity: GraphBase + Laplacian
 where
@@ -16,12 +146,45 @@ where
 {
     // Algebraic Connectivity, or the Fiedler Measure, is the second-smallest eigenvalue of the graph Laplacian.
     // The lower the value, the less decomposable the graph's adjacency matrix is. Thanks to the nalgebra
-    // crate computing this is quite straightforward.
+    // crate computing this is quite straightforward. Graphs with more than MAX_DENSE_EIGEN_NODES
+    // nodes, whose dense Laplacian would take too much memory, use get_laplacian_eigenpairs.
     fn get_algebraic_connectivity(&self) -> f64 {
+        if self.count_nodes() > MAX_DENSE_EIGEN_NODES {
+            return self.get_laplacian_eigenpairs(1).0[0];
+        }
         let (laplacian, _ids) = self.get_laplacian_matrix();
         let eigen = laplacian.symmetric_eigen();
         let mut eigenvalues: Vec<f64> = eigen.eigenvalues.iter().cloned().collect();
         eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
         eigenvalues[1]
     }
+
+    // The num_eigenpairs smallest nontrivial eigenvalues of the graph Laplacian (all but
+    // the 0 of the constant eigenvector), in increasing order, with their eigenvectors, as
+    // unit vectors by node id (with an arbitrary sign). On a connected graph, the first is
+    // the algebraic connectivity, and its eigenvector the Fiedler vector. Computed with
+    // Lanczos iterations on the sparse Laplacian, in memory linear in the size of the graph.
+    fn get_laplacian_eigenpairs(
+        &self,
+        num_eigenpairs: usize,
+    ) -> (Vec<f64>, Vec<HashMap<NodeId, f64>>) {
+        // a fixed seed, so that results are reproducible.
+        self.get_laplacian_eigenpairs_with_rng(num_eigenpairs, &mut StdRng::seed_from_u64(0))
+    }
+
+    // Laplacian eigenpairs, with Lanczos iterations started from a vector drawn from the
+    // given random number generator.
+    fn get_laplacian_eigenpairs_with_rng<R: Rng>(
+        &self,
+        num_eigenpairs: usize,
+        rng: &mut R,
+    ) -> (Vec<f64>, Vec<HashMap<NodeId, f64>>) {
+        let laplacian = self.get_sparse_laplacian_matrix();
+        let (values, vectors) = lanczos_smallest_eigenpairs(&laplacian, num_eigenpairs, rng);
+        let vectors = vectors
+            .into_iter()
+            .map(|x| laplacian.ids.iter().cloned().zip(x).collect())
+            .collect();
+        (values, vectors)
+    }
 }
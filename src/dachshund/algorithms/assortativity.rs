@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate serde_json;
+use crate::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use crate::dachshund::id_types::NodeTypeIdInternal;
+use crate::dachshund::node::{Node, NodeBase, NodeEdgeBase};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// How edges are spread across pairs of node categories: fractions[i][j] is the fraction
+/// of edge ends which go from a node of category categories[i] to one of categories[j].
+/// Each edge is seen from both of its ends, so the matrix is symmetric and sums to 1.
+pub struct MixingMatrix<T> {
+    pub categories: Vec<T>,
+    pub fractions: Vec<Vec<f64>>,
+}
+impl<T> MixingMatrix<T> {
+    /// Newman's (2003) assortativity coefficient: 1 if edges only join nodes of the same
+    /// category, 0 if categories mix as they would at random, and negative if edges tend
+    /// to join different categories. None if there are no edges, or if all edges lie
+    /// within a single category (which leaves the coefficient undefined).
+    pub fn get_assortativity(&self) -> Option<f64> {
+        let n = self.categories.len();
+        let total: f64 = self.fractions.iter().flatten().sum();
+        if total == 0.0 {
+            return None;
+        }
+        let within: f64 = (0..n).map(|i| self.fractions[i][i]).sum();
+        let expected: f64 = (0..n)
+            .map(|i| {
+                let row: f64 = self.fractions[i].iter().sum();
+                let column: f64 = self.fractions.iter().map(|x| x[i]).sum();
+                row * column
+            })
+            .sum();
+        if expected >= 1.0 {
+            return None;
+        }
+        Some((within - expected) / (1.0 - expected))
+    }
+}
+
+pub trait Assortativity: GraphBase
+where
+    Self::NodeType: NodeBase,
+    <Self::NodeType as NodeBase>::NodeEdgeType:
+        NodeEdgeBase<NodeIdType = <Self::NodeType as NodeBase>::NodeIdType>,
+{
+    // Mixing matrix of the categories given by get_category (e.g. the value of a node
+    // attribute), ordered by category. Edges with an end get_category returns None for
+    // are left out. Edges of directed graphs are counted regardless of their direction.
+    fn get_mixing_matrix<T, F>(&self, get_category: F) -> MixingMatrix<T>
+    where
+        T: Clone + Ord,
+        F: Fn(&Self::NodeType) -> Option<T>,
+    {
+        let mut counts: BTreeMap<T, BTreeMap<T, usize>> = BTreeMap::new();
+        let mut total: usize = 0;
+        for node in self.get_nodes_iter() {
+            let category = match get_category(node) {
+                Some(category) => category,
+                None => continue,
+            };
+            for e in node.get_edges() {
+                if let Some(neighbor_category) = get_category(self.get_node(e.get_neighbor_id())) {
+                    *counts
+                        .entry(category.clone())
+                        .or_default()
+                        .entry(neighbor_category.clone())
+                        .or_default() += 1;
+                    counts.entry(neighbor_category).or_default();
+                    total += 1;
+                }
+            }
+        }
+        let categories: Vec<T> = counts.keys().cloned().collect();
+        let fractions: Vec<Vec<f64>> = counts
+            .values()
+            .map(|row| {
+                categories
+                    .iter()
+                    .map(|x| *row.get(x).unwrap_or(&0) as f64 / total as f64)
+                    .collect()
+            })
+            .collect();
+        MixingMatrix {
+            categories,
+            fractions,
+        }
+    }
+
+    // Assortativity coefficient (see MixingMatrix::get_assortativity) of the categories
+    // given by get_category.
+    fn get_attribute_assortativity<T, F>(&self, get_category: F) -> Option<f64>
+    where
+        T: Clone + Ord,
+        F: Fn(&Self::NodeType) -> Option<T>,
+    {
+        self.get_mixing_matrix(get_category).get_assortativity()
+    }
+}
+
+pub trait TypeAssortativity: BipartiteGraphBase<NodeType = Node> + Assortativity {
+    // Mixing matrix of node types, with the core type as 0 and non-core types numbered
+    // from 1 (as in Transformer::process_typespec).
+    fn get_type_mixing_matrix(&self) -> MixingMatrix<NodeTypeIdInternal> {
+        self.get_mixing_matrix(|node| Some(node.non_core_type.map_or(0, |x| x.value())))
+    }
+
+    // Type mixing statistics, as JSON of the form
+    //   {"type_assortativity": -1.0, "type_mixing": {"author-journal": 0.25, ...}}
+    // where type_names holds the name of each type id (the core type first), and each
+    // pair of types is listed once, with the sum of the fractions of edge ends joining
+    // them in either direction.
+    fn get_type_mixing_json(&self, type_names: &[String]) -> Value {
+        let mixing = self.get_type_mixing_matrix();
+        let name = |type_id: NodeTypeIdInternal| match type_names.get(type_id) {
+            Some(type_name) => type_name.clone(),
+            None => type_id.to_string(),
+        };
+        let mut type_mixing: BTreeMap<String, f64> = BTreeMap::new();
+        for (i, type_i) in mixing.categories.iter().enumerate() {
+            for (j, type_j) in mixing.categories.iter().enumerate().skip(i) {
+                let fraction = if i == j {
+                    mixing.fractions[i][j]
+                } else {
+                    mixing.fractions[i][j] + mixing.fractions[j][i]
+                };
+                if fraction > 0.0 {
+                    let key = format!("{}-{}", name(*type_i), name(*type_j));
+                    type_mixing.insert(key, (fraction * 1000.0).round() / 1000.0);
+                }
+            }
+        }
+        json!({
+            "type_assortativity": mixing.get_assortativity(),
+            "type_mixing": type_mixing,
+        })
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{
+    NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase, WeightedNodeEdge, WeightedNodeEdgeBase,
+};
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+
+// Probability, under the disparity filter's null model, of a node's strength being split
+// so that one of its edges gets at least this weight: (1 - w / s)^(k - 1). Nodes of degree
+// 1 (for which the split is forced) and of zero strength give 1.
+fn get_disparity_alpha(node: &WeightedNode, weight: f64) -> f64 {
+    let strength = node.weight();
+    let degree = node.degree();
+    if degree < 2 || strength <= 0.0 {
+        return 1.0;
+    }
+    (1.0 - weight / strength).powi(degree as i32 - 1)
+}
+
+pub trait Backbone: GraphBase<NodeType = WeightedNode> {
+    // Disparity filter (Serrano, Boguna & Vespignani, 2009): the significance of each edge,
+    // as (smaller id, larger id, weight, alpha), ordered by ids. An edge's alpha is the
+    // smaller of those computed from either end, so an edge is significant if it stands out
+    // among the edges of either of its nodes. Lower alphas are more significant.
+    fn get_disparity_alphas(&self) -> Vec<(NodeId, NodeId, f64, f64)> {
+        let mut alphas: Vec<(NodeId, NodeId, f64, f64)> = Vec::new();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if id < neighbor_id {
+                    let weight = e.get_weight();
+                    let alpha = get_disparity_alpha(node, weight)
+                        .min(get_disparity_alpha(self.get_node(neighbor_id), weight));
+                    alphas.push((id, neighbor_id, weight, alpha));
+                }
+            }
+        }
+        alphas.sort_by_key(|a| (a.0, a.1));
+        alphas
+    }
+
+    // The backbone of the graph: the subgraph of edges with an alpha (see
+    // get_disparity_alphas) below the given significance level, e.g. 0.05. All nodes are
+    // kept, including those left without any edges.
+    fn get_disparity_backbone(&self, significance: f64) -> WeightedUndirectedGraph {
+        let kept: FxHashSet<(NodeId, NodeId)> = self
+            .get_disparity_alphas()
+            .into_iter()
+            .filter(|(_id1, _id2, _weight, alpha)| *alpha < significance)
+            .map(|(id1, id2, _weight, _alpha)| (id1, id2))
+            .collect();
+        let nodes: FxHashMap<NodeId, WeightedNode> = self
+            .get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                let edges: Vec<WeightedNodeEdge> = node
+                    .edges
+                    .iter()
+                    .filter(|e| kept.contains(&(id.min(e.target_id), id.max(e.target_id))))
+                    .map(|e| WeightedNodeEdge {
+                        target_id: e.target_id,
+                        weight: e.weight,
+                    })
+                    .collect();
+                let backbone_node = WeightedNode {
+                    node_id: id,
+                    neighbors: edges.iter().map(|e| e.target_id).collect(),
+                    edges,
+                };
+                (id, backbone_node)
+            })
+            .collect();
+        WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::butterflies::{get_shore_adjacencies, ShoreAdjacency};
+use crate::dachshund::graph_base::BipartiteGraphBase;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+// degrees of the nodes of both shores left while peeling a bipartite graph.
+struct Peeling {
+    core_degrees: Vec<usize>,
+    non_core_degrees: Vec<usize>,
+    core_removed: Vec<bool>,
+    non_core_removed: Vec<bool>,
+}
+impl Peeling {
+    fn new(core_adjacency: &ShoreAdjacency, non_core_adjacency: &ShoreAdjacency) -> Self {
+        Self {
+            core_degrees: core_adjacency.iter().map(|x| x.len()).collect(),
+            non_core_degrees: non_core_adjacency.iter().map(|x| x.len()).collect(),
+            core_removed: vec![false; core_adjacency.len()],
+            non_core_removed: vec![false; non_core_adjacency.len()],
+        }
+    }
+
+    // removes the given core (if is_core) or non-core node, and then iteratively all core
+    // nodes with fewer than alpha neighbors left and non-core nodes with fewer than beta.
+    // Returns the removed nodes, as (is_core, position) pairs.
+    fn remove(
+        &mut self,
+        start: (bool, usize),
+        alpha: usize,
+        beta: usize,
+        core_adjacency: &ShoreAdjacency,
+        non_core_adjacency: &ShoreAdjacency,
+    ) -> Vec<(bool, usize)> {
+        let mut removed: Vec<(bool, usize)> = Vec::new();
+        let mut stack: Vec<(bool, usize)> = vec![start];
+        while let Some((is_core, i)) = stack.pop() {
+            let (is_removed, adjacency, other_degrees, other_removed, other_min_degree) = if is_core
+            {
+                (
+                    &mut self.core_removed[i],
+                    &core_adjacency[i],
+                    &mut self.non_core_degrees,
+                    &self.non_core_removed,
+                    beta,
+                )
+            } else {
+                (
+                    &mut self.non_core_removed[i],
+                    &non_core_adjacency[i],
+                    &mut self.core_degrees,
+                    &self.core_removed,
+                    alpha,
+                )
+            };
+            if *is_removed {
+                continue;
+            }
+            *is_removed = true;
+            removed.push((is_core, i));
+            for &j in adjacency {
+                if !other_removed[j] {
+                    other_degrees[j] -= 1;
+                    if other_degrees[j] < other_min_degree {
+                        stack.push((!is_core, j));
+                    }
+                }
+            }
+        }
+        removed
+    }
+}
+
+pub trait BipartiteCores: BipartiteGraphBase
+where
+    Self::NodeType: NodeBase,
+    <Self::NodeType as NodeBase>::NodeIdType: Copy + Eq + Hash,
+    <Self::NodeType as NodeBase>::NodeEdgeType:
+        NodeEdgeBase<NodeIdType = <Self::NodeType as NodeBase>::NodeIdType>,
+{
+    // The (alpha, beta)-core of the graph: its largest subgraph in which every core node
+    // has at least alpha neighbors and every non-core node at least beta, found by removing
+    // nodes below their min degree iteratively. Returns (core ids, non-core ids), in the
+    // order of the graph's.
+    #[allow(clippy::type_complexity)]
+    fn get_bipartite_core(
+        &self,
+        alpha: usize,
+        beta: usize,
+    ) -> (
+        Vec<<Self::NodeType as NodeBase>::NodeIdType>,
+        Vec<<Self::NodeType as NodeBase>::NodeIdType>,
+    ) {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let mut peeling = Peeling::new(&core_adjacency, &non_core_adjacency);
+        for i in 0..core_adjacency.len() {
+            if !peeling.core_removed[i] && peeling.core_degrees[i] < alpha {
+                peeling.remove((true, i), alpha, beta, &core_adjacency, &non_core_adjacency);
+            }
+        }
+        for j in 0..non_core_adjacency.len() {
+            if !peeling.non_core_removed[j] && peeling.non_core_degrees[j] < beta {
+                peeling.remove(
+                    (false, j),
+                    alpha,
+                    beta,
+                    &core_adjacency,
+                    &non_core_adjacency,
+                );
+            }
+        }
+        let keep = |ids: &[<Self::NodeType as NodeBase>::NodeIdType], removed: &[bool]| {
+            ids.iter()
+                .zip(removed)
+                .filter(|(_id, removed)| !**removed)
+                .map(|(id, _removed)| *id)
+                .collect()
+        };
+        (
+            keep(self.get_core_ids(), &peeling.core_removed),
+            keep(self.get_non_core_ids(), &peeling.non_core_removed),
+        )
+    }
+
+    // (alpha, beta)-core decomposition for a fixed alpha: for each node of the (alpha, 0)-
+    // core, the largest beta for which it is in the (alpha, beta)-core (nodes outside of it
+    // are left out). Non-core nodes of the lowest degree are peeled off along with the nodes
+    // their removal leaves below their min degree, as in k-core decomposition. Core nodes
+    // left once all non-core nodes are gone (if alpha is 0) get the largest beta reached.
+    fn get_bipartite_core_numbers(
+        &self,
+        alpha: usize,
+    ) -> FxHashMap<<Self::NodeType as NodeBase>::NodeIdType, usize> {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let mut peeling = Peeling::new(&core_adjacency, &non_core_adjacency);
+        for i in 0..core_adjacency.len() {
+            if !peeling.core_removed[i] && peeling.core_degrees[i] < alpha {
+                peeling.remove((true, i), alpha, 0, &core_adjacency, &non_core_adjacency);
+            }
+        }
+        let core_ids = self.get_core_ids();
+        let non_core_ids = self.get_non_core_ids();
+        let mut core_numbers: FxHashMap<<Self::NodeType as NodeBase>::NodeIdType, usize> =
+            FxHashMap::default();
+        let mut beta: usize = 0;
+        loop {
+            // non-core nodes left, by degree.
+            let queue: BTreeSet<(usize, usize)> = (0..non_core_adjacency.len())
+                .filter(|j| !peeling.non_core_removed[*j])
+                .map(|j| (peeling.non_core_degrees[j], j))
+                .collect();
+            let Some((min_degree, _j)) = queue.first() else {
+                break;
+            };
+            beta = beta.max(*min_degree);
+            // nodes removed to get to the (alpha, beta + 1)-core are in the (alpha, beta)-core,
+            // but in no further one.
+            for (degree, j) in queue {
+                if degree > beta {
+                    break;
+                }
+                if peeling.non_core_removed[j] {
+                    continue;
+                }
+                let removed = peeling.remove(
+                    (false, j),
+                    alpha,
+                    beta + 1,
+                    &core_adjacency,
+                    &non_core_adjacency,
+                );
+                for (is_core, i) in removed {
+                    let id = if is_core {
+                        core_ids[i]
+                    } else {
+                        non_core_ids[i]
+                    };
+                    core_numbers.insert(id, beta);
+                }
+            }
+        }
+        for (i, id) in core_ids.iter().enumerate() {
+            if !peeling.core_removed[i] {
+                core_numbers.insert(*id, beta);
+            }
+        }
+        core_numbers
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::BipartiteGraphBase;
+use crate::dachshund::node::{Node, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::VecDeque;
+
+// marks an unmatched node, and an unreached node in the layering.
+const NONE: usize = usize::MAX;
+
+/// A matching of core to non-core nodes, in terms of positions in the graph's core_ids and
+/// non_core_ids vectors, along with the core adjacency the matching was computed on.
+pub struct Matching {
+    adjacency: Vec<Vec<usize>>,
+    core_match: Vec<usize>,
+    non_core_match: Vec<usize>,
+}
+impl Matching {
+    // layers cores by alternating path length from an unmatched core. Returns the
+    // layers, and whether an unmatched non-core (i.e. an augmenting path) was reached.
+    fn get_layers(&self) -> (Vec<usize>, bool) {
+        let mut layers = vec![NONE; self.adjacency.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for (i, m) in self.core_match.iter().enumerate() {
+            if *m == NONE {
+                layers[i] = 0;
+                queue.push_back(i);
+            }
+        }
+        let mut found_augmenting_path = false;
+        while let Some(i) = queue.pop_front() {
+            for &j in &self.adjacency[i] {
+                let next = self.non_core_match[j];
+                if next == NONE {
+                    found_augmenting_path = true;
+                } else if layers[next] == NONE {
+                    layers[next] = layers[i] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        (layers, found_augmenting_path)
+    }
+
+    // looks for an augmenting path from core i along the layering, flipping it if found.
+    fn augment(&mut self, i: usize, layers: &mut [usize]) -> bool {
+        for ix in 0..self.adjacency[i].len() {
+            let j = self.adjacency[i][ix];
+            let next = self.non_core_match[j];
+            if next == NONE || (layers[next] == layers[i] + 1 && self.augment(next, layers)) {
+                self.core_match[i] = j;
+                self.non_core_match[j] = i;
+                return true;
+            }
+        }
+        // dead end: no augmenting path goes through i in this phase.
+        layers[i] = NONE;
+        false
+    }
+}
+
+pub trait BipartiteMatching: BipartiteGraphBase<NodeType = Node> {
+    // Hopcroft-Karp, see https://en.wikipedia.org/wiki/Hopcroft%E2%80%93Karp_algorithm.
+    // Each phase finds a maximal set of shortest vertex-disjoint augmenting paths, so
+    // that O(sqrt(V)) phases suffice, for O(E sqrt(V)) time overall.
+    fn _get_maximum_matching(&self) -> Matching {
+        let core_ids = self.get_core_ids();
+        let non_core_ids = self.get_non_core_ids();
+        let positions: FxHashMap<u32, usize> = non_core_ids
+            .iter()
+            .enumerate()
+            .map(|(j, id)| (*id, j))
+            .collect();
+        let adjacency: Vec<Vec<usize>> = core_ids
+            .iter()
+            .map(|id| {
+                let mut neighbors: Vec<usize> = self
+                    .get_node(*id)
+                    .edges
+                    .iter()
+                    .filter_map(|e| positions.get(&e.get_neighbor_id()).copied())
+                    .collect();
+                // nodes can be joined by edges of several types.
+                neighbors.sort_unstable();
+                neighbors.dedup();
+                neighbors
+            })
+            .collect();
+        let mut matching = Matching {
+            core_match: vec![NONE; adjacency.len()],
+            non_core_match: vec![NONE; non_core_ids.len()],
+            adjacency,
+        };
+        loop {
+            let (mut layers, found_augmenting_path) = matching.get_layers();
+            if !found_augmenting_path {
+                return matching;
+            }
+            for i in 0..matching.adjacency.len() {
+                if matching.core_match[i] == NONE {
+                    matching.augment(i, &mut layers);
+                }
+            }
+        }
+    }
+
+    // A maximum matching of core to non-core nodes, as (core id, non-core id) pairs,
+    // ordered as the graph's core ids.
+    fn get_maximum_matching(&self) -> Vec<(u32, u32)> {
+        let matching = self._get_maximum_matching();
+        let non_core_ids = self.get_non_core_ids();
+        self.get_core_ids()
+            .iter()
+            .zip(matching.core_match.iter())
+            .filter(|(_id, j)| **j != NONE)
+            .map(|(id, j)| (*id, non_core_ids[*j]))
+            .collect()
+    }
+
+    // A minimum vertex cover, of the same size as a maximum matching (by Konig's theorem).
+    // Starting from unmatched cores, nodes reachable by alternating paths (non-matching
+    // edges from cores, matching edges from non-cores) are marked: the cover is made up of
+    // the unmarked cores and the marked non-cores. Returns (core ids, non-core ids).
+    fn get_minimum_vertex_cover(&self) -> (Vec<u32>, Vec<u32>) {
+        let matching = self._get_maximum_matching();
+        let mut core_marked = vec![false; matching.core_match.len()];
+        let mut non_core_marked = vec![false; matching.non_core_match.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for (i, m) in matching.core_match.iter().enumerate() {
+            if *m == NONE {
+                core_marked[i] = true;
+                queue.push_back(i);
+            }
+        }
+        while let Some(i) = queue.pop_front() {
+            for &j in &matching.adjacency[i] {
+                if !non_core_marked[j] && matching.core_match[i] != j {
+                    non_core_marked[j] = true;
+                    let next = matching.non_core_match[j];
+                    if next != NONE && !core_marked[next] {
+                        core_marked[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        let core_cover: Vec<u32> = self
+            .get_core_ids()
+            .iter()
+            .zip(core_marked.iter())
+            .filter(|(_id, marked)| !**marked)
+            .map(|(id, _marked)| *id)
+            .collect();
+        let non_core_cover: Vec<u32> = self
+            .get_non_core_ids()
+            .iter()
+            .zip(non_core_marked.iter())
+            .filter(|(_id, marked)| **marked)
+            .map(|(id, _marked)| *id)
+            .collect();
+        (core_cover, non_core_cover)
+    }
+}
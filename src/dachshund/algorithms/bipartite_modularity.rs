@@ -0,0 +1,332 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::butterflies::get_shore_adjacencies;
+use crate::dachshund::graph_base::BipartiteGraphBase;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::Hash;
+
+// gains smaller than this are treated as ties, to avoid moving nodes back and forth
+// because of floating point noise.
+const BILOUVAIN_EPSILON: f64 = 1e-12;
+
+/// A community of a bipartite graph, made up of core and non-core nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoCluster<T> {
+    pub core_ids: Vec<T>,
+    pub non_core_ids: Vec<T>,
+}
+
+/// Weighted bipartite graph BiLouvain operates on. Nodes are numbered across both shores,
+/// core nodes first. At the first level each node is a node of the original graph; at
+/// subsequent levels each node is the core or non-core part of a community of the previous
+/// level.
+pub struct BipartiteAggregateGraph {
+    num_core: usize,
+    // (neighbor, edge weight), all on the other shore.
+    adjacency: Vec<Vec<(usize, f64)>>,
+    // weighted degree.
+    degrees: Vec<f64>,
+    // positions (in the core or non-core ids of the original graph) of the nodes
+    // aggregated into each node.
+    members: Vec<Vec<usize>>,
+    // total edge weight (m in the modularity formula).
+    total_weight: f64,
+}
+impl BipartiteAggregateGraph {
+    fn count_nodes(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn is_core(&self, v: usize) -> bool {
+        v < self.num_core
+    }
+
+    // Barber's modularity: Q = 1/m sum_c (e_c - K_c * D_c / m), where e_c is the weight of
+    // the edges within community c, and K_c and D_c the total degrees of its core and
+    // non-core nodes.
+    fn get_modularity(&self, partition: &[usize]) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+        let mut internal: FxHashMap<usize, f64> = FxHashMap::default();
+        let mut core_totals: FxHashMap<usize, f64> = FxHashMap::default();
+        let mut non_core_totals: FxHashMap<usize, f64> = FxHashMap::default();
+        for v in 0..self.count_nodes() {
+            let c = partition[v];
+            if self.is_core(v) {
+                *core_totals.entry(c).or_insert(0.0) += self.degrees[v];
+                for (u, w) in &self.adjacency[v] {
+                    if partition[*u] == c {
+                        *internal.entry(c).or_insert(0.0) += w;
+                    }
+                }
+            } else {
+                *non_core_totals.entry(c).or_insert(0.0) += self.degrees[v];
+            }
+        }
+        core_totals
+            .iter()
+            .map(|(c, core_total)| {
+                let non_core_total = non_core_totals.get(c).copied().unwrap_or(0.0);
+                internal.get(c).copied().unwrap_or(0.0) / self.total_weight
+                    - core_total * non_core_total / (self.total_weight * self.total_weight)
+            })
+            .sum()
+    }
+
+    // Local moving phase: nodes are visited in order, and moved to the community (of a
+    // neighbor, or an empty one) with the largest modularity gain, until no node moves. A
+    // core node v gains w(v, c) - k_v * D_c / m from joining community c, where w(v, c) is
+    // the weight of its edges to c; non-core nodes likewise, with K_c. Returns whether any
+    // node moved.
+    fn move_nodes(&self, partition: &mut [usize]) -> bool {
+        let n = self.count_nodes();
+        let m = self.total_weight;
+        // total degrees of the core and non-core nodes of each community.
+        let mut totals: Vec<[f64; 2]> = vec![[0.0, 0.0]; n];
+        let mut sizes: Vec<usize> = vec![0; n];
+        for v in 0..n {
+            totals[partition[v]][self.is_core(v) as usize] += self.degrees[v];
+            sizes[partition[v]] += 1;
+        }
+        let mut empty: Vec<usize> = (0..n).filter(|c| sizes[*c] == 0).rev().collect();
+        let mut moved_any = false;
+        loop {
+            let mut moved = false;
+            for v in 0..n {
+                let shore = self.is_core(v) as usize;
+                let k_v = self.degrees[v];
+                let old = partition[v];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for (u, w) in &self.adjacency[v] {
+                    *links.entry(partition[*u]).or_insert(0.0) += w;
+                }
+                // the other shore's total degree is unaffected by v leaving its community.
+                let gain = |c: usize, links: &BTreeMap<usize, f64>| {
+                    links.get(&c).copied().unwrap_or(0.0) - k_v * totals[c][1 - shore] / m
+                };
+                let mut best = old;
+                let mut best_gain = gain(old, &links);
+                for c in links.keys() {
+                    let c_gain = gain(*c, &links);
+                    if c_gain > best_gain + BILOUVAIN_EPSILON {
+                        best = *c;
+                        best_gain = c_gain;
+                    }
+                }
+                // an empty community gains nothing, but v may be better off on its own.
+                if best_gain < -BILOUVAIN_EPSILON && sizes[old] > 1 {
+                    if let Some(c) = empty.pop() {
+                        best = c;
+                    }
+                }
+                if best != old {
+                    totals[old][shore] -= k_v;
+                    sizes[old] -= 1;
+                    if sizes[old] == 0 {
+                        empty.push(old);
+                    }
+                    totals[best][shore] += k_v;
+                    sizes[best] += 1;
+                    partition[v] = best;
+                    moved = true;
+                    moved_any = true;
+                }
+            }
+            if !moved {
+                return moved_any;
+            }
+        }
+    }
+
+    // Aggregates the core and non-core nodes of each community into a core and a non-core
+    // node, returning the aggregate graph and its partition, in which both parts of a
+    // community start out together.
+    fn aggregate(&self, partition: &[usize]) -> (Self, Vec<usize>) {
+        let mut core_parts: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut non_core_parts: BTreeMap<usize, usize> = BTreeMap::new();
+        for (v, c) in partition.iter().enumerate() {
+            if self.is_core(v) {
+                core_parts.insert(*c, 0);
+            } else {
+                non_core_parts.insert(*c, 0);
+            }
+        }
+        let num_core = core_parts.len();
+        for (i, ix) in core_parts.values_mut().enumerate() {
+            *ix = i;
+        }
+        for (i, ix) in non_core_parts.values_mut().enumerate() {
+            *ix = num_core + i;
+        }
+        let get_node = |v: usize| {
+            if self.is_core(v) {
+                core_parts[&partition[v]]
+            } else {
+                non_core_parts[&partition[v]]
+            }
+        };
+        // communities are renumbered, to stay within the aggregate graph's node count.
+        let communities: BTreeMap<usize, usize> = core_parts
+            .keys()
+            .chain(non_core_parts.keys())
+            .copied()
+            .collect::<BTreeSet<usize>>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (c, i))
+            .collect();
+        let n = num_core + non_core_parts.len();
+        let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); n];
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut aggregate_partition: Vec<usize> = vec![0; n];
+        for v in 0..self.count_nodes() {
+            let a = get_node(v);
+            members[a].extend(self.members[v].iter().copied());
+            aggregate_partition[a] = communities[&partition[v]];
+            for (u, w) in &self.adjacency[v] {
+                *weights[a].entry(get_node(*u)).or_insert(0.0) += w;
+            }
+        }
+        let adjacency: Vec<Vec<(usize, f64)>> = weights
+            .into_iter()
+            .map(|x| x.into_iter().collect())
+            .collect();
+        let aggregate = BipartiteAggregateGraph {
+            num_core,
+            degrees: adjacency
+                .iter()
+                .map(|x| x.iter().map(|(_u, w)| w).sum())
+                .collect(),
+            adjacency,
+            members,
+            total_weight: self.total_weight,
+        };
+        (aggregate, aggregate_partition)
+    }
+}
+
+pub trait BipartiteModularity: BipartiteGraphBase
+where
+    Self::NodeType: NodeBase,
+    <Self::NodeType as NodeBase>::NodeIdType: Copy + Eq + Hash,
+    <Self::NodeType as NodeBase>::NodeEdgeType:
+        NodeEdgeBase<NodeIdType = <Self::NodeType as NodeBase>::NodeIdType>,
+{
+    fn init_bipartite_aggregate_graph(&self) -> BipartiteAggregateGraph {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let num_core = core_adjacency.len();
+        let adjacency: Vec<Vec<(usize, f64)>> = core_adjacency
+            .iter()
+            .map(|x| x.iter().map(|j| (num_core + j, 1.0)).collect())
+            .chain(
+                non_core_adjacency
+                    .iter()
+                    .map(|x| x.iter().map(|i| (*i, 1.0)).collect()),
+            )
+            .collect();
+        let degrees: Vec<f64> = adjacency.iter().map(|x| x.len() as f64).collect();
+        BipartiteAggregateGraph {
+            num_core,
+            total_weight: degrees[..num_core].iter().sum(),
+            degrees,
+            adjacency,
+            members: (0..num_core)
+                .chain(0..non_core_adjacency.len())
+                .map(|i| vec![i])
+                .collect(),
+        }
+    }
+
+    // Barber's bipartite modularity of the given co-clusters (see
+    // https://arxiv.org/abs/0707.1616), which only rewards edges between core and non-core
+    // nodes of the same co-cluster, against a null model preserving the degrees of both
+    // shores. Nodes in no co-cluster are taken to be on their own.
+    fn get_bipartite_modularity(
+        &self,
+        co_clusters: &[CoCluster<<Self::NodeType as NodeBase>::NodeIdType>],
+    ) -> f64 {
+        let graph = self.init_bipartite_aggregate_graph();
+        let mut positions: FxHashMap<<Self::NodeType as NodeBase>::NodeIdType, usize> = self
+            .get_core_ids()
+            .iter()
+            .chain(self.get_non_core_ids().iter())
+            .enumerate()
+            .map(|(v, id)| (*id, v))
+            .collect();
+        let mut partition: Vec<usize> = (0..graph.count_nodes()).collect();
+        for (c, co_cluster) in co_clusters.iter().enumerate() {
+            for id in co_cluster
+                .core_ids
+                .iter()
+                .chain(co_cluster.non_core_ids.iter())
+            {
+                if let Some(v) = positions.remove(id) {
+                    partition[v] = graph.count_nodes() + c;
+                }
+            }
+        }
+        graph.get_modularity(&partition)
+    }
+
+    // BiLouvain community detection: the Louvain method, maximizing Barber's bipartite
+    // modularity. Alternates local moving of nodes and aggregation of the core and non-core
+    // nodes of each community, until no node moves. Returns co-clusters of core and
+    // non-core nodes (in order of their first node, core nodes first, with ids in the
+    // order of the graph's), along with their modularity.
+    #[allow(clippy::type_complexity)]
+    fn get_bilouvain_co_clusters(
+        &self,
+    ) -> (
+        Vec<CoCluster<<Self::NodeType as NodeBase>::NodeIdType>>,
+        f64,
+    ) {
+        let mut graph = self.init_bipartite_aggregate_graph();
+        let mut partition: Vec<usize> = (0..graph.count_nodes()).collect();
+        if graph.total_weight > 0.0 {
+            while graph.move_nodes(&mut partition) {
+                let (aggregate, aggregate_partition) = graph.aggregate(&partition);
+                graph = aggregate;
+                partition = aggregate_partition;
+            }
+        }
+        let modularity = graph.get_modularity(&partition);
+        let mut by_partition: BTreeMap<usize, (Vec<usize>, Vec<usize>)> = BTreeMap::new();
+        for (v, c) in partition.iter().enumerate() {
+            let (core, non_core) = by_partition.entry(*c).or_default();
+            if graph.is_core(v) {
+                core.extend(graph.members[v].iter().copied());
+            } else {
+                non_core.extend(graph.members[v].iter().copied());
+            }
+        }
+        let core_ids = self.get_core_ids();
+        let non_core_ids = self.get_non_core_ids();
+        let mut co_clusters: Vec<(Vec<usize>, Vec<usize>)> = by_partition.into_values().collect();
+        for (core, non_core) in co_clusters.iter_mut() {
+            core.sort_unstable();
+            non_core.sort_unstable();
+        }
+        co_clusters.sort_by_key(|(core, non_core)| match core.first() {
+            Some(i) => (0, *i),
+            None => (1, non_core[0]),
+        });
+        (
+            co_clusters
+                .into_iter()
+                .map(|(core, non_core)| CoCluster {
+                    core_ids: core.into_iter().map(|i| core_ids[i]).collect(),
+                    non_core_ids: non_core.into_iter().map(|j| non_core_ids[j]).collect(),
+                })
+                .collect(),
+            modularity,
+        )
+    }
+}
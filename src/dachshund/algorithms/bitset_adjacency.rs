@@ -0,0 +1,187 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+
+/// Largest number of nodes for which a bitset adjacency matrix is built.
+pub const MAX_BITSET_NODES: usize = 1 << 16;
+/// Smallest density (edges over pairs of nodes) for which a bitset adjacency matrix is
+/// built: at 1/64, its n^2 bits take as much memory as the 64-bit neighbor ids of
+/// adjacency lists do.
+pub const MIN_BITSET_DENSITY: f64 = 1.0 / 64.0;
+
+/// Whether a graph is small and dense enough for `BitsetAdjacency::from_graph` to be used.
+pub fn use_bitset_adjacency(num_nodes: usize, num_edges: usize) -> bool {
+    if !(2..=MAX_BITSET_NODES).contains(&num_nodes) {
+        return false;
+    }
+    let num_pairs = num_nodes * (num_nodes - 1) / 2;
+    num_edges as f64 >= MIN_BITSET_DENSITY * num_pairs as f64
+}
+
+/// Adjacency matrix of a simple graph, with one row of bits per node, padded to a fixed
+/// stride of 64-bit words, so that neighborhoods are intersected a word (64 nodes) at a
+/// time, rather than by hashing each neighbor: common neighbors of two nodes (and so
+/// triangles, clustering coefficients and clique checks) take O(n / 64) time, whatever
+/// their degrees. Row and column i stand for node ids[i].
+pub struct BitsetAdjacency {
+    ids: Vec<NodeId>,
+    positions: FxHashMap<NodeId, usize>,
+    stride: usize,
+    bits: Vec<u64>,
+}
+impl BitsetAdjacency {
+    /// The adjacency matrix of graph, if it is simple (without repeated edges or self-loops),
+    /// which its rows can't represent.
+    pub fn from_graph<G: GraphBase>(graph: &G) -> Option<Self>
+    where
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        let ids = graph.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let stride = ids.len().div_ceil(64);
+        let mut adjacency = Self {
+            bits: vec![0; stride * ids.len()],
+            ids,
+            positions,
+            stride,
+        };
+        for (i, id) in adjacency.ids.iter().enumerate() {
+            for e in graph.get_node(*id).get_edges() {
+                let j = adjacency.positions[&e.get_neighbor_id()];
+                let word = &mut adjacency.bits[i * stride + j / 64];
+                if i == j || *word & (1 << (j % 64)) != 0 {
+                    return None;
+                }
+                *word |= 1 << (j % 64);
+            }
+        }
+        Some(adjacency)
+    }
+
+    /// The adjacency matrix of graph, if it is simple and use_bitset_adjacency deems it
+    /// small and dense enough to be worth it.
+    pub fn from_dense_graph<G: GraphBase>(graph: &G) -> Option<Self>
+    where
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        if !use_bitset_adjacency(graph.count_nodes(), graph.count_edges()) {
+            return None;
+        }
+        Self::from_graph(graph)
+    }
+
+    pub fn count_nodes(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn get_ids(&self) -> &[NodeId] {
+        &self.ids
+    }
+
+    pub fn get_position(&self, id: NodeId) -> Option<usize> {
+        self.positions.get(&id).cloned()
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.bits[i * self.stride..(i + 1) * self.stride]
+    }
+
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.row(i)[j / 64] & (1 << (j % 64)) != 0
+    }
+
+    /// whether two nodes are adjacent (false for unknown nodes).
+    pub fn has_edge_between(&self, id1: NodeId, id2: NodeId) -> bool {
+        match (self.get_position(id1), self.get_position(id2)) {
+            (Some(i), Some(j)) => self.has_edge(i, j),
+            _ => false,
+        }
+    }
+
+    pub fn degree(&self, i: usize) -> usize {
+        self.row(i).iter().map(|x| x.count_ones() as usize).sum()
+    }
+
+    /// positions of the neighbors of node i, in order.
+    pub fn get_neighbors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(i).iter().enumerate().flat_map(|(w, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(w * 64 + bit)
+            })
+        })
+    }
+
+    pub fn count_common_neighbors(&self, i: usize, j: usize) -> usize {
+        self.row(i)
+            .iter()
+            .zip(self.row(j))
+            .map(|(x, y)| (x & y).count_ones() as usize)
+            .sum()
+    }
+
+    /// number of triangles through node i: half the neighbors it shares with each neighbor.
+    pub fn count_triangles(&self, i: usize) -> usize {
+        self.get_neighbors(i)
+            .map(|j| self.count_common_neighbors(i, j))
+            .sum::<usize>()
+            / 2
+    }
+
+    /// number of triangles through each node, as `Triangles::get_triangle_counts`.
+    pub fn get_triangle_counts(&self) -> FxHashMap<NodeId, usize> {
+        (0..self.count_nodes())
+            .map(|i| (self.ids[i], self.count_triangles(i)))
+            .collect()
+    }
+
+    /// fraction of pairs of neighbors of node i which are adjacent, as
+    /// `Clustering::get_clustering_coefficient`; None for nodes with fewer than 2 neighbors.
+    pub fn get_clustering_coefficient(&self, i: usize) -> Option<f64> {
+        let degree = self.degree(i);
+        if degree <= 1 {
+            return None;
+        }
+        Some(2.0 * self.count_triangles(i) as f64 / (degree * (degree - 1)) as f64)
+    }
+
+    /// whether the given nodes are pairwise adjacent (false if any is unknown).
+    pub fn is_clique(&self, ids: &[NodeId]) -> bool {
+        let positions: Option<Vec<usize>> = ids.iter().map(|id| self.get_position(*id)).collect();
+        let positions = match positions {
+            Some(positions) => positions,
+            None => return false,
+        };
+        // each node's row must contain every other node of the clique.
+        let mut mask: Vec<u64> = vec![0; self.stride];
+        for j in &positions {
+            mask[j / 64] |= 1 << (j % 64);
+        }
+        positions.iter().all(|i| {
+            self.row(*i)
+                .iter()
+                .zip(&mask)
+                .enumerate()
+                .all(|(w, (x, m))| {
+                    let own = if w == i / 64 { 1 << (i % 64) } else { 0 };
+                    (x | own) & m == *m
+                })
+        })
+    }
+}
@@ -0,0 +1,171 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::BipartiteGraphBase;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::hash::Hash;
+
+/// Neighbors of each node of one shore, as positions in the other shore's ids.
+pub type ShoreAdjacency = Vec<Vec<usize>>;
+
+/// Number of butterflies each edge is part of, keyed by (core id, non-core id).
+pub type EdgeButterflyCounts<T> = FxHashMap<(T, T), usize>;
+
+/// Neighbors of core and non-core nodes of a bipartite graph, as positions in the other
+/// shore's ids. Nodes joined by several edges (e.g. of different types) are counted as
+/// neighbors once.
+pub fn get_shore_adjacencies<G>(graph: &G) -> (ShoreAdjacency, ShoreAdjacency)
+where
+    G: BipartiteGraphBase,
+    G::NodeType: NodeBase,
+    <G::NodeType as NodeBase>::NodeIdType: Copy + Eq + Hash,
+    <G::NodeType as NodeBase>::NodeEdgeType:
+        NodeEdgeBase<NodeIdType = <G::NodeType as NodeBase>::NodeIdType>,
+{
+    let get_adjacency =
+        |ids: &[<G::NodeType as NodeBase>::NodeIdType],
+         other_ids: &[<G::NodeType as NodeBase>::NodeIdType]| {
+            let positions: FxHashMap<<G::NodeType as NodeBase>::NodeIdType, usize> = other_ids
+                .iter()
+                .enumerate()
+                .map(|(j, id)| (*id, j))
+                .collect();
+            ids.iter()
+                .map(|id| {
+                    let mut neighbors: Vec<usize> = graph
+                        .get_node(*id)
+                        .get_edges()
+                        .filter_map(|e| positions.get(&e.get_neighbor_id()).copied())
+                        .collect();
+                    neighbors.sort_unstable();
+                    neighbors.dedup();
+                    neighbors
+                })
+                .collect::<ShoreAdjacency>()
+        };
+    (
+        get_adjacency(graph.get_core_ids(), graph.get_non_core_ids()),
+        get_adjacency(graph.get_non_core_ids(), graph.get_core_ids()),
+    )
+}
+
+// counts the neighbors node i shares with each other node k of its shore (i.e. the wedges
+// i - j - k), recording the nodes with a nonzero count in touched.
+fn get_wedge_counts(
+    i: usize,
+    adjacency: &[Vec<usize>],
+    other_adjacency: &[Vec<usize>],
+    counts: &mut [usize],
+    touched: &mut Vec<usize>,
+) {
+    for &j in &adjacency[i] {
+        for &k in &other_adjacency[j] {
+            if k != i {
+                if counts[k] == 0 {
+                    touched.push(k);
+                }
+                counts[k] += 1;
+            }
+        }
+    }
+}
+
+// number of butterflies each node of a shore is part of: each other node of the shore with
+// which it shares c neighbors closes c choose 2 butterflies with it.
+fn get_shore_butterfly_counts(
+    adjacency: &[Vec<usize>],
+    other_adjacency: &[Vec<usize>],
+) -> Vec<usize> {
+    let mut counts = vec![0; adjacency.len()];
+    let mut touched: Vec<usize> = Vec::new();
+    (0..adjacency.len())
+        .map(|i| {
+            get_wedge_counts(i, adjacency, other_adjacency, &mut counts, &mut touched);
+            touched
+                .drain(..)
+                .map(|k| {
+                    let c = std::mem::take(&mut counts[k]);
+                    c * (c - 1) / 2
+                })
+                .sum()
+        })
+        .collect()
+}
+
+pub trait Butterflies: BipartiteGraphBase
+where
+    Self::NodeType: NodeBase,
+    <Self::NodeType as NodeBase>::NodeIdType: Copy + Eq + Hash,
+    <Self::NodeType as NodeBase>::NodeEdgeType:
+        NodeEdgeBase<NodeIdType = <Self::NodeType as NodeBase>::NodeIdType>,
+{
+    // Number of butterflies (4-cycles, or K2,2 bicliques) in the graph, the bipartite
+    // analogue of triangles. Butterflies are counted by enumerating wedges centered on
+    // the shore where this is cheapest, in O(sum of squared degrees of that shore) time.
+    fn get_butterfly_count(&self) -> usize {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let get_cost = |adjacency: &ShoreAdjacency| -> usize {
+            adjacency.iter().map(|x| x.len() * x.len()).sum()
+        };
+        // each butterfly has two nodes on each shore.
+        let counts = if get_cost(&non_core_adjacency) <= get_cost(&core_adjacency) {
+            get_shore_butterfly_counts(&core_adjacency, &non_core_adjacency)
+        } else {
+            get_shore_butterfly_counts(&non_core_adjacency, &core_adjacency)
+        };
+        counts.iter().sum::<usize>() / 2
+    }
+
+    // Number of butterflies each node, core or non-core, is part of.
+    fn get_butterfly_counts(&self) -> FxHashMap<<Self::NodeType as NodeBase>::NodeIdType, usize> {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let core_counts = get_shore_butterfly_counts(&core_adjacency, &non_core_adjacency);
+        let non_core_counts = get_shore_butterfly_counts(&non_core_adjacency, &core_adjacency);
+        self.get_core_ids()
+            .iter()
+            .zip(core_counts)
+            .chain(self.get_non_core_ids().iter().zip(non_core_counts))
+            .map(|(id, count)| (*id, count))
+            .collect()
+    }
+
+    // Butterfly support of each edge, as the number of butterflies it is part of, keyed by
+    // (core id, non-core id). An edge (u, v) closes a butterfly with any other neighbor w of
+    // v and any other neighbor of v shared by u and w.
+    fn get_edge_butterfly_counts(
+        &self,
+    ) -> EdgeButterflyCounts<<Self::NodeType as NodeBase>::NodeIdType> {
+        let (core_adjacency, non_core_adjacency) = get_shore_adjacencies(self);
+        let core_ids = self.get_core_ids();
+        let non_core_ids = self.get_non_core_ids();
+        let mut support = FxHashMap::default();
+        let mut counts = vec![0; core_adjacency.len()];
+        let mut touched: Vec<usize> = Vec::new();
+        for i in 0..core_adjacency.len() {
+            get_wedge_counts(
+                i,
+                &core_adjacency,
+                &non_core_adjacency,
+                &mut counts,
+                &mut touched,
+            );
+            for &j in &core_adjacency[i] {
+                let count: usize = non_core_adjacency[j]
+                    .iter()
+                    .filter(|k| **k != i)
+                    .map(|k| counts[*k] - 1)
+                    .sum();
+                support.insert((core_ids[i], non_core_ids[j]), count);
+            }
+            for k in touched.drain(..) {
+                counts[k] = 0;
+            }
+        }
+        support
+    }
+}
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate nalgebra as na;
+use crate::dachshund::algorithms::bitset_adjacency::BitsetAdjacency;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
@@ -37,7 +38,15 @@ where
         // edges.
         Some(num_ties as f64 / ((num_neighbors * (num_neighbors - 1)) as f64))
     }
+    // Average clustering coefficient of nodes with at least 2 neighbors, computed on a
+    // `BitsetAdjacency` for small dense graphs.
     fn get_avg_clustering(&self) -> f64 {
+        if let Some(adjacency) = BitsetAdjacency::from_dense_graph(self) {
+            let coefs = (0..adjacency.count_nodes())
+                .filter_map(|i| adjacency.get_clustering_coefficient(i))
+                .collect::<Vec<f64>>();
+            return Iterator::sum::<f64>(coefs.iter()) / coefs.len() as f64;
+        }
         let coefs = self
             .get_ids_iter()
             .filter_map(|x| self.get_clustering_coefficient(*x))
@@ -48,21 +57,30 @@ where
     // k~=26,000 gives an approximation w/ <1% chance of an error of more than 1 percentage point.
     // See http://jgaa.info/accepted/2005/SchankWagner2005.9.2.pdf for approximation guarantees.
     fn get_approx_avg_clustering(&self, samples: usize) -> f64 {
+        self.get_approx_avg_clustering_with_rng(samples, &mut rand::thread_rng())
+    }
+
+    // Same as get_approx_avg_clustering, but draws wedges from the provided random number
+    // generator, so that callers can make the estimate reproducible by seeding it.
+    fn get_approx_avg_clustering_with_rng<R: Rng>(&self, samples: usize, rng: &mut R) -> f64 {
         let ordered_nodes = self
             .get_nodes_iter()
             .filter(|node| node.degree() >= 2)
             .collect::<Vec<_>>();
 
         let n = ordered_nodes.len();
+        // mirrors get_avg_clustering, which averages over an empty set of coefficients.
+        if n == 0 || samples == 0 {
+            return f64::NAN;
+        }
         let mut successes = 0;
-        let mut rng = rand::thread_rng();
 
         for _i in 0..samples {
             // Pick a random node with degree at least 2.
             let v = &ordered_nodes[rng.gen_range(0..n)];
 
             // Choose 2 random nodes that are neighbors of j
-            let mut random_neighbors = v.get_edges().choose_multiple(&mut rng, 2).into_iter();
+            let mut random_neighbors = v.get_edges().choose_multiple(rng, 2).into_iter();
             let next_random_neighbor = random_neighbors.next();
             let u_id = next_random_neighbor.unwrap().get_neighbor_id();
             let w_id = random_neighbors.next().unwrap().get_neighbor_id();
@@ -78,4 +96,35 @@ where
         }
         (successes as f64) / (samples as f64)
     }
+
+    // Number of wedge samples required for get_approx_avg_clustering to be within epsilon
+    // of the average clustering coefficient with probability at least 1 - delta
+    // (Hoeffding bound, see Schank & Wagner above).
+    fn get_approx_clustering_num_samples(epsilon: f64, delta: f64) -> usize {
+        ((2.0 / delta).ln() / (2.0 * epsilon * epsilon)).ceil() as usize
+    }
+
+    // Average clustering, computed exactly when the graph has fewer wedges than the number
+    // of samples needed to reach the requested accuracy, and by wedge sampling otherwise.
+    // This bounds the cost of the statistic on graphs with high-degree nodes.
+    fn get_avg_clustering_with_accuracy<R: Rng>(
+        &self,
+        epsilon: f64,
+        delta: f64,
+        rng: &mut R,
+    ) -> f64 {
+        let samples = Self::get_approx_clustering_num_samples(epsilon, delta);
+        let num_wedges: usize = self
+            .get_nodes_iter()
+            .map(|node| {
+                let degree = node.degree();
+                degree * degree.saturating_sub(1) / 2
+            })
+            .sum();
+        if num_wedges <= samples {
+            self.get_avg_clustering()
+        } else {
+            self.get_approx_avg_clustering_with_rng(samples, rng)
+        }
+    }
 }
@@ -5,15 +5,16 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate nalgebra as na;
+use crate::dachshund::algorithms::dendrogram::Dendrogram;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode};
 use ordered_float::OrderedFloat;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
-type Community = HashSet<NodeId>;
+pub type Community = HashSet<NodeId>;
 
-type CommunityId = usize;
+pub type CommunityId = usize;
 #[derive(Clone, Copy, Eq)]
 pub struct CNMCommunityMergeInstruction {
     delta_ij: OrderedFloat<f64>,
@@ -217,7 +218,8 @@ pub trait CNMCommunities: GraphBase<NodeType = SimpleNode> {
             let old_maxheap: CNMCommunityMergeInstructionHeap = delta_q_maxheap.remove(&k).unwrap();
             let mut new_maxheap: CNMCommunityMergeInstructionHeap =
                 BinaryHeap::with_capacity(old_maxheap.len());
-            for el in old_maxheap.into_iter_sorted() {
+            // in decreasing order, as the heap would pop them.
+            for el in old_maxheap.into_sorted_vec().into_iter().rev() {
                 let ll = el.j;
 
                 if ll != i {
@@ -276,4 +278,28 @@ pub trait CNMCommunities: GraphBase<NodeType = SimpleNode> {
         }
         (state.communities, modularity_changes)
     }
+
+    // Keeps merging the communities with the largest modularity change, even once it turns
+    // negative, until no two communities are tied, and returns every merge made.
+    fn get_cnm_dendrogram(&self) -> Dendrogram {
+        let mut state = self.init_cnm_communities();
+        let leaves: Vec<NodeId> = (0..state.communities.len())
+            .map(|i| *state.communities[&i].iter().next().unwrap())
+            .collect();
+        let initial_modularity = match state.num_edges {
+            0 => 0.0,
+            num_edges => -state
+                .degree_map
+                .values()
+                .map(|d| (*d as f64 / (2 * num_edges) as f64).powi(2))
+                .sum::<f64>(),
+        };
+        let mut dendrogram = Dendrogram::new(leaves, initial_modularity);
+        while let Some(top) = state.maxh.peek() {
+            let (delta_ij, i, j) = top.tuple();
+            dendrogram.add_merge(i, j, delta_ij.into_inner());
+            state = self.iterate_cnm_communities(state);
+        }
+        dendrogram
+    }
 }
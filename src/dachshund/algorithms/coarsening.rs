@@ -0,0 +1,266 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeEdge};
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use fxhash::FxHashMap;
+use rand::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+// coarsening stops once a level keeps more than this fraction of the nodes of the previous
+// one, as matching then makes little progress (e.g. on star-like graphs).
+const MAX_COARSENING_RATIO: f64 = 0.95;
+
+/// One level of a coarsening hierarchy: a weighted graph each of whose nodes stands for a
+/// group of nodes of the previous (finer) level.
+pub struct CoarseGraph {
+    // edges between groups, with the total weight of the finer edges between them.
+    pub graph: WeightedUndirectedGraph,
+    // the node of graph each node of the finer level was merged into.
+    pub projection: HashMap<NodeId, NodeId>,
+    // number of nodes of the original graph each node of graph stands for.
+    pub node_weights: HashMap<NodeId, f64>,
+    // total weight of the edges of the original graph inside each node of graph.
+    pub internal_weights: HashMap<NodeId, f64>,
+}
+
+// Adjacency lists, node weights and internal edge weights of one level.
+pub(crate) struct CoarseningState {
+    pub(crate) ids: Vec<NodeId>,
+    pub(crate) edges: FxHashMap<NodeId, Vec<(NodeId, f64)>>,
+    pub(crate) node_weights: HashMap<NodeId, f64>,
+    pub(crate) internal_weights: HashMap<NodeId, f64>,
+}
+impl CoarseningState {
+    // The first level: the nodes of graph, each of weight 1, and its edges, with weights
+    // given by get_coarsening_edges.
+    pub(crate) fn from_graph<G: Coarsening>(graph: &G) -> Self
+    where
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        let ids = graph.get_ordered_node_ids();
+        let mut state = CoarseningState {
+            edges: ids
+                .iter()
+                .map(|id| (*id, graph.get_coarsening_edges(graph.get_node(*id))))
+                .collect(),
+            node_weights: ids.iter().map(|id| (*id, 1.0)).collect(),
+            internal_weights: ids.iter().map(|id| (*id, 0.0)).collect(),
+            ids,
+        };
+        // self-loops already sit inside their node.
+        for id in &state.ids {
+            let self_loop_weight: f64 = state.edges[id]
+                .iter()
+                .filter(|(neighbor_id, _)| neighbor_id == id)
+                .map(|(_, weight)| weight)
+                .sum();
+            state.internal_weights.insert(*id, self_loop_weight);
+        }
+        state
+    }
+
+    // Heavy edge matching: nodes are visited in random order, and each node not yet
+    // matched is matched with the unmatched neighbor it shares its heaviest edge with (or
+    // left on its own if there is none). Each pair of matched nodes is merged into a node
+    // of the next level, numbered from 0 in order of the smallest id it contains.
+    fn coarsen<R: Rng>(&self, rng: &mut R) -> CoarseGraph {
+        let mut order: Vec<NodeId> = self.ids.clone();
+        order.shuffle(rng);
+        let mut mates: FxHashMap<NodeId, NodeId> = FxHashMap::default();
+        for id in order {
+            if mates.contains_key(&id) {
+                continue;
+            }
+            let mut mate: NodeId = id;
+            let mut heaviest: f64 = 0.0;
+            for (neighbor_id, weight) in &self.edges[&id] {
+                if *neighbor_id != id && !mates.contains_key(neighbor_id) && *weight > heaviest {
+                    mate = *neighbor_id;
+                    heaviest = *weight;
+                }
+            }
+            mates.insert(id, mate);
+            mates.insert(mate, id);
+        }
+
+        let mut projection: HashMap<NodeId, NodeId> = HashMap::new();
+        let mut num_coarse_nodes: i64 = 0;
+        for id in &self.ids {
+            if !projection.contains_key(id) {
+                let coarse_id = NodeId::from(num_coarse_nodes);
+                projection.insert(*id, coarse_id);
+                projection.insert(mates[id], coarse_id);
+                num_coarse_nodes += 1;
+            }
+        }
+        self.contract(projection)
+    }
+
+    // Merges the nodes of this level mapped by projection to the same node into it, adding
+    // up their weights, and the weights of the edges between them into its internal weight.
+    fn contract(&self, projection: HashMap<NodeId, NodeId>) -> CoarseGraph {
+        let mut node_weights: HashMap<NodeId, f64> = HashMap::new();
+        let mut internal_weights: HashMap<NodeId, f64> = HashMap::new();
+        let mut coarse_edges: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = BTreeMap::new();
+        for id in &self.ids {
+            let coarse_id = projection[id];
+            *node_weights.entry(coarse_id).or_insert(0.0) += self.node_weights[id];
+            let internal = internal_weights.entry(coarse_id).or_insert(0.0);
+            *internal += self.internal_weights[id];
+            coarse_edges.entry(coarse_id).or_default();
+            for (neighbor_id, weight) in &self.edges[id] {
+                let coarse_neighbor_id = projection[neighbor_id];
+                if coarse_neighbor_id != coarse_id {
+                    *coarse_edges
+                        .entry(coarse_id)
+                        .or_default()
+                        .entry(coarse_neighbor_id)
+                        .or_insert(0.0) += weight;
+                } else if id < neighbor_id {
+                    // edges inside a group are seen from both ends.
+                    *internal += weight;
+                }
+            }
+        }
+        let nodes: FxHashMap<NodeId, WeightedNode> = coarse_edges
+            .into_iter()
+            .map(|(coarse_id, neighbors)| {
+                let node = WeightedNode {
+                    node_id: coarse_id,
+                    neighbors: neighbors.keys().cloned().collect(),
+                    edges: neighbors
+                        .into_iter()
+                        .map(|(target_id, weight)| WeightedNodeEdge { target_id, weight })
+                        .collect(),
+                };
+                (coarse_id, node)
+            })
+            .collect();
+        CoarseGraph {
+            graph: WeightedUndirectedGraph {
+                ids: nodes.keys().cloned().collect(),
+                nodes,
+            },
+            projection,
+            node_weights,
+            internal_weights,
+        }
+    }
+
+    pub(crate) fn from_coarse_graph(coarse: &CoarseGraph) -> Self {
+        let ids = coarse.graph.get_ordered_node_ids();
+        let edges = ids
+            .iter()
+            .map(|id| {
+                let node = coarse.graph.get_node(*id);
+                let edges = node.edges.iter().map(|e| (e.target_id, e.weight));
+                (*id, edges.collect())
+            })
+            .collect();
+        Self {
+            ids,
+            edges,
+            node_weights: coarse.node_weights.clone(),
+            internal_weights: coarse.internal_weights.clone(),
+        }
+    }
+}
+
+pub trait Coarsening: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Edges of a node, with their weights. Every edge has a weight of 1 in unweighted
+    // graphs; weighted graphs override this.
+    fn get_coarsening_edges(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    // Multilevel coarsening by heavy edge matching (as in METIS): a hierarchy of
+    // successively smaller weighted graphs, each roughly half the size of the previous
+    // one, ending once a level has at most min_nodes nodes (or stops shrinking). The
+    // projection of the first level maps the nodes of this graph; that of each following
+    // level, the nodes of the level before it. Empty if the graph has at most min_nodes
+    // nodes.
+    fn get_coarsening_hierarchy(&self, min_nodes: usize) -> Vec<CoarseGraph> {
+        self.get_coarsening_hierarchy_with_rng(min_nodes, &mut rand::thread_rng())
+    }
+
+    // Coarsening hierarchy, with nodes matched in an order drawn from the given random
+    // number generator.
+    fn get_coarsening_hierarchy_with_rng<R: Rng>(
+        &self,
+        min_nodes: usize,
+        rng: &mut R,
+    ) -> Vec<CoarseGraph> {
+        let mut state = CoarseningState::from_graph(self);
+        let mut hierarchy: Vec<CoarseGraph> = Vec::new();
+        while state.ids.len() > min_nodes {
+            let coarse = state.coarsen(rng);
+            let num_coarse_nodes = coarse.graph.count_nodes();
+            if num_coarse_nodes as f64 > MAX_COARSENING_RATIO * state.ids.len() as f64 {
+                break;
+            }
+            state = CoarseningState::from_coarse_graph(&coarse);
+            hierarchy.push(coarse);
+        }
+        hierarchy
+    }
+
+    // Contracts the given partition of the nodes, e.g. from a community detection
+    // algorithm, mapping each node of this graph to the node of the contracted graph it is
+    // merged into: edges between parts are weighted by the total weight of the edges
+    // between their nodes (as given by get_coarsening_edges), and node_weights and
+    // internal_weights count the nodes of each part and the total weight of the edges
+    // inside it. Errors if a node of the graph is missing from the partition, or the
+    // partition maps a node the graph doesn't have.
+    fn contract_partition(&self, partition: &HashMap<NodeId, NodeId>) -> CLQResult<CoarseGraph> {
+        for id in self.get_ids_iter() {
+            if !partition.contains_key(id) {
+                return Err(CLQError::from(format!(
+                    "Node {} is missing from the partition",
+                    id.value()
+                )));
+            }
+        }
+        if let Some(id) = partition.keys().find(|id| !self.has_node(**id)) {
+            return Err(CLQError::from(format!("No such node: {}", id.value())));
+        }
+        Ok(CoarseningState::from_graph(self).contract(partition.clone()))
+    }
+
+    // Contracts communities (e.g. from get_leiden_communities or
+    // get_label_propagation_communities), as contract_partition, into a node each,
+    // numbered by their community id. Errors if a node is in no community, or in several.
+    fn contract_communities(
+        &self,
+        communities: &HashMap<CommunityId, Community>,
+    ) -> CLQResult<CoarseGraph> {
+        let mut partition: HashMap<NodeId, NodeId> = HashMap::new();
+        for (community_id, community) in communities {
+            for id in community {
+                let coarse_id = NodeId::from(*community_id as i64);
+                if partition.insert(*id, coarse_id).is_some() {
+                    return Err(CLQError::from(format!(
+                        "Node {} is in more than one community",
+                        id.value()
+                    )));
+                }
+            }
+        }
+        self.contract_partition(&partition)
+    }
+}
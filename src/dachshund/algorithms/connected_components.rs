@@ -9,8 +9,9 @@ use crate::dachshund::algorithms::connectivity::Connectivity;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase, SimpleDirectedNode};
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
 
@@ -153,4 +154,106 @@ pub trait ConnectedComponentsDirected:
         }
         components
     }
+
+    // Strongly connected components by Tarjan's algorithm, in a single depth-first search
+    // (iterative, so that long paths don't overflow the stack). Components are listed in
+    // topological order, so that edges between components go from earlier to later ones,
+    // each sorted by node id.
+    fn get_strongly_connected_components_tarjan(&self) -> Vec<Vec<NodeId>> {
+        let mut index: FxHashMap<NodeId, usize> = FxHashMap::default();
+        let mut low_link: FxHashMap<NodeId, usize> = FxHashMap::default();
+        let mut on_stack: FxHashSet<NodeId> = FxHashSet::default();
+        let mut stack: Vec<NodeId> = Vec::new();
+        let mut components: Vec<Vec<NodeId>> = Vec::new();
+        for root in self.get_ordered_node_ids() {
+            if index.contains_key(&root) {
+                continue;
+            }
+            // nodes being visited, each with its out-neighbors left to visit.
+            let mut call_stack = vec![(root, self.get_node(root).get_out_neighbors())];
+            index.insert(root, index.len());
+            low_link.insert(root, index[&root]);
+            stack.push(root);
+            on_stack.insert(root);
+            while let Some((v, neighbors)) = call_stack.last_mut() {
+                let v = *v;
+                match neighbors.next().map(|e| e.get_neighbor_id()) {
+                    Some(w) if !index.contains_key(&w) => {
+                        index.insert(w, index.len());
+                        low_link.insert(w, index[&w]);
+                        stack.push(w);
+                        on_stack.insert(w);
+                        call_stack.push((w, self.get_node(w).get_out_neighbors()));
+                    }
+                    Some(w) => {
+                        if on_stack.contains(&w) {
+                            low_link.insert(v, low_link[&v].min(index[&w]));
+                        }
+                    }
+                    None => {
+                        call_stack.pop();
+                        if let Some((parent, _)) = call_stack.last() {
+                            low_link.insert(*parent, low_link[parent].min(low_link[&v]));
+                        }
+                        if low_link[&v] == index[&v] {
+                            let mut component: Vec<NodeId> = Vec::new();
+                            while let Some(w) = stack.pop() {
+                                on_stack.remove(&w);
+                                component.push(w);
+                                if w == v {
+                                    break;
+                                }
+                            }
+                            component.sort();
+                            components.push(component);
+                        }
+                    }
+                }
+            }
+        }
+        // Tarjan's algorithm finds components in reverse topological order.
+        components.reverse();
+        components
+    }
+
+    // Condensation of the graph: a directed acyclic graph with a node for each strongly
+    // connected component, numbered from 0 in the order of
+    // get_strongly_connected_components_tarjan, and an edge from one component to another
+    // if the graph has an edge from a node of the first to a node of the second. Also
+    // returns the component of each node.
+    fn get_condensation(&self) -> (SimpleDirectedGraph, HashMap<NodeId, NodeId>) {
+        let components = self.get_strongly_connected_components_tarjan();
+        let mut membership: HashMap<NodeId, NodeId> = HashMap::new();
+        for (i, component) in components.iter().enumerate() {
+            for id in component {
+                membership.insert(*id, NodeId::from(i as i64));
+            }
+        }
+        let mut nodes: FxHashMap<NodeId, SimpleDirectedNode> = (0..components.len())
+            .map(|i| {
+                let id = NodeId::from(i as i64);
+                let node = SimpleDirectedNode {
+                    node_id: id,
+                    in_neighbors: BTreeSet::new(),
+                    out_neighbors: BTreeSet::new(),
+                };
+                (id, node)
+            })
+            .collect();
+        for node in self.get_nodes_iter() {
+            let source = membership[&node.get_id()];
+            for e in node.get_out_neighbors() {
+                let target = membership[&e.get_neighbor_id()];
+                if target != source {
+                    nodes.get_mut(&source).unwrap().out_neighbors.insert(target);
+                    nodes.get_mut(&target).unwrap().in_neighbors.insert(source);
+                }
+            }
+        }
+        let condensation = SimpleDirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        };
+        (condensation, membership)
+    }
 }
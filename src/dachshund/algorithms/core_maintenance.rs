@@ -0,0 +1,258 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+use std::collections::{HashMap, VecDeque};
+
+/// Coreness of the nodes of a long-lived undirected graph which receives a stream of edge
+/// insertions and deletions, kept up to date after each one rather than recomputed from
+/// scratch as `Coreness::get_coreness_values` would. Adding or removing an edge changes the
+/// coreness of some nodes by at most 1, and only that of nodes with the coreness K of its
+/// endpoint with the lowest one, which are connected to it through nodes with coreness K
+/// (its "subcore"). Updates only visit that subcore, following the traversal algorithm of
+/// Sariyuce et al., "Streaming Algorithms for k-core Decomposition" (VLDB 2013). Self-loops
+/// are left out.
+#[derive(Clone, Debug, Default)]
+pub struct CoreMaintenance {
+    neighbors: FxHashMap<NodeId, FxHashSet<NodeId>>,
+    coreness: FxHashMap<NodeId, usize>,
+    num_edges: usize,
+}
+impl CoreMaintenance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// starts from the nodes and edges of graph, computing their coreness from scratch.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: GraphBase,
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        let mut maintenance = Self::new();
+        for node in graph.get_nodes_iter() {
+            let id = node.get_id();
+            let neighbors: FxHashSet<NodeId> = node
+                .get_edges()
+                .map(|e| e.get_neighbor_id())
+                .filter(|x| *x != id)
+                .collect();
+            maintenance.num_edges += neighbors.len();
+            maintenance.neighbors.insert(id, neighbors);
+        }
+        maintenance.num_edges /= 2;
+        maintenance.compute_coreness();
+        maintenance
+    }
+
+    // coreness of every node by peeling, as in Coreness::get_coreness_values: nodes are
+    // removed in increasing order of remaining degree, which they keep as their coreness.
+    fn compute_coreness(&mut self) {
+        let mut degrees: FxHashMap<NodeId, usize> = self
+            .neighbors
+            .iter()
+            .map(|(id, neighbors)| (*id, neighbors.len()))
+            .collect();
+        let max_degree = degrees.values().cloned().max().unwrap_or(0);
+        let mut bins: Vec<Vec<NodeId>> = vec![Vec::new(); max_degree + 1];
+        for (id, degree) in &degrees {
+            bins[*degree].push(*id);
+        }
+        self.coreness.clear();
+        let mut k: usize = 0;
+        while k < bins.len() {
+            // nodes move to lower bins as they lose neighbors, so entries can be stale.
+            let id = match bins[k].pop() {
+                Some(id) => id,
+                None => {
+                    k += 1;
+                    continue;
+                }
+            };
+            if self.coreness.contains_key(&id) || degrees[&id] != k {
+                continue;
+            }
+            self.coreness.insert(id, k);
+            for neighbor in &self.neighbors[&id] {
+                if !self.coreness.contains_key(neighbor) {
+                    let degree = degrees.get_mut(neighbor).unwrap();
+                    if *degree > k {
+                        *degree -= 1;
+                        bins[*degree].push(*neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn count_nodes(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn count_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    pub fn has_edge(&self, a: NodeId, b: NodeId) -> bool {
+        matches!(self.neighbors.get(&a), Some(neighbors) if neighbors.contains(&b))
+    }
+
+    /// adds a node with no edges (and so coreness 0), if it is new.
+    pub fn add_node(&mut self, id: NodeId) {
+        self.neighbors.entry(id).or_default();
+        self.coreness.entry(id).or_insert(0);
+    }
+
+    // nodes with coreness k reachable from roots through nodes with coreness k.
+    fn get_subcore(&self, roots: &[NodeId], k: usize) -> Vec<NodeId> {
+        let mut visited: FxHashSet<NodeId> = roots.iter().cloned().collect();
+        let mut queue: VecDeque<NodeId> = roots.iter().cloned().collect();
+        let mut subcore: Vec<NodeId> = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            subcore.push(id);
+            for neighbor in &self.neighbors[&id] {
+                if self.coreness[neighbor] == k && visited.insert(*neighbor) {
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        subcore
+    }
+
+    // endpoints of an edge with the lowest coreness of the two, and that coreness.
+    fn get_roots(&self, a: NodeId, b: NodeId) -> (Vec<NodeId>, usize) {
+        let (core_a, core_b) = (self.coreness[&a], self.coreness[&b]);
+        let k = core_a.min(core_b);
+        let roots = [a, b]
+            .iter()
+            .zip([core_a, core_b].iter())
+            .filter(|(_, core)| **core == k)
+            .map(|(id, _)| *id)
+            .collect();
+        (roots, k)
+    }
+
+    /// Adds an edge (along with its nodes if they are new) and updates the coreness of the
+    /// nodes it raises. Returns false, changing nothing else, if the edge was already there
+    /// or is a self-loop.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) -> bool {
+        self.add_node(a);
+        self.add_node(b);
+        if a == b || !self.neighbors.get_mut(&a).unwrap().insert(b) {
+            return false;
+        }
+        self.neighbors.get_mut(&b).unwrap().insert(a);
+        self.num_edges += 1;
+
+        // only nodes of the subcore can go up to k + 1, those which keep more than k
+        // neighbors in the (k + 1)-core candidate once the others have been evicted.
+        let (roots, k) = self.get_roots(a, b);
+        let subcore = self.get_subcore(&roots, k);
+        let mut degrees: FxHashMap<NodeId, usize> = subcore
+            .iter()
+            .map(|id| {
+                let degree = self.neighbors[id]
+                    .iter()
+                    .filter(|x| self.coreness[*x] >= k)
+                    .count();
+                (*id, degree)
+            })
+            .collect();
+        let mut evicted: FxHashSet<NodeId> = FxHashSet::default();
+        let mut queue: Vec<NodeId> = subcore
+            .iter()
+            .filter(|id| degrees[*id] <= k)
+            .cloned()
+            .collect();
+        while let Some(id) = queue.pop() {
+            if !evicted.insert(id) {
+                continue;
+            }
+            for neighbor in &self.neighbors[&id] {
+                if let Some(degree) = degrees.get_mut(neighbor) {
+                    if !evicted.contains(neighbor) {
+                        *degree -= 1;
+                        if *degree == k {
+                            queue.push(*neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        for id in subcore {
+            if !evicted.contains(&id) {
+                *self.coreness.get_mut(&id).unwrap() += 1;
+            }
+        }
+        true
+    }
+
+    /// Removes an edge and updates the coreness of the nodes it lowers. Nodes left without
+    /// edges stay, with coreness 0. Returns false if the edge wasn't there.
+    pub fn remove_edge(&mut self, a: NodeId, b: NodeId) -> bool {
+        if !self.has_edge(a, b) {
+            return false;
+        }
+        self.neighbors.get_mut(&a).unwrap().remove(&b);
+        self.neighbors.get_mut(&b).unwrap().remove(&a);
+        self.num_edges -= 1;
+
+        // nodes of the subcore (reached through the remaining edges from either endpoint
+        // with coreness k) drop to k - 1 when fewer than k of their neighbors remain in the
+        // k-core.
+        let (roots, k) = self.get_roots(a, b);
+        let subcore = self.get_subcore(&roots, k);
+        let mut degrees: FxHashMap<NodeId, usize> = subcore
+            .iter()
+            .map(|id| {
+                let degree = self.neighbors[id]
+                    .iter()
+                    .filter(|x| self.coreness[*x] >= k)
+                    .count();
+                (*id, degree)
+            })
+            .collect();
+        let mut queue: Vec<NodeId> = subcore
+            .iter()
+            .filter(|id| degrees[*id] < k)
+            .cloned()
+            .collect();
+        let mut evicted: FxHashSet<NodeId> = FxHashSet::default();
+        while let Some(id) = queue.pop() {
+            if !evicted.insert(id) {
+                continue;
+            }
+            *self.coreness.get_mut(&id).unwrap() -= 1;
+            for neighbor in &self.neighbors[&id] {
+                if let Some(degree) = degrees.get_mut(neighbor) {
+                    if !evicted.contains(neighbor) {
+                        *degree -= 1;
+                        if *degree + 1 == k {
+                            queue.push(*neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// coreness of the node, or None if it is unknown.
+    pub fn get_coreness(&self, id: NodeId) -> Option<usize> {
+        self.coreness.get(&id).cloned()
+    }
+
+    /// coreness of every node, as returned by `Coreness::get_coreness_values` for the
+    /// current graph (without self-loops).
+    pub fn get_coreness_values(&self) -> HashMap<NodeId, usize> {
+        self.coreness.iter().map(|(id, k)| (*id, *k)).collect()
+    }
+}
@@ -8,13 +8,16 @@ extern crate fxhash;
 
 use crate::dachshund::algorithms::connected_components::ConnectedComponents;
 use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::hashing::{FastHashMap, FastHashSet};
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use crate::dachshund::node::{
+    DirectedNodeBase, NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase,
+};
 use core::cmp::Reverse;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
 
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use ordered_float::NotNan;
 use priority_queue::PriorityQueue;
 
@@ -29,7 +32,7 @@ where
     fn _get_k_cores(&self, k: usize, removed: &mut FxHashSet<NodeId>) -> Vec<Vec<NodeId>> {
         // [BUG] This algorithm has a bug. See simple_graph.rs tests.
         let mut queue: OrderedNodeSet = self.get_ids_iter().cloned().collect();
-        let mut num_neighbors: HashMap<NodeId, usize> = self
+        let mut num_neighbors: FastHashMap<NodeId, usize> = self
             .get_nodes_iter()
             .map(|x| (x.get_id(), x.degree()))
             .collect();
@@ -120,12 +123,12 @@ where
 
         let mut bin_starts = self._init_bin_starts(&nodes, &coreness);
 
-        let mut node_idx: HashMap<NodeId, usize> = HashMap::new();
+        let mut node_idx: FastHashMap<NodeId, usize> = FastHashMap::default();
         for (i, &node) in nodes.iter().enumerate() {
             node_idx.insert(node, i);
         }
 
-        let mut neighbors: HashMap<NodeId, FxHashSet<NodeId>> = HashMap::new();
+        let mut neighbors: FastHashMap<NodeId, FxHashSet<NodeId>> = FastHashMap::default();
         for node in self.get_nodes_iter() {
             neighbors.insert(
                 node.get_id(),
@@ -185,13 +188,13 @@ where
         k: usize,
         ignore_nodes: &FxHashSet<NodeId>,
     ) -> (Vec<OrderedEdgeSet>, HashSet<OrderedNodeSet>) {
-        let mut neighbors: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
+        let mut neighbors: FastHashMap<NodeId, FastHashSet<NodeId>> = FastHashMap::default();
         let mut edges: OrderedEdgeSet = BTreeSet::new();
         for node in self.get_nodes_iter() {
             // [TODO] This step is unncessary now.
             neighbors.insert(
                 node.get_id(),
-                HashSet::from_iter(
+                FastHashSet::from_iter(
                     node.get_edges()
                         .map(|x| x.get_neighbor_id())
                         .filter(|x| !ignore_nodes.contains(x)),
@@ -316,6 +319,228 @@ pub trait FractionalCoreness: GraphBase<NodeType = WeightedNode> {
     }
 }
 
+/// D-core decomposition of a directed graph (Giatsidis, Thilikos and Vazirgiannis, 2011). The
+/// (k, l)-core is the largest subgraph in which every node has in-degree at least k and
+/// out-degree at least l, and (k, l)-cores are nested: the (k, l)-core contains every (k', l')-core
+/// with k' >= k and l' >= l. Self-loops are left out.
+pub struct DCores {
+    // for each node, and each k for which it is in the (k, 0)-core, the largest l for which it
+    // is in the (k, l)-core.
+    pub out_coreness: HashMap<NodeId, Vec<usize>>,
+}
+impl DCores {
+    /// nodes of the (k, l)-core, in order.
+    pub fn get_core(&self, k: usize, l: usize) -> Vec<NodeId> {
+        let mut core: Vec<NodeId> = self
+            .out_coreness
+            .iter()
+            .filter(|(_, values)| matches!(values.get(k), Some(value) if *value >= l))
+            .map(|(id, _)| *id)
+            .collect();
+        core.sort();
+        core
+    }
+
+    /// D-core matrix: entry [k][l] is the number of nodes in the (k, l)-core, for every
+    /// non-empty (k, l)-core.
+    pub fn get_matrix(&self) -> Vec<Vec<usize>> {
+        let mut matrix: Vec<Vec<usize>> = Vec::new();
+        for values in self.out_coreness.values() {
+            for (k, l) in values.iter().enumerate() {
+                if k == matrix.len() {
+                    matrix.push(Vec::new());
+                }
+                if *l >= matrix[k].len() {
+                    matrix[k].resize(l + 1, 0);
+                }
+                matrix[k][*l] += 1;
+            }
+        }
+        // so far, entries count the nodes for which l is the largest.
+        for row in &mut matrix {
+            for l in (1..row.len()).rev() {
+                row[l - 1] += row[l];
+            }
+        }
+        matrix
+    }
+
+    /// Skyline of the D-core matrix: the (k, l) with a non-empty (k, l)-core, but empty
+    /// (k + 1, l) and (k, l + 1)-cores, by increasing k (and so decreasing l). Every non-empty
+    /// (k, l)-core contains one of these.
+    pub fn get_skyline(&self) -> Vec<(usize, usize)> {
+        let max_l: Vec<usize> = self.get_matrix().iter().map(|row| row.len() - 1).collect();
+        (0..max_l.len())
+            .filter(|k| !matches!(max_l.get(k + 1), Some(l) if *l == max_l[*k]))
+            .map(|k| (k, max_l[k]))
+            .collect()
+    }
+}
+
+// Remaining nodes of a directed graph (without self-loops), as it is peeled down to its
+// (k, l)-cores for a given k.
+struct DCorePeeling<'a> {
+    k: usize,
+    in_neighbors: &'a FxHashMap<NodeId, Vec<NodeId>>,
+    out_neighbors: &'a FxHashMap<NodeId, Vec<NodeId>>,
+    in_degrees: FxHashMap<NodeId, usize>,
+    out_degrees: FxHashMap<NodeId, usize>,
+    // remaining nodes, by out-degree.
+    queue: BTreeSet<(usize, NodeId)>,
+    // remaining nodes with in-degree below k.
+    stack: Vec<NodeId>,
+}
+impl<'a> DCorePeeling<'a> {
+    fn new(
+        k: usize,
+        in_neighbors: &'a FxHashMap<NodeId, Vec<NodeId>>,
+        out_neighbors: &'a FxHashMap<NodeId, Vec<NodeId>>,
+    ) -> Self {
+        let in_degrees: FxHashMap<NodeId, usize> = in_neighbors
+            .iter()
+            .map(|(id, neighbors)| (*id, neighbors.len()))
+            .collect();
+        let out_degrees: FxHashMap<NodeId, usize> = out_neighbors
+            .iter()
+            .map(|(id, neighbors)| (*id, neighbors.len()))
+            .collect();
+        Self {
+            k,
+            in_neighbors,
+            out_neighbors,
+            queue: out_degrees.iter().map(|(id, d)| (*d, *id)).collect(),
+            stack: in_degrees
+                .iter()
+                .filter(|(_, d)| **d < k)
+                .map(|(id, _)| *id)
+                .collect(),
+            in_degrees,
+            out_degrees,
+        }
+    }
+
+    fn is_remaining(&self, id: NodeId) -> bool {
+        self.queue.contains(&(self.out_degrees[&id], id))
+    }
+
+    // removes the node, if it remains, and returns whether it did.
+    fn remove(&mut self, id: NodeId) -> bool {
+        if !self.queue.remove(&(self.out_degrees[&id], id)) {
+            return false;
+        }
+        for neighbor_id in &self.out_neighbors[&id] {
+            if self.is_remaining(*neighbor_id) {
+                let in_degree = self.in_degrees.get_mut(neighbor_id).unwrap();
+                *in_degree -= 1;
+                if *in_degree + 1 == self.k {
+                    self.stack.push(*neighbor_id);
+                }
+            }
+        }
+        for neighbor_id in &self.in_neighbors[&id] {
+            let out_degree = self.out_degrees.get_mut(neighbor_id).unwrap();
+            if self.queue.remove(&(*out_degree, *neighbor_id)) {
+                *out_degree -= 1;
+                self.queue.insert((*out_degree, *neighbor_id));
+            }
+        }
+        true
+    }
+
+    // removes nodes until all remaining ones have in-degree at least k, and returns them.
+    fn remove_below_k(&mut self) -> Vec<NodeId> {
+        let mut removed: Vec<NodeId> = Vec::new();
+        while let Some(id) = self.stack.pop() {
+            if self.remove(id) {
+                removed.push(id);
+            }
+        }
+        removed
+    }
+}
+
+pub trait DirectedCoreness: GraphBase
+where
+    Self::NodeType: DirectedNodeBase,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // in- and out-neighbors of each node, leaving out self-loops.
+    fn _get_dcore_neighbors(
+        &self,
+    ) -> (
+        FxHashMap<NodeId, Vec<NodeId>>,
+        FxHashMap<NodeId, Vec<NodeId>>,
+    ) {
+        let mut in_neighbors: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+        let mut out_neighbors: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            let others = |e: &<Self::NodeType as NodeBase>::NodeEdgeType| {
+                Some(e.get_neighbor_id()).filter(|x| *x != id)
+            };
+            in_neighbors.insert(id, node.get_in_neighbors().filter_map(others).collect());
+            out_neighbors.insert(id, node.get_out_neighbors().filter_map(others).collect());
+        }
+        (in_neighbors, out_neighbors)
+    }
+
+    // For each node of the (k, 0)-core, the largest l for which it is in the (k, l)-core.
+    // Like get_coreness_values, this peels off nodes by increasing out-degree, along with
+    // any nodes whose in-degree drops below k as a result.
+    fn _get_dcore_out_coreness(
+        &self,
+        k: usize,
+        in_neighbors: &FxHashMap<NodeId, Vec<NodeId>>,
+        out_neighbors: &FxHashMap<NodeId, Vec<NodeId>>,
+    ) -> HashMap<NodeId, usize> {
+        let mut peeling = DCorePeeling::new(k, in_neighbors, out_neighbors);
+        // what is left is the (k, 0)-core.
+        peeling.remove_below_k();
+        let mut out_coreness: HashMap<NodeId, usize> = HashMap::new();
+        let mut l: usize = 0;
+        while let Some((out_degree, id)) = peeling.queue.iter().next().cloned() {
+            // all remaining nodes have out-degree at least l, so they make up the (k, l)-core.
+            l = l.max(out_degree);
+            peeling.remove(id);
+            out_coreness.insert(id, l);
+            for id in peeling.remove_below_k() {
+                out_coreness.insert(id, l);
+            }
+        }
+        out_coreness
+    }
+
+    // nodes of the (k, l)-core, in order.
+    fn get_dcore(&self, k: usize, l: usize) -> Vec<NodeId> {
+        let (in_neighbors, out_neighbors) = self._get_dcore_neighbors();
+        let mut core: Vec<NodeId> = self
+            ._get_dcore_out_coreness(k, &in_neighbors, &out_neighbors)
+            .into_iter()
+            .filter(|(_, value)| *value >= l)
+            .map(|(id, _)| id)
+            .collect();
+        core.sort();
+        core
+    }
+
+    // Full D-core decomposition: the (k, l)-cores for every k and l. Takes one pass over
+    // the graph for each k up to the largest in-degree.
+    fn get_dcores(&self) -> DCores {
+        let (in_neighbors, out_neighbors) = self._get_dcore_neighbors();
+        let mut out_coreness: HashMap<NodeId, Vec<usize>> = HashMap::new();
+        for k in 0.. {
+            let values = self._get_dcore_out_coreness(k, &in_neighbors, &out_neighbors);
+            if values.is_empty() {
+                break;
+            }
+            for (id, l) in values {
+                out_coreness.entry(id).or_default().push(l);
+            }
+        }
+        DCores { out_coreness }
+    }
+}
+
 pub fn averaged_ties_ranking(scores: &HashMap<NodeId, usize>) -> HashMap<NodeId, f64> {
     // Given a map from NodeIds to values, create a new map from those NodeIds to their rank.
     // In the case of ties, all tied keys get the same, averaged rank.
@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::node::NodeBase;
+use std::collections::BTreeMap;
+
+// power laws are only fit to tails of at least this many nodes, as fits to fewer are
+// meaningless.
+pub const MIN_POWER_LAW_TAIL: usize = 10;
+
+/// Power law fit to the tail of a degree distribution, see `get_power_law_fit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerLawFit {
+    // exponent: the probability of degree k is proportional to k^-alpha, from x_min on.
+    pub alpha: f64,
+    // smallest degree the power law holds for.
+    pub x_min: usize,
+    // Kolmogorov-Smirnov statistic: largest difference between the fitted and empirical
+    // cumulative distributions of the tail.
+    pub ks_statistic: f64,
+    // number of nodes with degree at least x_min.
+    pub num_tail: usize,
+}
+
+/// number of nodes with each degree, for degrees with at least one node.
+pub fn get_histogram(degrees: &[usize]) -> BTreeMap<usize, usize> {
+    let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+    for degree in degrees {
+        *histogram.entry(*degree).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// complementary cumulative distribution of degrees: for each degree with at least one node,
+/// the fraction of nodes with at least that degree.
+pub fn get_ccdf(degrees: &[usize]) -> Vec<(usize, f64)> {
+    let n = degrees.len() as f64;
+    let mut num_at_least = degrees.len();
+    get_histogram(degrees)
+        .into_iter()
+        .map(|(degree, count)| {
+            let fraction = num_at_least as f64 / n;
+            num_at_least -= count;
+            (degree, fraction)
+        })
+        .collect()
+}
+
+// Fit of a discrete power law to the (ascending) histogram entries from the one with degree
+// x_min on, with alpha estimated by the approximate maximum likelihood of Clauset et al.
+fn fit_tail(tail: &[(usize, usize)], num_tail: usize) -> PowerLawFit {
+    let x_min = tail[0].0;
+    let shifted_x_min = x_min as f64 - 0.5;
+    let log_sum: f64 = tail
+        .iter()
+        .map(|(degree, count)| *count as f64 * (*degree as f64 / shifted_x_min).ln())
+        .sum();
+    let alpha = 1.0 + num_tail as f64 / log_sum;
+    // P(degree <= k) is about 1 - ((k + 0.5) / (x_min - 0.5))^(1 - alpha).
+    let mut num_at_most: usize = 0;
+    let mut ks_statistic: f64 = 0.0;
+    for (degree, count) in tail {
+        num_at_most += count;
+        let empirical = num_at_most as f64 / num_tail as f64;
+        let fitted = 1.0 - ((*degree as f64 + 0.5) / shifted_x_min).powf(1.0 - alpha);
+        ks_statistic = ks_statistic.max((empirical - fitted).abs());
+    }
+    PowerLawFit {
+        alpha,
+        x_min,
+        ks_statistic,
+        num_tail,
+    }
+}
+
+/// Fits a power law to the tail of the degree distribution, as in Clauset, Shalizi and
+/// Newman (2009): for each candidate x_min, alpha is estimated by maximum likelihood, and
+/// the x_min whose fit has the smallest Kolmogorov-Smirnov statistic is kept. Nodes of
+/// degree 0 are left out. Only tails of at least MIN_POWER_LAW_TAIL nodes, with at least two
+/// distinct degrees, are considered; returns None if there are none.
+pub fn get_power_law_fit(degrees: &[usize]) -> Option<PowerLawFit> {
+    let histogram: Vec<(usize, usize)> = get_histogram(degrees)
+        .into_iter()
+        .filter(|(degree, _count)| *degree > 0)
+        .collect();
+    let mut num_tail: usize = histogram.iter().map(|(_degree, count)| count).sum();
+    let mut best: Option<PowerLawFit> = None;
+    // tails of a single degree can't be fit.
+    for i in 0..histogram.len().saturating_sub(1) {
+        if num_tail < MIN_POWER_LAW_TAIL {
+            break;
+        }
+        let fit = fit_tail(&histogram[i..], num_tail);
+        if !matches!(best, Some(b) if b.ks_statistic <= fit.ks_statistic) {
+            best = Some(fit);
+        }
+        num_tail -= histogram[i].1;
+    }
+    best
+}
+
+pub trait DegreeDistribution: GraphBase {
+    fn get_degrees(&self) -> Vec<usize> {
+        self.get_nodes_iter().map(|node| node.degree()).collect()
+    }
+
+    // number of nodes with each degree, for degrees with at least one node.
+    fn get_degree_histogram(&self) -> BTreeMap<usize, usize> {
+        get_histogram(&self.get_degrees())
+    }
+
+    // fraction of nodes with at least each degree, for degrees with at least one node.
+    fn get_degree_ccdf(&self) -> Vec<(usize, f64)> {
+        get_ccdf(&self.get_degrees())
+    }
+
+    // power law fit to the tail of the degree distribution (see get_power_law_fit).
+    fn get_degree_power_law_fit(&self) -> Option<PowerLawFit> {
+        get_power_law_fit(&self.get_degrees())
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::id_types::NodeId;
+use std::collections::{HashMap, HashSet};
+
+/// One step of a `Dendrogram`: community `from` joins community `into`, which keeps its id,
+/// changing modularity by `modularity_change`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DendrogramMerge {
+    pub from: CommunityId,
+    pub into: CommunityId,
+    pub modularity_change: f64,
+}
+
+/// Full merge history of agglomerative community detection (see
+/// `CNMCommunities::get_cnm_dendrogram` and `Leiden::get_leiden_dendrogram`). Community i
+/// starts out as the singleton of `leaves[i]`, and communities are merged in order, so that
+/// each prefix of the merges gives a partition of the nodes, which callers can pick post hoc
+/// by number of communities (`cut_at`) or by modularity (`cut_at_modularity`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dendrogram {
+    pub leaves: Vec<NodeId>,
+    // modularity of the partition into singletons.
+    pub initial_modularity: f64,
+    pub merges: Vec<DendrogramMerge>,
+}
+impl Dendrogram {
+    pub fn new(leaves: Vec<NodeId>, initial_modularity: f64) -> Self {
+        Self {
+            leaves,
+            initial_modularity,
+            merges: Vec::new(),
+        }
+    }
+
+    pub fn add_merge(&mut self, from: CommunityId, into: CommunityId, modularity_change: f64) {
+        self.merges.push(DendrogramMerge {
+            from,
+            into,
+            modularity_change,
+        });
+    }
+
+    /// modularity of the partition after each prefix of the merges, starting with the
+    /// empty one (so that there is one more than there are merges).
+    pub fn get_modularities(&self) -> Vec<f64> {
+        let mut modularity = self.initial_modularity;
+        let mut modularities: Vec<f64> = vec![modularity];
+        for merge in &self.merges {
+            modularity += merge.modularity_change;
+            modularities.push(modularity);
+        }
+        modularities
+    }
+
+    /// communities after the first num_merges merges (or all of them, if there are fewer),
+    /// keyed by the ids they have in the merges.
+    pub fn cut_after(&self, num_merges: usize) -> HashMap<CommunityId, Community> {
+        let mut communities: HashMap<CommunityId, Community> = self
+            .leaves
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (i, HashSet::from([*id])))
+            .collect();
+        for merge in self.merges.iter().take(num_merges) {
+            let from = communities.remove(&merge.from).unwrap();
+            communities.get_mut(&merge.into).unwrap().extend(from);
+        }
+        communities
+    }
+
+    /// the finest partition into at most k communities. If the merges stop short of that
+    /// (e.g. CNM never merges disconnected communities), the coarsest partition there is.
+    pub fn cut_at(&self, k: usize) -> HashMap<CommunityId, Community> {
+        let num_merges = self.leaves.len().saturating_sub(k);
+        self.cut_after(num_merges)
+    }
+
+    /// the partition with the highest modularity, taking the one with the most communities
+    /// in case of ties.
+    pub fn cut_at_modularity(&self) -> HashMap<CommunityId, Community> {
+        let mut num_merges: usize = 0;
+        let mut best = f64::NEG_INFINITY;
+        for (i, modularity) in self.get_modularities().into_iter().enumerate() {
+            if modularity > best {
+                best = modularity;
+                num_merges = i;
+            }
+        }
+        self.cut_after(num_merges)
+    }
+}
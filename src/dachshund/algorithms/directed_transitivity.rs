@@ -0,0 +1,106 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+
+// Reciprocity and clustering of directed graphs, where Transitivity and Clustering treat
+// every edge as undirected. Self-loops are left out throughout.
+pub trait DirectedTransitivity: GraphBase
+where
+    Self::NodeType: DirectedNodeBase,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Fraction of edges u -> v for which v -> u is also an edge, or NaN if there are none.
+    fn get_reciprocity(&self) -> f64 {
+        let mut num_edges: usize = 0;
+        let mut num_mutual: usize = 0;
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_out_neighbors() {
+                let neighbor_id = e.get_neighbor_id();
+                if neighbor_id != id {
+                    num_edges += 1;
+                    num_mutual += node.has_in_neighbor(neighbor_id) as usize;
+                }
+            }
+        }
+        num_mutual as f64 / num_edges as f64
+    }
+
+    // Number of edges between the node and each of its neighbors (1, or 2 if reciprocated).
+    fn get_edge_counts(&self, node: &Self::NodeType) -> FxHashMap<NodeId, usize> {
+        let id = node.get_id();
+        let mut counts: FxHashMap<NodeId, usize> = FxHashMap::default();
+        for e in node.get_in_neighbors().chain(node.get_out_neighbors()) {
+            let neighbor_id = e.get_neighbor_id();
+            if neighbor_id != id {
+                *counts.entry(neighbor_id).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    // Directed triangles through the node, and the most there could be given its edges, as
+    // in Fagiolo (2007), https://arxiv.org/abs/physics/0612169: each triangle of neighbors
+    // counts once for every way of orienting its 3 edges present in the graph, so up to 8
+    // times. With total degree d and b reciprocated neighbors, there could be d(d - 1) - 2b.
+    fn count_directed_triangles(&self, id: NodeId) -> (usize, usize) {
+        let counts = self.get_edge_counts(self.get_node(id));
+        let mut num_triangles: usize = 0;
+        for (neighbor_id, count) in &counts {
+            let neighbor = self.get_node(*neighbor_id);
+            for e in neighbor
+                .get_in_neighbors()
+                .chain(neighbor.get_out_neighbors())
+            {
+                let other_id = e.get_neighbor_id();
+                if other_id != *neighbor_id {
+                    if let Some(other_count) = counts.get(&other_id) {
+                        num_triangles += count * other_count;
+                    }
+                }
+            }
+        }
+        let degree: usize = counts.values().sum();
+        let num_reciprocated = counts.values().filter(|count| **count == 2).count();
+        (
+            num_triangles / 2,
+            (degree * degree.saturating_sub(1)).saturating_sub(2 * num_reciprocated),
+        )
+    }
+
+    // Directed clustering coefficient of the node (see count_directed_triangles), or None
+    // if it couldn't be part of any triangle.
+    fn get_directed_clustering_coefficient(&self, id: NodeId) -> Option<f64> {
+        match self.count_directed_triangles(id) {
+            (_, 0) => None,
+            (num_triangles, num_possible) => Some(num_triangles as f64 / num_possible as f64),
+        }
+    }
+
+    // Average directed clustering coefficient, over nodes which have one.
+    fn get_avg_directed_clustering(&self) -> f64 {
+        let coefs = self
+            .get_ids_iter()
+            .filter_map(|x| self.get_directed_clustering_coefficient(*x))
+            .collect::<Vec<f64>>();
+        Iterator::sum::<f64>(coefs.iter()) / coefs.len() as f64
+    }
+
+    // Directed transitivity: directed triangles over possible ones, summed over all nodes
+    // (see count_directed_triangles). NaN if no node could be part of a triangle.
+    fn get_directed_transitivity(&self) -> f64 {
+        let (num_triangles, num_possible) = self
+            .get_ids_iter()
+            .map(|x| self.count_directed_triangles(*x))
+            .fold((0, 0), |(t, p), (x, y)| (t + x, p + y));
+        num_triangles as f64 / num_possible as f64
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{self, Write};
+
+/// Fill color of core nodes of a clique (or of all its nodes, in unipartite graphs).
+pub const DOT_CORE_COLOR: &str = "lightblue";
+/// Fill color of non-core nodes of a clique.
+pub const DOT_NON_CORE_COLOR: &str = "orange";
+/// Color of edges between nodes of a clique.
+pub const DOT_CLIQUE_EDGE_COLOR: &str = "red";
+
+/// Writes an undirected graph in Graphviz DOT format, with nodes given as (id, fill color),
+/// left unfilled if None, and edges as (id, id, whether they are clique edges), which are
+/// drawn bold in DOT_CLIQUE_EDGE_COLOR. Ids are written as they are displayed, so ids
+/// other than numbers must be quoted already.
+pub fn format_dot<T: fmt::Display>(
+    name: &str,
+    nodes: &[(T, Option<&str>)],
+    edges: &[(T, T, bool)],
+) -> String {
+    let mut s = String::new();
+    // writing to a String cannot fail.
+    writeln!(s, "graph \"{}\" {{", name.replace('"', "\\\"")).unwrap();
+    writeln!(s, "  node [shape=circle];").unwrap();
+    for (id, color) in nodes {
+        match color {
+            Some(color) => writeln!(s, "  {} [style=filled, fillcolor={}];", id, color),
+            None => writeln!(s, "  {};", id),
+        }
+        .unwrap();
+    }
+    for (id1, id2, in_clique) in edges {
+        if *in_clique {
+            writeln!(
+                s,
+                "  {} -- {} [color={}, penwidth=2];",
+                id1, id2, DOT_CLIQUE_EDGE_COLOR
+            )
+        } else {
+            writeln!(s, "  {} -- {};", id1, id2)
+        }
+        .unwrap();
+    }
+    s.push('}');
+    s
+}
+
+pub trait Dot: GraphBase + UndirectedGraph
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // The graph in Graphviz DOT format (see format_dot), with nodes and edges in order of
+    // id, each edge listed once. Nodes of clique (e.g. the nodes of a mined candidate) are
+    // filled, and edges between them highlighted.
+    fn to_dot(&self, name: &str, clique: &HashSet<NodeId>) -> String {
+        let mut nodes: Vec<(i64, Option<&str>)> = Vec::new();
+        let mut edges: BTreeSet<(NodeId, NodeId)> = BTreeSet::new();
+        for id in self.get_ordered_node_ids() {
+            let color = clique.contains(&id).then_some(DOT_CORE_COLOR);
+            nodes.push((id.value(), color));
+            for e in self.get_node(id).get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                edges.insert((id.min(neighbor_id), id.max(neighbor_id)));
+            }
+        }
+        let edges: Vec<(i64, i64, bool)> = edges
+            .into_iter()
+            .map(|(id1, id2)| {
+                let in_clique = clique.contains(&id1) && clique.contains(&id2);
+                (id1.value(), id2.value(), in_clique)
+            })
+            .collect();
+        format_dot(name, &nodes, &edges)
+    }
+}
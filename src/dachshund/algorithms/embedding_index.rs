@@ -0,0 +1,253 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::prelude::*;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+// token word2vec adds to its vocabulary for line ends, which isn't a node.
+const END_OF_SENTENCE: &str = "</s>";
+
+/// Reads node embeddings in the word2vec text format, as written by word2vec-style trainers
+/// run on the corpora of `RandomWalks::write_random_walks`: a header line with the number of
+/// vectors and their dimension, then one line per node, with its id followed by its vector.
+pub fn read_word2vec_embeddings<R: BufRead>(reader: R) -> CLQResult<HashMap<NodeId, Vec<f32>>> {
+    let mut lines = reader.lines();
+    let header = lines
+        .next()
+        .ok_or_else(|| CLQError::from("Embeddings are missing their header line"))??;
+    let dimension: usize = header
+        .split_whitespace()
+        .nth(1)
+        .and_then(|x| x.parse().ok())
+        .ok_or_else(|| CLQError::from(format!("Invalid embeddings header: {}", header)))?;
+    let mut embeddings: HashMap<NodeId, Vec<f32>> = HashMap::new();
+    for line in lines {
+        let line = line?;
+        let mut fields = line.split_whitespace();
+        let token = match fields.next() {
+            Some(END_OF_SENTENCE) | None => continue,
+            Some(token) => token,
+        };
+        let id: i64 = token
+            .parse()
+            .map_err(|_| CLQError::from(format!("Invalid node id in embeddings: {}", token)))?;
+        let vector = fields
+            .map(|x| x.parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()
+            .map_err(|_| CLQError::from(format!("Invalid embedding of node {}", id)))?;
+        if vector.len() != dimension {
+            return Err(CLQError::from(format!(
+                "Embedding of node {} has dimension {}, not {}",
+                id,
+                vector.len(),
+                dimension
+            )));
+        }
+        embeddings.insert(NodeId::from(id), vector);
+    }
+    Ok(embeddings)
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// the vector scaled to unit length, or None if it is 0 (or isn't finite).
+fn normalize(vector: &[f32]) -> Option<Vec<f32>> {
+    let norm = dot(vector, vector).sqrt();
+    if !(norm.is_finite() && norm > 0.0) {
+        return None;
+    }
+    Some(vector.iter().map(|x| x / norm).collect())
+}
+
+// a random vector of independent standard normal components (by the Box-Muller transform),
+// whose direction is uniformly distributed.
+fn get_random_normal<R: Rng>(dimension: usize, rng: &mut R) -> Vec<f32> {
+    (0..dimension)
+        .map(|_| {
+            let u: f32 = 1.0 - rng.gen::<f32>();
+            let v: f32 = rng.gen();
+            (-2.0 * u.ln()).sqrt() * (2.0 * std::f32::consts::PI * v).cos()
+        })
+        .collect()
+}
+
+/// Approximate nearest neighbor index over node embeddings, by cosine similarity. It uses
+/// random hyperplane locality-sensitive hashing (Charikar, 2002): each table hashes a vector
+/// to the sides of num_bits random hyperplanes it falls on, so that similar vectors tend to
+/// share a bucket. Queries only consider nodes in the same bucket as the query in some table,
+/// or in a bucket one hyperplane away, and rank those by their exact similarity. More tables
+/// find more of the true nearest neighbors, more bits make buckets smaller and queries faster.
+pub struct EmbeddingIndex {
+    dimension: usize,
+    ids: Vec<NodeId>,
+    positions: FxHashMap<NodeId, usize>,
+    // embeddings, scaled to unit length.
+    vectors: Vec<Vec<f32>>,
+    // normals of the hyperplanes of each table.
+    hyperplanes: Vec<Vec<Vec<f32>>>,
+    // positions of the nodes in each bucket of each table.
+    tables: Vec<FxHashMap<u64, Vec<usize>>>,
+}
+impl EmbeddingIndex {
+    pub fn new(
+        embeddings: HashMap<NodeId, Vec<f32>>,
+        num_tables: usize,
+        num_bits: usize,
+    ) -> CLQResult<Self> {
+        Self::new_with_rng(embeddings, num_tables, num_bits, &mut rand::thread_rng())
+    }
+
+    pub fn new_with_rng<R: Rng>(
+        embeddings: HashMap<NodeId, Vec<f32>>,
+        num_tables: usize,
+        num_bits: usize,
+        rng: &mut R,
+    ) -> CLQResult<Self> {
+        if num_tables == 0 {
+            return Err(CLQError::from("An embedding index needs at least 1 table"));
+        }
+        if !(1..=64).contains(&num_bits) {
+            return Err(CLQError::from(
+                "Embedding index hashes must have 1 to 64 bits",
+            ));
+        }
+        // sorted, so that indices built with the same rng are the same.
+        let mut embeddings: Vec<(NodeId, Vec<f32>)> = embeddings.into_iter().collect();
+        embeddings.sort_by_key(|(id, _)| *id);
+        let dimension = embeddings.first().map_or(0, |(_, vector)| vector.len());
+        let mut ids: Vec<NodeId> = Vec::with_capacity(embeddings.len());
+        let mut vectors: Vec<Vec<f32>> = Vec::with_capacity(embeddings.len());
+        for (id, vector) in embeddings {
+            if vector.len() != dimension {
+                return Err(CLQError::from(format!(
+                    "Embedding of node {} has dimension {}, not {}",
+                    id.value(),
+                    vector.len(),
+                    dimension
+                )));
+            }
+            let vector = normalize(&vector).ok_or_else(|| {
+                CLQError::from(format!("Embedding of node {} has no direction", id.value()))
+            })?;
+            ids.push(id);
+            vectors.push(vector);
+        }
+        let hyperplanes: Vec<Vec<Vec<f32>>> = (0..num_tables)
+            .map(|_| {
+                (0..num_bits)
+                    .map(|_| get_random_normal(dimension, rng))
+                    .collect()
+            })
+            .collect();
+        let mut index = Self {
+            dimension,
+            positions: ids.iter().enumerate().map(|(i, id)| (*id, i)).collect(),
+            ids,
+            vectors,
+            hyperplanes,
+            tables: vec![FxHashMap::default(); num_tables],
+        };
+        for i in 0..index.vectors.len() {
+            for t in 0..num_tables {
+                let hash = index.get_hash(t, &index.vectors[i]);
+                index.tables[t].entry(hash).or_default().push(i);
+            }
+        }
+        Ok(index)
+    }
+
+    // bucket of the vector in table t: bit i is set if it is on the positive side of the
+    // i-th hyperplane.
+    fn get_hash(&self, t: usize, vector: &[f32]) -> u64 {
+        self.hyperplanes[t]
+            .iter()
+            .enumerate()
+            .filter(|(_, normal)| dot(normal, vector) >= 0.0)
+            .fold(0, |hash, (i, _)| hash | (1 << i))
+    }
+
+    /// number of nodes in the index.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.positions.contains_key(&id)
+    }
+
+    /// cosine similarity of the embeddings of two nodes, or None if either isn't indexed.
+    pub fn get_similarity(&self, a: NodeId, b: NodeId) -> Option<f32> {
+        let a = self.positions.get(&a)?;
+        let b = self.positions.get(&b)?;
+        Some(dot(&self.vectors[*a], &self.vectors[*b]))
+    }
+
+    // (up to) k nodes most similar to the (unit length) query, among those sharing a bucket
+    // with it, or one bit away, in some table, leaving out excluded ones.
+    fn search(&self, query: &[f32], k: usize, excluded: &FxHashSet<NodeId>) -> Vec<(NodeId, f32)> {
+        let mut seen: FxHashSet<usize> = FxHashSet::default();
+        for (t, table) in self.tables.iter().enumerate() {
+            let hash = self.get_hash(t, query);
+            let probes = std::iter::once(hash)
+                .chain((0..self.hyperplanes[t].len()).map(|i| hash ^ (1 << i)));
+            for probe in probes {
+                if let Some(bucket) = table.get(&probe) {
+                    seen.extend(bucket.iter().copied());
+                }
+            }
+        }
+        let mut suggestions: Vec<(NodeId, f32)> = seen
+            .into_iter()
+            .filter(|i| !excluded.contains(&self.ids[*i]))
+            .map(|i| (self.ids[i], dot(query, &self.vectors[i])))
+            .collect();
+        suggestions.sort_by(|(a_id, a), (b_id, b)| b.partial_cmp(a).unwrap().then(a_id.cmp(b_id)));
+        suggestions.truncate(k);
+        suggestions
+    }
+
+    /// (Up to) k nodes whose embeddings are most similar to that of node, with their cosine
+    /// similarities, from most to least similar. Being approximate, this may miss some of
+    /// the most similar nodes, or return fewer than k.
+    pub fn suggest_similar_nodes(&self, node: NodeId, k: usize) -> CLQResult<Vec<(NodeId, f32)>> {
+        self.suggest_similar_to_nodes(&[node], k)
+    }
+
+    /// (Up to) k other nodes whose embeddings are most similar to the mean direction of
+    /// those of nodes, with their cosine similarities to it, from most to least similar. Given
+    /// the nodes of a candidate, these are expansion candidates which, unlike the ones the
+    /// beam search considers, needn't be neighbors of any of its nodes.
+    pub fn suggest_similar_to_nodes(
+        &self,
+        nodes: &[NodeId],
+        k: usize,
+    ) -> CLQResult<Vec<(NodeId, f32)>> {
+        let mut centroid: Vec<f32> = vec![0.0; self.dimension];
+        for id in nodes {
+            let i = self
+                .positions
+                .get(id)
+                .ok_or_else(|| CLQError::from(format!("Node {} has no embedding", id.value())))?;
+            for (x, y) in centroid.iter_mut().zip(&self.vectors[*i]) {
+                *x += y;
+            }
+        }
+        let query = normalize(&centroid)
+            .ok_or_else(|| CLQError::from("The embeddings of the nodes have no mean direction"))?;
+        Ok(self.search(&query, k, &nodes.iter().copied().collect()))
+    }
+}
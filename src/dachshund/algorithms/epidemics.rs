@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use rand::prelude::*;
+
+/// What infected nodes become once they recover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpidemicModel {
+    // immune, for good.
+    SIR,
+    // susceptible again.
+    SIS,
+}
+
+/// Parameters of an epidemic simulation, in discrete steps: at each step, every infected
+/// node infects each of its susceptible neighbors with probability infection_rate, and
+/// then recovers with probability recovery_rate. Simulations stop once no node is
+/// infected, or after max_steps steps.
+#[derive(Clone, Debug)]
+pub struct EpidemicConfig {
+    pub model: EpidemicModel,
+    pub infection_rate: f64,
+    pub recovery_rate: f64,
+    pub max_steps: usize,
+}
+
+/// Number of nodes in each state after a step of a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpidemicCounts {
+    pub susceptible: usize,
+    pub infected: usize,
+    pub recovered: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+pub trait Epidemics: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Neighbors an infected node can infect. Undirected graphs can infect any neighbor;
+    // directed graphs override this.
+    fn get_epidemic_edges(&self, node: &Self::NodeType) -> Vec<NodeId> {
+        node.get_outgoing_edges()
+            .map(|e| e.get_neighbor_id())
+            .collect()
+    }
+
+    // Runs an SIR or SIS simulation from the given infected seeds, returning the number of
+    // nodes in each state at the start, and after each step. Errors if a rate isn't in
+    // [0, 1], or a seed is missing.
+    fn simulate_epidemic_with_rng<R: Rng>(
+        &self,
+        config: &EpidemicConfig,
+        seeds: &[NodeId],
+        rng: &mut R,
+    ) -> CLQResult<Vec<EpidemicCounts>> {
+        for rate in [config.infection_rate, config.recovery_rate] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(CLQError::from(format!(
+                    "Infection and recovery rates must be in [0, 1], got {}",
+                    rate
+                )));
+            }
+        }
+        let ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let edges: Vec<Vec<usize>> = ids
+            .iter()
+            .map(|id| {
+                self.get_epidemic_edges(self.get_node(*id))
+                    .into_iter()
+                    .filter(|neighbor_id| neighbor_id != id)
+                    .map(|neighbor_id| positions[&neighbor_id])
+                    .collect()
+            })
+            .collect();
+
+        let mut states: Vec<State> = vec![State::Susceptible; ids.len()];
+        let mut infected: Vec<usize> = Vec::new();
+        for seed in seeds {
+            let i = *positions
+                .get(seed)
+                .ok_or_else(|| CLQError::from(format!("No such node: {}", seed.value())))?;
+            if states[i] == State::Susceptible {
+                states[i] = State::Infected;
+                infected.push(i);
+            }
+        }
+        let mut num_recovered: usize = 0;
+        let counts = |num_infected: usize, num_recovered: usize| EpidemicCounts {
+            susceptible: ids.len() - num_infected - num_recovered,
+            infected: num_infected,
+            recovered: num_recovered,
+        };
+        let mut history: Vec<EpidemicCounts> = vec![counts(infected.len(), 0)];
+        for _ in 0..config.max_steps {
+            if infected.is_empty() {
+                break;
+            }
+            // nodes infected during a step only become infectious at the next one.
+            let mut newly_infected: Vec<usize> = Vec::new();
+            for i in &infected {
+                for j in &edges[*i] {
+                    if states[*j] == State::Susceptible && rng.gen::<f64>() < config.infection_rate
+                    {
+                        states[*j] = State::Infected;
+                        newly_infected.push(*j);
+                    }
+                }
+            }
+            infected.retain(|i| {
+                if rng.gen::<f64>() >= config.recovery_rate {
+                    return true;
+                }
+                states[*i] = match config.model {
+                    EpidemicModel::SIR => {
+                        num_recovered += 1;
+                        State::Recovered
+                    }
+                    EpidemicModel::SIS => State::Susceptible,
+                };
+                false
+            });
+            infected.extend(newly_infected);
+            history.push(counts(infected.len(), num_recovered));
+        }
+        Ok(history)
+    }
+
+    // Same as simulate_epidemic_with_rng, with random draws seeded by seed, so that runs
+    // are reproducible.
+    fn simulate_epidemic(
+        &self,
+        config: &EpidemicConfig,
+        seeds: &[NodeId],
+        seed: u64,
+    ) -> CLQResult<Vec<EpidemicCounts>> {
+        self.simulate_epidemic_with_rng(config, seeds, &mut StdRng::seed_from_u64(seed))
+    }
+}
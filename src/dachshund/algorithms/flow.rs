@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::VecDeque;
+
+// arcs crossing a cut, as (from, to, capacity).
+pub type CutArcs = Vec<(NodeId, NodeId, f64)>;
+
+// residual capacities at or below this are treated as saturated.
+const RESIDUAL_EPS: f64 = 1e-12;
+
+struct FlowArc {
+    to: usize,
+    residual: f64,
+}
+
+/// A flow network over nodes 0..n, with arcs of (non-negative) capacity, on which maximum
+/// flows are computed with Dinic's algorithm. Each arc is stored alongside its reverse
+/// (residual) arc: arc 2i is the i-th arc added, and arc 2i + 1 its reverse.
+pub struct FlowNetwork {
+    arcs: Vec<FlowArc>,
+    capacities: Vec<f64>,
+    adjacency: Vec<Vec<usize>>,
+}
+impl FlowNetwork {
+    pub fn new(num_nodes: usize) -> Self {
+        Self {
+            arcs: Vec::new(),
+            capacities: Vec::new(),
+            adjacency: vec![Vec::new(); num_nodes],
+        }
+    }
+    pub fn add_arc(&mut self, from: usize, to: usize, capacity: f64) {
+        self.adjacency[from].push(self.arcs.len());
+        self.arcs.push(FlowArc {
+            to,
+            residual: capacity,
+        });
+        self.adjacency[to].push(self.arcs.len());
+        self.arcs.push(FlowArc {
+            to: from,
+            residual: 0.0,
+        });
+        self.capacities.push(capacity);
+    }
+    /// (from, to, capacity, flow) for each arc, in the order in which arcs were added.
+    pub fn get_arc_flows(&self) -> Vec<(usize, usize, f64, f64)> {
+        self.capacities
+            .iter()
+            .enumerate()
+            .map(|(i, capacity)| {
+                let arc = &self.arcs[2 * i];
+                let from = self.arcs[2 * i + 1].to;
+                (from, arc.to, *capacity, capacity - arc.residual)
+            })
+            .collect()
+    }
+
+    // distance from source in the residual network (usize::MAX if unreachable).
+    fn get_levels(&self, source: usize) -> Vec<usize> {
+        let mut levels = vec![usize::MAX; self.adjacency.len()];
+        levels[source] = 0;
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        queue.push_back(source);
+        while let Some(u) = queue.pop_front() {
+            for &a in &self.adjacency[u] {
+                let arc = &self.arcs[a];
+                if arc.residual > RESIDUAL_EPS && levels[arc.to] == usize::MAX {
+                    levels[arc.to] = levels[u] + 1;
+                    queue.push_back(arc.to);
+                }
+            }
+        }
+        levels
+    }
+
+    // saturates the level graph with augmenting paths (a blocking flow), returning the
+    // flow added. Paths are explored with an explicit stack of arcs, from the source.
+    fn push_blocking_flow(&mut self, source: usize, sink: usize, levels: &mut [usize]) -> f64 {
+        let mut next_arc: Vec<usize> = vec![0; self.adjacency.len()];
+        let mut path: Vec<usize> = Vec::new();
+        let mut total: f64 = 0.0;
+        let mut u = source;
+        loop {
+            if u == sink {
+                let bottleneck = path
+                    .iter()
+                    .map(|a| self.arcs[*a].residual)
+                    .fold(f64::INFINITY, f64::min);
+                for &a in &path {
+                    self.arcs[a].residual -= bottleneck;
+                    self.arcs[a ^ 1].residual += bottleneck;
+                }
+                total += bottleneck;
+                path.clear();
+                u = source;
+                continue;
+            }
+            let mut advanced = false;
+            while next_arc[u] < self.adjacency[u].len() {
+                let a = self.adjacency[u][next_arc[u]];
+                let arc = &self.arcs[a];
+                if arc.residual > RESIDUAL_EPS && levels[arc.to] == levels[u] + 1 {
+                    path.push(a);
+                    u = arc.to;
+                    advanced = true;
+                    break;
+                }
+                next_arc[u] += 1;
+            }
+            if !advanced {
+                // dead end: nothing more can go through u in this phase.
+                levels[u] = usize::MAX;
+                match path.pop() {
+                    Some(a) => {
+                        u = self.arcs[a ^ 1].to;
+                        next_arc[u] += 1;
+                    }
+                    None => return total,
+                }
+            }
+        }
+    }
+
+    /// Dinic's algorithm, see https://en.wikipedia.org/wiki/Dinic%27s_algorithm.
+    /// Runs in O(V^2 E) time. Flows can be read off with get_arc_flows afterwards.
+    pub fn get_max_flow(&mut self, source: usize, sink: usize) -> f64 {
+        let mut total: f64 = 0.0;
+        if source == sink {
+            return total;
+        }
+        loop {
+            let mut levels = self.get_levels(source);
+            if levels[sink] == usize::MAX {
+                return total;
+            }
+            total += self.push_blocking_flow(source, sink, &mut levels);
+        }
+    }
+
+    /// Nodes reachable from source in the residual network. After a maximum flow has been
+    /// computed these make up the source side of a minimum cut.
+    pub fn get_source_side(&self, source: usize) -> Vec<bool> {
+        self.get_levels(source)
+            .into_iter()
+            .map(|level| level != usize::MAX)
+            .collect()
+    }
+}
+
+pub trait MaxFlow: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Arcs leaving a node, with their capacities. Unweighted graphs give every outgoing
+    // edge a capacity of 1; weighted and directed graphs override this.
+    fn get_flow_arcs(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_outgoing_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    // Builds the flow network for this graph, along with the node id of each of its nodes.
+    fn get_flow_network(&self) -> (FlowNetwork, Vec<NodeId>) {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut network = FlowNetwork::new(node_ids.len());
+        for (i, id) in node_ids.iter().enumerate() {
+            for (neighbor_id, capacity) in self.get_flow_arcs(self.get_node(*id)) {
+                network.add_arc(i, positions[&neighbor_id], capacity);
+            }
+        }
+        (network, node_ids)
+    }
+
+    // Computes a maximum flow from source to sink, returning its value along with the
+    // flow network (from which flows and the source side of a minimum cut can be read).
+    fn _get_max_flow(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+    ) -> CLQResult<(f64, FlowNetwork, Vec<NodeId>)> {
+        for id in [source, sink] {
+            if !self.has_node(id) {
+                return Err(CLQError::from(format!("No such node: {}", id.value())));
+            }
+        }
+        if source == sink {
+            return Err(CLQError::from("Source and sink must be distinct"));
+        }
+        let (mut network, node_ids) = self.get_flow_network();
+        let source_ix = node_ids.binary_search(&source).unwrap();
+        let sink_ix = node_ids.binary_search(&sink).unwrap();
+        let flow = network.get_max_flow(source_ix, sink_ix);
+        Ok((flow, network, node_ids))
+    }
+
+    // Value of a maximum flow from source to sink.
+    fn get_max_flow(&self, source: NodeId, sink: NodeId) -> CLQResult<f64> {
+        Ok(self._get_max_flow(source, sink)?.0)
+    }
+
+    // A minimum source-sink cut: its capacity (equal to the maximum flow) and the arcs
+    // crossing it, from the source side to the sink side, as (from, to, capacity).
+    fn get_min_cut(&self, source: NodeId, sink: NodeId) -> CLQResult<(f64, CutArcs)> {
+        let (flow, network, node_ids) = self._get_max_flow(source, sink)?;
+        let source_ix = node_ids.binary_search(&source).unwrap();
+        let source_side = network.get_source_side(source_ix);
+        let cut: CutArcs = network
+            .get_arc_flows()
+            .into_iter()
+            .filter(|(from, to, capacity, _flow)| {
+                source_side[*from] && !source_side[*to] && *capacity > 0.0
+            })
+            .map(|(from, to, capacity, _flow)| (node_ids[from], node_ids[to], capacity))
+            .collect();
+        Ok((flow, cut))
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate ordered_float;
+use crate::dachshund::graph_base::BipartiteGraphBase;
+use crate::dachshund::id_types::GraphId;
+use crate::dachshund::node::{Node, NodeEdgeBase};
+use crate::dachshund::row::CliqueRow;
+use crate::dachshund::typed_graph::LabeledGraph;
+use core::cmp::Reverse;
+use fxhash::FxHashMap;
+use ordered_float::NotNan;
+use priority_queue::PriorityQueue;
+
+// constant in the column weights, 1 / ln(degree + c), as suggested in the Fraudar paper.
+const COLUMN_WEIGHT_CONSTANT: f64 = 5.0;
+
+/// A dense block of a bipartite graph, found by `Fraudar::get_fraudar_block`: its core and
+/// non-core nodes (sorted by id), and its score, the total weight of its edges per node.
+#[derive(Debug)]
+pub struct DenseBlock {
+    pub core_ids: Vec<u32>,
+    pub non_core_ids: Vec<u32>,
+    pub score: f64,
+}
+impl DenseBlock {
+    /// encodes the block as clique rows of the given graph, e.g. to seed the beam search
+    /// with it (see `Beam::new`).
+    pub fn to_clique_rows<TGraph: LabeledGraph<NodeType = Node>>(
+        &self,
+        graph: &TGraph,
+        graph_id: GraphId,
+    ) -> Vec<CliqueRow> {
+        self.core_ids
+            .iter()
+            .chain(self.non_core_ids.iter())
+            .map(|id| {
+                CliqueRow::new(
+                    graph_id,
+                    graph.get_node_label(*id).unwrap(),
+                    graph.get_node(*id).non_core_type,
+                )
+            })
+            .collect()
+    }
+}
+
+pub trait Fraudar: BipartiteGraphBase<NodeType = Node> {
+    // Fraudar (Hooi et al., 2016): greedily peels the node whose remaining edges weigh the
+    // least, and returns the set of nodes, among those left after each removal, with the
+    // largest total edge weight per node. This is a 2-approximation of the densest block,
+    // in O(E log V) time, with no search. With column weighting, edges weigh
+    // 1 / ln(d + 5), where d is the degree of their non-core node, so that ties to popular
+    // non-core nodes (which camouflage, or legitimate activity, can't hide) count for less;
+    // otherwise every edge weighs 1. Edges of several types between two nodes each count.
+    fn get_fraudar_block(&self, column_weighting: bool) -> DenseBlock {
+        let ids: Vec<u32> = self
+            .get_core_ids()
+            .iter()
+            .chain(self.get_non_core_ids())
+            .cloned()
+            .collect();
+        let positions: FxHashMap<u32, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); ids.len()];
+        let mut total_weight: f64 = 0.0;
+        for id in self.get_core_ids() {
+            let i = positions[id];
+            for e in self.get_node(*id).edges.iter() {
+                let target_id = e.get_neighbor_id();
+                let j = match positions.get(&target_id) {
+                    Some(j) => *j,
+                    None => continue,
+                };
+                let weight = if column_weighting {
+                    let degree = self.get_node(target_id).edges.len() as f64;
+                    1.0 / (degree + COLUMN_WEIGHT_CONSTANT).ln()
+                } else {
+                    1.0
+                };
+                adjacency[i].push((j, weight));
+                adjacency[j].push((i, weight));
+                total_weight += weight;
+            }
+        }
+
+        // The priority of a node is the weight of its edges to nodes not yet removed.
+        let mut pq = PriorityQueue::with_capacity(ids.len());
+        for (i, edges) in adjacency.iter().enumerate() {
+            let weight: f64 = edges.iter().map(|(_, w)| w).sum();
+            pq.push(i, Reverse(NotNan::new(weight).unwrap()));
+        }
+        let mut removed: Vec<usize> = Vec::with_capacity(ids.len());
+        let mut best_score: f64 = if ids.is_empty() {
+            0.0
+        } else {
+            total_weight / ids.len() as f64
+        };
+        let mut best_num_removed: usize = 0;
+        while let Some((i, Reverse(weight))) = pq.pop() {
+            removed.push(i);
+            total_weight -= weight.into_inner();
+            for (j, w) in &adjacency[i] {
+                if let Some(Reverse(old_priority)) = pq.get_priority(j) {
+                    // guards against rounding errors taking priorities below 0.
+                    let new_priority: f64 = (old_priority.into_inner() - w).max(0.0);
+                    pq.change_priority(j, Reverse(NotNan::new(new_priority).unwrap()));
+                }
+            }
+            let num_left = ids.len() - removed.len();
+            if num_left > 0 && total_weight / num_left as f64 > best_score {
+                best_score = total_weight / num_left as f64;
+                best_num_removed = removed.len();
+            }
+        }
+
+        let mut in_block = vec![true; ids.len()];
+        for i in &removed[..best_num_removed] {
+            in_block[*i] = false;
+        }
+        let mut core_ids: Vec<u32> = Vec::new();
+        let mut non_core_ids: Vec<u32> = Vec::new();
+        for (i, id) in ids.iter().enumerate() {
+            if !in_block[i] {
+                continue;
+            }
+            if self.get_node(*id).is_core {
+                core_ids.push(*id);
+            } else {
+                non_core_ids.push(*id);
+            }
+        }
+        core_ids.sort_unstable();
+        non_core_ids.sort_unstable();
+        DenseBlock {
+            core_ids,
+            non_core_ids,
+            score: best_score,
+        }
+    }
+}
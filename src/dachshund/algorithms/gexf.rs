@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use fxhash::FxHashSet;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Node attributes written by `Gexf::to_gexf`, as (id, title), all of them integers.
+pub const GEXF_NODE_ATTRIBUTES: [(usize, &str); 3] =
+    [(0, "degree"), (1, "coreness"), (2, "community")];
+
+pub trait Gexf: Coreness + UndirectedGraph
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Edges of a node, with their weights. Every edge has a weight of 1 in unweighted
+    // graphs; weighted graphs override this.
+    fn get_gexf_edges(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    // The graph in GEXF 1.3 format, as read by Gephi, with nodes and edges in order of id,
+    // each edge listed once, with its weight. Nodes have the GEXF_NODE_ATTRIBUTES: their
+    // degree, coreness and community, looked up in communities, as returned by e.g.
+    // get_leiden_communities (left out for nodes belonging to none).
+    fn to_gexf(&self, communities: &HashMap<CommunityId, Community>) -> String {
+        let coreness = self.get_coreness_values();
+        let membership: HashMap<NodeId, CommunityId> = communities
+            .iter()
+            .flat_map(|(community_id, members)| members.iter().map(move |x| (*x, *community_id)))
+            .collect();
+        let mut edges: BTreeMap<(NodeId, NodeId), f64> = BTreeMap::new();
+        // writing to a String cannot fail.
+        let mut s = String::new();
+        s.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        s.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        s.push_str("  <graph defaultedgetype=\"undirected\">\n");
+        s.push_str("    <attributes class=\"node\">\n");
+        for (id, title) in GEXF_NODE_ATTRIBUTES {
+            writeln!(
+                s,
+                "      <attribute id=\"{}\" title=\"{}\" type=\"integer\"/>",
+                id, title
+            )
+            .unwrap();
+        }
+        s.push_str("    </attributes>\n");
+        s.push_str("    <nodes>\n");
+        for id in self.get_ordered_node_ids() {
+            let node = self.get_node(id);
+            writeln!(s, "      <node id=\"{0}\" label=\"{0}\">", id.value()).unwrap();
+            s.push_str("        <attvalues>\n");
+            let node_coreness = coreness.get(&id).copied().unwrap_or(0);
+            let mut values: Vec<(usize, usize)> = vec![(0, node.degree()), (1, node_coreness)];
+            if let Some(community_id) = membership.get(&id) {
+                values.push((2, *community_id));
+            }
+            for (attribute_id, value) in values {
+                writeln!(
+                    s,
+                    "          <attvalue for=\"{}\" value=\"{}\"/>",
+                    attribute_id, value
+                )
+                .unwrap();
+            }
+            s.push_str("        </attvalues>\n");
+            s.push_str("      </node>\n");
+            for (neighbor_id, weight) in self.get_gexf_edges(node) {
+                edges.insert((id.min(neighbor_id), id.max(neighbor_id)), weight);
+            }
+        }
+        s.push_str("    </nodes>\n");
+        s.push_str("    <edges>\n");
+        for (i, ((source, target), weight)) in edges.into_iter().enumerate() {
+            writeln!(
+                s,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\"/>",
+                i,
+                source.value(),
+                target.value(),
+                weight
+            )
+            .unwrap();
+        }
+        s.push_str("    </edges>\n");
+        s.push_str("  </graph>\n");
+        s.push_str("</gexf>\n");
+        s
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashSet;
+
+/// Structural change from one graph to another (see `GraphDiff::diff`): the nodes and edges
+/// only found in the other graph (added) or only in the first (removed), each sorted.
+#[derive(Debug, Default, PartialEq)]
+pub struct GraphDelta {
+    pub added_nodes: Vec<NodeId>,
+    pub removed_nodes: Vec<NodeId>,
+    pub added_edges: Vec<(NodeId, NodeId)>,
+    pub removed_edges: Vec<(NodeId, NodeId)>,
+}
+impl GraphDelta {
+    /// true if the two graphs have the same nodes and edges.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+// items of a which aren't in b, sorted.
+fn get_sorted_difference<T: Copy + Ord + std::hash::Hash>(
+    a: &FxHashSet<T>,
+    b: &FxHashSet<T>,
+) -> Vec<T> {
+    let mut difference: Vec<T> = a.difference(b).cloned().collect();
+    difference.sort();
+    difference
+}
+
+pub trait GraphDiff: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Edges of the graph, as pairs of node ids. Edges are taken to be undirected, and listed
+    // once, as (smaller id, larger id); directed graphs override this.
+    fn get_diff_edges(&self) -> FxHashSet<(NodeId, NodeId)> {
+        let mut edges: FxHashSet<(NodeId, NodeId)> = FxHashSet::default();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if id <= neighbor_id {
+                    edges.insert((id, neighbor_id));
+                }
+            }
+        }
+        edges
+    }
+
+    // Nodes and edges added and removed going from this graph to other, e.g. from one time
+    // window of a temporal graph to the next. Runs in time linear in the size of the two
+    // graphs. Edge weights are not compared.
+    fn diff(&self, other: &Self) -> GraphDelta {
+        let nodes: FxHashSet<NodeId> = self.get_ids_iter().cloned().collect();
+        let other_nodes: FxHashSet<NodeId> = other.get_ids_iter().cloned().collect();
+        let edges = self.get_diff_edges();
+        let other_edges = other.get_diff_edges();
+        GraphDelta {
+            added_nodes: get_sorted_difference(&other_nodes, &nodes),
+            removed_nodes: get_sorted_difference(&nodes, &other_nodes),
+            added_edges: get_sorted_difference(&other_edges, &edges),
+            removed_edges: get_sorted_difference(&edges, &other_edges),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+
+/// Connected graphlets on 2 to 4 nodes, numbered as in Przulj (2007): G0 (edge),
+/// G1 (3-path), G2 (triangle), G3 (4-path), G4 (3-star), G5 (4-cycle), G6 (paw, a triangle
+/// with a pendant edge), G7 (diamond, a 4-clique less one edge) and G8 (4-clique).
+pub const NUM_GRAPHLETS: usize = 9;
+/// Automorphism orbits of the graphlets above, i.e. the distinct positions a node can
+/// take in them: 0 (G0), 1-2 (G1: end, middle), 3 (G2), 4-5 (G3: end, middle), 6-7 (G4:
+/// leaf, center), 8 (G5), 9-11 (G6: pendant, degree 2, degree 3), 12-13 (G7: degree 2,
+/// degree 3) and 14 (G8).
+pub const NUM_ORBITS: usize = 15;
+pub type OrbitCounts = [usize; NUM_ORBITS];
+
+type AdjacencySets = FxHashMap<NodeId, FxHashSet<NodeId>>;
+
+// orbit of each node of the induced subgraph on nodes, given its degree in the subgraph.
+fn get_orbits(adjacency: &AdjacencySets, nodes: &[NodeId]) -> Vec<usize> {
+    let degrees: Vec<usize> = nodes
+        .iter()
+        .map(|x| nodes.iter().filter(|y| adjacency[x].contains(y)).count())
+        .collect();
+    let num_edges = degrees.iter().sum::<usize>() / 2;
+    let max_degree = *degrees.iter().max().unwrap();
+    degrees
+        .iter()
+        .map(
+            |degree| match (nodes.len(), num_edges, max_degree, degree) {
+                (3, 2, _, 1) => 1,
+                (3, 2, _, _) => 2,
+                (3, _, _, _) => 3,
+                (4, 3, 2, 1) => 4,
+                (4, 3, 2, _) => 5,
+                (4, 3, _, 1) => 6,
+                (4, 3, _, _) => 7,
+                (4, 4, 2, _) => 8,
+                (4, 4, _, 1) => 9,
+                (4, 4, _, 2) => 10,
+                (4, 4, _, _) => 11,
+                (4, 5, _, 2) => 12,
+                (4, 5, _, _) => 13,
+                _ => 14,
+            },
+        )
+        .collect()
+}
+
+// ESU (Wernicke, 2006): grows subgraph, whose smallest node is root, with nodes of
+// extension, so that every connected subgraph of up to 4 nodes is visited exactly once.
+fn extend_subgraph(
+    adjacency: &AdjacencySets,
+    root: NodeId,
+    subgraph: &mut Vec<NodeId>,
+    mut extension: Vec<NodeId>,
+    counts: &mut FxHashMap<NodeId, OrbitCounts>,
+) {
+    if subgraph.len() >= 3 {
+        for (id, orbit) in subgraph.iter().zip(get_orbits(adjacency, subgraph)) {
+            counts.get_mut(id).unwrap()[orbit] += 1;
+        }
+    }
+    if subgraph.len() == 4 {
+        return;
+    }
+    while let Some(w) = extension.pop() {
+        // nodes only reachable through w are left for this branch of the search.
+        let mut next_extension = extension.clone();
+        for u in &adjacency[&w] {
+            if *u > root
+                && !subgraph.contains(u)
+                && !subgraph.iter().any(|x| adjacency[x].contains(u))
+            {
+                next_extension.push(*u);
+            }
+        }
+        subgraph.push(w);
+        extend_subgraph(adjacency, root, subgraph, next_extension, counts);
+        subgraph.pop();
+    }
+}
+
+fn choose_2(n: usize) -> i64 {
+    (n * n.saturating_sub(1) / 2) as i64
+}
+
+pub trait Graphlets: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // neighbors of each node, leaving out self-loops.
+    fn get_adjacency_sets(&self) -> AdjacencySets {
+        self.get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                let neighbors: FxHashSet<NodeId> = node
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|neighbor_id| *neighbor_id != id)
+                    .collect();
+                (id, neighbors)
+            })
+            .collect()
+    }
+
+    // Number of times each node appears in each orbit (see NUM_ORBITS), i.e. its graphlet
+    // degree vector. Counted exactly by enumerating all connected induced subgraphs of 3
+    // and 4 nodes, which is only practical on small graphs: the number of such subgraphs
+    // grows with the cube of the degree of hubs.
+    fn get_orbit_counts(&self) -> FxHashMap<NodeId, OrbitCounts> {
+        let adjacency = self.get_adjacency_sets();
+        let mut counts: FxHashMap<NodeId, OrbitCounts> = adjacency
+            .iter()
+            .map(|(id, neighbors)| {
+                let mut orbit_counts = [0; NUM_ORBITS];
+                orbit_counts[0] = neighbors.len();
+                (*id, orbit_counts)
+            })
+            .collect();
+        for (id, neighbors) in &adjacency {
+            let extension: Vec<NodeId> = neighbors.iter().filter(|x| *x > id).copied().collect();
+            extend_subgraph(&adjacency, *id, &mut vec![*id], extension, &mut counts);
+        }
+        counts
+    }
+
+    // Number of (induced) occurrences of each graphlet in the graph (see NUM_GRAPHLETS).
+    // Rather than enumerating subgraphs, occurrences as (not necessarily induced)
+    // subgraphs are counted from degrees, triangles, 4-cliques and co-degrees, and
+    // induced counts derived from those, in O(sum of squared degrees) time.
+    fn get_graphlet_counts(&self) -> [usize; NUM_GRAPHLETS] {
+        let adjacency = self.get_adjacency_sets();
+        let degree = |id: &NodeId| adjacency[id].len();
+        let rank = |id: &NodeId| (degree(id), *id);
+        let higher_neighbors: AdjacencySets = adjacency
+            .iter()
+            .map(|(id, neighbors)| {
+                let higher: FxHashSet<NodeId> = neighbors
+                    .iter()
+                    .filter(|x| rank(x) > rank(id))
+                    .copied()
+                    .collect();
+                (*id, higher)
+            })
+            .collect();
+
+        let mut num_edges: i64 = 0;
+        let mut num_wedges: i64 = 0;
+        let mut num_stars: i64 = 0;
+        // paths with 3 edges, counting those closed into a triangle.
+        let mut num_walks: i64 = 0;
+        let mut num_triangles: i64 = 0;
+        let mut node_triangles: FxHashMap<NodeId, i64> = FxHashMap::default();
+        let mut num_diamonds: i64 = 0;
+        let mut num_cliques: i64 = 0;
+        for (id, neighbors) in &adjacency {
+            let d = neighbors.len() as i64;
+            num_wedges += d * (d - 1) / 2;
+            num_stars += d * (d - 1) * (d - 2) / 6;
+            for neighbor_id in neighbors.iter().filter(|x| *x > id) {
+                num_edges += 1;
+                num_walks += (d - 1) * (degree(neighbor_id) as i64 - 1);
+                let num_common = neighbors.intersection(&adjacency[neighbor_id]).count();
+                num_diamonds += choose_2(num_common);
+            }
+            for neighbor_id in &higher_neighbors[id] {
+                let common: Vec<&NodeId> = higher_neighbors[id]
+                    .intersection(&higher_neighbors[neighbor_id])
+                    .collect();
+                for third_id in &common {
+                    num_triangles += 1;
+                    for x in [id, neighbor_id, *third_id] {
+                        *node_triangles.entry(*x).or_insert(0) += 1;
+                    }
+                    num_cliques += common
+                        .iter()
+                        .filter(|x| higher_neighbors[*third_id].contains(x))
+                        .count() as i64;
+                }
+            }
+        }
+        let num_paths = num_walks - 3 * num_triangles;
+        let num_paws: i64 = node_triangles
+            .iter()
+            .map(|(id, t)| t * (degree(id) as i64 - 2))
+            .sum();
+        // each 4-cycle is found from both of its diagonals, from either end.
+        let mut num_cycles: i64 = 0;
+        for neighbors in adjacency.values() {
+            let mut codegrees: FxHashMap<NodeId, usize> = FxHashMap::default();
+            for neighbor_id in neighbors {
+                for x in &adjacency[neighbor_id] {
+                    *codegrees.entry(*x).or_insert(0) += 1;
+                }
+            }
+            num_cycles += codegrees.values().map(|c| choose_2(*c)).sum::<i64>();
+        }
+        // each node is its own co-neighbor through each of its neighbors.
+        num_cycles -= adjacency.values().map(|x| choose_2(x.len())).sum::<i64>();
+        num_cycles /= 4;
+
+        // subtract occurrences within larger graphlets on the same nodes.
+        let induced_diamonds = num_diamonds - 6 * num_cliques;
+        let induced_paws = num_paws - 4 * induced_diamonds - 12 * num_cliques;
+        let induced_cycles = num_cycles - induced_diamonds - 3 * num_cliques;
+        let induced_stars = num_stars - induced_paws - 2 * induced_diamonds - 4 * num_cliques;
+        let induced_paths = num_paths
+            - 4 * induced_cycles
+            - 2 * induced_paws
+            - 6 * induced_diamonds
+            - 12 * num_cliques;
+        [
+            num_edges,
+            num_wedges - 3 * num_triangles,
+            num_triangles,
+            induced_paths,
+            induced_stars,
+            induced_cycles,
+            induced_paws,
+            induced_diamonds,
+            num_cliques,
+        ]
+        .map(|x| x as usize)
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::HashMap;
+
+pub trait Hits: GraphBase
+where
+    Self::NodeType: DirectedNodeBase,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Hubs and authorities, see https://www.cs.cornell.edu/home/kleinber/auth.pdf.
+    // Power iteration: a node's authority score is the sum of the hub scores of its
+    // in-neighbors, and its hub score the sum of the authority scores of its out-neighbors.
+    // Both are normalized to sum to 1. Iterates until the L1 distance between successive
+    // hub vectors drops below eps, or max_iter iterations have been run.
+    // Returns (hub scores, authority scores).
+    fn get_hits(&self, eps: f64, max_iter: usize) -> (HashMap<NodeId, f64>, HashMap<NodeId, f64>) {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let out_neighbors: Vec<Vec<usize>> = node_ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_out_neighbors()
+                    .map(|e| positions[&e.get_neighbor_id()])
+                    .collect()
+            })
+            .collect();
+
+        let normalize = |x: &mut Vec<f64>| {
+            let total: f64 = x.iter().sum();
+            if total > 0.0 {
+                x.iter_mut().for_each(|v| *v /= total);
+            }
+        };
+        let mut hubs: Vec<f64> = vec![1.0 / n as f64; n];
+        let mut authorities: Vec<f64> = vec![0.0; n];
+        for _ in 0..max_iter {
+            authorities = vec![0.0; n];
+            for (i, neighbors) in out_neighbors.iter().enumerate() {
+                for j in neighbors {
+                    authorities[*j] += hubs[i];
+                }
+            }
+            normalize(&mut authorities);
+            let mut new_hubs: Vec<f64> = out_neighbors
+                .iter()
+                .map(|neighbors| neighbors.iter().map(|j| authorities[*j]).sum())
+                .collect();
+            normalize(&mut new_hubs);
+            let diff: f64 = new_hubs
+                .iter()
+                .zip(hubs.iter())
+                .map(|(x, y)| (x - y).abs())
+                .sum();
+            hubs = new_hubs;
+            if diff < eps {
+                break;
+            }
+        }
+        (
+            node_ids.iter().copied().zip(hubs).collect(),
+            node_ids.into_iter().zip(authorities).collect(),
+        )
+    }
+}
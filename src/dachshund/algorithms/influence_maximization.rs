@@ -0,0 +1,212 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use ordered_float::NotNan;
+use rand::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Seeds chosen by `InfluenceMaximization::get_influence_maximizing_seeds`, in the order
+/// they were chosen, with the estimated number of nodes a cascade started from all of them
+/// reaches (seeds included), and the estimated gain in spread each seed brought.
+#[derive(Debug)]
+pub struct InfluenceEstimate {
+    pub seeds: Vec<NodeId>,
+    pub spread: f64,
+    pub gains: Vec<f64>,
+}
+
+// Independent cascade simulations over a graph whose nodes are numbered by position.
+struct CascadeSimulator {
+    ids: Vec<NodeId>,
+    positions: FxHashMap<NodeId, usize>,
+    edges: Vec<Vec<usize>>,
+    probability: f64,
+    // round in which each node was last activated, so that it needn't be reset.
+    activated: Vec<usize>,
+    round: usize,
+}
+impl CascadeSimulator {
+    // Number of nodes activated by a cascade from seeds: each newly activated node gets one
+    // chance to activate each of its inactive neighbors, with the given probability.
+    fn simulate<R: Rng>(&mut self, seeds: &[usize], rng: &mut R) -> usize {
+        self.round += 1;
+        let mut frontier: Vec<usize> = Vec::new();
+        for seed in seeds {
+            if self.activated[*seed] != self.round {
+                self.activated[*seed] = self.round;
+                frontier.push(*seed);
+            }
+        }
+        let mut num_activated = frontier.len();
+        while let Some(i) = frontier.pop() {
+            for j in &self.edges[i] {
+                if self.activated[*j] != self.round && rng.gen::<f64>() < self.probability {
+                    self.activated[*j] = self.round;
+                    frontier.push(*j);
+                    num_activated += 1;
+                }
+            }
+        }
+        num_activated
+    }
+
+    fn estimate_spread<R: Rng>(
+        &mut self,
+        seeds: &[usize],
+        num_simulations: usize,
+        rng: &mut R,
+    ) -> f64 {
+        let total: usize = (0..num_simulations)
+            .map(|_| self.simulate(seeds, rng))
+            .sum();
+        total as f64 / num_simulations as f64
+    }
+}
+
+// A simulator for cascades over graph in which each edge activates with the given
+// probability. Errors unless it is in [0, 1].
+fn get_cascade_simulator<G>(graph: &G, probability: f64) -> CLQResult<CascadeSimulator>
+where
+    G: InfluenceMaximization,
+    G::NodeType: NodeBase<NodeIdType = NodeId>,
+    <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    if !(0.0..=1.0).contains(&probability) {
+        return Err(CLQError::from(format!(
+            "Activation probabilities must be in [0, 1], got {}",
+            probability
+        )));
+    }
+    let ids = graph.get_ordered_node_ids();
+    let positions: FxHashMap<NodeId, usize> =
+        ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let edges: Vec<Vec<usize>> = ids
+        .iter()
+        .map(|id| {
+            graph
+                .get_influence_edges(graph.get_node(*id))
+                .into_iter()
+                .filter(|neighbor_id| neighbor_id != id)
+                .map(|neighbor_id| positions[&neighbor_id])
+                .collect()
+        })
+        .collect();
+    Ok(CascadeSimulator {
+        activated: vec![0; ids.len()],
+        ids,
+        positions,
+        edges,
+        probability,
+        round: 0,
+    })
+}
+
+pub trait InfluenceMaximization: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Neighbors a node can activate. Undirected graphs can activate any neighbor; directed
+    // graphs override this.
+    fn get_influence_edges(&self, node: &Self::NodeType) -> Vec<NodeId> {
+        node.get_outgoing_edges()
+            .map(|e| e.get_neighbor_id())
+            .collect()
+    }
+
+    // Expected number of nodes reached by an independent cascade (Kempe, Kleinberg &
+    // Tardos, 2003) from the given seeds, in which each edge activates with the given
+    // probability, estimated over num_simulations Monte-Carlo runs. Errors on missing
+    // seeds.
+    fn estimate_influence_spread_with_rng<R: Rng>(
+        &self,
+        seeds: &[NodeId],
+        probability: f64,
+        num_simulations: usize,
+        rng: &mut R,
+    ) -> CLQResult<f64> {
+        let mut simulator = get_cascade_simulator(self, probability)?;
+        let positions: Vec<usize> = seeds
+            .iter()
+            .map(|id| {
+                simulator
+                    .positions
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| CLQError::from(format!("No such node: {}", id.value())))
+            })
+            .collect::<CLQResult<Vec<usize>>>()?;
+        Ok(simulator.estimate_spread(&positions, num_simulations.max(1), rng))
+    }
+
+    // Greedy influence maximization under the independent cascade model: picks num_seeds
+    // nodes (or all of them, in smaller graphs), one at a time, each with the largest
+    // estimated gain in spread (see estimate_influence_spread_with_rng), which is within
+    // 1 - 1/e of the best spread of any seed set of that size. Gains are evaluated lazily,
+    // as in CELF (Leskovec et al., 2007): as they can only shrink as seeds are added,
+    // a node whose gain, computed in an earlier round, is still the largest needn't have it
+    // computed again. Ties go to the smallest id.
+    fn get_influence_maximizing_seeds_with_rng<R: Rng>(
+        &self,
+        num_seeds: usize,
+        probability: f64,
+        num_simulations: usize,
+        rng: &mut R,
+    ) -> CLQResult<InfluenceEstimate> {
+        let mut simulator = get_cascade_simulator(self, probability)?;
+        let num_simulations = num_simulations.max(1);
+        let n = simulator.ids.len();
+        // (gain, smaller position first, number of seeds it was computed with).
+        let mut heap: BinaryHeap<(NotNan<f64>, Reverse<usize>, usize)> = BinaryHeap::new();
+        for i in 0..n {
+            let gain = simulator.estimate_spread(&[i], num_simulations, rng);
+            heap.push((NotNan::new(gain).unwrap(), Reverse(i), 0));
+        }
+        let mut seeds: Vec<usize> = Vec::new();
+        let mut gains: Vec<f64> = Vec::new();
+        let mut spread: f64 = 0.0;
+        while seeds.len() < num_seeds.min(n) {
+            let (gain, Reverse(i), computed_with) = heap.pop().unwrap();
+            if computed_with == seeds.len() {
+                seeds.push(i);
+                gains.push(gain.into_inner());
+                spread += gain.into_inner();
+                continue;
+            }
+            seeds.push(i);
+            let new_spread = simulator.estimate_spread(&seeds, num_simulations, rng);
+            seeds.pop();
+            let new_gain = (new_spread - spread).max(0.0);
+            heap.push((NotNan::new(new_gain).unwrap(), Reverse(i), seeds.len()));
+        }
+        Ok(InfluenceEstimate {
+            seeds: seeds.into_iter().map(|i| simulator.ids[i]).collect(),
+            spread,
+            gains,
+        })
+    }
+
+    fn get_influence_maximizing_seeds(
+        &self,
+        num_seeds: usize,
+        probability: f64,
+        num_simulations: usize,
+    ) -> CLQResult<InfluenceEstimate> {
+        self.get_influence_maximizing_seeds_with_rng(
+            num_seeds,
+            probability,
+            num_simulations,
+            &mut StdRng::seed_from_u64(0),
+        )
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use rand::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+pub trait LabelPropagation: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Neighbors whose labels a node considers, with the weight of their vote.
+    // Unweighted graphs give every neighbor the same weight; weighted graphs override this.
+    fn get_label_propagation_neighbors(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    // Asynchronous label propagation, see https://arxiv.org/abs/0709.2938.
+    // Every node starts with its own label. At each iteration nodes are visited in a random
+    // order, and each adopts the label with the largest total weight among its neighbors,
+    // keeping its current label if that is one of the best. Other ties are broken at random.
+    // Stops when no node changes label, or after max_iter iterations. Runs in time linear in
+    // the number of edges per iteration; the same seed always yields the same communities.
+    // Communities are numbered in order of their smallest node id.
+    fn get_label_propagation_communities(
+        &self,
+        seed: u64,
+        max_iter: usize,
+    ) -> HashMap<CommunityId, Community> {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: Vec<Vec<(usize, f64)>> = node_ids
+            .iter()
+            .map(|id| {
+                self.get_label_propagation_neighbors(self.get_node(*id))
+                    .into_iter()
+                    .filter(|(neighbor_id, _w)| neighbor_id != id)
+                    .map(|(neighbor_id, w)| (positions[&neighbor_id], w))
+                    .collect()
+            })
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        for _ in 0..max_iter {
+            order.shuffle(&mut rng);
+            let mut changed = false;
+            for &v in &order {
+                if neighbors[v].is_empty() {
+                    continue;
+                }
+                // BTreeMap, so that ties are listed in a deterministic order.
+                let mut votes: BTreeMap<usize, f64> = BTreeMap::new();
+                for (u, w) in &neighbors[v] {
+                    *votes.entry(labels[*u]).or_insert(0.0) += w;
+                }
+                let best_vote = votes.values().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if votes.get(&labels[v]) == Some(&best_vote) {
+                    continue;
+                }
+                let best_labels: Vec<usize> = votes
+                    .into_iter()
+                    .filter(|(_label, vote)| *vote == best_vote)
+                    .map(|(label, _vote)| label)
+                    .collect();
+                labels[v] = *best_labels.choose(&mut rng).unwrap();
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut by_label: BTreeMap<usize, Community> = BTreeMap::new();
+        for (v, label) in labels.into_iter().enumerate() {
+            by_label.entry(label).or_default().insert(node_ids[v]);
+        }
+        let mut communities: Vec<Community> = by_label.into_values().collect();
+        communities.sort_by_key(|c| *c.iter().min().unwrap());
+        communities.into_iter().enumerate().collect()
+    }
+}
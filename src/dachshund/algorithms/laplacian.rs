@@ -9,9 +9,45 @@ use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
 use nalgebra::{DMatrix, DVector};
+use std::collections::{BTreeMap, HashMap};
 
 type GraphMatrix = DMatrix<f64>;
 
+/// The graph Laplacian in compressed sparse row form, for graphs too large for
+/// get_laplacian_matrix (which takes memory quadratic in the number of nodes). Row and
+/// column i stand for node ids[i]; the entries of row i are values[indptr[i]..indptr[i + 1]],
+/// in the columns given by the same range of indices, in increasing order.
+pub struct SparseLaplacian {
+    pub ids: Vec<NodeId>,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+}
+impl SparseLaplacian {
+    pub fn num_rows(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let row = self.indptr[i]..self.indptr[i + 1];
+        match self.indices[row.clone()].binary_search(&j) {
+            Ok(pos) => self.values[row.start + pos],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// product of the Laplacian with vector x.
+    pub fn multiply(&self, x: &[f64]) -> Vec<f64> {
+        (0..self.num_rows())
+            .map(|i| {
+                (self.indptr[i]..self.indptr[i + 1])
+                    .map(|pos| self.values[pos] * x[self.indices[pos]])
+                    .sum()
+            })
+            .collect()
+    }
+}
+
 pub trait Laplacian: GraphBase + AdjacencyMatrix
 where
     Self::NodeType: NodeBase<NodeIdType = NodeId>,
@@ -33,4 +69,33 @@ where
         let adj_mat = self.get_adjacency_matrix_given_node_ids(&node_ids);
         (deg_mat - adj_mat, node_ids)
     }
+    // Same entries as get_laplacian_matrix, in memory linear in the number of edges.
+    fn get_sparse_laplacian_matrix(&self) -> SparseLaplacian {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut indptr: Vec<usize> = vec![0];
+        let mut indices: Vec<usize> = Vec::new();
+        let mut values: Vec<f64> = Vec::new();
+        for (i, node_id) in node_ids.iter().enumerate() {
+            let node = self.get_node(*node_id);
+            let mut row: BTreeMap<usize, f64> = BTreeMap::new();
+            row.insert(i, node.degree() as f64);
+            for e in node.get_edges() {
+                *row.entry(positions[&e.get_neighbor_id()]).or_insert(0.0) -= 1.0;
+            }
+            indices.extend(row.keys());
+            values.extend(row.values());
+            indptr.push(indices.len());
+        }
+        SparseLaplacian {
+            ids: node_ids,
+            indptr,
+            indices,
+            values,
+        }
+    }
 }
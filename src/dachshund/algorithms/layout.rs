@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+// how far nodes can move in the first iteration (as a fraction of the layout's side). This
+// "temperature" then cools down linearly to 0.
+const INITIAL_TEMPERATURE: f64 = 0.1;
+// distance below which two nodes are considered to be on top of each other.
+const MIN_DISTANCE: f64 = 1e-9;
+
+pub trait ForceLayout: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Fruchterman-Reingold force-directed layout: all pairs of nodes repel each other with a
+    // force of k^2 / d, and nodes joined by an edge attract each other with a force of
+    // d^2 / k, where k = sqrt(1 / n) is the ideal distance between neighbors. Nodes start
+    // at random positions in the unit square and stay within it. Each iteration takes
+    // O(V^2 + E) time. Returns (x, y) coordinates for each node.
+    fn get_layout(&self, num_iterations: usize) -> HashMap<NodeId, (f64, f64)> {
+        self.get_layout_with_rng(num_iterations, &mut rand::thread_rng())
+    }
+
+    // Force-directed layout, with initial positions drawn from the given random number
+    // generator.
+    fn get_layout_with_rng<R: Rng>(
+        &self,
+        num_iterations: usize,
+        rng: &mut R,
+    ) -> HashMap<NodeId, (f64, f64)> {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for (i, id) in node_ids.iter().enumerate() {
+            for e in self.get_node(*id).get_edges() {
+                let j = positions[&e.get_neighbor_id()];
+                if i < j {
+                    edges.push((i, j));
+                }
+            }
+        }
+        // edges of directed graphs are seen from both ends.
+        edges.sort_unstable();
+        edges.dedup();
+        let mut coords: Vec<(f64, f64)> = (0..n).map(|_| (rng.gen(), rng.gen())).collect();
+        let k = (1.0 / n.max(1) as f64).sqrt();
+
+        for iteration in 0..num_iterations {
+            let mut displacements: Vec<(f64, f64)> = vec![(0.0, 0.0); n];
+            for i in 0..n {
+                for j in i + 1..n {
+                    let (dx, dy) = (coords[i].0 - coords[j].0, coords[i].1 - coords[j].1);
+                    let d = (dx * dx + dy * dy).sqrt().max(MIN_DISTANCE);
+                    let force = k * k / d;
+                    displacements[i].0 += dx / d * force;
+                    displacements[i].1 += dy / d * force;
+                    displacements[j].0 -= dx / d * force;
+                    displacements[j].1 -= dy / d * force;
+                }
+            }
+            for (i, j) in &edges {
+                let (dx, dy) = (coords[*i].0 - coords[*j].0, coords[*i].1 - coords[*j].1);
+                let d = (dx * dx + dy * dy).sqrt().max(MIN_DISTANCE);
+                let force = d * d / k;
+                displacements[*i].0 -= dx / d * force;
+                displacements[*i].1 -= dy / d * force;
+                displacements[*j].0 += dx / d * force;
+                displacements[*j].1 += dy / d * force;
+            }
+            let temperature =
+                INITIAL_TEMPERATURE * (1.0 - iteration as f64 / num_iterations as f64);
+            for (coord, (dx, dy)) in coords.iter_mut().zip(displacements) {
+                let d = (dx * dx + dy * dy).sqrt().max(MIN_DISTANCE);
+                let step = d.min(temperature);
+                coord.0 = (coord.0 + dx / d * step).clamp(0.0, 1.0);
+                coord.1 = (coord.1 + dy / d * step).clamp(0.0, 1.0);
+            }
+        }
+        node_ids.into_iter().zip(coords).collect()
+    }
+}
@@ -0,0 +1,424 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::algorithms::dendrogram::Dendrogram;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+// gains smaller than this are treated as ties, to avoid moving nodes back and forth
+// because of floating point noise.
+const LEIDEN_EPSILON: f64 = 1e-12;
+
+/// Weighted graph the Leiden algorithm operates on. At the first level each node is a node
+/// of the original graph; at subsequent levels each node is a community of the previous level.
+pub struct LeidenAggregateGraph {
+    // (neighbor, edge weight), self loops excluded
+    adjacency: Vec<Vec<(usize, f64)>>,
+    // weight of self loops (i.e. of edges internal to the aggregated node)
+    self_loops: Vec<f64>,
+    // weighted degree, with self loops counted twice
+    degrees: Vec<f64>,
+    // original nodes aggregated into each node
+    members: Vec<Vec<NodeId>>,
+    // twice the total edge weight (2m in the modularity formula)
+    total_weight: f64,
+}
+impl LeidenAggregateGraph {
+    fn count_nodes(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn get_modularity(&self, partition: &[usize], resolution: f64) -> f64 {
+        let mut internal: FxHashMap<usize, f64> = FxHashMap::default();
+        let mut totals: FxHashMap<usize, f64> = FxHashMap::default();
+        for v in 0..self.count_nodes() {
+            let c = partition[v];
+            *totals.entry(c).or_insert(0.0) += self.degrees[v];
+            let mut inside = 2.0 * self.self_loops[v];
+            for (u, w) in &self.adjacency[v] {
+                if partition[*u] == c {
+                    inside += w;
+                }
+            }
+            *internal.entry(c).or_insert(0.0) += inside;
+        }
+        totals
+            .iter()
+            .map(|(c, tot)| {
+                internal[c] / self.total_weight
+                    - resolution * (tot / self.total_weight) * (tot / self.total_weight)
+            })
+            .sum()
+    }
+
+    // Fast local moving phase: nodes are visited from a queue and moved to the neighboring
+    // community with the largest modularity gain. Only neighbors of moved nodes are revisited.
+    fn move_nodes(&self, partition: &mut [usize], resolution: f64) {
+        let n = self.count_nodes();
+        let mut totals: Vec<f64> = vec![0.0; n];
+        let mut sizes: Vec<usize> = vec![0; n];
+        for v in 0..n {
+            totals[partition[v]] += self.degrees[v];
+            sizes[partition[v]] += 1;
+        }
+        let mut empty: Vec<usize> = (0..n).filter(|c| sizes[*c] == 0).rev().collect();
+        let mut queue: VecDeque<usize> = (0..n).collect();
+        let mut in_queue: Vec<bool> = vec![true; n];
+
+        while let Some(v) = queue.pop_front() {
+            in_queue[v] = false;
+            let k_v = self.degrees[v];
+            let old = partition[v];
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (u, w) in &self.adjacency[v] {
+                *links.entry(partition[*u]).or_insert(0.0) += w;
+            }
+            totals[old] -= k_v;
+            sizes[old] -= 1;
+
+            let mut best = old;
+            let mut best_gain = links.get(&old).unwrap_or(&0.0)
+                - resolution * k_v * totals[old] / self.total_weight;
+            for (c, w) in &links {
+                let gain = w - resolution * k_v * totals[*c] / self.total_weight;
+                if gain > best_gain + LEIDEN_EPSILON {
+                    best = *c;
+                    best_gain = gain;
+                }
+            }
+            // moving to an empty community has a gain of 0.
+            if sizes[old] > 0 && best_gain < -LEIDEN_EPSILON {
+                best = empty.pop().unwrap();
+            }
+            if sizes[old] == 0 && best != old {
+                empty.push(old);
+            }
+            totals[best] += k_v;
+            sizes[best] += 1;
+            partition[v] = best;
+
+            if best != old {
+                for (u, _w) in &self.adjacency[v] {
+                    if partition[*u] != best && !in_queue[*u] {
+                        queue.push_back(*u);
+                        in_queue[*u] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    // Refinement phase: within each community of the partition, starts from singletons and
+    // greedily merges well-connected nodes into well-connected sub-communities. This is what
+    // guarantees that the communities found by Leiden are connected.
+    fn refine_partition(&self, partition: &[usize], resolution: f64) -> Vec<usize> {
+        let n = self.count_nodes();
+        let mut refined: Vec<usize> = (0..n).collect();
+        let mut refined_totals: Vec<f64> = self.degrees.clone();
+        let mut is_singleton: Vec<bool> = vec![true; n];
+
+        let mut community_totals: FxHashMap<usize, f64> = FxHashMap::default();
+        for (c, k) in partition.iter().zip(self.degrees.iter()) {
+            *community_totals.entry(*c).or_insert(0.0) += k;
+        }
+        // weight of edges between each refined community and the rest of its community.
+        let mut external: Vec<f64> = (0..n)
+            .map(|v| {
+                self.adjacency[v]
+                    .iter()
+                    .filter(|(u, _w)| partition[*u] == partition[v])
+                    .map(|(_u, w)| w)
+                    .sum()
+            })
+            .collect();
+
+        for v in 0..n {
+            if !is_singleton[refined[v]] {
+                continue;
+            }
+            let k_v = self.degrees[v];
+            let community_total = community_totals[&partition[v]];
+            if external[v] < resolution * k_v * (community_total - k_v) / self.total_weight {
+                continue;
+            }
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (u, w) in &self.adjacency[v] {
+                if partition[*u] == partition[v] {
+                    *links.entry(refined[*u]).or_insert(0.0) += w;
+                }
+            }
+            let mut best = refined[v];
+            let mut best_gain = 0.0;
+            for (r, w) in &links {
+                if *r == refined[v] {
+                    continue;
+                }
+                let r_total = refined_totals[*r];
+                if external[*r]
+                    < resolution * r_total * (community_total - r_total) / self.total_weight
+                {
+                    continue;
+                }
+                let gain = w - resolution * k_v * r_total / self.total_weight;
+                if gain > best_gain + LEIDEN_EPSILON {
+                    best = *r;
+                    best_gain = gain;
+                }
+            }
+            if best != refined[v] {
+                let old = refined[v];
+                refined_totals[old] -= k_v;
+                refined_totals[best] += k_v;
+                external[best] += external[old] - 2.0 * links[&best];
+                external[old] = 0.0;
+                is_singleton[best] = false;
+                refined[v] = best;
+            }
+        }
+        refined
+    }
+
+    // Builds the graph whose nodes are the communities of refined, along with the partition
+    // of that graph induced by partition (each refined community is a subset of a community).
+    fn aggregate(&self, refined: &[usize], partition: &[usize]) -> (Self, Vec<usize>) {
+        let n = self.count_nodes();
+        let mut new_ids: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut new_community_ids: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut new_partition: Vec<usize> = Vec::new();
+        for v in 0..n {
+            let next_id = new_ids.len();
+            if let std::collections::hash_map::Entry::Vacant(e) = new_ids.entry(refined[v]) {
+                e.insert(next_id);
+                let next_community_id = new_community_ids.len();
+                new_partition.push(
+                    *new_community_ids
+                        .entry(partition[v])
+                        .or_insert(next_community_id),
+                );
+            }
+        }
+        let num_new_nodes = new_ids.len();
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); num_new_nodes];
+        let mut self_loops: Vec<f64> = vec![0.0; num_new_nodes];
+        let mut degrees: Vec<f64> = vec![0.0; num_new_nodes];
+        let mut members: Vec<Vec<NodeId>> = vec![Vec::new(); num_new_nodes];
+        for v in 0..n {
+            let new_v = new_ids[&refined[v]];
+            self_loops[new_v] += self.self_loops[v];
+            degrees[new_v] += self.degrees[v];
+            members[new_v].extend(self.members[v].iter().copied());
+            for (u, w) in &self.adjacency[v] {
+                let new_u = new_ids[&refined[*u]];
+                if new_u == new_v {
+                    // each internal edge is seen from both of its endpoints.
+                    self_loops[new_v] += w / 2.0;
+                } else {
+                    *adjacency[new_v].entry(new_u).or_insert(0.0) += w;
+                }
+            }
+        }
+        (
+            LeidenAggregateGraph {
+                adjacency: adjacency
+                    .into_iter()
+                    .map(|neighbors| neighbors.into_iter().collect())
+                    .collect(),
+                self_loops,
+                degrees,
+                members,
+                total_weight: self.total_weight,
+            },
+            new_partition,
+        )
+    }
+
+    // Records in dendrogram the merges grouping nodes by groups, given the dendrogram id of
+    // each node, one node at a time into the first node of its group. Returns the dendrogram
+    // ids of the groups, in order of their first node, as numbered by aggregate.
+    fn add_merges(
+        &self,
+        groups: &[usize],
+        ids: &[CommunityId],
+        resolution: f64,
+        dendrogram: &mut Dendrogram,
+    ) -> Vec<CommunityId> {
+        let mut firsts: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut totals: FxHashMap<usize, f64> = FxHashMap::default();
+        let mut group_ids: Vec<CommunityId> = Vec::new();
+        for v in 0..self.count_nodes() {
+            let g = groups[v];
+            let k_v = self.degrees[v];
+            match firsts.get(&g) {
+                None => {
+                    firsts.insert(g, v);
+                    totals.insert(g, k_v);
+                    group_ids.push(ids[v]);
+                }
+                Some(first) => {
+                    // weight of the ties of v to the nodes of its group merged so far.
+                    let w: f64 = self.adjacency[v]
+                        .iter()
+                        .filter(|(u, _w)| *u < v && groups[*u] == g)
+                        .map(|(_u, w)| w)
+                        .sum();
+                    let total = totals.get_mut(&g).unwrap();
+                    let change = 2.0 * w / self.total_weight
+                        - 2.0 * resolution * *total * k_v / (self.total_weight * self.total_weight);
+                    dendrogram.add_merge(ids[v], ids[*first], change);
+                    *total += k_v;
+                }
+            }
+        }
+        group_ids
+    }
+
+    // Runs Leiden on the graph, returning the last level's aggregate graph and partition of
+    // it, the modularity change achieved at each level, and the dendrogram of the refined
+    // communities aggregated at each level, followed by the final partition.
+    fn run(self, resolution: f64) -> (LeidenAggregateGraph, Vec<usize>, Vec<f64>, Dendrogram) {
+        let mut graph = self;
+        let mut partition: Vec<usize> = (0..graph.count_nodes()).collect();
+        let mut modularity_changes: Vec<f64> = Vec::new();
+        let leaves: Vec<NodeId> = graph.members.iter().map(|members| members[0]).collect();
+        let mut dendrogram = Dendrogram::new(leaves, 0.0);
+        let mut ids: Vec<CommunityId> = partition.clone();
+        if graph.total_weight > 0.0 {
+            let mut modularity = graph.get_modularity(&partition, resolution);
+            dendrogram.initial_modularity = modularity;
+            loop {
+                graph.move_nodes(&mut partition, resolution);
+                let new_modularity = graph.get_modularity(&partition, resolution);
+                modularity_changes.push(new_modularity - modularity);
+                modularity = new_modularity;
+
+                let num_communities = partition.iter().collect::<HashSet<_>>().len();
+                if num_communities == graph.count_nodes() {
+                    break;
+                }
+                let refined = graph.refine_partition(&partition, resolution);
+                let (aggregate, aggregate_partition) = graph.aggregate(&refined, &partition);
+                if aggregate.count_nodes() == graph.count_nodes() {
+                    break;
+                }
+                ids = graph.add_merges(&refined, &ids, resolution, &mut dendrogram);
+                graph = aggregate;
+                partition = aggregate_partition;
+            }
+            graph.add_merges(&partition, &ids, resolution, &mut dendrogram);
+        }
+        (graph, partition, modularity_changes, dendrogram)
+    }
+}
+
+pub trait Leiden: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Leiden community detection, see https://arxiv.org/abs/1810.08473.
+    // Like the Louvain method, alternates local moving of nodes and aggregation of the graph,
+    // but refines communities before aggregating them, which guarantees that every community
+    // found is connected. Refinement merges are greedy, so results are deterministic.
+    // Returns communities in the same format as get_cnm_communities, along with the
+    // modularity change achieved at each level.
+    fn get_leiden_communities(&self) -> (HashMap<CommunityId, Community>, Vec<f64>) {
+        self.get_leiden_communities_with_resolution(1.0)
+    }
+
+    // Same as get_leiden_communities, with a resolution parameter scaling the null model
+    // term of modularity. Higher resolutions yield more, smaller communities.
+    fn get_leiden_communities_with_resolution(
+        &self,
+        resolution: f64,
+    ) -> (HashMap<CommunityId, Community>, Vec<f64>) {
+        let (graph, partition, modularity_changes, _) = self.init_leiden_graph().run(resolution);
+
+        let mut by_partition: BTreeMap<usize, Community> = BTreeMap::new();
+        for (v, c) in partition.iter().enumerate() {
+            by_partition
+                .entry(*c)
+                .or_default()
+                .extend(graph.members[v].iter().copied());
+        }
+        // number communities in order of their smallest node id.
+        let mut communities: Vec<Community> = by_partition.into_values().collect();
+        communities.sort_by_key(|c| *c.iter().min().unwrap());
+        (
+            communities.into_iter().enumerate().collect(),
+            modularity_changes,
+        )
+    }
+
+    // Every merge of communities made by get_leiden_communities: at each level, the nodes
+    // of each refined community are merged one at a time (and so are the last level's nodes
+    // into the final communities), so that cutting the dendrogram after any level's merges
+    // gives the communities aggregated at that level. Modularity changes are exact.
+    fn get_leiden_dendrogram(&self) -> Dendrogram {
+        self.get_leiden_dendrogram_with_resolution(1.0)
+    }
+
+    // Same as get_leiden_dendrogram, with modularity (and so the merges made) scaled by
+    // resolution, as in get_leiden_communities_with_resolution.
+    fn get_leiden_dendrogram_with_resolution(&self, resolution: f64) -> Dendrogram {
+        self.init_leiden_graph().run(resolution).3
+    }
+
+    // modularity of the given partition of the graph's nodes (each edge has weight 1).
+    fn get_modularity(&self, communities: &HashMap<CommunityId, Community>) -> f64 {
+        let graph = self.init_leiden_graph();
+        let mut positions: FxHashMap<NodeId, usize> = FxHashMap::default();
+        for (i, members) in graph.members.iter().enumerate() {
+            positions.insert(members[0], i);
+        }
+        let mut partition: Vec<usize> = vec![0; graph.count_nodes()];
+        for (c, community) in communities {
+            for id in community {
+                partition[positions[id]] = *c;
+            }
+        }
+        graph.get_modularity(&partition, 1.0)
+    }
+
+    fn init_leiden_graph(&self) -> LeidenAggregateGraph {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut adjacency: Vec<Vec<(usize, f64)>> = Vec::with_capacity(node_ids.len());
+        let mut self_loops: Vec<f64> = vec![0.0; node_ids.len()];
+        for (i, id) in node_ids.iter().enumerate() {
+            let mut neighbors: Vec<(usize, f64)> = Vec::new();
+            for e in self.get_node(*id).get_edges() {
+                let j = positions[&e.get_neighbor_id()];
+                if j == i {
+                    self_loops[i] += 1.0;
+                } else {
+                    neighbors.push((j, 1.0));
+                }
+            }
+            adjacency.push(neighbors);
+        }
+        let degrees: Vec<f64> = adjacency
+            .iter()
+            .zip(self_loops.iter())
+            .map(|(neighbors, self_loop)| neighbors.len() as f64 + 2.0 * self_loop)
+            .collect();
+        LeidenAggregateGraph {
+            adjacency,
+            self_loops,
+            total_weight: degrees.iter().sum(),
+            degrees,
+            members: node_ids.into_iter().map(|id| vec![id]).collect(),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+
+/// Similarity scores of pairs of nodes, used to predict missing or future links. See
+/// Liben-Nowell & Kleinberg (2007), "The link-prediction problem for social networks".
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkPredictionScore {
+    /// Number of neighbors the two nodes have in common.
+    CommonNeighbors,
+    /// Common neighbors, over the number of neighbors of either node.
+    Jaccard,
+    /// Sum of 1 / ln(degree) over common neighbors, so that rarer neighbors count for more.
+    AdamicAdar,
+    /// Product of the two nodes' degrees.
+    PreferentialAttachment,
+}
+
+type NeighborSets = FxHashMap<NodeId, FxHashSet<NodeId>>;
+
+fn get_score(
+    neighbors: &NeighborSets,
+    score: LinkPredictionScore,
+    id1: NodeId,
+    id2: NodeId,
+) -> f64 {
+    let (n1, n2) = (&neighbors[&id1], &neighbors[&id2]);
+    match score {
+        LinkPredictionScore::CommonNeighbors => n1.intersection(n2).count() as f64,
+        LinkPredictionScore::Jaccard => {
+            let num_common = n1.intersection(n2).count();
+            let num_union = n1.len() + n2.len() - num_common;
+            if num_union == 0 {
+                0.0
+            } else {
+                num_common as f64 / num_union as f64
+            }
+        }
+        // common neighbors have a degree of at least 2, so the log is positive.
+        LinkPredictionScore::AdamicAdar => n1
+            .intersection(n2)
+            .map(|x| 1.0 / (neighbors[x].len() as f64).ln())
+            .sum(),
+        LinkPredictionScore::PreferentialAttachment => (n1.len() * n2.len()) as f64,
+    }
+}
+
+pub trait LinkPrediction: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // neighbors of each node, leaving out self-loops.
+    fn get_link_prediction_neighbors(&self) -> NeighborSets {
+        self.get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                let neighbors: FxHashSet<NodeId> = node
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|neighbor_id| *neighbor_id != id)
+                    .collect();
+                (id, neighbors)
+            })
+            .collect()
+    }
+
+    // Scores of the given pairs of nodes, in the same order. Errors if any node is missing.
+    fn get_link_prediction_scores(
+        &self,
+        score: LinkPredictionScore,
+        pairs: &[(NodeId, NodeId)],
+    ) -> CLQResult<Vec<f64>> {
+        for (id1, id2) in pairs {
+            for id in [id1, id2] {
+                if !self.has_node(*id) {
+                    return Err(CLQError::from(format!("No such node: {}", id.value())));
+                }
+            }
+        }
+        let neighbors = self.get_link_prediction_neighbors();
+        Ok(pairs
+            .iter()
+            .map(|(id1, id2)| get_score(&neighbors, score, *id1, *id2))
+            .collect())
+    }
+
+    // All pairs of distinct, non-adjacent nodes scoring at least min_score, as (smaller id,
+    // larger id, score), ordered by ids. Unless min_score is positive (or scores are by
+    // preferential attachment), which restricts pairs to nodes with a common neighbor, this
+    // considers every pair of nodes, in O(V^2) time.
+    fn get_predicted_links(
+        &self,
+        score: LinkPredictionScore,
+        min_score: f64,
+    ) -> Vec<(NodeId, NodeId, f64)> {
+        let neighbors = self.get_link_prediction_neighbors();
+        let node_ids = self.get_ordered_node_ids();
+        let mut links: Vec<(NodeId, NodeId, f64)> = Vec::new();
+        let mut add_link = |id1: NodeId, id2: NodeId| {
+            let s = get_score(&neighbors, score, id1, id2);
+            if s >= min_score {
+                links.push((id1, id2, s));
+            }
+        };
+        if min_score > 0.0 && score != LinkPredictionScore::PreferentialAttachment {
+            for id in &node_ids {
+                let two_hop: FxHashSet<NodeId> = neighbors[id]
+                    .iter()
+                    .flat_map(|x| neighbors[x].iter())
+                    .filter(|x| *x > id && !neighbors[id].contains(x))
+                    .copied()
+                    .collect();
+                let mut two_hop: Vec<NodeId> = two_hop.into_iter().collect();
+                two_hop.sort();
+                for other in two_hop {
+                    add_link(*id, other);
+                }
+            }
+        } else {
+            for (i, id) in node_ids.iter().enumerate() {
+                for other in &node_ids[i + 1..] {
+                    if !neighbors[id].contains(other) {
+                        add_link(*id, *other);
+                    }
+                }
+            }
+        }
+        links
+    }
+}
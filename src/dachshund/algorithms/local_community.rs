@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+
+// cut / min(volume, total_volume - volume), None if either volume is 0.
+fn conductance(cut: f64, volume: f64, total_volume: f64) -> Option<f64> {
+    let denominator = volume.min(total_volume - volume);
+    if denominator > 0.0 {
+        Some(cut / denominator)
+    } else {
+        None
+    }
+}
+
+// Communities grown from seed nodes, for undirected graphs. Edges are weighted as for
+// PageRank (see get_pagerank_out_edges), and the volume of a set of nodes is the total
+// weight of their edges.
+pub trait LocalCommunity: PageRank
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // total weight of the edges of a node.
+    fn get_volume(&self, id: NodeId) -> f64 {
+        self.get_pagerank_out_edges(self.get_node(id))
+            .iter()
+            .map(|(_, w)| w.max(0.0))
+            .sum()
+    }
+
+    // Conductance of a set of nodes: the total weight of the edges leaving it, over the
+    // smaller of its volume and that of the rest of the graph. Returns an error if a node
+    // isn't in the graph, or if either volume is 0 (e.g. for the whole graph).
+    fn get_conductance(&self, nodes: &[NodeId]) -> CLQResult<f64> {
+        let set: FxHashSet<NodeId> = nodes.iter().cloned().collect();
+        let mut volume: f64 = 0.0;
+        let mut cut: f64 = 0.0;
+        for id in &set {
+            if !self.has_node(*id) {
+                return Err(CLQError::from(format!("No such node: {}", id.value())));
+            }
+            for (target, w) in self.get_pagerank_out_edges(self.get_node(*id)) {
+                volume += w.max(0.0);
+                if !set.contains(&target) {
+                    cut += w.max(0.0);
+                }
+            }
+        }
+        let total_volume: f64 = self.get_ids_iter().map(|id| self.get_volume(*id)).sum();
+        conductance(cut, volume, total_volume).ok_or_else(|| {
+            CLQError::from("Conductance is undefined for sets with no edges or all edges")
+        })
+    }
+
+    // Local community of a set of seeds (Andersen, Chung & Lang, 2006): nodes are ranked
+    // by their personalized PageRank from the seeds (see get_personalized_pagerank) over
+    // their volume, and the community is the prefix of that ranking which contains all the
+    // seeds and has the lowest conductance (the smallest one, on ties). Only nodes with a
+    // nonzero score are considered, so that the sweep stays near the seeds, although
+    // conductance takes the volume of the whole graph. Returns the community, sorted by
+    // node id, and its conductance, or an error if there are no seeds, if PageRank fails,
+    // or if no such prefix has a defined conductance.
+    fn get_local_community(
+        &self,
+        seeds: &[NodeId],
+        damping: f64,
+        epsilon: f64,
+    ) -> CLQResult<(Vec<NodeId>, f64)> {
+        if seeds.is_empty() {
+            return Err(CLQError::from("At least one seed is required"));
+        }
+        let scores = self.get_personalized_pagerank(seeds, damping, epsilon)?;
+        let mut volumes: FxHashMap<NodeId, f64> = scores
+            .keys()
+            .chain(seeds)
+            .map(|id| (*id, self.get_volume(*id)))
+            .collect();
+        let mut ranking: Vec<(NodeId, f64)> = volumes
+            .iter()
+            .map(|(id, volume)| {
+                let score = scores.get(id).cloned().unwrap_or(0.0);
+                let key = if *volume > 0.0 {
+                    score / volume
+                } else {
+                    f64::INFINITY
+                };
+                (*id, key)
+            })
+            .collect();
+        ranking.sort_by(|(a_id, a), (b_id, b)| b.partial_cmp(a).unwrap().then(a_id.cmp(b_id)));
+
+        let total_volume: f64 = self.get_ids_iter().map(|id| self.get_volume(*id)).sum();
+        let seed_set: FxHashSet<NodeId> = seeds.iter().cloned().collect();
+        let mut community: FxHashSet<NodeId> = FxHashSet::default();
+        let mut num_seeds_in: usize = 0;
+        let mut volume: f64 = 0.0;
+        let mut cut: f64 = 0.0;
+        // (prefix length, conductance) of the best community so far.
+        let mut best: Option<(usize, f64)> = None;
+        for (ix, (id, _)) in ranking.iter().enumerate() {
+            let node_volume = volumes.remove(id).unwrap();
+            let mut weight_in: f64 = 0.0;
+            let mut weight_to_self: f64 = 0.0;
+            for (target, w) in self.get_pagerank_out_edges(self.get_node(*id)) {
+                if target == *id {
+                    weight_to_self += w.max(0.0);
+                } else if community.contains(&target) {
+                    weight_in += w.max(0.0);
+                }
+            }
+            community.insert(*id);
+            volume += node_volume;
+            cut += node_volume - weight_to_self - 2.0 * weight_in;
+            if seed_set.contains(id) {
+                num_seeds_in += 1;
+            }
+            if num_seeds_in < seed_set.len() {
+                continue;
+            }
+            if let Some(phi) = conductance(cut.max(0.0), volume, total_volume) {
+                if best.is_none_or(|(_, best_phi)| phi < best_phi) {
+                    best = Some((ix + 1, phi));
+                }
+            }
+        }
+        let (len, phi) = best.ok_or_else(|| {
+            CLQError::from("No community containing the seeds has a defined conductance")
+        })?;
+        let mut nodes: Vec<NodeId> = ranking[..len].iter().map(|(id, _)| *id).collect();
+        nodes.sort();
+        Ok((nodes, phi))
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::bitset_adjacency::BitsetAdjacency;
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+use rand::prelude::*;
+use std::collections::HashMap;
+
+/// A clique found by `MaxClique::get_approximate_max_clique`, with bounds on the size of a
+/// maximum clique of the graph: the size of the clique found, and one more than the
+/// degeneracy of the graph (its largest coreness).
+#[derive(Debug)]
+pub struct MaxCliqueEstimate {
+    // sorted by node id.
+    pub clique: Vec<NodeId>,
+    pub lower_bound: usize,
+    pub upper_bound: usize,
+}
+
+// Grows clique greedily from candidates (nodes adjacent to every node of clique), adding
+// the candidate with the most neighbors among the other candidates (at random on ties).
+fn extend_clique<R: Rng, F: Fn(NodeId, NodeId) -> bool>(
+    clique: &mut Vec<NodeId>,
+    mut candidates: Vec<NodeId>,
+    is_adjacent: &F,
+    rng: &mut R,
+) {
+    let mut best: Vec<NodeId> = Vec::new();
+    while !candidates.is_empty() {
+        let mut best_count: usize = 0;
+        best.clear();
+        for id in &candidates {
+            let count = candidates.iter().filter(|x| is_adjacent(*id, **x)).count();
+            if best.is_empty() || count > best_count {
+                best.clear();
+                best_count = count;
+            }
+            if count == best_count {
+                best.push(*id);
+            }
+        }
+        let chosen = *best.choose(rng).unwrap();
+        clique.push(chosen);
+        candidates.retain(|x| is_adjacent(chosen, *x));
+    }
+}
+
+pub trait MaxClique: GraphBase + Coreness
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Heuristic maximum clique, for graphs too large for exact search: a clique is grown
+    // greedily from each node, in decreasing order of coreness (a node of coreness k is in
+    // no clique of more than k + 1 nodes, so the search stops once that is no larger than
+    // the best clique so far), and then improved by num_iterations rounds of iterated local
+    // search. Each round adds a random node to the current clique, drops the nodes it isn't
+    // adjacent to, and grows the clique greedily again. Stops early if the clique reaches
+    // the degeneracy bound, which proves it maximum.
+    fn get_approximate_max_clique(&self, num_iterations: usize) -> MaxCliqueEstimate {
+        self.get_approximate_max_clique_with_rng(num_iterations, &mut rand::thread_rng())
+    }
+
+    // Heuristic maximum clique, with ties and local search moves drawn from the given
+    // random number generator.
+    fn get_approximate_max_clique_with_rng<R: Rng>(
+        &self,
+        num_iterations: usize,
+        rng: &mut R,
+    ) -> MaxCliqueEstimate {
+        let coreness: HashMap<NodeId, usize> = self.get_coreness_values();
+        let upper_bound: usize = coreness.values().max().map_or(0, |x| x + 1);
+        let adjacency: FxHashMap<NodeId, FxHashSet<NodeId>> = self
+            .get_nodes_iter()
+            .map(|node| {
+                let neighbors = node
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|x| *x != node.get_id())
+                    .collect();
+                (node.get_id(), neighbors)
+            })
+            .collect();
+        // adjacency checks, on a bitset for small dense graphs.
+        let bitset = BitsetAdjacency::from_dense_graph(self);
+        let is_adjacent = |id1: NodeId, id2: NodeId| match &bitset {
+            Some(bitset) => bitset.has_edge_between(id1, id2),
+            None => adjacency[&id1].contains(&id2),
+        };
+        let mut order: Vec<NodeId> = self.get_ids_iter().cloned().collect();
+        order.sort_by_key(|id| (std::cmp::Reverse(coreness[id]), *id));
+
+        // nodes that could be in a clique larger than k nodes, i.e. of coreness at least k.
+        let candidates_for = |id: NodeId, k: usize| -> Vec<NodeId> {
+            let mut candidates: Vec<NodeId> = adjacency[&id]
+                .iter()
+                .filter(|x| coreness[x] >= k)
+                .cloned()
+                .collect();
+            candidates.sort();
+            candidates
+        };
+        let mut best: Vec<NodeId> = Vec::new();
+        for id in &order {
+            if coreness[id] < best.len() || best.len() == upper_bound {
+                break;
+            }
+            let mut clique: Vec<NodeId> = vec![*id];
+            extend_clique(
+                &mut clique,
+                candidates_for(*id, best.len()),
+                &is_adjacent,
+                rng,
+            );
+            if clique.len() > best.len() {
+                best = clique;
+            }
+        }
+
+        let mut current: Vec<NodeId> = best.clone();
+        for _ in 0..num_iterations {
+            if best.len() == upper_bound {
+                break;
+            }
+            let eligible: Vec<NodeId> = order
+                .iter()
+                .take_while(|id| coreness[id] >= best.len())
+                .filter(|id| !current.contains(id))
+                .cloned()
+                .collect();
+            let added: NodeId = match eligible.choose(rng) {
+                Some(id) => *id,
+                None => break,
+            };
+            current.retain(|x| is_adjacent(added, *x));
+            let candidates: Vec<NodeId> = candidates_for(added, best.len())
+                .into_iter()
+                .filter(|x| !current.contains(x))
+                .filter(|x| current.iter().all(|y| is_adjacent(*x, *y)))
+                .collect();
+            current.push(added);
+            extend_clique(&mut current, candidates, &is_adjacent, rng);
+            if current.len() > best.len() {
+                best = current.clone();
+            }
+        }
+        best.sort();
+        MaxCliqueEstimate {
+            lower_bound: best.len(),
+            clique: best,
+            upper_bound,
+        }
+    }
+}
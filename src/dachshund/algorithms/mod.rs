@@ -6,15 +6,58 @@
  */
 pub mod adjacency_matrix;
 pub mod algebraic_connectivity;
+pub mod assortativity;
+pub mod backbone;
 pub mod betweenness;
+pub mod bipartite_cores;
+pub mod bipartite_matching;
+pub mod bipartite_modularity;
+pub mod bitset_adjacency;
 pub mod brokerage;
+pub mod butterflies;
 pub mod clustering;
 pub mod cnm_communities;
+pub mod coarsening;
 pub mod connected_components;
 pub mod connectivity;
+pub mod core_maintenance;
 pub mod coreness;
+pub mod degree_distribution;
+pub mod dendrogram;
+pub mod directed_transitivity;
+pub mod dot;
 pub mod eigenvector_centrality;
+pub mod embedding_index;
+pub mod epidemics;
+pub mod flow;
+pub mod fraudar;
+pub mod gexf;
+pub mod graph_diff;
+pub mod graphlets;
+pub mod hits;
+pub mod influence_maximization;
 pub mod k_peaks;
+pub mod label_propagation;
 pub mod laplacian;
+pub mod layout;
+pub mod leiden;
+pub mod link_prediction;
+pub mod local_community;
+pub mod max_clique;
+pub mod pagerank;
+pub mod partition_comparison;
+pub mod partitioning;
+pub mod random_walks;
+pub mod relaxed_cliques;
+pub mod rewiring;
+pub mod rich_club;
 pub mod shortest_paths;
+pub mod spanning_tree;
+pub mod sparsification;
+pub mod stochastic_block_model;
+pub mod streaming_triangles;
+pub mod subgraphs;
 pub mod transitivity;
+pub mod triangles;
+pub mod union_find;
+pub mod visualization;
@@ -0,0 +1,169 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+use std::collections::{HashMap, VecDeque};
+
+pub trait PageRank: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Edges along which a node distributes its rank, with their (unnormalized) weights.
+    // Unweighted graphs give every outgoing edge the same weight; weighted and directed
+    // graphs override this.
+    fn get_pagerank_out_edges(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_outgoing_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    // Power iteration, see http://ilpubs.stanford.edu:8090/422/1/1999-66.pdf.
+    // Rank held by nodes without outgoing edges is spread uniformly over the graph.
+    // Iterates until the L1 distance between successive rank vectors drops below tol,
+    // or max_iter iterations have been run. Ranks sum to 1.
+    fn get_pagerank(&self, damping: f64, tol: f64, max_iter: usize) -> HashMap<NodeId, f64> {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+
+        // out_edges[i] holds (target position, transition probability) for node i.
+        let out_edges: Vec<Vec<(usize, f64)>> = node_ids
+            .iter()
+            .map(|id| {
+                let edges = self.get_pagerank_out_edges(self.get_node(*id));
+                let total: f64 = edges.iter().map(|(_, w)| w).sum();
+                edges
+                    .into_iter()
+                    .filter(|(_, w)| total > 0.0 && *w > 0.0)
+                    .map(|(target, w)| (positions[&target], w / total))
+                    .collect()
+            })
+            .collect();
+
+        let teleport = (1.0 - damping) / n as f64;
+        let mut ranks: Vec<f64> = vec![1.0 / n as f64; n];
+        for _ in 0..max_iter {
+            let dangling: f64 = out_edges
+                .iter()
+                .zip(ranks.iter())
+                .filter(|(edges, _)| edges.is_empty())
+                .map(|(_, r)| r)
+                .sum();
+            let base = teleport + damping * dangling / n as f64;
+            let mut next: Vec<f64> = vec![base; n];
+            for (i, edges) in out_edges.iter().enumerate() {
+                for (j, p) in edges {
+                    next[*j] += damping * ranks[i] * p;
+                }
+            }
+            let diff: f64 = next
+                .iter()
+                .zip(ranks.iter())
+                .map(|(x, y)| (x - y).abs())
+                .sum();
+            ranks = next;
+            if diff < tol {
+                break;
+            }
+        }
+        node_ids.into_iter().zip(ranks).collect()
+    }
+
+    // Personalized PageRank (random walk with restart) from a set of seeds, restarting at
+    // each seed with equal probability, approximated by pushing residual probability from
+    // node to node (Andersen, Chung & Lang, 2006) instead of iterating over the whole graph:
+    // only nodes near the seeds are visited. A node is pushed while its residual is at least
+    // epsilon times its (weighted) out-degree, which also bounds the error of its score.
+    // Rank held by nodes without outgoing edges goes back to the seeds. Returns the nonzero
+    // scores (which sum to at most 1), or an error if a seed isn't a node of the graph, or
+    // epsilon isn't positive.
+    fn get_personalized_pagerank(
+        &self,
+        seeds: &[NodeId],
+        damping: f64,
+        epsilon: f64,
+    ) -> CLQResult<HashMap<NodeId, f64>> {
+        if epsilon.is_nan() || epsilon <= 0.0 {
+            return Err(CLQError::from(format!(
+                "epsilon must be positive, got {epsilon}"
+            )));
+        }
+        for id in seeds {
+            if !self.has_node(*id) {
+                return Err(CLQError::from(format!("No such node: {}", id.value())));
+            }
+        }
+        // transition probabilities and weighted out-degree of the nodes visited so far.
+        // Nodes without outgoing edges go back to the seeds, as if with a single edge.
+        let restart: Vec<(NodeId, f64)> = seeds
+            .iter()
+            .map(|id| (*id, 1.0 / seeds.len() as f64))
+            .collect();
+        let get_transitions = |id: NodeId| -> (Vec<(NodeId, f64)>, f64) {
+            let edges = self.get_pagerank_out_edges(self.get_node(id));
+            let total: f64 = edges.iter().map(|(_, w)| w.max(0.0)).sum();
+            if total == 0.0 {
+                return (restart.clone(), 1.0);
+            }
+            let probabilities = edges
+                .into_iter()
+                .filter(|(_, w)| *w > 0.0)
+                .map(|(target, w)| (target, w / total))
+                .collect();
+            (probabilities, total)
+        };
+        let mut transitions: FxHashMap<NodeId, (Vec<(NodeId, f64)>, f64)> = FxHashMap::default();
+
+        let mut scores: HashMap<NodeId, f64> = HashMap::new();
+        let mut residuals: FxHashMap<NodeId, f64> = FxHashMap::default();
+        let mut queue: VecDeque<NodeId> = VecDeque::new();
+        let mut queued: FxHashSet<NodeId> = FxHashSet::default();
+        for (id, p) in &restart {
+            *residuals.entry(*id).or_insert(0.0) += p;
+            if queued.insert(*id) {
+                queue.push_back(*id);
+            }
+        }
+        let mut targets: Vec<NodeId> = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            queued.remove(&id);
+            let residual = residuals[&id];
+            let (edges, degree) = transitions.entry(id).or_insert_with(|| get_transitions(id));
+            if residual < epsilon * *degree {
+                continue;
+            }
+            residuals.insert(id, 0.0);
+            *scores.entry(id).or_insert(0.0) += (1.0 - damping) * residual;
+            targets.clear();
+            for (target, p) in edges.iter() {
+                *residuals.entry(*target).or_insert(0.0) += damping * residual * p;
+                targets.push(*target);
+            }
+            for target in &targets {
+                let (_, target_degree) = transitions
+                    .entry(*target)
+                    .or_insert_with(|| get_transitions(*target));
+                if residuals[target] >= epsilon * *target_degree && queued.insert(*target) {
+                    queue.push_back(*target);
+                }
+            }
+        }
+        Ok(scores)
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use fxhash::FxHashMap;
+use std::collections::HashMap;
+
+// Comparisons of two partitions of the same nodes, e.g. communities found by different
+// algorithms, or found and ground truth ones. Partitions are given as the label of each node
+// (as in `Partition::parts` or `BlockModel::blocks`); see `get_community_labels` for
+// communities. Each returns an error if the partitions aren't of the same nodes.
+
+/// label of each node of communities, which is the id of its community.
+pub fn get_community_labels(
+    communities: &HashMap<CommunityId, Community>,
+) -> HashMap<NodeId, usize> {
+    communities
+        .iter()
+        .flat_map(|(c, community)| community.iter().map(move |id| (*id, *c)))
+        .collect()
+}
+
+// Number of nodes with each label in a, in b, and with each pair of labels.
+struct ContingencyTable {
+    num_nodes: usize,
+    a_counts: FxHashMap<usize, usize>,
+    b_counts: FxHashMap<usize, usize>,
+    counts: FxHashMap<(usize, usize), usize>,
+}
+impl ContingencyTable {
+    fn new(a: &HashMap<NodeId, usize>, b: &HashMap<NodeId, usize>) -> CLQResult<Self> {
+        if a.len() != b.len() {
+            return Err(CLQError::from(format!(
+                "Partitions are of different nodes: {} and {} nodes",
+                a.len(),
+                b.len()
+            )));
+        }
+        let mut a_counts: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut b_counts: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut counts: FxHashMap<(usize, usize), usize> = FxHashMap::default();
+        for (id, a_label) in a {
+            let b_label = b.get(id).ok_or_else(|| {
+                CLQError::from(format!("Node {} is only in one partition", id.value()))
+            })?;
+            *a_counts.entry(*a_label).or_insert(0) += 1;
+            *b_counts.entry(*b_label).or_insert(0) += 1;
+            *counts.entry((*a_label, *b_label)).or_insert(0) += 1;
+        }
+        Ok(Self {
+            num_nodes: a.len(),
+            a_counts,
+            b_counts,
+            counts,
+        })
+    }
+
+    // entropy of the distribution of nodes over the given counts, in nats.
+    fn get_entropy<'a>(&self, counts: impl Iterator<Item = &'a usize>) -> f64 {
+        let n = self.num_nodes as f64;
+        -counts
+            .map(|c| *c as f64 / n)
+            .map(|p| p * p.ln())
+            .sum::<f64>()
+    }
+
+    // entropies of a and of b, and their mutual information, in nats.
+    fn get_information(&self) -> (f64, f64, f64) {
+        let n = self.num_nodes as f64;
+        let mutual_information: f64 = self
+            .counts
+            .iter()
+            .map(|((a_label, b_label), c)| {
+                let c = *c as f64;
+                let expected = (self.a_counts[a_label] * self.b_counts[b_label]) as f64 / n;
+                c / n * (c / expected).ln()
+            })
+            .sum();
+        (
+            self.get_entropy(self.a_counts.values()),
+            self.get_entropy(self.b_counts.values()),
+            mutual_information,
+        )
+    }
+}
+
+// number of pairs of c items.
+fn count_pairs(c: usize) -> f64 {
+    (c * c.saturating_sub(1)) as f64 / 2.0
+}
+
+/// Normalized mutual information of two partitions: their mutual information over the mean
+/// of their entropies, from 0 for independent partitions to 1 for identical ones (including
+/// when both put every node together).
+pub fn get_normalized_mutual_information(
+    a: &HashMap<NodeId, usize>,
+    b: &HashMap<NodeId, usize>,
+) -> CLQResult<f64> {
+    let (a_entropy, b_entropy, mutual_information) = ContingencyTable::new(a, b)?.get_information();
+    if a_entropy + b_entropy == 0.0 {
+        return Ok(1.0);
+    }
+    Ok((2.0 * mutual_information / (a_entropy + b_entropy)).clamp(0.0, 1.0))
+}
+
+/// Adjusted Rand index of two partitions (Hubert and Arabie, 1985): the share of pairs of
+/// nodes both put together or both apart, adjusted for chance, so that it is 1 for identical
+/// partitions and about 0 for random ones (and may be negative).
+pub fn get_adjusted_rand_index(
+    a: &HashMap<NodeId, usize>,
+    b: &HashMap<NodeId, usize>,
+) -> CLQResult<f64> {
+    let table = ContingencyTable::new(a, b)?;
+    let index: f64 = table.counts.values().map(|c| count_pairs(*c)).sum();
+    let a_pairs: f64 = table.a_counts.values().map(|c| count_pairs(*c)).sum();
+    let b_pairs: f64 = table.b_counts.values().map(|c| count_pairs(*c)).sum();
+    let expected = match table.num_nodes {
+        0 | 1 => 0.0,
+        n => a_pairs * b_pairs / count_pairs(n),
+    };
+    let max_index = (a_pairs + b_pairs) / 2.0;
+    if max_index == expected {
+        return Ok(1.0);
+    }
+    Ok((index - expected) / (max_index - expected))
+}
+
+/// Variation of information between two partitions (Meila, 2007): the sum of their
+/// conditional entropies given each other, in nats. This is a distance, 0 for identical
+/// partitions, and at most the log of the number of nodes.
+pub fn get_variation_of_information(
+    a: &HashMap<NodeId, usize>,
+    b: &HashMap<NodeId, usize>,
+) -> CLQResult<f64> {
+    let (a_entropy, b_entropy, mutual_information) = ContingencyTable::new(a, b)?.get_information();
+    Ok((a_entropy + b_entropy - 2.0 * mutual_information).max(0.0))
+}
@@ -0,0 +1,336 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate ordered_float;
+use crate::dachshund::algorithms::coarsening::{Coarsening, CoarseningState};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+use ordered_float::NotNan;
+use rand::prelude::*;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+// graphs are coarsened down to about this many nodes per part before being split.
+const COARSEST_NODES_PER_PART: usize = 20;
+// refinement passes per level, each stopping early if it doesn't reduce the edge cut.
+const MAX_REFINEMENT_PASSES: usize = 10;
+// a refinement pass gives up after this many moves without reducing the edge cut.
+const MAX_MOVES_WITHOUT_GAIN: usize = 50;
+
+/// A split of the nodes of a graph into k parts.
+pub struct Partition {
+    // part of each node, from 0 to k - 1.
+    pub parts: HashMap<NodeId, usize>,
+    // number of nodes in each part.
+    pub part_sizes: Vec<usize>,
+    // total weight of the edges between nodes in different parts.
+    pub edge_cut: f64,
+}
+
+// Partition of the nodes of one level of a coarsening hierarchy, with the total node weight
+// in each part, and the largest total weight any part is allowed. Refinement may go over
+// that by up to slack (the weight of the heaviest node), so that a node can be moved into
+// a full part before another is moved out of it.
+struct LevelPartition<'a> {
+    level: &'a CoarseningState,
+    parts: FxHashMap<NodeId, usize>,
+    part_weights: Vec<f64>,
+    max_part_weight: f64,
+    slack: f64,
+}
+impl<'a> LevelPartition<'a> {
+    fn new(
+        level: &'a CoarseningState,
+        parts: FxHashMap<NodeId, usize>,
+        k: usize,
+        max_part_weight: f64,
+    ) -> Self {
+        let mut part_weights: Vec<f64> = vec![0.0; k];
+        for id in &level.ids {
+            part_weights[parts[id]] += level.node_weights[id];
+        }
+        Self {
+            level,
+            parts,
+            part_weights,
+            max_part_weight,
+            slack: level.node_weights.values().cloned().fold(0.0, f64::max),
+        }
+    }
+
+    // Graph growing: nodes are ordered by breadth-first search (from a random node in each
+    // connected component), and this order is cut into k runs of about equal weight.
+    fn grow<R: Rng>(
+        level: &'a CoarseningState,
+        k: usize,
+        max_part_weight: f64,
+        rng: &mut R,
+    ) -> Self {
+        let mut starts: Vec<NodeId> = level.ids.clone();
+        starts.shuffle(rng);
+        let mut visited: FxHashSet<NodeId> = FxHashSet::default();
+        let mut order: Vec<NodeId> = Vec::new();
+        for start in starts {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut queue: VecDeque<NodeId> = VecDeque::new();
+            queue.push_back(start);
+            while let Some(id) = queue.pop_front() {
+                order.push(id);
+                for (neighbor_id, _) in &level.edges[&id] {
+                    if visited.insert(*neighbor_id) {
+                        queue.push_back(*neighbor_id);
+                    }
+                }
+            }
+        }
+        let total_weight: f64 = level.node_weights.values().sum();
+        let mut parts: FxHashMap<NodeId, usize> = FxHashMap::default();
+        let mut filled: f64 = 0.0;
+        for id in order {
+            let weight = level.node_weights[&id];
+            // the part the middle of the node's share of the total weight falls in.
+            let part = ((filled + weight / 2.0) / total_weight * k as f64) as usize;
+            parts.insert(id, part.min(k - 1));
+            filled += weight;
+        }
+        Self::new(level, parts, k, max_part_weight)
+    }
+
+    // Projects the partition of the next (coarser) level onto this one.
+    fn project(
+        level: &'a CoarseningState,
+        coarse: &LevelPartition,
+        projection: &HashMap<NodeId, NodeId>,
+    ) -> Self {
+        let parts: FxHashMap<NodeId, usize> = level
+            .ids
+            .iter()
+            .map(|id| (*id, coarse.parts[&projection[id]]))
+            .collect();
+        Self::new(
+            level,
+            parts,
+            coarse.part_weights.len(),
+            coarse.max_part_weight,
+        )
+    }
+
+    // Total weight of the edges from a node to each part.
+    fn get_connections(&self, id: NodeId) -> FxHashMap<usize, f64> {
+        let mut connections: FxHashMap<usize, f64> = FxHashMap::default();
+        for (neighbor_id, weight) in &self.level.edges[&id] {
+            if *neighbor_id != id {
+                *connections.entry(self.parts[neighbor_id]).or_insert(0.0) += weight;
+            }
+        }
+        connections
+    }
+
+    // Reduction in edge cut from moving a node to the given part.
+    fn get_gain(&self, id: NodeId, to: usize, connections: &FxHashMap<usize, f64>) -> f64 {
+        let from = self.parts[&id];
+        connections.get(&to).unwrap_or(&0.0) - connections.get(&from).unwrap_or(&0.0)
+    }
+
+    fn fits(&self, id: NodeId, to: usize, slack: f64) -> bool {
+        self.part_weights[to] + self.level.node_weights[&id] <= self.max_part_weight + slack
+    }
+
+    fn is_balanced(&self) -> bool {
+        self.part_weights.iter().all(|x| *x <= self.max_part_weight)
+    }
+
+    // The move of a node to a part one of its neighbors is in which most reduces the edge
+    // cut (ties going to the lightest part), among those keeping parts within their
+    // maximum weight (plus slack), as (gain, part).
+    fn get_best_move(&self, id: NodeId) -> Option<(NotNan<f64>, usize)> {
+        let from = self.parts[&id];
+        let connections = self.get_connections(id);
+        let mut best: Option<(NotNan<f64>, usize)> = None;
+        for to in connections.keys() {
+            if *to == from || !self.fits(id, *to, self.slack) {
+                continue;
+            }
+            let gain = match NotNan::new(self.get_gain(id, *to, &connections)) {
+                Ok(gain) => gain,
+                Err(_) => continue,
+            };
+            best = match best {
+                Some((best_gain, best_to))
+                    if best_gain > gain
+                        || (best_gain == gain
+                            && self.part_weights[best_to] <= self.part_weights[*to]) =>
+                {
+                    Some((best_gain, best_to))
+                }
+                _ => Some((gain, *to)),
+            };
+        }
+        best
+    }
+
+    fn move_node(&mut self, id: NodeId, to: usize) {
+        let weight = self.level.node_weights[&id];
+        let from = self.parts.insert(id, to).unwrap();
+        self.part_weights[from] -= weight;
+        self.part_weights[to] += weight;
+    }
+
+    // Moves nodes out of parts above their maximum weight (as when coarse nodes are too
+    // heavy to split the coarsest level evenly), picking the moves which increase the
+    // edge cut least. Gives up if no move fits.
+    fn rebalance(&mut self) {
+        let k = self.part_weights.len();
+        while let Some(from) = (0..k).find(|p| self.part_weights[*p] > self.max_part_weight) {
+            let mut best: Option<(f64, NodeId, usize)> = None;
+            for id in &self.level.ids {
+                if self.parts[id] != from {
+                    continue;
+                }
+                let connections = self.get_connections(*id);
+                for to in (0..k).filter(|to| *to != from && self.fits(*id, *to, 0.0)) {
+                    let gain = self.get_gain(*id, to, &connections);
+                    if best.is_none_or(|(best_gain, _, _)| gain > best_gain) {
+                        best = Some((gain, *id, to));
+                    }
+                }
+            }
+            match best {
+                Some((_, id, to)) => self.move_node(id, to),
+                None => return,
+            }
+        }
+    }
+
+    // A pass of (k-way) Fiduccia-Mattheyses refinement: the best move of any node is
+    // repeatedly made, even if it increases the edge cut, with each node moved at most
+    // once, and the moves past the point the edge cut was lowest (with parts within their
+    // maximum weight) are then undone. Moves are kept in a heap, with gains refreshed as
+    // they are popped. Returns the reduction in edge cut.
+    fn refine_pass(&mut self) -> f64 {
+        let initially_balanced = self.is_balanced();
+        let mut heap: BinaryHeap<(NotNan<f64>, NodeId, usize)> = BinaryHeap::new();
+        for id in &self.level.ids {
+            if let Some((gain, to)) = self.get_best_move(*id) {
+                heap.push((gain, *id, to));
+            }
+        }
+        let mut locked: FxHashSet<NodeId> = FxHashSet::default();
+        let mut moves: Vec<(NodeId, usize)> = Vec::new();
+        let mut total_gain: f64 = 0.0;
+        let mut best_gain: f64 = 0.0;
+        let mut num_best_moves: usize = 0;
+        while let Some((gain, id, to)) = heap.pop() {
+            if locked.contains(&id) {
+                continue;
+            }
+            match self.get_best_move(id) {
+                Some(best_move) if best_move == (gain, to) => (),
+                Some((new_gain, new_to)) => {
+                    heap.push((new_gain, id, new_to));
+                    continue;
+                }
+                None => continue,
+            }
+            moves.push((id, self.parts[&id]));
+            self.move_node(id, to);
+            locked.insert(id);
+            total_gain += gain.into_inner();
+            if total_gain > best_gain && (self.is_balanced() || !initially_balanced) {
+                best_gain = total_gain;
+                num_best_moves = moves.len();
+            } else if moves.len() - num_best_moves >= MAX_MOVES_WITHOUT_GAIN {
+                break;
+            }
+            for (neighbor_id, _) in &self.level.edges[&id] {
+                if !locked.contains(neighbor_id) {
+                    if let Some((neighbor_gain, neighbor_to)) = self.get_best_move(*neighbor_id) {
+                        heap.push((neighbor_gain, *neighbor_id, neighbor_to));
+                    }
+                }
+            }
+        }
+        for (id, from) in moves.into_iter().skip(num_best_moves).rev() {
+            self.move_node(id, from);
+        }
+        best_gain
+    }
+
+    fn refine(&mut self) {
+        self.rebalance();
+        for _ in 0..MAX_REFINEMENT_PASSES {
+            if self.refine_pass() <= 0.0 {
+                break;
+            }
+        }
+    }
+}
+
+pub trait Partitioning: Coarsening
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Multilevel k-way partitioning (as in METIS): the graph is coarsened (see
+    // get_coarsening_hierarchy), its coarsest level split by graph growing, and the
+    // partition projected back level by level, refined at each level with
+    // Fiduccia-Mattheyses moves. Parts have at most (1 + imbalance) * n / k nodes (or
+    // ceil(n / k) if that's more), and edges are weighted by get_coarsening_edges.
+    fn get_partition(&self, k: usize, imbalance: f64) -> CLQResult<Partition> {
+        self.get_partition_with_rng(k, imbalance, &mut rand::thread_rng())
+    }
+
+    // Multilevel k-way partitioning, with random choices drawn from the given random
+    // number generator.
+    fn get_partition_with_rng<R: Rng>(
+        &self,
+        k: usize,
+        imbalance: f64,
+        rng: &mut R,
+    ) -> CLQResult<Partition> {
+        if k == 0 {
+            return Err(CLQError::from("Number of parts must be positive"));
+        }
+        if imbalance.is_nan() || imbalance < 0.0 {
+            return Err(CLQError::from(format!(
+                "Imbalance must be non-negative, got {imbalance}"
+            )));
+        }
+        let hierarchy = self.get_coarsening_hierarchy_with_rng(k * COARSEST_NODES_PER_PART, rng);
+        let mut levels: Vec<CoarseningState> = vec![CoarseningState::from_graph(self)];
+        levels.extend(hierarchy.iter().map(CoarseningState::from_coarse_graph));
+        let num_nodes = levels[0].ids.len() as f64;
+        let max_part_weight =
+            ((1.0 + imbalance) * num_nodes / k as f64).max((num_nodes / k as f64).ceil());
+
+        let mut partition = LevelPartition::grow(levels.last().unwrap(), k, max_part_weight, rng);
+        partition.refine();
+        for (level, coarse) in levels.iter().zip(hierarchy.iter()).rev() {
+            partition = LevelPartition::project(level, &partition, &coarse.projection);
+            partition.refine();
+        }
+
+        let mut part_sizes: Vec<usize> = vec![0; k];
+        let mut edge_cut: f64 = 0.0;
+        for id in &levels[0].ids {
+            part_sizes[partition.parts[id]] += 1;
+            for (neighbor_id, weight) in &levels[0].edges[id] {
+                if id < neighbor_id && partition.parts[id] != partition.parts[neighbor_id] {
+                    edge_cut += weight;
+                }
+            }
+        }
+        Ok(Partition {
+            parts: partition.parts.into_iter().collect(),
+            part_sizes,
+            edge_cut,
+        })
+    }
+}
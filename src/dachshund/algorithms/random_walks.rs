@@ -0,0 +1,178 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::output::Output;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+/// Parameters of node2vec walks (Grover & Leskovec, 2016). The return parameter p and the
+/// in-out parameter q bias each step by where the walk came from: going back to the previous
+/// node is weighted by 1 / p, going to another neighbor of the previous node by 1, and going
+/// further away by 1 / q. With p = q = 1, walks are uniform (as in DeepWalk).
+pub struct RandomWalkParams {
+    // number of nodes in each walk, including the node it starts from.
+    pub walk_length: usize,
+    pub walks_per_node: usize,
+    pub p: f64,
+    pub q: f64,
+}
+impl RandomWalkParams {
+    pub fn new(walk_length: usize, walks_per_node: usize, p: f64, q: f64) -> CLQResult<Self> {
+        if walk_length == 0 {
+            return Err(CLQError::from("Walks must have a length of at least 1"));
+        }
+        if !(p > 0.0 && q > 0.0) {
+            return Err(CLQError::from("p and q must be positive"));
+        }
+        Ok(Self {
+            walk_length,
+            walks_per_node,
+            p,
+            q,
+        })
+    }
+    fn is_uniform(&self) -> bool {
+        self.p == 1.0 && self.q == 1.0
+    }
+}
+
+/// Steps a walk can take from each node, with their weights, along with the set of
+/// neighbors of each node (needed to bias steps by the previous node).
+pub struct WalkTable {
+    steps: FxHashMap<NodeId, Vec<(NodeId, f64)>>,
+    neighbors: FxHashMap<NodeId, FxHashSet<NodeId>>,
+}
+impl WalkTable {
+    fn get_next<R: Rng>(
+        &self,
+        previous: Option<NodeId>,
+        current: NodeId,
+        params: &RandomWalkParams,
+        rng: &mut R,
+    ) -> Option<NodeId> {
+        let steps = &self.steps[&current];
+        let weights: Vec<f64> = match previous {
+            Some(t) if !params.is_uniform() => steps
+                .iter()
+                .map(|(x, w)| {
+                    if *x == t {
+                        w / params.p
+                    } else if self.neighbors[&t].contains(x) {
+                        *w
+                    } else {
+                        w / params.q
+                    }
+                })
+                .collect(),
+            _ => steps.iter().map(|(_x, w)| *w).collect(),
+        };
+        // fails if there are no steps, or their weights are all 0.
+        let dist = WeightedIndex::new(weights).ok()?;
+        Some(steps[dist.sample(rng)].0)
+    }
+
+    /// A walk from start, which ends early if it reaches a node it can't leave.
+    pub fn walk<R: Rng>(
+        &self,
+        start: NodeId,
+        params: &RandomWalkParams,
+        rng: &mut R,
+    ) -> Vec<NodeId> {
+        let mut walk = vec![start];
+        while walk.len() < params.walk_length {
+            let previous = if walk.len() > 1 {
+                Some(walk[walk.len() - 2])
+            } else {
+                None
+            };
+            match self.get_next(previous, walk[walk.len() - 1], params, rng) {
+                Some(next) => walk.push(next),
+                None => break,
+            }
+        }
+        walk
+    }
+}
+
+pub trait RandomWalks: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Steps a walk can take from a node, with their (unnormalized) probabilities. Every
+    // outgoing edge is equally likely in unweighted graphs; weighted and directed graphs
+    // override this.
+    fn get_walk_steps(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_outgoing_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    fn get_walk_table(&self) -> WalkTable {
+        let steps: FxHashMap<NodeId, Vec<(NodeId, f64)>> = self
+            .get_nodes_iter()
+            .map(|node| (node.get_id(), self.get_walk_steps(node)))
+            .collect();
+        let neighbors = steps
+            .iter()
+            .map(|(id, s)| (*id, s.iter().map(|(x, _w)| *x).collect()))
+            .collect();
+        WalkTable { steps, neighbors }
+    }
+
+    // A single walk from start. To generate many walks, get a walk table once and use its
+    // walk method instead.
+    fn get_random_walk_with_rng<R: Rng>(
+        &self,
+        start: NodeId,
+        params: &RandomWalkParams,
+        rng: &mut R,
+    ) -> CLQResult<Vec<NodeId>> {
+        if !self.has_node(start) {
+            return Err(CLQError::from(format!("No such node: {}", start.value())));
+        }
+        Ok(self.get_walk_table().walk(start, params, rng))
+    }
+
+    // Writes walks_per_node walks from each node to output, one per line, as node ids
+    // separated by spaces (the corpus format expected by word2vec-style embedding
+    // trainers). Every node is used as a starting point once per round, in shuffled
+    // order. Returns the number of walks written.
+    fn write_random_walks_with_rng<R: Rng>(
+        &self,
+        params: &RandomWalkParams,
+        output: &mut Output,
+        rng: &mut R,
+    ) -> CLQResult<usize> {
+        let table = self.get_walk_table();
+        let mut node_ids = self.get_ordered_node_ids();
+        let mut num_walks: usize = 0;
+        for _ in 0..params.walks_per_node {
+            node_ids.shuffle(rng);
+            for id in &node_ids {
+                let walk = table.walk(*id, params, rng);
+                let line: Vec<String> = walk.iter().map(|x| x.value().to_string()).collect();
+                output.print(line.join(" "))?;
+                num_walks += 1;
+            }
+        }
+        Ok(num_walks)
+    }
+
+    fn write_random_walks(
+        &self,
+        params: &RandomWalkParams,
+        output: &mut Output,
+    ) -> CLQResult<usize> {
+        self.write_random_walks_with_rng(params, output, &mut rand::thread_rng())
+    }
+}
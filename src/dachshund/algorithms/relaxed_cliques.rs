@@ -0,0 +1,226 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use roaring::RoaringBitmap;
+
+/// true if every node of nodes is tied to all but at most k of them (itself included),
+/// given the neighbors of each node, by number (with no self-loops).
+pub fn is_k_plex_within(neighbors: &[RoaringBitmap], nodes: &RoaringBitmap, k: usize) -> bool {
+    let size = nodes.len();
+    nodes
+        .iter()
+        .all(|v| size - neighbors[v as usize].intersection_len(nodes) <= k as u64)
+}
+
+// nodes of nodes further than k ties from source, through ties among nodes.
+fn get_far_nodes(
+    neighbors: &[RoaringBitmap],
+    nodes: &RoaringBitmap,
+    source: u32,
+    k: usize,
+) -> RoaringBitmap {
+    let mut far = nodes.clone();
+    far.remove(source);
+    let mut frontier = RoaringBitmap::new();
+    frontier.insert(source);
+    for _ in 0..k {
+        if frontier.is_empty() || far.is_empty() {
+            break;
+        }
+        let mut next = RoaringBitmap::new();
+        for v in &frontier {
+            next |= &neighbors[v as usize] & &far;
+        }
+        far -= &next;
+        frontier = next;
+    }
+    far
+}
+
+/// true if every two nodes of nodes are joined by a path of at most k ties among them,
+/// given the neighbors of each node, by number (with no self-loops).
+pub fn is_k_club_within(neighbors: &[RoaringBitmap], nodes: &RoaringBitmap, k: usize) -> bool {
+    nodes
+        .iter()
+        .all(|v| get_far_nodes(neighbors, nodes, v, k).is_empty())
+}
+
+// Branch and bound over the nodes of candidates, each either added to plex (if plex stays
+// a k-plex) or left out, keeping the largest k-plex found in best.
+fn grow_k_plex(
+    neighbors: &[RoaringBitmap],
+    k: usize,
+    plex: &mut RoaringBitmap,
+    candidates: RoaringBitmap,
+    best: &mut RoaringBitmap,
+) {
+    if plex.len() + candidates.len() <= best.len() {
+        return;
+    }
+    let v = match candidates.min() {
+        Some(v) => v,
+        None => {
+            best.clone_from(plex);
+            return;
+        }
+    };
+    let mut rest = candidates;
+    rest.remove(v);
+    plex.insert(v);
+    let compatible: RoaringBitmap = rest
+        .iter()
+        .filter(|w| {
+            plex.insert(*w);
+            let is_k_plex = is_k_plex_within(neighbors, plex, k);
+            plex.remove(*w);
+            is_k_plex
+        })
+        .collect();
+    grow_k_plex(neighbors, k, plex, compatible, best);
+    plex.remove(v);
+    grow_k_plex(neighbors, k, plex, rest, best);
+}
+
+// Branch and bound by dropping nodes (as in Bourjolly et al., 2002): while some nodes of
+// club are further than k ties apart within it, the node with the most such nodes is either
+// kept, dropping those nodes, or dropped. Nodes kept are fixed, and since distances only
+// grow as nodes are dropped, nodes too far from a fixed node can be dropped outright.
+// Keeps the largest k-club found in best.
+fn drop_to_k_club(
+    neighbors: &[RoaringBitmap],
+    k: usize,
+    club: RoaringBitmap,
+    fixed: RoaringBitmap,
+    best: &mut RoaringBitmap,
+) {
+    if club.len() <= best.len() {
+        return;
+    }
+    let mut branch: Option<(u32, RoaringBitmap)> = None;
+    for v in &club {
+        let far = get_far_nodes(neighbors, &club, v, k);
+        if far.is_empty() {
+            continue;
+        }
+        if fixed.contains(v) {
+            if !far.is_disjoint(&fixed) {
+                return;
+            }
+            drop_to_k_club(neighbors, k, club - far, fixed, best);
+            return;
+        }
+        if !matches!(&branch, Some((_, most)) if most.len() >= far.len()) {
+            branch = Some((v, far));
+        }
+    }
+    match branch {
+        None => best.clone_from(&club),
+        Some((v, far)) => {
+            let mut with_v = fixed.clone();
+            with_v.insert(v);
+            drop_to_k_club(neighbors, k, &club - &far, with_v, best);
+            let mut without_v = club;
+            without_v.remove(v);
+            drop_to_k_club(neighbors, k, without_v, fixed, best);
+        }
+    }
+}
+
+// Cliques relax in two ways: a k-plex is a set of nodes each tied to all but at most k of
+// them (itself included), so that 1-plexes are cliques, and a k-club a set of nodes any two
+// of which are joined by a path of at most k ties among them, so that 1-clubs are cliques.
+// Exact searches take exponential time, and suit small graphs; for large ones, see the
+// beam search of `QuasiCliqueTransformer` with a `CliqueRelaxation`.
+pub trait RelaxedCliques: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // node ids in increasing order, and the neighbors of each, by position (leaving out
+    // self-loops).
+    fn get_numbered_neighbors(&self) -> (Vec<NodeId>, Vec<RoaringBitmap>) {
+        let ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, u32> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i as u32))
+            .collect();
+        let neighbors: Vec<RoaringBitmap> = ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|neighbor_id| neighbor_id != id)
+                    .map(|neighbor_id| positions[&neighbor_id])
+                    .collect()
+            })
+            .collect();
+        (ids, neighbors)
+    }
+
+    // positions of nodes, or an error if one isn't in the graph.
+    fn get_positions(&self, ids: &[NodeId], nodes: &[NodeId]) -> CLQResult<RoaringBitmap> {
+        nodes
+            .iter()
+            .map(|id| match ids.binary_search(id) {
+                Ok(i) => Ok(i as u32),
+                Err(_) => Err(CLQError::from(format!("No such node: {}", id.value()))),
+            })
+            .collect()
+    }
+
+    // true if nodes form a k-plex. Returns an error if a node isn't in the graph.
+    fn is_k_plex(&self, nodes: &[NodeId], k: usize) -> CLQResult<bool> {
+        let (ids, neighbors) = self.get_numbered_neighbors();
+        let positions = self.get_positions(&ids, nodes)?;
+        Ok(is_k_plex_within(&neighbors, &positions, k))
+    }
+
+    // true if nodes form a k-club. Returns an error if a node isn't in the graph.
+    fn is_k_club(&self, nodes: &[NodeId], k: usize) -> CLQResult<bool> {
+        let (ids, neighbors) = self.get_numbered_neighbors();
+        let positions = self.get_positions(&ids, nodes)?;
+        Ok(is_k_club_within(&neighbors, &positions, k))
+    }
+
+    // A maximum k-plex, sorted by node id, by branch and bound. Returns an error if k is 0.
+    fn get_max_k_plex(&self, k: usize) -> CLQResult<Vec<NodeId>> {
+        if k == 0 {
+            return Err(CLQError::from("k must be at least 1"));
+        }
+        let (ids, neighbors) = self.get_numbered_neighbors();
+        let mut best = RoaringBitmap::new();
+        let candidates: RoaringBitmap = (0..ids.len() as u32).collect();
+        grow_k_plex(
+            &neighbors,
+            k,
+            &mut RoaringBitmap::new(),
+            candidates,
+            &mut best,
+        );
+        Ok(best.iter().map(|v| ids[v as usize]).collect())
+    }
+
+    // A maximum k-club, sorted by node id, by branch and bound (dropping nodes from the
+    // whole graph). Returns an error if k is 0.
+    fn get_max_k_club(&self, k: usize) -> CLQResult<Vec<NodeId>> {
+        if k == 0 {
+            return Err(CLQError::from("k must be at least 1"));
+        }
+        let (ids, neighbors) = self.get_numbered_neighbors();
+        let mut best = RoaringBitmap::new();
+        let nodes: RoaringBitmap = (0..ids.len() as u32).collect();
+        drop_to_k_club(&neighbors, k, nodes, RoaringBitmap::new(), &mut best);
+        Ok(best.iter().map(|v| ids[v as usize]).collect())
+    }
+}
@@ -0,0 +1,221 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::prelude::*;
+
+// rewiring gives up after this many attempts per requested swap, e.g. on graphs (such as
+// complete graphs) where few or no swaps are possible.
+const MAX_ATTEMPTS_PER_SWAP: usize = 10;
+
+// Double edge swaps: edges (a, b) and (c, d) are replaced with (a, d) and (c, b), unless
+// that would create a self-loop or a multi-edge. Every node keeps its degree. Returns the
+// number of swaps made, which is below num_swaps if rewiring gave up.
+pub fn rewire_edges<R: Rng>(
+    edges: &mut [(NodeId, NodeId)],
+    num_swaps: usize,
+    rng: &mut R,
+) -> usize {
+    if edges.len() < 2 {
+        return 0;
+    }
+    let ordered = |x: NodeId, y: NodeId| if x < y { (x, y) } else { (y, x) };
+    let mut edge_set: FxHashSet<(NodeId, NodeId)> =
+        edges.iter().map(|(x, y)| ordered(*x, *y)).collect();
+    let mut num_swapped = 0;
+    for _ in 0..num_swaps * MAX_ATTEMPTS_PER_SWAP {
+        if num_swapped == num_swaps {
+            break;
+        }
+        let i = rng.gen_range(0..edges.len());
+        let j = rng.gen_range(0..edges.len());
+        let (a, b) = edges[i];
+        // either orientation of the second edge gives a different swap.
+        let (c, d) = if rng.gen::<bool>() {
+            edges[j]
+        } else {
+            (edges[j].1, edges[j].0)
+        };
+        if a == d
+            || c == b
+            || edge_set.contains(&ordered(a, d))
+            || edge_set.contains(&ordered(c, b))
+        {
+            continue;
+        }
+        edge_set.remove(&ordered(a, b));
+        edge_set.remove(&ordered(c, d));
+        edge_set.insert(ordered(a, d));
+        edge_set.insert(ordered(c, b));
+        edges[i] = (a, d);
+        edges[j] = (c, b);
+        num_swapped += 1;
+    }
+    num_swapped
+}
+
+// Whether the nodes touched by edges form a single connected component.
+fn is_connected(edges: &[(NodeId, NodeId)]) -> bool {
+    let mut adjacency: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+    for (x, y) in edges {
+        adjacency.entry(*x).or_default().push(*y);
+        adjacency.entry(*y).or_default().push(*x);
+    }
+    let start = match edges.first() {
+        Some((x, _)) => *x,
+        None => return true,
+    };
+    let mut visited: FxHashSet<NodeId> = FxHashSet::default();
+    visited.insert(start);
+    let mut stack: Vec<NodeId> = vec![start];
+    while let Some(id) = stack.pop() {
+        for neighbor_id in &adjacency[&id] {
+            if visited.insert(*neighbor_id) {
+                stack.push(*neighbor_id);
+            }
+        }
+    }
+    visited.len() == adjacency.len()
+}
+
+// Double edge swaps (see rewire_edges) which keep the graph formed by edges connected.
+// Checking connectivity after every swap would take O(E) time per swap, so swaps are made
+// in windows, checking connectivity after each: a window which disconnects the graph is
+// undone and the next window halved, otherwise the next window grows by one (Gkantsidis,
+// Mihail & Zegura, 2003). Returns the number of swaps made, or an error if the edges
+// don't form a connected graph to begin with.
+pub fn rewire_edges_connected<R: Rng>(
+    edges: &mut [(NodeId, NodeId)],
+    num_swaps: usize,
+    rng: &mut R,
+) -> CLQResult<usize> {
+    if !is_connected(edges) {
+        return Err(CLQError::from(
+            "Cannot preserve the connectivity of a disconnected graph",
+        ));
+    }
+    let mut window: usize = 1;
+    let mut num_swapped: usize = 0;
+    let mut num_attempted: usize = 0;
+    while num_swapped < num_swaps && num_attempted < num_swaps * MAX_ATTEMPTS_PER_SWAP {
+        let saved: Vec<(NodeId, NodeId)> = edges.to_vec();
+        let batch_size = window.min(num_swaps - num_swapped);
+        let batch_swapped = rewire_edges(edges, batch_size, rng);
+        if batch_swapped == 0 {
+            break;
+        }
+        num_attempted += batch_size;
+        if is_connected(edges) {
+            num_swapped += batch_swapped;
+            window += 1;
+        } else {
+            edges.copy_from_slice(&saved);
+            window = window.div_ceil(2);
+        }
+    }
+    Ok(num_swapped)
+}
+
+// Splits a graph into its self-loops (as nodes with no other neighbors) and its other
+// edges, as (smaller id, larger id), ordered by ids.
+fn split_self_loops<G: GraphBase<NodeType = SimpleNode>>(
+    graph: &G,
+) -> (FxHashMap<NodeId, SimpleNode>, Vec<(NodeId, NodeId)>) {
+    let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+    let mut edges: Vec<(NodeId, NodeId)> = Vec::new();
+    for id in graph.get_ordered_node_ids() {
+        let node = graph.get_node(id);
+        let mut neighbors = SimpleNeighbors::new();
+        if node.has_neighbor(id) {
+            neighbors.push(id);
+        }
+        edges.extend(node.neighbors.iter().filter(|x| **x > id).map(|x| (id, *x)));
+        nodes.insert(
+            id,
+            SimpleNode {
+                node_id: id,
+                neighbors,
+            },
+        );
+    }
+    (nodes, edges)
+}
+
+// Adds edges back to the nodes returned by split_self_loops.
+fn join_edges(
+    mut nodes: FxHashMap<NodeId, SimpleNode>,
+    edges: Vec<(NodeId, NodeId)>,
+) -> SimpleUndirectedGraph {
+    for (x, y) in edges {
+        nodes.get_mut(&x).unwrap().add_neighbor(y);
+        nodes.get_mut(&y).unwrap().add_neighbor(x);
+    }
+    SimpleUndirectedGraph {
+        ids: nodes.keys().cloned().collect(),
+        nodes,
+    }
+}
+
+pub trait Rewiring: GraphBase<NodeType = SimpleNode> {
+    // A random graph with the same degrees as this one (the configuration null model),
+    // obtained with swaps_per_edge double edge swaps per edge (see rewire_edges). All nodes
+    // are kept, and self-loops stay where they are.
+    fn get_rewired_graph(&self, swaps_per_edge: usize) -> SimpleUndirectedGraph {
+        self.get_rewired_graph_with_rng(swaps_per_edge, &mut rand::thread_rng())
+    }
+
+    // Rewired graph, with swaps drawn from the given random number generator.
+    fn get_rewired_graph_with_rng<R: Rng>(
+        &self,
+        swaps_per_edge: usize,
+        rng: &mut R,
+    ) -> SimpleUndirectedGraph {
+        let (nodes, mut edges) = split_self_loops(self);
+        let num_swaps = swaps_per_edge * edges.len();
+        rewire_edges(&mut edges, num_swaps, rng);
+        join_edges(nodes, edges)
+    }
+
+    // A random connected graph with the same degrees as this (connected) one, for
+    // statistics such as diameter or algebraic connectivity which only make sense on
+    // connected graphs (see rewire_edges_connected). Returns an error if the graph isn't
+    // connected (with self-loops ignored).
+    fn get_connected_rewired_graph(
+        &self,
+        swaps_per_edge: usize,
+    ) -> CLQResult<SimpleUndirectedGraph> {
+        self.get_connected_rewired_graph_with_rng(swaps_per_edge, &mut rand::thread_rng())
+    }
+
+    // Connected rewired graph, with swaps drawn from the given random number generator.
+    fn get_connected_rewired_graph_with_rng<R: Rng>(
+        &self,
+        swaps_per_edge: usize,
+        rng: &mut R,
+    ) -> CLQResult<SimpleUndirectedGraph> {
+        let (nodes, mut edges) = split_self_loops(self);
+        // nodes left without edges by split_self_loops are disconnected from the rest.
+        let num_connected = edges
+            .iter()
+            .flat_map(|(x, y)| [*x, *y])
+            .collect::<FxHashSet<NodeId>>()
+            .len();
+        if nodes.len() > 1 && num_connected < nodes.len() {
+            return Err(CLQError::from(
+                "Cannot preserve the connectivity of a disconnected graph",
+            ));
+        }
+        let num_swaps = swaps_per_edge * edges.len();
+        rewire_edges_connected(&mut edges, num_swaps, rng)?;
+        Ok(join_edges(nodes, edges))
+    }
+}
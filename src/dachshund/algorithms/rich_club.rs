@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::rewiring::rewire_edges;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::SimpleNode;
+use fxhash::FxHashSet;
+use rand::prelude::*;
+
+// fraction of the possible edges among rich nodes which are present, or None if there are
+// fewer than 2 rich nodes.
+fn get_rich_club_density(edges: &[(NodeId, NodeId)], rich: &FxHashSet<NodeId>) -> Option<f64> {
+    let n = rich.len();
+    if n < 2 {
+        return None;
+    }
+    let num_rich_edges = edges
+        .iter()
+        .filter(|(x, y)| rich.contains(x) && rich.contains(y))
+        .count();
+    Some(2.0 * num_rich_edges as f64 / (n * (n - 1)) as f64)
+}
+
+pub trait RichClub: GraphBase<NodeType = SimpleNode> {
+    // Edges as (smaller id, larger id), along with the nodes of degree greater than k.
+    // Self-loops are left out, and don't count towards degrees.
+    fn get_rich_club_edges(&self, k: usize) -> (Vec<(NodeId, NodeId)>, FxHashSet<NodeId>) {
+        let mut edges: Vec<(NodeId, NodeId)> = Vec::new();
+        let mut rich: FxHashSet<NodeId> = FxHashSet::default();
+        for node in self.get_nodes_iter() {
+            let id = node.node_id;
+            if node.neighbors.iter().filter(|x| **x != id).count() > k {
+                rich.insert(id);
+            }
+            edges.extend(node.neighbors.iter().filter(|x| **x > id).map(|x| (id, *x)));
+        }
+        (edges, rich)
+    }
+
+    // Rich-club coefficient (Zhou & Mondragon, 2004): the density of the subgraph induced
+    // by nodes of degree greater than k, or None if there are fewer than two such nodes.
+    fn get_rich_club_coefficient(&self, k: usize) -> Option<f64> {
+        let (edges, rich) = self.get_rich_club_edges(k);
+        get_rich_club_density(&edges, &rich)
+    }
+
+    // Rich-club coefficient, normalized by that of a random graph with the same degrees
+    // (Colizza et al., 2006), obtained by rewiring the graph with swaps_per_edge double edge
+    // swaps per edge. Values above 1 indicate high-degree nodes are more densely connected
+    // than degrees alone would explain. None if the coefficient of either graph is undefined
+    // or that of the random graph is 0.
+    fn get_normalized_rich_club_coefficient(&self, k: usize, swaps_per_edge: usize) -> Option<f64> {
+        self.get_normalized_rich_club_coefficient_with_rng(
+            k,
+            swaps_per_edge,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    // Normalized rich-club coefficient, rewiring with the given random number generator.
+    fn get_normalized_rich_club_coefficient_with_rng<R: Rng>(
+        &self,
+        k: usize,
+        swaps_per_edge: usize,
+        rng: &mut R,
+    ) -> Option<f64> {
+        let (mut edges, rich) = self.get_rich_club_edges(k);
+        let coefficient = get_rich_club_density(&edges, &rich)?;
+        let num_swaps = swaps_per_edge * edges.len();
+        rewire_edges(&mut edges, num_swaps, rng);
+        let random_coefficient = get_rich_club_density(&edges, &rich)?;
+        if random_coefficient == 0.0 {
+            return None;
+        }
+        Some(coefficient / random_coefficient)
+    }
+}
@@ -4,10 +4,15 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::hashing::{FastHashMap, FastHashSet};
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
-use std::collections::{HashMap, HashSet, VecDeque};
+use core::cmp::Reverse;
+use ordered_float::NotNan;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 type NodePredecessors = HashMap<NodeId, Vec<NodeId>>;
 pub trait ShortestPaths: GraphBase
@@ -15,7 +20,9 @@ where
     Self::NodeType: NodeBase<NodeIdType = NodeId>,
     <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
 {
-    // Dikstra's algorithm for shortest paths. Returns distance and parent mappings
+    // Dijkstra's algorithm for shortest paths (with every edge of length 1), using a binary
+    // heap. Returns distance and parent mappings: nodes not reachable from source have a
+    // distance of None, and source is its own parent.
     fn get_shortest_paths(
         &self,
         source: NodeId,
@@ -30,8 +37,6 @@ where
             HashSet<<Self::NodeType as NodeBase>::NodeIdType>,
         >,
     ) {
-        // TODO: this should be changed to a binary heap
-        let mut queue: HashSet<&NodeId> = HashSet::new();
         let mut dist: HashMap<NodeId, Option<usize>> = HashMap::new();
         let mut parents: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
 
@@ -40,34 +45,34 @@ where
             None => self.get_ids_iter().cloned().collect(),
         };
         for id in &targets {
-            queue.insert(id);
             dist.insert(*id, None);
             parents.insert(*id, HashSet::new());
         }
         *dist.get_mut(&source).unwrap() = Some(0);
 
-        while !queue.is_empty() {
-            let mut min_dist: Option<usize> = None;
-            let mut u: Option<&NodeId> = None;
-            // find next node u to visit
-            for maybe_u in &queue {
-                let d: Option<usize> = dist[maybe_u];
-                if let Some(d) = d {
-                    if min_dist.is_none() || d < min_dist.unwrap() {
-                        min_dist = Some(d);
-                        u = Some(maybe_u);
-                    }
-                }
+        let mut heap: BinaryHeap<Reverse<(usize, NodeId)>> = BinaryHeap::new();
+        heap.push(Reverse((0, source)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            // stale entries are left in the heap when distances decrease.
+            if dist[&u] != Some(d) {
+                continue;
             }
-            // remove u from queue
-            queue.remove(u.unwrap());
-            for e in self.get_node(*u.unwrap()).get_edges() {
-                let v = &e.get_neighbor_id();
-                if queue.contains(v) {
-                    let alt = min_dist.unwrap() + 1;
-                    if dist[v].is_none() || alt <= dist[v].unwrap() {
-                        *dist.get_mut(v).unwrap() = Some(alt);
-                        parents.get_mut(v).unwrap().insert(*u.unwrap());
+            for e in self.get_node(u).get_edges() {
+                let v = e.get_neighbor_id();
+                let alt = d + 1;
+                match dist.get(&v) {
+                    // v is outside of the targets.
+                    None => {}
+                    Some(Some(dv)) if alt > *dv => {}
+                    Some(Some(dv)) if alt == *dv => {
+                        parents.get_mut(&v).unwrap().insert(u);
+                    }
+                    Some(_) => {
+                        *dist.get_mut(&v).unwrap() = Some(alt);
+                        let v_parents = parents.get_mut(&v).unwrap();
+                        v_parents.clear();
+                        v_parents.insert(u);
+                        heap.push(Reverse((alt, v)));
                     }
                 }
             }
@@ -91,7 +96,7 @@ where
         // Count of shortest paths to from source to v
         let mut shortest_path_counts: HashMap<NodeId, u32> = HashMap::new();
         // Distances from source to v
-        let mut dists: HashMap<NodeId, i32> = HashMap::new();
+        let mut dists: FastHashMap<NodeId, i32> = FastHashMap::default();
 
         for node_id in self.get_ids_iter() {
             preds.insert(*node_id, Vec::new());
@@ -150,7 +155,7 @@ where
         parents: &HashMap<NodeId, HashSet<NodeId>>,
         destination: NodeId,
     ) -> HashMap<NodeId, Vec<Vec<NodeId>>> {
-        let mut nodes_by_distance: HashMap<usize, Vec<NodeId>> = HashMap::new();
+        let mut nodes_by_distance: FastHashMap<usize, Vec<NodeId>> = FastHashMap::default();
         for (node_id, distance) in dist {
             if *node_id != destination {
                 let d = distance.unwrap();
@@ -176,4 +181,148 @@ where
         }
         paths
     }
+
+    // Expands every node at distance level from one end of a bidirectional search, returning
+    // the length of the shortest path through the nodes met from the other end, if any.
+    fn expand_bfs_level(
+        &self,
+        frontier: &mut Vec<NodeId>,
+        dists: &mut FastHashMap<NodeId, usize>,
+        other_dists: &FastHashMap<NodeId, usize>,
+    ) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        let mut next_frontier: Vec<NodeId> = Vec::new();
+        for id in frontier.iter() {
+            let d = dists[id] + 1;
+            for e in self.get_node(*id).get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if let Some(other_d) = other_dists.get(&neighbor_id) {
+                    best = Some(best.map_or(d + other_d, |x| x.min(d + other_d)));
+                }
+                if let Entry::Vacant(entry) = dists.entry(neighbor_id) {
+                    entry.insert(d);
+                    next_frontier.push(neighbor_id);
+                }
+            }
+        }
+        *frontier = next_frontier;
+        best
+    }
+
+    /// Length of a shortest path between source and target, or None if they aren't
+    /// connected. Uses a bidirectional BFS, growing whichever side has the smaller frontier
+    /// a level at a time, so that only balls of about half the distance around each end are
+    /// explored, rather than everything within the full distance of source.
+    fn get_distance(&self, source: NodeId, target: NodeId) -> CLQResult<Option<usize>> {
+        for id in [source, target] {
+            if !self.has_node(id) {
+                return Err(CLQError::from(format!("No such node: {}", id.value())));
+            }
+        }
+        if source == target {
+            return Ok(Some(0));
+        }
+        let mut source_dists: FastHashMap<NodeId, usize> = FastHashMap::default();
+        let mut target_dists: FastHashMap<NodeId, usize> = FastHashMap::default();
+        source_dists.insert(source, 0);
+        target_dists.insert(target, 0);
+        let mut source_frontier = vec![source];
+        let mut target_frontier = vec![target];
+        while !source_frontier.is_empty() && !target_frontier.is_empty() {
+            let found = if source_frontier.len() <= target_frontier.len() {
+                self.expand_bfs_level(&mut source_frontier, &mut source_dists, &target_dists)
+            } else {
+                self.expand_bfs_level(&mut target_frontier, &mut target_dists, &source_dists)
+            };
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Distances between each of the given pairs of nodes, in the same order, as with
+    /// get_distance. Errors if any node is missing.
+    fn get_distances(&self, pairs: &[(NodeId, NodeId)]) -> CLQResult<Vec<Option<usize>>> {
+        pairs
+            .iter()
+            .map(|(source, target)| self.get_distance(*source, *target))
+            .collect()
+    }
+
+    // Edges leaving a node, with their lengths. Every edge has a length of 1 in unweighted
+    // graphs; weighted graphs override this.
+    fn get_shortest_path_edges(&self, node: &Self::NodeType) -> Vec<(NodeId, f64)> {
+        node.get_outgoing_edges()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+
+    /// Dijkstra's algorithm, with a binary heap, over edge lengths given by
+    /// get_shortest_path_edges. Returns distances from source to each reachable node, and
+    /// the predecessors of each reachable node on its shortest paths from source (the
+    /// source having none). Runs in O((V + E) log V) time. Errors if source is missing, or
+    /// if a negative (or NaN) edge length is reached.
+    fn get_weighted_shortest_paths(
+        &self,
+        source: NodeId,
+    ) -> CLQResult<(HashMap<NodeId, f64>, NodePredecessors)> {
+        let (_order, dists, preds) = self.get_shortest_paths_dijkstra(source)?;
+        Ok((dists, preds))
+    }
+
+    /// Same as get_weighted_shortest_paths, also returning reachable nodes in the order
+    /// their distances were settled (nondecreasing by distance, each node after all its
+    /// predecessors), as get_shortest_paths_bfs does for unweighted graphs.
+    fn get_shortest_paths_dijkstra(
+        &self,
+        source: NodeId,
+    ) -> CLQResult<(Vec<NodeId>, HashMap<NodeId, f64>, NodePredecessors)> {
+        if !self.has_node(source) {
+            return Err(CLQError::from(format!("No such node: {}", source.value())));
+        }
+        let mut dists: HashMap<NodeId, f64> = HashMap::new();
+        let mut preds: NodePredecessors = HashMap::new();
+        let mut settled: FastHashSet<NodeId> = FastHashSet::default();
+        let mut order: Vec<NodeId> = Vec::new();
+        let mut heap: BinaryHeap<(Reverse<NotNan<f64>>, NodeId)> = BinaryHeap::new();
+        dists.insert(source, 0.0);
+        preds.insert(source, Vec::new());
+        heap.push((Reverse(NotNan::new(0.0).unwrap()), source));
+        while let Some((Reverse(d), id)) = heap.pop() {
+            // stale entries are left in the heap when distances decrease.
+            if !settled.insert(id) {
+                continue;
+            }
+            order.push(id);
+            for (neighbor_id, length) in self.get_shortest_path_edges(self.get_node(id)) {
+                if length.is_nan() || length < 0.0 {
+                    return Err(CLQError::from(format!(
+                        "Edge lengths must be non-negative, got {} from {} to {}",
+                        length,
+                        id.value(),
+                        neighbor_id.value()
+                    )));
+                }
+                // settled nodes can't be reached any sooner (but may be reached as soon,
+                // e.g. over self-loops or edges of length 0).
+                if settled.contains(&neighbor_id) {
+                    continue;
+                }
+                let alt = d.into_inner() + length;
+                match dists.get(&neighbor_id) {
+                    Some(current) if alt > *current => {}
+                    Some(current) if alt == *current => {
+                        preds.get_mut(&neighbor_id).unwrap().push(id);
+                    }
+                    _ => {
+                        dists.insert(neighbor_id, alt);
+                        preds.insert(neighbor_id, vec![id]);
+                        heap.push((Reverse(NotNan::new(alt).unwrap()), neighbor_id));
+                    }
+                }
+            }
+        }
+        Ok((order, dists, preds))
+    }
 }
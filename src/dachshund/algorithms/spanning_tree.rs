@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeEdgeBase};
+use fxhash::FxHashMap;
+use rand::prelude::*;
+
+pub trait SpanningTree: GraphBase<NodeType = WeightedNode> {
+    // Kruskal's algorithm: edges are considered in order of increasing weight, and kept
+    // whenever they join two distinct trees (tracked with a union-find over node positions).
+    // Returns the edges of a minimum spanning tree of each connected component (a forest,
+    // if the graph is disconnected) as (smaller id, larger id, weight), in the order in which
+    // they were added, along with their total weight. Ties are broken by node ids, so the
+    // result is deterministic. Self-loops are never part of the forest.
+    fn get_minimum_spanning_forest(&self) -> (Vec<(NodeId, NodeId, f64)>, f64) {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut edges: Vec<(f64, NodeId, NodeId)> = Vec::new();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if id < neighbor_id {
+                    edges.push((e.get_weight(), id, neighbor_id));
+                }
+            }
+        }
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+
+        let mut parents: Vec<usize> = (0..node_ids.len()).collect();
+        fn find(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let mut forest: Vec<(NodeId, NodeId, f64)> = Vec::new();
+        let mut total_weight: f64 = 0.0;
+        for (weight, id, neighbor_id) in edges {
+            let root = find(&mut parents, positions[&id]);
+            let neighbor_root = find(&mut parents, positions[&neighbor_id]);
+            if root != neighbor_root {
+                parents[root] = neighbor_root;
+                forest.push((id, neighbor_id, weight));
+                total_weight += weight;
+            }
+        }
+        (forest, total_weight)
+    }
+}
+
+pub trait UniformSpanningTree: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Wilson's algorithm ("Generating random spanning trees more quickly than the cover
+    // time", 1996): starting from a tree made of a root, a random walk is run from each node
+    // not yet in the tree until it hits the tree, and its loop-erased path is added to the
+    // tree. The tree obtained is drawn uniformly at random among all spanning trees (with
+    // repeated edges counted as distinct), in expected time proportional to the mean hitting
+    // time of the root. Returns the edges of a uniform spanning tree of each connected
+    // component (rooted at its smallest id), as (smaller id, larger id), in the order in
+    // which they were added. Self-loops are never part of the forest.
+    fn get_uniform_spanning_forest_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<(NodeId, NodeId)> {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: Vec<Vec<usize>> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| positions[&e.get_neighbor_id()])
+                    .filter(|j| *j != i)
+                    .collect()
+            })
+            .collect();
+
+        // roots are the first nodes found in their component, going through nodes in order.
+        let mut in_tree: Vec<bool> = vec![false; node_ids.len()];
+        let mut seen: Vec<bool> = vec![false; node_ids.len()];
+        for root in 0..node_ids.len() {
+            if seen[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            seen[root] = true;
+            let mut stack: Vec<usize> = vec![root];
+            while let Some(i) = stack.pop() {
+                for j in &neighbors[i] {
+                    if !seen[*j] {
+                        seen[*j] = true;
+                        stack.push(*j);
+                    }
+                }
+            }
+        }
+
+        // next[i] is the node the walk last left i for, so that following it erases loops.
+        let mut next: Vec<usize> = (0..node_ids.len()).collect();
+        let mut forest: Vec<(NodeId, NodeId)> = Vec::new();
+        for start in 0..node_ids.len() {
+            let mut i = start;
+            while !in_tree[i] {
+                next[i] = *neighbors[i].choose(rng).unwrap();
+                i = next[i];
+            }
+            i = start;
+            while !in_tree[i] {
+                in_tree[i] = true;
+                let (a, b) = (node_ids[i], node_ids[next[i]]);
+                forest.push((a.min(b), a.max(b)));
+                i = next[i];
+            }
+        }
+        forest
+    }
+
+    fn get_uniform_spanning_forest(&self, seed: u64) -> Vec<(NodeId, NodeId)> {
+        self.get_uniform_spanning_forest_with_rng(&mut StdRng::seed_from_u64(seed))
+    }
+}
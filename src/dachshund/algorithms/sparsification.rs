@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::laplacian::{Laplacian, SparseLaplacian};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::prelude::*;
+use std::collections::VecDeque;
+
+/// Relative residual at which conjugate gradient solves of Laplacian systems stop.
+pub const LAPLACIAN_SOLVER_TOLERANCE: f64 = 1e-8;
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+// Solves laplacian * x = b by conjugate gradient, for b summing to zero over each connected
+// component (as differences of unit vectors do), in which case the solution is the one
+// orthogonal to the constant vectors of the components, i.e. pseudoinverse * b.
+fn solve_laplacian(laplacian: &SparseLaplacian, b: &[f64]) -> Vec<f64> {
+    let n = laplacian.num_rows();
+    let mut x = vec![0.0; n];
+    let mut r = b.to_vec();
+    let mut p = r.clone();
+    let mut rr = dot(&r, &r);
+    let threshold = rr * LAPLACIAN_SOLVER_TOLERANCE * LAPLACIAN_SOLVER_TOLERANCE;
+    // exact in n steps, barring rounding errors.
+    for _ in 0..(10 * n).max(10) {
+        if rr <= threshold || rr == 0.0 {
+            break;
+        }
+        let lp = laplacian.multiply(&p);
+        let alpha = rr / dot(&p, &lp);
+        for i in 0..n {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * lp[i];
+        }
+        let next_rr = dot(&r, &r);
+        let beta = next_rr / rr;
+        for i in 0..n {
+            p[i] = r[i] + beta * p[i];
+        }
+        rr = next_rr;
+    }
+    x
+}
+
+pub trait Sparsification: GraphBase<NodeType = SimpleNode> + Laplacian {
+    // Edges of the graph, as (smaller id, larger id), in order.
+    fn get_ordered_edges(&self) -> Vec<(NodeId, NodeId)> {
+        let mut edges: Vec<(NodeId, NodeId)> = self
+            .get_nodes_iter()
+            .flat_map(|node| {
+                node.neighbors
+                    .iter()
+                    .filter(move |id| node.node_id < **id)
+                    .map(move |id| (node.node_id, *id))
+            })
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    // A graph with all the nodes of this one, and only the given edges between them.
+    fn get_subgraph_with_edges(&self, edges: &[(NodeId, NodeId)]) -> SimpleUndirectedGraph {
+        let ids = self.get_ordered_node_ids();
+        let mut neighbors: FxHashMap<NodeId, SimpleNeighbors> =
+            ids.iter().map(|id| (*id, SimpleNeighbors::new())).collect();
+        for (a, b) in edges {
+            neighbors.get_mut(a).unwrap().push(*b);
+            neighbors.get_mut(b).unwrap().push(*a);
+        }
+        let nodes: FxHashMap<NodeId, SimpleNode> = neighbors
+            .into_iter()
+            .map(|(id, neighbors)| (id, SimpleNode::new(id, neighbors)))
+            .collect();
+        SimpleUndirectedGraph { ids, nodes }
+    }
+
+    // Effective resistance between two nodes, seeing edges as unit resistors: the voltage
+    // between them when a unit current flows in at id1 and out at id2. Infinite for nodes in
+    // different connected components. Takes one Laplacian solve.
+    fn get_effective_resistance(&self, id1: NodeId, id2: NodeId) -> f64 {
+        if id1 == id2 {
+            return 0.0;
+        }
+        let mut visited: FxHashSet<NodeId> = FxHashSet::default();
+        let mut queue: VecDeque<NodeId> = VecDeque::from([id1]);
+        visited.insert(id1);
+        while let Some(id) = queue.pop_front() {
+            for neighbor in &self.get_node(id).neighbors {
+                if visited.insert(*neighbor) {
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        if !visited.contains(&id2) {
+            return f64::INFINITY;
+        }
+        let laplacian = self.get_sparse_laplacian_matrix();
+        let i = laplacian.ids.iter().position(|id| *id == id1).unwrap();
+        let j = laplacian.ids.iter().position(|id| *id == id2).unwrap();
+        let mut b = vec![0.0; laplacian.num_rows()];
+        b[i] = 1.0;
+        b[j] = -1.0;
+        let x = solve_laplacian(&laplacian, &b);
+        x[i] - x[j]
+    }
+
+    // Estimated effective resistance of every edge, as (smaller id, larger id, resistance),
+    // in order, following Spielman & Srivastava ("Graph Sparsification by Effective
+    // Resistances", 2008): resistances are squared distances between the columns of
+    // W^1/2 B L^+ (B being the edge-node incidence matrix), which are preserved up to a
+    // factor of 1 +- epsilon by num_projections ~ log(n) / epsilon^2 random projections, each
+    // taking one Laplacian solve (rather than one per edge).
+    fn get_effective_resistances_with_rng<R: Rng>(
+        &self,
+        num_projections: usize,
+        rng: &mut R,
+    ) -> Vec<(NodeId, NodeId, f64)> {
+        let laplacian = self.get_sparse_laplacian_matrix();
+        let positions: FxHashMap<NodeId, usize> = laplacian
+            .ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let edges: Vec<(usize, usize)> = self
+            .get_ordered_edges()
+            .into_iter()
+            .map(|(a, b)| (positions[&a], positions[&b]))
+            .collect();
+        let scale = 1.0 / (num_projections.max(1) as f64).sqrt();
+        let mut resistances: Vec<f64> = vec![0.0; edges.len()];
+        for _ in 0..num_projections.max(1) {
+            let mut b = vec![0.0; laplacian.num_rows()];
+            for (i, j) in &edges {
+                let sign = if rng.gen::<bool>() { scale } else { -scale };
+                b[*i] += sign;
+                b[*j] -= sign;
+            }
+            let z = solve_laplacian(&laplacian, &b);
+            for (resistance, (i, j)) in resistances.iter_mut().zip(&edges) {
+                *resistance += (z[*i] - z[*j]).powi(2);
+            }
+        }
+        edges
+            .into_iter()
+            .zip(resistances)
+            .map(|((i, j), resistance)| (laplacian.ids[i], laplacian.ids[j], resistance))
+            .collect()
+    }
+
+    fn get_effective_resistances(&self, num_projections: usize) -> Vec<(NodeId, NodeId, f64)> {
+        self.get_effective_resistances_with_rng(num_projections, &mut StdRng::seed_from_u64(0))
+    }
+
+    // Edges of a spectral sparsifier of the graph, as (smaller id, larger id, weight): each
+    // edge is kept with probability p = min(1, 4 ln(n) R / epsilon^2), R being its
+    // (estimated) effective resistance, and weighted by 1 / p, so that the weighted
+    // Laplacian approximates the graph's, and with it the size of every cut, within a factor
+    // of about 1 +- epsilon. As effective resistances sum to the number of nodes less that
+    // of connected components, about 4 n ln(n) / epsilon^2 edges are kept, whatever the
+    // density of the graph, and bridges (of resistance 1) are kept unless epsilon is large.
+    fn get_spectral_sparsifier_edges_with_rng<R: Rng>(
+        &self,
+        epsilon: f64,
+        rng: &mut R,
+    ) -> CLQResult<Vec<(NodeId, NodeId, f64)>> {
+        if epsilon <= 0.0 || epsilon.is_nan() {
+            return Err(CLQError::from(format!(
+                "Sparsification needs a positive epsilon, got {}",
+                epsilon
+            )));
+        }
+        let log_n = (self.count_nodes().max(2) as f64).ln();
+        // resistances only have to be known within a constant factor to sample edges.
+        let num_projections = (8.0 * log_n).ceil() as usize;
+        let oversampling = 4.0 * log_n / (epsilon * epsilon);
+        Ok(self
+            .get_effective_resistances_with_rng(num_projections, rng)
+            .into_iter()
+            .filter_map(|(a, b, resistance)| {
+                let p = (oversampling * resistance).min(1.0);
+                if p > 0.0 && rng.gen::<f64>() < p {
+                    Some((a, b, 1.0 / p))
+                } else {
+                    None
+                }
+            })
+            .collect())
+    }
+
+    // A spectral sparsifier of the graph (see get_spectral_sparsifier_edges_with_rng), with
+    // all of its nodes. Its edges carry no weights, so cut sizes are only preserved once
+    // edges are weighted as given by get_spectral_sparsifier_edges_with_rng.
+    fn get_spectral_sparsifier_with_rng<R: Rng>(
+        &self,
+        epsilon: f64,
+        rng: &mut R,
+    ) -> CLQResult<SimpleUndirectedGraph> {
+        let edges: Vec<(NodeId, NodeId)> = self
+            .get_spectral_sparsifier_edges_with_rng(epsilon, rng)?
+            .into_iter()
+            .map(|(a, b, _weight)| (a, b))
+            .collect();
+        Ok(self.get_subgraph_with_edges(&edges))
+    }
+
+    fn get_spectral_sparsifier(&self, epsilon: f64) -> CLQResult<SimpleUndirectedGraph> {
+        self.get_spectral_sparsifier_with_rng(epsilon, &mut StdRng::seed_from_u64(0))
+    }
+
+    // A t-spanner of the graph: a subgraph, with all of its nodes, in which the distance
+    // between any two nodes is at most stretch times their distance in the graph. Built
+    // greedily (Althofer et al., 1993), going through edges in order and keeping those whose
+    // ends are still more than stretch hops apart in the spanner. With stretch = 2k - 1, the
+    // spanner has O(n^(1 + 1/k)) edges.
+    fn get_spanner(&self, stretch: f64) -> CLQResult<SimpleUndirectedGraph> {
+        if stretch < 1.0 || stretch.is_nan() {
+            return Err(CLQError::from(format!(
+                "A spanner needs a stretch of at least 1, got {}",
+                stretch
+            )));
+        }
+        let max_hops = stretch.floor() as usize;
+        let mut spanner: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+        let mut kept: Vec<(NodeId, NodeId)> = Vec::new();
+        let mut hops: FxHashMap<NodeId, usize> = FxHashMap::default();
+        let mut queue: VecDeque<NodeId> = VecDeque::new();
+        for (a, b) in self.get_ordered_edges() {
+            // breadth-first search from a in the spanner, up to max_hops.
+            hops.clear();
+            queue.clear();
+            hops.insert(a, 0);
+            queue.push_back(a);
+            while let Some(id) = queue.pop_front() {
+                let h = hops[&id];
+                if id == b || h == max_hops {
+                    continue;
+                }
+                for neighbor in spanner.get(&id).into_iter().flatten() {
+                    if !hops.contains_key(neighbor) {
+                        hops.insert(*neighbor, h + 1);
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+            if !hops.contains_key(&b) {
+                spanner.entry(a).or_default().push(b);
+                spanner.entry(b).or_default().push(a);
+                kept.push((a, b));
+            }
+        }
+        Ok(self.get_subgraph_with_edges(&kept))
+    }
+}
@@ -0,0 +1,295 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+// sweeps over all nodes while annealing, the inverse temperature rising linearly from
+// INITIAL_BETA to FINAL_BETA.
+const NUM_ANNEALING_SWEEPS: usize = 100;
+const INITIAL_BETA: f64 = 1.0;
+const FINAL_BETA: f64 = 10.0;
+// greedy sweeps after annealing, each stopping early if no node moves.
+const MAX_GREEDY_SWEEPS: usize = 100;
+// probability of proposing a uniformly random block rather than that of a random neighbor.
+const RANDOM_BLOCK_PROBABILITY: f64 = 0.1;
+
+/// Block assignments of a degree-corrected stochastic block model fit to a graph.
+pub struct BlockModel {
+    // block of each node, from 0 to num_blocks - 1.
+    pub blocks: HashMap<NodeId, usize>,
+    // number of nodes in each block.
+    pub block_sizes: Vec<usize>,
+    // description length of the graph under the model, in nats: lower is better, including
+    // when comparing fits with different numbers of blocks.
+    pub description_length: f64,
+}
+
+fn x_log_x(x: f64) -> f64 {
+    if x > 0.0 {
+        x * x.ln()
+    } else {
+        0.0
+    }
+}
+
+// h(x) = (1 + x) ln(1 + x) - x ln(x).
+fn h(x: f64) -> f64 {
+    x_log_x(1.0 + x) - x_log_x(x)
+}
+
+// Edge counts between blocks: edges[r][s] counts the edges between blocks r and s, and
+// edges[r][r] twice the edges within block r, so that totals[r], the sum of edges[r], is the
+// total degree of block r.
+struct BlockState {
+    blocks: Vec<usize>,
+    block_sizes: Vec<usize>,
+    edges: Vec<Vec<f64>>,
+    totals: Vec<f64>,
+}
+impl BlockState {
+    fn new(blocks: Vec<usize>, neighbors: &[Vec<usize>], num_blocks: usize) -> Self {
+        let mut block_sizes: Vec<usize> = vec![0; num_blocks];
+        let mut edges: Vec<Vec<f64>> = vec![vec![0.0; num_blocks]; num_blocks];
+        let mut totals: Vec<f64> = vec![0.0; num_blocks];
+        for (v, v_neighbors) in neighbors.iter().enumerate() {
+            block_sizes[blocks[v]] += 1;
+            for u in v_neighbors {
+                edges[blocks[v]][blocks[*u]] += 1.0;
+                totals[blocks[v]] += 1.0;
+            }
+        }
+        Self {
+            blocks,
+            block_sizes,
+            edges,
+            totals,
+        }
+    }
+
+    // sum over pairs of blocks of e_rs ln(e_rs / (e_r e_s)), which fitting maximizes.
+    fn get_log_likelihood(&self) -> f64 {
+        let within: f64 = self.edges.iter().flatten().map(|e| x_log_x(*e)).sum();
+        let totals: f64 = self.totals.iter().map(|e| x_log_x(*e)).sum();
+        within - 2.0 * totals
+    }
+
+    // number of edges from v to each block, in the order the blocks are first reached.
+    fn count_ties_by_block(&self, v: usize, neighbors: &[Vec<usize>]) -> Vec<(usize, f64)> {
+        let mut ties: Vec<(usize, f64)> = Vec::new();
+        let mut positions: FxHashMap<usize, usize> = FxHashMap::default();
+        for u in &neighbors[v] {
+            let block = self.blocks[*u];
+            let i = *positions.entry(block).or_insert_with(|| {
+                ties.push((block, 0.0));
+                ties.len() - 1
+            });
+            ties[i].1 += 1.0;
+        }
+        ties
+    }
+
+    // change in log likelihood if v moved from its block to block s, given its ties to each
+    // block and its degree.
+    fn get_move_delta(&self, v: usize, s: usize, ties: &[(usize, f64)], degree: f64) -> f64 {
+        let r = self.blocks[v];
+        let ties_to = |block: usize| -> f64 {
+            ties.iter()
+                .find(|(b, _)| *b == block)
+                .map_or(0.0, |(_, k)| *k)
+        };
+        let (k_r, k_s) = (ties_to(r), ties_to(s));
+        let mut delta: f64 = 0.0;
+        for (t, k_t) in ties {
+            if *t == r || *t == s {
+                continue;
+            }
+            // edges[r][t] and edges[t][r] both change, as do those of s.
+            delta += 2.0
+                * (x_log_x(self.edges[r][*t] - k_t) - x_log_x(self.edges[r][*t])
+                    + x_log_x(self.edges[s][*t] + k_t)
+                    - x_log_x(self.edges[s][*t]));
+        }
+        delta += x_log_x(self.edges[r][r] - 2.0 * k_r) - x_log_x(self.edges[r][r]);
+        delta += x_log_x(self.edges[s][s] + 2.0 * k_s) - x_log_x(self.edges[s][s]);
+        delta += 2.0 * (x_log_x(self.edges[r][s] + k_r - k_s) - x_log_x(self.edges[r][s]));
+        delta -= 2.0
+            * (x_log_x(self.totals[r] - degree) - x_log_x(self.totals[r])
+                + x_log_x(self.totals[s] + degree)
+                - x_log_x(self.totals[s]));
+        delta
+    }
+
+    fn move_node(&mut self, v: usize, s: usize, ties: &[(usize, f64)], degree: f64) {
+        let r = self.blocks[v];
+        for (t, k_t) in ties {
+            // v's ties to t now run from s rather than r, on both ends of the edge.
+            self.edges[r][*t] -= k_t;
+            self.edges[*t][r] -= k_t;
+            self.edges[s][*t] += k_t;
+            self.edges[*t][s] += k_t;
+        }
+        self.totals[r] -= degree;
+        self.totals[s] += degree;
+        self.block_sizes[r] -= 1;
+        self.block_sizes[s] += 1;
+        self.blocks[v] = s;
+    }
+
+    // One sweep over the nodes, in a random order: each proposes to move to the block of a
+    // random neighbor (or, with some probability, to a random block), and does so if that
+    // increases the log likelihood, or otherwise with probability exp(beta * delta). None for
+    // beta means only improving moves are made. Moves that would empty a block are
+    // rejected. Returns the number of moves made.
+    fn sweep<R: Rng>(&mut self, neighbors: &[Vec<usize>], beta: Option<f64>, rng: &mut R) -> usize {
+        let num_blocks = self.block_sizes.len();
+        let mut order: Vec<usize> = (0..neighbors.len()).collect();
+        order.shuffle(rng);
+        let mut num_moves: usize = 0;
+        for v in order {
+            if neighbors[v].is_empty() || self.block_sizes[self.blocks[v]] == 1 {
+                continue;
+            }
+            let s = if rng.gen::<f64>() < RANDOM_BLOCK_PROBABILITY {
+                rng.gen_range(0..num_blocks)
+            } else {
+                self.blocks[*neighbors[v].choose(rng).unwrap()]
+            };
+            if s == self.blocks[v] {
+                continue;
+            }
+            let ties = self.count_ties_by_block(v, neighbors);
+            let degree = neighbors[v].len() as f64;
+            let delta = self.get_move_delta(v, s, &ties, degree);
+            let accept = match beta {
+                _ if delta > 0.0 => true,
+                Some(beta) => rng.gen::<f64>() < (beta * delta).exp(),
+                None => false,
+            };
+            if accept {
+                self.move_node(v, s, &ties, degree);
+                num_moves += 1;
+            }
+        }
+        num_moves
+    }
+}
+
+pub trait StochasticBlockModel: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Fits a degree-corrected stochastic block model (Karrer & Newman, 2011) with num_blocks
+    // blocks, see get_sbm_blocks_with_rng.
+    fn get_sbm_blocks(&self, num_blocks: usize) -> CLQResult<BlockModel> {
+        self.get_sbm_blocks_with_rng(num_blocks, &mut rand::thread_rng())
+    }
+
+    // Fits a degree-corrected stochastic block model with num_blocks (non-empty) blocks, by
+    // simulated annealing from a random assignment: Metropolis moves of single nodes with a
+    // rising inverse temperature, keeping the most likely assignment at the end of a sweep,
+    // then greedy moves from it until no node moves. Self-loops are ignored. The description length is that of
+    // Peixoto (2013, "Parsimonious module inference in large networks"), the entropy of the
+    // graph given the blocks and degrees plus the information needed to encode the blocks,
+    // the edge counts between them and the degree distribution, so that the number of
+    // blocks with the lowest description length can be picked. Returns an error if the
+    // graph has no edges, or if num_blocks is 0 or more than the number of nodes.
+    fn get_sbm_blocks_with_rng<R: Rng>(
+        &self,
+        num_blocks: usize,
+        rng: &mut R,
+    ) -> CLQResult<BlockModel> {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        if num_blocks == 0 || num_blocks > n {
+            return Err(CLQError::from(format!(
+                "Number of blocks must be between 1 and the number of nodes ({n}), got {num_blocks}"
+            )));
+        }
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: Vec<Vec<usize>> = node_ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|neighbor_id| neighbor_id != id)
+                    .map(|neighbor_id| positions[&neighbor_id])
+                    .collect()
+            })
+            .collect();
+        let num_edges = neighbors.iter().map(|v| v.len()).sum::<usize>() as f64 / 2.0;
+        if num_edges == 0.0 {
+            return Err(CLQError::from(
+                "Cannot fit a block model to a graph with no edges",
+            ));
+        }
+
+        // a random assignment, with every block non-empty.
+        let mut order: Vec<usize> = (0..n).collect();
+        order.shuffle(rng);
+        let mut blocks: Vec<usize> = vec![0; n];
+        for (i, v) in order.into_iter().enumerate() {
+            blocks[v] = i % num_blocks;
+        }
+        let mut state = BlockState::new(blocks, &neighbors, num_blocks);
+        let mut best_log_likelihood = state.get_log_likelihood();
+        let mut best_blocks: Vec<usize> = state.blocks.clone();
+        for i in 0..NUM_ANNEALING_SWEEPS {
+            let beta = INITIAL_BETA
+                + (FINAL_BETA - INITIAL_BETA) * i as f64 / (NUM_ANNEALING_SWEEPS - 1) as f64;
+            state.sweep(&neighbors, Some(beta), rng);
+            let log_likelihood = state.get_log_likelihood();
+            if log_likelihood > best_log_likelihood {
+                best_log_likelihood = log_likelihood;
+                best_blocks.clone_from(&state.blocks);
+            }
+        }
+        let mut state = BlockState::new(best_blocks, &neighbors, num_blocks);
+        for _ in 0..MAX_GREEDY_SWEEPS {
+            if state.sweep(&neighbors, None, rng) == 0 {
+                break;
+            }
+        }
+
+        // S = -E - sum_i ln(k_i!) - 1/2 sum_rs e_rs ln(e_rs / (e_r e_s)).
+        let mut degree_counts: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut log_degree_factorials: f64 = 0.0;
+        for v_neighbors in &neighbors {
+            *degree_counts.entry(v_neighbors.len()).or_insert(0) += 1;
+            log_degree_factorials += (2..=v_neighbors.len())
+                .map(|j| (j as f64).ln())
+                .sum::<f64>();
+        }
+        let entropy = -num_edges - log_degree_factorials - state.get_log_likelihood() / 2.0;
+        // L = E h(B (B + 1) / 2E) + N ln B + N H(degrees).
+        let b = num_blocks as f64;
+        let degree_entropy: f64 = degree_counts
+            .values()
+            .map(|count| -x_log_x(*count as f64 / n as f64))
+            .sum();
+        let model_length = num_edges * h(b * (b + 1.0) / (2.0 * num_edges))
+            + n as f64 * b.ln()
+            + n as f64 * degree_entropy;
+
+        Ok(BlockModel {
+            blocks: node_ids.into_iter().zip(state.blocks).collect(),
+            block_sizes: state.block_sizes,
+            description_length: entropy + model_length,
+        })
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::prelude::*;
+
+/// Smallest reservoir TriestEstimator accepts, as required by its variance bounds.
+pub const MIN_RESERVOIR_SIZE: usize = 6;
+
+/// Estimates the number of triangles of a graph (overall and through each node) whose edges
+/// arrive one at a time, keeping a uniform sample of at most reservoir_size of them, as in
+/// TRIEST-IMPR (De Stefani et al., "TRIEST: Counting Local and Global Triangles in Fully
+/// Dynamic Streams with Fixed Memory Size", KDD 2016). Each edge closes the triangles it
+/// forms with sampled edges, which are counted with a weight making up for the sampling, so
+/// that estimates are unbiased, and exact as long as all edges fit in the reservoir. Degrees
+/// are kept exactly (in memory linear in the number of nodes), to estimate transitivity.
+/// Edges are expected to appear once in the stream; self-loops are left out.
+pub struct TriestEstimator {
+    reservoir_size: usize,
+    rng: StdRng,
+    num_edges: usize,
+    reservoir: Vec<(NodeId, NodeId)>,
+    // neighbors of each node through sampled edges.
+    sample: FxHashMap<NodeId, FxHashSet<NodeId>>,
+    degrees: FxHashMap<NodeId, usize>,
+    num_triangles: f64,
+    local_triangles: FxHashMap<NodeId, f64>,
+}
+impl TriestEstimator {
+    pub fn new(reservoir_size: usize) -> CLQResult<Self> {
+        Self::new_with_rng(reservoir_size, StdRng::from_entropy())
+    }
+
+    pub fn new_with_rng(reservoir_size: usize, rng: StdRng) -> CLQResult<Self> {
+        if reservoir_size < MIN_RESERVOIR_SIZE {
+            return Err(CLQError::from(format!(
+                "A triangle estimator needs a reservoir of at least {} edges",
+                MIN_RESERVOIR_SIZE
+            )));
+        }
+        Ok(Self {
+            reservoir_size,
+            rng,
+            num_edges: 0,
+            reservoir: Vec::with_capacity(reservoir_size),
+            sample: FxHashMap::default(),
+            degrees: FxHashMap::default(),
+            num_triangles: 0.0,
+            local_triangles: FxHashMap::default(),
+        })
+    }
+
+    /// forgets all edges (and estimates), to start on another graph.
+    pub fn reset(&mut self) {
+        self.num_edges = 0;
+        self.reservoir.clear();
+        self.sample.clear();
+        self.degrees.clear();
+        self.num_triangles = 0.0;
+        self.local_triangles.clear();
+    }
+
+    // adds an edge to the sample, or removes it from it.
+    fn set_sampled(&mut self, (a, b): (NodeId, NodeId), sampled: bool) {
+        for (x, y) in [(a, b), (b, a)] {
+            if sampled {
+                self.sample.entry(x).or_default().insert(y);
+            } else if let Some(neighbors) = self.sample.get_mut(&x) {
+                neighbors.remove(&y);
+                if neighbors.is_empty() {
+                    self.sample.remove(&x);
+                }
+            }
+        }
+    }
+
+    /// Adds the next edge of the stream.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) {
+        if a == b {
+            return;
+        }
+        self.num_edges += 1;
+        *self.degrees.entry(a).or_insert(0) += 1;
+        *self.degrees.entry(b).or_insert(0) += 1;
+
+        // each triangle closed with two sampled edges is counted with the inverse of the
+        // probability that both of them are in the sample.
+        let t = self.num_edges as f64;
+        let m = self.reservoir_size as f64;
+        let weight = ((t - 1.0) * (t - 2.0) / (m * (m - 1.0))).max(1.0);
+        if let (Some(a_neighbors), Some(b_neighbors)) = (self.sample.get(&a), self.sample.get(&b)) {
+            let (smaller, larger) = if a_neighbors.len() <= b_neighbors.len() {
+                (a_neighbors, b_neighbors)
+            } else {
+                (b_neighbors, a_neighbors)
+            };
+            let mut num_closed: usize = 0;
+            for c in smaller.intersection(larger) {
+                *self.local_triangles.entry(*c).or_insert(0.0) += weight;
+                num_closed += 1;
+            }
+            if num_closed > 0 {
+                let closed = num_closed as f64 * weight;
+                self.num_triangles += closed;
+                *self.local_triangles.entry(a).or_insert(0.0) += closed;
+                *self.local_triangles.entry(b).or_insert(0.0) += closed;
+            }
+        }
+
+        // reservoir sampling: the first edges fill it, and later ones replace a random one
+        // with probability reservoir_size / num_edges.
+        if self.reservoir.len() < self.reservoir_size {
+            self.reservoir.push((a, b));
+            self.set_sampled((a, b), true);
+        } else if self.rng.gen_range(0..self.num_edges) < self.reservoir_size {
+            let i = self.rng.gen_range(0..self.reservoir_size);
+            let evicted = std::mem::replace(&mut self.reservoir[i], (a, b));
+            self.set_sampled(evicted, false);
+            self.set_sampled((a, b), true);
+        }
+    }
+
+    /// number of edges added (leaving out self-loops).
+    pub fn count_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    /// whether every edge is in the reservoir, in which case estimates are exact.
+    pub fn is_exact(&self) -> bool {
+        self.num_edges <= self.reservoir_size
+    }
+
+    pub fn get_triangle_estimate(&self) -> f64 {
+        self.num_triangles
+    }
+
+    /// estimated number of triangles through the node (0 for unknown nodes).
+    pub fn get_local_triangle_estimate(&self, id: NodeId) -> f64 {
+        self.local_triangles.get(&id).cloned().unwrap_or(0.0)
+    }
+
+    /// estimated number of triangles through each node, leaving out those with none.
+    pub fn get_local_triangle_estimates(&self) -> &FxHashMap<NodeId, f64> {
+        &self.local_triangles
+    }
+
+    /// number of pairs of edges sharing a node (counted exactly).
+    pub fn count_wedges(&self) -> usize {
+        self.degrees
+            .values()
+            .map(|d| d * d.saturating_sub(1) / 2)
+            .sum()
+    }
+
+    /// estimated transitivity, 3 * triangles / wedges, as in `Transitivity::get_transitivity`.
+    /// NaN if there are no wedges.
+    pub fn get_transitivity_estimate(&self) -> f64 {
+        3.0 * self.num_triangles / self.count_wedges() as f64
+    }
+}
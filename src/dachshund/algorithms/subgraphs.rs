@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashSet;
+use std::collections::VecDeque;
+
+pub trait Subgraphs: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // The subgraph induced by the given nodes: a new graph with the nodes (leaving out
+    // those missing from this graph) and all edges between them.
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self;
+
+    // Ego network of a node: the subgraph induced by the nodes at most radius hops away
+    // from it (following outgoing edges), center included. Empty if center is missing.
+    fn ego_net(&self, center: NodeId, radius: usize) -> Self {
+        let mut nodes: FxHashSet<NodeId> = FxHashSet::default();
+        if self.has_node(center) {
+            nodes.insert(center);
+        }
+        let mut queue: VecDeque<(NodeId, usize)> = nodes.iter().map(|id| (*id, 0)).collect();
+        while let Some((id, distance)) = queue.pop_front() {
+            if distance == radius {
+                continue;
+            }
+            for e in self.get_node(id).get_outgoing_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if nodes.insert(neighbor_id) {
+                    queue.push_back((neighbor_id, distance + 1));
+                }
+            }
+        }
+        self.subgraph(&nodes)
+    }
+}
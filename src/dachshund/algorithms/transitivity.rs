@@ -4,6 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::algorithms::bitset_adjacency::BitsetAdjacency;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
@@ -41,8 +42,12 @@ where
 
     // Transitivity: 3 * number of triangles  / number of triples
     fn get_transitivity(&self) -> f64 {
-        let num_triangles =
-            Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triangle_count(*x)));
+        let num_triangles = match BitsetAdjacency::from_dense_graph(self) {
+            Some(adjacency) => (0..adjacency.count_nodes())
+                .map(|i| adjacency.count_triangles(i))
+                .sum(),
+            None => Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triangle_count(*x))),
+        };
 
         let num_triples =
             Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triples_count(*x)));
@@ -54,6 +59,11 @@ where
     // k~=26,000 gives an approximation w/ <1% chance of an error of more than 1 percentage point.
     // See http://jgaa.info/accepted/2005/SchankWagner2005.9.2.pdf for approximation guarantees.
     fn get_approx_transitivity(&self, samples: usize) -> f64 {
+        self.get_approx_transitivity_with_rng(samples, &mut rand::thread_rng())
+    }
+
+    // Approximate Transitivity, drawing samples from the given random number generator.
+    fn get_approx_transitivity_with_rng<R: Rng>(&self, samples: usize, rng: &mut R) -> f64 {
         let ordered_nodes = self
             .get_nodes_iter()
             .filter(|node| node.degree() >= 2)
@@ -67,26 +77,28 @@ where
         let dist = WeightedIndex::new(triples_counts).unwrap();
 
         let mut successes = 0;
-        let mut rng = rand::thread_rng();
         for _i in 0..samples {
             // Choose a random node weighted by degree.
-            let v = &ordered_nodes[dist.sample(&mut rng)];
-
-            // Choose 2 random nodes that are neighbors of j
-            let mut random_neighbors = v.get_edges().choose_multiple(&mut rng, 2).into_iter();
-            let next_random_neighbor = random_neighbors.next();
-            let u_id = next_random_neighbor.unwrap().get_neighbor_id();
-            let w_id = random_neighbors.next().unwrap().get_neighbor_id();
-
-            // TODO: No constant time way to check if there's an edge?
-            for edge in self.get_node(u_id).get_edges() {
-                if edge.get_neighbor_id() == w_id {
-                    successes += 1;
-                    break;
-                }
+            let v = ordered_nodes[dist.sample(rng)];
+            if self.sample_wedge_is_closed(v, rng) {
+                successes += 1;
             }
         }
 
         (successes as f64) / (samples as f64)
     }
+
+    // Samples a wedge centered at node v (which must have at least 2 neighbors),
+    // returning whether it is closed, i.e. part of a triangle.
+    fn sample_wedge_is_closed<R: Rng>(&self, v: &Self::NodeType, rng: &mut R) -> bool {
+        // Choose 2 random nodes that are neighbors of v
+        let mut random_neighbors = v.get_edges().choose_multiple(rng, 2).into_iter();
+        let u_id = random_neighbors.next().unwrap().get_neighbor_id();
+        let w_id = random_neighbors.next().unwrap().get_neighbor_id();
+
+        // TODO: No constant time way to check if there's an edge?
+        self.get_node(u_id)
+            .get_edges()
+            .any(|edge| edge.get_neighbor_id() == w_id)
+    }
 }
@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::bitset_adjacency::BitsetAdjacency;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::{FxHashMap, FxHashSet};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+pub trait Triangles: Transitivity
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Number of triangles each node participates in, counted exactly by iterating over
+    // edges. Edges are oriented from lower to higher (degree, id), and each triangle is
+    // found once, from its lowest two nodes, by intersecting their higher neighbors.
+    // Takes O(E^1.5) time. Small dense graphs are counted on a `BitsetAdjacency` instead,
+    // in O(E * V / 64) time.
+    fn get_triangle_counts(&self) -> FxHashMap<NodeId, usize> {
+        if let Some(adjacency) = BitsetAdjacency::from_dense_graph(self) {
+            return adjacency.get_triangle_counts();
+        }
+        let rank = |id: NodeId| (self.get_node(id).degree(), id);
+        let higher_neighbors: FxHashMap<NodeId, FxHashSet<NodeId>> = self
+            .get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                let neighbors: FxHashSet<NodeId> = node
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|neighbor_id| rank(*neighbor_id) > rank(id))
+                    .collect();
+                (id, neighbors)
+            })
+            .collect();
+        let mut counts: FxHashMap<NodeId, usize> = self.get_ids_iter().map(|id| (*id, 0)).collect();
+        for (id, neighbors) in &higher_neighbors {
+            for neighbor_id in neighbors {
+                for third_id in neighbors.intersection(&higher_neighbors[neighbor_id]) {
+                    for x in [id, neighbor_id, third_id] {
+                        *counts.get_mut(x).unwrap() += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    // Number of triangles in the graph.
+    fn get_triangle_count(&self) -> usize {
+        self.get_triangle_counts().values().sum::<usize>() / 3
+    }
+
+    // Approximate number of triangles each node participates in: the fraction of samples
+    // of its wedges that are closed, times its number of wedges. Nodes with fewer than
+    // samples wedges are counted exactly instead.
+    fn get_approx_triangle_counts<R: Rng>(
+        &self,
+        samples: usize,
+        rng: &mut R,
+    ) -> FxHashMap<NodeId, f64> {
+        self.get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                let num_triples = if node.degree() >= 2 {
+                    self.triples_count(id)
+                } else {
+                    0
+                };
+                let count = if num_triples <= samples {
+                    self.triangle_count(id) as f64
+                } else {
+                    let successes = (0..samples)
+                        .filter(|_| self.sample_wedge_is_closed(node, rng))
+                        .count();
+                    successes as f64 / samples as f64 * num_triples as f64
+                };
+                (id, count)
+            })
+            .collect()
+    }
+
+    // Approximate number of triangles in the graph, from samples of wedges drawn uniformly
+    // over the whole graph (as for approximate transitivity): each triangle closes 3 wedges.
+    fn get_approx_triangle_count<R: Rng>(&self, samples: usize, rng: &mut R) -> f64 {
+        let nodes = self
+            .get_nodes_iter()
+            .filter(|node| node.degree() >= 2)
+            .collect::<Vec<_>>();
+        let triples_counts: Vec<usize> = nodes
+            .iter()
+            .map(|node| self.triples_count(node.get_id()))
+            .collect();
+        let num_triples: usize = triples_counts.iter().sum();
+        if num_triples == 0 {
+            return 0.0;
+        }
+        let dist = WeightedIndex::new(triples_counts).unwrap();
+        let successes = (0..samples)
+            .filter(|_| self.sample_wedge_is_closed(nodes[dist.sample(rng)], rng))
+            .count();
+        successes as f64 / samples as f64 * num_triples as f64 / 3.0
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::id_types::NodeId;
+use fxhash::FxHashMap;
+
+/// Connected components of an undirected graph whose edges arrive one at a time, kept in a
+/// union-find (disjoint-set forest) with union by size and path compression, so that adding
+/// an edge or asking whether two nodes are connected takes amortized nearly constant time,
+/// without building the graph. Edges can't be removed.
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    ids: Vec<NodeId>,
+    positions: FxHashMap<NodeId, usize>,
+    // each node's parent in its tree, which is the node itself for roots.
+    parents: Vec<usize>,
+    // number of nodes in the tree of each root.
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+impl UnionFind {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// forgets all nodes and edges.
+    pub fn reset(&mut self) {
+        self.ids.clear();
+        self.positions.clear();
+        self.parents.clear();
+        self.sizes.clear();
+        self.num_components = 0;
+    }
+
+    pub fn count_nodes(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn count_components(&self) -> usize {
+        self.num_components
+    }
+
+    // position of the node, which is added on its own if it is new.
+    fn get_position(&mut self, id: NodeId) -> usize {
+        if let Some(i) = self.positions.get(&id) {
+            return *i;
+        }
+        let i = self.ids.len();
+        self.ids.push(id);
+        self.positions.insert(id, i);
+        self.parents.push(i);
+        self.sizes.push(1);
+        self.num_components += 1;
+        i
+    }
+
+    // root of the tree of the node at position i, pointing every node on the way straight
+    // at it.
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut j = i;
+        while self.parents[j] != root {
+            let parent = self.parents[j];
+            self.parents[j] = root;
+            j = parent;
+        }
+        root
+    }
+
+    /// adds a node with no edges, if it is new.
+    pub fn add_node(&mut self, id: NodeId) {
+        self.get_position(id);
+    }
+
+    /// adds an edge, along with its nodes if they are new, and returns whether it joined two
+    /// components.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) -> bool {
+        let i = self.get_position(a);
+        let j = self.get_position(b);
+        let (mut root, mut other_root) = (self.find(i), self.find(j));
+        if root == other_root {
+            return false;
+        }
+        // the smaller tree goes under the larger one, keeping trees shallow.
+        if self.sizes[root] < self.sizes[other_root] {
+            std::mem::swap(&mut root, &mut other_root);
+        }
+        self.parents[other_root] = root;
+        self.sizes[root] += self.sizes[other_root];
+        self.num_components -= 1;
+        true
+    }
+
+    /// representative of the node's component (the same for all of its nodes until its
+    /// component joins another), or None if the node is unknown.
+    pub fn get_representative(&mut self, id: NodeId) -> Option<NodeId> {
+        let i = *self.positions.get(&id)?;
+        let root = self.find(i);
+        Some(self.ids[root])
+    }
+
+    /// whether there is a path between a and b (false if either is unknown).
+    pub fn is_connected(&mut self, a: NodeId, b: NodeId) -> bool {
+        match (self.positions.get(&a), self.positions.get(&b)) {
+            (Some(i), Some(j)) => {
+                let (i, j) = (*i, *j);
+                self.find(i) == self.find(j)
+            }
+            _ => false,
+        }
+    }
+
+    /// number of nodes in the node's component (0 if it is unknown).
+    pub fn get_component_size(&mut self, id: NodeId) -> usize {
+        match self.positions.get(&id) {
+            Some(i) => {
+                let root = self.find(*i);
+                self.sizes[root]
+            }
+            None => 0,
+        }
+    }
+
+    /// nodes of each component, in order, with components in order of their smallest node.
+    pub fn get_components(&mut self) -> Vec<Vec<NodeId>> {
+        let mut order: Vec<usize> = (0..self.ids.len()).collect();
+        order.sort_by_key(|i| self.ids[*i]);
+        let mut indices: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut components: Vec<Vec<NodeId>> = Vec::with_capacity(self.num_components);
+        for i in order {
+            let root = self.find(i);
+            let index = *indices.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[index].push(self.ids[i]);
+        }
+        components
+    }
+}
@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate serde_json;
+use crate::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use crate::dachshund::algorithms::layout::ForceLayout;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use rand::prelude::*;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// iterations of the force-directed layout used by to_visualization_json.
+pub const DEFAULT_LAYOUT_ITERATIONS: usize = 100;
+
+pub trait Visualization: ForceLayout
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Everything needed to draw the graph, as JSON of the form
+    //   {"nodes": [{"id": 1, "x": 0.2, "y": 0.7, "community": 0, "in_clique": true}, ...],
+    //    "edges": [{"source": 1, "target": 2}, ...]}
+    // Nodes are laid out with get_layout, and ordered by id. Their community (null if they
+    // belong to none) is looked up in communities, as returned by e.g.
+    // get_leiden_communities, and in_clique tells whether they are part of clique (e.g. the
+    // nodes of a mined candidate). Each edge is listed once, from its smaller id.
+    fn to_visualization_json(
+        &self,
+        communities: &HashMap<CommunityId, Community>,
+        clique: &HashSet<NodeId>,
+    ) -> Value {
+        self.to_visualization_json_with_rng(
+            communities,
+            clique,
+            DEFAULT_LAYOUT_ITERATIONS,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    // Same as to_visualization_json, with the given number of layout iterations, and the
+    // initial layout drawn from the given random number generator.
+    fn to_visualization_json_with_rng<R: Rng>(
+        &self,
+        communities: &HashMap<CommunityId, Community>,
+        clique: &HashSet<NodeId>,
+        num_iterations: usize,
+        rng: &mut R,
+    ) -> Value {
+        let layout = self.get_layout_with_rng(num_iterations, rng);
+        let membership: HashMap<NodeId, CommunityId> = communities
+            .iter()
+            .flat_map(|(community_id, members)| members.iter().map(move |x| (*x, *community_id)))
+            .collect();
+        let mut nodes: Vec<Value> = Vec::new();
+        let mut edges: BTreeSet<(NodeId, NodeId)> = BTreeSet::new();
+        for id in self.get_ordered_node_ids() {
+            let (x, y) = layout[&id];
+            nodes.push(json!({
+                "id": id.value(),
+                "x": x,
+                "y": y,
+                "community": membership.get(&id),
+                "in_clique": clique.contains(&id),
+            }));
+            for e in self.get_node(id).get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                edges.insert((id.min(neighbor_id), id.max(neighbor_id)));
+            }
+        }
+        let edges: Vec<Value> = edges
+            .into_iter()
+            .map(|(source, target)| json!({"source": source.value(), "target": target.value()}))
+            .collect();
+        json!({"nodes": nodes, "edges": edges})
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::connected_components::{
+    ConnectedComponentsDirected, ConnectedComponentsUndirected,
+};
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::algorithms::triangles::Triangles;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::typed_graph::TypedGraph;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use std::collections::HashMap;
+
+/// A graph of any of the types in this crate, so that applications can hold heterogeneous
+/// collections of graphs and compute the same statistics on each of them. GraphBase and
+/// the algorithm traits are generic over node types, and so can't be made into trait
+/// objects: each statistic is instead dispatched on the variant, and returns an error for
+/// graph types the underlying algorithm isn't implemented for.
+pub enum AnyGraph {
+    SimpleUndirected(SimpleUndirectedGraph),
+    SimpleDirected(SimpleDirectedGraph),
+    WeightedUndirected(WeightedUndirectedGraph),
+    Typed(TypedGraph),
+}
+
+// applies an expression to the graph held by any variant.
+macro_rules! dispatch {
+    ($graph:expr, $g:ident => $e:expr) => {
+        match $graph {
+            AnyGraph::SimpleUndirected($g) => $e,
+            AnyGraph::SimpleDirected($g) => $e,
+            AnyGraph::WeightedUndirected($g) => $e,
+            AnyGraph::Typed($g) => $e,
+        }
+    };
+}
+
+impl From<SimpleUndirectedGraph> for AnyGraph {
+    fn from(graph: SimpleUndirectedGraph) -> Self {
+        AnyGraph::SimpleUndirected(graph)
+    }
+}
+impl From<SimpleDirectedGraph> for AnyGraph {
+    fn from(graph: SimpleDirectedGraph) -> Self {
+        AnyGraph::SimpleDirected(graph)
+    }
+}
+impl From<WeightedUndirectedGraph> for AnyGraph {
+    fn from(graph: WeightedUndirectedGraph) -> Self {
+        AnyGraph::WeightedUndirected(graph)
+    }
+}
+impl From<TypedGraph> for AnyGraph {
+    fn from(graph: TypedGraph) -> Self {
+        AnyGraph::Typed(graph)
+    }
+}
+
+impl AnyGraph {
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            AnyGraph::SimpleUndirected(_) => "SimpleUndirectedGraph",
+            AnyGraph::SimpleDirected(_) => "SimpleDirectedGraph",
+            AnyGraph::WeightedUndirected(_) => "WeightedUndirectedGraph",
+            AnyGraph::Typed(_) => "TypedGraph",
+        }
+    }
+    pub fn is_directed(&self) -> bool {
+        matches!(self, AnyGraph::SimpleDirected(_))
+    }
+    fn unsupported(&self, statistic: &str) -> CLQError {
+        CLQError::from(format!(
+            "{} is not supported for {}",
+            statistic,
+            self.get_type_name()
+        ))
+    }
+
+    pub fn count_nodes(&self) -> usize {
+        dispatch!(self, g => g.count_nodes())
+    }
+    pub fn count_edges(&self) -> usize {
+        dispatch!(self, g => g.count_edges())
+    }
+    /// Node ids in increasing order. The (u32) internal ids of a TypedGraph are converted
+    /// to NodeIds.
+    pub fn get_ordered_node_ids(&self) -> Vec<NodeId> {
+        match self {
+            AnyGraph::Typed(g) => g
+                .get_ordered_node_ids()
+                .into_iter()
+                .map(NodeId::from)
+                .collect(),
+            AnyGraph::SimpleUndirected(g) => g.get_ordered_node_ids(),
+            AnyGraph::SimpleDirected(g) => g.get_ordered_node_ids(),
+            AnyGraph::WeightedUndirected(g) => g.get_ordered_node_ids(),
+        }
+    }
+
+    /// Connected components, or weakly connected components for directed graphs.
+    pub fn get_connected_components(&self) -> CLQResult<Vec<Vec<NodeId>>> {
+        match self {
+            AnyGraph::SimpleUndirected(g) => Ok(g.get_connected_components()),
+            AnyGraph::SimpleDirected(g) => Ok(g.get_weakly_connected_components()),
+            AnyGraph::WeightedUndirected(g) => Ok(g.get_connected_components()),
+            AnyGraph::Typed(_) => Err(self.unsupported("Connected components")),
+        }
+    }
+    pub fn get_coreness_values(&self) -> CLQResult<HashMap<NodeId, usize>> {
+        match self {
+            AnyGraph::SimpleUndirected(g) => Ok(g.get_coreness_values()),
+            AnyGraph::WeightedUndirected(g) => Ok(g.get_coreness_values()),
+            _ => Err(self.unsupported("Coreness")),
+        }
+    }
+    pub fn get_pagerank(
+        &self,
+        damping: f64,
+        tol: f64,
+        max_iter: usize,
+    ) -> CLQResult<HashMap<NodeId, f64>> {
+        match self {
+            AnyGraph::SimpleUndirected(g) => Ok(g.get_pagerank(damping, tol, max_iter)),
+            AnyGraph::SimpleDirected(g) => Ok(g.get_pagerank(damping, tol, max_iter)),
+            AnyGraph::WeightedUndirected(g) => Ok(g.get_pagerank(damping, tol, max_iter)),
+            AnyGraph::Typed(_) => Err(self.unsupported("PageRank")),
+        }
+    }
+    pub fn get_transitivity(&self) -> CLQResult<f64> {
+        match self {
+            AnyGraph::SimpleUndirected(g) => Ok(g.get_transitivity()),
+            AnyGraph::WeightedUndirected(g) => Ok(g.get_transitivity()),
+            _ => Err(self.unsupported("Transitivity")),
+        }
+    }
+    pub fn get_triangle_count(&self) -> CLQResult<usize> {
+        match self {
+            AnyGraph::SimpleUndirected(g) => Ok(g.get_triangle_count()),
+            AnyGraph::WeightedUndirected(g) => Ok(g.get_triangle_count()),
+            _ => Err(self.unsupported("Triangle counting")),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate serde_json;
+
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
+use crate::dachshund::simple_undirected_graph::{SimpleUndirectedGraph, UndirectedGraph};
+use fxhash::{FxHashMap, FxHashSet};
+use serde_json::json;
+use std::collections::hash_map::{Keys, Values};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value of a node or edge attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+impl AttributeValue {
+    /// the value of a JSON boolean, number or string. Integers that fit in an i64 are read
+    /// as Int, other numbers as Float.
+    pub fn from_json(value: &serde_json::Value) -> CLQResult<Self> {
+        match value {
+            serde_json::Value::Bool(x) => Ok(AttributeValue::Bool(*x)),
+            serde_json::Value::Number(x) => match x.as_i64() {
+                Some(x) => Ok(AttributeValue::Int(x)),
+                None => Ok(AttributeValue::Float(x.as_f64().unwrap())),
+            },
+            serde_json::Value::String(x) => Ok(AttributeValue::Str(x.clone())),
+            _ => Err(CLQError::from(format!(
+                "Attribute values must be booleans, numbers or strings, got {value}"
+            ))),
+        }
+    }
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            AttributeValue::Bool(x) => json!(x),
+            AttributeValue::Int(x) => json!(x),
+            AttributeValue::Float(x) => json!(x),
+            AttributeValue::Str(x) => json!(x),
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttributeValue::Bool(x) => Some(*x),
+            _ => None,
+        }
+    }
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AttributeValue::Int(x) => Some(*x),
+            _ => None,
+        }
+    }
+    /// the value of numeric attributes, integer or not.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AttributeValue::Int(x) => Some(*x as f64),
+            AttributeValue::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeValue::Str(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttributeValue::Bool(x) => write!(f, "{x}"),
+            AttributeValue::Int(x) => write!(f, "{x}"),
+            AttributeValue::Float(x) => write!(f, "{x}"),
+            AttributeValue::Str(x) => write!(f, "{x}"),
+        }
+    }
+}
+impl From<bool> for AttributeValue {
+    fn from(x: bool) -> Self {
+        AttributeValue::Bool(x)
+    }
+}
+impl From<i64> for AttributeValue {
+    fn from(x: i64) -> Self {
+        AttributeValue::Int(x)
+    }
+}
+impl From<f64> for AttributeValue {
+    fn from(x: f64) -> Self {
+        AttributeValue::Float(x)
+    }
+}
+impl From<&str> for AttributeValue {
+    fn from(x: &str) -> Self {
+        AttributeValue::Str(x.to_owned())
+    }
+}
+impl From<String> for AttributeValue {
+    fn from(x: String) -> Self {
+        AttributeValue::Str(x)
+    }
+}
+
+/// Attributes of a node or edge, by key.
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+/// Parses attributes written as a JSON object of booleans, numbers and strings, e.g.
+/// `{"country": "US", "age": 31}`. An empty string stands for no attributes.
+pub fn parse_attributes(text: &str) -> CLQResult<Attributes> {
+    if text.trim().is_empty() {
+        return Ok(Attributes::new());
+    }
+    match serde_json::from_str::<serde_json::Value>(text)? {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), AttributeValue::from_json(value)?)))
+            .collect(),
+        _ => Err(CLQError::from(format!(
+            "Attributes must be a JSON object, got {text}"
+        ))),
+    }
+}
+
+/// Writes attributes as a JSON object, as read by parse_attributes.
+pub fn format_attributes(attributes: &Attributes) -> String {
+    let map: serde_json::Map<String, serde_json::Value> = attributes
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_json()))
+        .collect();
+    serde_json::Value::Object(map).to_string()
+}
+
+// edges are keyed by (smaller id, larger id).
+fn get_edge_key(id1: NodeId, id2: NodeId) -> (NodeId, NodeId) {
+    (id1.min(id2), id1.max(id2))
+}
+
+/// A simple undirected graph whose nodes and edges carry attributes. Every node has
+/// attributes (possibly none), as does every edge. Graph algorithms run on the underlying
+/// graph (see get_graph), which leaves attributes out.
+pub struct AttributedGraph {
+    pub graph: SimpleUndirectedGraph,
+    pub node_attributes: FxHashMap<NodeId, Attributes>,
+    pub edge_attributes: FxHashMap<(NodeId, NodeId), Attributes>,
+}
+impl GraphBase for AttributedGraph {
+    type NodeType = SimpleNode;
+
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, SimpleNode> {
+        self.graph.get_ids_iter()
+    }
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, SimpleNode> {
+        self.graph.get_nodes_iter()
+    }
+    fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, SimpleNode> {
+        self.graph.get_mut_nodes()
+    }
+    fn has_node(&self, node_id: NodeId) -> bool {
+        self.graph.has_node(node_id)
+    }
+    fn get_node(&self, node_id: NodeId) -> &SimpleNode {
+        self.graph.get_node(node_id)
+    }
+    fn count_edges(&self) -> usize {
+        self.graph.count_edges()
+    }
+    fn count_nodes(&self) -> usize {
+        self.graph.count_nodes()
+    }
+    fn create_empty() -> Self {
+        AttributedGraph {
+            graph: SimpleUndirectedGraph::create_empty(),
+            node_attributes: FxHashMap::default(),
+            edge_attributes: FxHashMap::default(),
+        }
+    }
+}
+impl UndirectedGraph for AttributedGraph {}
+impl Subgraphs for AttributedGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        self.get_subgraph(
+            |id, _| nodes.contains(&id),
+            |id1, id2, _| nodes.contains(&id1) && nodes.contains(&id2),
+        )
+    }
+}
+impl AttributedGraph {
+    pub fn get_graph(&self) -> &SimpleUndirectedGraph {
+        &self.graph
+    }
+
+    /// attributes of a node, None if there is no such node.
+    pub fn get_node_attributes(&self, id: NodeId) -> Option<&Attributes> {
+        self.node_attributes.get(&id)
+    }
+    pub fn get_node_attribute(&self, id: NodeId, key: &str) -> Option<&AttributeValue> {
+        self.node_attributes.get(&id)?.get(key)
+    }
+    /// attributes of the edge between two nodes (either way), None if there is no such edge.
+    pub fn get_edge_attributes(&self, id1: NodeId, id2: NodeId) -> Option<&Attributes> {
+        self.edge_attributes.get(&get_edge_key(id1, id2))
+    }
+    pub fn get_edge_attribute(
+        &self,
+        id1: NodeId,
+        id2: NodeId,
+        key: &str,
+    ) -> Option<&AttributeValue> {
+        self.get_edge_attributes(id1, id2)?.get(key)
+    }
+    /// sets an attribute of a node, returning its previous value. Errors on missing nodes.
+    pub fn set_node_attribute(
+        &mut self,
+        id: NodeId,
+        key: &str,
+        value: AttributeValue,
+    ) -> CLQResult<Option<AttributeValue>> {
+        let attributes = self
+            .node_attributes
+            .get_mut(&id)
+            .ok_or_else(|| CLQError::from(format!("No such node: {}", id.value())))?;
+        Ok(attributes.insert(key.to_owned(), value))
+    }
+    /// sets an attribute of an edge, returning its previous value. Errors on missing edges.
+    pub fn set_edge_attribute(
+        &mut self,
+        id1: NodeId,
+        id2: NodeId,
+        key: &str,
+        value: AttributeValue,
+    ) -> CLQResult<Option<AttributeValue>> {
+        let attributes = self
+            .edge_attributes
+            .get_mut(&get_edge_key(id1, id2))
+            .ok_or_else(|| {
+                CLQError::from(format!("No such edge: {}-{}", id1.value(), id2.value()))
+            })?;
+        Ok(attributes.insert(key.to_owned(), value))
+    }
+
+    /// ids of the nodes whose attribute key has the given value, in order.
+    pub fn get_nodes_with_attribute(&self, key: &str, value: &AttributeValue) -> Vec<NodeId> {
+        let mut ids: Vec<NodeId> = self
+            .node_attributes
+            .iter()
+            .filter(|(_, attributes)| attributes.get(key) == Some(value))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// the subgraph induced by the given nodes (leaving out missing ones): all edges
+    /// between them, with their attributes and those of the nodes.
+    pub fn get_induced_subgraph(&self, ids: &[NodeId]) -> AttributedGraph {
+        let kept: FxHashSet<NodeId> = ids
+            .iter()
+            .cloned()
+            .filter(|id| self.has_node(*id))
+            .collect();
+        self.get_subgraph(
+            |id, _| kept.contains(&id),
+            |id1, id2, _| kept.contains(&id1) && kept.contains(&id2),
+        )
+    }
+
+    /// the subgraph induced by the nodes whose attributes satisfy predicate.
+    pub fn filter_nodes<F: Fn(NodeId, &Attributes) -> bool>(
+        &self,
+        predicate: F,
+    ) -> AttributedGraph {
+        let kept: FxHashSet<NodeId> = self
+            .node_attributes
+            .iter()
+            .filter(|(id, attributes)| predicate(**id, attributes))
+            .map(|(id, _)| *id)
+            .collect();
+        self.get_subgraph(
+            |id, _| kept.contains(&id),
+            |id1, id2, _| kept.contains(&id1) && kept.contains(&id2),
+        )
+    }
+
+    /// the subgraph with all the nodes of this one, and only the edges whose attributes
+    /// satisfy predicate (called with the smaller id first).
+    pub fn filter_edges<F: Fn(NodeId, NodeId, &Attributes) -> bool>(
+        &self,
+        predicate: F,
+    ) -> AttributedGraph {
+        self.get_subgraph(|_, _| true, predicate)
+    }
+
+    fn get_subgraph<F, G>(&self, keep_node: F, keep_edge: G) -> AttributedGraph
+    where
+        F: Fn(NodeId, &Attributes) -> bool,
+        G: Fn(NodeId, NodeId, &Attributes) -> bool,
+    {
+        let node_attributes: FxHashMap<NodeId, Attributes> = self
+            .node_attributes
+            .iter()
+            .filter(|(id, attributes)| keep_node(**id, attributes))
+            .map(|(id, attributes)| (*id, attributes.clone()))
+            .collect();
+        let edge_attributes: FxHashMap<(NodeId, NodeId), Attributes> = self
+            .edge_attributes
+            .iter()
+            .filter(|((id1, id2), attributes)| {
+                node_attributes.contains_key(id1)
+                    && node_attributes.contains_key(id2)
+                    && keep_edge(*id1, *id2, attributes)
+            })
+            .map(|(key, attributes)| (*key, attributes.clone()))
+            .collect();
+        let mut neighbors: FxHashMap<NodeId, SimpleNeighbors> = node_attributes
+            .keys()
+            .map(|id| (*id, SimpleNeighbors::new()))
+            .collect();
+        for (id1, id2) in edge_attributes.keys() {
+            neighbors.get_mut(id1).unwrap().push(*id2);
+            neighbors.get_mut(id2).unwrap().push(*id1);
+        }
+        let ids: Vec<NodeId> = self
+            .graph
+            .ids
+            .iter()
+            .cloned()
+            .filter(|id| node_attributes.contains_key(id))
+            .collect();
+        let nodes: FxHashMap<NodeId, SimpleNode> = neighbors
+            .into_iter()
+            .map(|(id, node_neighbors)| (id, SimpleNode::new(id, node_neighbors)))
+            .collect();
+        AttributedGraph {
+            graph: SimpleUndirectedGraph { ids, nodes },
+            node_attributes,
+            edge_attributes,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+
+use crate::dachshund::attributed_graph::{AttributedGraph, Attributes};
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
+use crate::dachshund::row::AttributedRow;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use fxhash::FxHashMap;
+
+pub struct AttributedGraphBuilder {}
+
+impl GraphBuilderBase for AttributedGraphBuilder {
+    type GraphType = AttributedGraph;
+    type RowType = AttributedRow;
+
+    // builds a graph from node and edge rows, in any order. Nodes only found in edge rows
+    // have no attributes. Repeated edges (either way) are merged, as are repeated node
+    // rows, with later rows overriding the values of earlier ones for the same keys.
+    fn from_vector(&mut self, data: Vec<AttributedRow>) -> CLQResult<AttributedGraph> {
+        let mut node_attributes: FxHashMap<NodeId, Attributes> = FxHashMap::default();
+        let mut edge_attributes: FxHashMap<(NodeId, NodeId), Attributes> = FxHashMap::default();
+        for row in data {
+            node_attributes.entry(row.source_id).or_default();
+            let attributes = match row.target_id {
+                Some(target_id) => {
+                    node_attributes.entry(target_id).or_default();
+                    let key = (row.source_id.min(target_id), row.source_id.max(target_id));
+                    edge_attributes.entry(key).or_default()
+                }
+                None => node_attributes.get_mut(&row.source_id).unwrap(),
+            };
+            attributes.extend(row.attributes);
+        }
+        let mut neighbors: FxHashMap<NodeId, SimpleNeighbors> = node_attributes
+            .keys()
+            .map(|id| (*id, SimpleNeighbors::new()))
+            .collect();
+        for (id1, id2) in edge_attributes.keys() {
+            neighbors.get_mut(id1).unwrap().push(*id2);
+            neighbors.get_mut(id2).unwrap().push(*id1);
+        }
+        let nodes: FxHashMap<NodeId, SimpleNode> = neighbors
+            .into_iter()
+            .map(|(id, neighbors)| (id, SimpleNode::new(id, neighbors)))
+            .collect();
+        let mut ids: Vec<NodeId> = nodes.keys().cloned().collect();
+        ids.sort();
+        Ok(AttributedGraph {
+            graph: SimpleUndirectedGraph { ids, nodes },
+            node_attributes,
+            edge_attributes,
+        })
+    }
+}
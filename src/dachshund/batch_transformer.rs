@@ -0,0 +1,252 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+extern crate serde_json;
+
+use clap::ArgMatches;
+use serde_json::Value;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::GraphId;
+use crate::dachshund::line_processor::{FieldValue, LineProcessorBase, LineSchema};
+use crate::dachshund::row::Row;
+use crate::dachshund::transformer::Transformer;
+use crate::dachshund::transformer_base::TransformerBase;
+use crate::dachshund::typed_graph_line_processor::{TypedGraphLineProcessor, TYPED_EDGE_SCHEMA};
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Arc;
+
+/// A line of typed graph input, as parsed by the line processor of each typespec it is
+/// valid for (by index of the typespec in its `BatchTransformer`).
+pub struct BatchRow {
+    pub graph_id: GraphId,
+    pub rows: Vec<(usize, Box<dyn Row>)>,
+}
+impl Row for BatchRow {
+    fn get_graph_id(&self) -> GraphId {
+        self.graph_id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Splits each line of input once, and parses it with the line processor of every
+/// typespec, keeping the rows of those it is valid for.
+pub struct BatchLineProcessor {
+    pub line_processors: Vec<Arc<TypedGraphLineProcessor>>,
+}
+impl LineProcessorBase for BatchLineProcessor {
+    fn is_skipped(&self, line: &str) -> bool {
+        line.trim().is_empty() || line.starts_with('#')
+    }
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        Some(&TYPED_EDGE_SCHEMA)
+    }
+    /// processes a line of typed graph input (see `TypedGraphLineProcessor`). Returns an
+    /// error if the line isn't valid for any of the typespecs, e.g. if its edge type is in
+    /// none of them (with the error of the first typespec).
+    fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
+        let vec: Vec<&str> = TYPED_EDGE_SCHEMA.split(&line)?;
+        self.process_values(&FieldValue::from_fields(&vec))
+    }
+    fn process_values(&self, values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
+        let mut rows: Vec<(usize, Box<dyn Row>)> = Vec::new();
+        let mut first_error: Option<CLQError> = None;
+        for (typespec_ix, line_processor) in self.line_processors.iter().enumerate() {
+            match line_processor.process_values(values) {
+                Ok(row) => rows.push((typespec_ix, row)),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        match rows.first() {
+            Some((_, row)) => Ok(Box::new(BatchRow {
+                graph_id: row.get_graph_id(),
+                rows,
+            })),
+            None => Err(first_error.ok_or_else(CLQError::err_none)?),
+        }
+    }
+    fn release_graph(&self, graph_id: GraphId) {
+        for line_processor in &self.line_processors {
+            line_processor.release_graph(graph_id);
+        }
+    }
+}
+
+/// Runs the typed graph clique search (see `Transformer`) for several named typespecs,
+/// e.g. one per relation family, over the same input: each line is read and split once,
+/// and goes into the graphs of the typespecs it is valid for. Every line of output is
+/// prefixed with the name of its typespec and a tab.
+pub struct BatchTransformer {
+    pub transformers: Vec<(String, Transformer)>,
+    line_processor: Arc<BatchLineProcessor>,
+}
+impl TransformerBase for BatchTransformer {
+    fn get_line_processor(&self) -> Arc<dyn LineProcessorBase> {
+        self.line_processor.clone()
+    }
+    fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
+        let batch_row = row
+            .downcast_ref::<BatchRow>()
+            .ok_or_else(|| CLQError::from("Expected a row parsed for several typespecs"))?;
+        for (typespec_ix, row) in &batch_row.rows {
+            self.transformers[*typespec_ix].1.add_row(row.as_ref());
+        }
+        Ok(())
+    }
+    fn reset(&mut self) -> CLQResult<()> {
+        for (_name, transformer) in &mut self.transformers {
+            transformer.reset()?;
+        }
+        Ok(())
+    }
+    fn describe_row(&self, row: &dyn Row) -> String {
+        match row.as_any().downcast_ref::<BatchRow>() {
+            Some(batch_row) => batch_row
+                .rows
+                .iter()
+                .map(|(typespec_ix, row)| {
+                    let (name, transformer) = &self.transformers[*typespec_ix];
+                    format!("{}: {}", name, transformer.describe_row(row.as_ref()))
+                })
+                .collect::<Vec<String>>()
+                .join("; "),
+            None => "unknown".to_owned(),
+        }
+    }
+    // searches the graph of each typespec in turn. Graphs without a conforming clique
+    // for any typespec are acknowledged (once) without output.
+    fn process_batch(
+        &mut self,
+        graph_id: GraphId,
+        output: &Sender<(Option<String>, bool)>,
+    ) -> CLQResult<()> {
+        let mut num_lines: usize = 0;
+        for (name, transformer) in &mut self.transformers {
+            let (sender, receiver) = channel();
+            transformer.process_batch(graph_id, &sender)?;
+            drop(sender);
+            for (line, _shutdown) in receiver {
+                if let Some(line) = line {
+                    output
+                        .send((Some(format!("{name}\t{line}")), false))
+                        .unwrap();
+                    num_lines += 1;
+                }
+            }
+        }
+        if num_lines == 0 {
+            output.send((None, false)).unwrap();
+        }
+        Ok(())
+    }
+}
+impl BatchTransformer {
+    /// creates a batch transformer from named transformers. Returns an error if there are
+    /// none, or if two share a name.
+    // line processors are shared like those of `Transformer`, within a single thread.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn new(transformers: Vec<(String, Transformer)>) -> CLQResult<Self> {
+        if transformers.is_empty() {
+            return Err(CLQError::from("At least one typespec is required"));
+        }
+        let mut names: HashSet<&str> = HashSet::new();
+        for (name, _transformer) in &transformers {
+            if !names.insert(name) {
+                return Err(CLQError::from(format!("Duplicate typespec name: {name}")));
+            }
+        }
+        let line_processor = Arc::new(BatchLineProcessor {
+            line_processors: transformers
+                .iter()
+                .map(|(_name, transformer)| transformer.line_processor.clone())
+                .collect(),
+        });
+        Ok(Self {
+            transformers,
+            line_processor,
+        })
+    }
+
+    /// parses a JSON object mapping names to typespecs, e.g.:
+    /// {"publishing": [["author", "published", "article"]], "citing": [["author", "cited",
+    /// "article"]]}, as a list of named typespecs, in order of name.
+    pub fn parse_typespecs(typespecs_str: &str) -> CLQResult<Vec<(String, Vec<Vec<String>>)>> {
+        let typespecs: serde_json::Map<String, Value> = serde_json::from_str(typespecs_str)?;
+        typespecs
+            .into_iter()
+            .map(|(name, typespec)| {
+                let typespec = Transformer::parse_typespec(&typespec.to_string())?;
+                Ok((name, typespec))
+            })
+            .collect()
+    }
+
+    /// constructs a batch transformer from an ArgMatches object, with the typespecs given
+    /// by the typespecs argument and all other arguments shared. Local thresholds for
+    /// non-core types (and min degrees for edge types) apply to the typespecs which have
+    /// that type; it is an error for a type to be in none of them.
+    pub fn from_argmatches(matches: ArgMatches) -> CLQResult<Self> {
+        let typespecs_str: String = matches
+            .value_of("typespecs")
+            .ok_or_else(|| CLQError::from("Missing required argument: typespecs"))?
+            .to_owned();
+        let non_core_local_thresh = Transformer::parse_non_core_local_thresh(&matches)?;
+        let edge_type_min_degree = Transformer::parse_edge_type_min_degree(&matches)?;
+        let mut transformers: Vec<(String, Transformer)> = Vec::new();
+        for (name, typespec) in Self::parse_typespecs(&typespecs_str)? {
+            let mut transformer = Transformer::from_argmatches_with_typespec(&matches, typespec)?;
+            if let Some(non_core_local_thresh) = &non_core_local_thresh {
+                let thresh_for_typespec = non_core_local_thresh
+                    .iter()
+                    .filter(|(non_core_type, _)| transformer.non_core_types.contains(non_core_type))
+                    .map(|(non_core_type, thresh)| (non_core_type.clone(), *thresh))
+                    .collect();
+                transformer = transformer.with_non_core_local_thresh(thresh_for_typespec)?;
+            }
+            if let Some(edge_type_min_degree) = &edge_type_min_degree {
+                let min_degree_for_typespec = edge_type_min_degree
+                    .iter()
+                    .filter(|(edge_type, _)| transformer.edge_types.contains(edge_type))
+                    .map(|(edge_type, min_degree)| (edge_type.clone(), *min_degree))
+                    .collect();
+                transformer = transformer.with_edge_type_min_degree(min_degree_for_typespec)?;
+            }
+            transformers.push((name, transformer));
+        }
+        if let Some(non_core_local_thresh) = &non_core_local_thresh {
+            for non_core_type in non_core_local_thresh.keys() {
+                if !transformers
+                    .iter()
+                    .any(|(_name, transformer)| transformer.non_core_types.contains(non_core_type))
+                {
+                    return Err(CLQError::from(format!(
+                        "Not a non-core type in any typespec: {non_core_type}"
+                    )));
+                }
+            }
+        }
+        if let Some(edge_type_min_degree) = &edge_type_min_degree {
+            for edge_type in edge_type_min_degree.keys() {
+                if !transformers
+                    .iter()
+                    .any(|(_name, transformer)| transformer.edge_types.contains(edge_type))
+                {
+                    return Err(CLQError::from(format!(
+                        "Not an edge type in any typespec: {edge_type}"
+                    )));
+                }
+            }
+        }
+        Self::new(transformers)
+    }
+}
@@ -23,29 +23,105 @@ use crate::dachshund::typed_graph::LabeledGraph;
 
 use std::rc::Rc;
 
+/// What a beam search needs of its candidates: `Candidate` for typed graphs, and
+/// `QuasiCliqueCandidate` for simple undirected graphs.
+pub trait BeamCandidate: Sized {
+    // scores the expansions of candidates.
+    type Scorer;
+    fn get_checksum(&self) -> Option<u64>;
+    fn get_score(&self) -> CLQResult<f32>;
+    fn as_recipe(&self) -> Recipe;
+    fn expand_from_recipe(&self, recipe: &Recipe) -> CLQResult<Self>;
+    // finds (up to) num_to_search expansions not visited yet, and scores them.
+    fn one_step_search(
+        &self,
+        num_to_search: usize,
+        visited_candidates: &mut HashSet<u64>,
+        scorer: &Self::Scorer,
+    ) -> CLQResult<Vec<Recipe>>;
+    fn replicate(&self, keep_score: bool) -> Self;
+    fn estimated_memory_bytes(&self) -> usize;
+    // human-readable description, for verbose output (non-core types are those of typed
+    // graphs).
+    fn describe(&self, non_core_types: &[String]) -> CLQResult<String>;
+}
+
 /// The result of a beam search.
-pub struct BeamSearchResult<'a, TGraph>
-where
-    TGraph: LabeledGraph<NodeType = Node>,
-{
-    pub top_candidate: Candidate<'a, TGraph>,
+pub struct BeamSearchResult<C: BeamCandidate> {
+    pub top_candidate: C,
     pub num_steps: usize,
+    // largest estimated memory used by the candidates in the beam during the search.
+    pub peak_memory_bytes: usize,
+}
+
+/// The state of the beam, as returned by `Beam::step` and `Beam::snapshot`.
+pub struct BeamSnapshot {
+    // number of steps taken so far.
+    pub epoch: usize,
+    // (score, checksum) of each candidate in the beam, best first.
+    pub candidates: Vec<(f32, u64)>,
+    // whether the last step found any candidate not visited before. Once it did not,
+    // further steps leave the beam unchanged.
+    pub can_continue: bool,
+    // estimated memory used by the candidates in the beam, in bytes.
+    pub memory_bytes: usize,
+}
+impl BeamSnapshot {
+    pub fn top_score(&self) -> Option<f32> {
+        self.candidates.first().map(|(score, _checksum)| *score)
+    }
 }
 
 /// Used for (quasi-clique) detection. A singleton object that keeps state across the beam search.
 /// At any point this object considers a "beam" of candidates that is always kept under beam_size,
-/// to avoid exponential blowup of the search space.
-pub struct Beam<'a, TGraph>
+/// to avoid exponential blowup of the search space. Candidates are (quasi-)bicliques of typed
+/// graphs by default.
+pub struct Beam<'a, TGraph, C = Candidate<'a, TGraph>>
 where
-    TGraph: LabeledGraph<NodeType = Node>,
+    C: BeamCandidate,
 {
-    pub candidates: Vec<Candidate<'a, TGraph>>,
+    pub candidates: Vec<C>,
     pub graph: &'a TGraph,
     pub search_problem: Rc<SearchProblem>,
     verbose: bool,
     non_core_types: &'a [String],
     visited_candidates: HashSet<u64>,
-    scorer: Scorer,
+    scorer: C::Scorer,
+    num_steps: usize,
+    can_continue: bool,
+    peak_memory_bytes: usize,
+}
+
+/// A random number generator seeded with the graph_id, so that two identically configured
+/// runs search each graph the same way.
+pub fn seeded_rng(graph_id: GraphId) -> StdRng {
+    let mut seeder = DefaultHasher::new();
+    graph_id.hash(&mut seeder);
+    StdRng::seed_from_u64(seeder.finish())
+}
+
+/// Adds candidate_bytes to the memory_bytes used by the num_kept candidates already kept
+/// in a beam, returning false if that would go over max_beam_memory, in which case the
+/// beam should stop growing, and an error if the beam is still empty.
+pub fn fits_in_memory(
+    memory_bytes: &mut usize,
+    candidate_bytes: usize,
+    num_kept: usize,
+    max_beam_memory: Option<usize>,
+) -> CLQResult<bool> {
+    if let Some(limit) = max_beam_memory {
+        if *memory_bytes + candidate_bytes > limit {
+            if num_kept == 0 {
+                return Err(CLQError::BeamMemoryExceeded {
+                    needed: candidate_bytes,
+                    limit,
+                });
+            }
+            return Ok(false);
+        }
+    }
+    *memory_bytes += candidate_bytes;
+    Ok(true)
 }
 
 impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
@@ -85,14 +161,14 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         graph: &'a TGraph,
-        clique_rows: &'a Vec<CliqueRow>,
+        clique_rows: &[CliqueRow],
         verbose: bool,
         non_core_types: &'a [String],
         search_problem: Rc<SearchProblem>,
         graph_id: GraphId,
     ) -> CLQResult<Beam<'a, TGraph>> {
-        let core_ids: &Vec<u32> = graph.get_core_ids();
-        let non_core_ids: &Vec<u32> = graph.get_non_core_ids().unwrap();
+        let core_ids: &[u32] = graph.get_core_ids();
+        let non_core_ids: &[u32] = graph.get_non_core_ids();
 
         let num_non_core_types: usize = non_core_types.len();
 
@@ -101,13 +177,18 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
 
         // To ensure deterministic behaviour between two identically configured runs,
         // seed the pseudorandom sequence with the current cluster.
-        let mut seeder = DefaultHasher::new();
-        graph_id.hash(&mut seeder);
-        let mut rng = StdRng::seed_from_u64(seeder.finish());
+        let mut rng = seeded_rng(graph_id);
+        let mut memory_bytes: usize = 0;
 
         if !clique_rows.is_empty() {
             let init_clique = Candidate::from_clique_rows(clique_rows, graph, &scorer)?;
             if let Some(init_clique) = init_clique {
+                fits_in_memory(
+                    &mut memory_bytes,
+                    init_clique.estimated_memory_bytes(),
+                    0,
+                    search_problem.max_beam_memory,
+                )?;
                 candidates.push(init_clique);
             }
         }
@@ -126,33 +207,69 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
                 .ok_or_else(|| format!("Problem finding root in graph_id: {}", graph_id.value()))?;
             let candidate_node = Beam::random_walk(&mut rng, graph, *root_id, 7)?;
             let candidate = Candidate::new(candidate_node, graph, &scorer)?;
+            if !fits_in_memory(
+                &mut memory_bytes,
+                candidate.estimated_memory_bytes(),
+                candidates.len(),
+                search_problem.max_beam_memory,
+            )? {
+                if verbose {
+                    eprintln!(
+                        "Beam memory limit reached, starting with {} candidates",
+                        candidates.len()
+                    );
+                }
+                break;
+            }
             candidates.push(candidate);
         }
-        let visited_candidates: HashSet<u64> = HashSet::new();
-        let beam: Beam<TGraph> = Beam {
+        Ok(Beam::from_candidates(
+            candidates,
+            graph,
+            verbose,
+            non_core_types,
+            search_problem,
+            scorer,
+        ))
+    }
+}
+
+impl<'a, TGraph, C: BeamCandidate> Beam<'a, TGraph, C> {
+    /// creates a beam from its initial candidates (see `Beam::new` for the parameters).
+    pub fn from_candidates(
+        candidates: Vec<C>,
+        graph: &'a TGraph,
+        verbose: bool,
+        non_core_types: &'a [String],
+        search_problem: Rc<SearchProblem>,
+        scorer: C::Scorer,
+    ) -> Self {
+        let peak_memory_bytes: usize = candidates
+            .iter()
+            .map(|candidate| candidate.estimated_memory_bytes())
+            .sum();
+        Beam {
             candidates,
             graph,
             search_problem,
             verbose,
             non_core_types,
-            visited_candidates,
+            visited_candidates: HashSet::new(),
             scorer,
-        };
-        Ok(beam)
+            num_steps: 0,
+            can_continue: true,
+            peak_memory_bytes,
+        }
     }
 
     /// Try expanding each member of the beam and keep the top candidates.
-    fn one_step_search(
-        &mut self,
-        num_to_search: usize,
-        beam_size: usize,
-    ) -> CLQResult<(Candidate<'a, TGraph>, bool)> {
+    fn one_step_search(&mut self, num_to_search: usize, beam_size: usize) -> CLQResult<(C, bool)> {
         let mut scored_expansion_recipes: HashSet<Recipe> = HashSet::new();
-        let mut new_candidates: Vec<Candidate<TGraph>> = Vec::new();
+        let mut new_candidates: Vec<C> = Vec::new();
         let mut can_continue: bool = false;
         // A map from a checksum to a reference to a candidate from the previous generation.
         // Used as a hint when materializing the neighborhood for the next generation of candidates.
-        let mut previous_candidates: HashMap<u64, &Candidate<TGraph>> = HashMap::new();
+        let mut previous_candidates: HashMap<u64, &C> = HashMap::new();
 
         for candidate in &self.candidates {
             if self.verbose {
@@ -162,16 +279,13 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
                         Ok(n) => n.to_string(),
                         Err(_) => "No score".to_string(),
                     },
-                    candidate,
-                    candidate.to_printable_row(
-                        self.non_core_types,
-                        self.graph.get_reverse_labels_map()
-                    )?,
+                    candidate.get_checksum().unwrap(),
+                    candidate.describe(self.non_core_types)?,
                 );
             }
             if !self
                 .visited_candidates
-                .contains(&candidate.checksum.unwrap())
+                .contains(&candidate.get_checksum().unwrap())
             {
                 can_continue = true;
 
@@ -189,10 +303,9 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
                         eprintln!(
                             "(score = {}): {}",
                             recipe.score.unwrap_or(0.0),
-                            candidate.expand_from_recipe(&recipe)?.to_printable_row(
-                                self.non_core_types,
-                                self.graph.get_reverse_labels_map()
-                            )?,
+                            candidate
+                                .expand_from_recipe(&recipe)?
+                                .describe(self.non_core_types)?,
                         );
                     }
                     scored_expansion_recipes.insert(recipe);
@@ -200,7 +313,7 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
             }
             previous_candidates.insert(
                 candidate
-                    .checksum
+                    .get_checksum()
                     .expect("Previous candidate had no checksum"),
                 candidate,
             );
@@ -231,49 +344,101 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
         if self.verbose {
             eprintln!("Beam now contains:");
         }
+        let mut memory_bytes: usize = 0;
         for recipe in v {
             if new_candidates.len() < beam_size {
                 let new_candidate = previous_candidates
                     [&recipe.checksum.expect("Recipe had no checksum")]
                     .expand_from_recipe(&recipe)?;
+                // the best candidates are kept, shrinking the beam if they don't all fit.
+                if !fits_in_memory(
+                    &mut memory_bytes,
+                    new_candidate.estimated_memory_bytes(),
+                    new_candidates.len(),
+                    self.search_problem.max_beam_memory,
+                )? {
+                    if self.verbose {
+                        eprintln!(
+                            "Beam memory limit reached, keeping {} candidates",
+                            new_candidates.len()
+                        );
+                    }
+                    break;
+                }
                 new_candidates.push(new_candidate);
             }
         }
 
         self.candidates = new_candidates;
+        self.peak_memory_bytes = self.peak_memory_bytes.max(memory_bytes);
         Ok((self.candidates[0].replicate(true), can_continue))
     }
 
+    /// returns a rough estimate of the memory used by the candidates in the beam, in bytes
+    /// (see `Candidate::estimated_memory_bytes`).
+    pub fn estimated_memory_bytes(&self) -> usize {
+        self.candidates
+            .iter()
+            .map(|candidate| candidate.estimated_memory_bytes())
+            .sum()
+    }
+
+    /// returns the largest estimated memory used by the candidates in the beam so far.
+    pub fn peak_memory_bytes(&self) -> usize {
+        self.peak_memory_bytes
+    }
+
+    /// returns the current state of the beam.
+    pub fn snapshot(&self) -> CLQResult<BeamSnapshot> {
+        let candidates = self
+            .candidates
+            .iter()
+            .map(|candidate| Ok((candidate.get_score()?, candidate.get_checksum().unwrap())))
+            .collect::<CLQResult<Vec<(f32, u64)>>>()?;
+        Ok(BeamSnapshot {
+            epoch: self.num_steps,
+            candidates,
+            can_continue: self.can_continue,
+            memory_bytes: self.estimated_memory_bytes(),
+        })
+    }
+
+    /// advances the search by one epoch, expanding each candidate in the beam and keeping
+    /// the top `beam_size` candidates, and returns the resulting state of the beam.
+    pub fn step(&mut self) -> CLQResult<BeamSnapshot> {
+        let (_top, can_continue) = self.one_step_search(
+            self.search_problem.num_to_search,
+            self.search_problem.beam_size,
+        )?;
+        self.num_steps += 1;
+        self.can_continue = can_continue;
+        self.snapshot()
+    }
+
     /// runs one_step_search for `num_epochs` epochs, trying `num_to_search`
     /// expansion candidates for each candidate in the beam (the list of top
     /// candidates found so far). The beam is of `beam_size`. If the top
     /// score resulting from a one step search is repeated `max_repeated_prior_scores`
     /// times, the search is terminated early. (Note that the search has a stochastic
     /// component, which is why repeating the search may yield different results).
-    pub fn run_search(&mut self) -> CLQResult<BeamSearchResult<'a, TGraph>> {
+    pub fn run_search(&mut self) -> CLQResult<BeamSearchResult<C>> {
         let mut prior_score: f32 = -2.0;
         let mut num_repeated_prior_scores: usize = 0;
         let mut num_steps: usize = 0;
         if self.search_problem.num_epochs > 0 {
             for i in 0..self.search_problem.num_epochs - 1 {
                 num_steps = i + 1;
-                let (top, can_continue): (Candidate<TGraph>, bool) = self.one_step_search(
-                    self.search_problem.num_to_search,
-                    self.search_problem.beam_size,
-                )?;
+                let snapshot = self.step()?;
                 // result of all candidates being previously visited
-                if !can_continue {
+                if !snapshot.can_continue {
                     break;
                 }
-                let score: f32 = top.get_score()?;
+                let score: f32 = snapshot.top_score().ok_or_else(CLQError::err_none)?;
                 if self.verbose {
                     eprintln!(
                         "Top candidate found: (score = {}): {}",
                         score,
-                        top.to_printable_row(
-                            self.non_core_types,
-                            self.graph.get_reverse_labels_map()
-                        )?,
+                        self.candidates[0].describe(self.non_core_types)?,
                     );
                 }
                 assert!(score >= prior_score);
@@ -290,17 +455,15 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
                 }
                 prior_score = score;
             }
-            let result = self.one_step_search(
-                self.search_problem.num_to_search,
-                self.search_problem.beam_size,
-            )?;
+            self.step()?;
             return Ok(BeamSearchResult {
-                top_candidate: result.0,
+                top_candidate: self.candidates[0].replicate(true),
                 num_steps,
+                peak_memory_bytes: self.peak_memory_bytes,
             });
         }
         // if we're just running for 0 epochs (for debug purposes, return top candidate)
-        let mut best_candidate: Candidate<TGraph> = self.candidates[0].replicate(true);
+        let mut best_candidate: C = self.candidates[0].replicate(true);
         let mut best_score: f32 = 0.0;
         for candidate in &self.candidates {
             let score = candidate.get_score()?;
@@ -309,9 +472,10 @@ impl<'a, TGraph: LabeledGraph<NodeType = Node>> Beam<'a, TGraph> {
                 best_score = score;
             }
         }
-        Ok(BeamSearchResult::<TGraph> {
+        Ok(BeamSearchResult {
             top_candidate: best_candidate,
             num_steps: 0,
+            peak_memory_bytes: self.peak_memory_bytes,
         })
     }
 }
@@ -0,0 +1,204 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+
+use crate::dachshund::algorithms::bipartite_cores::BipartiteCores;
+use crate::dachshund::algorithms::bipartite_modularity::BipartiteModularity;
+use crate::dachshund::algorithms::butterflies::Butterflies;
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
+use crate::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNode, WeightedNode, WeightedNodeEdge};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::{Keys, Values};
+use std::collections::BTreeMap;
+
+/// One of the two shores of a `BipartiteGraph`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shore {
+    Left,
+    Right,
+}
+
+/// How edges of a one-mode projection are weighed, given the nodes two nodes of the same
+/// shore share on the other one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectionWeighting {
+    /// every edge weighs 1.
+    Simple,
+    /// edges weigh the number of shared neighbors.
+    SharedNeighbors,
+    /// Newman's collaboration weighting: each shared neighbor k contributes
+    /// 1 / (degree(k) - 1), so that large "collaborations" count for less.
+    Collaboration,
+}
+
+/// Keeps track of a bipartite graph, whose nodes are split into a left and a right shore,
+/// with edges only running between the two. Node ids are unique across both shores. Left
+/// nodes are the "core" nodes of `BipartiteGraphBase`, right nodes the "non-core" ones.
+pub struct BipartiteGraph {
+    pub nodes: FxHashMap<NodeId, SimpleNode>,
+    pub ids: Vec<NodeId>,
+    pub left_ids: Vec<NodeId>,
+    pub right_ids: Vec<NodeId>,
+}
+impl GraphBase for BipartiteGraph {
+    type NodeType = SimpleNode;
+
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, SimpleNode> {
+        self.nodes.keys()
+    }
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, SimpleNode> {
+        self.nodes.values()
+    }
+    fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, SimpleNode> {
+        &mut self.nodes
+    }
+    fn has_node(&self, node_id: NodeId) -> bool {
+        self.nodes.contains_key(&node_id)
+    }
+    fn get_node(&self, node_id: NodeId) -> &SimpleNode {
+        &self.nodes[&node_id]
+    }
+    fn count_edges(&self) -> usize {
+        self.left_ids
+            .iter()
+            .map(|id| self.nodes[id].neighbors.len())
+            .sum()
+    }
+    fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+    fn create_empty() -> Self {
+        BipartiteGraph {
+            nodes: FxHashMap::default(),
+            ids: Vec::new(),
+            left_ids: Vec::new(),
+            right_ids: Vec::new(),
+        }
+    }
+}
+impl BipartiteGraphBase for BipartiteGraph {
+    fn get_core_ids(&self) -> &[NodeId] {
+        &self.left_ids
+    }
+    fn get_non_core_ids(&self) -> &[NodeId] {
+        &self.right_ids
+    }
+}
+impl UndirectedGraph for BipartiteGraph {}
+impl BipartiteCores for BipartiteGraph {}
+impl BipartiteModularity for BipartiteGraph {}
+impl Butterflies for BipartiteGraph {}
+impl Subgraphs for BipartiteGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        let filter = |ids: &[NodeId]| -> Vec<NodeId> {
+            ids.iter()
+                .filter(|id| nodes.contains(id))
+                .cloned()
+                .collect()
+        };
+        BipartiteGraph {
+            ids: filter(&self.ids),
+            nodes: self
+                .nodes
+                .values()
+                .filter(|node| nodes.contains(&node.node_id))
+                .map(|node| {
+                    let neighbors = node
+                        .neighbors
+                        .iter()
+                        .filter(|id| nodes.contains(id))
+                        .cloned()
+                        .collect();
+                    (node.node_id, SimpleNode::new(node.node_id, neighbors))
+                })
+                .collect(),
+            left_ids: filter(&self.left_ids),
+            right_ids: filter(&self.right_ids),
+        }
+    }
+}
+
+impl BipartiteGraph {
+    /// ids of the nodes on the given shore, in increasing order.
+    pub fn get_shore_ids(&self, shore: Shore) -> &[NodeId] {
+        match shore {
+            Shore::Left => &self.left_ids,
+            Shore::Right => &self.right_ids,
+        }
+    }
+    /// shore of the given node, if it is in the graph.
+    pub fn get_shore(&self, id: NodeId) -> Option<Shore> {
+        if self.left_ids.binary_search(&id).is_ok() {
+            Some(Shore::Left)
+        } else if self.right_ids.binary_search(&id).is_ok() {
+            Some(Shore::Right)
+        } else {
+            None
+        }
+    }
+    /// One-mode projection of the graph onto the given shore: two nodes of that shore are
+    /// linked if they share at least one neighbor on the other shore, with edges weighed as
+    /// per `weighting`. All nodes of the shore are kept, including those left without edges.
+    pub fn get_projection(
+        &self,
+        shore: Shore,
+        weighting: ProjectionWeighting,
+    ) -> WeightedUndirectedGraph {
+        let ids = self.get_shore_ids(shore);
+        let mut weights: FxHashMap<NodeId, BTreeMap<NodeId, f64>> =
+            ids.iter().map(|id| (*id, BTreeMap::new())).collect();
+        let other_ids = match shore {
+            Shore::Left => &self.right_ids,
+            Shore::Right => &self.left_ids,
+        };
+        for other_id in other_ids {
+            let neighbors = &self.nodes[other_id].neighbors;
+            if neighbors.len() < 2 {
+                continue;
+            }
+            let weight = match weighting {
+                ProjectionWeighting::Simple | ProjectionWeighting::SharedNeighbors => 1.0,
+                ProjectionWeighting::Collaboration => 1.0 / (neighbors.len() - 1) as f64,
+            };
+            let mut add_weight = |id1: &NodeId, id2: &NodeId| {
+                let total = weights.get_mut(id1).unwrap().entry(*id2).or_insert(0.0);
+                *total = match weighting {
+                    ProjectionWeighting::Simple => 1.0,
+                    _ => *total + weight,
+                };
+            };
+            for (i, id1) in neighbors.iter().enumerate() {
+                for id2 in &neighbors[i + 1..] {
+                    add_weight(id1, id2);
+                    add_weight(id2, id1);
+                }
+            }
+        }
+        let nodes: FxHashMap<NodeId, WeightedNode> = weights
+            .into_iter()
+            .map(|(id, neighbors)| {
+                let node = WeightedNode {
+                    node_id: id,
+                    edges: neighbors
+                        .iter()
+                        .map(|(target_id, weight)| WeightedNodeEdge::new(*target_id, *weight))
+                        .collect(),
+                    neighbors: neighbors.keys().cloned().collect(),
+                };
+                (id, node)
+            })
+            .collect();
+        WeightedUndirectedGraph {
+            ids: ids.to_vec(),
+            nodes,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+
+use crate::dachshund::bipartite_graph::BipartiteGraph;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::SimpleNode;
+use fxhash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct BipartiteGraphBuilder {}
+
+impl GraphBuilderBase for BipartiteGraphBuilder {
+    type GraphType = BipartiteGraph;
+    type RowType = (i64, i64);
+
+    // builds a graph from (left id, right id) rows. Repeated edges are ignored. An id may
+    // not appear on both shores.
+    fn from_vector(&mut self, data: Vec<(i64, i64)>) -> CLQResult<BipartiteGraph> {
+        let mut left: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+        let mut right: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+        for (left_id, right_id) in data {
+            let (left_id, right_id) = (NodeId::from(left_id), NodeId::from(right_id));
+            left.entry(left_id).or_default().insert(right_id);
+            right.entry(right_id).or_default().insert(left_id);
+        }
+        if let Some(id) = left.keys().find(|id| right.contains_key(id)) {
+            return Err(CLQError::from(format!(
+                "Node {} appears on both shores of the bipartite graph",
+                id.value()
+            )));
+        }
+        let left_ids: Vec<NodeId> = left.keys().cloned().collect();
+        let right_ids: Vec<NodeId> = right.keys().cloned().collect();
+        let nodes: FxHashMap<NodeId, SimpleNode> = left
+            .into_iter()
+            .chain(right)
+            .map(|(id, neighbors)| (id, SimpleNode::new(id, neighbors.into_iter().collect())))
+            .collect();
+        Ok(BipartiteGraph {
+            ids: left_ids.iter().chain(right_ids.iter()).cloned().collect(),
+            nodes,
+            left_ids,
+            right_ids,
+        })
+    }
+}
@@ -7,17 +7,21 @@
 
 use std::cmp::{min, Eq, PartialEq, Reverse};
 use std::collections::hash_map::DefaultHasher;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-
-use fxhash::FxHashMap;
+use std::rc::Rc;
 
 use roaring::RoaringBitmap;
 
+use crate::dachshund::algorithms::dot::{format_dot, DOT_CORE_COLOR, DOT_NON_CORE_COLOR};
+use crate::dachshund::beam::BeamCandidate;
+use crate::dachshund::edge_type_weights::EdgeTypeWeights;
 use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::hashing::FastHashMap;
 use crate::dachshund::id_types::{GraphId, NodeLabel, NodeTypeIdInternal};
 use crate::dachshund::node::{Node, NodeBase};
+use crate::dachshund::node_labels::{format_node_id, NodeLabels};
 use crate::dachshund::row::CliqueRow;
 use crate::dachshund::scorer::Scorer;
 use crate::dachshund::typed_graph::LabeledGraph;
@@ -69,7 +73,7 @@ impl Hash for Recipe {
     }
 }
 
-type NeigbhorhoodMap = HashMap<u32, u32>;
+pub type NeigbhorhoodMap = FastHashMap<u32, u32>;
 
 /// This data structure contains everything that identifies a candidate (fuzzy) clique. To
 /// reiterate, a (fuzzy) clique is a subgraph of edges going from some set of "core" nodes
@@ -86,6 +90,7 @@ type NeigbhorhoodMap = HashMap<u32, u32>;
 /// during add node.
 /// - ties_between_nodes and max_core_node_edges help calculate cliqueness
 ///     (maintainted by increment_max_core_node_edges and increment_ties_between_nodes)
+/// - weighted_ties_between_nodes and max_core_node_weight: the same, weighted by edge type
 /// - neighborhood: of nodes adjacent to the clique and the edge count from
 ///     'in the clique' to help with candidate generation
 ///     (maintained by adjust_neighborhood)
@@ -109,6 +114,9 @@ where
     score: Option<f32>,
     max_core_node_edges: usize,
     ties_between_nodes: usize,
+    max_core_node_weight: f32,
+    weighted_ties_between_nodes: f32,
+    edge_type_weights: Option<Rc<EdgeTypeWeights>>,
     local_guarantee: LocalDensityGuarantee,
     neighborhood: NeigbhorhoodMap,
     node_counts: Vec<usize>,
@@ -145,18 +153,30 @@ where
             score: None,
             max_core_node_edges: 0,
             ties_between_nodes: 0,
+            max_core_node_weight: 0.0,
+            weighted_ties_between_nodes: 0.0,
+            edge_type_weights: None,
             local_guarantee: LocalDensityGuarantee {
                 num_edges: 0,
                 exceptions: RoaringBitmap::new(),
             },
-            neighborhood: HashMap::new(),
+            neighborhood: NeigbhorhoodMap::default(),
             node_counts: vec![0; num_non_core_types + 1],
         }
     }
 
+    /// creates an empty candidate object, with ties weighted by their edge type as
+    /// specified by the scorer (which can only be set before any node is added).
+    fn init_for_scorer(graph: &'a TGraph, scorer: &Scorer) -> Self {
+        Self {
+            edge_type_weights: scorer.get_edge_type_weights(),
+            ..Candidate::init_blank(graph, scorer.get_num_non_core_types())
+        }
+    }
+
     /// creates a Candidate object from a single node ID.
     pub fn new(node_id: u32, graph: &'a TGraph, scorer: &Scorer) -> CLQResult<Self> {
-        let mut candidate: Self = Candidate::init_blank(graph, scorer.get_num_non_core_types());
+        let mut candidate: Self = Candidate::init_for_scorer(graph, scorer);
         candidate.add_node(node_id)?;
         let score = scorer.score(&mut candidate)?;
         candidate.set_score(score)?;
@@ -165,18 +185,56 @@ where
 
     /// creates a Candidate object from an array of CliqueRows.
     pub fn from_clique_rows(
-        rows: &'a Vec<CliqueRow>,
+        rows: &[CliqueRow],
         graph: &'a TGraph,
         scorer: &Scorer,
     ) -> CLQResult<Option<Self>> {
         assert!(!rows.is_empty());
-        let mut candidate: Candidate<TGraph> =
-            Candidate::init_blank(graph, scorer.get_num_non_core_types());
+        let mut core_labels: Vec<NodeLabel> = Vec::new();
+        let mut non_core_labels: Vec<NodeLabel> = Vec::new();
         for row in rows {
             if graph.has_node_by_label(row.node_id) {
                 let node = graph.get_node_by_label(row.node_id);
                 assert_eq!(node.non_core_type, row.target_type);
-                candidate.add_node(node.node_id)?;
+            }
+            match row.target_type {
+                None => core_labels.push(row.node_id),
+                Some(_) => non_core_labels.push(row.node_id),
+            }
+        }
+        Self::from_labels(&core_labels, &non_core_labels, graph, scorer)
+    }
+
+    /// creates a Candidate object from the (human-readable) labels of its core and
+    /// non-core nodes. Labels absent from the graph (e.g. pruned nodes) are ignored;
+    /// returns None if none of the labels are in the graph, and an error if a label
+    /// refers to a node of the wrong kind (core vs. non-core).
+    pub fn from_labels(
+        core_labels: &[NodeLabel],
+        non_core_labels: &[NodeLabel],
+        graph: &'a TGraph,
+        scorer: &Scorer,
+    ) -> CLQResult<Option<Self>> {
+        let mut candidate: Candidate<TGraph> = Candidate::init_for_scorer(graph, scorer);
+        let labels = core_labels
+            .iter()
+            .map(|label| (label, true))
+            .chain(non_core_labels.iter().map(|label| (label, false)));
+        for (label, is_core) in labels {
+            if graph.has_node_by_label(*label) {
+                let node = graph.get_node_by_label(*label);
+                if node.is_core() != is_core {
+                    return Err(CLQError::from(format!(
+                        "Node {} is unexpectedly a {} node.",
+                        label.value(),
+                        if node.is_core() { "core" } else { "non-core" }
+                    )));
+                }
+                if !candidate.core_ids.contains(node.node_id)
+                    && !candidate.non_core_ids.contains(node.node_id)
+                {
+                    candidate.add_node(node.node_id)?;
+                }
             }
         }
         // could be that no nodes overlapped
@@ -210,13 +268,63 @@ where
         Ok(())
     }
 
+    /// removes a node from the clique, undoing add_node -- this results in the score being
+    /// reset, and the clique checksum being changed. Returns an error if the node is not
+    /// part of the clique.
+    pub fn remove_node(&mut self, node_id: u32) -> CLQResult<()> {
+        let node: &Node = self.graph.get_node(node_id);
+        let was_present = if node.is_core() {
+            self.core_ids.remove(node_id)
+        } else {
+            self.non_core_ids.remove(node_id)
+        };
+        if !was_present {
+            return Err(CLQError::from(format!(
+                "Tried to remove node {node_id}, which is not part of the candidate."
+            )));
+        }
+        self.checksum = if self.core_ids.is_empty() && self.non_core_ids.is_empty() {
+            None
+        } else {
+            self.checksum
+                .map(|checksum| checksum.wrapping_sub(hash_node_id(node_id)))
+        };
+
+        if node.is_core() {
+            self.local_guarantee.exceptions.remove(node_id);
+            self.node_counts[0_usize] -= 1;
+        } else {
+            self.decrement_max_core_node_edges(node_id)?;
+            self.node_counts[node.non_core_type.unwrap().value()] -= 1;
+            // core nodes tied to node may now have fewer edges than guaranteed.
+            for neighbors in node.neighbors_sets.values() {
+                self.local_guarantee.exceptions |= neighbors & &self.core_ids;
+            }
+        }
+
+        self.decrement_ties_between_nodes(node_id);
+        self.reset_score();
+
+        self.adjust_neighborhood_for_removal(node_id);
+        Ok(())
+    }
+
+    /// returns a rough estimate of the memory used by the candidate, in bytes: its id
+    /// bitmaps, neighborhood map and counters (but not the graph, which is shared).
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let bitmap_bytes = self.core_ids.serialized_size()
+            + self.non_core_ids.serialized_size()
+            + self.local_guarantee.exceptions.serialized_size();
+        // hashbrown keeps one control byte per bucket next to each (key, value) pair.
+        let neighborhood_bytes =
+            self.neighborhood.capacity() * (std::mem::size_of::<(u32, u32)>() + 1);
+        let node_counts_bytes = self.node_counts.capacity() * std::mem::size_of::<usize>();
+        std::mem::size_of::<Self>() + bitmap_bytes + neighborhood_bytes + node_counts_bytes
+    }
+
     /// returns sorted vector of core IDs -- useful for printing
-    pub fn sorted_core_labels(&self, reverse_labels_map: &FxHashMap<u32, NodeLabel>) -> Vec<i64> {
-        let mut vec: Vec<i64> = self
-            .core_ids
-            .iter()
-            .map(|x| reverse_labels_map[&x].value())
-            .collect();
+    pub fn sorted_core_labels(&self) -> Vec<i64> {
+        let mut vec: Vec<i64> = self.core_ids.iter().map(|x| self.get_label(x)).collect();
         vec.sort();
         vec
     }
@@ -231,14 +339,11 @@ where
     }
 
     /// returns sorted vector of non-core IDs -- useful for printing
-    pub fn sorted_non_core_labels(
-        &self,
-        reverse_labels_map: &FxHashMap<u32, NodeLabel>,
-    ) -> Vec<i64> {
+    pub fn sorted_non_core_labels(&self) -> Vec<i64> {
         let mut vec: Vec<i64> = self
             .non_core_ids
             .iter()
-            .map(|x| reverse_labels_map[&x].value())
+            .map(|x| self.get_label(x))
             .collect();
         vec.sort();
         vec
@@ -261,6 +366,11 @@ where
         self.score = None;
     }
 
+    // label of a node of the candidate, which is always one of the graph's.
+    fn get_label(&self, node_id: u32) -> i64 {
+        self.graph.get_node_label(node_id).unwrap().value()
+    }
+
     /// given a node ID, returns a reference to that node.
     pub fn get_node(&self, node_id: u32) -> &Node {
         self.graph.get_node(node_id)
@@ -294,17 +404,18 @@ where
         self.node_counts.clone()
     }
 
-    /// encodes self as tab-separated "wide" format
+    /// encodes self as tab-separated "wide" format, with node ids written as the string
+    /// labels they were interned from if node_labels are given.
     pub fn to_printable_row(
         &self,
         target_types: &[String],
-        reverse_labels_map: FxHashMap<u32, NodeLabel>,
+        node_labels: Option<&NodeLabels>,
     ) -> CLQResult<String> {
         let encode_err_handler = |e: serde_json::Error| Err(CLQError::from(e.to_string()));
 
         let cliqueness = self.get_cliqueness()?;
-        let core_ids: Vec<i64> = self.sorted_core_labels(&reverse_labels_map);
-        let non_core_ids: Vec<i64> = self.sorted_non_core_labels(&reverse_labels_map);
+        let core_ids: Vec<i64> = self.sorted_core_labels();
+        let non_core_ids: Vec<i64> = self.sorted_non_core_labels();
 
         let mut s = String::new();
         s.push_str(&core_ids.len().to_string());
@@ -312,10 +423,10 @@ where
         s.push_str(&non_core_ids.len().to_string());
         s.push('\t');
 
-        s.push_str(&serde_json::to_string(&core_ids).or_else(encode_err_handler)?);
+        s.push_str(&ids_to_json(&core_ids, node_labels).or_else(encode_err_handler)?);
         s.push('\t');
 
-        s.push_str(&serde_json::to_string(&non_core_ids).or_else(encode_err_handler)?);
+        s.push_str(&ids_to_json(&non_core_ids, node_labels).or_else(encode_err_handler)?);
         s.push('\t');
 
         let non_core_types_str: Vec<String> = self
@@ -338,14 +449,10 @@ where
     }
 
     /// used for interaction with Transformer classes.
-    pub fn get_output_rows(
-        &self,
-        graph_id: GraphId,
-        reverse_labels_map: FxHashMap<u32, NodeLabel>,
-    ) -> CLQResult<Vec<CliqueRow>> {
+    pub fn get_output_rows(&self, graph_id: GraphId) -> CLQResult<Vec<CliqueRow>> {
         let mut out: Vec<CliqueRow> = Vec::new();
 
-        for core_id in self.sorted_core_labels(&reverse_labels_map) {
+        for core_id in self.sorted_core_labels() {
             let row: CliqueRow = CliqueRow {
                 graph_id,
                 node_id: core_id.into(),
@@ -354,7 +461,7 @@ where
             out.push(row);
         }
 
-        for non_core_id in self.sorted_non_core_labels(&reverse_labels_map) {
+        for non_core_id in self.sorted_non_core_labels() {
             let non_core_node = self.graph.get_node_by_label(non_core_id.into());
             let row: CliqueRow = CliqueRow {
                 graph_id,
@@ -366,15 +473,66 @@ where
         Ok(out)
     }
 
-    /// convenience function, used for debugging and "long-format" printing
+    /// the candidate's graph in Graphviz DOT format (see `format_dot`), with nodes
+    /// labeled by their original ids (or the string labels they were interned from, if
+    /// node_labels are given). Core nodes of the candidate are filled with
+    /// DOT_CORE_COLOR, its non-core nodes with DOT_NON_CORE_COLOR, and edges between them
+    /// highlighted. Edges of several types between two nodes are drawn once.
+    pub fn to_dot(&self, name: &str, node_labels: Option<&NodeLabels>) -> String {
+        let label = |id: u32| self.get_label(id);
+        let mut nodes: Vec<(i64, Option<&str>)> = Vec::new();
+        let mut edges: BTreeSet<(i64, i64, bool)> = BTreeSet::new();
+        for node in self.graph.get_nodes_iter() {
+            let color = if self.core_ids.contains(node.node_id) {
+                Some(DOT_CORE_COLOR)
+            } else if self.non_core_ids.contains(node.node_id) {
+                Some(DOT_NON_CORE_COLOR)
+            } else {
+                None
+            };
+            nodes.push((label(node.node_id), color));
+            if node.is_core {
+                for e in node.get_edges() {
+                    let in_clique = self.core_ids.contains(node.node_id)
+                        && self.non_core_ids.contains(e.target_id);
+                    edges.insert((label(node.node_id), label(e.target_id), in_clique));
+                }
+            }
+        }
+        nodes.sort();
+        let edges: Vec<(i64, i64, bool)> = edges.into_iter().collect();
+        match node_labels {
+            None => format_dot(name, &nodes, &edges),
+            Some(node_labels) => {
+                // labels are quoted, as they may not be valid DOT ids.
+                let quote = |id: i64| {
+                    format!(
+                        "\"{}\"",
+                        node_labels.get_label_or_id(id.into()).replace('"', "\\\"")
+                    )
+                };
+                let nodes: Vec<(String, Option<&str>)> =
+                    nodes.into_iter().map(|(id, x)| (quote(id), x)).collect();
+                let edges: Vec<(String, String, bool)> = edges
+                    .into_iter()
+                    .map(|(id1, id2, x)| (quote(id1), quote(id2), x))
+                    .collect();
+                format_dot(name, &nodes, &edges)
+            }
+        }
+    }
+
+    /// convenience function, used for debugging and "long-format" printing. Node ids are
+    /// written as the string labels they were interned from if node_labels are given.
     pub fn print(
         &self,
         graph_id: GraphId,
         target_types: &[String],
         core_type: &str,
+        node_labels: Option<&NodeLabels>,
         output: &Sender<(Option<String>, bool)>,
     ) -> CLQResult<()> {
-        for output_row in &self.get_output_rows(graph_id, self.graph.get_reverse_labels_map())? {
+        for output_row in &self.get_output_rows(graph_id)? {
             let node_type: String = match output_row.target_type {
                 // this is hacky -- when t is 0 it's an indication of this being the
                 // core type, but not for TypedGraphBuilder
@@ -386,7 +544,7 @@ where
                     Some(format!(
                         "{}\t{}\t{}",
                         graph_id.value(),
-                        output_row.node_id.value(),
+                        format_node_id(output_row.node_id, node_labels),
                         node_type
                     )),
                     false,
@@ -419,6 +577,9 @@ where
             },
             max_core_node_edges: self.max_core_node_edges,
             ties_between_nodes: self.ties_between_nodes,
+            max_core_node_weight: self.max_core_node_weight,
+            weighted_ties_between_nodes: self.weighted_ties_between_nodes,
+            edge_type_weights: self.edge_type_weights.clone(),
             local_guarantee: self.local_guarantee.clone(),
             neighborhood: new_neighborhood,
             node_counts: self.node_counts.clone(),
@@ -452,42 +613,12 @@ where
         num_to_search: usize,
         visited_candidates: &mut HashSet<u64>,
     ) -> CLQResult<Vec<Recipe>> {
-        assert!(!visited_candidates.contains(&self.checksum.unwrap()));
-        let mut h = BinaryHeap::with_capacity(num_to_search);
-
-        // Use the heap to keep track of the nodes with the most ties to the
-        // current candidate: If the heap is already full, look at the max element
-        // (the one with fewest ties because of Reverse). If the element we're
-        // considering is smaller (more ties) we can remove the max element
-        // and push the new element onto the heap.
-        for (node_id, num_ties) in self.neighborhood.iter() {
-            let heap_element = (Reverse(num_ties), node_id);
-            if h.len() < num_to_search {
-                h.push(heap_element);
-            } else if heap_element < *h.peek().unwrap() {
-                h.pop();
-                h.push(heap_element);
-            }
-        }
-
-        let mut expansion_candidates: Vec<Recipe> = Vec::with_capacity(num_to_search);
-
-        for (_num_ties, &node_id) in h.into_sorted_vec().iter() {
-            let recipe = Recipe {
-                checksum: self.checksum,
-                node_id: Some(node_id),
-                score: None,
-                local_guarantee: None,
-            };
-
-            let new_checksum = merge_checksum(self.checksum, node_id).unwrap();
-            if !visited_candidates.contains(&new_checksum) {
-                expansion_candidates.push(recipe);
-            }
-        }
-        assert!(self.checksum.unwrap() != 0);
-        visited_candidates.insert(self.checksum.unwrap());
-        Ok(expansion_candidates)
+        Ok(get_expansion_recipes(
+            self.checksum,
+            &self.neighborhood,
+            num_to_search,
+            visited_candidates,
+        ))
     }
 
     /// finds (up to) num_to_search expansion candidates and scores them.
@@ -527,6 +658,19 @@ where
         }
     }
 
+    /// Returns size of candidate if the given node were to be removed. Assumes that
+    /// node is currently part of the candidate.
+    pub fn get_size_without_node(&self, node: &Node) -> CLQResult<usize> {
+        if node.is_core() {
+            Ok((self.core_ids.len() as usize - 1) * self.max_core_node_edges)
+        } else {
+            let edge_count = node
+                .max_edge_count_with_core_node()?
+                .ok_or_else(CLQError::err_none)?;
+            Ok(self.core_ids.len() as usize * (self.max_core_node_edges - edge_count))
+        }
+    }
+
     // Update the size to account for for adding node_id. Can be called immediately before
     // or after inserting the node into the set of ids. Only call this when adding a noncore node.
     fn increment_max_core_node_edges(&mut self, node_id: u32) -> CLQResult<()> {
@@ -535,15 +679,49 @@ where
             .max_edge_count_with_core_node()?
             .ok_or_else(CLQError::err_none)?;
         self.max_core_node_edges += new_edge_count;
+        self.max_core_node_weight += self.max_weight_with_core_node(self.get_node(node_id))?;
         Ok(())
     }
 
-    /// computes "cliqueness", the density of ties between core and non-core nodes.
+    // Update the size to account for removing node_id. Can be called immediately before
+    // or after removing the node from the set of ids. Only call this when removing a noncore node.
+    fn decrement_max_core_node_edges(&mut self, node_id: u32) -> CLQResult<()> {
+        let edge_count = self
+            .get_node(node_id)
+            .max_edge_count_with_core_node()?
+            .ok_or_else(CLQError::err_none)?;
+        self.max_core_node_edges -= edge_count;
+        self.max_core_node_weight -= self.max_weight_with_core_node(self.get_node(node_id))?;
+        Ok(())
+    }
+
+    // Weighted counterpart of max_edge_count_with_core_node: the total weight of the ties
+    // node (a non-core node) could have with a core node.
+    fn max_weight_with_core_node(&self, node: &Node) -> CLQResult<f32> {
+        let max_edge_count = node
+            .max_edge_count_with_core_node()?
+            .ok_or_else(CLQError::err_none)?;
+        Ok(match &self.edge_type_weights {
+            Some(weights) => weights.max_weight_with_core_node(&node.non_core_type.unwrap()),
+            None => max_edge_count as f32,
+        })
+    }
+
+    // Weighted counterpart of count_ties_with_ids: the total weight of the ties between
+    // node and the nodes in ids.
+    fn weigh_ties_with_ids(&self, node: &Node, ids: &RoaringBitmap) -> f32 {
+        match &self.edge_type_weights {
+            Some(weights) => weights.weigh_ties_with_ids(node, ids),
+            None => node.count_ties_with_ids(ids) as f32,
+        }
+    }
+
+    /// computes "cliqueness", the density of ties between core and non-core nodes. If edge
+    /// types are weighted, this is the total weight of these ties over the largest it could be.
     pub fn get_cliqueness(&self) -> CLQResult<f32> {
-        let size = self.get_size()?;
-        let ties_between_nodes = self.count_ties_between_nodes()?;
-        let cliqueness: f32 = if size > 0 {
-            ties_between_nodes as f32 / size as f32
+        let size = self.core_ids.len() as f32 * self.max_core_node_weight;
+        let cliqueness: f32 = if size > 0.0 {
+            self.weighted_ties_between_nodes / size
         } else {
             1.0
         };
@@ -552,23 +730,77 @@ where
 
     /// computes "cliqueness", the density of ties between core and non-core nodes.
     pub fn get_cliqueness_with_node(&self, node: &Node) -> CLQResult<f32> {
-        let size = self.get_size_with_node(node)?;
+        let (size, new_ties) = if node.is_core() {
+            (
+                (self.core_ids.len() + 1) as f32 * self.max_core_node_weight,
+                self.weigh_ties_with_ids(node, &self.non_core_ids),
+            )
+        } else {
+            (
+                self.core_ids.len() as f32
+                    * (self.max_core_node_weight + self.max_weight_with_core_node(node)?),
+                self.weigh_ties_with_ids(node, &self.core_ids),
+            )
+        };
+
+        let ties_between_nodes = self.weighted_ties_between_nodes + new_ties;
+        let cliqueness: f32 = if size > 0.0 {
+            ties_between_nodes / size
+        } else {
+            1.0
+        };
+        Ok(cliqueness)
+    }
 
-        let new_ties = if node.is_core() {
-            node.count_ties_with_ids(&self.non_core_ids)
+    /// computes "cliqueness" if the given node were to be removed. Assumes that node is
+    /// currently part of the candidate.
+    pub fn get_cliqueness_without_node(&self, node: &Node) -> CLQResult<f32> {
+        let (size, lost_ties) = if node.is_core() {
+            (
+                (self.core_ids.len() - 1) as f32 * self.max_core_node_weight,
+                self.weigh_ties_with_ids(node, &self.non_core_ids),
+            )
         } else {
-            node.count_ties_with_ids(&self.core_ids)
+            (
+                self.core_ids.len() as f32
+                    * (self.max_core_node_weight - self.max_weight_with_core_node(node)?),
+                self.weigh_ties_with_ids(node, &self.core_ids),
+            )
         };
 
-        let ties_between_nodes = self.count_ties_between_nodes()? + new_ties;
-        let cliqueness: f32 = if size > 0 {
-            ties_between_nodes as f32 / size as f32
+        let ties_between_nodes = self.weighted_ties_between_nodes - lost_ties;
+        let cliqueness: f32 = if size > 0.0 {
+            ties_between_nodes / size
         } else {
             1.0
         };
         Ok(cliqueness)
     }
 
+    // Returns true if every core node has at least thresh fraction of the possible edges
+    // when node (part of the candidate) is removed. The local density guarantee only
+    // tracks additions, so every remaining core node is checked.
+    pub fn local_thresh_score_without_node_at_least(&self, thresh: f32, node: &Node) -> bool {
+        let new_max_core_node_edges = if node.is_core() {
+            self.max_core_node_edges
+        } else {
+            self.max_core_node_edges - node.max_edge_count_with_core_node().unwrap().unwrap()
+        };
+        let implied_edge_thresh = (thresh * new_max_core_node_edges as f32).ceil() as usize;
+        self.core_ids
+            .iter()
+            .filter(|node_id| *node_id != node.node_id)
+            .all(|node_id| {
+                let mut edge_count = self
+                    .get_node(node_id)
+                    .count_ties_with_ids(&self.non_core_ids);
+                if !node.is_core() {
+                    edge_count -= node.count_ties_with_id(node_id);
+                }
+                edge_count >= implied_edge_thresh
+            })
+    }
+
     // Returns true if every core node has at least thresh fraction
     // of the possible edges (when node is added), using the
     // local density guarantee as applicable.
@@ -692,6 +924,86 @@ where
         true
     }
 
+    // Returns true if every non-core node of a type with a threshold in non_core_thresh
+    // has at least that fraction of its possible ties with core nodes (when node, if any,
+    // is added). Adding a core node raises the number of possible ties of every non-core
+    // node, so all non-core nodes of these types are checked.
+    fn non_core_local_thresh_check(
+        &self,
+        non_core_thresh: &BTreeMap<NodeTypeIdInternal, f32>,
+        node: Option<&Node>,
+    ) -> bool {
+        let added_core = node.filter(|x| x.is_core());
+        let num_core_ids = self.core_ids.len() as usize + added_core.is_some() as usize;
+        let has_enough_ties = |non_core: &Node, num_ties: usize| {
+            let non_core_type_id = non_core.non_core_type.unwrap().value();
+            match non_core_thresh.get(&non_core_type_id) {
+                Some(thresh) => {
+                    let max_ties =
+                        non_core.max_edge_count_with_core_node().unwrap().unwrap() * num_core_ids;
+                    num_ties >= (thresh * max_ties as f32).ceil() as usize
+                }
+                None => true,
+            }
+        };
+        for node_id in &self.non_core_ids {
+            let non_core = self.get_node(node_id);
+            let mut num_ties = non_core.count_ties_with_ids(&self.core_ids);
+            if let Some(core) = added_core {
+                num_ties += non_core.count_ties_with_id(core.node_id);
+            }
+            if !has_enough_ties(non_core, num_ties) {
+                return false;
+            }
+        }
+        match node {
+            Some(non_core) if !non_core.is_core() => {
+                has_enough_ties(non_core, non_core.count_ties_with_ids(&self.core_ids))
+            }
+            _ => true,
+        }
+    }
+
+    // Returns true if every non-core node of a type with a threshold in non_core_thresh
+    // has at least that fraction of its possible ties with core nodes.
+    pub fn non_core_local_thresh_score_at_least(
+        &self,
+        non_core_thresh: &BTreeMap<NodeTypeIdInternal, f32>,
+    ) -> bool {
+        self.non_core_local_thresh_check(non_core_thresh, None)
+    }
+
+    // Returns true if every non-core node of a type with a threshold in non_core_thresh
+    // has at least that fraction of its possible ties with core nodes, when node is added.
+    pub fn non_core_local_thresh_score_with_node_at_least(
+        &self,
+        non_core_thresh: &BTreeMap<NodeTypeIdInternal, f32>,
+        node: &Node,
+    ) -> bool {
+        self.non_core_local_thresh_check(non_core_thresh, Some(node))
+    }
+
+    /// Returns the local density of each core node: the fraction of the possible edges
+    /// (as bounded by max_core_node_edges, the same bound the local threshold is checked
+    /// against) that it has with non-core nodes of the candidate. The core node with the
+    /// lowest density is the one that fails a local threshold first.
+    pub fn get_local_densities(&self) -> HashMap<u32, f32> {
+        self.core_ids
+            .iter()
+            .map(|node_id| {
+                let edge_count = self
+                    .get_node(node_id)
+                    .count_ties_with_ids(&self.non_core_ids);
+                let density = if self.max_core_node_edges > 0 {
+                    edge_count as f32 / self.max_core_node_edges as f32
+                } else {
+                    1.0
+                };
+                (node_id, density)
+            })
+            .collect()
+    }
+
     /// checks if Candidate is a true clique, defined as a subgraph where the total number
     /// of ties between nodes is equal to the maximum number of ties between nodes.
     pub fn is_clique(&self) -> CLQResult<bool> {
@@ -706,13 +1018,31 @@ where
     // Update the count of ties between nodes to account for adding node_id. Can be called
     // immediately before or immediately after inserting node into the set of ids.
     fn increment_ties_between_nodes(&mut self, node_id: u32) {
-        let new_ties = if self.graph.get_node(node_id).is_core() {
-            self.get_node(node_id)
-                .count_ties_with_ids(&self.non_core_ids)
+        let node = self.get_node(node_id);
+        let opposite_shore = if node.is_core() {
+            &self.non_core_ids
         } else {
-            self.get_node(node_id).count_ties_with_ids(&self.core_ids)
+            &self.core_ids
         };
+        let new_ties = node.count_ties_with_ids(opposite_shore);
+        let new_weighted_ties = self.weigh_ties_with_ids(node, opposite_shore);
         self.ties_between_nodes += new_ties;
+        self.weighted_ties_between_nodes += new_weighted_ties;
+    }
+
+    // Update the count of ties between nodes to account for removing node_id. Can be called
+    // immediately before or immediately after removing node from the set of ids.
+    fn decrement_ties_between_nodes(&mut self, node_id: u32) {
+        let node = self.get_node(node_id);
+        let opposite_shore = if node.is_core() {
+            &self.non_core_ids
+        } else {
+            &self.core_ids
+        };
+        let lost_ties = node.count_ties_with_ids(opposite_shore);
+        let lost_weighted_ties = self.weigh_ties_with_ids(node, opposite_shore);
+        self.ties_between_nodes -= lost_ties;
+        self.weighted_ties_between_nodes -= lost_weighted_ties;
     }
 
     // Adjust the neighborhood hashmap to account for adding added_node:
@@ -742,6 +1072,34 @@ where
         self.neighborhood.remove(&node_id);
     }
 
+    // Adjust the neighborhood hashmap to account for removing node_id, undoing
+    // adjust_neighborhood: neighbors outside the clique lose an edge from it (and leave
+    // the neighborhood if that was their last one), and node_id joins the neighborhood
+    // if it has edges to nodes still in the clique.
+    fn adjust_neighborhood_for_removal(&mut self, node_id: u32) {
+        let opposite_shore = if self.graph.get_node(node_id).is_core() {
+            &self.non_core_ids
+        } else {
+            &self.core_ids
+        };
+
+        let mut num_ties_with_clique: u32 = 0;
+        for edge in &self.graph.get_node(node_id).edges {
+            let target_id = edge.target_id;
+            if opposite_shore.contains(target_id) {
+                num_ties_with_clique += 1;
+            } else if let Some(counter) = self.neighborhood.get_mut(&target_id) {
+                *counter -= 1;
+                if *counter == 0 {
+                    self.neighborhood.remove(&target_id);
+                }
+            }
+        }
+        if num_ties_with_clique > 0 {
+            self.neighborhood.insert(node_id, num_ties_with_clique);
+        }
+    }
+
     /// TODO: Can this use the non_core_counts?
     /// gets densities over each non-core type
     fn get_non_core_densities(&self, num_non_core_types: usize) -> CLQResult<Vec<f32>> {
@@ -790,10 +1148,112 @@ where
     }
 }
 
-fn merge_checksum(checksum: Option<u64>, node_id: u32) -> Option<u64> {
+impl<'a, TGraph> BeamCandidate for Candidate<'a, TGraph>
+where
+    TGraph: LabeledGraph<NodeType = Node>,
+{
+    type Scorer = Scorer;
+    fn get_checksum(&self) -> Option<u64> {
+        self.checksum
+    }
+    fn get_score(&self) -> CLQResult<f32> {
+        Candidate::get_score(self)
+    }
+    fn as_recipe(&self) -> Recipe {
+        Candidate::as_recipe(self)
+    }
+    fn expand_from_recipe(&self, recipe: &Recipe) -> CLQResult<Self> {
+        Candidate::expand_from_recipe(self, recipe)
+    }
+    fn one_step_search(
+        &self,
+        num_to_search: usize,
+        visited_candidates: &mut HashSet<u64>,
+        scorer: &Scorer,
+    ) -> CLQResult<Vec<Recipe>> {
+        Candidate::one_step_search(self, num_to_search, visited_candidates, scorer)
+    }
+    fn replicate(&self, keep_score: bool) -> Self {
+        Candidate::replicate(self, keep_score)
+    }
+    fn estimated_memory_bytes(&self) -> usize {
+        Candidate::estimated_memory_bytes(self)
+    }
+    fn describe(&self, non_core_types: &[String]) -> CLQResult<String> {
+        self.to_printable_row(non_core_types, None)
+    }
+}
+
+// ids as a JSON array, of the string labels they were interned from if node_labels are
+// given.
+fn ids_to_json(ids: &[i64], node_labels: Option<&NodeLabels>) -> serde_json::Result<String> {
+    match node_labels {
+        None => serde_json::to_string(ids),
+        Some(node_labels) => serde_json::to_string(
+            &ids.iter()
+                .map(|x| node_labels.get_label_or_id((*x).into()))
+                .collect::<Vec<String>>(),
+        ),
+    }
+}
+
+fn hash_node_id(node_id: u32) -> u64 {
     let mut s = DefaultHasher::new();
     node_id.hash(&mut s);
-    let node_hash: u64 = s.finish();
+    s.finish()
+}
+
+/// Recipes for expanding the candidate with the given checksum and neighborhood (map
+/// from nodes adjacent to the candidate to their number of ties with it) with each of the
+/// (up to) num_to_search nodes with the most ties, leaving out those giving candidates
+/// visited before. Marks the candidate as visited.
+pub fn get_expansion_recipes(
+    checksum: Option<u64>,
+    neighborhood: &NeigbhorhoodMap,
+    num_to_search: usize,
+    visited_candidates: &mut HashSet<u64>,
+) -> Vec<Recipe> {
+    assert!(!visited_candidates.contains(&checksum.unwrap()));
+    let mut h = BinaryHeap::with_capacity(num_to_search);
+
+    // Use the heap to keep track of the nodes with the most ties to the
+    // current candidate: If the heap is already full, look at the max element
+    // (the one with fewest ties because of Reverse). If the element we're
+    // considering is smaller (more ties) we can remove the max element
+    // and push the new element onto the heap.
+    for (node_id, num_ties) in neighborhood.iter() {
+        let heap_element = (Reverse(num_ties), node_id);
+        if h.len() < num_to_search {
+            h.push(heap_element);
+        } else if heap_element < *h.peek().unwrap() {
+            h.pop();
+            h.push(heap_element);
+        }
+    }
+
+    let mut expansion_candidates: Vec<Recipe> = Vec::with_capacity(num_to_search);
+
+    for (_num_ties, &node_id) in h.into_sorted_vec().iter() {
+        let recipe = Recipe {
+            checksum,
+            node_id: Some(node_id),
+            score: None,
+            local_guarantee: None,
+        };
+
+        let new_checksum = merge_checksum(checksum, node_id).unwrap();
+        if !visited_candidates.contains(&new_checksum) {
+            expansion_candidates.push(recipe);
+        }
+    }
+    assert!(checksum.unwrap() != 0);
+    visited_candidates.insert(checksum.unwrap());
+    expansion_candidates
+}
+
+/// checksum of the candidate with the given checksum (None if empty) and node node_id.
+pub fn merge_checksum(checksum: Option<u64>, node_id: u32) -> Option<u64> {
+    let node_hash: u64 = hash_node_id(node_id);
     if let Some(candidate_hash) = checksum {
         Some(candidate_hash.wrapping_add(node_hash))
     } else {
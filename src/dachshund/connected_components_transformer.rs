@@ -8,28 +8,36 @@ extern crate clap;
 extern crate fxhash;
 extern crate serde_json;
 
-use crate::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use crate::dachshund::algorithms::union_find::UnionFind;
 use crate::dachshund::error::CLQResult;
-use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
-use crate::dachshund::row::{Row, SimpleEdgeRow};
-use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use crate::dachshund::node_labels::format_node_id;
+use crate::dachshund::row::Row;
 use crate::dachshund::transformer_base::TransformerBase;
-use crate::GraphId;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
+/// Prints the connected components of each graph, as original_graph_id\tcomponent\tnode_id,
+/// with components numbered in order of their smallest node. Components are updated as
+/// edges are read, so graphs are never built.
 pub struct ConnectedComponentsTransformer {
-    batch: Vec<SimpleEdgeRow>,
+    components: UnionFind,
     line_processor: Arc<LineProcessor>,
 }
 impl ConnectedComponentsTransformer {
     pub fn new() -> Self {
         Self {
-            batch: Vec::new(),
+            components: UnionFind::new(),
             line_processor: Arc::new(LineProcessor::new()),
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`), and writes them back as such in the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
 }
 impl Default for ConnectedComponentsTransformer {
     fn default() -> Self {
@@ -42,11 +50,13 @@ impl TransformerBase for ConnectedComponentsTransformer {
         self.line_processor.clone()
     }
     fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
-        self.batch.push(row.as_simple_edge_row().unwrap());
+        let (source_id, target_id) = row.as_simple_edge_row().unwrap().as_tuple();
+        self.components
+            .add_edge(NodeId::from(source_id), NodeId::from(target_id));
         Ok(())
     }
     fn reset(&mut self) -> CLQResult<()> {
-        self.batch.clear();
+        self.components.reset();
         Ok(())
     }
     fn process_batch(
@@ -54,17 +64,22 @@ impl TransformerBase for ConnectedComponentsTransformer {
         graph_id: GraphId,
         output: &Sender<(Option<String>, bool)>,
     ) -> CLQResult<()> {
-        let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
-        let mut builder = SimpleUndirectedGraphBuilder {};
-        let graph = builder.from_vector(tuples)?;
-
-        let conn_comp = graph.get_connected_components();
+        let conn_comp = self.components.get_components();
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
+        let labels = self
+            .line_processor
+            .get_node_labels()
+            .and_then(|x| x.get(graph_id));
         for (cid, nodes) in conn_comp.into_iter().enumerate() {
             for node_id in nodes {
-                let line = format!("{}\t{}\t{}", original_id, cid, node_id.value());
+                let line = format!(
+                    "{}\t{}\t{}",
+                    original_id,
+                    cid,
+                    format_node_id(node_id, labels.as_deref())
+                );
                 output.send((Some(line), false)).unwrap();
             }
         }
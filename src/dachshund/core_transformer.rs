@@ -9,9 +9,11 @@ extern crate serde_json;
 
 use crate::dachshund::algorithms::coreness::Coreness;
 use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_arena::GraphArena;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::node_labels::format_node_id;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_transformer::GraphStatsTransformerBase;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
@@ -24,6 +26,8 @@ use std::sync::Arc;
 pub struct CoreTransformer {
     batch: Vec<SimpleEdgeRow>,
     line_processor: Arc<LineProcessor>,
+    // reused from one graph to the next.
+    arena: GraphArena,
 }
 
 impl CoreTransformer {
@@ -31,12 +35,27 @@ impl CoreTransformer {
         Self {
             batch: Vec::new(),
             line_processor: Arc::new(LineProcessor::new()),
+            arena: GraphArena::new(),
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`), and writes them back as such in the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
     fn compute_coreness_and_anomalies(
         graph: &SimpleUndirectedGraph,
+        arena: &mut GraphArena,
     ) -> (HashMap<NodeId, usize>, HashMap<NodeId, f64>) {
-        let (_, coreness) = graph.get_coreness();
+        arena.load(graph);
+        arena.compute_coreness_values();
+        let coreness: HashMap<NodeId, usize> = arena
+            .get_ids()
+            .iter()
+            .cloned()
+            .zip(arena.get_coreness_values().iter().cloned())
+            .collect();
         let coreness_anomalies = graph.get_coreness_anomaly(&coreness);
         (coreness, coreness_anomalies)
     }
@@ -69,10 +88,15 @@ impl TransformerBase for CoreTransformer {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleUndirectedGraphBuilder {};
         let graph = builder.from_vector(tuples)?;
-        let (coreness_map, anomaly_map) = CoreTransformer::compute_coreness_and_anomalies(&graph);
+        let (coreness_map, anomaly_map) =
+            CoreTransformer::compute_coreness_and_anomalies(&graph, &mut self.arena);
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
+        let labels = self
+            .line_processor
+            .get_node_labels()
+            .and_then(|x| x.get(graph_id));
         let mut coreness: Vec<(NodeId, usize)> = coreness_map.into_iter().collect();
         coreness.sort_by_key(|(_node_id, coreness)| *coreness);
         for (node_id, node_coreness) in coreness {
@@ -81,7 +105,7 @@ impl TransformerBase for CoreTransformer {
             let line: String = format!(
                 "{}\t{}\t{}\t{}\t{}",
                 original_id,
-                node_id.value(),
+                format_node_id(node_id, labels.as_deref()),
                 node_coreness,
                 degree,
                 anomaly
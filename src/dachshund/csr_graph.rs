@@ -0,0 +1,176 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
+use crate::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use crate::dachshund::algorithms::betweenness::Betweenness;
+use crate::dachshund::algorithms::clustering::Clustering;
+use crate::dachshund::algorithms::connected_components::{
+    ConnectedComponents, ConnectedComponentsUndirected,
+};
+use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::degree_distribution::DegreeDistribution;
+use crate::dachshund::algorithms::dot::Dot;
+use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use crate::dachshund::algorithms::gexf::Gexf;
+use crate::dachshund::algorithms::label_propagation::LabelPropagation;
+use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::algorithms::triangles::Triangles;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{CsrNode, NodeBase, NodeEdgeBase};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::{Keys, Values};
+use std::sync::Arc;
+
+/// Keeps track of an immutable, simple undirected graph in compressed sparse row form: the
+/// neighbors of all nodes are stored back to back in a single array, with each node's
+/// neighbors in increasing order of id. Nodes are lightweight views into that array, kept
+/// in a map so that algorithms written against `GraphBase` work on the graph unchanged.
+pub struct CsrGraph {
+    // node ids, in increasing order.
+    pub ids: Vec<NodeId>,
+    // neighbors of the i-th node are targets[offsets[i]..offsets[i + 1]].
+    offsets: Vec<usize>,
+    targets: Arc<[NodeId]>,
+    nodes: FxHashMap<NodeId, CsrNode>,
+}
+impl GraphBase for CsrGraph {
+    type NodeType = CsrNode;
+
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, CsrNode> {
+        self.nodes.keys()
+    }
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, CsrNode> {
+        self.nodes.values()
+    }
+    fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, CsrNode> {
+        &mut self.nodes
+    }
+    fn has_node(&self, node_id: NodeId) -> bool {
+        self.nodes.contains_key(&node_id)
+    }
+    fn get_node(&self, node_id: NodeId) -> &CsrNode {
+        &self.nodes[&node_id]
+    }
+    fn count_edges(&self) -> usize {
+        self.targets.len() / 2
+    }
+    fn count_nodes(&self) -> usize {
+        self.ids.len()
+    }
+    fn create_empty() -> Self {
+        Self::from_adjacency(Vec::new(), Vec::new())
+    }
+}
+impl CsrGraph {
+    // builds the graph from node ids, in increasing order, and their sorted neighbors.
+    fn from_adjacency(ids: Vec<NodeId>, neighbors: Vec<Vec<NodeId>>) -> Self {
+        let mut offsets: Vec<usize> = Vec::with_capacity(ids.len() + 1);
+        offsets.push(0);
+        for x in &neighbors {
+            offsets.push(offsets[offsets.len() - 1] + x.len());
+        }
+        let targets: Arc<[NodeId]> = neighbors.into_iter().flatten().collect();
+        let nodes = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let node = CsrNode::new(*id, targets.clone(), offsets[i], offsets[i + 1]);
+                (*id, node)
+            })
+            .collect();
+        Self {
+            ids,
+            offsets,
+            targets,
+            nodes,
+        }
+    }
+
+    /// builds the undirected graph underlying any graph: nodes are tied if either has an
+    /// edge to the other, with edges repeated across types or directions kept once.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: GraphBase,
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        let ids = graph.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut neighbors: Vec<Vec<NodeId>> = vec![Vec::new(); ids.len()];
+        for (i, id) in ids.iter().enumerate() {
+            for e in graph.get_node(*id).get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                neighbors[i].push(neighbor_id);
+                neighbors[positions[&neighbor_id]].push(*id);
+            }
+        }
+        for x in neighbors.iter_mut() {
+            x.sort_unstable();
+            x.dedup();
+        }
+        Self::from_adjacency(ids, neighbors)
+    }
+
+    /// position of a node in `ids`, which indexes its neighbors in the CSR arrays.
+    pub fn get_position(&self, id: NodeId) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+    /// neighbors of the node at the given position, in increasing order of id.
+    pub fn get_neighbors_at(&self, position: usize) -> &[NodeId] {
+        &self.targets[self.offsets[position]..self.offsets[position + 1]]
+    }
+    pub fn get_node_degree(&self, id: NodeId) -> usize {
+        self.nodes[&id].degree()
+    }
+}
+impl UndirectedGraph for CsrGraph {}
+
+impl AdjacencyMatrix for CsrGraph {}
+impl AlgebraicConnectivity for CsrGraph {}
+impl Betweenness for CsrGraph {}
+impl Clustering for CsrGraph {}
+impl ConnectedComponents for CsrGraph {}
+impl ConnectedComponentsUndirected for CsrGraph {}
+impl Connectivity for CsrGraph {}
+impl ConnectivityUndirected for CsrGraph {}
+impl Coreness for CsrGraph {}
+impl DegreeDistribution for CsrGraph {}
+impl Dot for CsrGraph {}
+impl Gexf for CsrGraph {}
+impl EigenvectorCentrality for CsrGraph {}
+impl LabelPropagation for CsrGraph {}
+impl Laplacian for CsrGraph {}
+impl PageRank for CsrGraph {}
+impl ShortestPaths for CsrGraph {}
+impl Subgraphs for CsrGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        let (ids, neighbors) = (0..self.ids.len())
+            .filter(|i| nodes.contains(&self.ids[*i]))
+            .map(|i| {
+                let neighbors: Vec<NodeId> = self
+                    .get_neighbors_at(i)
+                    .iter()
+                    .filter(|id| nodes.contains(id))
+                    .cloned()
+                    .collect();
+                (self.ids[i], neighbors)
+            })
+            .unzip();
+        Self::from_adjacency(ids, neighbors)
+    }
+}
+impl Transitivity for CsrGraph {}
+impl Triangles for CsrGraph {}
@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::{EdgeTypeId, NodeTypeId};
+use crate::dachshund::node::{Node, TypedNodeBase};
+use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
+use roaring::RoaringBitmap;
+use std::collections::HashMap;
+
+/// How much ties of each edge type count towards cliqueness, as given by the optional
+/// fourth element of typespec entries, e.g.:
+/// [["author", "published", "article", 1.0], ["author", "cited", "article", 0.25]].
+/// Edge types without a weight count 1.0.
+pub struct EdgeTypeWeights {
+    // weight of each edge type, indexed by EdgeTypeId.
+    weights: Vec<f32>,
+    // largest total weight of the ties a non-core node of each type can have with a core
+    // node, indexed by NodeTypeId (0 being the core type).
+    max_weights_with_core_node: Vec<f32>,
+}
+
+impl EdgeTypeWeights {
+    /// reads edge type weights from a typespec, whose edge types are indexed as in
+    /// edge_types and non-core types as in non_core_type_ids. Returns None if no entry
+    /// has a weight, and an error if a weight isn't a positive number, or if an edge type
+    /// is given different weights.
+    pub fn from_typespec(
+        typespec: &[Vec<String>],
+        edge_types: &[String],
+        non_core_type_ids: &NonCoreTypeIds,
+    ) -> CLQResult<Option<Self>> {
+        if typespec.iter().all(|item| item.len() <= 3) {
+            return Ok(None);
+        }
+        let mut weights_by_name: HashMap<&str, f32> = HashMap::new();
+        let mut max_weights_with_core_node: Vec<f32> = Vec::new();
+        for item in typespec {
+            if item.len() > 4 {
+                return Err(CLQError::from(format!(
+                    "Typespec entries have 3 or 4 elements, got {item:?}"
+                )));
+            }
+            let edge_type = &item[1];
+            let weight: f32 = match item.get(3) {
+                Some(value) => value.parse::<f32>().map_err(|err| {
+                    CLQError::from(format!(
+                        "Invalid weight for edge type {edge_type}: '{value}' ({err})"
+                    ))
+                })?,
+                None => 1.0,
+            };
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(CLQError::from(format!(
+                    "Weight for edge type {edge_type} must be positive, got {weight}"
+                )));
+            }
+            if *weights_by_name.entry(edge_type).or_insert(weight) != weight {
+                return Err(CLQError::from(format!(
+                    "Edge type {edge_type} is given different weights"
+                )));
+            }
+            let non_core_type_id = non_core_type_ids.require(&item[2])?.value();
+            if max_weights_with_core_node.len() <= non_core_type_id {
+                max_weights_with_core_node.resize(non_core_type_id + 1, 0.0);
+            }
+            max_weights_with_core_node[non_core_type_id] += weight;
+        }
+        let weights: Vec<f32> = edge_types
+            .iter()
+            .map(|edge_type| *weights_by_name.get(edge_type.as_str()).unwrap_or(&1.0))
+            .collect();
+        Ok(Some(Self {
+            weights,
+            max_weights_with_core_node,
+        }))
+    }
+
+    pub fn get_weight(&self, edge_type: &EdgeTypeId) -> f32 {
+        self.weights[edge_type.value()]
+    }
+
+    /// total weight of the ties a non-core node of the given type could have with a
+    /// core node (the weighted counterpart of `max_edge_count_with_core_node`).
+    pub fn max_weight_with_core_node(&self, non_core_type: &NodeTypeId) -> f32 {
+        self.max_weights_with_core_node[non_core_type.value()]
+    }
+
+    /// total weight of the ties between node and the nodes in ids (the weighted
+    /// counterpart of `count_ties_with_ids`).
+    pub fn weigh_ties_with_ids(&self, node: &Node, ids: &RoaringBitmap) -> f32 {
+        node.neighbors_sets
+            .keys()
+            .map(|edge_type| {
+                self.get_weight(edge_type)
+                    * node.count_ties_with_ids_of_type(ids, *edge_type) as f32
+            })
+            .sum()
+    }
+}
@@ -33,6 +33,37 @@ pub enum CLQError {
     #[error("JSON error: {0}")]
     JSON(#[from] serde_json::Error),
 
+    #[error("Invalid search problem: {0}")]
+    InvalidSearchProblem(String),
+
+    #[error("Expected {expected} tab-separated fields ({schema}), found {found}")]
+    FieldCount {
+        expected: usize,
+        found: usize,
+        schema: String,
+    },
+
+    #[error("Invalid value for field {field} ('{value}'): {reason} (expected {schema})")]
+    FieldParse {
+        field: String,
+        value: String,
+        reason: String,
+        schema: String,
+    },
+
+    #[error("A single beam candidate takes about {needed} bytes, over the beam memory limit of {limit} bytes")]
+    BeamMemoryExceeded { needed: usize, limit: usize },
+
+    #[error("Line {line_num}: {error}")]
+    Line {
+        line_num: usize,
+        error: Box<CLQError>,
+    },
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
     #[error("Impossible error: {0}")]
     Infallible(#[from] std::convert::Infallible),
 }
@@ -0,0 +1,199 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashMap;
+use std::collections::VecDeque;
+
+/// Scratch space for algorithms run on each of many small graphs, as in the streaming
+/// transformers. Building the hash maps keyed by node id that the graph algorithms use
+/// allocates (and frees) several small buffers per node and per graph; an arena is loaded with
+/// one graph at a time instead, numbering its nodes densely and keeping their edges and any
+/// per-node values in flat vectors indexed by those numbers. reset (or loading the next graph)
+/// forgets the graph but keeps the memory, so that once an arena has seen a graph of a given
+/// size, graphs up to that size need no further allocations.
+#[derive(Default)]
+pub struct GraphArena {
+    ids: Vec<NodeId>,
+    indices: FxHashMap<NodeId, usize>,
+    // the neighbors of the node with index i are neighbors[offsets[i]..offsets[i + 1]].
+    offsets: Vec<usize>,
+    neighbors: Vec<usize>,
+    // per-node buffers, reused by each algorithm.
+    values: Vec<usize>,
+    bins: Vec<usize>,
+    positions: Vec<usize>,
+    order: Vec<usize>,
+    distances: Vec<Option<u32>>,
+    queue: VecDeque<usize>,
+}
+impl GraphArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// forgets the loaded graph, keeping the memory it used.
+    pub fn reset(&mut self) {
+        self.ids.clear();
+        self.indices.clear();
+        self.offsets.clear();
+        self.neighbors.clear();
+        self.values.clear();
+        self.bins.clear();
+        self.positions.clear();
+        self.order.clear();
+        self.distances.clear();
+        self.queue.clear();
+    }
+
+    /// replaces the loaded graph with graph, whose edges must go both ways (as they do in
+    /// undirected graphs).
+    pub fn load<G>(&mut self, graph: &G)
+    where
+        G: GraphBase,
+        G::NodeType: NodeBase<NodeIdType = NodeId>,
+        <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+    {
+        self.reset();
+        for id in graph.get_ids_iter() {
+            self.indices.insert(*id, self.ids.len());
+            self.ids.push(*id);
+        }
+        self.offsets.push(0);
+        for id in &self.ids {
+            for e in graph.get_node(*id).get_edges() {
+                self.neighbors.push(self.indices[&e.get_neighbor_id()]);
+            }
+            self.offsets.push(self.neighbors.len());
+        }
+    }
+
+    /// number of nodes of the loaded graph.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// ids of the nodes of the loaded graph, by index.
+    pub fn get_ids(&self) -> &[NodeId] {
+        &self.ids
+    }
+
+    pub fn get_index(&self, id: NodeId) -> Option<usize> {
+        self.indices.get(&id).cloned()
+    }
+
+    /// Computes the coreness of each node of the loaded graph, as
+    /// `Coreness::get_coreness_values` does (see https://arxiv.org/abs/cs/0310049): nodes are
+    /// kept in bins by their remaining degree, and processed in increasing order of it.
+    pub fn compute_coreness_values(&mut self) {
+        let n = self.ids.len();
+        // values are remaining degrees, and end up as corenesses.
+        self.values.clear();
+        self.values
+            .extend((0..n).map(|i| self.offsets[i + 1] - self.offsets[i]));
+        let max_degree = self.values.iter().cloned().max().unwrap_or(0);
+        // bins[d] is the position in order of the first node with remaining degree d.
+        self.bins.clear();
+        self.bins.resize(max_degree + 1, 0);
+        for degree in &self.values {
+            self.bins[*degree] += 1;
+        }
+        let mut start: usize = 0;
+        for bin in self.bins.iter_mut() {
+            let size = *bin;
+            *bin = start;
+            start += size;
+        }
+        self.positions.clear();
+        self.positions.resize(n, 0);
+        self.order.clear();
+        self.order.resize(n, 0);
+        for i in 0..n {
+            let degree = self.values[i];
+            self.positions[i] = self.bins[degree];
+            self.order[self.bins[degree]] = i;
+            self.bins[degree] += 1;
+        }
+        for degree in (1..=max_degree).rev() {
+            self.bins[degree] = self.bins[degree - 1];
+        }
+        if !self.bins.is_empty() {
+            self.bins[0] = 0;
+        }
+        for k in 0..n {
+            let i = self.order[k];
+            for j in self.offsets[i]..self.offsets[i + 1] {
+                let neighbor = self.neighbors[j];
+                let degree = self.values[neighbor];
+                if degree > self.values[i] {
+                    // moves the neighbor to the start of its bin, and the bin past it.
+                    let position = self.positions[neighbor];
+                    let first_position = self.bins[degree];
+                    let first = self.order[first_position];
+                    if neighbor != first {
+                        self.order.swap(position, first_position);
+                        self.positions[neighbor] = first_position;
+                        self.positions[first] = position;
+                    }
+                    self.bins[degree] += 1;
+                    self.values[neighbor] -= 1;
+                }
+            }
+        }
+    }
+
+    /// coreness of each node, by index, once computed by compute_coreness_values (and empty
+    /// before).
+    pub fn get_coreness_values(&self) -> &[usize] {
+        &self.values
+    }
+
+    pub fn get_coreness(&self, id: NodeId) -> Option<usize> {
+        self.values.get(self.get_index(id)?).cloned()
+    }
+
+    /// Computes the distance of each node of the loaded graph from source, by breadth-first
+    /// search. Returns an error if source isn't in the graph.
+    pub fn compute_distances(&mut self, source: NodeId) -> CLQResult<()> {
+        let source = self.get_index(source).ok_or_else(|| {
+            CLQError::from(format!("Node {} is not in the graph", source.value()))
+        })?;
+        self.distances.clear();
+        self.distances.resize(self.ids.len(), None);
+        self.distances[source] = Some(0);
+        self.queue.clear();
+        self.queue.push_back(source);
+        while let Some(i) = self.queue.pop_front() {
+            let distance = self.distances[i].map(|d| d + 1);
+            for k in self.offsets[i]..self.offsets[i + 1] {
+                let neighbor = self.neighbors[k];
+                if self.distances[neighbor].is_none() {
+                    self.distances[neighbor] = distance;
+                    self.queue.push_back(neighbor);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// distance of each node from the source of compute_distances, by index, or None for nodes
+    /// it can't reach (and empty before distances are computed).
+    pub fn get_distances(&self) -> &[Option<u32>] {
+        &self.distances
+    }
+
+    pub fn get_distance(&self, id: NodeId) -> Option<u32> {
+        *self.distances.get(self.get_index(id)?)?
+    }
+}
@@ -11,8 +11,8 @@ use fxhash::FxHashMap;
 use std::collections::hash_map::{Keys, Values};
 
 /// General-purpose trait which indicates the minimum amount of shared context
-/// required between all graph objects. Currently built to accommodate a graph
-/// with "core" and "non-core" ids. A GraphBase is built by a GraphBuilder.
+/// required between all graph objects. Graphs with "core" and "non-core" ids also
+/// implement `BipartiteGraphBase`. A GraphBase is built by a GraphBuilder.
 pub trait GraphBase
 where
     Self: Sized,
@@ -20,8 +20,6 @@ where
 {
     type NodeType;
 
-    fn get_core_ids(&self) -> &Vec<<Self::NodeType as NodeBase>::NodeIdType>;
-    fn get_non_core_ids(&self) -> Option<&Vec<<Self::NodeType as NodeBase>::NodeIdType>>;
     fn get_ids_iter(&self) -> Keys<<Self::NodeType as NodeBase>::NodeIdType, Self::NodeType>;
     fn get_nodes_iter(&self) -> Values<<Self::NodeType as NodeBase>::NodeIdType, Self::NodeType>;
     fn get_mut_nodes(
@@ -40,3 +38,13 @@ where
         node_ids
     }
 }
+
+/// A graph whose nodes are split into two shores, of "core" and "non-core" nodes, with
+/// edges only running between the two (as in a `TypedGraph`).
+pub trait BipartiteGraphBase: GraphBase
+where
+    Self::NodeType: NodeBase,
+{
+    fn get_core_ids(&self) -> &[<Self::NodeType as NodeBase>::NodeIdType];
+    fn get_non_core_ids(&self) -> &[<Self::NodeType as NodeBase>::NodeIdType];
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate serde_json;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use fxhash::FxHasher64;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// Hashes the lines of a graph, in order. Unlike `DefaultHasher`, hashes are the same
+/// across runs and builds, so they can be stored on disk.
+#[derive(Default)]
+pub struct RowsHasher {
+    hasher: FxHasher64,
+}
+impl RowsHasher {
+    pub fn add_line(&mut self, line: &str) {
+        let mut line_hasher = FxHasher64::default();
+        line_hasher.write(line.as_bytes());
+        self.hasher.write_u64(line_hasher.finish());
+    }
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+/// An optional on-disk cache of the output of a transformer for each graph, keyed by the
+/// graph_id (as written in the input) and a hash of the graph's rows, so that re-running
+/// a pipeline over mostly unchanged inputs only recomputes the graphs whose rows changed
+/// (see `TransformerBase::run_with_cache`). Output depends on the transformer's settings
+/// as well: the cache is opened with a config_key describing them, and entries written
+/// with a different one are discarded.
+///
+/// The cache file holds one JSON object per line: a header with the config_key, then one
+/// entry per graph with its output lines. Saving keeps the entries of the graphs of the
+/// current run only.
+pub struct GraphCache {
+    path: PathBuf,
+    config_key: String,
+    // output lines by (graph_id, hash of rows), as read from the cache file.
+    entries: HashMap<(String, u64), Vec<String>>,
+    // entries of the graphs of the current run, in order, to be saved.
+    used: Vec<((String, u64), Vec<String>)>,
+    pub num_hits: usize,
+    pub num_misses: usize,
+}
+impl GraphCache {
+    /// opens the cache stored at path, which starts out empty if there is no such file,
+    /// or if it was written with a different config_key. Returns an error if the file
+    /// can't be read or parsed.
+    pub fn open<P: AsRef<Path>>(path: P, config_key: &str) -> CLQResult<Self> {
+        let mut cache = Self {
+            path: path.as_ref().to_path_buf(),
+            config_key: config_key.to_owned(),
+            entries: HashMap::new(),
+            used: Vec::new(),
+            num_hits: 0,
+            num_misses: 0,
+        };
+        if !cache.path.exists() {
+            return Ok(cache);
+        }
+        let mut lines = BufReader::new(File::open(&cache.path)?).lines();
+        let header: Value = match lines.next() {
+            Some(line) => serde_json::from_str(&line?)?,
+            None => return Ok(cache),
+        };
+        if header["config"].as_str() != Some(config_key) {
+            return Ok(cache);
+        }
+        for line in lines {
+            let entry: Value = serde_json::from_str(&line?)?;
+            let malformed = || CLQError::from(format!("Malformed cache entry: {entry}"));
+            let graph_id = entry["graph_id"].as_str().ok_or_else(malformed)?;
+            let hash = entry["hash"]
+                .as_str()
+                .and_then(|x| u64::from_str_radix(x, 16).ok())
+                .ok_or_else(malformed)?;
+            let output_lines = entry["lines"]
+                .as_array()
+                .ok_or_else(malformed)?
+                .iter()
+                .map(|x| x.as_str().map(str::to_owned).ok_or_else(malformed))
+                .collect::<CLQResult<Vec<String>>>()?;
+            cache
+                .entries
+                .insert((graph_id.to_owned(), hash), output_lines);
+        }
+        Ok(cache)
+    }
+
+    /// returns the cached output lines of the graph with the given graph_id and rows hash,
+    /// keeping them for the next save.
+    pub fn get(&mut self, graph_id: &str, hash: u64) -> Option<Vec<String>> {
+        let key = (graph_id.to_owned(), hash);
+        let lines = self.entries.get(&key)?.clone();
+        self.num_hits += 1;
+        self.used.push((key, lines.clone()));
+        Some(lines)
+    }
+
+    /// records the output lines of a graph that was not in the cache.
+    pub fn insert(&mut self, graph_id: &str, hash: u64, lines: Vec<String>) {
+        self.num_misses += 1;
+        self.used.push(((graph_id.to_owned(), hash), lines));
+    }
+
+    /// writes the entries of the graphs of the current run to the cache file (through a
+    /// temporary file, so that an interrupted save leaves the previous cache intact).
+    pub fn save(&self) -> CLQResult<()> {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        writeln!(writer, "{}", json!({ "config": self.config_key }))?;
+        for ((graph_id, hash), lines) in &self.used {
+            let entry = json!({
+                "graph_id": graph_id,
+                "hash": format!("{hash:016x}"),
+                "lines": lines,
+            });
+            writeln!(writer, "{entry}")?;
+        }
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+//! Hasher used by maps and sets on hot paths (candidate neighborhoods, breadth-first
+//! searches, core decompositions...), keyed by small integers, for which the default
+//! SipHash of std is needlessly slow. FxHash by default; aHash with the "ahash" feature.
+//! Both are deterministic (unlike std's randomly keyed hasher), so iteration orders are the
+//! same across runs. Maps that are part of public signatures keep their current types.
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasherDefault;
+
+#[cfg(not(feature = "ahash"))]
+pub type FastHasher = BuildHasherDefault<fxhash::FxHasher>;
+#[cfg(feature = "ahash")]
+pub type FastHasher = BuildHasherDefault<ahash::AHasher>;
+
+pub type FastHashMap<K, V> = HashMap<K, V, FastHasher>;
+pub type FastHashSet<K> = HashSet<K, FastHasher>;
@@ -0,0 +1,162 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+
+use crate::dachshund::algorithms::degree_distribution::get_histogram;
+use crate::dachshund::bipartite_graph::BipartiteGraph;
+use crate::dachshund::id_types::{HyperedgeId, NodeId};
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use fxhash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Summary of how many hyperedges nodes belong to (their hyperdegree), and of how many
+/// nodes hyperedges have (their size).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HyperdegreeStats {
+    pub mean_hyperdegree: f64,
+    pub max_hyperdegree: usize,
+    pub mean_hyperedge_size: f64,
+    pub max_hyperedge_size: usize,
+    // number of nodes with each hyperdegree (see `get_histogram`).
+    pub hyperdegree_histogram: BTreeMap<usize, usize>,
+    // number of hyperedges of each size.
+    pub hyperedge_size_histogram: BTreeMap<usize, usize>,
+}
+
+/// Keeps track of a hypergraph, whose (hyper)edges can join any number of nodes, along
+/// with the hyperedges each node belongs to.
+pub struct Hypergraph {
+    // members of each hyperedge, in increasing order.
+    pub hyperedges: BTreeMap<HyperedgeId, Vec<NodeId>>,
+    // hyperedges each node belongs to, in increasing order.
+    pub memberships: BTreeMap<NodeId, Vec<HyperedgeId>>,
+}
+impl Hypergraph {
+    /// builds a hypergraph from (hyperedge id, node id) membership rows. Repeated rows are
+    /// ignored.
+    pub fn from_memberships(rows: Vec<(i64, i64)>) -> Self {
+        let mut hyperedges: BTreeMap<HyperedgeId, BTreeSet<NodeId>> = BTreeMap::new();
+        let mut memberships: BTreeMap<NodeId, BTreeSet<HyperedgeId>> = BTreeMap::new();
+        for (hyperedge_id, node_id) in rows {
+            let (hyperedge_id, node_id) = (HyperedgeId::from(hyperedge_id), NodeId::from(node_id));
+            hyperedges.entry(hyperedge_id).or_default().insert(node_id);
+            memberships.entry(node_id).or_default().insert(hyperedge_id);
+        }
+        Self {
+            hyperedges: hyperedges
+                .into_iter()
+                .map(|(id, members)| (id, members.into_iter().collect()))
+                .collect(),
+            memberships: memberships
+                .into_iter()
+                .map(|(id, hyperedges)| (id, hyperedges.into_iter().collect()))
+                .collect(),
+        }
+    }
+    pub fn count_nodes(&self) -> usize {
+        self.memberships.len()
+    }
+    pub fn count_hyperedges(&self) -> usize {
+        self.hyperedges.len()
+    }
+    pub fn get_hyperedge(&self, id: HyperedgeId) -> Option<&[NodeId]> {
+        self.hyperedges.get(&id).map(|members| members.as_slice())
+    }
+    pub fn get_node_hyperedges(&self, id: NodeId) -> Option<&[HyperedgeId]> {
+        self.memberships.get(&id).map(|x| x.as_slice())
+    }
+    /// number of hyperedges the node belongs to (0 if it is not in the hypergraph).
+    pub fn get_hyperdegree(&self, id: NodeId) -> usize {
+        self.memberships.get(&id).map_or(0, |x| x.len())
+    }
+    pub fn get_hyperdegree_stats(&self) -> HyperdegreeStats {
+        let hyperdegrees: Vec<usize> = self.memberships.values().map(|x| x.len()).collect();
+        let sizes: Vec<usize> = self.hyperedges.values().map(|x| x.len()).collect();
+        let mean = |values: &[usize]| {
+            if values.is_empty() {
+                0.0
+            } else {
+                values.iter().sum::<usize>() as f64 / values.len() as f64
+            }
+        };
+        HyperdegreeStats {
+            mean_hyperdegree: mean(&hyperdegrees),
+            max_hyperdegree: hyperdegrees.iter().copied().max().unwrap_or(0),
+            mean_hyperedge_size: mean(&sizes),
+            max_hyperedge_size: sizes.iter().copied().max().unwrap_or(0),
+            hyperdegree_histogram: get_histogram(&hyperdegrees),
+            hyperedge_size_histogram: get_histogram(&sizes),
+        }
+    }
+    /// Clique expansion: the graph on the same nodes, in which nodes are tied if they share
+    /// a hyperedge. Nodes only in hyperedges of their own are kept, without neighbors.
+    pub fn get_clique_expansion(&self) -> SimpleUndirectedGraph {
+        let mut neighbors: FxHashMap<NodeId, BTreeSet<NodeId>> = self
+            .memberships
+            .keys()
+            .map(|id| (*id, BTreeSet::new()))
+            .collect();
+        for members in self.hyperedges.values() {
+            for id in members {
+                let node_neighbors = neighbors.get_mut(id).unwrap();
+                node_neighbors.extend(members.iter().filter(|x| *x != id));
+            }
+        }
+        SimpleUndirectedGraph {
+            ids: self.memberships.keys().cloned().collect(),
+            nodes: neighbors
+                .into_iter()
+                .map(|(id, x)| (id, SimpleNode::new(id, x.into_iter().collect())))
+                .collect(),
+        }
+    }
+    /// Star expansion: the bipartite graph between nodes (on the left shore) and
+    /// hyperedges (on the right shore), in which each node is tied to the hyperedges it
+    /// belongs to. As ids must be unique across shores, hyperedges are given node ids
+    /// following the largest node id, in order: these are returned along with the graph.
+    pub fn get_star_expansion(&self) -> (BipartiteGraph, BTreeMap<NodeId, HyperedgeId>) {
+        let first_id = self
+            .memberships
+            .keys()
+            .next_back()
+            .map_or(0, |id| id.value() + 1);
+        let star_ids: BTreeMap<HyperedgeId, NodeId> = self
+            .hyperedges
+            .keys()
+            .enumerate()
+            .map(|(i, id)| (*id, NodeId::from(first_id + i as i64)))
+            .collect();
+        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+        for (id, hyperedges) in &self.memberships {
+            let neighbors: SimpleNeighbors = hyperedges.iter().map(|x| star_ids[x]).collect();
+            nodes.insert(*id, SimpleNode::new(*id, neighbors));
+        }
+        for (hyperedge_id, members) in &self.hyperedges {
+            let star_id = star_ids[hyperedge_id];
+            nodes.insert(
+                star_id,
+                SimpleNode::new(star_id, members.iter().copied().collect()),
+            );
+        }
+        let left_ids: Vec<NodeId> = self.memberships.keys().cloned().collect();
+        let right_ids: Vec<NodeId> = star_ids.values().cloned().collect();
+        let graph = BipartiteGraph {
+            ids: left_ids.iter().chain(right_ids.iter()).cloned().collect(),
+            nodes,
+            left_ids,
+            right_ids,
+        };
+        (
+            graph,
+            star_ids
+                .into_iter()
+                .map(|(id, star_id)| (star_id, id))
+                .collect(),
+        )
+    }
+}
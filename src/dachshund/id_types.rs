@@ -51,7 +51,7 @@ where
 }
 
 /// An opaque identifier for edge types. Not interpreted by dachshund logic in any way.
-#[derive(Hash, Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub struct EdgeTypeId {
     id: usize,
 }
@@ -93,6 +93,30 @@ impl fmt::Display for NodeId {
     }
 }
 
+/// Uniquely identifies a hyperedge of a `Hypergraph`.
+#[derive(Hash, Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
+pub struct HyperedgeId {
+    id: i64,
+}
+impl HyperedgeId {
+    pub fn value(&self) -> i64 {
+        self.id
+    }
+}
+impl<T> From<T> for HyperedgeId
+where
+    T: Into<i64>,
+{
+    fn from(n: T) -> Self {
+        Self { id: n.into() }
+    }
+}
+impl fmt::Display for HyperedgeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Hyperedge:{}", self.id)
+    }
+}
+
 /// Type alias used for cleanliness in places where the distinction between internal and extenral node id
 /// is essential.
 pub type NodeLabel = NodeId;
@@ -5,11 +5,13 @@
  * LICENSE file in the root directory of this source tree.
  */
 // see https://stackoverflow.com/questions/36088116/how-to-do-polymorphic-io-from-either-a-file-or-stdin-in-rust
+use crate::dachshund::line_format::LineFormat;
 use std::fs::File;
 use std::io::{self, BufRead, Read};
 use std::os::unix::io::FromRawFd;
 pub struct Input<'a> {
-    source: Box<dyn BufRead + 'a>,
+    source: Box<dyn BufRead + Send + 'a>,
+    line_format: LineFormat,
 }
 
 impl<'a> Input<'a> {
@@ -18,20 +20,34 @@ impl<'a> Input<'a> {
         let reader = io::BufReader::new(stdin);
         Input {
             source: Box::new(reader),
+            line_format: LineFormat::default(),
         }
     }
 
     pub fn file(path: &str) -> io::Result<Input<'a>> {
         File::open(path).map(|file| Input {
             source: Box::new(io::BufReader::new(file)),
+            line_format: LineFormat::default(),
         })
     }
 
     pub fn string(text: &'a [u8]) -> Input<'a> {
         Input {
             source: Box::new(text),
+            line_format: LineFormat::default(),
         }
     }
+
+    /// Reads lines in the given format rather than as tab-separated fields (see
+    /// `TransformerBase::get_input_line_processor`).
+    pub fn with_line_format(mut self, line_format: LineFormat) -> Input<'a> {
+        self.line_format = line_format;
+        self
+    }
+
+    pub fn get_line_format(&self) -> &LineFormat {
+        &self.line_format
+    }
 }
 
 impl<'a> Read for Input<'a> {
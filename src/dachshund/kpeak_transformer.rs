@@ -12,6 +12,7 @@ use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::node_labels::format_node_id;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_transformer::GraphStatsTransformerBase;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
@@ -33,6 +34,12 @@ impl KPeakTransformer {
             line_processor: Arc::new(LineProcessor::new()),
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`), and writes them back as such in the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
     fn compute_kpeaks_and_mountains(
         graph: &SimpleUndirectedGraph,
     ) -> (HashMap<NodeId, i32>, HashMap<usize, HashMap<NodeId, usize>>) {
@@ -71,13 +78,17 @@ impl TransformerBase for KPeakTransformer {
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
+        let labels = self
+            .line_processor
+            .get_node_labels()
+            .and_then(|x| x.get(graph_id));
         for (mountain_id, m_nodes) in mountain_assignments {
             for (node_id, coreness) in m_nodes {
                 let peak_number = *peaks.get(&node_id).unwrap();
                 let line: String = format!(
                     "{}\t{}\t{}\t{}\t{}",
                     original_id,
-                    node_id.value(),
+                    format_node_id(node_id, labels.as_deref()),
                     coreness,
                     peak_number,
                     mountain_id
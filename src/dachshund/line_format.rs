@@ -0,0 +1,251 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::GraphId;
+use crate::dachshund::line_processor::{FieldValue, LineProcessorBase, LineSchema};
+use crate::dachshund::row::Row;
+use std::sync::Arc;
+
+/// How the fields of a line of input are separated. By default they are tab-separated,
+/// which is what line processors expect: input in any other format is converted to it
+/// by a `DelimitedLineProcessor` before being parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineFormat {
+    pub delimiter: char,
+    // whether fields may be enclosed in double quotes, so as to contain the delimiter (a
+    // double quote within such a field being written twice).
+    pub quoted: bool,
+    // whether the first line of input is a header naming the columns, which are then
+    // matched to the fields expected by the line processor by name, whatever their order.
+    pub header: bool,
+}
+impl Default for LineFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: '\t',
+            quoted: false,
+            header: false,
+        }
+    }
+}
+impl LineFormat {
+    pub fn new(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            ..Self::default()
+        }
+    }
+    pub fn with_quoted_fields(mut self) -> Self {
+        self.quoted = true;
+        self
+    }
+    pub fn with_header(mut self) -> Self {
+        self.header = true;
+        self
+    }
+    /// whether lines are read as they are, without any conversion.
+    pub fn is_tab_separated(&self) -> bool {
+        *self == Self::default()
+    }
+    /// parses a delimiter given by name (tab, comma or space) or as a single character.
+    pub fn parse_delimiter(name: &str) -> CLQResult<char> {
+        let mut chars = name.chars();
+        match (name, chars.next(), chars.next()) {
+            ("tab", _, _) => Ok('\t'),
+            ("comma", _, _) => Ok(','),
+            ("space", _, _) => Ok(' '),
+            (_, Some(c), None) if c != '"' && c != '\n' => Ok(c),
+            _ => Err(CLQError::from(format!(
+                "Invalid delimiter: '{}' (expected tab, comma, space or a single character)",
+                name
+            ))),
+        }
+    }
+    /// splits line into its fields, unquoting them if fields may be quoted.
+    pub fn split(&self, line: &str) -> CLQResult<Vec<String>> {
+        if !self.quoted {
+            return Ok(line.split(self.delimiter).map(str::to_owned).collect());
+        }
+        let mut fields: Vec<String> = Vec::new();
+        let mut field = String::new();
+        let mut chars = line.chars().peekable();
+        // whether we're in between the quotes of a quoted field.
+        let mut in_quotes = false;
+        // whether the current field was quoted, in which case it must end at its closing
+        // quote.
+        let mut was_quoted = false;
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    _ => field.push(c),
+                }
+            } else if c == self.delimiter {
+                fields.push(std::mem::take(&mut field));
+                was_quoted = false;
+            } else if was_quoted {
+                return Err(CLQError::from(format!(
+                    "Unexpected character after closing quote of field {}",
+                    fields.len() + 1
+                )));
+            } else if c == '"' && field.is_empty() {
+                in_quotes = true;
+                was_quoted = true;
+            } else {
+                field.push(c);
+            }
+        }
+        if in_quotes {
+            return Err(CLQError::from(format!(
+                "Unterminated quoted field {}",
+                fields.len() + 1
+            )));
+        }
+        fields.push(field);
+        Ok(fields)
+    }
+    /// for each of fields, the index of the column of header bearing its name.
+    pub fn get_columns(&self, header: &str, fields: &[&str]) -> CLQResult<Vec<usize>> {
+        let columns: Vec<String> = self
+            .split(header)?
+            .into_iter()
+            .map(|x| x.trim().to_owned())
+            .collect();
+        fields
+            .iter()
+            .map(|field| {
+                columns.iter().position(|x| x == field).ok_or_else(|| {
+                    CLQError::from(format!(
+                        "Header has no column named {} (columns: {})",
+                        field,
+                        columns.join(", ")
+                    ))
+                })
+            })
+            .collect()
+    }
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        let delimiter = match matches.value_of("delimiter") {
+            Some(name) => Self::parse_delimiter(name)?,
+            None => '\t',
+        };
+        let mut format = Self::new(delimiter);
+        if matches.is_present("quoted_fields") {
+            format = format.with_quoted_fields();
+        }
+        if matches.is_present("header") {
+            format = format.with_header();
+        }
+        Ok(format)
+    }
+}
+
+/// Command line arguments read by `LineFormat::from_argmatches`, shared by all binaries.
+pub fn get_line_format_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("delimiter")
+            .long("delimiter")
+            .takes_value(true)
+            .default_value("tab")
+            .help(
+                "Delimiter between the fields of input lines: tab, comma, space, or any \
+                   other single character.",
+            ),
+        Arg::with_name("quoted_fields").long("quoted_fields").help(
+            "Allow fields of input lines to be enclosed in double quotes, so that they \
+                   can contain the delimiter (with double quotes within them doubled).",
+        ),
+        Arg::with_name("header").long("header").help(
+            "Read the first line of input as a header naming its columns, which are \
+                   then matched to the expected fields by name, in any order. Columns that \
+                   aren't expected are ignored.",
+        ),
+    ]
+}
+
+/// Converts lines in a given format (see `LineFormat`) into tab-separated ones, in the
+/// order of the fields expected by the line processor it wraps, which then parses them.
+pub struct DelimitedLineProcessor {
+    format: LineFormat,
+    // for each field expected by line_processor, the column it is read from. None keeps
+    // columns in the order they come in.
+    columns: Option<Vec<usize>>,
+    line_processor: Arc<dyn LineProcessorBase>,
+}
+impl DelimitedLineProcessor {
+    pub fn new(format: LineFormat, line_processor: Arc<dyn LineProcessorBase>) -> Self {
+        Self {
+            format,
+            columns: None,
+            line_processor,
+        }
+    }
+    /// reads columns as named in header. Only possible if the wrapped line processor
+    /// declares the fields it expects (see `LineProcessorBase::get_schema`): otherwise the
+    /// header is ignored, and columns are read in order.
+    pub fn with_header(mut self, header: &str) -> CLQResult<Self> {
+        if let Some(schema) = self.line_processor.get_schema() {
+            self.columns = Some(self.format.get_columns(header, schema.fields)?);
+        }
+        Ok(self)
+    }
+    /// line as a tab-separated line, in the order of the fields expected.
+    pub fn to_tab_separated(&self, line: &str) -> CLQResult<String> {
+        let fields: Vec<String> = self.format.split(line)?;
+        if fields.iter().any(|x| x.contains('\t')) {
+            return Err(CLQError::from("Fields may not contain tabs"));
+        }
+        match &self.columns {
+            None => Ok(fields.join("\t")),
+            Some(columns) => columns
+                .iter()
+                .map(|ix| {
+                    fields.get(*ix).map(String::as_str).ok_or_else(|| {
+                        CLQError::from(format!(
+                            "Line has {} columns, fewer than its header",
+                            fields.len()
+                        ))
+                    })
+                })
+                .collect::<CLQResult<Vec<&str>>>()
+                .map(|x| x.join("\t")),
+        }
+    }
+}
+impl LineProcessorBase for DelimitedLineProcessor {
+    fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
+        self.line_processor
+            .process_line(self.to_tab_separated(&line)?)
+    }
+    fn is_skipped(&self, line: &str) -> bool {
+        self.line_processor.is_skipped(line)
+    }
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        self.line_processor.get_schema()
+    }
+    fn get_graph_key(&self, line: &str) -> String {
+        match self.to_tab_separated(line) {
+            Ok(line) => self.line_processor.get_graph_key(&line),
+            Err(_) => String::new(),
+        }
+    }
+    // values aren't delimited, whatever the format of lines.
+    fn process_values(&self, values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
+        self.line_processor.process_values(values)
+    }
+    fn release_graph(&self, graph_id: GraphId) {
+        self.line_processor.release_graph(graph_id)
+    }
+}
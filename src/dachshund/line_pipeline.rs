@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate crossbeam;
+
+use crate::dachshund::error::CLQError;
+use crate::dachshund::input::Input;
+use crate::dachshund::line_processor::LineProcessorBase;
+use crate::dachshund::row::Row;
+use crossbeam::channel::{bounded, Receiver};
+use crossbeam::thread::Scope;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::sync::Arc;
+
+/// Number of lines read before being handed over to a parsing thread at once.
+pub const PIPELINE_CHUNK_SIZE: usize = 1024;
+
+/// A line of input, as read and parsed ahead of `TransformerBase::process_row`.
+pub enum ParsedLine {
+    // blank or comment line, as per `LineProcessorBase::is_skipped`.
+    Skipped,
+    // the row of the line, along with the line itself if it was kept.
+    Row(Box<dyn Row>, Option<String>),
+    // the reason the line could not be parsed, along with the line itself if it was kept.
+    Invalid(CLQError, Option<String>),
+    // the line could not be read.
+    Unreadable(io::Error),
+}
+
+/// Parses a line read from input, keeping the line itself along with the result if
+/// keep_line is set (e.g. to report or hash it).
+pub fn parse_line(
+    line_processor: &dyn LineProcessorBase,
+    line: io::Result<String>,
+    keep_line: bool,
+) -> ParsedLine {
+    match line {
+        Ok(line) if line_processor.is_skipped(&line) => ParsedLine::Skipped,
+        Ok(line) => {
+            let raw = if keep_line { Some(line.clone()) } else { None };
+            match line_processor.process_line(line) {
+                Ok(row) => ParsedLine::Row(row, raw),
+                Err(error) => ParsedLine::Invalid(error, raw),
+            }
+        }
+        Err(error) => ParsedLine::Unreadable(error),
+    }
+}
+
+/// Lines of an input, read by one thread and parsed by a pool of others, so that the
+/// thread running the transformer only has to process rows. Lines are read in chunks of
+/// PIPELINE_CHUNK_SIZE, which are parsed concurrently and put back in order here. Reading
+/// stays at most a few chunks per parsing thread ahead of processing. Dropping this stops
+/// the reading and parsing threads (once their current chunk is done).
+///
+/// Line processors which number graph_ids as they see them (e.g. `LineProcessor`) may
+/// number them in a different order than when lines are parsed one at a time, but still
+/// give every line of a graph the same one.
+pub struct ParsedLines {
+    receiver: Receiver<(usize, Vec<ParsedLine>)>,
+    // chunks parsed ahead of the one being waited for, by sequence number.
+    pending: BTreeMap<usize, Vec<ParsedLine>>,
+    next_chunk: usize,
+    current: std::vec::IntoIter<ParsedLine>,
+}
+impl ParsedLines {
+    /// Spawns, within scope, a thread reading input and num_threads (at least 1) threads
+    /// parsing its lines with line_processor (see `parse_line`).
+    pub fn spawn<'env>(
+        scope: &Scope<'env>,
+        input: Input<'env>,
+        line_processor: Arc<dyn LineProcessorBase>,
+        num_threads: usize,
+        keep_lines: bool,
+    ) -> Self {
+        let num_threads = num_threads.max(1);
+        let (chunk_sender, chunk_receiver) =
+            bounded::<(usize, Vec<io::Result<String>>)>(2 * num_threads);
+        let (parsed_sender, parsed_receiver) = bounded(2 * num_threads);
+        scope.spawn(move |_| {
+            let mut lines = input.lines();
+            for seq in 0.. {
+                let chunk: Vec<io::Result<String>> =
+                    lines.by_ref().take(PIPELINE_CHUNK_SIZE).collect();
+                // stops at the end of input, or once lines are no longer wanted.
+                if chunk.is_empty() || chunk_sender.send((seq, chunk)).is_err() {
+                    return;
+                }
+            }
+        });
+        for _ in 0..num_threads {
+            let chunk_receiver = chunk_receiver.clone();
+            let parsed_sender = parsed_sender.clone();
+            let line_processor = line_processor.clone();
+            scope.spawn(move |_| {
+                for (seq, chunk) in chunk_receiver {
+                    let parsed: Vec<ParsedLine> = chunk
+                        .into_iter()
+                        .map(|line| parse_line(line_processor.as_ref(), line, keep_lines))
+                        .collect();
+                    if parsed_sender.send((seq, parsed)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        Self {
+            receiver: parsed_receiver,
+            pending: BTreeMap::new(),
+            next_chunk: 0,
+            current: Vec::new().into_iter(),
+        }
+    }
+}
+impl Iterator for ParsedLines {
+    type Item = ParsedLine;
+
+    fn next(&mut self) -> Option<ParsedLine> {
+        loop {
+            if let Some(line) = self.current.next() {
+                return Some(line);
+            }
+            let chunk = match self.pending.remove(&self.next_chunk) {
+                Some(chunk) => chunk,
+                None => match self.receiver.recv() {
+                    Ok((seq, chunk)) if seq == self.next_chunk => chunk,
+                    Ok((seq, chunk)) => {
+                        self.pending.insert(seq, chunk);
+                        continue;
+                    }
+                    // all chunks have been parsed.
+                    Err(_) => return None,
+                },
+            };
+            self.next_chunk += 1;
+            self.current = chunk.into_iter();
+        }
+    }
+}
@@ -7,14 +7,259 @@
 extern crate clap;
 extern crate serde_json;
 
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::attributed_graph::parse_attributes;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::{GraphId, NodeId};
-use crate::dachshund::row::{Row, SimpleEdgeRow, WeightedEdgeRow};
-use std::collections::HashMap;
+use crate::dachshund::node_labels::{GraphNodeLabels, NodeLabels};
+use crate::dachshund::row::{AttributedRow, Row, SimpleEdgeRow, WeightedEdgeRow};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
-pub trait LineProcessorBase {
+// line processors may be shared by several threads parsing lines (see `ParsedLines`).
+pub trait LineProcessorBase: Send + Sync {
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>>;
+    // whether a line carries no row at all (e.g. a comment), and should be skipped
+    // rather than parsed.
+    fn is_skipped(&self, _line: &str) -> bool {
+        false
+    }
+    // fields expected in a line, if all lines have the same ones (e.g. so that they can
+    // be matched to the columns of a header, see `DelimitedLineProcessor`).
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        None
+    }
+    // graph_id of a line as written in it (its first field), e.g. to key graph caches.
+    fn get_graph_key(&self, line: &str) -> String {
+        line.split('\t').next().unwrap_or("").to_owned()
+    }
+    // builds the row of a record read from columnar input (e.g. `ParquetInput`), given
+    // the values of the fields of get_schema in order, without writing them as a line.
+    fn process_values(&self, _values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
+        Err(CLQError::from(
+            "This line processor cannot read rows other than lines",
+        ))
+    }
+    // drops any state kept for a graph once it has been processed (e.g. the table of its
+    // string node labels, see `GraphNodeLabels`).
+    fn release_graph(&self, _graph_id: GraphId) {}
+}
+
+/// Value of a field of a row, as written in a line (`Str`) or as read from a typed column
+/// of columnar input, so that values needn't be written out and parsed back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldValue<'a> {
+    Int(i64),
+    Float(f64),
+    Str(&'a str),
+    Null,
+}
+impl<'a> FieldValue<'a> {
+    /// values of the fields of a line split by `LineSchema::split`.
+    pub fn from_fields(fields: &[&'a str]) -> Vec<Self> {
+        fields.iter().map(|x| FieldValue::Str(x)).collect()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self, FieldValue::Null | FieldValue::Str(""))
+    }
+}
+// values are displayed as they would be written in a line, nulls as empty fields.
+impl fmt::Display for FieldValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Int(x) => write!(f, "{}", x),
+            FieldValue::Float(x) => write!(f, "{}", x),
+            FieldValue::Str(x) => write!(f, "{}", x),
+            FieldValue::Null => Ok(()),
+        }
+    }
+}
+
+/// Names of the tab-separated fields expected in a line of input, used to split lines
+/// and to report which field of a line could not be parsed.
+pub struct LineSchema {
+    pub fields: &'static [&'static str],
+}
+impl LineSchema {
+    /// splits line into its fields, checking that there are as many as expected.
+    pub fn split<'a>(&self, line: &'a str) -> CLQResult<Vec<&'a str>> {
+        let values: Vec<&str> = line.split('\t').collect();
+        if values.len() != self.fields.len() {
+            return Err(CLQError::FieldCount {
+                expected: self.fields.len(),
+                found: values.len(),
+                schema: self.to_string(),
+            });
+        }
+        Ok(values)
+    }
+    /// parses the ix-th field of a line split by split.
+    pub fn parse<T>(&self, values: &[&str], ix: usize) -> CLQResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        values[ix]
+            .parse::<T>()
+            .map_err(|err| self.field_error(values, ix, err.to_string()))
+    }
+    /// parses the ix-th field of a line as a node id: an integer, or any string but an
+    /// empty one if labels are given, in which it is interned.
+    pub fn parse_node_id(
+        &self,
+        values: &[&str],
+        ix: usize,
+        labels: Option<&NodeLabels>,
+    ) -> CLQResult<NodeId> {
+        match labels {
+            Some(_) if values[ix].trim_end().is_empty() => {
+                Err(self.field_error(values, ix, "missing node id".to_owned()))
+            }
+            Some(labels) => Ok(labels.intern(values[ix].trim_end())),
+            None => Ok(self.parse::<i64>(values, ix)?.into()),
+        }
+    }
+    /// error reporting that the ix-th field of a line is invalid, for the given reason.
+    pub fn field_error(&self, values: &[&str], ix: usize, reason: String) -> CLQError {
+        CLQError::FieldParse {
+            field: self.fields[ix].to_owned(),
+            value: values[ix].to_owned(),
+            reason,
+            schema: self.to_string(),
+        }
+    }
+    /// the ix-th of values as an integer, parsing it if it is a string.
+    pub fn get_i64(&self, values: &[FieldValue], ix: usize) -> CLQResult<i64> {
+        match values[ix] {
+            FieldValue::Int(x) => Ok(x),
+            FieldValue::Str(x) => x
+                .parse::<i64>()
+                .map_err(|err| self.value_error(values, ix, err.to_string())),
+            _ => Err(self.value_error(values, ix, "not an integer".to_owned())),
+        }
+    }
+    /// the ix-th of values as a float, parsing it if it is a string.
+    pub fn get_f64(&self, values: &[FieldValue], ix: usize) -> CLQResult<f64> {
+        match values[ix] {
+            FieldValue::Float(x) => Ok(x),
+            FieldValue::Int(x) => Ok(x as f64),
+            FieldValue::Str(x) => x
+                .parse::<f64>()
+                .map_err(|err| self.value_error(values, ix, err.to_string())),
+            FieldValue::Null => Err(self.value_error(values, ix, "not a number".to_owned())),
+        }
+    }
+    /// the ix-th of values as a string, without trailing whitespace (nulls being empty).
+    pub fn get_str<'a>(&self, values: &[FieldValue<'a>], ix: usize) -> CLQResult<&'a str> {
+        match values[ix] {
+            FieldValue::Str(x) => Ok(x.trim_end()),
+            FieldValue::Null => Ok(""),
+            _ => Err(self.value_error(values, ix, "not a string".to_owned())),
+        }
+    }
+    /// the ix-th of values as a node id: an integer, or any value but a null or empty one
+    /// if labels are given, in which it is interned (as it would be written in a line).
+    pub fn get_node_id(
+        &self,
+        values: &[FieldValue],
+        ix: usize,
+        labels: Option<&NodeLabels>,
+    ) -> CLQResult<NodeId> {
+        match (labels, values[ix]) {
+            (Some(_), FieldValue::Null) => {
+                Err(self.value_error(values, ix, "missing node id".to_owned()))
+            }
+            (Some(_), FieldValue::Str(x)) if x.trim_end().is_empty() => {
+                Err(self.value_error(values, ix, "missing node id".to_owned()))
+            }
+            (Some(labels), FieldValue::Str(x)) => Ok(labels.intern(x.trim_end())),
+            (Some(labels), value) => Ok(labels.intern(&value.to_string())),
+            (None, _) => Ok(self.get_i64(values, ix)?.into()),
+        }
+    }
+    /// error reporting that the ix-th of values is invalid, for the given reason.
+    pub fn value_error(&self, values: &[FieldValue], ix: usize, reason: String) -> CLQError {
+        CLQError::FieldParse {
+            field: self.fields[ix].to_owned(),
+            value: values[ix].to_string(),
+            reason,
+            schema: self.to_string(),
+        }
+    }
+}
+impl fmt::Display for LineSchema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.fields.join("\\t"))
+    }
+}
+
+pub const SIMPLE_EDGE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "source_id", "target_id"],
+};
+pub const WEIGHTED_EDGE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "source_id", "target_id", "weight"],
+};
+// attributes are JSON objects (see `parse_attributes`).
+pub const ATTRIBUTED_NODE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "node_id", "attributes"],
+};
+pub const ATTRIBUTED_EDGE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "source_id", "target_id", "attributes"],
+};
+
+/// Dispatches lines to one of several line processors, according to their number of
+/// tab-separated fields, so that new kinds of rows can be read alongside existing ones
+/// without changing the `Row` trait or the existing processors.
+#[derive(Default)]
+pub struct LineProcessorRegistry {
+    processors: BTreeMap<usize, Arc<dyn LineProcessorBase>>,
+}
+impl LineProcessorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// registers processor for lines with as many fields as schema.
+    pub fn register(
+        &mut self,
+        schema: &LineSchema,
+        processor: Arc<dyn LineProcessorBase>,
+    ) -> CLQResult<()> {
+        let num_fields = schema.fields.len();
+        if self.processors.contains_key(&num_fields) {
+            return Err(CLQError::from(format!(
+                "A line processor is already registered for lines with {} fields",
+                num_fields
+            )));
+        }
+        self.processors.insert(num_fields, processor);
+        Ok(())
+    }
+}
+impl LineProcessorBase for LineProcessorRegistry {
+    fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
+        let num_fields = line.split('\t').count();
+        match self.processors.get(&num_fields) {
+            Some(processor) => processor.process_line(line),
+            None => Err(CLQError::from(format!(
+                "No line processor registered for lines with {} fields (registered: {})",
+                num_fields,
+                self.processors
+                    .keys()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))),
+        }
+    }
+    fn is_skipped(&self, line: &str) -> bool {
+        self.processors.values().any(|x| x.is_skipped(line))
+    }
+    fn release_graph(&self, graph_id: GraphId) {
+        for processor in self.processors.values() {
+            processor.release_graph(graph_id);
+        }
+    }
 }
 
 /// deals with processing lines and turning them into rows.
@@ -23,29 +268,57 @@ pub trait LineProcessorBase {
 pub struct LineProcessor {
     ids: Arc<RwLock<HashMap<String, i64>>>,
     reverse_ids: Arc<RwLock<Vec<String>>>,
+    node_labels: Option<Arc<GraphNodeLabels>>,
 }
 impl LineProcessorBase for LineProcessor {
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        Some(&SIMPLE_EDGE_SCHEMA)
+    }
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
-        let vec: Vec<&str> = line.split('\t').collect();
-        assert!(vec.len() == 3);
-        let key = vec[0].to_string();
+        let vec: Vec<&str> = SIMPLE_EDGE_SCHEMA.split(&line)?;
+        self.process_values(&FieldValue::from_fields(&vec))
+    }
+    fn process_values(&self, values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
+        let schema = &SIMPLE_EDGE_SCHEMA;
+        let key = values[0].to_string();
         let graph_id = self.record_new_key_or_return_current_one(key);
-        let source_id: NodeId = vec[1].parse::<i64>()?.into();
-        let target_id: NodeId = vec[2].parse::<i64>()?.into();
+        let labels = self.get_graph_labels(graph_id);
+        let source_id: NodeId = schema.get_node_id(values, 1, labels.as_deref())?;
+        let target_id: NodeId = schema.get_node_id(values, 2, labels.as_deref())?;
         Ok(Box::new(SimpleEdgeRow {
             graph_id,
             source_id,
             target_id,
         }))
     }
+    fn release_graph(&self, graph_id: GraphId) {
+        if let Some(node_labels) = &self.node_labels {
+            node_labels.release(graph_id);
+        }
+    }
 }
 impl LineProcessor {
     pub fn new() -> Self {
         Self {
             ids: Arc::new(RwLock::new(HashMap::new())),
             reverse_ids: Arc::new(RwLock::new(Vec::new())),
+            node_labels: None,
         }
     }
+    /// reads node ids as string labels, interned in a table per graph (see
+    /// `get_node_labels`), rather than as integers.
+    pub fn with_string_labels(mut self) -> Self {
+        self.node_labels = Some(Arc::new(GraphNodeLabels::new()));
+        self
+    }
+    /// tables of the labels of the graphs in flight, if node ids are read as string labels.
+    pub fn get_node_labels(&self) -> Option<Arc<GraphNodeLabels>> {
+        self.node_labels.clone()
+    }
+    /// table of the labels of graph_id, if node ids are read as string labels.
+    pub fn get_graph_labels(&self, graph_id: GraphId) -> Option<Arc<NodeLabels>> {
+        self.node_labels.as_ref().map(|x| x.get_or_insert(graph_id))
+    }
     fn record_new_key_or_return_current_one(&self, key: String) -> GraphId {
         let mut ids = self.ids.write().unwrap();
         let mut reverse_ids = self.reverse_ids.write().unwrap();
@@ -70,16 +343,24 @@ impl Default for LineProcessor {
 pub struct WeightedLineProcessor {
     ids: Arc<RwLock<HashMap<String, i64>>>,
     reverse_ids: Arc<RwLock<Vec<String>>>,
+    node_labels: Option<Arc<GraphNodeLabels>>,
 }
 impl LineProcessorBase for WeightedLineProcessor {
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        Some(&WEIGHTED_EDGE_SCHEMA)
+    }
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
-        let vec: Vec<&str> = line.split('\t').collect();
-        assert!(vec.len() == 4);
-        let key = vec[0].to_string();
+        let vec: Vec<&str> = WEIGHTED_EDGE_SCHEMA.split(&line)?;
+        self.process_values(&FieldValue::from_fields(&vec))
+    }
+    fn process_values(&self, values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
+        let schema = &WEIGHTED_EDGE_SCHEMA;
+        let key = values[0].to_string();
         let graph_id = self.record_new_key_or_return_current_one(key);
-        let source_id: NodeId = vec[1].parse::<i64>()?.into();
-        let target_id: NodeId = vec[2].parse::<i64>()?.into();
-        let weight: f64 = vec[3].parse::<f64>()?;
+        let labels = self.get_graph_labels(graph_id);
+        let source_id: NodeId = schema.get_node_id(values, 1, labels.as_deref())?;
+        let target_id: NodeId = schema.get_node_id(values, 2, labels.as_deref())?;
+        let weight: f64 = schema.get_f64(values, 3)?;
         Ok(Box::new(WeightedEdgeRow {
             graph_id,
             source_id,
@@ -87,14 +368,34 @@ impl LineProcessorBase for WeightedLineProcessor {
             weight,
         }))
     }
+    fn release_graph(&self, graph_id: GraphId) {
+        if let Some(node_labels) = &self.node_labels {
+            node_labels.release(graph_id);
+        }
+    }
 }
 impl WeightedLineProcessor {
     pub fn new() -> Self {
         Self {
             ids: Arc::new(RwLock::new(HashMap::new())),
             reverse_ids: Arc::new(RwLock::new(Vec::new())),
+            node_labels: None,
         }
     }
+    /// reads node ids as string labels, interned in a table per graph (see
+    /// `get_node_labels`), rather than as integers.
+    pub fn with_string_labels(mut self) -> Self {
+        self.node_labels = Some(Arc::new(GraphNodeLabels::new()));
+        self
+    }
+    /// tables of the labels of the graphs in flight, if node ids are read as string labels.
+    pub fn get_node_labels(&self) -> Option<Arc<GraphNodeLabels>> {
+        self.node_labels.clone()
+    }
+    /// table of the labels of graph_id, if node ids are read as string labels.
+    pub fn get_graph_labels(&self, graph_id: GraphId) -> Option<Arc<NodeLabels>> {
+        self.node_labels.as_ref().map(|x| x.get_or_insert(graph_id))
+    }
     fn record_new_key_or_return_current_one(&self, key: String) -> GraphId {
         let mut ids = self.ids.write().unwrap();
         let mut reverse_ids = self.reverse_ids.write().unwrap();
@@ -115,3 +416,92 @@ impl Default for WeightedLineProcessor {
         WeightedLineProcessor::new()
     }
 }
+
+/// Processes lines of attributed graphs, which are either node rows, of the form
+/// graph_id\tnode_id\tattributes, or edge rows, of the form
+/// graph_id\tsource_id\ttarget_id\tattributes, attributes being a JSON object (or empty).
+/// Can mutate ids and reverse_ids maps that keep track of graph_ids seen so far.
+pub struct AttributedLineProcessor {
+    ids: Arc<RwLock<HashMap<String, i64>>>,
+    reverse_ids: Arc<RwLock<Vec<String>>>,
+    node_labels: Option<Arc<GraphNodeLabels>>,
+}
+impl LineProcessorBase for AttributedLineProcessor {
+    /// Blank lines and lines starting with '#' (comments) are skipped.
+    fn is_skipped(&self, line: &str) -> bool {
+        line.trim().is_empty() || line.starts_with('#')
+    }
+    fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
+        let num_fields = line.split('\t').count();
+        let schema = if num_fields == ATTRIBUTED_NODE_SCHEMA.fields.len() {
+            &ATTRIBUTED_NODE_SCHEMA
+        } else {
+            &ATTRIBUTED_EDGE_SCHEMA
+        };
+        let vec: Vec<&str> = schema.split(&line)?;
+        let key = vec[0].to_string();
+        let graph_id = self.record_new_key_or_return_current_one(key);
+        let labels = self.get_graph_labels(graph_id);
+        let source_id: NodeId = schema.parse_node_id(&vec, 1, labels.as_deref())?;
+        let target_id: Option<NodeId> = match vec.len() {
+            4 => Some(schema.parse_node_id(&vec, 2, labels.as_deref())?),
+            _ => None,
+        };
+        let attributes_ix = vec.len() - 1;
+        let attributes = parse_attributes(vec[attributes_ix])
+            .map_err(|err| schema.field_error(&vec, attributes_ix, err.to_string()))?;
+        Ok(Box::new(AttributedRow {
+            graph_id,
+            source_id,
+            target_id,
+            attributes,
+        }))
+    }
+    fn release_graph(&self, graph_id: GraphId) {
+        if let Some(node_labels) = &self.node_labels {
+            node_labels.release(graph_id);
+        }
+    }
+}
+impl AttributedLineProcessor {
+    pub fn new() -> Self {
+        Self {
+            ids: Arc::new(RwLock::new(HashMap::new())),
+            reverse_ids: Arc::new(RwLock::new(Vec::new())),
+            node_labels: None,
+        }
+    }
+    /// reads node ids as string labels, interned in a table per graph (see
+    /// `get_node_labels`), rather than as integers.
+    pub fn with_string_labels(mut self) -> Self {
+        self.node_labels = Some(Arc::new(GraphNodeLabels::new()));
+        self
+    }
+    /// tables of the labels of the graphs in flight, if node ids are read as string labels.
+    pub fn get_node_labels(&self) -> Option<Arc<GraphNodeLabels>> {
+        self.node_labels.clone()
+    }
+    /// table of the labels of graph_id, if node ids are read as string labels.
+    pub fn get_graph_labels(&self, graph_id: GraphId) -> Option<Arc<NodeLabels>> {
+        self.node_labels.as_ref().map(|x| x.get_or_insert(graph_id))
+    }
+    fn record_new_key_or_return_current_one(&self, key: String) -> GraphId {
+        let mut ids = self.ids.write().unwrap();
+        let mut reverse_ids = self.reverse_ids.write().unwrap();
+        let num_items: usize = ids.len();
+        if !ids.contains_key(&key) {
+            ids.insert(key.clone(), num_items as i64);
+            reverse_ids.push(key.clone());
+        }
+        let id = ids.get(&key).unwrap();
+        GraphId::from(*id)
+    }
+    pub fn get_original_id(&self, local_id: usize) -> String {
+        self.reverse_ids.read().unwrap()[local_id].clone()
+    }
+}
+impl Default for AttributedLineProcessor {
+    fn default() -> Self {
+        AttributedLineProcessor::new()
+    }
+}
@@ -5,20 +5,40 @@
  * LICENSE file in the root directory of this source tree.
  */
 pub mod algorithms;
+pub mod any_graph;
+pub mod attributed_graph;
+pub mod attributed_graph_builder;
+pub mod batch_transformer;
 pub mod beam;
+pub mod bipartite_graph;
+pub mod bipartite_graph_builder;
 pub mod candidate;
 pub mod connected_components_transformer;
 pub mod core_transformer;
+pub mod csr_graph;
+pub mod edge_type_weights;
 pub mod error;
+pub mod graph_arena;
 pub mod graph_base;
 pub mod graph_builder_base;
+pub mod graph_cache;
+pub mod hashing;
+pub mod hypergraph;
 pub mod id_types;
 pub mod input;
 pub mod kpeak_transformer;
+pub mod line_format;
+pub mod line_pipeline;
 pub mod line_processor;
 pub mod node;
+pub mod node_labels;
 pub mod non_core_type_ids;
+pub mod ordered_output;
 pub mod output;
+#[cfg(feature = "parquet")]
+pub mod parquet_input;
+pub mod quasi_clique;
+pub mod quasi_clique_transformer;
 pub mod row;
 pub mod scorer;
 pub mod search_problem;
@@ -27,6 +47,7 @@ pub mod simple_directed_graph_builder;
 pub mod simple_transformer;
 pub mod simple_undirected_graph;
 pub mod simple_undirected_graph_builder;
+pub mod streaming_triangles_transformer;
 pub mod strongly_connected_components_transformer;
 pub mod test_utils;
 pub mod transformer;
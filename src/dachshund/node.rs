@@ -7,13 +7,26 @@
 use std::cmp::{Eq, PartialEq};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use fxhash::FxHashSet;
 use roaring::RoaringBitmap;
+use smallvec::SmallVec;
 
 use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::{EdgeTypeId, NodeId, NodeTypeId};
 
+/// Number of edges a node keeps inline, before moving them to the heap. Most nodes of the
+/// graphs we process have fewer, so building them needs no allocations per node.
+pub const NODE_INLINE_EDGES: usize = 8;
+
+/// Edges of a typed node (see `Node::edges`).
+pub type NodeEdges = SmallVec<[NodeEdge; NODE_INLINE_EDGES]>;
+
+/// Neighbors of a simple node (see `SimpleNode::neighbors`), in increasing order of id,
+/// without repeats.
+pub type SimpleNeighbors = SmallVec<[NodeId; NODE_INLINE_EDGES]>;
+
 /// Used to indicate a typed edge leading to the neighbor of a node.
 pub trait NodeEdgeBase
 where
@@ -104,7 +117,7 @@ pub struct Node {
     pub node_id: u32,
     pub is_core: bool,
     pub non_core_type: Option<NodeTypeId>,
-    pub edges: Vec<NodeEdge>,
+    pub edges: NodeEdges,
     pub neighbors_sets: HashMap<EdgeTypeId, RoaringBitmap>,
 }
 impl Hash for Node {
@@ -145,12 +158,43 @@ impl NodeBase for Node {
     }
 }
 
+/// Nodes whose edges are typed, and which keep their neighbors by edge type, so that ties
+/// can be counted by intersecting sets of neighbors (see `Node::neighbors_sets`) rather
+/// than by iterating over edges. Repeated ties of one type to a node count once.
+pub trait TypedNodeBase: NodeBase {
+    // number of neighbors through edges of the given type.
+    fn count_neighbors_of_type(&self, edge_type: EdgeTypeId) -> usize;
+    // number of ties with the nodes in ids through edges of the given type.
+    fn count_ties_with_ids_of_type(&self, ids: &Self::NodeSetType, edge_type: EdgeTypeId) -> usize;
+    // number of ties with the nodes in ids, by edge type (leaving out types with none).
+    fn count_ties_with_ids_by_type(&self, ids: &Self::NodeSetType) -> HashMap<EdgeTypeId, usize>;
+}
+impl TypedNodeBase for Node {
+    fn count_neighbors_of_type(&self, edge_type: EdgeTypeId) -> usize {
+        self.neighbors_sets
+            .get(&edge_type)
+            .map_or(0, |neighbors| neighbors.len() as usize)
+    }
+    fn count_ties_with_ids_of_type(&self, ids: &RoaringBitmap, edge_type: EdgeTypeId) -> usize {
+        self.neighbors_sets
+            .get(&edge_type)
+            .map_or(0, |neighbors| neighbors.intersection_len(ids) as usize)
+    }
+    fn count_ties_with_ids_by_type(&self, ids: &RoaringBitmap) -> HashMap<EdgeTypeId, usize> {
+        self.neighbors_sets
+            .iter()
+            .map(|(edge_type, neighbors)| (*edge_type, neighbors.intersection_len(ids) as usize))
+            .filter(|(_, num_ties)| *num_ties > 0)
+            .collect()
+    }
+}
+
 impl Node {
     pub fn new(
         node_id: u32,
         is_core: bool,
         non_core_type: Option<NodeTypeId>,
-        edges: Vec<NodeEdge>,
+        edges: NodeEdges,
         neighbors_sets: HashMap<EdgeTypeId, RoaringBitmap>,
     ) -> Node {
         Node {
@@ -185,7 +229,28 @@ impl Node {
 
 pub struct SimpleNode {
     pub node_id: NodeId,
-    pub neighbors: BTreeSet<NodeId>,
+    pub neighbors: SimpleNeighbors,
+}
+impl SimpleNode {
+    /// a node with the given neighbors, which are sorted and deduplicated.
+    pub fn new(node_id: NodeId, mut neighbors: SimpleNeighbors) -> Self {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        Self { node_id, neighbors }
+    }
+    pub fn has_neighbor(&self, id: NodeId) -> bool {
+        self.neighbors.binary_search(&id).is_ok()
+    }
+    /// adds a neighbor, keeping neighbors in order. Returns false if it was already one.
+    pub fn add_neighbor(&mut self, id: NodeId) -> bool {
+        match self.neighbors.binary_search(&id) {
+            Ok(_) => false,
+            Err(i) => {
+                self.neighbors.insert(i, id);
+                true
+            }
+        }
+    }
 }
 impl Hash for SimpleNode {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -219,7 +284,65 @@ impl NodeBase for SimpleNode {
     /// used to determine degree in a subgraph (i.e., the clique we're considering).
     /// HashSet is supplied by Candidate struct.
     fn count_ties_with_ids(&self, ids: &FxHashSet<NodeId>) -> usize {
-        ids.iter().filter(|x| self.neighbors.contains(x)).count()
+        ids.iter().filter(|x| self.has_neighbor(**x)).count()
+    }
+}
+
+/// A node of a `CsrGraph`: a view of its neighbors, which are kept along with those of all
+/// other nodes in one array shared by the graph.
+pub struct CsrNode {
+    pub node_id: NodeId,
+    targets: Arc<[NodeId]>,
+    start: usize,
+    end: usize,
+}
+impl CsrNode {
+    /// a node whose neighbors are targets[start..end], in increasing order of id.
+    pub fn new(node_id: NodeId, targets: Arc<[NodeId]>, start: usize, end: usize) -> Self {
+        Self {
+            node_id,
+            targets,
+            start,
+            end,
+        }
+    }
+    pub fn get_neighbors(&self) -> &[NodeId] {
+        &self.targets[self.start..self.end]
+    }
+    pub fn has_neighbor(&self, id: NodeId) -> bool {
+        self.get_neighbors().binary_search(&id).is_ok()
+    }
+}
+impl Hash for CsrNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_id.hash(state);
+    }
+}
+impl PartialEq for CsrNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_id == other.node_id
+    }
+}
+impl Eq for CsrNode {}
+impl NodeBase for CsrNode {
+    type NodeEdgeType = NodeId;
+    type NodeIdType = NodeId;
+    type NodeSetType = FxHashSet<NodeId>;
+
+    fn get_id(&self) -> NodeId {
+        self.node_id
+    }
+    fn get_edges(&self) -> Box<dyn Iterator<Item = &NodeId> + '_> {
+        Box::new(self.get_neighbors().iter())
+    }
+    fn get_outgoing_edges(&self) -> Box<dyn Iterator<Item = &NodeId> + '_> {
+        self.get_edges()
+    }
+    fn degree(&self) -> usize {
+        self.end - self.start
+    }
+    fn count_ties_with_ids(&self, ids: &FxHashSet<NodeId>) -> usize {
+        ids.iter().filter(|x| self.has_neighbor(**x)).count()
     }
 }
 
@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::{GraphId, NodeId};
+use fxhash::FxHashMap;
+use std::sync::{Arc, RwLock};
+
+/// Interning table for string node labels (e.g. user names), which gives each label a
+/// NodeId, in the order labels are first seen, so that graphs can be built from them
+/// without hashing them to integers beforehand. May be shared by several threads parsing
+/// lines (see `GraphNodeLabels`).
+#[derive(Default)]
+pub struct NodeLabels {
+    ids: RwLock<FxHashMap<String, NodeId>>,
+    labels: RwLock<Vec<String>>,
+}
+impl NodeLabels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// id of label, giving it the next one if it wasn't seen yet.
+    pub fn intern(&self, label: &str) -> NodeId {
+        if let Some(id) = self.ids.read().unwrap().get(label) {
+            return *id;
+        }
+        let mut ids = self.ids.write().unwrap();
+        // another thread may have interned label since we checked.
+        if let Some(id) = ids.get(label) {
+            return *id;
+        }
+        let mut labels = self.labels.write().unwrap();
+        let id = NodeId::from(labels.len() as i64);
+        ids.insert(label.to_owned(), id);
+        labels.push(label.to_owned());
+        id
+    }
+    /// id of label, None if it was never interned.
+    pub fn get_id(&self, label: &str) -> Option<NodeId> {
+        self.ids.read().unwrap().get(label).copied()
+    }
+    /// label the id was given to, None if there is no such id.
+    pub fn get_label(&self, id: NodeId) -> Option<String> {
+        let labels = self.labels.read().unwrap();
+        usize::try_from(id.value())
+            .ok()
+            .and_then(|i| labels.get(i).cloned())
+    }
+    /// label the id was given to, or the id itself if there is no such id, so that node
+    /// ids can be written out as labels whether or not they were all interned.
+    pub fn get_label_or_id(&self, id: NodeId) -> String {
+        self.get_label(id).unwrap_or_else(|| id.value().to_string())
+    }
+    /// labels of all interned ids, to look up many ids at once.
+    pub fn get_reverse_labels_map(&self) -> FxHashMap<NodeId, String> {
+        self.labels
+            .read()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, label)| (NodeId::from(i as i64), label.clone()))
+            .collect()
+    }
+    pub fn len(&self) -> usize {
+        self.labels.read().unwrap().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Interning tables of the string node labels of the graphs being read, one per graph (see
+/// `LineProcessor::with_string_labels`). The table of a graph is released once the graph
+/// has been processed (see `LineProcessorBase::release_graph`), so that a long run only
+/// keeps the labels of graphs in flight, rather than every label ever read.
+#[derive(Default)]
+pub struct GraphNodeLabels {
+    graphs: RwLock<FxHashMap<GraphId, Arc<NodeLabels>>>,
+}
+impl GraphNodeLabels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// table of the labels of graph_id, creating it if none of its nodes was read yet.
+    pub fn get_or_insert(&self, graph_id: GraphId) -> Arc<NodeLabels> {
+        if let Some(labels) = self.graphs.read().unwrap().get(&graph_id) {
+            return labels.clone();
+        }
+        self.graphs
+            .write()
+            .unwrap()
+            .entry(graph_id)
+            .or_default()
+            .clone()
+    }
+    /// table of the labels of graph_id, None if none of its nodes was read (or it was
+    /// released). Tables stay valid after being released.
+    pub fn get(&self, graph_id: GraphId) -> Option<Arc<NodeLabels>> {
+        self.graphs.read().unwrap().get(&graph_id).cloned()
+    }
+    /// drops the table of graph_id, e.g. once its output has been written.
+    pub fn release(&self, graph_id: GraphId) {
+        self.graphs.write().unwrap().remove(&graph_id);
+    }
+    /// number of graphs with a table.
+    pub fn len(&self) -> usize {
+        self.graphs.read().unwrap().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// node_id as written in output: the label it was interned from, if labels are given, or
+/// the id itself.
+pub fn format_node_id(node_id: NodeId, labels: Option<&NodeLabels>) -> String {
+    match labels {
+        Some(labels) => labels.get_label_or_id(node_id),
+        None => node_id.value().to_string(),
+    }
+}
+
+/// Builds graphs from edges between string labels, with any builder taking
+/// (source id, target id) rows. Labels are interned in the given table, which can then map
+/// the ids of the graph back to them.
+pub trait LabeledGraphBuilder: GraphBuilderBase<RowType = (i64, i64)> {
+    // named after GraphBuilderBase::from_vector, which it wraps.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_labeled_vector<S: AsRef<str>>(
+        &mut self,
+        data: &[(S, S)],
+        labels: &NodeLabels,
+    ) -> CLQResult<Self::GraphType> {
+        let rows: Vec<(i64, i64)> = data
+            .iter()
+            .map(|(source, target)| {
+                (
+                    labels.intern(source.as_ref()).value(),
+                    labels.intern(target.as_ref()).value(),
+                )
+            })
+            .collect();
+        self.from_vector(rows)
+    }
+}
+impl<T: GraphBuilderBase<RowType = (i64, i64)>> LabeledGraphBuilder for T {}
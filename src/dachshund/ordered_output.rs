@@ -0,0 +1,77 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use std::collections::BTreeMap;
+use std::sync::mpsc::Sender;
+use std::sync::{Condvar, Mutex};
+
+struct OrderedOutputState {
+    // sequence number to hand out to the next graph.
+    next_reserved: usize,
+    // sequence number of the next graph whose output can be sent.
+    next_to_send: usize,
+    // output of graphs that finished ahead of their turn.
+    pending: BTreeMap<usize, Option<String>>,
+}
+
+/// Reorders the output of graphs processed concurrently so that it is sent in the order
+/// in which graphs were read. At most `window` graphs can be in flight (processed or
+/// waiting for their turn) at once: reserving a slot for another graph blocks until the
+/// oldest graph in the window has been sent.
+pub struct OrderedOutput {
+    window: usize,
+    state: Mutex<OrderedOutputState>,
+    turn_taken: Condvar,
+}
+impl OrderedOutput {
+    pub fn new(window: usize) -> CLQResult<Self> {
+        if window == 0 {
+            return Err(CLQError::from("Reordering window must be positive"));
+        }
+        Ok(Self {
+            window,
+            state: Mutex::new(OrderedOutputState {
+                next_reserved: 0,
+                next_to_send: 0,
+                pending: BTreeMap::new(),
+            }),
+            turn_taken: Condvar::new(),
+        })
+    }
+
+    /// Returns the sequence number of the next graph, blocking while the window is full.
+    pub fn reserve(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        while state.next_reserved - state.next_to_send >= self.window {
+            state = self.turn_taken.wait(state).unwrap();
+        }
+        state.next_reserved += 1;
+        state.next_reserved - 1
+    }
+
+    /// Records the output of graph seq, and sends it, along with the output of any
+    /// graphs that were waiting on it, if its turn has come.
+    pub fn send(&self, seq: usize, line: Option<String>, output: &Sender<(Option<String>, bool)>) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.insert(seq, line);
+        let mut sent_any = false;
+        loop {
+            let next = state.next_to_send;
+            match state.pending.remove(&next) {
+                Some(line) => {
+                    output.send((line, false)).unwrap();
+                    state.next_to_send += 1;
+                    sent_any = true;
+                }
+                None => break,
+            }
+        }
+        if sent_any {
+            self.turn_taken.notify_all();
+        }
+    }
+}
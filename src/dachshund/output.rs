@@ -8,11 +8,19 @@
 use std::io::Error;
 use std::io::Write;
 
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
+
+/// First field of the row written before the output of each graph, when graph markers are
+/// on (see `Output::with_graph_markers`): `#begin\tgraph_id`.
+pub const GRAPH_BEGIN_MARKER: &str = "#begin";
+/// First field of the row written after the output of each graph, when graph markers are
+/// on: `#end\tgraph_id\tnum_lines`, num_lines being the number of rows in between.
+pub const GRAPH_END_MARKER: &str = "#end";
 
 pub struct Output<'a> {
     pub destination: &'a mut Vec<u8>,
     is_stdout: bool,
+    graph_markers: bool,
 }
 
 impl<'a> Output<'a> {
@@ -20,14 +28,27 @@ impl<'a> Output<'a> {
         Output {
             destination: text,
             is_stdout: true,
+            graph_markers: false,
         }
     }
     pub fn string(text: &'a mut Vec<u8>) -> Output {
         Output {
             destination: text,
             is_stdout: false,
+            graph_markers: false,
         }
     }
+    /// brackets the output of each graph with begin and end marker rows (see
+    /// GRAPH_BEGIN_MARKER and GRAPH_END_MARKER), so that consumers can tell the output of a
+    /// run that stopped midway through a graph from complete output. Marker rows start
+    /// with '#', so typed graph line processors skip them as comments.
+    pub fn with_graph_markers(mut self) -> Self {
+        self.graph_markers = true;
+        self
+    }
+    pub fn has_graph_markers(&self) -> bool {
+        self.graph_markers
+    }
     pub fn print(&mut self, text: String) -> CLQResult<()> {
         if !self.is_stdout {
             self.write_all(text.as_bytes())?;
@@ -39,6 +60,58 @@ impl<'a> Output<'a> {
         Ok(())
     }
 }
+pub fn get_graph_begin_marker(graph_id: &str) -> String {
+    format!("{GRAPH_BEGIN_MARKER}\t{graph_id}")
+}
+
+pub fn get_graph_end_marker(graph_id: &str, num_lines: usize) -> String {
+    format!("{GRAPH_END_MARKER}\t{graph_id}\t{num_lines}")
+}
+
+/// Checks that output written with graph markers is complete: that the rows of every graph
+/// are bracketed by a begin and a matching end marker, which counts them. Returns the
+/// number of graphs, or an error for the first row at which output is found to be
+/// truncated or malformed.
+pub fn check_graph_markers<'b, I: IntoIterator<Item = &'b str>>(lines: I) -> CLQResult<usize> {
+    let mut current: Option<(&str, usize)> = None;
+    let mut num_graphs: usize = 0;
+    for (line_ix, line) in lines.into_iter().enumerate() {
+        let error = |message: String| CLQError::from(format!("Row {}: {message}", line_ix + 1));
+        let fields: Vec<&str> = line.split('\t').collect();
+        match (fields[0], current) {
+            (GRAPH_BEGIN_MARKER, None) if fields.len() == 2 => current = Some((fields[1], 0)),
+            (GRAPH_BEGIN_MARKER, None) => return Err(error("malformed begin marker".to_owned())),
+            (GRAPH_BEGIN_MARKER, Some((graph_id, _))) => {
+                return Err(error(format!("graph {graph_id} has no end marker")));
+            }
+            (GRAPH_END_MARKER, Some((graph_id, num_lines))) => {
+                if fields.len() != 3 || fields[1] != graph_id {
+                    return Err(error(format!("end marker does not match graph {graph_id}")));
+                }
+                if fields[2] != num_lines.to_string() {
+                    return Err(error(format!(
+                        "graph {graph_id} has {num_lines} rows, its end marker says {}",
+                        fields[2]
+                    )));
+                }
+                current = None;
+                num_graphs += 1;
+            }
+            (_, Some((graph_id, num_lines))) => current = Some((graph_id, num_lines + 1)),
+            (GRAPH_END_MARKER, None) => {
+                return Err(error("end marker without a begin marker".to_owned()));
+            }
+            (_, None) => return Err(error("row outside of any graph".to_owned())),
+        }
+    }
+    match current {
+        Some((graph_id, _)) => Err(CLQError::from(format!(
+            "Output ends before the end marker of graph {graph_id}"
+        ))),
+        None => Ok(num_graphs),
+    }
+}
+
 impl<'a> Write for Output<'a> {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
         self.destination.write(buf)
@@ -0,0 +1,222 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate arrow_array;
+extern crate arrow_schema;
+extern crate parquet;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::line_pipeline::ParsedLine;
+use crate::dachshund::line_processor::{FieldValue, LineProcessorBase, LineSchema};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ArrowPrimitiveType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    UInt16Type, UInt32Type, UInt8Type,
+};
+use arrow_array::{Array, OffsetSizeTrait, RecordBatch, RecordBatchReader};
+use arrow_schema::DataType;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use std::fs::File;
+use std::io;
+use std::sync::Arc;
+
+/// Number of rows read from a Parquet file at once.
+pub const PARQUET_BATCH_SIZE: usize = 8192;
+
+/// A Parquet file of rows, e.g. an edge list exported from a warehouse, to be read by
+/// transformers directly (see `TransformerBase::run_parquet`) rather than through
+/// line-based `Input`. Columns are matched to the fields expected by the line processor of
+/// the transformer by name, whatever their order, and other columns are ignored. Integer
+/// (but for unsigned 64-bit), floating point and string columns are supported, and rows
+/// are parsed from their values as typed (see `LineProcessorBase::process_values`), so
+/// that strings may contain tabs; nulls are read as empty fields.
+pub struct ParquetInput {
+    reader: ParquetRecordBatchReader,
+}
+impl ParquetInput {
+    pub fn file(path: &str) -> CLQResult<Self> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?
+            .with_batch_size(PARQUET_BATCH_SIZE)
+            .build()?;
+        Ok(Self { reader })
+    }
+
+    /// Rows of the file, as parsed by line_processor, in order. Fails if line_processor
+    /// doesn't declare the fields it expects (see `LineProcessorBase::get_schema`), or if
+    /// any of them has no column. Rows are also written out as tab-separated lines if
+    /// keep_lines is set (e.g. to report or hash them).
+    pub fn parsed_lines(
+        self,
+        line_processor: Arc<dyn LineProcessorBase>,
+        keep_lines: bool,
+    ) -> CLQResult<ParquetLines> {
+        let schema = line_processor.get_schema().ok_or_else(|| {
+            CLQError::from("Parquet input needs a line processor with a fixed set of fields")
+        })?;
+        let arrow_schema = self.reader.schema();
+        for field in schema.fields.iter().filter(|x| !x.is_empty()) {
+            let data_type = match arrow_schema.field_with_name(field) {
+                Ok(column) => column.data_type(),
+                Err(_) => {
+                    return Err(CLQError::from(format!(
+                        "Parquet input has no column named {} (expected {})",
+                        field, schema
+                    )))
+                }
+            };
+            if !is_supported(data_type) {
+                return Err(CLQError::from(format!(
+                    "Unsupported type for Parquet column {}: {}",
+                    field, data_type
+                )));
+            }
+        }
+        Ok(ParquetLines {
+            reader: self.reader,
+            schema,
+            line_processor,
+            keep_lines,
+            current: Vec::new().into_iter(),
+        })
+    }
+}
+
+fn is_supported(data_type: &DataType) -> bool {
+    // unsigned 64-bit integers may not fit node ids.
+    (data_type.is_integer() && *data_type != DataType::UInt64)
+        || data_type.is_floating()
+        || matches!(data_type, DataType::Utf8 | DataType::LargeUtf8)
+}
+
+// reads the value of a column at a given row.
+type ColumnReader<'a> = Box<dyn Fn(usize) -> FieldValue<'a> + 'a>;
+
+// reader of the values of a column, as they are typed in it (nulls being read as such).
+fn get_column_reader(column: &dyn Array) -> CLQResult<ColumnReader<'_>> {
+    fn integers<T: ArrowPrimitiveType>(column: &dyn Array) -> ColumnReader<'_>
+    where
+        T::Native: Into<i64>,
+    {
+        let values = column.as_primitive::<T>();
+        Box::new(move |i| match values.is_null(i) {
+            true => FieldValue::Null,
+            false => FieldValue::Int(values.value(i).into()),
+        })
+    }
+    fn floats<T: ArrowPrimitiveType>(column: &dyn Array) -> ColumnReader<'_>
+    where
+        T::Native: Into<f64>,
+    {
+        let values = column.as_primitive::<T>();
+        Box::new(move |i| match values.is_null(i) {
+            true => FieldValue::Null,
+            false => FieldValue::Float(values.value(i).into()),
+        })
+    }
+    fn strings<O: OffsetSizeTrait>(column: &dyn Array) -> ColumnReader<'_> {
+        let values = column.as_string::<O>();
+        Box::new(move |i| match values.is_null(i) {
+            true => FieldValue::Null,
+            false => FieldValue::Str(values.value(i)),
+        })
+    }
+    Ok(match column.data_type() {
+        DataType::Int8 => integers::<Int8Type>(column),
+        DataType::Int16 => integers::<Int16Type>(column),
+        DataType::Int32 => integers::<Int32Type>(column),
+        DataType::Int64 => integers::<Int64Type>(column),
+        DataType::UInt8 => integers::<UInt8Type>(column),
+        DataType::UInt16 => integers::<UInt16Type>(column),
+        DataType::UInt32 => integers::<UInt32Type>(column),
+        DataType::Float32 => floats::<Float32Type>(column),
+        DataType::Float64 => floats::<Float64Type>(column),
+        DataType::Utf8 => strings::<i32>(column),
+        DataType::LargeUtf8 => strings::<i64>(column),
+        data_type => {
+            return Err(CLQError::from(format!(
+                "Unsupported Parquet column type: {}",
+                data_type
+            )))
+        }
+    })
+}
+
+/// Rows of a `ParquetInput`, as parsed by a line processor, read a batch at a time.
+pub struct ParquetLines {
+    reader: ParquetRecordBatchReader,
+    schema: &'static LineSchema,
+    line_processor: Arc<dyn LineProcessorBase>,
+    keep_lines: bool,
+    current: std::vec::IntoIter<ParsedLine>,
+}
+impl ParquetLines {
+    // parses the rows of batch from the values of its columns, in the order of the fields
+    // of schema (fields without a name being null). Rows are only written out as lines if
+    // they are to be kept.
+    fn parse_batch(&self, batch: &RecordBatch) -> CLQResult<Vec<ParsedLine>> {
+        let columns: Vec<Option<ColumnReader>> = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| match field.is_empty() {
+                true => Ok(None),
+                false => batch
+                    .column_by_name(field)
+                    .ok_or_else(CLQError::err_none)
+                    .and_then(|column| get_column_reader(column.as_ref()))
+                    .map(Some),
+            })
+            .collect::<CLQResult<_>>()?;
+        let mut values: Vec<FieldValue> = Vec::with_capacity(columns.len());
+        Ok((0..batch.num_rows())
+            .map(|i| {
+                values.clear();
+                values.extend(
+                    columns
+                        .iter()
+                        .map(|x| x.as_ref().map_or(FieldValue::Null, |column| column(i))),
+                );
+                let raw = match self.keep_lines {
+                    true => Some(
+                        values
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\t"),
+                    ),
+                    false => None,
+                };
+                match self.line_processor.process_values(&values) {
+                    Ok(row) => ParsedLine::Row(row, raw),
+                    Err(error) => ParsedLine::Invalid(error, raw),
+                }
+            })
+            .collect())
+    }
+}
+impl Iterator for ParquetLines {
+    type Item = ParsedLine;
+
+    fn next(&mut self) -> Option<ParsedLine> {
+        loop {
+            if let Some(line) = self.current.next() {
+                return Some(line);
+            }
+            // batches that cannot be read are reported as unreadable lines.
+            let lines = self
+                .reader
+                .next()?
+                .map_err(|error| CLQError::from(error.to_string()))
+                .and_then(|batch| self.parse_batch(&batch));
+            match lines {
+                Ok(lines) => self.current = lines.into_iter(),
+                Err(error) => {
+                    return Some(ParsedLine::Unreadable(io::Error::other(error.to_string())))
+                }
+            }
+        }
+    }
+}
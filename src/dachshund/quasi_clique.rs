@@ -0,0 +1,430 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate serde_json;
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use rand::prelude::*;
+use roaring::RoaringBitmap;
+
+use crate::dachshund::algorithms::relaxed_cliques::is_k_club_within;
+use crate::dachshund::beam::{fits_in_memory, seeded_rng, Beam, BeamCandidate};
+use crate::dachshund::candidate::{get_expansion_recipes, merge_checksum, NeigbhorhoodMap, Recipe};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::{GraphId, NodeId};
+use crate::dachshund::search_problem::{CliqueRelaxation, SearchProblem};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+
+/// A simple undirected graph as searched for quasi-cliques, with nodes numbered from 0 in
+/// increasing order of id (candidates refer to nodes by number, as in typed graphs).
+pub struct QuasiCliqueGraph {
+    pub ids: Vec<NodeId>,
+    pub neighbors: Vec<RoaringBitmap>,
+}
+impl QuasiCliqueGraph {
+    /// numbers the nodes of graph, leaving out self-loops.
+    pub fn new(graph: &SimpleUndirectedGraph) -> Self {
+        let ids: Vec<NodeId> = graph.get_ordered_node_ids();
+        let positions: HashMap<NodeId, u32> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i as u32))
+            .collect();
+        let neighbors = ids
+            .iter()
+            .map(|id| {
+                graph
+                    .get_node(*id)
+                    .neighbors
+                    .iter()
+                    .filter(|x| *x != id)
+                    .map(|x| positions[x])
+                    .collect()
+            })
+            .collect();
+        Self { ids, neighbors }
+    }
+    pub fn num_nodes(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+/// Scores candidate quasi-cliques of simple undirected graphs. The score grows with the
+/// number of nodes and, weighted by alpha, with density (the proportion of pairs of nodes
+/// that are tied), and is 0 unless density is at least global_thresh, and every node is tied
+/// to at least gamma (the local threshold) of the other nodes, as in a gamma-quasi-clique.
+/// With a clique relaxation, the score is instead 0 unless the candidate is a k-plex or a
+/// k-club. Candidates of a single node score -1.
+pub struct QuasiCliqueScorer {
+    alpha: f32,
+    global_thresh: Option<f32>,
+    gamma: Option<f32>,
+    relaxation: Option<CliqueRelaxation>,
+}
+impl QuasiCliqueScorer {
+    /// takes alpha and global_thresh from the search problem, gamma from its local_thresh,
+    /// and its clique relaxation, if any (in which case the thresholds don't apply).
+    pub fn new(search_problem: &SearchProblem) -> Self {
+        let relaxation = search_problem.clique_relaxation;
+        let (global_thresh, gamma) = match relaxation {
+            Some(_) => (None, None),
+            None => (search_problem.global_thresh, search_problem.local_thresh),
+        };
+        Self {
+            alpha: search_problem.alpha,
+            global_thresh,
+            gamma,
+            relaxation,
+        }
+    }
+
+    /// score of a candidate of size nodes, with num_ties ties among them, and min_degree
+    /// ties for the node with the fewest.
+    pub fn score_counts(&self, size: usize, num_ties: usize, min_degree: usize) -> f32 {
+        if size < 2 {
+            return -1.0;
+        }
+        let density = get_density(size, num_ties);
+        if self.global_thresh.is_some_and(|thresh| density < thresh) {
+            return 0.0;
+        }
+        if self
+            .gamma
+            .is_some_and(|gamma| (min_degree as f32) < gamma * (size - 1) as f32)
+        {
+            return 0.0;
+        }
+        if let Some(CliqueRelaxation::KPlex(k)) = self.relaxation {
+            if min_degree + k < size {
+                return 0.0;
+            }
+        }
+        (size as f32 + 1.0).ln() + self.alpha * density
+    }
+
+    // 0 for candidates (by their nodes) which aren't k-clubs, if searching for those, and
+    // score otherwise.
+    fn check_k_club(&self, score: f32, graph: &QuasiCliqueGraph, node_ids: &RoaringBitmap) -> f32 {
+        match self.relaxation {
+            Some(CliqueRelaxation::KClub(k))
+                if score > 0.0 && !is_k_club_within(&graph.neighbors, node_ids, k) =>
+            {
+                0.0
+            }
+            _ => score,
+        }
+    }
+
+    pub fn score(&self, candidate: &QuasiCliqueCandidate) -> f32 {
+        let score = self.score_counts(
+            candidate.get_size(),
+            candidate.count_ties(),
+            candidate.get_min_degree(),
+        );
+        self.check_k_club(score, candidate.graph, &candidate.node_ids)
+    }
+
+    /// scores the candidate that recipe would give, without materializing it.
+    pub fn score_recipe(&self, recipe: &Recipe, candidate: &QuasiCliqueCandidate) -> f32 {
+        assert_eq!(recipe.checksum, candidate.checksum);
+        let node_id = recipe
+            .node_id
+            .expect("Can't score recipe with no score and no node.");
+        let num_new_ties = candidate.neighborhood.get(&node_id).cloned().unwrap_or(0) as usize;
+        // only needed for the local threshold, or for k-plexes.
+        let min_degree = if self.gamma.is_some()
+            || matches!(self.relaxation, Some(CliqueRelaxation::KPlex(_)))
+        {
+            let neighbors = &candidate.graph.neighbors[node_id as usize];
+            candidate
+                .degrees
+                .iter()
+                .map(|(id, degree)| *degree as usize + neighbors.contains(*id) as usize)
+                .fold(num_new_ties, usize::min)
+        } else {
+            0
+        };
+        let score = self.score_counts(
+            candidate.get_size() + 1,
+            candidate.count_ties() + num_new_ties,
+            min_degree,
+        );
+        match self.relaxation {
+            Some(CliqueRelaxation::KClub(_)) => {
+                let mut node_ids = candidate.node_ids.clone();
+                node_ids.insert(node_id);
+                self.check_k_club(score, candidate.graph, &node_ids)
+            }
+            _ => score,
+        }
+    }
+}
+
+// proportion of the pairs of size nodes that are tied, 0 for fewer than 2 nodes.
+fn get_density(size: usize, num_ties: usize) -> f32 {
+    if size < 2 {
+        return 0.0;
+    }
+    2.0 * num_ties as f32 / (size * (size - 1)) as f32
+}
+
+/// A candidate quasi-clique of a simple undirected graph, searched by `Beam` like the
+/// (quasi-)bicliques of typed graphs (see `Candidate`). Ties are counted incrementally as
+/// nodes are added: among the nodes of the candidate, for each of them, and for each node
+/// adjacent to the candidate (its neighborhood, from which expansions are drawn).
+pub struct QuasiCliqueCandidate<'a> {
+    pub graph: &'a QuasiCliqueGraph,
+    pub node_ids: RoaringBitmap,
+    pub checksum: Option<u64>,
+    score: Option<f32>,
+    num_ties: usize,
+    // ties of each node of the candidate with the other ones.
+    degrees: HashMap<u32, u32>,
+    // ties with the candidate of each node adjacent to it (and not in it).
+    neighborhood: NeigbhorhoodMap,
+}
+impl<'a> QuasiCliqueCandidate<'a> {
+    /// creates an empty candidate, refering to a graph.
+    pub fn init_blank(graph: &'a QuasiCliqueGraph) -> Self {
+        Self {
+            graph,
+            node_ids: RoaringBitmap::new(),
+            checksum: None,
+            score: None,
+            num_ties: 0,
+            degrees: HashMap::new(),
+            neighborhood: NeigbhorhoodMap::default(),
+        }
+    }
+
+    /// creates a scored candidate from a single node (by number).
+    pub fn new(node_id: u32, graph: &'a QuasiCliqueGraph, scorer: &QuasiCliqueScorer) -> Self {
+        let mut candidate = Self::init_blank(graph);
+        candidate.add_node(node_id).unwrap();
+        candidate.score = Some(scorer.score(&candidate));
+        candidate
+    }
+
+    /// adds a node (by number) to the candidate, resetting its score. Returns an error if
+    /// it is already in the candidate.
+    pub fn add_node(&mut self, node_id: u32) -> CLQResult<()> {
+        if !self.node_ids.insert(node_id) {
+            return Err(CLQError::from(format!(
+                "Node {} is already in the candidate",
+                self.graph.ids[node_id as usize].value()
+            )));
+        }
+        self.checksum = merge_checksum(self.checksum, node_id);
+        self.score = None;
+        let num_new_ties = self.neighborhood.remove(&node_id).unwrap_or(0);
+        self.num_ties += num_new_ties as usize;
+        self.degrees.insert(node_id, num_new_ties);
+        for neighbor in &self.graph.neighbors[node_id as usize] {
+            match self.degrees.get_mut(&neighbor) {
+                Some(degree) => *degree += 1,
+                None => *self.neighborhood.entry(neighbor).or_insert(0) += 1,
+            }
+        }
+        Ok(())
+    }
+
+    /// sets score, as computed by a QuasiCliqueScorer.
+    pub fn set_score(&mut self, score: f32) -> CLQResult<()> {
+        if self.score.is_some() {
+            return Err(CLQError::from(
+                "Tried to set score on an already scored candidate.",
+            ));
+        }
+        self.score = Some(score);
+        Ok(())
+    }
+
+    pub fn get_score(&self) -> CLQResult<f32> {
+        let score = self
+            .score
+            .ok_or("Tried to get score from an unscored candidate.")?;
+        Ok(score)
+    }
+
+    /// number of nodes in the candidate.
+    pub fn get_size(&self) -> usize {
+        self.node_ids.len() as usize
+    }
+
+    /// number of ties between nodes of the candidate.
+    pub fn count_ties(&self) -> usize {
+        self.num_ties
+    }
+
+    /// proportion of the pairs of nodes of the candidate that are tied.
+    pub fn get_density(&self) -> f32 {
+        get_density(self.get_size(), self.num_ties)
+    }
+
+    /// fewest ties any node of the candidate has with the other ones (0 if empty).
+    pub fn get_min_degree(&self) -> usize {
+        self.degrees.values().min().cloned().unwrap_or(0) as usize
+    }
+
+    /// ties of each node of the candidate (by number) with the other ones.
+    pub fn get_degrees(&self) -> HashMap<u32, u32> {
+        self.degrees.clone()
+    }
+
+    /// ties with the candidate of each node (by number) adjacent to it.
+    pub fn get_neighborhood(&self) -> NeigbhorhoodMap {
+        self.neighborhood.clone()
+    }
+
+    /// ids of the nodes in the candidate, in increasing order.
+    pub fn sorted_ids(&self) -> Vec<NodeId> {
+        self.node_ids
+            .iter()
+            .map(|x| self.graph.ids[x as usize])
+            .collect()
+    }
+
+    /// encodes self as a tab-separated row: number of nodes, node ids, density, and the
+    /// proportion of the other nodes each node is tied to (in the order of node ids).
+    pub fn to_printable_row(&self) -> CLQResult<String> {
+        let ids: Vec<i64> = self.sorted_ids().iter().map(|x| x.value()).collect();
+        let others = self.get_size().saturating_sub(1).max(1) as f32;
+        let local_densities: Vec<f32> = self
+            .node_ids
+            .iter()
+            .map(|x| self.degrees[&x] as f32 / others)
+            .collect();
+        Ok(format!(
+            "{}\t{}\t{}\t{}",
+            ids.len(),
+            serde_json::to_string(&ids)?,
+            self.get_density(),
+            serde_json::to_string(&local_densities)?,
+        ))
+    }
+}
+impl<'a> BeamCandidate for QuasiCliqueCandidate<'a> {
+    type Scorer = QuasiCliqueScorer;
+    fn get_checksum(&self) -> Option<u64> {
+        self.checksum
+    }
+    fn get_score(&self) -> CLQResult<f32> {
+        QuasiCliqueCandidate::get_score(self)
+    }
+    fn as_recipe(&self) -> Recipe {
+        Recipe {
+            checksum: self.checksum,
+            node_id: None,
+            score: self.score,
+            local_guarantee: None,
+        }
+    }
+    fn expand_from_recipe(&self, recipe: &Recipe) -> CLQResult<Self> {
+        let mut candidate = self.replicate(false);
+        if let Some(node_id) = recipe.node_id {
+            candidate.add_node(node_id)?;
+        }
+        candidate.score = recipe.score;
+        Ok(candidate)
+    }
+    fn one_step_search(
+        &self,
+        num_to_search: usize,
+        visited_candidates: &mut HashSet<u64>,
+        scorer: &QuasiCliqueScorer,
+    ) -> CLQResult<Vec<Recipe>> {
+        let mut recipes = get_expansion_recipes(
+            self.checksum,
+            &self.neighborhood,
+            num_to_search,
+            visited_candidates,
+        );
+        for recipe in &mut recipes {
+            recipe.score = Some(scorer.score_recipe(recipe, self));
+        }
+        Ok(recipes)
+    }
+    fn replicate(&self, keep_score: bool) -> Self {
+        let mut neighborhood = self.neighborhood.clone();
+        if 2 * neighborhood.capacity() > neighborhood.len() {
+            neighborhood.shrink_to_fit()
+        }
+        Self {
+            graph: self.graph,
+            node_ids: self.node_ids.clone(),
+            checksum: self.checksum,
+            score: if keep_score { self.score } else { None },
+            num_ties: self.num_ties,
+            degrees: self.degrees.clone(),
+            neighborhood,
+        }
+    }
+    fn estimated_memory_bytes(&self) -> usize {
+        // hashbrown keeps one control byte per bucket next to each (key, value) pair.
+        let maps_bytes = (self.degrees.capacity() + self.neighborhood.capacity())
+            * (std::mem::size_of::<(u32, u32)>() + 1);
+        std::mem::size_of::<Self>() + self.node_ids.serialized_size() + maps_bytes
+    }
+    fn describe(&self, _non_core_types: &[String]) -> CLQResult<String> {
+        self.to_printable_row()
+    }
+}
+
+impl<'a> Beam<'a, QuasiCliqueGraph, QuasiCliqueCandidate<'a>> {
+    /// creates a new beam for mining quasi-cliques of a simple undirected graph, starting
+    /// from single nodes reached by short random walks from random nodes (see `Beam::new`
+    /// for the other parameters). The graph must have at least one edge.
+    pub fn new_quasi_clique(
+        graph: &'a QuasiCliqueGraph,
+        verbose: bool,
+        search_problem: Rc<SearchProblem>,
+        graph_id: GraphId,
+    ) -> CLQResult<Self> {
+        let roots: Vec<u32> = (0..graph.num_nodes() as u32)
+            .filter(|x| !graph.neighbors[*x as usize].is_empty())
+            .collect();
+        if roots.is_empty() {
+            return Err(CLQError::from(format!(
+                "No edges in graph_id: {}",
+                graph_id.value()
+            )));
+        }
+        let scorer = QuasiCliqueScorer::new(&search_problem);
+        let mut rng = seeded_rng(graph_id);
+        let mut candidates: Vec<QuasiCliqueCandidate> =
+            Vec::with_capacity(search_problem.beam_size);
+        let mut memory_bytes: usize = 0;
+        while candidates.len() < search_problem.beam_size {
+            let mut node_id: u32 = *roots.choose(&mut rng).unwrap();
+            for _ in 0..7 {
+                let neighbors = &graph.neighbors[node_id as usize];
+                let ix = rng.gen_range(0..neighbors.len());
+                node_id = neighbors.select(ix as u32).unwrap();
+            }
+            let candidate = QuasiCliqueCandidate::new(node_id, graph, &scorer);
+            if !fits_in_memory(
+                &mut memory_bytes,
+                BeamCandidate::estimated_memory_bytes(&candidate),
+                candidates.len(),
+                search_problem.max_beam_memory,
+            )? {
+                break;
+            }
+            candidates.push(candidate);
+        }
+        Ok(Beam::from_candidates(
+            candidates,
+            graph,
+            verbose,
+            &[],
+            search_problem,
+            scorer,
+        ))
+    }
+}
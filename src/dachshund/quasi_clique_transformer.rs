@@ -0,0 +1,173 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+
+use crate::dachshund::beam::{Beam, BeamSearchResult};
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_arena::GraphArena;
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::{GraphId, NodeId};
+use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::quasi_clique::{QuasiCliqueCandidate, QuasiCliqueGraph};
+use crate::dachshund::row::{Row, SimpleEdgeRow};
+use crate::dachshund::search_problem::{CliqueRelaxation, SearchProblem};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use crate::dachshund::transformer::Transformer;
+use crate::dachshund::transformer_base::TransformerBase;
+use clap::ArgMatches;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Mines quasi-cliques of unipartite graphs, read as simple edge rows
+/// (graph_id\tsource_id\ttarget_id), with the same beam search as `Transformer` uses for
+/// (quasi-)bicliques of typed graphs (see `QuasiCliqueScorer` for the objective). Prints
+/// the top candidate of each graph, if it is a conforming quasi-clique, as
+/// original_graph_id\tsize\t[node ids]\tdensity\t[local densities].
+pub struct QuasiCliqueTransformer {
+    pub search_problem: Rc<SearchProblem>,
+    pub debug: bool,
+    batch: Vec<SimpleEdgeRow>,
+    line_processor: Arc<LineProcessor>,
+    // reused from one graph to the next.
+    arena: GraphArena,
+}
+
+impl QuasiCliqueTransformer {
+    /// creates a transformer for the given search problem, whose local_thresh is the
+    /// proportion of the other nodes of a quasi-clique each node must be tied to, and whose
+    /// min_degree is used to prune graphs to their min_degree-core before searching.
+    /// Returns an error if the search problem is invalid.
+    pub fn new(search_problem: SearchProblem, debug: bool) -> CLQResult<Self> {
+        search_problem.validate(debug)?;
+        Ok(Self {
+            search_problem: Rc::new(search_problem),
+            debug,
+            batch: Vec::new(),
+            line_processor: Arc::new(LineProcessor::new()),
+            arena: GraphArena::new(),
+        })
+    }
+
+    /// constructs a transformer from the search command line arguments shared with
+    /// `Transformer` (see `Transformer::from_argmatches_with_typespec`), and the optional
+    /// k_plex or k_club argument, to search for k-plexes or k-clubs instead.
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        let mut search_problem = SearchProblem::new(
+            Transformer::parse_arg(matches, "beam_size")?,
+            Transformer::parse_arg(matches, "alpha")?,
+            Some(Transformer::parse_arg(matches, "global_thresh")?),
+            Some(Transformer::parse_arg(matches, "local_thresh")?),
+            Transformer::parse_arg(matches, "num_to_search")?,
+            Transformer::parse_arg(matches, "epochs")?,
+            Transformer::parse_arg(matches, "max_repeated_prior_scores")?,
+            Transformer::parse_arg(matches, "min_degree")?,
+        );
+        if matches.is_present("max_beam_memory") {
+            let max_beam_memory_mb: usize = Transformer::parse_arg(matches, "max_beam_memory")?;
+            search_problem = search_problem.with_max_beam_memory(max_beam_memory_mb * 1024 * 1024);
+        }
+        if matches.is_present("k_plex") {
+            search_problem = search_problem.with_clique_relaxation(CliqueRelaxation::KPlex(
+                Transformer::parse_arg(matches, "k_plex")?,
+            ));
+        } else if matches.is_present("k_club") {
+            search_problem = search_problem.with_clique_relaxation(CliqueRelaxation::KClub(
+                Transformer::parse_arg(matches, "k_club")?,
+            ));
+        }
+        Self::new(
+            search_problem,
+            Transformer::parse_arg(matches, "debug_mode")?,
+        )
+    }
+
+    /// builds graph from edge rows, pruned to its min_degree-core (the largest subgraph in
+    /// which every node has at least min_degree neighbors), as no node outside it can be
+    /// tied to min_degree other nodes of a quasi-clique.
+    pub fn build_pruned_graph(&self, rows: &[SimpleEdgeRow]) -> CLQResult<SimpleUndirectedGraph> {
+        Self::prune_graph(rows, self.search_problem.min_degree, &mut GraphArena::new())
+    }
+
+    // build_pruned_graph, computing the coreness of nodes in arena.
+    fn prune_graph(
+        rows: &[SimpleEdgeRow],
+        min_degree: usize,
+        arena: &mut GraphArena,
+    ) -> CLQResult<SimpleUndirectedGraph> {
+        let tuples: Vec<(i64, i64)> = rows.iter().map(|x| x.as_tuple()).collect();
+        let graph = SimpleUndirectedGraphBuilder {}.from_vector(tuples.clone())?;
+        if min_degree == 0 {
+            return Ok(graph);
+        }
+        arena.load(&graph);
+        arena.compute_coreness_values();
+        let kept = |id: i64| arena.get_coreness(NodeId::from(id)) >= Some(min_degree);
+        SimpleUndirectedGraphBuilder {}.from_vector(
+            tuples
+                .into_iter()
+                .filter(|(source, target)| kept(*source) && kept(*target))
+                .collect(),
+        )
+    }
+
+    /// runs the beam search on a graph with at least one edge.
+    pub fn process_graph<'a>(
+        &self,
+        graph: &'a QuasiCliqueGraph,
+        graph_id: GraphId,
+    ) -> CLQResult<BeamSearchResult<QuasiCliqueCandidate<'a>>> {
+        let mut beam =
+            Beam::new_quasi_clique(graph, self.debug, self.search_problem.clone(), graph_id)?;
+        beam.run_search()
+    }
+}
+
+impl TransformerBase for QuasiCliqueTransformer {
+    fn get_line_processor(&self) -> Arc<dyn LineProcessorBase> {
+        self.line_processor.clone()
+    }
+    fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
+        self.batch.push(row.as_simple_edge_row().unwrap());
+        Ok(())
+    }
+    fn reset(&mut self) -> CLQResult<()> {
+        self.batch.clear();
+        Ok(())
+    }
+
+    fn process_batch(
+        &mut self,
+        graph_id: GraphId,
+        output: &Sender<(Option<String>, bool)>,
+    ) -> CLQResult<()> {
+        let graph = QuasiCliqueGraph::new(&Self::prune_graph(
+            &self.batch,
+            self.search_problem.min_degree,
+            &mut self.arena,
+        )?);
+        if graph.neighbors.iter().all(|x| x.is_empty()) {
+            // acknowledge the graph, so that it counts as processed.
+            output.send((None, false)).unwrap();
+            return Ok(());
+        }
+        let result = self.process_graph(&graph, graph_id)?;
+        if result.top_candidate.get_score()? > 0.0 {
+            let line: String = format!(
+                "{}\t{}",
+                self.line_processor
+                    .get_original_id(graph_id.value() as usize),
+                result.top_candidate.to_printable_row()?
+            );
+            output.send((Some(line), false)).unwrap();
+        } else {
+            output.send((None, false)).unwrap();
+        }
+        Ok(())
+    }
+}
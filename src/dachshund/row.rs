@@ -4,7 +4,9 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::attributed_graph::Attributes;
 use crate::dachshund::id_types::{EdgeTypeId, GraphId, NodeId, NodeLabel, NodeTypeId};
+use std::any::Any;
 use std::fmt;
 
 ///  Used to keep track of edge row input.
@@ -76,22 +78,26 @@ impl WeightedEdgeRow {
     }
 }
 
+/// used to keep track of row input for attributed graphs: attributes of a node if
+/// target_id is None, and of an edge otherwise. Recovered with `downcast_ref`.
+#[derive(Clone)]
+pub struct AttributedRow {
+    pub graph_id: GraphId,
+    pub source_id: NodeId,
+    pub target_id: Option<NodeId>,
+    pub attributes: Attributes,
+}
+
 /// Used in lieu of a union type. All rows processed by a Transformer
-/// must implement this trait.
-pub trait Row {
+/// must implement this trait. Rows of the built-in kinds are recovered with the as_*
+/// methods, which default to None; rows of any other kind (e.g. added along with a
+/// line processor registered with a `LineProcessorRegistry`) with `downcast_ref`.
+pub trait Row: Send {
     /// this is the key used by each transformer.
     fn get_graph_id(&self) -> GraphId;
-    fn as_edge_row(&self) -> Option<EdgeRow>;
-    fn as_clique_row(&self) -> Option<CliqueRow>;
-    fn as_simple_edge_row(&self) -> Option<SimpleEdgeRow>;
-    fn as_weighted_edge_row(&self) -> Option<WeightedEdgeRow>;
-}
-impl Row for EdgeRow {
-    fn get_graph_id(&self) -> GraphId {
-        self.graph_id
-    }
+    fn as_any(&self) -> &dyn Any;
     fn as_edge_row(&self) -> Option<EdgeRow> {
-        Some(*self)
+        None
     }
     fn as_clique_row(&self) -> Option<CliqueRow> {
         None
@@ -103,54 +109,61 @@ impl Row for EdgeRow {
         None
     }
 }
-impl Row for CliqueRow {
+impl dyn Row {
+    /// returns the row as a T, if it is one.
+    pub fn downcast_ref<T: Row + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+}
+impl Row for EdgeRow {
     fn get_graph_id(&self) -> GraphId {
         self.graph_id
     }
-    fn as_edge_row(&self) -> Option<EdgeRow> {
-        None
+    fn as_any(&self) -> &dyn Any {
+        self
     }
-    fn as_clique_row(&self) -> Option<CliqueRow> {
+    fn as_edge_row(&self) -> Option<EdgeRow> {
         Some(*self)
     }
-    fn as_simple_edge_row(&self) -> Option<SimpleEdgeRow> {
-        None
+}
+impl Row for CliqueRow {
+    fn get_graph_id(&self) -> GraphId {
+        self.graph_id
     }
-    fn as_weighted_edge_row(&self) -> Option<WeightedEdgeRow> {
-        None
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_clique_row(&self) -> Option<CliqueRow> {
+        Some(*self)
     }
 }
 impl Row for SimpleEdgeRow {
     fn get_graph_id(&self) -> GraphId {
         self.graph_id
     }
-    fn as_edge_row(&self) -> Option<EdgeRow> {
-        None
-    }
-    fn as_clique_row(&self) -> Option<CliqueRow> {
-        None
+    fn as_any(&self) -> &dyn Any {
+        self
     }
     fn as_simple_edge_row(&self) -> Option<SimpleEdgeRow> {
         Some(*self)
     }
-    fn as_weighted_edge_row(&self) -> Option<WeightedEdgeRow> {
-        None
-    }
 }
 impl Row for WeightedEdgeRow {
     fn get_graph_id(&self) -> GraphId {
         self.graph_id
     }
-    fn as_edge_row(&self) -> Option<EdgeRow> {
-        None
-    }
-    fn as_clique_row(&self) -> Option<CliqueRow> {
-        None
-    }
-    fn as_simple_edge_row(&self) -> Option<SimpleEdgeRow> {
-        None
+    fn as_any(&self) -> &dyn Any {
+        self
     }
     fn as_weighted_edge_row(&self) -> Option<WeightedEdgeRow> {
         Some(*self)
     }
 }
+impl Row for AttributedRow {
+    fn get_graph_id(&self) -> GraphId {
+        self.graph_id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -5,18 +5,60 @@
  * LICENSE file in the root directory of this source tree.
  */
 use crate::dachshund::candidate::{Candidate, Recipe};
+use crate::dachshund::edge_type_weights::EdgeTypeWeights;
 use crate::dachshund::error::CLQResult;
+use crate::dachshund::id_types::NodeTypeIdInternal;
 use crate::dachshund::node::Node;
 use crate::dachshund::search_problem::SearchProblem;
 use crate::dachshund::typed_graph::LabeledGraph;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::rc::Rc;
 
+/// The components of a candidate's score, as returned by `Scorer::explain`. The score is
+/// (size_term + cliqueness_term) * global_thresh_factor * local_thresh_factor.
+pub struct ScoreExplanation {
+    // grows with the number of nodes of each type (higher with more diverse types).
+    pub size_term: f32,
+    pub cliqueness: f32,
+    // cliqueness weighted by alpha.
+    pub cliqueness_term: f32,
+    // 0 if the candidate is less dense than global_thresh, 1 otherwise.
+    pub global_thresh_factor: f32,
+    // 0 if some core node is less dense than local_thresh (or some non-core node less
+    // dense than the local threshold of its type), 1 otherwise.
+    pub local_thresh_factor: f32,
+    // density of the least dense core node (see `Candidate::get_local_densities`).
+    pub min_local_density: Option<f32>,
+    pub score: f32,
+}
+impl fmt::Display for ScoreExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "score {} = (size {} + cliqueness {} * alpha = {}) * global thresh {} * local thresh {}",
+            self.score,
+            self.size_term,
+            self.cliqueness,
+            self.cliqueness_term,
+            self.global_thresh_factor,
+            self.local_thresh_factor,
+        )?;
+        if let Some(density) = self.min_local_density {
+            write!(f, " (min local density {})", density)?;
+        }
+        Ok(())
+    }
+}
+
 /// Used to compute the "cliqueness" score of a particular candidate.
 pub struct Scorer {
     num_non_core_types: usize,
     alpha: f32,
     global_thresh: Option<f32>,
     local_thresh: Option<f32>,
+    non_core_local_thresh: BTreeMap<NodeTypeIdInternal, f32>,
+    edge_type_weights: Option<Rc<EdgeTypeWeights>>,
 }
 
 impl Scorer {
@@ -29,12 +71,16 @@ impl Scorer {
     /// valid (quasi-)cliques.
     /// - `local_thresh`: If provided, each node in the candidate must have at least `local_thresh`
     /// proportion of ties to other nodes in the candidate, for the candidate to be considered valid.
+    /// - `non_core_local_thresh`: The same, for non-core nodes of some types (by type id).
+    /// - `edge_type_weights`: If provided, how much ties of each edge type count in cliqueness.
     pub fn new(num_non_core_types: usize, search_problem: &Rc<SearchProblem>) -> Scorer {
         Scorer {
             num_non_core_types,
             alpha: search_problem.alpha,
             global_thresh: search_problem.global_thresh,
             local_thresh: search_problem.local_thresh,
+            non_core_local_thresh: search_problem.non_core_local_thresh.clone(),
+            edge_type_weights: search_problem.edge_type_weights.clone(),
         }
     }
 
@@ -47,20 +93,54 @@ impl Scorer {
         if candidate.core_ids.is_empty() || candidate.non_core_ids.is_empty() {
             return Ok(-1.0);
         }
+        Ok(self.explain(candidate)?.score)
+    }
+
+    /// breaks the score of a candidate down into its components, to see which of them
+    /// dominates the objective for given alpha, global_thresh and local_thresh.
+    pub fn explain<TGraph: LabeledGraph<NodeType = Node>>(
+        &self,
+        candidate: &mut Candidate<TGraph>,
+    ) -> CLQResult<ScoreExplanation> {
+        let min_local_density = candidate
+            .get_local_densities()
+            .values()
+            .cloned()
+            .reduce(f32::min);
+        // degenerate case where there are no edges.
+        if candidate.core_ids.is_empty() || candidate.non_core_ids.is_empty() {
+            return Ok(ScoreExplanation {
+                size_term: 0.0,
+                cliqueness: 0.0,
+                cliqueness_term: 0.0,
+                global_thresh_factor: 0.0,
+                local_thresh_factor: 0.0,
+                min_local_density,
+                score: -1.0,
+            });
+        }
         // // the more core nodes we have, the better
         // // the more diverse the non-core types, the better
-        let mut score = self.get_diversity_score(candidate)?;
+        let size_term = self.get_diversity_score(candidate)?;
 
         // the denser the ties, the better
         let cliqueness: f32 = candidate.get_cliqueness()?;
-        score += cliqueness * self.alpha;
+        let cliqueness_term = cliqueness * self.alpha;
 
         // enforce a minimum density threshold on cliqueness (1.0 for true cliques)
-        score *= self.get_global_thresh_score(cliqueness);
+        let global_thresh_factor = self.get_global_thresh_score(cliqueness);
 
         // enforce a minimum density threshold for each core node.
-        score *= self.get_local_thresh_score(candidate);
-        Ok(score)
+        let local_thresh_factor = self.get_local_thresh_score(candidate);
+        Ok(ScoreExplanation {
+            size_term,
+            cliqueness,
+            cliqueness_term,
+            global_thresh_factor,
+            local_thresh_factor,
+            min_local_density,
+            score: (size_term + cliqueness_term) * global_thresh_factor * local_thresh_factor,
+        })
     }
 
     pub fn score_recipe<TGraph: LabeledGraph<NodeType = Node>>(
@@ -110,6 +190,13 @@ impl Scorer {
                 score = 0.0;
             }
         }
+        // enforce a minimum density threshold for non-core nodes of some types.
+        if !self.non_core_local_thresh.is_empty()
+            && !candidate
+                .non_core_local_thresh_score_with_node_at_least(&self.non_core_local_thresh, node)
+        {
+            score = 0.0;
+        }
         Ok(score)
     }
 
@@ -117,21 +204,31 @@ impl Scorer {
         self.num_non_core_types
     }
 
+    pub fn get_edge_type_weights(&self) -> Option<Rc<EdgeTypeWeights>> {
+        self.edge_type_weights.clone()
+    }
+
     pub fn get_global_thresh_score(&self, cliqueness: f32) -> f32 {
         match self.global_thresh {
             Some(n) => (cliqueness >= n) as i64 as f32,
             None => 1.0,
         }
     }
-    // used to ensure that each core node has at least % of ties with non-core nodes.
+    // used to ensure that each core node has at least % of ties with non-core nodes
+    // (and each non-core node of a type with a local threshold, % of ties with core nodes).
     pub fn get_local_thresh_score<TGraph: LabeledGraph<NodeType = Node>>(
         &self,
         candidate: &mut Candidate<TGraph>,
     ) -> f32 {
-        match self.local_thresh {
+        let core_score = match self.local_thresh {
             Some(thresh) => candidate.local_thresh_score_at_least(thresh) as i64 as f32,
             None => 1.0,
+        };
+        if core_score > 0.0 && !self.non_core_local_thresh.is_empty() {
+            return candidate.non_core_local_thresh_score_at_least(&self.non_core_local_thresh)
+                as i64 as f32;
         }
+        core_score
     }
 
     /// returns a diversity score that increases with number of nodes and
@@ -4,15 +4,46 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::edge_type_weights::EdgeTypeWeights;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::{EdgeTypeId, NodeTypeIdInternal};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Relaxations of cliques which quasi-clique search (see `QuasiCliqueScorer`) can look for,
+/// instead of sets of nodes meeting density thresholds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CliqueRelaxation {
+    // every node is tied to all but at most k of the nodes (itself included).
+    KPlex(usize),
+    // every two nodes are joined by a path of at most k ties among the nodes.
+    KClub(usize),
+}
+
+#[derive(Clone)]
 pub struct SearchProblem {
     pub beam_size: usize,
     pub alpha: f32,
     pub global_thresh: Option<f32>,
+    // applies to core nodes.
     pub local_thresh: Option<f32>,
+    // local thresholds for non-core nodes, by non-core type (types without one have none).
+    pub non_core_local_thresh: BTreeMap<NodeTypeIdInternal, f32>,
     pub num_to_search: usize,
     pub num_epochs: usize,
     pub max_repeated_prior_scores: usize,
     pub min_degree: usize,
+    // min degree of non-core nodes when pruning, if different from min_degree (which then
+    // only applies to core nodes).
+    pub non_core_min_degree: Option<usize>,
+    // min number of neighbors of core nodes through edges of each type (types without one
+    // have none), enforced when pruning, in addition to min_degree.
+    pub edge_type_min_degree: BTreeMap<EdgeTypeId, usize>,
+    pub edge_type_weights: Option<Rc<EdgeTypeWeights>>,
+    // cap on the estimated memory used by the candidates in the beam, in bytes.
+    pub max_beam_memory: Option<usize>,
+    // for unipartite graphs, replaces global_thresh and local_thresh.
+    pub clique_relaxation: Option<CliqueRelaxation>,
 }
 impl SearchProblem {
     pub fn new(
@@ -30,10 +61,119 @@ impl SearchProblem {
             alpha,
             global_thresh,
             local_thresh,
+            non_core_local_thresh: BTreeMap::new(),
             num_to_search,
             num_epochs,
             max_repeated_prior_scores,
             min_degree,
+            non_core_min_degree: None,
+            edge_type_min_degree: BTreeMap::new(),
+            edge_type_weights: None,
+            max_beam_memory: None,
+            clique_relaxation: None,
+        }
+    }
+
+    /// weighs ties by their edge type when computing cliqueness (by default, every tie
+    /// counts 1).
+    pub fn with_edge_type_weights(mut self, edge_type_weights: EdgeTypeWeights) -> Self {
+        self.edge_type_weights = Some(Rc::new(edge_type_weights));
+        self
+    }
+
+    /// requires each non-core node of the given types to have at least the given proportion
+    /// of its possible ties with core nodes of the candidate, as some relation types are
+    /// naturally sparser than others.
+    pub fn with_non_core_local_thresh(
+        mut self,
+        non_core_local_thresh: BTreeMap<NodeTypeIdInternal, f32>,
+    ) -> Self {
+        self.non_core_local_thresh = non_core_local_thresh;
+        self
+    }
+
+    /// requires non-core nodes to have at least the given number of neighbors (beta), with
+    /// core nodes required to have min_degree (alpha), for graphs to be pruned to their
+    /// (alpha, beta)-core.
+    pub fn with_non_core_min_degree(mut self, non_core_min_degree: usize) -> Self {
+        self.non_core_min_degree = Some(non_core_min_degree);
+        self
+    }
+
+    /// requires core nodes to have at least the given number of neighbors through edges
+    /// of each of the given types (e.g. at least 2 "published" ties), for graphs to be
+    /// pruned accordingly.
+    pub fn with_edge_type_min_degree(
+        mut self,
+        edge_type_min_degree: BTreeMap<EdgeTypeId, usize>,
+    ) -> Self {
+        self.edge_type_min_degree = edge_type_min_degree;
+        self
+    }
+
+    /// caps the estimated memory used by the candidates in the beam (see
+    /// `Candidate::estimated_memory_bytes`) at the given number of bytes: the beam keeps
+    /// fewer candidates when they would not fit, and the search stops with a
+    /// `BeamMemoryExceeded` error if not even one does.
+    pub fn with_max_beam_memory(mut self, max_beam_memory: usize) -> Self {
+        self.max_beam_memory = Some(max_beam_memory);
+        self
+    }
+
+    /// searches unipartite graphs for k-plexes or k-clubs rather than for quasi-cliques
+    /// meeting the global and local thresholds.
+    pub fn with_clique_relaxation(mut self, clique_relaxation: CliqueRelaxation) -> Self {
+        self.clique_relaxation = Some(clique_relaxation);
+        self
+    }
+
+    /// checks that the search parameters are coherent, returning an
+    /// `InvalidSearchProblem` error listing every problem found otherwise.
+    /// A search with no epochs only makes sense in debug mode, where it is used
+    /// to inspect the initial candidates.
+    pub fn validate(&self, debug: bool) -> CLQResult<()> {
+        let mut problems: Vec<String> = Vec::new();
+        if self.beam_size == 0 {
+            problems.push("beam_size must be at least 1".to_string());
+        }
+        if self.num_to_search == 0 {
+            problems.push("num_to_search must be at least 1".to_string());
+        }
+        if self.num_epochs == 0 && !debug {
+            problems.push("epochs must be at least 1 (0 is only allowed in debug mode)".into());
+        }
+        if self.max_beam_memory == Some(0) {
+            problems.push("max_beam_memory must be at least 1 byte".to_string());
+        }
+        match self.clique_relaxation {
+            Some(CliqueRelaxation::KPlex(0)) => problems.push("k_plex must be at least 1".into()),
+            Some(CliqueRelaxation::KClub(0)) => problems.push("k_club must be at least 1".into()),
+            _ => (),
+        }
+        if !self.alpha.is_finite() {
+            problems.push(format!("alpha must be a finite number, got {}", self.alpha));
+        }
+        let non_core_threshs = self
+            .non_core_local_thresh
+            .iter()
+            .map(|(type_id, thresh)| (format!("local_thresh for type {type_id}"), Some(*thresh)));
+        for (name, thresh) in [
+            ("global_thresh".to_string(), self.global_thresh),
+            ("local_thresh".to_string(), self.local_thresh),
+        ]
+        .into_iter()
+        .chain(non_core_threshs)
+        {
+            if let Some(value) = thresh {
+                if !(0.0..=1.0).contains(&value) {
+                    problems.push(format!("{name} must be between 0 and 1, got {value}"));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(CLQError::InvalidSearchProblem(problems.join("; ")))
         }
     }
 }
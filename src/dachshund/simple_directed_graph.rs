@@ -5,17 +5,30 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate fxhash;
+use crate::dachshund::algorithms::assortativity::Assortativity;
 use crate::dachshund::algorithms::brokerage::Brokerage;
 use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsDirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityDirected};
+use crate::dachshund::algorithms::coreness::DirectedCoreness;
+use crate::dachshund::algorithms::directed_transitivity::DirectedTransitivity;
+use crate::dachshund::algorithms::epidemics::Epidemics;
+use crate::dachshund::algorithms::flow::MaxFlow;
+use crate::dachshund::algorithms::graph_diff::GraphDiff;
+use crate::dachshund::algorithms::hits::Hits;
+use crate::dachshund::algorithms::influence_maximization::InfluenceMaximization;
+use crate::dachshund::algorithms::layout::ForceLayout;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::random_walks::RandomWalks;
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
+use crate::dachshund::algorithms::visualization::Visualization;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase, SimpleDirectedNode};
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::hash_map::{Keys, Values};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 pub trait DirectedGraph: GraphBase
 where
@@ -49,14 +62,6 @@ pub struct SimpleDirectedGraph {
 impl GraphBase for SimpleDirectedGraph {
     type NodeType = SimpleDirectedNode;
 
-    /// core and non-core IDs are the same for a `SimpleDirectedGraph`.
-    fn get_core_ids(&self) -> &Vec<NodeId> {
-        &self.ids
-    }
-    /// core and non-core IDs are the same for a `SimpleDirectedGraph`.
-    fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
-        Some(&self.ids)
-    }
     fn get_ids_iter(&self) -> Keys<NodeId, SimpleDirectedNode> {
         self.nodes.keys()
     }
@@ -91,8 +96,96 @@ impl GraphBase for SimpleDirectedGraph {
     }
 }
 impl DirectedGraph for SimpleDirectedGraph {}
+impl Assortativity for SimpleDirectedGraph {}
 impl Brokerage for SimpleDirectedGraph {}
 impl ConnectedComponents for SimpleDirectedGraph {}
 impl ConnectedComponentsDirected for SimpleDirectedGraph {}
 impl Connectivity for SimpleDirectedGraph {}
 impl ConnectivityDirected for SimpleDirectedGraph {}
+impl DirectedCoreness for SimpleDirectedGraph {}
+impl DirectedTransitivity for SimpleDirectedGraph {}
+impl Epidemics for SimpleDirectedGraph {
+    // infections only spread along out-edges.
+    fn get_epidemic_edges(&self, node: &SimpleDirectedNode) -> Vec<NodeId> {
+        node.get_out_neighbors()
+            .map(|e| e.get_neighbor_id())
+            .collect()
+    }
+}
+impl ForceLayout for SimpleDirectedGraph {}
+impl Visualization for SimpleDirectedGraph {}
+impl Hits for SimpleDirectedGraph {}
+impl Subgraphs for SimpleDirectedGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        let filter = |ids: &BTreeSet<NodeId>| -> BTreeSet<NodeId> {
+            ids.iter()
+                .filter(|id| nodes.contains(id))
+                .cloned()
+                .collect()
+        };
+        SimpleDirectedGraph {
+            ids: self
+                .ids
+                .iter()
+                .filter(|id| nodes.contains(id))
+                .cloned()
+                .collect(),
+            nodes: self
+                .nodes
+                .values()
+                .filter(|node| nodes.contains(&node.node_id))
+                .map(|node| {
+                    let subgraph_node = SimpleDirectedNode {
+                        node_id: node.node_id,
+                        in_neighbors: filter(&node.in_neighbors),
+                        out_neighbors: filter(&node.out_neighbors),
+                    };
+                    (node.node_id, subgraph_node)
+                })
+                .collect(),
+        }
+    }
+}
+impl InfluenceMaximization for SimpleDirectedGraph {
+    // influence only spreads along out-edges.
+    fn get_influence_edges(&self, node: &SimpleDirectedNode) -> Vec<NodeId> {
+        node.get_out_neighbors()
+            .map(|e| e.get_neighbor_id())
+            .collect()
+    }
+}
+impl GraphDiff for SimpleDirectedGraph {
+    // edges keep their direction.
+    fn get_diff_edges(&self) -> FxHashSet<(NodeId, NodeId)> {
+        self.get_nodes_iter()
+            .flat_map(|node| {
+                node.get_out_neighbors()
+                    .map(move |e| (node.get_id(), e.get_neighbor_id()))
+            })
+            .collect()
+    }
+}
+impl MaxFlow for SimpleDirectedGraph {
+    // flow only goes along out-edges.
+    fn get_flow_arcs(&self, node: &SimpleDirectedNode) -> Vec<(NodeId, f64)> {
+        node.get_out_neighbors()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+}
+impl PageRank for SimpleDirectedGraph {
+    // rank only flows along out-edges.
+    fn get_pagerank_out_edges(&self, node: &SimpleDirectedNode) -> Vec<(NodeId, f64)> {
+        node.get_out_neighbors()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+}
+impl RandomWalks for SimpleDirectedGraph {
+    // walks only step along out-edges.
+    fn get_walk_steps(&self, node: &SimpleDirectedNode) -> Vec<(NodeId, f64)> {
+        node.get_out_neighbors()
+            .map(|e| (e.get_neighbor_id(), 1.0))
+            .collect()
+    }
+}
@@ -12,75 +12,300 @@ use crate::dachshund::algorithms::betweenness::Betweenness;
 use crate::dachshund::algorithms::clustering::Clustering;
 use crate::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
 use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::degree_distribution::{get_histogram, get_power_law_fit};
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::algorithms::graphlets::Graphlets;
+use crate::dachshund::algorithms::leiden::Leiden;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::rewiring::Rewiring;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::ordered_output::OrderedOutput;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
 use fxhash::FxHashSet;
-use rand::seq::SliceRandom;
+use rand::prelude::*;
 use rayon::{ThreadPool, ThreadPoolBuilder};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 pub struct SimpleTransformer {
     batch: Vec<SimpleEdgeRow>,
     line_processor: Arc<LineProcessor>,
+    // number of degree-preserving randomizations z-scores are computed against (none if 0).
+    num_null_models: usize,
+}
+/// How SimpleParallelTransformer uses its thread pool: either one graph per thread
+/// (best with many small graphs), or one graph at a time with its statistics computed
+/// concurrently (best with few large graphs).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Parallelism {
+    PerGraph,
+    PerAlgorithm,
+}
+impl FromStr for Parallelism {
+    type Err = CLQError;
+    fn from_str(s: &str) -> CLQResult<Self> {
+        match s {
+            "graph" => Ok(Parallelism::PerGraph),
+            "algorithm" => Ok(Parallelism::PerAlgorithm),
+            _ => Err(CLQError::from(format!(
+                "Unknown parallelism: {s} (expected graph or algorithm)"
+            ))),
+        }
+    }
 }
 pub struct SimpleParallelTransformer {
     batch: Vec<SimpleEdgeRow>,
     pool: ThreadPool,
+    parallelism: Parallelism,
+    // if set, graphs processed concurrently are output in input order.
+    ordered_output: Option<Arc<OrderedOutput>>,
     line_processor: Arc<LineProcessor>,
+    num_null_models: usize,
 }
+// Accuracy of the average clustering coefficient reported in the stats: within
+// CLUST_COEF_EPSILON of the true value with probability 1 - CLUST_COEF_DELTA.
+// Graphs with few wedges are computed exactly.
+pub const CLUST_COEF_EPSILON: f64 = 0.01;
+pub const CLUST_COEF_DELTA: f64 = 0.01;
+// Quantiles of the per-node degree and clustering coefficient distributions reported
+// in the stats (as {name}_p10, {name}_p50, {name}_p90).
+pub const STATS_QUANTILES: [f64; 3] = [0.1, 0.5, 0.9];
+// Double edge swaps per edge used to randomize graphs for null-model z-scores.
+pub const NULL_MODEL_SWAPS_PER_EDGE: usize = 10;
+
+// Runs a and b, concurrently (on the current rayon thread pool) if parallel is set.
+fn join_if<A, B, RA, RB>(parallel: bool, a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    if parallel {
+        rayon::join(a, b)
+    } else {
+        (a(), b())
+    }
+}
+
 pub trait GraphStatsTransformerBase: TransformerBase {
-    fn compute_graph_stats_json(graph: &SimpleUndirectedGraph) -> String {
-        let conn_comp = graph.get_connected_components();
-        let largest_cc = conn_comp.iter().max_by_key(|x| x.len()).unwrap().to_vec();
-        let size_of_largest_cc = largest_cc.len();
-        let sources: Vec<NodeId> = largest_cc
-            .choose_multiple(&mut rand::thread_rng(), 100)
-            .copied()
+    // Nearest-rank quantiles of values, one per entry in qs (each in [0, 1]).
+    // Returns None for every quantile if values is empty.
+    fn compute_quantiles<T: PartialOrd + Copy>(values: &mut [T], qs: &[f64]) -> Vec<Option<T>> {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = values.len();
+        qs.iter()
+            .map(|q| match n {
+                0 => None,
+                _ => {
+                    let rank = (q * n as f64).ceil() as usize;
+                    Some(values[rank.clamp(1, n) - 1])
+                }
+            })
+            .collect()
+    }
+    // Statistics which null-model z-scores are reported for, as (name, value) pairs: the
+    // (exact) average clustering coefficient, and the modularity of Leiden communities.
+    fn compute_null_model_stats(graph: &SimpleUndirectedGraph) -> [(&'static str, f64); 2] {
+        let (communities, _) = graph.get_leiden_communities();
+        [
+            ("clust_coef", graph.get_avg_clustering()),
+            ("modularity", graph.get_modularity(&communities)),
+        ]
+    }
+    // Z-scores of the statistics in compute_null_model_stats, relative to their values on
+    // num_null_models random graphs with the same degrees (see Rewiring), as a JSON object
+    // with keys {name}_zscore. A z-score is null if fewer than 2 random graphs are drawn,
+    // or if the statistic takes the same value on all of them.
+    fn compute_null_model_zscores<R: Rng>(
+        graph: &SimpleUndirectedGraph,
+        num_null_models: usize,
+        rng: &mut R,
+    ) -> Value {
+        let observed = Self::compute_null_model_stats(graph);
+        let samples: Vec<[(&'static str, f64); 2]> = (0..num_null_models)
+            .map(|_| {
+                let random_graph = graph.get_rewired_graph_with_rng(NULL_MODEL_SWAPS_PER_EDGE, rng);
+                Self::compute_null_model_stats(&random_graph)
+            })
             .collect();
-        let betcent = graph
-            .get_node_betweenness_starting_from_sources(&sources, false, Some(largest_cc))
-            .unwrap();
-        let evcent = graph.get_eigenvector_centrality(0.001, 1000);
+        let n = samples.len() as f64;
+        let mut zscores = serde_json::Map::new();
+        for (i, (name, value)) in observed.iter().enumerate() {
+            let mean = samples.iter().map(|x| x[i].1).sum::<f64>() / n;
+            let variance = samples.iter().map(|x| (x[i].1 - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let zscore = if samples.len() > 1 && variance > 0.0 {
+                Some(((value - mean) / variance.sqrt() * 1000.0).floor() / 1000.0)
+            } else {
+                None
+            };
+            zscores.insert(format!("{name}_zscore"), json!(zscore));
+        }
+        Value::Object(zscores)
+    }
+    fn compute_graph_stats_json(graph: &SimpleUndirectedGraph) -> String {
+        Self::compute_graph_stats_json_with_parallelism(graph, false)
+    }
+    // If parallel, the (independent) statistics are computed concurrently on the
+    // current rayon thread pool, otherwise one after the other.
+    fn compute_graph_stats_json_with_parallelism(
+        graph: &SimpleUndirectedGraph,
+        parallel: bool,
+    ) -> String {
+        Self::compute_graph_stats_json_with_null_models(graph, parallel, 0)
+    }
+    // Same as compute_graph_stats_json_with_parallelism, also reporting z-scores against
+    // num_null_models degree-preserving randomizations (see compute_null_model_zscores)
+    // if num_null_models is positive.
+    fn compute_graph_stats_json_with_null_models(
+        graph: &SimpleUndirectedGraph,
+        parallel: bool,
+        num_null_models: usize,
+    ) -> String {
+        let get_centralities = || {
+            let conn_comp = graph.get_connected_components();
+            let largest_cc = conn_comp.iter().max_by_key(|x| x.len()).unwrap().to_vec();
+            let size_of_largest_cc = largest_cc.len();
+            let sources: Vec<NodeId> = largest_cc
+                .choose_multiple(&mut rand::thread_rng(), 100)
+                .copied()
+                .collect();
+            let betcent = graph
+                .get_node_betweenness_starting_from_sources(&sources, false, Some(largest_cc))
+                .unwrap();
+            (conn_comp.len(), size_of_largest_cc, betcent)
+        };
+        let get_spectral = || {
+            join_if(
+                parallel,
+                || graph.get_eigenvector_centrality(0.001, 1000),
+                || graph.get_pagerank(0.85, 1e-6, 100),
+            )
+        };
+        let get_clust_coef = || {
+            graph.get_avg_clustering_with_accuracy(
+                CLUST_COEF_EPSILON,
+                CLUST_COEF_DELTA,
+                &mut rand::thread_rng(),
+            )
+        };
+        // degrees and local clustering coefficients, gathered in a single pass over the nodes.
+        let get_node_stats = || {
+            let mut degrees: Vec<usize> = Vec::with_capacity(graph.count_nodes());
+            let mut clust_coefs: Vec<f64> = Vec::with_capacity(graph.count_nodes());
+            for id in graph.get_ids_iter() {
+                degrees.push(graph.get_node_degree(*id));
+                if let Some(coef) = graph.get_clustering_coefficient(*id) {
+                    clust_coefs.push(coef);
+                }
+            }
+            (degrees, clust_coefs)
+        };
+        // number of 2, 4, 8, 16-cores and 3, 5, 9, 17-trusses, each computed from the last.
+        let get_cores_and_trusses = || {
+            let mut removed: FxHashSet<NodeId> = FxHashSet::default();
+            let k_cores_2 = graph._get_k_cores(2, &mut removed).len();
+            let k_trusses_3 = graph._get_k_trusses(3, &removed).1.len();
+            let k_cores_4 = graph._get_k_cores(4, &mut removed).len();
+            let k_trusses_5 = graph._get_k_trusses(5, &removed).1.len();
+            let k_cores_8 = graph._get_k_cores(8, &mut removed).len();
+            let k_trusses_9 = graph._get_k_trusses(9, &removed).1.len();
+            let k_cores_16 = graph._get_k_cores(16, &mut removed).len();
+            let k_trusses_17 = graph._get_k_trusses(17, &removed).1.len();
+            [
+                k_cores_2,
+                k_cores_4,
+                k_cores_8,
+                k_cores_16,
+                k_trusses_3,
+                k_trusses_5,
+                k_trusses_9,
+                k_trusses_17,
+            ]
+        };
+        // induced 3- and 4-node graphlet counts, as a motif signature.
+        let get_motifs = || graph.get_graphlet_counts();
 
-        let mut removed: FxHashSet<NodeId> = FxHashSet::default();
-        let k_cores_2 = graph._get_k_cores(2, &mut removed);
-        let k_trusses_3 = graph._get_k_trusses(3, &removed).1;
-        let k_cores_4 = graph._get_k_cores(4, &mut removed);
-        let k_trusses_5 = graph._get_k_trusses(5, &removed).1;
-        let k_cores_8 = graph._get_k_cores(8, &mut removed);
-        let k_trusses_9 = graph._get_k_trusses(9, &removed).1;
-        let k_cores_16 = graph._get_k_cores(16, &mut removed);
-        let k_trusses_17 = graph._get_k_trusses(17, &removed).1;
+        let (
+            ((num_connected_components, size_of_largest_cc, betcent), (evcent, pagerank)),
+            ((clust_coef, (mut degrees, mut clust_coefs)), (cores_and_trusses, motifs)),
+        ) = join_if(
+            parallel,
+            || join_if(parallel, get_centralities, get_spectral),
+            || {
+                join_if(
+                    parallel,
+                    || join_if(parallel, get_clust_coef, get_node_stats),
+                    || join_if(parallel, get_cores_and_trusses, get_motifs),
+                )
+            },
+        );
+        let degree_histogram: Vec<(usize, usize)> = get_histogram(&degrees).into_iter().collect();
+        let power_law_fit = get_power_law_fit(&degrees);
+        let degree_quantiles = Self::compute_quantiles(&mut degrees, &STATS_QUANTILES);
+        let clust_coef_quantiles: Vec<Option<f64>> =
+            Self::compute_quantiles(&mut clust_coefs, &STATS_QUANTILES)
+                .into_iter()
+                .map(|x| x.map(|coef| (coef * 1000.0).floor() / 1000.0))
+                .collect();
 
-        json!({
+        let mut stats = json!({
             "num_edges": graph.count_edges(),
-            "num_2_cores": k_cores_2.len(),
-            "num_4_cores": k_cores_4.len(),
-            "num_8_cores": k_cores_8.len(),
-            "num_16_cores": k_cores_16.len(),
-            "num_3_trusses": k_trusses_3.len(),
-            "num_5_trusses": k_trusses_5.len(),
-            "num_9_trusses": k_trusses_9.len(),
-            "num_17_trusses": k_trusses_17.len(),
-            "num_connected_components": conn_comp.len(),
+            "num_2_cores": cores_and_trusses[0],
+            "num_4_cores": cores_and_trusses[1],
+            "num_8_cores": cores_and_trusses[2],
+            "num_16_cores": cores_and_trusses[3],
+            "num_3_trusses": cores_and_trusses[4],
+            "num_5_trusses": cores_and_trusses[5],
+            "num_9_trusses": cores_and_trusses[6],
+            "num_17_trusses": cores_and_trusses[7],
+            "num_3_paths": motifs[1],
+            "num_triangles": motifs[2],
+            "num_4_paths": motifs[3],
+            "num_3_stars": motifs[4],
+            "num_4_cycles": motifs[5],
+            "num_paws": motifs[6],
+            "num_diamonds": motifs[7],
+            "num_4_cliques": motifs[8],
+            "num_connected_components": num_connected_components,
             "size_of_largest_cc": size_of_largest_cc,
             "bet_cent": (Iterator::sum::<f64>(betcent.values()) /
                 (betcent.len() as f64) * 1000.0).floor() / 1000.0,
             "evcent": (Iterator::sum::<f64>(evcent.values()) /
                 (evcent.len() as f64) * 1000.0).floor() / 1000.0,
-            "clust_coef": (graph.get_avg_clustering() * 1000.0).floor() / 1000.0,
-        })
-        .to_string()
+            "max_pagerank": (pagerank.values().cloned().fold(0.0, f64::max) *
+                1000.0).floor() / 1000.0,
+            "clust_coef": (clust_coef * 1000.0).floor() / 1000.0,
+            "clust_coef_p10": clust_coef_quantiles[0],
+            "clust_coef_p50": clust_coef_quantiles[1],
+            "clust_coef_p90": clust_coef_quantiles[2],
+            "degree_p10": degree_quantiles[0],
+            "degree_p50": degree_quantiles[1],
+            "degree_p90": degree_quantiles[2],
+            "degree_histogram": degree_histogram,
+            "degree_power_law_alpha": power_law_fit.map(|fit|
+                (fit.alpha * 1000.0).floor() / 1000.0),
+            "degree_power_law_x_min": power_law_fit.map(|fit| fit.x_min),
+            "degree_power_law_ks": power_law_fit.map(|fit|
+                (fit.ks_statistic * 1000.0).floor() / 1000.0),
+        });
+        if num_null_models > 0 {
+            let zscores =
+                Self::compute_null_model_zscores(graph, num_null_models, &mut rand::thread_rng());
+            if let (Value::Object(stats), Value::Object(zscores)) = (&mut stats, zscores) {
+                stats.extend(zscores);
+            }
+        }
+        stats.to_string()
     }
 }
 impl SimpleTransformer {
@@ -88,8 +313,24 @@ impl SimpleTransformer {
         Self {
             batch: Vec::new(),
             line_processor: Arc::new(LineProcessor::new()),
+            num_null_models: 0,
+        }
+    }
+    /// Also reports z-scores of some statistics against num_null_models degree-preserving
+    /// randomizations of each graph (see compute_null_model_zscores).
+    pub fn with_null_models(self, num_null_models: usize) -> Self {
+        Self {
+            num_null_models,
+            ..self
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`). Statistics don't name nodes, so labels are
+    /// only interned to build graphs.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
 }
 impl Default for SimpleTransformer {
     fn default() -> Self {
@@ -102,8 +343,51 @@ impl SimpleParallelTransformer {
             batch: Vec::new(),
             line_processor: Arc::new(LineProcessor::new()),
             pool: ThreadPoolBuilder::new().build().unwrap(),
+            parallelism: Parallelism::PerGraph,
+            ordered_output: None,
+            num_null_models: 0,
+        }
+    }
+    /// num_threads of 0 lets rayon pick (one thread per CPU). If reorder_window is provided,
+    /// output follows input order, with at most that many graphs in flight at once
+    /// (graphs are always output in input order with PerAlgorithm parallelism).
+    pub fn with_threads(
+        num_threads: usize,
+        parallelism: Parallelism,
+        reorder_window: Option<usize>,
+    ) -> CLQResult<Self> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|err| CLQError::from(format!("Could not build thread pool: {err}")))?;
+        let ordered_output = match reorder_window {
+            Some(window) => Some(Arc::new(OrderedOutput::new(window)?)),
+            None => None,
+        };
+        Ok(Self {
+            batch: Vec::new(),
+            line_processor: Arc::new(LineProcessor::new()),
+            pool,
+            parallelism,
+            ordered_output,
+            num_null_models: 0,
+        })
+    }
+    /// Also reports z-scores of some statistics against num_null_models degree-preserving
+    /// randomizations of each graph (see compute_null_model_zscores).
+    pub fn with_null_models(self, num_null_models: usize) -> Self {
+        Self {
+            num_null_models,
+            ..self
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`). Statistics don't name nodes, so labels are
+    /// only interned to build graphs.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
 }
 impl Default for SimpleParallelTransformer {
     fn default() -> Self {
@@ -131,7 +415,8 @@ impl TransformerBase for SimpleTransformer {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleUndirectedGraphBuilder {};
         let graph = builder.from_vector(tuples)?;
-        let stats = Self::compute_graph_stats_json(&graph);
+        let stats =
+            Self::compute_graph_stats_json_with_null_models(&graph, false, self.num_null_models);
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
@@ -158,15 +443,39 @@ impl TransformerBase for SimpleParallelTransformer {
         output: &Sender<(Option<String>, bool)>,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
+        if self.parallelism == Parallelism::PerAlgorithm {
+            let graph = SimpleUndirectedGraphBuilder {}.from_vector(tuples)?;
+            let num_null_models = self.num_null_models;
+            let stats = self.pool.install(|| {
+                Self::compute_graph_stats_json_with_null_models(&graph, true, num_null_models)
+            });
+            let original_id = self
+                .line_processor
+                .get_original_id(graph_id.value() as usize);
+            output
+                .send((Some(format!("{original_id}\t{stats}")), false))
+                .unwrap();
+            return Ok(());
+        }
         let output_clone = output.clone();
         let line_processor = self.line_processor.clone();
+        let ordered_output = self.ordered_output.clone();
+        let num_null_models = self.num_null_models;
+        // reserve a turn before spawning, so that the window bounds the graphs in flight.
+        let seq: Option<usize> = ordered_output.as_ref().map(|x| x.reserve());
         self.pool.spawn(move || {
             let mut builder = SimpleUndirectedGraphBuilder {};
             let graph = builder.from_vector(tuples).unwrap();
-            let stats = Self::compute_graph_stats_json(&graph);
+            let stats =
+                Self::compute_graph_stats_json_with_null_models(&graph, false, num_null_models);
             let original_id = line_processor.get_original_id(graph_id.value() as usize);
             let line: String = format!("{}\t{}", original_id, stats);
-            output_clone.send((Some(line), false)).unwrap();
+            match (ordered_output, seq) {
+                (Some(ordered_output), Some(seq)) => {
+                    ordered_output.send(seq, Some(line), &output_clone)
+                }
+                _ => output_clone.send((Some(line), false)).unwrap(),
+            }
         });
         Ok(())
     }
@@ -7,23 +7,51 @@
 extern crate fxhash;
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use crate::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use crate::dachshund::algorithms::assortativity::Assortativity;
 use crate::dachshund::algorithms::betweenness::Betweenness;
 use crate::dachshund::algorithms::clustering::Clustering;
 use crate::dachshund::algorithms::cnm_communities::CNMCommunities;
+use crate::dachshund::algorithms::coarsening::Coarsening;
 use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsUndirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::degree_distribution::DegreeDistribution;
+use crate::dachshund::algorithms::dot::Dot;
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use crate::dachshund::algorithms::epidemics::Epidemics;
+use crate::dachshund::algorithms::flow::MaxFlow;
+use crate::dachshund::algorithms::gexf::Gexf;
+use crate::dachshund::algorithms::graph_diff::GraphDiff;
+use crate::dachshund::algorithms::graphlets::Graphlets;
+use crate::dachshund::algorithms::influence_maximization::InfluenceMaximization;
 use crate::dachshund::algorithms::k_peaks::KPeaks;
+use crate::dachshund::algorithms::label_propagation::LabelPropagation;
 use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::layout::ForceLayout;
+use crate::dachshund::algorithms::leiden::Leiden;
+use crate::dachshund::algorithms::link_prediction::LinkPrediction;
+use crate::dachshund::algorithms::local_community::LocalCommunity;
+use crate::dachshund::algorithms::max_clique::MaxClique;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::partitioning::Partitioning;
+use crate::dachshund::algorithms::random_walks::RandomWalks;
+use crate::dachshund::algorithms::relaxed_cliques::RelaxedCliques;
+use crate::dachshund::algorithms::rewiring::Rewiring;
+use crate::dachshund::algorithms::rich_club::RichClub;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::spanning_tree::UniformSpanningTree;
+use crate::dachshund::algorithms::sparsification::Sparsification;
+use crate::dachshund::algorithms::stochastic_block_model::StochasticBlockModel;
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
 use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::algorithms::triangles::Triangles;
+use crate::dachshund::algorithms::visualization::Visualization;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode};
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::hash_map::{Keys, Values};
 
 pub trait UndirectedGraph
@@ -40,14 +68,6 @@ pub struct SimpleUndirectedGraph {
 impl GraphBase for SimpleUndirectedGraph {
     type NodeType = SimpleNode;
 
-    /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
-    fn get_core_ids(&self) -> &Vec<NodeId> {
-        &self.ids
-    }
-    /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
-    fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
-        Some(&self.ids)
-    }
     fn get_ids_iter(&self) -> Keys<NodeId, SimpleNode> {
         self.nodes.keys()
     }
@@ -104,11 +124,19 @@ impl SimpleUndirectedGraph {
 impl UndirectedGraph for SimpleUndirectedGraph {}
 
 impl CNMCommunities for SimpleUndirectedGraph {}
+impl Coarsening for SimpleUndirectedGraph {}
 impl ConnectedComponents for SimpleUndirectedGraph {}
 impl ConnectedComponentsUndirected for SimpleUndirectedGraph {}
 impl Coreness for SimpleUndirectedGraph {}
+impl DegreeDistribution for SimpleUndirectedGraph {}
+impl Dot for SimpleUndirectedGraph {}
+impl Gexf for SimpleUndirectedGraph {}
 impl KPeaks for SimpleUndirectedGraph {}
+impl LabelPropagation for SimpleUndirectedGraph {}
+impl Leiden for SimpleUndirectedGraph {}
+impl LinkPrediction for SimpleUndirectedGraph {}
 
+impl Assortativity for SimpleUndirectedGraph {}
 impl AdjacencyMatrix for SimpleUndirectedGraph {}
 impl Clustering for SimpleUndirectedGraph {}
 impl Connectivity for SimpleUndirectedGraph {}
@@ -116,6 +144,51 @@ impl ConnectivityUndirected for SimpleUndirectedGraph {}
 impl Betweenness for SimpleUndirectedGraph {}
 impl Laplacian for SimpleUndirectedGraph {}
 impl Transitivity for SimpleUndirectedGraph {}
+impl Triangles for SimpleUndirectedGraph {}
+impl Graphlets for SimpleUndirectedGraph {}
+impl GraphDiff for SimpleUndirectedGraph {}
+impl InfluenceMaximization for SimpleUndirectedGraph {}
 impl ShortestPaths for SimpleUndirectedGraph {}
 impl AlgebraicConnectivity for SimpleUndirectedGraph {}
 impl EigenvectorCentrality for SimpleUndirectedGraph {}
+impl Epidemics for SimpleUndirectedGraph {}
+impl MaxFlow for SimpleUndirectedGraph {}
+impl PageRank for SimpleUndirectedGraph {}
+impl LocalCommunity for SimpleUndirectedGraph {}
+impl MaxClique for SimpleUndirectedGraph {}
+impl Partitioning for SimpleUndirectedGraph {}
+impl RandomWalks for SimpleUndirectedGraph {}
+impl RelaxedCliques for SimpleUndirectedGraph {}
+impl Rewiring for SimpleUndirectedGraph {}
+impl RichClub for SimpleUndirectedGraph {}
+impl Sparsification for SimpleUndirectedGraph {}
+impl UniformSpanningTree for SimpleUndirectedGraph {}
+impl StochasticBlockModel for SimpleUndirectedGraph {}
+impl Subgraphs for SimpleUndirectedGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        SimpleUndirectedGraph {
+            ids: self
+                .ids
+                .iter()
+                .filter(|id| nodes.contains(id))
+                .cloned()
+                .collect(),
+            nodes: self
+                .nodes
+                .values()
+                .filter(|node| nodes.contains(&node.node_id))
+                .map(|node| {
+                    let neighbors = node
+                        .neighbors
+                        .iter()
+                        .filter(|id| nodes.contains(id))
+                        .cloned()
+                        .collect();
+                    (node.node_id, SimpleNode::new(node.node_id, neighbors))
+                })
+                .collect(),
+        }
+    }
+}
+impl ForceLayout for SimpleUndirectedGraph {}
+impl Visualization for SimpleUndirectedGraph {}
@@ -4,14 +4,14 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_builder_base::{
     GraphBuilderBase, GraphBuilderBaseWithCliques, GraphBuilderBaseWithPreProcessing,
 };
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::SimpleNode;
+use crate::dachshund::node::{SimpleNeighbors, SimpleNode};
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeSet, HashSet};
 extern crate fxhash;
 use fxhash::FxHashMap;
 use itertools::Itertools;
@@ -76,30 +76,22 @@ pub trait TSimpleUndirectedGraphBuilder:
         self.from_vector(v.into_iter().map(|(x, y)| (x as i64, y as i64)).collect())
     }
 
-    fn get_node_ids(data: &Vec<(i64, i64)>) -> BTreeMap<NodeId, BTreeSet<NodeId>> {
-        let mut ids: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+    fn get_node_ids(data: &Vec<(i64, i64)>) -> FxHashMap<NodeId, SimpleNeighbors> {
+        let mut ids: FxHashMap<NodeId, SimpleNeighbors> = FxHashMap::default();
         for (id1, id2) in data {
             ids.entry(NodeId::from(*id1))
-                .or_insert_with(BTreeSet::new)
-                .insert(NodeId::from(*id2));
+                .or_default()
+                .push(NodeId::from(*id2));
             ids.entry(NodeId::from(*id2))
-                .or_insert_with(BTreeSet::new)
-                .insert(NodeId::from(*id1));
+                .or_default()
+                .push(NodeId::from(*id1));
         }
         ids
     }
-    fn get_nodes(ids: BTreeMap<NodeId, BTreeSet<NodeId>>) -> FxHashMap<NodeId, SimpleNode> {
-        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
-        for (id, neighbors) in ids.into_iter() {
-            nodes.insert(
-                id,
-                SimpleNode {
-                    node_id: id,
-                    neighbors,
-                },
-            );
-        }
-        nodes
+    fn get_nodes(ids: FxHashMap<NodeId, SimpleNeighbors>) -> FxHashMap<NodeId, SimpleNode> {
+        ids.into_iter()
+            .map(|(id, neighbors)| (id, SimpleNode::new(id, neighbors)))
+            .collect()
     }
 }
 
@@ -121,6 +113,74 @@ impl<T: GraphBuilderBaseWithPreProcessing + TSimpleUndirectedGraphBuilder> Graph
     }
 }
 impl TSimpleUndirectedGraphBuilder for SimpleUndirectedGraphBuilder {}
+impl SimpleUndirectedGraphBuilder {
+    /// Builds a graph from edges sorted by (source, target), as e.g. warehouse exports can
+    /// guarantee, without the intermediate maps of from_vector: the edges are sorted again
+    /// by (target, source), and both lists walked through in step, node by node, merging
+    /// the targets of the edges from a node with the sources of those to it, which come in
+    /// order. Repeated edges are ignored, and edges may be given in one or both directions.
+    /// Returns an error if the edges aren't sorted.
+    pub fn from_sorted_edges(&self, data: &[(i64, i64)]) -> CLQResult<SimpleUndirectedGraph> {
+        if let Some(i) = (1..data.len()).find(|i| data[i - 1] > data[*i]) {
+            return Err(CLQError::from(format!(
+                "Edges are not sorted by (source, target): {:?} comes after {:?}",
+                data[i],
+                data[i - 1]
+            )));
+        }
+        let mut reversed: Vec<(i64, i64)> = data.iter().map(|(x, y)| (*y, *x)).collect();
+        reversed.sort_unstable();
+
+        let mut ids: Vec<NodeId> = Vec::new();
+        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+        let (mut i, mut j) = (0, 0);
+        while i < data.len() || j < reversed.len() {
+            let id = match (data.get(i), reversed.get(j)) {
+                (Some((x, _)), Some((y, _))) => *x.min(y),
+                (Some((x, _)), None) => *x,
+                (None, Some((y, _))) => *y,
+                (None, None) => unreachable!(),
+            };
+            let mut neighbors = SimpleNeighbors::new();
+            loop {
+                let neighbor = match (data.get(i), reversed.get(j)) {
+                    (Some((x, a)), Some((y, b))) if *x == id && *y == id => {
+                        if a <= b {
+                            i += 1;
+                            *a
+                        } else {
+                            j += 1;
+                            *b
+                        }
+                    }
+                    (Some((x, a)), _) if *x == id => {
+                        i += 1;
+                        *a
+                    }
+                    (_, Some((y, b))) if *y == id => {
+                        j += 1;
+                        *b
+                    }
+                    _ => break,
+                };
+                let neighbor = NodeId::from(neighbor);
+                if neighbors.last() != Some(&neighbor) {
+                    neighbors.push(neighbor);
+                }
+            }
+            let id = NodeId::from(id);
+            ids.push(id);
+            nodes.insert(
+                id,
+                SimpleNode {
+                    node_id: id,
+                    neighbors,
+                },
+            );
+        }
+        Ok(SimpleUndirectedGraph { ids, nodes })
+    }
+}
 
 pub struct SimpleUndirectedGraphBuilderWithCliques {
     cliques: Vec<BTreeSet<NodeId>>,
@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+extern crate serde_json;
+
+use crate::dachshund::algorithms::streaming_triangles::TriestEstimator;
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::id_types::{GraphId, NodeId};
+use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::row::Row;
+use crate::dachshund::transformer_base::TransformerBase;
+use rand::prelude::*;
+use serde_json::json;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Prints estimates of the number of triangles and of the transitivity of each graph, as
+/// original_graph_id\t{"exact":..., "num_edges":..., "num_triangles":..., "transitivity":...},
+/// using a fixed-size reservoir of edges (see `TriestEstimator`) rather than building the
+/// graph, so that graphs too large to hold in memory can be summarized. Estimates are exact
+/// (and so is "exact") for graphs with no more edges than the reservoir holds.
+pub struct StreamingTrianglesTransformer {
+    estimator: TriestEstimator,
+    line_processor: Arc<LineProcessor>,
+}
+impl StreamingTrianglesTransformer {
+    pub fn new(reservoir_size: usize) -> CLQResult<Self> {
+        Ok(Self {
+            estimator: TriestEstimator::new(reservoir_size)?,
+            line_processor: Arc::new(LineProcessor::new()),
+        })
+    }
+
+    /// as new, but sampling edges with a generator seeded with seed, so that runs are
+    /// reproducible.
+    pub fn with_seed(reservoir_size: usize, seed: u64) -> CLQResult<Self> {
+        Ok(Self {
+            estimator: TriestEstimator::new_with_rng(reservoir_size, StdRng::seed_from_u64(seed))?,
+            line_processor: Arc::new(LineProcessor::new()),
+        })
+    }
+}
+
+impl TransformerBase for StreamingTrianglesTransformer {
+    fn get_line_processor(&self) -> Arc<dyn LineProcessorBase> {
+        self.line_processor.clone()
+    }
+    fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
+        let (source_id, target_id) = row.as_simple_edge_row().unwrap().as_tuple();
+        self.estimator
+            .add_edge(NodeId::from(source_id), NodeId::from(target_id));
+        Ok(())
+    }
+    fn reset(&mut self) -> CLQResult<()> {
+        self.estimator.reset();
+        Ok(())
+    }
+    fn process_batch(
+        &mut self,
+        graph_id: GraphId,
+        output: &Sender<(Option<String>, bool)>,
+    ) -> CLQResult<()> {
+        let stats = json!({
+            "exact": self.estimator.is_exact(),
+            "num_edges": self.estimator.count_edges(),
+            "num_triangles": self.estimator.get_triangle_estimate(),
+            // null for graphs without wedges.
+            "transitivity": self.estimator.get_transitivity_estimate(),
+        });
+        let original_id = self
+            .line_processor
+            .get_original_id(graph_id.value() as usize);
+        output
+            .send((Some(format!("{original_id}\t{stats}")), false))
+            .unwrap();
+        Ok(())
+    }
+}
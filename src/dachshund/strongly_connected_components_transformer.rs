@@ -12,6 +12,7 @@ use crate::dachshund::algorithms::connected_components::ConnectedComponentsDirec
 use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::node_labels::format_node_id;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
@@ -30,6 +31,12 @@ impl StronglyConnectedComponentsTransformer {
             line_processor: Arc::new(LineProcessor::new()),
         }
     }
+    /// reads node ids as string labels rather than as integers (see
+    /// `LineProcessor::with_string_labels`), and writes them back as such in the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(LineProcessor::new().with_string_labels());
+        self
+    }
 }
 impl Default for StronglyConnectedComponentsTransformer {
     fn default() -> Self {
@@ -62,9 +69,18 @@ impl TransformerBase for StronglyConnectedComponentsTransformer {
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
+        let labels = self
+            .line_processor
+            .get_node_labels()
+            .and_then(|x| x.get(graph_id));
         for (cid, nodes) in conn_comp.into_iter().enumerate() {
             for node_id in nodes {
-                let line = format!("{}\t{}\t{}", original_id, cid, node_id.value());
+                let line = format!(
+                    "{}\t{}\t{}",
+                    original_id,
+                    cid,
+                    format_node_id(node_id, labels.as_deref())
+                );
                 output.send((Some(line), false)).unwrap();
             }
         }
@@ -5,18 +5,29 @@
  * LICENSE file in the root directory of this source tree.
  */
 // use std::collections::HashSet;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Debug;
+use std::sync::mpsc::channel;
 
+use rand::prelude::*;
 use roaring::RoaringBitmap;
 
+use crate::dachshund::bipartite_graph::BipartiteGraph;
+use crate::dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
 use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId, NodeTypeId};
 use crate::dachshund::line_processor::LineProcessorBase;
 use crate::dachshund::row::EdgeRow;
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use crate::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
 use crate::dachshund::transformer::Transformer;
 use crate::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use crate::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
 
 pub fn gen_test_transformer(
     typespec: Vec<Vec<String>>,
@@ -80,6 +91,43 @@ pub fn assert_nodes_have_ids<T>(
     );
 }
 
+fn as_label_set<T>(labels: &[T]) -> BTreeSet<NodeId>
+where
+    T: Copy + Into<NodeId>,
+{
+    labels.iter().map(|&label| label.into()).collect()
+}
+
+/// Panics unless node_ids are exactly the nodes with the given labels, in any order.
+pub fn assert_nodes_have_labels<T>(node_ids: &[NodeId], test_labels: Vec<T>)
+where
+    T: Copy + Debug + Into<NodeId>,
+{
+    let node_set: BTreeSet<NodeId> = node_ids.iter().cloned().collect();
+    if node_set.len() != node_ids.len() || node_set != as_label_set(&test_labels) {
+        panic!("Node set {:?} != Labels {:?}", node_set, test_labels);
+    }
+}
+
+/// Panics unless node_sets (e.g. components or communities) are exactly the sets of nodes
+/// with the given labels, with the sets and the nodes in each in any order.
+pub fn assert_node_sets_have_labels<T>(node_sets: &[Vec<NodeId>], test_labels: Vec<Vec<T>>)
+where
+    T: Copy + Debug + Into<NodeId>,
+{
+    let actual: BTreeSet<BTreeSet<NodeId>> = node_sets
+        .iter()
+        .map(|node_ids| node_ids.iter().cloned().collect())
+        .collect();
+    let expected: BTreeSet<BTreeSet<NodeId>> = test_labels
+        .iter()
+        .map(|labels| as_label_set(labels))
+        .collect();
+    if actual.len() != node_sets.len() || actual != expected {
+        panic!("Node sets {:?} != Labels {:?}", actual, test_labels);
+    }
+}
+
 pub fn process_raw_vector(transformer: &Transformer, raw: Vec<String>) -> CLQResult<Vec<EdgeRow>> {
     let mut rows: Vec<EdgeRow> = Vec::new();
     for r in raw {
@@ -158,3 +206,261 @@ pub fn gen_single_clique(
     );
     (core_ids, non_core_ids, clique_rows)
 }
+
+/// Edges of a random graph on num_nodes nodes, each pair tied with probability p, with a clique
+/// planted on the nodes of clique. Each edge appears once, from its smaller node.
+pub fn gen_planted_clique_edges<R: Rng>(
+    num_nodes: i64,
+    p: f64,
+    clique: &[i64],
+    rng: &mut R,
+) -> Vec<(i64, i64)> {
+    let clique: HashSet<i64> = clique.iter().cloned().collect();
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..num_nodes {
+        for j in (i + 1)..num_nodes {
+            if (clique.contains(&i) && clique.contains(&j)) || rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges
+}
+
+/// Random graph with a planted clique (see gen_planted_clique_edges).
+pub fn gen_planted_clique_undirected_graph<R: Rng>(
+    num_nodes: i64,
+    p: f64,
+    clique: &[i64],
+    rng: &mut R,
+) -> CLQResult<SimpleUndirectedGraph> {
+    SimpleUndirectedGraphBuilder {}.from_vector(gen_planted_clique_edges(num_nodes, p, clique, rng))
+}
+
+/// Random directed graph with a planted clique (see gen_planted_clique_edges): ties within the
+/// clique go both ways, others in a random direction.
+pub fn gen_planted_clique_directed_graph<R: Rng>(
+    num_nodes: i64,
+    p: f64,
+    clique: &[i64],
+    rng: &mut R,
+) -> CLQResult<SimpleDirectedGraph> {
+    let clique_set: HashSet<i64> = clique.iter().cloned().collect();
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for (i, j) in gen_planted_clique_edges(num_nodes, p, clique, rng) {
+        if clique_set.contains(&i) && clique_set.contains(&j) {
+            edges.push((i, j));
+            edges.push((j, i));
+        } else if rng.gen::<bool>() {
+            edges.push((i, j));
+        } else {
+            edges.push((j, i));
+        }
+    }
+    SimpleDirectedGraphBuilder {}.from_vector(edges)
+}
+
+/// Random weighted graph with a planted clique (see gen_planted_clique_edges): ties within the
+/// clique have weight clique_weight, others weight 1.
+pub fn gen_planted_clique_weighted_graph<R: Rng>(
+    num_nodes: i64,
+    p: f64,
+    clique: &[i64],
+    clique_weight: f64,
+    rng: &mut R,
+) -> CLQResult<WeightedUndirectedGraph> {
+    let clique_set: HashSet<i64> = clique.iter().cloned().collect();
+    let edges: Vec<(i64, i64, f64)> = gen_planted_clique_edges(num_nodes, p, clique, rng)
+        .into_iter()
+        .map(|(i, j)| {
+            if clique_set.contains(&i) && clique_set.contains(&j) {
+                (i, j, clique_weight)
+            } else {
+                (i, j, 1.0)
+            }
+        })
+        .collect();
+    WeightedUndirectedGraphBuilder {}.from_vector(edges)
+}
+
+/// A candidate that the search should find among rows of a typed graph, given by the labels of
+/// its core and non-core nodes, in ascending order. Rows are for a transformer with
+/// gen_test_typespec and "author" as the core type.
+#[derive(Clone, Debug)]
+pub struct GoldenCandidate {
+    pub name: String,
+    pub rows: Vec<String>,
+    pub core_labels: Vec<i64>,
+    pub non_core_labels: Vec<i64>,
+}
+impl GoldenCandidate {
+    /// Builds the graph of the rows with transformer, runs the search on it, and panics unless
+    /// the top candidate is made of the expected nodes, naming the golden candidate if not.
+    pub fn assert_found(&self, transformer: &Transformer) -> CLQResult<()> {
+        let graph_id: GraphId = 0.into();
+        let rows = process_raw_vector(transformer, self.rows.clone())?;
+        let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+        let (sender, _receiver) = channel();
+        let candidate = transformer
+            .process_clique_rows(&graph, &[], graph_id, false, &sender)?
+            .ok_or_else(CLQError::err_none)?
+            .top_candidate;
+        assert_eq!(
+            (
+                candidate.sorted_core_labels(),
+                candidate.sorted_non_core_labels()
+            ),
+            (self.core_labels.clone(), self.non_core_labels.clone()),
+            "{}",
+            self.name
+        );
+        Ok(())
+    }
+}
+
+/// Bipartite clique between num_core authors and non_core_counts conferences and journals
+/// (see gen_single_clique), along with noise: num_noise_core other authors, who each published
+/// at each of the clique's conferences and journals with probability p.
+pub fn gen_planted_clique_typed_rows<R: Rng>(
+    num_core: u32,
+    non_core_counts: Vec<u32>,
+    num_noise_core: u32,
+    p: f64,
+    rng: &mut R,
+) -> GoldenCandidate {
+    let non_core_types: Vec<String> = gen_test_typespec().iter().map(|x| x[2].clone()).collect();
+    let (core_ids, non_core_ids, mut rows) = gen_single_clique(
+        0.into(),
+        num_core,
+        non_core_counts,
+        non_core_types.clone(),
+        "author".to_string(),
+        vec!["published_at".to_string()],
+    );
+    let first_noise_id = (core_ids.len() + non_core_ids.len()) as u32;
+    for core_id in first_noise_id..first_noise_id + num_noise_core {
+        for (non_core_id, non_core_type) in &non_core_ids {
+            if rng.gen::<f64>() < p {
+                rows.push(format!(
+                    "0\t{}\t{}\tauthor\tpublished_at\t{}",
+                    core_id,
+                    non_core_id,
+                    non_core_types[non_core_type.value()]
+                ));
+            }
+        }
+    }
+    GoldenCandidate {
+        name: format!(
+            "planted clique of {} authors and {} venues",
+            core_ids.len(),
+            non_core_ids.len()
+        ),
+        rows,
+        core_labels: core_ids.into_iter().map(i64::from).collect(),
+        non_core_labels: non_core_ids.iter().map(|x| x.0 as i64).collect(),
+    }
+}
+
+/// Small typed graphs with the candidates the search should find in them.
+pub fn gen_golden_candidates() -> Vec<GoldenCandidate> {
+    let golden =
+        |name: &str, rows: Vec<&str>, core_labels: Vec<i64>, non_core_labels| GoldenCandidate {
+            name: name.to_string(),
+            rows: rows.into_iter().map(String::from).collect(),
+            core_labels,
+            non_core_labels,
+        };
+    vec![
+        golden(
+            "single row",
+            vec!["0\t1\t2\tauthor\tpublished_at\tconference"],
+            vec![1],
+            vec![2],
+        ),
+        golden(
+            "small clique",
+            vec![
+                "0\t1\t3\tauthor\tpublished_at\tconference",
+                "0\t2\t3\tauthor\tpublished_at\tconference",
+                "0\t1\t4\tauthor\tpublished_at\tconference",
+                "0\t2\t4\tauthor\tpublished_at\tconference",
+            ],
+            vec![1, 2],
+            vec![3, 4],
+        ),
+        golden(
+            "small clique with a row of the wrong source type",
+            vec![
+                "0\t1\t3\tauthor\tpublished_at\tconference",
+                "0\t2\t3\tauthor\tpublished_at\tconference",
+                "0\t1\t4\tauthor\tpublished_at\tconference",
+                "0\t2\t4\tauthor\tpublished_at\tconference",
+                "0\t2\t5\tconference\tpublished_at\tconference",
+            ],
+            vec![1, 2],
+            vec![3, 4],
+        ),
+        golden(
+            "small clique across venue types, with a pendant author",
+            vec![
+                "0\t1\t3\tauthor\tpublished_at\tconference",
+                "0\t2\t3\tauthor\tpublished_at\tconference",
+                "0\t1\t4\tauthor\tpublished_at\tjournal",
+                "0\t2\t4\tauthor\tpublished_at\tjournal",
+                "0\t5\t4\tauthor\tpublished_at\tjournal",
+            ],
+            vec![1, 2],
+            vec![3, 4],
+        ),
+    ]
+}
+
+/// Two triangles, 0-1-2 and 3-4-5.
+pub fn gen_two_triangles_graph() -> CLQResult<SimpleUndirectedGraph> {
+    SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+    ])
+}
+
+/// Two triangles, 0-1-2 and 3-4-5, joined by the edge 2-3.
+pub fn gen_bridged_triangles_graph() -> CLQResult<SimpleUndirectedGraph> {
+    SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (2, 3),
+    ])
+}
+
+/// A triangle 0-1-2 with a pendant node 3 attached to 2 (a "paw"): degrees 2, 2, 3 and 1.
+pub fn gen_triangle_with_pendant_graph() -> CLQResult<SimpleUndirectedGraph> {
+    SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0), (2, 3)])
+}
+
+/// A square 0-1-3-2 with a pendant node 4 attached to 3.
+pub fn gen_square_with_pendant_graph() -> CLQResult<SimpleUndirectedGraph> {
+    SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)])
+}
+
+/// Authors 1-4 (left) and papers 10-12 (right): paper 10 by 1, 2 and 3, paper 11 by 1 and 2
+/// (the latter listed twice), paper 12 by 4 alone.
+pub fn gen_authorship_graph() -> CLQResult<BipartiteGraph> {
+    BipartiteGraphBuilder {}.from_vector(vec![
+        (1, 10),
+        (2, 10),
+        (3, 10),
+        (1, 11),
+        (2, 11),
+        (2, 11),
+        (4, 12),
+    ])
+}
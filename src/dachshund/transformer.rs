@@ -8,35 +8,74 @@ extern crate clap;
 extern crate serde_json;
 
 use clap::ArgMatches;
+use serde_json::{json, Value};
 
+use crate::dachshund::algorithms::assortativity::TypeAssortativity;
+use crate::dachshund::algorithms::fraudar::Fraudar;
 use crate::dachshund::beam::{Beam, BeamSearchResult};
+use crate::dachshund::candidate::Candidate;
+use crate::dachshund::edge_type_weights::EdgeTypeWeights;
 use crate::dachshund::error::{CLQError, CLQResult};
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
-use crate::dachshund::id_types::{GraphId, NodeTypeId};
+use crate::dachshund::id_types::{EdgeTypeId, GraphId, NodeTypeId, NodeTypeIdInternal};
 use crate::dachshund::line_processor::LineProcessorBase;
 use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
 use crate::dachshund::row::{CliqueRow, EdgeRow, Row};
 use crate::dachshund::search_problem::SearchProblem;
 use crate::dachshund::transformer_base::TransformerBase;
-use crate::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use crate::dachshund::typed_graph::TypedGraph;
 use crate::dachshund::typed_graph_builder::TypedGraphBuilder;
 use crate::dachshund::typed_graph_line_processor::TypedGraphLineProcessor;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
+/// How the top candidate of each graph is printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    // graph_id, then one tab-separated field per property (see `Candidate::to_printable_row`).
+    Wide,
+    // graph_id\tnode_id\tnode_type, one row per node.
+    Long,
+    // the graph in Graphviz DOT format, with the candidate highlighted (see `Candidate::to_dot`).
+    Dot,
+    // graph_id, then node and edge counts and type mixing, as JSON, instead of the top
+    // candidate (see `Transformer::get_graph_stats_json`).
+    Stats,
+}
+impl FromStr for OutputFormat {
+    type Err = CLQError;
+    fn from_str(s: &str) -> CLQResult<Self> {
+        match s {
+            "wide" => Ok(OutputFormat::Wide),
+            "long" => Ok(OutputFormat::Long),
+            "dot" => Ok(OutputFormat::Dot),
+            "stats" => Ok(OutputFormat::Stats),
+            _ => Err(CLQError::from(format!(
+                "Unknown output format: {} (expected wide, long, dot or stats)",
+                s
+            ))),
+        }
+    }
+}
+
 /// Used to set up the typed graph clique mining algorithm.
 pub struct Transformer {
     pub core_type: String,
-    pub non_core_type_ids: Rc<NonCoreTypeIds>,
-    pub non_core_types: Rc<Vec<String>>,
-    pub edge_types: Rc<Vec<String>>,
+    pub non_core_type_ids: Arc<NonCoreTypeIds>,
+    pub non_core_types: Arc<Vec<String>>,
+    pub edge_types: Arc<Vec<String>>,
     pub num_non_core_types: usize,
     pub line_processor: Arc<TypedGraphLineProcessor>,
     pub search_problem: Rc<SearchProblem>,
     pub debug: bool,
-    pub long_format: bool,
+    pub output_format: OutputFormat,
+    // whether to seed searches with no clique rows with the graph's Fraudar block.
+    pub dense_block_seed: bool,
 
     edge_rows: Vec<EdgeRow>,
     clique_rows: Vec<CliqueRow>,
@@ -46,12 +85,7 @@ impl TransformerBase for Transformer {
         self.line_processor.clone()
     }
     fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
-        if let Some(edge_row) = row.as_edge_row() {
-            self.edge_rows.push(edge_row);
-        }
-        if let Some(clique_row) = row.as_clique_row() {
-            self.clique_rows.push(clique_row);
-        }
+        self.add_row(row.as_ref());
         Ok(())
     }
     fn reset(&mut self) -> CLQResult<()> {
@@ -59,6 +93,28 @@ impl TransformerBase for Transformer {
         self.clique_rows.clear();
         Ok(())
     }
+    fn describe_row(&self, row: &dyn Row) -> String {
+        let type_name = |type_id| {
+            self.non_core_type_ids
+                .type_name(type_id)
+                .unwrap_or_else(|| "unknown".to_owned())
+        };
+        if let Some(edge_row) = row.as_edge_row() {
+            return format!(
+                "edge {} {} {}",
+                self.core_type,
+                self.edge_types[edge_row.edge_type_id.value()],
+                type_name(&edge_row.target_type_id)
+            );
+        }
+        if let Some(clique_row) = row.as_clique_row() {
+            return match clique_row.target_type {
+                Some(target_type) => format!("clique {}", type_name(&target_type)),
+                None => format!("clique {}", self.core_type),
+            };
+        }
+        "unknown".to_owned()
+    }
     fn process_batch(
         &mut self,
         graph_id: GraphId,
@@ -82,7 +138,8 @@ impl Transformer {
     /// [["author", "published_in", "journal"], ["author", "co-authored", "article"]].
     /// This sets up the semantics related to the set of relations contained in the
     /// typed graph. A requirement is that all relations share a "core" type, in this
-    /// case, "author". Non-core types must be listed in a vector, which is used to
+    /// case, "author". Relations may carry a fourth element, the weight of their edge
+    /// type (see `EdgeTypeWeights`). Non-core types must be listed in a vector, which is used to
     /// index the non core-types. The function creates a vector of NonCoreTypeIds, which
     /// will then be used to process input rows.
     pub fn process_typespec(
@@ -111,7 +168,8 @@ impl Transformer {
     ///     [["author", "published_in", "journal"], ["author", "co-authored", "article"]].
     ///     This sets up the semantics related to the set of relations contained in the
     ///     typed graph. A requirement is that all relations share a "core" type, in this
-    ///     case, "author".
+    ///     case, "author". Relations may carry a fourth element, e.g.
+    ///     ["author", "cited", "article", "0.25"], weighing their ties in cliqueness.
     ///     - `beam_size`: Beam construction parameter. The number of top candidates to
     ///     maintain as potential future cores for expansion in the "beam" (i.e., the list of top candidates).
     ///     - `alpha`: `Scorer` constructor parameter. Controls the contribution of density
@@ -147,7 +205,7 @@ impl Transformer {
         core_type: String,
         long_format: bool,
     ) -> CLQResult<Self> {
-        let search_problem = Rc::new(SearchProblem::new(
+        let mut search_problem = SearchProblem::new(
             beam_size,
             alpha,
             global_thresh,
@@ -156,21 +214,27 @@ impl Transformer {
             num_epochs,
             max_repeated_prior_scores,
             min_degree,
-        ));
+        );
+        search_problem.validate(debug)?;
         let mut edge_types_v: Vec<String> = typespec.iter().map(|x| x[1].clone()).collect();
         edge_types_v.sort();
-        let edge_types = Rc::new(edge_types_v);
+        let edge_types = Arc::new(edge_types_v);
 
         let mut non_core_types_v: Vec<String> = typespec.iter().map(|x| x[2].clone()).collect();
         non_core_types_v.sort();
-        let non_core_types = Rc::new(non_core_types_v);
+        let non_core_types = Arc::new(non_core_types_v);
 
         let num_non_core_types: usize = non_core_types.len();
-        let non_core_type_ids: Rc<NonCoreTypeIds> = Rc::new(Transformer::process_typespec(
-            typespec,
+        let non_core_type_ids: Arc<NonCoreTypeIds> = Arc::new(Transformer::process_typespec(
+            typespec.clone(),
             &core_type,
             non_core_types.to_vec(),
         )?);
+        if let Some(edge_type_weights) =
+            EdgeTypeWeights::from_typespec(&typespec, &edge_types, &non_core_type_ids)?
+        {
+            search_problem = search_problem.with_edge_type_weights(edge_type_weights);
+        }
         let line_processor = Arc::new(TypedGraphLineProcessor::new(
             core_type.clone(),
             non_core_type_ids.clone(),
@@ -184,38 +248,221 @@ impl Transformer {
             edge_types,
             num_non_core_types,
             line_processor,
-            search_problem,
+            search_problem: Rc::new(search_problem),
             debug,
-            long_format,
+            output_format: if long_format {
+                OutputFormat::Long
+            } else {
+                OutputFormat::Wide
+            },
             edge_rows: Vec::new(),
             clique_rows: Vec::new(),
+            dense_block_seed: false,
         };
         Ok(transformer)
     }
 
+    /// parses the value of a command line argument, reporting which argument was
+    /// missing or malformed.
+    pub(crate) fn parse_arg<T>(matches: &ArgMatches, name: &str) -> CLQResult<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value: &str = matches
+            .value_of(name)
+            .ok_or_else(|| CLQError::from(format!("Missing required argument: {name}")))?;
+        value.parse::<T>().map_err(|err| {
+            CLQError::from(format!(
+                "Invalid value for argument {name}: '{value}' ({err})"
+            ))
+        })
+    }
+
+    /// sets local thresholds for non-core nodes of the given types (see
+    /// `SearchProblem::with_non_core_local_thresh`), e.g. {"journal": 0.5}. Core nodes
+    /// are still held to `local_thresh`. Returns an error if a type isn't a non-core type
+    /// of the typespec, or a threshold isn't between 0 and 1.
+    pub fn with_non_core_local_thresh(
+        mut self,
+        non_core_local_thresh: HashMap<String, f32>,
+    ) -> CLQResult<Self> {
+        let mut thresh_by_type_id: BTreeMap<NodeTypeIdInternal, f32> = BTreeMap::new();
+        for (non_core_type, thresh) in non_core_local_thresh {
+            if !self.non_core_types.contains(&non_core_type) {
+                return Err(CLQError::from(format!(
+                    "Not a non-core type in the typespec: {non_core_type}"
+                )));
+            }
+            let non_core_type_id = self.non_core_type_ids.require(&non_core_type)?.value();
+            thresh_by_type_id.insert(non_core_type_id, thresh);
+        }
+        let search_problem = (*self.search_problem)
+            .clone()
+            .with_non_core_local_thresh(thresh_by_type_id);
+        search_problem.validate(self.debug)?;
+        self.search_problem = Rc::new(search_problem);
+        Ok(self)
+    }
+
+    /// requires core nodes to have at least the given number of neighbors through edges of
+    /// each of the given types (see `SearchProblem::with_edge_type_min_degree`), pruning
+    /// graphs accordingly. Returns an error if a type is not an edge type of the typespec.
+    pub fn with_edge_type_min_degree(
+        mut self,
+        edge_type_min_degree: HashMap<String, usize>,
+    ) -> CLQResult<Self> {
+        let mut min_degree_by_type_id: BTreeMap<EdgeTypeId, usize> = BTreeMap::new();
+        for (edge_type, min_degree) in edge_type_min_degree {
+            let edge_type_id = self
+                .edge_types
+                .iter()
+                .position(|x| *x == edge_type)
+                .ok_or_else(|| {
+                    CLQError::from(format!("Not an edge type in the typespec: {edge_type}"))
+                })?;
+            min_degree_by_type_id.insert(EdgeTypeId::from(edge_type_id), min_degree);
+        }
+        let search_problem = (*self.search_problem)
+            .clone()
+            .with_edge_type_min_degree(min_degree_by_type_id);
+        self.search_problem = Rc::new(search_problem);
+        Ok(self)
+    }
+
+    /// requires non-core nodes to have at least the given number of neighbors, with core
+    /// nodes required to have min_degree, pruning graphs to their (min_degree,
+    /// non_core_min_degree)-core (see `SearchProblem::with_non_core_min_degree`).
+    pub fn with_non_core_min_degree(mut self, non_core_min_degree: usize) -> Self {
+        let search_problem = (*self.search_problem)
+            .clone()
+            .with_non_core_min_degree(non_core_min_degree);
+        self.search_problem = Rc::new(search_problem);
+        self
+    }
+
+    /// caps the estimated memory used by the beam's candidates at the given number of bytes
+    /// (see `SearchProblem::with_max_beam_memory`). Returns an error if the cap is 0.
+    pub fn with_max_beam_memory(mut self, max_beam_memory: usize) -> CLQResult<Self> {
+        let search_problem = (*self.search_problem)
+            .clone()
+            .with_max_beam_memory(max_beam_memory);
+        search_problem.validate(self.debug)?;
+        self.search_problem = Rc::new(search_problem);
+        Ok(self)
+    }
+
+    /// prints the top candidate of each graph in the given format.
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// seeds the search of each graph with no clique rows with its densest block, as
+    /// found by Fraudar with column weighting (see `Fraudar::get_fraudar_block`).
+    pub fn with_dense_block_seed(mut self) -> Self {
+        self.dense_block_seed = true;
+        self
+    }
+
+    /// reads node ids as string labels rather than as integers (see
+    /// `TypedGraphLineProcessor::with_string_labels`), and writes them back as such in
+    /// the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(
+            TypedGraphLineProcessor::new(
+                self.core_type.clone(),
+                self.non_core_type_ids.clone(),
+                self.non_core_types.clone(),
+                self.edge_types.clone(),
+            )
+            .with_string_labels(),
+        );
+        self
+    }
+
+    /// parses a JSON-encoded typespec. Edge type weights may be given as numbers, e.g.
+    /// [["author", "cited", "article", 0.25]], and are kept as strings like other elements.
+    pub fn parse_typespec(typespec_str: &str) -> CLQResult<Vec<Vec<String>>> {
+        let typespec: Vec<Vec<Value>> = serde_json::from_str(typespec_str)?;
+        Ok(typespec
+            .into_iter()
+            .map(|item| {
+                item.into_iter()
+                    .map(|x| match x {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
     /// constructs a transformer from an ArgMatches object (to help with command line arguments).
     pub fn from_argmatches(matches: ArgMatches) -> CLQResult<Self> {
-        let arg_value = |name: &str| -> CLQResult<&str> {
-            matches
-                .value_of(name)
-                .ok_or_else(|| CLQError::from(format!("Missing required argument: {name}")))
-        };
-        let typespec_str: &str = arg_value("typespec")?;
-        let typespec: Vec<Vec<String>> = serde_json::from_str(typespec_str)?;
-        let beam_size: usize = arg_value("beam_size")?.parse::<usize>()?;
-        let alpha: f32 = arg_value("alpha")?.parse::<f32>()?;
-        let global_thresh: Option<f32> = Some(arg_value("global_thresh")?.parse::<f32>()?);
-        let local_thresh: Option<f32> = Some(arg_value("local_thresh")?.parse::<f32>()?);
-        let num_to_search: usize = arg_value("num_to_search")?.parse::<usize>()?;
-        let num_epochs: usize = arg_value("epochs")?.parse::<usize>()?;
+        let typespec_str: String = Self::parse_arg(&matches, "typespec")?;
+        let typespec: Vec<Vec<String>> = Self::parse_typespec(&typespec_str)?;
+        let mut transformer = Self::from_argmatches_with_typespec(&matches, typespec)?;
+        if let Some(non_core_local_thresh) = Self::parse_non_core_local_thresh(&matches)? {
+            transformer = transformer.with_non_core_local_thresh(non_core_local_thresh)?;
+        }
+        if let Some(edge_type_min_degree) = Self::parse_edge_type_min_degree(&matches)? {
+            transformer = transformer.with_edge_type_min_degree(edge_type_min_degree)?;
+        }
+        Ok(transformer)
+    }
+
+    /// parses the optional non_core_local_thresh command line argument, a JSON-encoded map
+    /// from non-core types to their local threshold.
+    pub fn parse_non_core_local_thresh(
+        matches: &ArgMatches,
+    ) -> CLQResult<Option<HashMap<String, f32>>> {
+        if !matches.is_present("non_core_local_thresh") {
+            return Ok(None);
+        }
+        let non_core_local_thresh_str: String = Self::parse_arg(matches, "non_core_local_thresh")?;
+        Ok(Some(serde_json::from_str(&non_core_local_thresh_str)?))
+    }
+
+    /// parses the optional edge_type_min_degree command line argument, a JSON-encoded map
+    /// from edge types to the min number of neighbors core nodes must have through them.
+    pub fn parse_edge_type_min_degree(
+        matches: &ArgMatches,
+    ) -> CLQResult<Option<HashMap<String, usize>>> {
+        if !matches.is_present("edge_type_min_degree") {
+            return Ok(None);
+        }
+        let edge_type_min_degree_str: String = Self::parse_arg(matches, "edge_type_min_degree")?;
+        Ok(Some(serde_json::from_str(&edge_type_min_degree_str)?))
+    }
+
+    /// constructs a transformer for the given typespec from the other command line
+    /// arguments, leaving out non_core_local_thresh and edge_type_min_degree (whose types
+    /// depend on the typespec).
+    pub fn from_argmatches_with_typespec(
+        matches: &ArgMatches,
+        typespec: Vec<Vec<String>>,
+    ) -> CLQResult<Self> {
+        let beam_size: usize = Self::parse_arg(matches, "beam_size")?;
+        let alpha: f32 = Self::parse_arg(matches, "alpha")?;
+        let global_thresh: Option<f32> = Some(Self::parse_arg(matches, "global_thresh")?);
+        let local_thresh: Option<f32> = Some(Self::parse_arg(matches, "local_thresh")?);
+        let num_to_search: usize = Self::parse_arg(matches, "num_to_search")?;
+        let num_epochs: usize = Self::parse_arg(matches, "epochs")?;
         let max_repeated_prior_scores: usize =
-            arg_value("max_repeated_prior_scores")?.parse::<usize>()?;
-        let debug: bool = arg_value("debug_mode")?.parse::<bool>()?;
-        let min_degree: usize = arg_value("min_degree")?.parse::<usize>()?;
-        let core_type: String = arg_value("core_type")?.parse::<String>()?;
-        let long_format: bool = arg_value("long_format")?.parse::<bool>()?;
+            Self::parse_arg(matches, "max_repeated_prior_scores")?;
+        let debug: bool = Self::parse_arg(matches, "debug_mode")?;
+        // core_min_degree and non_core_min_degree (alpha and beta) override min_degree for
+        // core and non-core nodes respectively.
+        let min_degree: usize = if matches.is_present("core_min_degree") {
+            Self::parse_arg(matches, "core_min_degree")?
+        } else {
+            Self::parse_arg(matches, "min_degree")?
+        };
+        let core_type: String = Self::parse_arg(matches, "core_type")?;
+        let long_format: bool = Self::parse_arg(matches, "long_format")?;
 
-        let transformer = Transformer::new(
+        let mut transformer = Transformer::new(
             typespec,
             beam_size,
             alpha,
@@ -229,11 +476,69 @@ impl Transformer {
             core_type,
             long_format,
         )?;
+        if matches.is_present("max_beam_memory") {
+            let max_beam_memory_mb: usize = Self::parse_arg(matches, "max_beam_memory")?;
+            transformer = transformer.with_max_beam_memory(max_beam_memory_mb * 1024 * 1024)?;
+        }
+        if matches.is_present("non_core_min_degree") {
+            let non_core_min_degree: usize = Self::parse_arg(matches, "non_core_min_degree")?;
+            transformer = transformer.with_non_core_min_degree(non_core_min_degree);
+        } else if matches.is_present("core_min_degree") {
+            let non_core_min_degree: usize = Self::parse_arg(matches, "min_degree")?;
+            transformer = transformer.with_non_core_min_degree(non_core_min_degree);
+        }
+        // output_format overrides long_format.
+        if matches.is_present("output_format") {
+            let output_format: OutputFormat = Self::parse_arg(matches, "output_format")?;
+            transformer = transformer.with_output_format(output_format);
+        }
+        if matches.is_present("dense_block_seed") && Self::parse_arg(matches, "dense_block_seed")? {
+            transformer = transformer.with_dense_block_seed();
+        }
+        if matches.is_present("string_labels") && Self::parse_arg(matches, "string_labels")? {
+            transformer = transformer.with_string_labels();
+        }
         Ok(transformer)
     }
 
+    /// keeps an edge or clique row, to be processed along with the rest of its graph.
+    pub fn add_row(&mut self, row: &dyn Row) {
+        if let Some(edge_row) = row.as_edge_row() {
+            self.edge_rows.push(edge_row);
+        }
+        if let Some(clique_row) = row.as_clique_row() {
+            self.clique_rows.push(clique_row);
+        }
+    }
+
+    /// names of all node types, indexed by type id: the core type, then non-core types.
+    pub fn get_type_names(&self) -> Vec<String> {
+        let mut type_names: Vec<String> = vec![self.core_type.clone()];
+        type_names.extend(self.non_core_types.iter().cloned());
+        type_names
+    }
+
+    /// statistics of a typed graph, as JSON: the number of core and non-core nodes, the
+    /// number of edges, and how edges mix node types (see `get_type_mixing_json`).
+    pub fn get_graph_stats_json(&self, graph: &TypedGraph) -> Value {
+        let mut stats = json!({
+            "num_core_nodes": graph.get_core_ids().len(),
+            "num_non_core_nodes": graph.get_non_core_ids().len(),
+            // each edge is kept by both of its ends.
+            "num_edges": graph.count_edges() / 2,
+        });
+        if let (Value::Object(stats), Value::Object(mixing)) = (
+            &mut stats,
+            graph.get_type_mixing_json(&self.get_type_names()),
+        ) {
+            stats.extend(mixing);
+        }
+        stats
+    }
+
     /// builds graph, pruned to ensure all nodes have at least self.min_degree degree
-    /// with other nodes in the graph. This is done via a greedy algorithm which removes
+    /// with other nodes in the graph (non-core nodes non_core_min_degree instead, if set,
+    /// and core nodes the degree required for each edge type, if any). This is done via a greedy algorithm which removes
     /// low-degree nodes iteratively.
     #[allow(clippy::ptr_arg)]
     pub fn build_pruned_graph(
@@ -244,6 +549,8 @@ impl Transformer {
         TypedGraphBuilder {
             graph_id,
             min_degree: Some(self.search_problem.min_degree),
+            non_core_min_degree: self.search_problem.non_core_min_degree,
+            edge_type_min_degree: self.search_problem.edge_type_min_degree.clone(),
         }
         .from_vector(rows)
     }
@@ -252,10 +559,19 @@ impl Transformer {
     pub fn process_graph<'a>(
         &'a self,
         graph: &'a TypedGraph,
-        clique_rows: &'a Vec<CliqueRow>,
+        clique_rows: &'a [CliqueRow],
         graph_id: GraphId,
         verbose: bool,
-    ) -> CLQResult<BeamSearchResult<'a, TypedGraph>> {
+    ) -> CLQResult<BeamSearchResult<Candidate<'a, TypedGraph>>> {
+        let dense_block_rows: Vec<CliqueRow>;
+        let clique_rows: &[CliqueRow] = if self.dense_block_seed && clique_rows.is_empty() {
+            dense_block_rows = graph
+                .get_fraudar_block(true)
+                .to_clique_rows(graph, graph_id);
+            &dense_block_rows
+        } else {
+            clique_rows
+        };
         let mut beam: Beam<TypedGraph> = Beam::new(
             graph,
             clique_rows,
@@ -271,12 +587,18 @@ impl Transformer {
     pub fn process_clique_rows<'a>(
         &'a self,
         graph: &'a TypedGraph,
-        clique_rows: &'a Vec<CliqueRow>,
+        clique_rows: &'a [CliqueRow],
         graph_id: GraphId,
         verbose: bool,
         output: &Sender<(Option<String>, bool)>,
-    ) -> CLQResult<Option<BeamSearchResult<'a, TypedGraph>>> {
-        if graph.get_core_ids().is_empty() || graph.get_non_core_ids().unwrap().is_empty() {
+    ) -> CLQResult<Option<BeamSearchResult<Candidate<'a, TypedGraph>>>> {
+        if self.output_format == OutputFormat::Stats {
+            let stats = self.get_graph_stats_json(graph);
+            let line: String = format!("{}\t{}", graph_id.value(), stats);
+            output.send((Some(line), false)).unwrap();
+            return Ok(None);
+        }
+        if graph.get_core_ids().is_empty() || graph.get_non_core_ids().is_empty() {
             // still have to send an acknowledgement to the output channel
             // that we have actually processed this graph, otherwise
             // we lose track of how many graphs have been processed so
@@ -284,27 +606,46 @@ impl Transformer {
             output.send((None, false)).unwrap();
             return Ok(None);
         }
-        let result: BeamSearchResult<TypedGraph> =
+        let result: BeamSearchResult<Candidate<TypedGraph>> =
             self.process_graph(graph, clique_rows, graph_id, verbose)?;
         // only print if this is a conforming clique
         if result.top_candidate.get_score()? > 0.0 {
-            if !self.long_format {
-                let line: String = format!(
-                    "{}\t{}",
-                    graph_id.value(),
-                    result
+            let node_labels = self
+                .line_processor
+                .get_node_labels()
+                .and_then(|x| x.get(graph_id));
+            match self.output_format {
+                OutputFormat::Wide => {
+                    let line: String = format!(
+                        "{}\t{}",
+                        graph_id.value(),
+                        result
+                            .top_candidate
+                            .to_printable_row(&self.non_core_types, node_labels.as_deref(),)?,
+                    );
+                    output.send((Some(line), false)).unwrap();
+                }
+                OutputFormat::Long => {
+                    result.top_candidate.print(
+                        graph_id,
+                        &self.non_core_types,
+                        &self.core_type,
+                        node_labels.as_deref(),
+                        output,
+                    )?;
+                }
+                OutputFormat::Dot => {
+                    let dot = result
                         .top_candidate
-                        .to_printable_row(&self.non_core_types, graph.get_reverse_labels_map())?,
-                );
-                output.send((Some(line), false)).unwrap();
-            } else {
-                result.top_candidate.print(
-                    graph_id,
-                    &self.non_core_types,
-                    &self.core_type,
-                    output,
-                )?;
+                        .to_dot(&graph_id.value().to_string(), node_labels.as_deref());
+                    output.send((Some(dot), false)).unwrap();
+                }
+                // graphs are not searched for stats, see above.
+                OutputFormat::Stats => unreachable!(),
             }
+        } else {
+            // acknowledge graphs without a conforming clique, as with empty graphs above.
+            output.send((None, false)).unwrap();
         }
         Ok(Some(result))
     }
@@ -5,20 +5,393 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate clap;
+extern crate ctrlc;
 extern crate serde_json;
 
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_cache::{GraphCache, RowsHasher};
 use crate::dachshund::id_types::GraphId;
 use crate::dachshund::input::Input;
+use crate::dachshund::line_format::DelimitedLineProcessor;
+use crate::dachshund::line_pipeline::{parse_line, ParsedLine, ParsedLines};
 use crate::dachshund::line_processor::LineProcessorBase;
-use crate::dachshund::output::Output;
+use crate::dachshund::output::{get_graph_begin_marker, get_graph_end_marker, Output};
+#[cfg(feature = "parquet")]
+use crate::dachshund::parquet_input::ParquetInput;
 use crate::dachshund::row::Row;
+use serde_json::json;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::File;
 use std::io::prelude::*;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::io::BufWriter;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Exit code used by binaries when a run was cut short by SIGINT (as per shell convention).
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Installs a SIGINT (Ctrl-C) handler that asks running transformers to stop after the
+/// graph currently being processed. A second SIGINT exits immediately.
+pub fn install_interrupt_handler() -> CLQResult<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        eprintln!("Interrupted: finishing in-flight graph (interrupt again to exit now).");
+    })
+    .map_err(|err| CLQError::from(format!("Could not install SIGINT handler: {err}")))
+}
+
+/// Asks running transformers to stop, as SIGINT does once the handler is installed.
+pub fn request_interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Opens the file that rejected lines are written to, if a path is provided.
+pub fn open_rejects_file(path: Option<&str>) -> CLQResult<Option<BufWriter<File>>> {
+    Ok(path.map(File::create).transpose()?.map(BufWriter::new))
+}
+
+/// Environment variable read for the number of threads, when not given on the command line.
+pub const THREADS_ENV_VAR: &str = "DACHSHUND_THREADS";
+
+/// Number of threads requested either via the (command line) value provided, or failing
+/// that the THREADS_ENV_VAR environment variable. None if neither is set.
+pub fn resolve_num_threads(value: Option<&str>) -> CLQResult<Option<usize>> {
+    let value: Option<String> = match value {
+        Some(value) => Some(value.to_owned()),
+        None => std::env::var(THREADS_ENV_VAR).ok(),
+    };
+    value
+        .map(|value| {
+            value.parse::<usize>().map_err(|err| {
+                CLQError::from(format!("Invalid number of threads: '{value}' ({err})"))
+            })
+        })
+        .transpose()
+}
+
+/// Summary of the first lines of an input, as produced by `TransformerBase::dry_run`.
+#[derive(Default)]
+pub struct DryRunReport {
+    pub num_lines: usize,
+    // blank or comment lines, as per `LineProcessorBase::is_skipped`.
+    pub num_lines_skipped: usize,
+    pub num_rows: usize,
+    // number of rows for each graph_id, in order of appearance.
+    pub rows_per_graph: Vec<(GraphId, usize)>,
+    // number of rows of each kind, as described by `TransformerBase::describe_row`.
+    pub rows_per_type: BTreeMap<String, usize>,
+    // (line number, message) for every line that could not be used.
+    pub issues: Vec<(usize, String)>,
+}
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Parsed {} rows out of {} lines ({} skipped).",
+            self.num_rows, self.num_lines, self.num_lines_skipped
+        )?;
+        writeln!(f, "Rows per type:")?;
+        for (row_type, count) in &self.rows_per_type {
+            writeln!(f, "  {row_type}: {count}")?;
+        }
+        writeln!(f, "Rows per graph_id:")?;
+        for (graph_id, count) in &self.rows_per_graph {
+            writeln!(f, "  {}: {}", graph_id.value(), count)?;
+        }
+        writeln!(f, "{} issues found.", self.issues.len())?;
+        for (line_num, message) in &self.issues {
+            writeln!(f, "  line {line_num}: {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals for a complete run, as returned by `TransformerBase::run`.
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub num_graphs: usize,
+    // graphs whose output was taken from the cache rather than computed.
+    pub num_graphs_cached: usize,
+    // graphs acknowledged by the transformer without any output (e.g. empty after pruning).
+    pub num_graphs_skipped: usize,
+    // blank or comment lines, as per `LineProcessorBase::is_skipped`.
+    pub num_lines_skipped: usize,
+    pub num_rows: usize,
+    // lines that could not be read or parsed, and were therefore left out of their graph.
+    pub num_rows_rejected: usize,
+    pub num_lines_emitted: usize,
+    pub elapsed: Duration,
+    // whether the run was stopped early by SIGINT.
+    pub interrupted: bool,
+}
+impl RunSummary {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "graphs_processed": self.num_graphs,
+            "graphs_cached": self.num_graphs_cached,
+            "graphs_skipped": self.num_graphs_skipped,
+            "lines_skipped": self.num_lines_skipped,
+            "rows_parsed": self.num_rows,
+            "rows_rejected": self.num_rows_rejected,
+            "lines_emitted": self.num_lines_emitted,
+            "wall_time_secs": self.elapsed.as_secs_f64(),
+            "interrupted": self.interrupted,
+        })
+    }
+    /// writes the summary as JSON to summary_file if provided, and as text to stderr otherwise.
+    pub fn write(&self, summary_file: Option<&str>) -> CLQResult<()> {
+        match summary_file {
+            Some(path) => writeln!(File::create(path)?, "{}", self.to_json())?,
+            None => eprint!("{self}"),
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Run summary:")?;
+        writeln!(f, "  graphs processed: {}", self.num_graphs)?;
+        writeln!(f, "  graphs cached: {}", self.num_graphs_cached)?;
+        writeln!(f, "  graphs skipped: {}", self.num_graphs_skipped)?;
+        writeln!(f, "  lines skipped: {}", self.num_lines_skipped)?;
+        writeln!(f, "  rows parsed: {}", self.num_rows)?;
+        writeln!(f, "  rows rejected: {}", self.num_rows_rejected)?;
+        writeln!(f, "  lines emitted: {}", self.num_lines_emitted)?;
+        writeln!(f, "  wall time: {:.3}s", self.elapsed.as_secs_f64())?;
+        if self.interrupted {
+            writeln!(f, "  interrupted: remaining input was not processed")?;
+        }
+        Ok(())
+    }
+}
+
+// Processes the batch of rows of a graph, collecting its output on a channel of its own,
+// so that it is attributed to the right graph even for transformers which process graphs
+// asynchronously.
+fn collect_graph<T: TransformerBase + ?Sized>(
+    transformer: &mut T,
+    graph_id: GraphId,
+) -> CLQResult<Vec<String>> {
+    let (graph_sender, graph_receiver) = channel();
+    transformer.process_batch(graph_id, &graph_sender)?;
+    drop(graph_sender);
+    Ok(graph_receiver
+        .into_iter()
+        .filter_map(|(line, _)| line)
+        .collect())
+}
+
+// Processes the batch of rows of a graph. If a cache is provided, along with the graph_id
+// as written in the input and the hash of the graph's lines, output is taken from the cache
+// when it has an entry for them, and otherwise recorded in it. If marker_graph_id (the
+// graph_id as written in the input) is provided, output is bracketed with graph markers
+// (see `Output::with_graph_markers`). Either way, the output of the graph is collected
+// before being forwarded (see collect_graph).
+fn process_graph<T: TransformerBase + ?Sized>(
+    transformer: &mut T,
+    graph_id: GraphId,
+    cache_key: Option<(&mut GraphCache, &str, u64)>,
+    marker_graph_id: Option<&str>,
+    output: &Sender<(Option<String>, bool)>,
+) -> CLQResult<()> {
+    let lines: Vec<String> = match cache_key {
+        Some((cache, input_graph_id, hash)) => match cache.get(input_graph_id, hash) {
+            Some(lines) => lines,
+            None => {
+                let lines = collect_graph(transformer, graph_id)?;
+                cache.insert(input_graph_id, hash, lines.clone());
+                lines
+            }
+        },
+        None if marker_graph_id.is_some() => collect_graph(transformer, graph_id)?,
+        None => return transformer.process_batch(graph_id, output),
+    };
+    if let Some(marker_graph_id) = marker_graph_id {
+        output
+            .send((Some(get_graph_begin_marker(marker_graph_id)), false))
+            .unwrap();
+    }
+    let num_lines = lines.len();
+    if lines.is_empty() {
+        output.send((None, false)).unwrap();
+    }
+    for line in lines {
+        output.send((Some(line), false)).unwrap();
+    }
+    if let Some(marker_graph_id) = marker_graph_id {
+        output
+            .send((
+                Some(get_graph_end_marker(marker_graph_id, num_lines)),
+                false,
+            ))
+            .unwrap();
+    }
+    Ok(())
+}
+
+// Main loop of `TransformerBase::run_with_parsing_threads`, over lines already read and
+// parsed (from any source), numbered from first_line_num on. line_processor gives the
+// graph_id of lines as written in the input (see `LineProcessorBase::get_graph_key`), and
+// releases the state it keeps for each graph once the graph is processed.
+fn run_parsed_lines<'a, T: TransformerBase + ?Sized>(
+    transformer: &mut T,
+    lines: Box<dyn Iterator<Item = ParsedLine> + 'a>,
+    mut output: Output,
+    mut rejects: Option<&mut dyn Write>,
+    mut cache: Option<&mut GraphCache>,
+    line_processor: &dyn LineProcessorBase,
+    first_line_num: usize,
+) -> CLQResult<RunSummary> {
+    let start = Instant::now();
+    let num_hits_before: usize = cache.as_ref().map_or(0, |x| x.num_hits);
+    let ret = crossbeam::scope(|scope| {
+        let graph_markers = output.has_graph_markers();
+        let num_processed = Arc::new(AtomicUsize::new(0_usize));
+        let (sender, receiver) = channel();
+        let num_processed_clone = num_processed.clone();
+        // returns (lines emitted, acknowledgements without output).
+        let writer = scope.spawn(move |_| {
+            let mut num_lines: usize = 0;
+            let mut num_empty: usize = 0;
+            loop {
+                match receiver.recv() {
+                    Ok((line, shutdown)) => {
+                        if shutdown {
+                            return (num_lines, num_empty);
+                        }
+                        match line {
+                            Some(string) => {
+                                output.print(string).unwrap();
+                                num_lines += 1;
+                            }
+                            None => num_empty += 1,
+                        }
+                        num_processed_clone.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // the reader stopped early, on an error.
+                    Err(_) => return (num_lines, num_empty),
+                }
+            }
+        });
+        let mut summary = RunSummary::default();
+        let mut current_graph_id: Option<GraphId> = None;
+        let mut num_to_process: usize = 0;
+        // graph_id as written in the input, and hash of the lines, of the current graph.
+        let mut input_graph_id = String::new();
+        let mut rows_hasher = RowsHasher::default();
+        for (line_ix, line) in lines.enumerate() {
+            if is_interrupted() {
+                summary.interrupted = true;
+                break;
+            }
+            let line_num = line_ix + first_line_num;
+            match line {
+                ParsedLine::Skipped => summary.num_lines_skipped += 1,
+                ParsedLine::Row(row, raw) => {
+                    summary.num_rows += 1;
+                    let new_graph_id: GraphId = row.get_graph_id();
+                    if let Some(some_current_graph_id) = current_graph_id {
+                        if new_graph_id != some_current_graph_id {
+                            let cache_key = cache
+                                .as_deref_mut()
+                                .map(|x| (x, input_graph_id.as_str(), rows_hasher.finish()));
+                            let marker_graph_id = graph_markers.then_some(input_graph_id.as_str());
+                            process_graph(
+                                transformer,
+                                some_current_graph_id,
+                                cache_key,
+                                marker_graph_id,
+                                &sender,
+                            )?;
+                            line_processor.release_graph(some_current_graph_id);
+                            num_to_process += 1;
+                            transformer.reset()?;
+                            rows_hasher = RowsHasher::default();
+                        }
+                    }
+                    current_graph_id = Some(new_graph_id);
+                    if let Some(raw) = raw.as_ref().filter(|_| cache.is_some() || graph_markers) {
+                        input_graph_id = line_processor.get_graph_key(raw);
+                        if cache.is_some() {
+                            rows_hasher.add_line(raw);
+                        }
+                    }
+                    transformer.process_row(row)?;
+                }
+                ParsedLine::Invalid(error, raw) => match rejects.as_mut() {
+                    Some(rejects) => {
+                        writeln!(rejects, "{line_num}\t{error}\t{}", raw.unwrap())?;
+                        summary.num_rows_rejected += 1;
+                    }
+                    None => {
+                        return Err(CLQError::Line {
+                            line_num,
+                            error: Box::new(error),
+                        })
+                    }
+                },
+                ParsedLine::Unreadable(error) => {
+                    match rejects.as_mut() {
+                        Some(rejects) => writeln!(rejects, "{line_num}\tI/O error: {error}\t")?,
+                        None => eprintln!("Line {line_num}: I/O error: {error}"),
+                    }
+                    summary.num_rows_rejected += 1;
+                }
+            }
+        }
+        if summary.interrupted {
+            transformer.reset()?;
+            if let Some(some_current_graph_id) = current_graph_id {
+                line_processor.release_graph(some_current_graph_id);
+            }
+        } else if let Some(some_current_graph_id) = current_graph_id {
+            let cache_key = cache
+                .as_deref_mut()
+                .map(|x| (x, input_graph_id.as_str(), rows_hasher.finish()));
+            let marker_graph_id = graph_markers.then_some(input_graph_id.as_str());
+            process_graph(
+                transformer,
+                some_current_graph_id,
+                cache_key,
+                marker_graph_id,
+                &sender,
+            )?;
+            line_processor.release_graph(some_current_graph_id);
+            num_to_process += 1;
+        } else {
+            return Err("No input rows!".into());
+        }
+        while num_to_process > num_processed.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(100));
+        }
+        sender.send((None, true)).unwrap();
+        let (num_lines, num_empty) = writer.join().unwrap();
+        summary.num_graphs = num_to_process;
+        summary.num_graphs_cached = cache.as_ref().map_or(0, |x| x.num_hits) - num_hits_before;
+        summary.num_graphs_skipped = num_empty;
+        // marker rows aren't counted as output.
+        summary.num_lines_emitted = if graph_markers {
+            num_lines - 2 * num_to_process
+        } else {
+            num_lines
+        };
+        summary.elapsed = start.elapsed();
+        Ok(summary)
+    });
+    ret.unwrap()
+}
 
 pub trait TransformerBase {
     fn get_line_processor(&self) -> Arc<dyn LineProcessorBase>;
@@ -32,61 +405,201 @@ pub trait TransformerBase {
     ) -> CLQResult<()>;
     // reset transformer state after processing;
     fn reset(&mut self) -> CLQResult<()>;
+    // short description of the kind of row, used to tally rows in dry runs.
+    fn describe_row(&self, row: &dyn Row) -> String {
+        if row.as_edge_row().is_some() {
+            "edge".to_owned()
+        } else if row.as_clique_row().is_some() {
+            "clique".to_owned()
+        } else if row.as_weighted_edge_row().is_some() {
+            "weighted edge".to_owned()
+        } else if row.as_simple_edge_row().is_some() {
+            "simple edge".to_owned()
+        } else {
+            "other".to_owned()
+        }
+    }
 
-    // main loop, runs through lines ordered by graph_id, updates state accordingly
-    // and runs process_batch when graph_id changes
-    fn run(&mut self, input: Input, mut output: Output) -> CLQResult<()> {
-        let ret = crossbeam::scope(|scope| {
-            let line_processor = self.get_line_processor();
-            let num_processed = Arc::new(AtomicUsize::new(0_usize));
-            let (sender, receiver) = channel();
-            let num_processed_clone = num_processed.clone();
-            let writer = scope.spawn(move |_| loop {
-                match receiver.recv() {
-                    Ok((line, shutdown)) => {
-                        if shutdown {
-                            return;
-                        }
-                        if let Some(string) = line {
-                            output.print(string).unwrap();
-                        }
-                        num_processed_clone.fetch_add(1, Ordering::SeqCst);
-                    }
-                    Err(error) => panic!("{}", error),
+    // line processor to parse lines of input with: that of get_line_processor, wrapped
+    // in a DelimitedLineProcessor if lines of input aren't tab-separated (see
+    // `Input::with_line_format`). If they start with a header, it is read off input here.
+    fn get_input_line_processor(&self, input: &mut Input) -> CLQResult<Arc<dyn LineProcessorBase>> {
+        let line_processor = self.get_line_processor();
+        let format = input.get_line_format().clone();
+        if format.is_tab_separated() {
+            return Ok(line_processor);
+        }
+        let mut delimited = DelimitedLineProcessor::new(format.clone(), line_processor);
+        if format.header {
+            let mut header = String::new();
+            if input.read_line(&mut header)? == 0 {
+                return Err("No header line in input!".into());
+            }
+            delimited = delimited.with_header(header.trim_end_matches(['\n', '\r']))?;
+        }
+        Ok(Arc::new(delimited))
+    }
+
+    // parses (at most) the first max_lines lines of input without processing any
+    // graphs, reporting row counts and any lines that could not be parsed. Rows of a
+    // graph_id that are not contiguous are also reported, since run expects input
+    // sorted by graph_id.
+    fn dry_run(&self, mut input: Input, max_lines: usize) -> DryRunReport {
+        let mut report = DryRunReport::default();
+        let line_processor = match self.get_input_line_processor(&mut input) {
+            Ok(line_processor) => line_processor,
+            Err(error) => {
+                report.issues.push((1, error.to_string()));
+                return report;
+            }
+        };
+        // lines are numbered as in input, header included.
+        let num_header_lines = usize::from(input.get_line_format().header);
+        let mut seen_graph_ids: HashSet<GraphId> = HashSet::new();
+        for (line_ix, line) in input.lines().take(max_lines).enumerate() {
+            let line_num = line_ix + 1 + num_header_lines;
+            report.num_lines += 1;
+            let row = match line {
+                Ok(n) if line_processor.is_skipped(&n) => {
+                    report.num_lines_skipped += 1;
+                    continue;
                 }
-            });
-            let mut current_graph_id: Option<GraphId> = None;
-            let mut num_to_process: usize = 0;
-            for line in input.lines() {
-                match line {
-                    Ok(n) => {
-                        let row: Box<dyn Row> = line_processor.process_line(n)?;
-                        let new_graph_id: GraphId = row.get_graph_id();
-                        if let Some(some_current_graph_id) = current_graph_id {
-                            if new_graph_id != some_current_graph_id {
-                                self.process_batch(some_current_graph_id, &sender.clone())?;
-                                num_to_process += 1;
-                                self.reset()?;
+                Ok(n) => line_processor.process_line(n),
+                Err(error) => Err(error.into()),
+            };
+            match row {
+                Ok(row) => {
+                    report.num_rows += 1;
+                    *report
+                        .rows_per_type
+                        .entry(self.describe_row(row.as_ref()))
+                        .or_insert(0) += 1;
+                    let graph_id = row.get_graph_id();
+                    match report.rows_per_graph.last_mut() {
+                        Some((last_id, count)) if *last_id == graph_id => *count += 1,
+                        _ => {
+                            if !seen_graph_ids.insert(graph_id) {
+                                report.issues.push((
+                                    line_num,
+                                    format!(
+                                        "rows for graph_id {} are not contiguous",
+                                        graph_id.value()
+                                    ),
+                                ));
                             }
+                            report.rows_per_graph.push((graph_id, 1));
                         }
-                        current_graph_id = Some(new_graph_id);
-                        self.process_row(row)?;
                     }
-                    Err(error) => eprintln!("I/O error: {error}"),
-                }
-            }
-            if let Some(some_current_graph_id) = current_graph_id {
-                self.process_batch(some_current_graph_id, &sender)?;
-                num_to_process += 1;
-                while num_to_process > num_processed.load(Ordering::SeqCst) {
-                    thread::sleep(Duration::from_millis(100));
                 }
-                sender.send((None, true)).unwrap();
-                writer.join().unwrap();
-                return Ok(());
+                Err(error) => report.issues.push((line_num, error.to_string())),
             }
-            Err("No input rows!".into())
+        }
+        report
+    }
+
+    // main loop, runs through lines ordered by graph_id, updates state accordingly
+    // and runs process_batch when graph_id changes. Returns totals for the run.
+    // If interrupted, stops reading input, drops the rows of the graph being read
+    // (so as not to emit results for a partial graph) and flushes pending output.
+    // Stops at the first line that cannot be parsed, reporting its line number.
+    // If output has graph markers (see `Output::with_graph_markers`), the output of each
+    // graph is bracketed by marker rows naming it by its graph_id as written in the
+    // input, and graphs are processed one at a time.
+    fn run(&mut self, input: Input, output: Output) -> CLQResult<RunSummary> {
+        self.run_with_rejects(input, output, None)
+    }
+
+    // as run, but if rejects is provided lines that cannot be read or parsed are written
+    // to it, as line_num\terror\tline, and skipped rather than ending the run.
+    fn run_with_rejects(
+        &mut self,
+        input: Input,
+        output: Output,
+        rejects: Option<&mut dyn Write>,
+    ) -> CLQResult<RunSummary> {
+        self.run_with_cache(input, output, rejects, None)
+    }
+
+    // as run_with_rejects, but if cache is provided the output of graphs whose lines are
+    // unchanged since the cache was saved is taken from it rather than computed, and the
+    // output of all other graphs is recorded in it (to be saved by the caller). Graphs are
+    // keyed by their graph_id as written in the input (the first field of each line) and
+    // a hash of their lines. With a cache, graphs are processed one at a time.
+    fn run_with_cache(
+        &mut self,
+        input: Input,
+        output: Output,
+        rejects: Option<&mut dyn Write>,
+        cache: Option<&mut GraphCache>,
+    ) -> CLQResult<RunSummary> {
+        self.run_with_parsing_threads(input, output, rejects, cache, 0)
+    }
+
+    // as run_with_cache, but if num_parsing_threads is positive lines are read by another
+    // thread and parsed by num_parsing_threads more, ahead of process_row (see
+    // `ParsedLines`), rather than read and parsed one at a time in between rows.
+    fn run_with_parsing_threads(
+        &mut self,
+        mut input: Input,
+        output: Output,
+        rejects: Option<&mut dyn Write>,
+        cache: Option<&mut GraphCache>,
+        num_parsing_threads: usize,
+    ) -> CLQResult<RunSummary> {
+        let line_processor = self.get_input_line_processor(&mut input)?;
+        // lines are numbered as in input, header included.
+        let num_header_lines = usize::from(input.get_line_format().header);
+        let ret = crossbeam::scope(|scope| {
+            let keep_lines = rejects.is_some() || cache.is_some() || output.has_graph_markers();
+            let lines: Box<dyn Iterator<Item = ParsedLine>> = if num_parsing_threads > 0 {
+                Box::new(ParsedLines::spawn(
+                    scope,
+                    input,
+                    line_processor.clone(),
+                    num_parsing_threads,
+                    keep_lines,
+                ))
+            } else {
+                Box::new(input.lines().map({
+                    let line_processor = line_processor.clone();
+                    move |line| parse_line(line_processor.as_ref(), line, keep_lines)
+                }))
+            };
+            run_parsed_lines(
+                self,
+                lines,
+                output,
+                rejects,
+                cache,
+                line_processor.as_ref(),
+                num_header_lines + 1,
+            )
         });
         ret.unwrap()
     }
+
+    // as run_with_cache, but reads rows from a Parquet file (see `ParquetInput`) rather than
+    // from lines of text. Rows are numbered from 1 in the order they are read, and written
+    // to rejects (or hashed for cache) as tab-separated lines.
+    #[cfg(feature = "parquet")]
+    fn run_parquet(
+        &mut self,
+        input: ParquetInput,
+        output: Output,
+        rejects: Option<&mut dyn Write>,
+        cache: Option<&mut GraphCache>,
+    ) -> CLQResult<RunSummary> {
+        let line_processor = self.get_line_processor();
+        let keep_lines = rejects.is_some() || cache.is_some() || output.has_graph_markers();
+        let lines = input.parsed_lines(line_processor.clone(), keep_lines)?;
+        run_parsed_lines(
+            self,
+            Box::new(lines),
+            output,
+            rejects,
+            cache,
+            line_processor.as_ref(),
+            1,
+        )
+    }
 }
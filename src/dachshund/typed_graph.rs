@@ -6,18 +6,30 @@
  */
 extern crate fxhash;
 extern crate nalgebra as na;
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::algorithms::assortativity::{Assortativity, TypeAssortativity};
+use crate::dachshund::algorithms::bipartite_cores::BipartiteCores;
+use crate::dachshund::algorithms::bipartite_matching::BipartiteMatching;
+use crate::dachshund::algorithms::bipartite_modularity::BipartiteModularity;
+use crate::dachshund::algorithms::butterflies::Butterflies;
+use crate::dachshund::algorithms::fraudar::Fraudar;
+use crate::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
 use crate::dachshund::id_types::NodeLabel;
 use crate::dachshund::node::Node;
 use fxhash::FxHashMap;
 use std::collections::hash_map::{Keys, Values};
 
-pub trait LabeledGraph: GraphBase {
+pub trait LabeledGraph: BipartiteGraphBase {
     fn get_core_labels(&self) -> Vec<NodeLabel>;
     fn get_non_core_labels(&self) -> Option<Vec<NodeLabel>>;
     fn get_node_by_label(&self, node_id: NodeLabel) -> &Node;
     fn has_node_by_label(&self, node_id: NodeLabel) -> bool;
     fn get_reverse_labels_map(&self) -> FxHashMap<u32, NodeLabel>;
+    /// iterates over the labels of all nodes in the graph (in no particular order).
+    fn get_labels_iter(&self) -> Keys<'_, NodeLabel, u32>;
+    /// returns the label of the node with the given internal id.
+    fn get_node_label(&self, node_id: u32) -> Option<NodeLabel>;
+    /// whether a node with the given label was ever added to the graph.
+    fn has_label(&self, label: NodeLabel) -> bool;
 }
 
 /// Keeps track of a bipartite graph composed of "core" and "non-core" nodes. Only core ->
@@ -25,12 +37,14 @@ pub trait LabeledGraph: GraphBase {
 /// neighbors of non-core nodes are cores. Graph edges are stored in the neighbors field of
 /// each node. If the id of a node is known, its Node object can be retrieved via the
 /// nodes HashMap. To iterate over core and non-core nodes, the struct also provides the
-/// core_ids and non_core_ids vectors.
+/// core_ids and non_core_ids vectors. Internal ids run from 0 to the number of nodes, and
+/// labels holds the label of each, by internal id.
 pub struct TypedGraph {
     pub nodes: FxHashMap<u32, Node>,
     pub core_ids: Vec<u32>,
     pub non_core_ids: Vec<u32>,
     pub labels_map: FxHashMap<NodeLabel, u32>,
+    pub labels: Vec<NodeLabel>,
 }
 impl LabeledGraph for TypedGraph {
     fn get_core_labels(&self) -> Vec<NodeLabel> {
@@ -64,16 +78,26 @@ impl LabeledGraph for TypedGraph {
             .map(|(label, node_id)| (*node_id, *label))
             .collect()
     }
+    fn get_labels_iter(&self) -> Keys<'_, NodeLabel, u32> {
+        self.labels_map.keys()
+    }
+    fn get_node_label(&self, node_id: u32) -> Option<NodeLabel> {
+        self.labels.get(node_id as usize).copied()
+    }
+    fn has_label(&self, label: NodeLabel) -> bool {
+        self.labels_map.contains_key(&label)
+    }
 }
-impl GraphBase for TypedGraph {
-    type NodeType = Node;
-
-    fn get_core_ids(&self) -> &Vec<u32> {
+impl BipartiteGraphBase for TypedGraph {
+    fn get_core_ids(&self) -> &[u32] {
         &self.core_ids
     }
-    fn get_non_core_ids(&self) -> Option<&Vec<u32>> {
-        Some(&self.non_core_ids)
+    fn get_non_core_ids(&self) -> &[u32] {
+        &self.non_core_ids
     }
+}
+impl GraphBase for TypedGraph {
+    type NodeType = Node;
 
     fn get_ids_iter(&self) -> Keys<u32, Node> {
         self.nodes.keys()
@@ -106,6 +130,14 @@ impl GraphBase for TypedGraph {
             core_ids: Vec::new(),
             non_core_ids: Vec::new(),
             labels_map: FxHashMap::default(),
+            labels: Vec::new(),
         }
     }
 }
+impl Assortativity for TypedGraph {}
+impl BipartiteCores for TypedGraph {}
+impl BipartiteMatching for TypedGraph {}
+impl BipartiteModularity for TypedGraph {}
+impl Butterflies for TypedGraph {}
+impl Fraudar for TypedGraph {}
+impl TypeAssortativity for TypedGraph {}
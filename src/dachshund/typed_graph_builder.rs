@@ -10,16 +10,21 @@ use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::graph_builder_base::{GraphBuilderBase, GraphBuilderBaseWithPreProcessing};
 use crate::dachshund::id_types::{EdgeTypeId, GraphId, NodeLabel, NodeTypeId};
-use crate::dachshund::node::{Node, NodeBase, NodeEdge};
+use crate::dachshund::node::{Node, NodeBase, NodeEdge, NodeEdges, TypedNodeBase};
 use crate::dachshund::row::EdgeRow;
 use crate::dachshund::typed_graph::{LabeledGraph, TypedGraph};
 use fxhash::FxHashMap;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 /// In the TypedGraph world, we use the type NodeLabel as an alias for the NodeId
 /// type. Internally we represent node ids with u32s of 0...n.
 pub struct TypedGraphBuilder {
     pub min_degree: Option<usize>,
+    // min degree of non-core nodes, if different from min_degree (which then only applies to
+    // core nodes), pruning graphs to their (min_degree, non_core_min_degree)-core.
+    pub non_core_min_degree: Option<usize>,
+    // see `TypedGraphBuilderBase::trim_edges_by_type`.
+    pub edge_type_min_degree: BTreeMap<EdgeTypeId, usize>,
     pub graph_id: GraphId,
 }
 impl GraphBuilderBase for TypedGraphBuilder {
@@ -47,8 +52,18 @@ impl GraphBuilderBase for TypedGraphBuilder {
             Self::init_nodes(&source_labels_vec, &target_labels_vec, &target_type_ids);
         Self::populate_edges(&rows, &mut node_map, &labels_map)?;
         let mut graph = Self::create_graph(node_map, source_ids_vec, target_ids_vec, labels_map)?;
-        if let Some(min_degree) = self.min_degree {
-            graph = Self::prune(graph, &rows, min_degree)?;
+        if self.min_degree.is_some()
+            || self.non_core_min_degree.is_some()
+            || !self.edge_type_min_degree.is_empty()
+        {
+            let min_degree = self.min_degree.unwrap_or(0);
+            graph = Self::prune_bipartite(
+                graph,
+                &rows,
+                min_degree,
+                self.non_core_min_degree.unwrap_or(min_degree),
+                &self.edge_type_min_degree,
+            )?;
         }
         Ok(graph)
     }
@@ -61,11 +76,28 @@ pub trait TypedGraphBuilderBase {
         non_core_ids: Vec<u32>,
         labels_map: FxHashMap<NodeLabel, u32>,
     ) -> CLQResult<TypedGraph> {
+        // internal ids are given in order from 0 (see init_nodes), each to a single label.
+        let mut labels: Vec<Option<NodeLabel>> = vec![None; labels_map.len()];
+        for (label, id) in labels_map.iter() {
+            match labels.get_mut(*id as usize) {
+                Some(slot @ None) => *slot = Some(*label),
+                _ => {
+                    return Err(CLQError::from(format!(
+                        "Internal node ids must run from 0 to the number of nodes, one per \
+                         label (found {} for node {})",
+                        id,
+                        label.value()
+                    )))
+                }
+            }
+        }
+        let labels: Vec<NodeLabel> = labels.into_iter().flatten().collect();
         Ok(TypedGraph {
             nodes,
             core_ids,
             non_core_ids,
             labels_map,
+            labels,
         })
     }
 
@@ -141,11 +173,11 @@ pub trait TypedGraphBuilderBase {
         let mut non_core_idxs: Vec<u32> = Vec::with_capacity(non_core_ids.len());
         for &id in core_ids {
             let node = Node::new(
-                i,              // node_id,
-                true,           // is_core,
-                None,           // non_core_type,
-                Vec::new(),     // edges,
-                HashMap::new(), //neighbors
+                i,                // node_id,
+                true,             // is_core,
+                None,             // non_core_type,
+                NodeEdges::new(), // edges,
+                HashMap::new(),   //neighbors
             );
             node_map.insert(i, node);
             core_idxs.push(i);
@@ -157,7 +189,7 @@ pub trait TypedGraphBuilderBase {
                 i,                            // node_id,
                 false,                        // is_core,
                 Some(non_core_type_ids[&id]), // non_core_type,
-                Vec::new(),                   // edges,
+                NodeEdges::new(),             // edges,
                 HashMap::new(),               // neighbors
             );
             node_map.insert(i, node);
@@ -172,16 +204,64 @@ pub trait TypedGraphBuilderBase {
     /// Note that this function does not delete any nodes -- just finds nodes to delete. It is
     /// called by `prune`, which actually does the deletion.
     fn trim_edges(node_map: &mut FxHashMap<u32, Node>, min_degree: &usize) -> HashSet<u32> {
+        Self::trim_edges_by_type(node_map, *min_degree, &BTreeMap::new())
+    }
+
+    /// Trims edges greedily, as `trim_edges` does, until all nodes have degree at least
+    /// min_degree, and all core nodes have at least the number of neighbors given in
+    /// edge_type_min_degree through edges of each type (counted from `neighbors_sets`, so
+    /// that repeated ties of one type to a node count once).
+    fn trim_edges_by_type(
+        node_map: &mut FxHashMap<u32, Node>,
+        min_degree: usize,
+        edge_type_min_degree: &BTreeMap<EdgeTypeId, usize>,
+    ) -> HashSet<u32> {
+        Self::trim_edges_bipartite(node_map, min_degree, min_degree, edge_type_min_degree)
+    }
+
+    /// Trims edges greedily, as `trim_edges_by_type` does, but with distinct min degrees
+    /// for core nodes (alpha) and non-core nodes (beta): the nodes left form the
+    /// (alpha, beta)-core of the graph, its largest subgraph in which every core node has
+    /// at least alpha neighbors and every non-core node at least beta.
+    fn trim_edges_bipartite(
+        node_map: &mut FxHashMap<u32, Node>,
+        core_min_degree: usize,
+        non_core_min_degree: usize,
+        edge_type_min_degree: &BTreeMap<EdgeTypeId, usize>,
+    ) -> HashSet<u32> {
+        let min_degree = |node_id: &u32| {
+            if node_map[node_id].is_core() {
+                core_min_degree
+            } else {
+                non_core_min_degree
+            }
+        };
         let mut degree_map: HashMap<u32, usize> = HashMap::new();
+        // neighbors of core nodes through edges of each type with a min degree.
+        let mut type_degree_map: HashMap<(u32, EdgeTypeId), usize> = HashMap::new();
         for (node_id, node) in node_map.iter() {
             let node_degree: usize = node.degree();
             degree_map.insert(*node_id, node_degree);
+            if node.is_core() {
+                for edge_type in edge_type_min_degree.keys() {
+                    let type_degree = node.count_neighbors_of_type(*edge_type);
+                    type_degree_map.insert((*node_id, *edge_type), type_degree);
+                }
+            }
         }
         let mut nodes_to_delete: HashSet<u32> = HashSet::new();
         loop {
             let mut nodes_to_update: HashSet<u32> = HashSet::new();
             for (node_id, node_degree) in degree_map.iter() {
-                if node_degree < min_degree && !nodes_to_delete.contains(node_id) {
+                if *node_degree < min_degree(node_id) && !nodes_to_delete.contains(node_id) {
+                    nodes_to_update.insert(*node_id);
+                    nodes_to_delete.insert(*node_id);
+                }
+            }
+            for ((node_id, edge_type), type_degree) in type_degree_map.iter() {
+                if *type_degree < edge_type_min_degree[edge_type]
+                    && !nodes_to_delete.contains(node_id)
+                {
                     nodes_to_update.insert(*node_id);
                     nodes_to_delete.insert(*node_id);
                 }
@@ -196,6 +276,15 @@ pub trait TypedGraphBuilderBase {
                     let current_degree: usize = degree_map[&neighbor_node_id];
                     degree_map.insert(neighbor_node_id, current_degree - 1);
                 }
+                for (edge_type, neighbors) in node.neighbors_sets.iter() {
+                    for neighbor_node_id in neighbors.iter() {
+                        if let Some(type_degree) =
+                            type_degree_map.get_mut(&(neighbor_node_id, *edge_type))
+                        {
+                            *type_degree -= 1;
+                        }
+                    }
+                }
             }
         }
         nodes_to_delete
@@ -206,12 +295,40 @@ pub trait TypedGraphBuilderBase {
     /// The provision of a <Self as GraphBuilderBase>::GraphType is necessary, since the notion of "degree" does
     /// not make sense outside of a graph.
     fn prune(graph: TypedGraph, rows: &[EdgeRow], min_degree: usize) -> CLQResult<TypedGraph> {
+        Self::prune_by_type(graph, rows, min_degree, &BTreeMap::new())
+    }
+
+    /// Prunes graph as `prune` does, also enforcing the per-edge-type degrees of core nodes
+    /// in edge_type_min_degree (see `trim_edges_by_type`).
+    fn prune_by_type(
+        graph: TypedGraph,
+        rows: &[EdgeRow],
+        min_degree: usize,
+        edge_type_min_degree: &BTreeMap<EdgeTypeId, usize>,
+    ) -> CLQResult<TypedGraph> {
+        Self::prune_bipartite(graph, rows, min_degree, min_degree, edge_type_min_degree)
+    }
+
+    /// Prunes graph as `prune_by_type` does, to its (core_min_degree, non_core_min_degree)-
+    /// core (see `trim_edges_bipartite`).
+    fn prune_bipartite(
+        graph: TypedGraph,
+        rows: &[EdgeRow],
+        core_min_degree: usize,
+        non_core_min_degree: usize,
+        edge_type_min_degree: &BTreeMap<EdgeTypeId, usize>,
+    ) -> CLQResult<TypedGraph> {
         let mut target_type_ids: HashMap<NodeLabel, NodeTypeId> = HashMap::new();
         for r in rows.iter() {
             target_type_ids.insert(r.target_id, r.target_type_id);
         }
         let (filtered_source_labels, filtered_target_labels, filtered_rows) =
-            Self::get_filtered_sources_targets_rows(graph, min_degree, rows);
+            Self::get_filtered_sources_targets_rows(
+                graph,
+                (core_min_degree, non_core_min_degree),
+                edge_type_min_degree,
+                rows,
+            );
         let (mut filtered_node_map, filtered_label_map, filtered_source_ids, filtered_target_ids) =
             Self::init_nodes(
                 &filtered_source_labels,
@@ -228,30 +345,37 @@ pub trait TypedGraphBuilderBase {
     }
     /// called by `prune`, finds source and target nodes to exclude, as well as edges to exclude
     /// when rebuilding the graph from a filtered vector of `EdgeRows`.
+    /// min_degrees are the min degrees of core and non-core nodes.
     fn get_filtered_sources_targets_rows(
         mut graph: TypedGraph,
-        min_degree: usize,
+        min_degrees: (usize, usize),
+        edge_type_min_degree: &BTreeMap<EdgeTypeId, usize>,
         rows: &[EdgeRow],
     ) -> (Vec<NodeLabel>, Vec<NodeLabel>, Vec<EdgeRow>) {
-        let exclude_nodes: HashSet<u32> = Self::trim_edges(graph.get_mut_nodes(), &min_degree);
+        let exclude_nodes: HashSet<u32> = Self::trim_edges_bipartite(
+            graph.get_mut_nodes(),
+            min_degrees.0,
+            min_degrees.1,
+            edge_type_min_degree,
+        );
         let filtered_source_ids: Vec<NodeLabel> = graph
             .get_core_labels()
             .iter()
-            .filter(|x| !exclude_nodes.contains(&graph.labels_map[x]))
+            .filter(|x| !exclude_nodes.contains(&graph.get_node_by_label(**x).node_id))
             .cloned()
             .collect();
         let filtered_target_ids: Vec<NodeLabel> = graph
             .get_non_core_labels()
             .unwrap()
             .iter()
-            .filter(|x| !exclude_nodes.contains(&graph.labels_map[x]))
+            .filter(|x| !exclude_nodes.contains(&graph.get_node_by_label(**x).node_id))
             .cloned()
             .collect();
         let filtered_rows: Vec<EdgeRow> = rows
             .iter()
             .filter(|x| {
-                !(exclude_nodes.contains(&graph.labels_map[&x.source_id])
-                    || (exclude_nodes.contains(&graph.labels_map[&x.target_id])))
+                !(exclude_nodes.contains(&graph.get_node_by_label(x.source_id).node_id)
+                    || (exclude_nodes.contains(&graph.get_node_by_label(x.target_id).node_id)))
             })
             .cloned()
             .collect();
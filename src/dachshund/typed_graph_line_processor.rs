@@ -7,24 +7,48 @@
 extern crate clap;
 extern crate serde_json;
 
-use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::error::CLQResult;
 use crate::dachshund::id_types::{EdgeTypeId, GraphId, NodeId, NodeTypeId};
-use crate::dachshund::line_processor::LineProcessorBase;
+use crate::dachshund::line_processor::{FieldValue, LineProcessorBase, LineSchema};
+use crate::dachshund::node_labels::{GraphNodeLabels, NodeLabels};
 use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
 use crate::dachshund::row::Row;
 use crate::dachshund::row::{CliqueRow, EdgeRow};
-use std::rc::Rc;
+use std::sync::Arc;
+
+pub const TYPED_EDGE_SCHEMA: LineSchema = LineSchema {
+    fields: &[
+        "graph_id",
+        "core_id",
+        "non_core_id",
+        "core_type",
+        "edge_type",
+        "non_core_type",
+    ],
+};
+// clique rows leave the last three fields empty.
+pub const TYPED_CLIQUE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "node_id", "node_type", "", "", ""],
+};
 
 /// Processing lines for typed graphs
 /// Can mutate ids and reverse_ids maps that keep track of
 /// graph_ids seen so far.
 pub struct TypedGraphLineProcessor {
     pub core_type: String,
-    pub non_core_type_ids: Rc<NonCoreTypeIds>,
-    pub non_core_types: Rc<Vec<String>>,
-    pub edge_types: Rc<Vec<String>>,
+    pub non_core_type_ids: Arc<NonCoreTypeIds>,
+    pub non_core_types: Arc<Vec<String>>,
+    pub edge_types: Arc<Vec<String>>,
+    node_labels: Option<Arc<GraphNodeLabels>>,
 }
 impl LineProcessorBase for TypedGraphLineProcessor {
+    /// Blank lines and lines starting with '#' (comments) are skipped.
+    fn is_skipped(&self, line: &str) -> bool {
+        line.trim().is_empty() || line.starts_with('#')
+    }
+    fn get_schema(&self) -> Option<&'static LineSchema> {
+        Some(&TYPED_EDGE_SCHEMA)
+    }
     /// processes a line of (tab-separated) input, of the form:
     /// graph_id\tcore_id\tnon_core_id\tcore_type\tedge_type\tnon_core_type
     ///
@@ -38,22 +62,32 @@ impl LineProcessorBase for TypedGraphLineProcessor {
     /// clique may be invalidated if it no longer meets cliqueness requirements
     /// as per the current search process.
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
-        let vec: Vec<&str> = line.split('\t').collect();
+        // both kinds of rows have the same number of fields.
+        let vec: Vec<&str> = TYPED_EDGE_SCHEMA.split(&line)?;
+        self.process_fields(&vec)
+    }
+    fn process_values(&self, values: &[FieldValue]) -> CLQResult<Box<dyn Row>> {
         // this is an edge row if we have something on column 3
-        assert!(vec.len() == 6);
-        let is_edge_row: bool = !vec[3].is_empty();
+        let is_edge_row: bool = !values[3].is_empty();
         if is_edge_row {
-            let graph_id: GraphId = vec[0].parse::<i64>()?.into();
-            let core_id: NodeId = vec[1].parse::<i64>()?.into();
-            let non_core_id: NodeId = vec[2].parse::<i64>()?.into();
-            let edge_type: &str = vec[4].trim_end();
-            let non_core_type: &str = vec[5].trim_end();
-            let non_core_type_id: NodeTypeId = *self.non_core_type_ids.require(non_core_type)?;
+            let schema = &TYPED_EDGE_SCHEMA;
+            let graph_id: GraphId = schema.get_i64(values, 0)?.into();
+            let labels = self.get_graph_labels(graph_id);
+            let core_id: NodeId = schema.get_node_id(values, 1, labels.as_deref())?;
+            let non_core_id: NodeId = schema.get_node_id(values, 2, labels.as_deref())?;
+            let edge_type: &str = schema.get_str(values, 4)?;
+            let non_core_type: &str = schema.get_str(values, 5)?;
+            let non_core_type_id: NodeTypeId = *self
+                .non_core_type_ids
+                .require(non_core_type)
+                .map_err(|err| schema.value_error(values, 5, err.to_string()))?;
             let edge_type_id: EdgeTypeId = self
                 .edge_types
                 .iter()
                 .position(|r| r == edge_type)
-                .ok_or_else(CLQError::err_none)?
+                .ok_or_else(|| {
+                    schema.value_error(values, 4, "not an edge type in the typespec".to_owned())
+                })?
                 .into();
             let core_type_id: NodeTypeId = *self.non_core_type_ids.require(&self.core_type)?;
             return Ok(Box::new(EdgeRow {
@@ -65,13 +99,18 @@ impl LineProcessorBase for TypedGraphLineProcessor {
                 edge_type_id,
             }));
         }
-        let graph_id: GraphId = vec[0].parse::<i64>()?.into();
-        let node_id: NodeId = vec[1].parse::<i64>()?.into();
-        let node_type: &str = vec[2].trim_end();
+        let schema = &TYPED_CLIQUE_SCHEMA;
+        let graph_id: GraphId = schema.get_i64(values, 0)?.into();
+        let labels = self.get_graph_labels(graph_id);
+        let node_id: NodeId = schema.get_node_id(values, 1, labels.as_deref())?;
+        let node_type: &str = schema.get_str(values, 2)?;
         let non_core_type = if node_type == self.core_type {
             None
         } else {
-            let non_core_type_id: NodeTypeId = *self.non_core_type_ids.require(node_type)?;
+            let non_core_type_id: NodeTypeId = *self
+                .non_core_type_ids
+                .require(node_type)
+                .map_err(|err| schema.value_error(values, 2, err.to_string()))?;
             Some(non_core_type_id)
         };
         Ok(Box::new(CliqueRow {
@@ -80,19 +119,45 @@ impl LineProcessorBase for TypedGraphLineProcessor {
             target_type: non_core_type,
         }))
     }
+    fn release_graph(&self, graph_id: GraphId) {
+        if let Some(node_labels) = &self.node_labels {
+            node_labels.release(graph_id);
+        }
+    }
 }
 impl TypedGraphLineProcessor {
     pub fn new(
         core_type: String,
-        non_core_type_ids: Rc<NonCoreTypeIds>,
-        non_core_types: Rc<Vec<String>>,
-        edge_types: Rc<Vec<String>>,
+        non_core_type_ids: Arc<NonCoreTypeIds>,
+        non_core_types: Arc<Vec<String>>,
+        edge_types: Arc<Vec<String>>,
     ) -> Self {
         Self {
             core_type,
             non_core_type_ids,
             non_core_types,
             edge_types,
+            node_labels: None,
         }
     }
+    /// reads node ids (core_id, non_core_id and node_id) as string labels, interned in a
+    /// table per graph (see `get_node_labels`), rather than as integers.
+    pub fn with_string_labels(mut self) -> Self {
+        self.node_labels = Some(Arc::new(GraphNodeLabels::new()));
+        self
+    }
+    /// tables of the labels of the graphs in flight, if node ids are read as string labels.
+    pub fn get_node_labels(&self) -> Option<Arc<GraphNodeLabels>> {
+        self.node_labels.clone()
+    }
+    /// table of the labels of graph_id, if node ids are read as string labels.
+    pub fn get_graph_labels(&self, graph_id: GraphId) -> Option<Arc<NodeLabels>> {
+        self.node_labels.as_ref().map(|x| x.get_or_insert(graph_id))
+    }
+
+    /// processes a line already split into its tab-separated fields (see `process_line`),
+    /// so that a line can be split once and processed for several typespecs.
+    pub fn process_fields(&self, vec: &[&str]) -> CLQResult<Box<dyn Row>> {
+        self.process_values(&FieldValue::from_fields(vec))
+    }
 }
@@ -13,9 +13,12 @@ use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessorBase, WeightedLineProcessor};
+use crate::dachshund::node_labels::format_node_id;
 use crate::dachshund::row::{Row, WeightedEdgeRow};
 use crate::dachshund::transformer_base::TransformerBase;
-use crate::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use crate::dachshund::weighted_undirected_graph_builder::{
+    DecayedWeightedUndirectedGraphBuilder, TemporalDecay, WeightedUndirectedGraphBuilder,
+};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
@@ -24,6 +27,7 @@ use ordered_float::NotNan;
 pub struct WeightedCoreTransformer {
     batch: Vec<WeightedEdgeRow>,
     line_processor: Arc<WeightedLineProcessor>,
+    decay: Option<TemporalDecay>,
 }
 
 impl WeightedCoreTransformer {
@@ -31,6 +35,21 @@ impl WeightedCoreTransformer {
         Self {
             batch: Vec::new(),
             line_processor: Arc::new(WeightedLineProcessor::new()),
+            decay: None,
+        }
+    }
+    /// reads node ids as string labels rather than as integers (see
+    /// `WeightedLineProcessor::with_string_labels`), and writes them back as such in the output.
+    pub fn with_string_labels(mut self) -> Self {
+        self.line_processor = Arc::new(WeightedLineProcessor::new().with_string_labels());
+        self
+    }
+    /// Reads the weight column of the input as interaction timestamps instead, weighting
+    /// edges by the decayed sum of their interactions (see TemporalDecay).
+    pub fn with_temporal_decay(decay: TemporalDecay) -> Self {
+        Self {
+            decay: Some(decay),
+            ..Self::new()
         }
     }
 }
@@ -59,12 +78,18 @@ impl TransformerBase for WeightedCoreTransformer {
         output: &Sender<(Option<String>, bool)>,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64, f64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
-        let mut builder = WeightedUndirectedGraphBuilder {};
-        let graph = builder.from_vector(tuples)?;
+        let graph = match self.decay {
+            Some(decay) => DecayedWeightedUndirectedGraphBuilder { decay }.from_vector(tuples)?,
+            None => WeightedUndirectedGraphBuilder {}.from_vector(tuples)?,
+        };
         let coreness_map = graph.get_fractional_coreness_values();
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
+        let labels = self
+            .line_processor
+            .get_node_labels()
+            .and_then(|x| x.get(graph_id));
         let mut coreness: Vec<(NodeId, f64)> = coreness_map.into_iter().collect();
         coreness.sort_by_key(|(_node_id, coreness)| NotNan::new(*coreness).unwrap());
         for (node_id, node_coreness) in coreness {
@@ -72,7 +97,7 @@ impl TransformerBase for WeightedCoreTransformer {
             let line: String = format!(
                 "{}\t{}\t{}\t{}",
                 original_id,
-                node_id.value(),
+                format_node_id(node_id, labels.as_deref()),
                 node_coreness,
                 degree
             );
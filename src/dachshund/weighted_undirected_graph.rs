@@ -10,23 +10,46 @@ extern crate priority_queue;
 
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use crate::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use crate::dachshund::algorithms::assortativity::Assortativity;
+use crate::dachshund::algorithms::backbone::Backbone;
 use crate::dachshund::algorithms::betweenness::Betweenness;
 use crate::dachshund::algorithms::clustering::Clustering;
+use crate::dachshund::algorithms::coarsening::Coarsening;
 use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsUndirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use crate::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
+use crate::dachshund::algorithms::degree_distribution::DegreeDistribution;
+use crate::dachshund::algorithms::dot::Dot;
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use crate::dachshund::algorithms::epidemics::Epidemics;
+use crate::dachshund::algorithms::flow::MaxFlow;
+use crate::dachshund::algorithms::gexf::Gexf;
+use crate::dachshund::algorithms::graph_diff::GraphDiff;
+use crate::dachshund::algorithms::influence_maximization::InfluenceMaximization;
+use crate::dachshund::algorithms::label_propagation::LabelPropagation;
 use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::layout::ForceLayout;
+use crate::dachshund::algorithms::link_prediction::LinkPrediction;
+use crate::dachshund::algorithms::local_community::LocalCommunity;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::partitioning::Partitioning;
+use crate::dachshund::algorithms::random_walks::RandomWalks;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::spanning_tree::{SpanningTree, UniformSpanningTree};
+use crate::dachshund::algorithms::subgraphs::Subgraphs;
 use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::algorithms::triangles::Triangles;
+use crate::dachshund::algorithms::visualization::Visualization;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use crate::dachshund::node::{
+    NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase, WeightedNodeEdge, WeightedNodeEdgeBase,
+};
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::hash_map::{Keys, Values};
 
 /// Keeps track of a weighted undirected graph, composed of nodes that have weighed.
@@ -37,14 +60,6 @@ pub struct WeightedUndirectedGraph {
 impl GraphBase for WeightedUndirectedGraph {
     type NodeType = WeightedNode;
 
-    /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
-    fn get_core_ids(&self) -> &Vec<NodeId> {
-        &self.ids
-    }
-    /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
-    fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
-        Some(&self.ids)
-    }
     fn get_ids_iter(&self) -> Keys<NodeId, WeightedNode> {
         self.nodes.keys()
     }
@@ -104,18 +119,126 @@ impl WeightedUndirectedGraph {
 }
 impl UndirectedGraph for WeightedUndirectedGraph {}
 
+impl Coarsening for WeightedUndirectedGraph {
+    // heavier edges are matched first.
+    fn get_coarsening_edges(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
 impl ConnectedComponents for WeightedUndirectedGraph {}
 impl ConnectedComponentsUndirected for WeightedUndirectedGraph {}
 impl Coreness for WeightedUndirectedGraph {}
 impl FractionalCoreness for WeightedUndirectedGraph {}
+impl DegreeDistribution for WeightedUndirectedGraph {}
+impl Dot for WeightedUndirectedGraph {}
+impl Gexf for WeightedUndirectedGraph {
+    fn get_gexf_edges(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
+impl MaxFlow for WeightedUndirectedGraph {
+    // edge weights are capacities, in both directions.
+    fn get_flow_arcs(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
+impl LabelPropagation for WeightedUndirectedGraph {
+    // neighbors vote in proportion to edge weights.
+    fn get_label_propagation_neighbors(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
 
+impl Assortativity for WeightedUndirectedGraph {}
 impl AdjacencyMatrix for WeightedUndirectedGraph {}
 impl Clustering for WeightedUndirectedGraph {}
 impl Connectivity for WeightedUndirectedGraph {}
 impl ConnectivityUndirected for WeightedUndirectedGraph {}
 impl Betweenness for WeightedUndirectedGraph {}
 impl Laplacian for WeightedUndirectedGraph {}
+impl LinkPrediction for WeightedUndirectedGraph {}
 impl Transitivity for WeightedUndirectedGraph {}
-impl ShortestPaths for WeightedUndirectedGraph {}
+impl GraphDiff for WeightedUndirectedGraph {}
+impl Triangles for WeightedUndirectedGraph {}
+impl ShortestPaths for WeightedUndirectedGraph {
+    // edge weights are lengths.
+    fn get_shortest_path_edges(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
+impl SpanningTree for WeightedUndirectedGraph {}
+impl Subgraphs for WeightedUndirectedGraph {
+    fn subgraph(&self, nodes: &FxHashSet<NodeId>) -> Self {
+        WeightedUndirectedGraph {
+            ids: self
+                .ids
+                .iter()
+                .filter(|id| nodes.contains(id))
+                .cloned()
+                .collect(),
+            nodes: self
+                .nodes
+                .values()
+                .filter(|node| nodes.contains(&node.node_id))
+                .map(|node| {
+                    let subgraph_node = WeightedNode {
+                        node_id: node.node_id,
+                        edges: node
+                            .edges
+                            .iter()
+                            .filter(|e| nodes.contains(&e.target_id))
+                            .map(|e| WeightedNodeEdge {
+                                target_id: e.target_id,
+                                weight: e.weight,
+                            })
+                            .collect(),
+                        neighbors: node
+                            .neighbors
+                            .iter()
+                            .filter(|id| nodes.contains(id))
+                            .cloned()
+                            .collect(),
+                    };
+                    (node.node_id, subgraph_node)
+                })
+                .collect(),
+        }
+    }
+}
+// spanning trees are drawn regardless of edge weights.
+impl UniformSpanningTree for WeightedUndirectedGraph {}
+impl Partitioning for WeightedUndirectedGraph {}
 impl AlgebraicConnectivity for WeightedUndirectedGraph {}
+impl Backbone for WeightedUndirectedGraph {}
 impl EigenvectorCentrality for WeightedUndirectedGraph {}
+impl Epidemics for WeightedUndirectedGraph {}
+impl ForceLayout for WeightedUndirectedGraph {}
+impl InfluenceMaximization for WeightedUndirectedGraph {}
+impl Visualization for WeightedUndirectedGraph {}
+impl PageRank for WeightedUndirectedGraph {
+    // rank is distributed in proportion to edge weights.
+    fn get_pagerank_out_edges(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
+impl LocalCommunity for WeightedUndirectedGraph {}
+impl RandomWalks for WeightedUndirectedGraph {
+    // walks step along edges in proportion to their weights.
+    fn get_walk_steps(&self, node: &WeightedNode) -> Vec<(NodeId, f64)> {
+        node.get_edges()
+            .map(|e| (e.get_neighbor_id(), e.get_weight()))
+            .collect()
+    }
+}
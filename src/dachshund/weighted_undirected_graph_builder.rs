@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_builder_base::{GraphBuilderBase, GraphBuilderBaseWithPreProcessing};
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{WeightedNode, WeightedNodeEdge};
@@ -71,3 +71,55 @@ impl GraphBuilderBase for WeightedUndirectedGraphBuilder {
         })
     }
 }
+
+/// Exponential decay of the weight of interactions with their age: an interaction at time t
+/// weighs 2^(-(reference_time - t) / half_life), so 1 at the reference time, 1/2 one
+/// half-life before it, and so on. Interactions after the reference time weigh 1.
+#[derive(Clone, Copy, Debug)]
+pub struct TemporalDecay {
+    reference_time: f64,
+    half_life: f64,
+}
+impl TemporalDecay {
+    pub fn new(reference_time: f64, half_life: f64) -> CLQResult<Self> {
+        if !(half_life > 0.0 && half_life.is_finite()) {
+            return Err(CLQError::from(format!(
+                "Half-life must be positive, got {}",
+                half_life
+            )));
+        }
+        Ok(Self {
+            reference_time,
+            half_life,
+        })
+    }
+    pub fn get_weight(&self, timestamp: f64) -> f64 {
+        let age = (self.reference_time - timestamp).max(0.0);
+        0.5_f64.powf(age / self.half_life)
+    }
+}
+
+/// Builds a WeightedUndirectedGraph from timestamped interactions, as (source, target,
+/// timestamp) rows. Each edge weighs the sum of the decayed weights of the interactions
+/// between its nodes (in either direction), so that stale interactions count for less.
+pub struct DecayedWeightedUndirectedGraphBuilder {
+    pub decay: TemporalDecay,
+}
+impl GraphBuilderBase for DecayedWeightedUndirectedGraphBuilder {
+    type GraphType = WeightedUndirectedGraph;
+    type RowType = (i64, i64, f64);
+
+    fn from_vector(&mut self, data: Vec<(i64, i64, f64)>) -> CLQResult<WeightedUndirectedGraph> {
+        let mut weights: BTreeMap<(i64, i64), f64> = BTreeMap::new();
+        for (id1, id2, timestamp) in data {
+            let key = (id1.min(id2), id1.max(id2));
+            *weights.entry(key).or_insert(0.0) += self.decay.get_weight(timestamp);
+        }
+        WeightedUndirectedGraphBuilder {}.from_vector(
+            weights
+                .into_iter()
+                .map(|((id1, id2), weight)| (id1, id2, weight))
+                .collect(),
+        )
+    }
+}
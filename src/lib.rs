@@ -4,7 +4,6 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-#![feature(binary_heap_into_iter_sorted)]
 extern crate clap;
 extern crate rand;
 extern crate thiserror;
@@ -13,35 +12,100 @@ pub mod dachshund;
 
 pub use dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 pub use dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+pub use dachshund::algorithms::assortativity::{Assortativity, TypeAssortativity};
+pub use dachshund::algorithms::backbone::Backbone;
 pub use dachshund::algorithms::betweenness::Betweenness;
+pub use dachshund::algorithms::bipartite_cores::BipartiteCores;
+pub use dachshund::algorithms::bipartite_matching::BipartiteMatching;
+pub use dachshund::algorithms::bipartite_modularity::{BipartiteModularity, CoCluster};
 pub use dachshund::algorithms::brokerage::Brokerage;
+pub use dachshund::algorithms::butterflies::Butterflies;
 pub use dachshund::algorithms::clustering::Clustering;
 pub use dachshund::algorithms::cnm_communities::CNMCommunities;
-pub use dachshund::algorithms::connected_components::ConnectedComponents;
-pub use dachshund::algorithms::coreness::Coreness;
+pub use dachshund::algorithms::coarsening::{CoarseGraph, Coarsening};
+pub use dachshund::algorithms::connected_components::{
+    ConnectedComponents, ConnectedComponentsDirected,
+};
+pub use dachshund::algorithms::connectivity::{Connectivity, ConnectivityDirected};
+pub use dachshund::algorithms::core_maintenance::CoreMaintenance;
+pub use dachshund::algorithms::coreness::{Coreness, DCores, DirectedCoreness};
+pub use dachshund::algorithms::degree_distribution::{DegreeDistribution, PowerLawFit};
+pub use dachshund::algorithms::dendrogram::Dendrogram;
+pub use dachshund::algorithms::directed_transitivity::DirectedTransitivity;
+pub use dachshund::algorithms::dot::Dot;
+pub use dachshund::algorithms::embedding_index::EmbeddingIndex;
+pub use dachshund::algorithms::epidemics::{
+    EpidemicConfig, EpidemicCounts, EpidemicModel, Epidemics,
+};
+pub use dachshund::algorithms::flow::MaxFlow;
+pub use dachshund::algorithms::fraudar::{DenseBlock, Fraudar};
+pub use dachshund::algorithms::gexf::Gexf;
+pub use dachshund::algorithms::graph_diff::{GraphDelta, GraphDiff};
+pub use dachshund::algorithms::graphlets::Graphlets;
+pub use dachshund::algorithms::hits::Hits;
+pub use dachshund::algorithms::influence_maximization::{InfluenceEstimate, InfluenceMaximization};
+pub use dachshund::algorithms::label_propagation::LabelPropagation;
 pub use dachshund::algorithms::laplacian::Laplacian;
+pub use dachshund::algorithms::layout::ForceLayout;
+pub use dachshund::algorithms::leiden::Leiden;
+pub use dachshund::algorithms::link_prediction::{LinkPrediction, LinkPredictionScore};
+pub use dachshund::algorithms::local_community::LocalCommunity;
+pub use dachshund::algorithms::max_clique::{MaxClique, MaxCliqueEstimate};
+pub use dachshund::algorithms::pagerank::PageRank;
+pub use dachshund::algorithms::partitioning::{Partition, Partitioning};
+pub use dachshund::algorithms::random_walks::RandomWalks;
+pub use dachshund::algorithms::relaxed_cliques::RelaxedCliques;
+pub use dachshund::algorithms::rewiring::Rewiring;
+pub use dachshund::algorithms::rich_club::RichClub;
 pub use dachshund::algorithms::shortest_paths::ShortestPaths;
+pub use dachshund::algorithms::spanning_tree::{SpanningTree, UniformSpanningTree};
+pub use dachshund::algorithms::sparsification::Sparsification;
+pub use dachshund::algorithms::stochastic_block_model::{BlockModel, StochasticBlockModel};
+pub use dachshund::algorithms::streaming_triangles::TriestEstimator;
+pub use dachshund::algorithms::subgraphs::Subgraphs;
 pub use dachshund::algorithms::transitivity::Transitivity;
-pub use dachshund::beam::Beam;
+pub use dachshund::algorithms::triangles::Triangles;
+pub use dachshund::algorithms::union_find::UnionFind;
+pub use dachshund::algorithms::visualization::Visualization;
+pub use dachshund::any_graph::AnyGraph;
+pub use dachshund::attributed_graph::{AttributeValue, AttributedGraph, Attributes};
+pub use dachshund::attributed_graph_builder::AttributedGraphBuilder;
+pub use dachshund::batch_transformer::BatchTransformer;
+pub use dachshund::beam::{Beam, BeamCandidate};
+pub use dachshund::bipartite_graph::{BipartiteGraph, ProjectionWeighting, Shore};
+pub use dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
 pub use dachshund::candidate::Candidate;
 pub use dachshund::core_transformer::CoreTransformer;
+pub use dachshund::csr_graph::CsrGraph;
+pub use dachshund::edge_type_weights::EdgeTypeWeights;
+pub use dachshund::graph_arena::GraphArena;
 pub use dachshund::graph_base::GraphBase;
 pub use dachshund::graph_builder_base::GraphBuilderBase;
-pub use dachshund::id_types::{EdgeTypeId, GraphId, NodeId, NodeTypeId};
+pub use dachshund::graph_cache::GraphCache;
+pub use dachshund::hypergraph::{HyperdegreeStats, Hypergraph};
+pub use dachshund::id_types::{EdgeTypeId, GraphId, HyperedgeId, NodeId, NodeTypeId};
 pub use dachshund::input::Input;
-pub use dachshund::line_processor::LineProcessor;
-pub use dachshund::node::{Node, SimpleDirectedNode};
+pub use dachshund::line_format::{DelimitedLineProcessor, LineFormat};
+pub use dachshund::line_pipeline::ParsedLines;
+pub use dachshund::line_processor::{AttributedLineProcessor, LineProcessor};
+pub use dachshund::node::{CsrNode, Node, SimpleDirectedNode};
+pub use dachshund::node_labels::{LabeledGraphBuilder, NodeLabels};
 pub use dachshund::output::Output;
+#[cfg(feature = "parquet")]
+pub use dachshund::parquet_input::ParquetInput;
+pub use dachshund::quasi_clique::{QuasiCliqueCandidate, QuasiCliqueGraph, QuasiCliqueScorer};
+pub use dachshund::quasi_clique_transformer::QuasiCliqueTransformer;
 pub use dachshund::row::EdgeRow;
 pub use dachshund::scorer::Scorer;
 pub use dachshund::search_problem::SearchProblem;
-pub use dachshund::simple_directed_graph::SimpleDirectedGraph;
+pub use dachshund::simple_directed_graph::{DirectedGraph, SimpleDirectedGraph};
 pub use dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
 pub use dachshund::simple_transformer::SimpleTransformer;
 pub use dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 pub use dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+pub use dachshund::streaming_triangles_transformer::StreamingTrianglesTransformer;
 pub use dachshund::test_utils::*;
-pub use dachshund::transformer::Transformer;
+pub use dachshund::transformer::{OutputFormat, Transformer};
 pub use dachshund::transformer_base::TransformerBase;
 pub use dachshund::typed_graph::TypedGraph;
 pub use dachshund::typed_graph_builder::TypedGraphBuilder;
@@ -8,14 +8,22 @@ extern crate clap;
 extern crate lib_dachshund;
 
 use std::io;
+use std::io::Write;
 
-use clap::{App, ArgMatches};
+use clap::{App, Arg, ArgMatches};
 
 use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_cache::GraphCache;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_format::{get_line_format_args, LineFormat};
 use lib_dachshund::dachshund::output::Output;
-use lib_dachshund::dachshund::simple_transformer::SimpleTransformer;
-use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::simple_transformer::{
+    Parallelism, SimpleParallelTransformer, SimpleTransformer,
+};
+use lib_dachshund::dachshund::transformer_base::{
+    install_interrupt_handler, open_rejects_file, resolve_num_threads, TransformerBase,
+    INTERRUPTED_EXIT_CODE,
+};
 
 fn get_command_line_args() -> ArgMatches<'static> {
     let matches: ArgMatches = App::new("Dachshund Graph Featurizer")
@@ -30,18 +38,157 @@ fn get_command_line_args() -> ArgMatches<'static> {
                 Pär Winzell <zell@fb.com>",
         )
         .about("Featurizes simple undirected graphs specified from stdin.")
+        .arg(
+            Arg::with_name("summary_file")
+                .long("summary_file")
+                .takes_value(true)
+                .help("File to write run totals to, as JSON. If not provided, totals are printed to stderr."),
+        )
+        .arg(
+            Arg::with_name("threads")
+                .long("threads")
+                .takes_value(true)
+                .help("Number of threads to featurize graphs with (0 for one per CPU). Defaults to \
+                       the DACHSHUND_THREADS environment variable; if neither is set graphs are \
+                       featurized on the main thread."),
+        )
+        .arg(
+            Arg::with_name("parsing_threads")
+                .long("parsing_threads")
+                .takes_value(true)
+                .help("Number of threads to parse lines of input with, ahead of building graphs, \
+                       besides one reading them. If not provided, lines are read and parsed on \
+                       the main thread."),
+        )
+        .arg(
+            Arg::with_name("parallelism")
+                .long("parallelism")
+                .takes_value(true)
+                .possible_values(&["graph", "algorithm"])
+                .default_value("graph")
+                .help("With multiple threads, whether to featurize several graphs at once (graph) \
+                       or compute the features of one graph at a time concurrently (algorithm)."),
+        )
+        .arg(
+            Arg::with_name("reorder_window")
+                .long("reorder_window")
+                .takes_value(true)
+                .help("With --parallelism graph, output graphs in input order (as a single thread \
+                       would), holding at most this many graphs in flight at once."),
+        )
+        .arg(
+            Arg::with_name("null_models")
+                .long("null_models")
+                .takes_value(true)
+                .help("Number of degree-preserving randomizations of each graph to also compute \
+                       statistics on, reporting clustering and modularity z-scores against them \
+                       (as clust_coef_zscore and modularity_zscore)."),
+        )
+        .arg(
+            Arg::with_name("rejects_file")
+                .long("rejects_file")
+                .takes_value(true)
+                .help("File to write lines that cannot be parsed to (as line_num, error, line), \
+                       skipping them. If not provided, the run stops at the first such line."),
+        )
+        .arg(
+            Arg::with_name("cache_file")
+                .long("cache_file")
+                .takes_value(true)
+                .help("File to cache the features of each graph in, keyed by graph_id and a hash \
+                       of its lines. Graphs whose lines are unchanged since the previous run \
+                       with the same options are not featurized again."),
+        )
+        .arg(
+            Arg::with_name("graph_markers")
+                .long("graph_markers")
+                .help("Bracket the output of each graph with (#begin, graph_id) and \
+                       (#end, graph_id, number of lines) rows, so that truncated output can be \
+                       told from complete output."),
+        )
+        .arg(
+            Arg::with_name("string_labels")
+                .long("string_labels")
+                .help("Read node ids as arbitrary strings (e.g. user names) rather than \
+                       integers."),
+        )
+        .args(&get_line_format_args())
         .get_matches();
     matches
 }
 
 fn main() -> CLQResult<()> {
     // TODO: add proper command line args
-    let _matches: ArgMatches = get_command_line_args();
-    let mut transformer = SimpleTransformer::new();
+    let matches: ArgMatches = get_command_line_args();
+    install_interrupt_handler()?;
+    let num_threads: Option<usize> = resolve_num_threads(matches.value_of("threads"))?;
+    let num_parsing_threads: usize = matches
+        .value_of("parsing_threads")
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or(0);
+    let parallelism: Parallelism = matches.value_of("parallelism").unwrap().parse()?;
+    let reorder_window: Option<usize> = matches
+        .value_of("reorder_window")
+        .map(str::parse)
+        .transpose()?;
+    let num_null_models: usize = matches
+        .value_of("null_models")
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or(0);
     let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
+    let input: Input =
+        Input::console(&stdio).with_line_format(LineFormat::from_argmatches(&matches)?);
     let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
-    transformer.run(input, output)?;
+    let mut output: Output = Output::console(&mut dummy);
+    if matches.is_present("graph_markers") {
+        output = output.with_graph_markers();
+    }
+    let mut rejects = open_rejects_file(matches.value_of("rejects_file"))?;
+    // features only depend on the number of null models, not on how they are computed.
+    let mut cache: Option<GraphCache> = matches
+        .value_of("cache_file")
+        .map(|path| GraphCache::open(path, &format!("null_models={num_null_models}")))
+        .transpose()?;
+    let rejects = rejects.as_mut().map(|x| x as &mut dyn Write);
+    let string_labels: bool = matches.is_present("string_labels");
+    let summary = match num_threads {
+        Some(num_threads) => {
+            let mut transformer =
+                SimpleParallelTransformer::with_threads(num_threads, parallelism, reorder_window)?
+                    .with_null_models(num_null_models);
+            if string_labels {
+                transformer = transformer.with_string_labels();
+            }
+            transformer.run_with_parsing_threads(
+                input,
+                output,
+                rejects,
+                cache.as_mut(),
+                num_parsing_threads,
+            )?
+        }
+        None => {
+            let mut transformer = SimpleTransformer::new().with_null_models(num_null_models);
+            if string_labels {
+                transformer = transformer.with_string_labels();
+            }
+            transformer.run_with_parsing_threads(
+                input,
+                output,
+                rejects,
+                cache.as_mut(),
+                num_parsing_threads,
+            )?
+        }
+    };
+    if let Some(cache) = &cache {
+        cache.save()?;
+    }
+    summary.write(matches.value_of("summary_file"))?;
+    if summary.interrupted {
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
     Ok(())
 }
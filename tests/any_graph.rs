@@ -0,0 +1,75 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::any_graph::AnyGraph;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::{
+    gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+fn get_graphs() -> CLQResult<Vec<AnyGraph>> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let raw = vec![
+        "0\t1\t2\tauthor\tpublished_at\tjournal".to_string(),
+        "0\t1\t3\tauthor\tpublished_at\tjournal".into(),
+    ];
+    let rows = process_raw_vector(&transformer, raw)?;
+    Ok(vec![
+        SimpleUndirectedGraphBuilder {}
+            .get_complete_graph(4)?
+            .into(),
+        SimpleDirectedGraphBuilder {}
+            .from_vector(vec![(0, 1), (1, 2), (2, 0), (3, 4)])?
+            .into(),
+        WeightedUndirectedGraphBuilder {}
+            .from_vector(vec![(0, 1, 1.0), (1, 2, 2.0), (0, 2, 0.5)])?
+            .into(),
+        transformer
+            .build_pruned_graph(GraphId::from(0), rows)?
+            .into(),
+    ])
+}
+
+#[test]
+fn test_any_graph() -> CLQResult<()> {
+    let graphs = get_graphs()?;
+    let num_nodes: Vec<usize> = graphs.iter().map(|g| g.count_nodes()).collect();
+    assert_eq!(num_nodes, vec![4, 5, 3, 3]);
+    assert_eq!(graphs.iter().filter(|g| g.is_directed()).count(), 1);
+    assert_eq!(
+        graphs[0].get_ordered_node_ids(),
+        (1..5).map(NodeId::from).collect::<Vec<NodeId>>()
+    );
+    assert_eq!(graphs[3].get_ordered_node_ids().len(), 3);
+
+    let num_components: Vec<Option<usize>> = graphs
+        .iter()
+        .map(|g| g.get_connected_components().ok().map(|x| x.len()))
+        .collect();
+    assert_eq!(num_components, vec![Some(1), Some(2), Some(1), None]);
+
+    assert_eq!(graphs[0].get_triangle_count()?, 4);
+    assert_eq!(graphs[2].get_transitivity()?, 1.0);
+    assert!(graphs[1].get_transitivity().is_err());
+    assert_eq!(graphs[0].get_coreness_values()?[&NodeId::from(1)], 3);
+
+    for graph in &graphs[..3] {
+        let pagerank = graph.get_pagerank(0.85, 1e-8, 100)?;
+        assert_eq!(pagerank.len(), graph.count_nodes());
+    }
+    let err = graphs[3].get_pagerank(0.85, 1e-8, 100).unwrap_err();
+    assert_eq!(err.to_string(), "PageRank is not supported for TypedGraph");
+    Ok(())
+}
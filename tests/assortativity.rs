@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::assortativity::{Assortativity, TypeAssortativity};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::node::SimpleNode;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::{
+    gen_bridged_triangles_graph, gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::transformer::OutputFormat;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[test]
+fn test_attribute_assortativity() -> CLQResult<()> {
+    // two triangles joined by an edge, each triangle in its own category.
+    let graph = gen_bridged_triangles_graph()?;
+    let categories: HashMap<i64, &str> =
+        (0..6).map(|i| (i, if i < 3 { "a" } else { "b" })).collect();
+    let get_category = |node: &SimpleNode| categories.get(&node.node_id.value()).cloned();
+    let mixing = graph.get_mixing_matrix(get_category);
+    assert_eq!(mixing.categories, vec!["a", "b"]);
+    assert_eq!(
+        mixing.fractions,
+        vec![vec![6.0 / 14.0, 1.0 / 14.0], vec![1.0 / 14.0, 6.0 / 14.0]]
+    );
+    let assortativity = graph.get_attribute_assortativity(get_category).unwrap();
+    assert!((assortativity - 5.0 / 7.0).abs() < 1e-9);
+
+    // alternating categories around an even cycle: perfectly disassortative.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3), (3, 0)])?;
+    let assortativity = graph
+        .get_attribute_assortativity(|node| Some(node.node_id.value() % 2))
+        .unwrap();
+    assert!((assortativity + 1.0).abs() < 1e-9);
+    // a single category, or none at all, leaves the coefficient undefined.
+    assert_eq!(graph.get_attribute_assortativity(|_| Some(0)), None);
+    assert_eq!(graph.get_attribute_assortativity(|_| None::<usize>), None);
+    Ok(())
+}
+
+#[test]
+fn test_type_assortativity() -> CLQResult<()> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let raw = vec![
+        "0\t1\t2\tauthor\tpublished_at\tjournal".to_string(),
+        "0\t1\t3\tauthor\tpublished_at\tjournal".into(),
+        "0\t1\t4\tauthor\tpublished_at\tconference".into(),
+    ];
+    let rows = process_raw_vector(&transformer, raw)?;
+    let graph = transformer.build_pruned_graph(GraphId::from(0), rows)?;
+    let mixing = graph.get_type_mixing_matrix();
+    assert_eq!(mixing.categories.len(), 3);
+    // edges only join the core type to non-core types.
+    assert_eq!(mixing.fractions[0][0], 0.0);
+    let assortativity = mixing.get_assortativity().unwrap();
+    assert!((assortativity + 7.0 / 11.0).abs() < 1e-9);
+
+    let stats = graph.get_type_mixing_json(&transformer.get_type_names());
+    assert_eq!(stats["type_mixing"]["author-journal"], 0.667);
+    assert_eq!(stats["type_mixing"]["author-conference"], 0.333);
+    assert_eq!(stats["type_mixing"].as_object().unwrap().len(), 2);
+    assert!(stats["type_assortativity"].as_f64().unwrap() < 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_type_mixing_stats_output() -> CLQResult<()> {
+    let mut transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?
+        .with_output_format(OutputFormat::Stats);
+    let text = "0\t1\t2\tauthor\tpublished_at\tjournal\n\
+                0\t1\t3\tauthor\tpublished_at\tjournal\n\
+                0\t1\t4\tauthor\tpublished_at\tconference\n";
+    let mut buffer: Vec<u8> = Vec::new();
+    transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let output = String::from_utf8(buffer)?;
+    let (graph_id, stats) = output.trim_end().split_once('\t').unwrap();
+    assert_eq!(graph_id, "0");
+    let stats: Value = serde_json::from_str(stats)?;
+    assert_eq!(stats["num_core_nodes"], 1);
+    assert_eq!(stats["num_non_core_nodes"], 3);
+    assert_eq!(stats["num_edges"], 3);
+    assert_eq!(stats["type_mixing"]["author-journal"], 0.667);
+    assert_eq!(stats["type_mixing"]["author-conference"], 0.333);
+    assert!(stats["type_assortativity"].as_f64().unwrap() < 0.0);
+    Ok(())
+}
@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::attributed_graph::{
+    format_attributes, parse_attributes, AttributeValue, AttributedGraph,
+};
+use lib_dachshund::dachshund::attributed_graph_builder::AttributedGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::line_processor::{AttributedLineProcessor, LineProcessorBase};
+use lib_dachshund::dachshund::row::AttributedRow;
+
+fn get_graph(text: &str) -> CLQResult<AttributedGraph> {
+    let line_processor = AttributedLineProcessor::new();
+    let rows: Vec<AttributedRow> = text
+        .lines()
+        .filter(|line| !line_processor.is_skipped(line))
+        .map(|line| {
+            let row = line_processor.process_line(line.to_owned())?;
+            Ok(row.downcast_ref::<AttributedRow>().unwrap().clone())
+        })
+        .collect::<CLQResult<Vec<AttributedRow>>>()?;
+    AttributedGraphBuilder {}.from_vector(rows)
+}
+
+// a triangle of users in the US, linked to a user in France, and a user with no edges.
+const TEXT: &str = "# users\n\
+    g\t1\t{\"country\": \"US\", \"age\": 31, \"verified\": true}\n\
+    g\t2\t{\"country\": \"US\", \"age\": 25}\n\
+    g\t3\t{\"country\": \"US\", \"score\": 0.5}\n\
+    g\t4\t{\"country\": \"FR\"}\n\
+    g\t5\t{\"country\": \"FR\"}\n\
+    g\t1\t2\t{\"kind\": \"friend\", \"since\": 2019}\n\
+    g\t2\t3\t{\"kind\": \"friend\"}\n\
+    g\t3\t1\t{\"kind\": \"coworker\"}\n\
+    g\t3\t4\t\n\
+    g\t1\t2\t{\"since\": 2020}\n";
+
+#[test]
+fn test_attributed_graph() -> CLQResult<()> {
+    let id = NodeId::from;
+    let mut graph = get_graph(TEXT)?;
+    assert_eq!(graph.count_nodes(), 5);
+    assert_eq!(graph.count_edges(), 4);
+    assert_eq!(graph.get_graph().get_connected_components().len(), 2);
+
+    assert_eq!(
+        graph.get_node_attribute(id(1), "country"),
+        Some(&AttributeValue::from("US"))
+    );
+    assert_eq!(
+        graph
+            .get_node_attribute(id(1), "age")
+            .and_then(|x| x.as_i64()),
+        Some(31)
+    );
+    assert_eq!(
+        graph
+            .get_node_attribute(id(1), "verified")
+            .and_then(|x| x.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        graph
+            .get_node_attribute(id(3), "score")
+            .and_then(|x| x.as_f64()),
+        Some(0.5)
+    );
+    assert_eq!(graph.get_node_attribute(id(2), "verified"), None);
+    assert_eq!(graph.get_node_attributes(id(6)), None);
+
+    // edges can be looked up either way, and repeated edges are merged.
+    assert_eq!(
+        graph.get_edge_attribute(id(2), id(1), "kind"),
+        Some(&AttributeValue::from("friend"))
+    );
+    assert_eq!(
+        graph.get_edge_attribute(id(1), id(2), "since"),
+        Some(&AttributeValue::Int(2020))
+    );
+    assert!(graph.get_edge_attributes(id(4), id(3)).unwrap().is_empty());
+    assert_eq!(graph.get_edge_attributes(id(1), id(4)), None);
+
+    assert_eq!(
+        graph.set_node_attribute(id(5), "age", AttributeValue::Int(40))?,
+        None
+    );
+    assert_eq!(
+        graph.set_edge_attribute(id(4), id(3), "kind", "friend".into())?,
+        None
+    );
+    assert!(graph
+        .set_edge_attribute(id(4), id(5), "kind", "friend".into())
+        .is_err());
+    assert_eq!(
+        graph.get_nodes_with_attribute("country", &"FR".into()),
+        vec![id(4), id(5)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_attributed_subgraphs() -> CLQResult<()> {
+    let id = NodeId::from;
+    let graph = get_graph(TEXT)?;
+
+    // US users only: the triangle.
+    let us = graph.filter_nodes(|_, attributes| {
+        attributes.get("country") == Some(&AttributeValue::from("US"))
+    });
+    assert_eq!(us.get_ordered_node_ids(), vec![id(1), id(2), id(3)]);
+    assert_eq!(us.count_edges(), 3);
+    assert_eq!(
+        us.get_edge_attribute(id(1), id(3), "kind"),
+        Some(&AttributeValue::from("coworker"))
+    );
+    assert_eq!(
+        us.get_node_attribute(id(2), "age"),
+        Some(&AttributeValue::Int(25))
+    );
+
+    // friendships only, with all users.
+    let friends = graph.filter_edges(|_, _, attributes| {
+        attributes.get("kind").and_then(|x| x.as_str()) == Some("friend")
+    });
+    assert_eq!(friends.count_nodes(), 5);
+    assert_eq!(friends.count_edges(), 2);
+    assert_eq!(friends.get_edge_attributes(id(1), id(3)), None);
+    assert_eq!(friends.get_node(id(2)).neighbors.len(), 2);
+
+    // missing nodes are left out of induced subgraphs.
+    let induced = graph.get_induced_subgraph(&[id(3), id(4), id(9)]);
+    assert_eq!(induced.get_ordered_node_ids(), vec![id(3), id(4)]);
+    assert_eq!(induced.count_edges(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_attribute_parsing() -> CLQResult<()> {
+    let attributes = parse_attributes("{\"a\": 1, \"b\": 1.5, \"c\": \"x\", \"d\": false}")?;
+    assert_eq!(attributes["a"], AttributeValue::Int(1));
+    assert_eq!(attributes["b"], AttributeValue::Float(1.5));
+    assert_eq!(attributes["c"].to_string(), "x");
+    assert_eq!(attributes["d"], AttributeValue::Bool(false));
+    assert_eq!(
+        parse_attributes(&format_attributes(&attributes))?,
+        attributes
+    );
+    assert!(parse_attributes("")?.is_empty());
+    assert!(parse_attributes("[1, 2]").is_err());
+    assert!(parse_attributes("{\"a\": [1]}").is_err());
+    assert!(parse_attributes("{\"a\": ").is_err());
+
+    let line_processor = AttributedLineProcessor::new();
+    let error = line_processor
+        .process_line("g\t1\t{\"a\": null}".to_owned())
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("attributes"));
+    assert!(line_processor
+        .process_line("g\tx\t2\t{}".to_owned())
+        .is_err());
+    assert!(line_processor.process_line("g\t1".to_owned()).is_err());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::backbone::Backbone;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+#[test]
+fn test_disparity_backbone() -> CLQResult<()> {
+    // a star on 0 with one heavy edge, and a light edge between two of its leaves.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 10.0),
+        (0, 2, 1.0),
+        (0, 3, 1.0),
+        (0, 4, 1.0),
+        (1, 2, 1.0),
+    ])?;
+    let alphas: Vec<(i64, i64, f64)> = graph
+        .get_disparity_alphas()
+        .into_iter()
+        .map(|(id1, id2, _weight, alpha)| (id1.value(), id2.value(), alpha))
+        .collect();
+    let light = (1.0 - 1.0_f64 / 13.0).powi(3);
+    assert_eq!(
+        alphas,
+        vec![
+            (0, 1, (1.0 - 10.0_f64 / 13.0).powi(3)),
+            (0, 2, 0.5),
+            (0, 3, light),
+            (0, 4, light),
+            (1, 2, 0.5),
+        ]
+    );
+
+    let backbone = graph.get_disparity_backbone(0.05);
+    assert_eq!(backbone.count_nodes(), 5);
+    let neighbors = |id: i64| -> Vec<NodeId> {
+        backbone
+            .get_node(NodeId::from(id))
+            .neighbors
+            .iter()
+            .cloned()
+            .collect()
+    };
+    assert_eq!(neighbors(0), vec![NodeId::from(1)]);
+    assert_eq!(neighbors(1), vec![NodeId::from(0)]);
+    assert!(neighbors(2).is_empty());
+
+    // everything is below a significance level above 1.
+    let backbone = graph.get_disparity_backbone(1.1);
+    assert_eq!(backbone.get_node(NodeId::from(0)).edges.len(), 4);
+    Ok(())
+}
@@ -253,8 +253,174 @@ fn test_beam_with_empty_graph_after_pruning() -> CLQResult<()> {
     let input = Input::string(bytes);
     let mut buffer: Vec<u8> = Vec::new();
     let output = Output::string(&mut buffer);
-    transformer.run(input, output)?;
+    let summary = transformer.run(input, output)?;
     let output_str: String = String::from_utf8(buffer)?;
     assert_eq!(output_str, "");
+    // the graph is still accounted for, as skipped.
+    assert_eq!(summary.num_graphs, 1);
+    assert_eq!(summary.num_graphs_skipped, 1);
+    assert_eq!(summary.num_rows, raw.len());
+    assert_eq!(summary.num_lines_emitted, 0);
+    Ok(())
+}
+
+#[test]
+fn test_beam_step() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let target_types: Vec<String> = vec!["article".to_string()];
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t2\t3\tauthor\tpublished\tarticle".into(),
+        "0\t1\t4\tauthor\tpublished\tarticle".into(),
+        "0\t2\t4\tauthor\tpublished\tarticle".into(),
+        "0\t2\t5\tauthor\tpublished\tarticle".into(),
+    ];
+    let graph_id: GraphId = 0.into();
+    let transformer = Transformer::new(
+        typespec,
+        20,
+        1.0,
+        Some(0.5),
+        Some(0.5),
+        20,
+        100,
+        3,
+        true,
+        0,
+        "author".to_string(),
+        false,
+    )?;
+    let rows: Vec<EdgeRow> = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+    let clique_rows: Vec<CliqueRow> = Vec::new();
+    let mut beam: Beam<TypedGraph> = Beam::new(
+        &graph,
+        &clique_rows,
+        false,
+        &target_types,
+        transformer.search_problem.clone(),
+        graph_id,
+    )?;
+    let snapshot = beam.snapshot()?;
+    assert_eq!(snapshot.epoch, 0);
+    assert!(snapshot.can_continue);
+    assert_eq!(
+        snapshot.candidates.len(),
+        transformer.search_problem.beam_size
+    );
+
+    let mut prior_score = f32::MIN;
+    for epoch in 1..=10 {
+        let snapshot = beam.step()?;
+        assert_eq!(snapshot.epoch, epoch);
+        assert!(snapshot.candidates.len() <= transformer.search_problem.beam_size);
+        assert!(snapshot.candidates.windows(2).all(|x| x[0].0 >= x[1].0));
+        let score = snapshot.top_score().ok_or_else(CLQError::err_none)?;
+        assert!(score >= prior_score);
+        prior_score = score;
+        if !snapshot.can_continue {
+            break;
+        }
+    }
+    // the search settles on authors 1 and 2 with articles 3 and 4.
+    let top: &Candidate<TypedGraph> = &beam.candidates[0];
+    assert_nodes_have_ids(&graph, &top.core_ids, vec![1, 2], true);
+    assert_nodes_have_ids(&graph, &top.non_core_ids, vec![3, 4], false);
+    assert_eq!(beam.snapshot()?.top_score(), Some(prior_score));
+    Ok(())
+}
+
+#[test]
+fn test_beam_memory_cap() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let target_types: Vec<String> = vec!["article".to_string()];
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t2\t3\tauthor\tpublished\tarticle".into(),
+        "0\t1\t4\tauthor\tpublished\tarticle".into(),
+        "0\t2\t4\tauthor\tpublished\tarticle".into(),
+        "0\t2\t5\tauthor\tpublished\tarticle".into(),
+    ];
+    let graph_id: GraphId = 0.into();
+    let transformer = Transformer::new(
+        typespec,
+        20,
+        1.0,
+        Some(0.5),
+        Some(0.5),
+        20,
+        100,
+        3,
+        true,
+        0,
+        "author".to_string(),
+        false,
+    )?;
+    let rows: Vec<EdgeRow> = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+    let clique_rows: Vec<CliqueRow> = Vec::new();
+
+    // without a cap, memory is only reported.
+    let mut beam: Beam<TypedGraph> = Beam::new(
+        &graph,
+        &clique_rows,
+        false,
+        &target_types,
+        transformer.search_problem.clone(),
+        graph_id,
+    )?;
+    let snapshot = beam.step()?;
+    assert!(snapshot.candidates.len() <= 20);
+    assert_eq!(snapshot.memory_bytes, beam.estimated_memory_bytes());
+    assert!(snapshot.memory_bytes > 0);
+    assert!(beam.peak_memory_bytes() >= snapshot.memory_bytes);
+    let largest_candidate_bytes: usize = beam
+        .candidates
+        .iter()
+        .map(|candidate| candidate.estimated_memory_bytes())
+        .max()
+        .ok_or_else(CLQError::err_none)?;
+
+    // with room for only a few candidates, the beam shrinks to fit.
+    let max_beam_memory = 3 * largest_candidate_bytes;
+    let transformer = transformer.with_max_beam_memory(max_beam_memory)?;
+    let mut beam: Beam<TypedGraph> = Beam::new(
+        &graph,
+        &clique_rows,
+        false,
+        &target_types,
+        transformer.search_problem.clone(),
+        graph_id,
+    )?;
+    assert!(beam.snapshot()?.memory_bytes <= max_beam_memory);
+    for _epoch in 0..5 {
+        let snapshot = beam.step()?;
+        assert!(snapshot.memory_bytes <= max_beam_memory);
+        assert!(!snapshot.candidates.is_empty());
+        assert!(snapshot.candidates.len() < 20);
+    }
+    assert!(beam.peak_memory_bytes() <= max_beam_memory);
+
+    // without room for a single candidate, the search stops.
+    let transformer = transformer.with_max_beam_memory(1)?;
+    let result = Beam::new(
+        &graph,
+        &clique_rows,
+        false,
+        &target_types,
+        transformer.search_problem.clone(),
+        graph_id,
+    );
+    assert!(matches!(
+        result,
+        Err(CLQError::BeamMemoryExceeded { limit: 1, .. })
+    ));
+    assert!(transformer.with_max_beam_memory(0).is_err());
     Ok(())
 }
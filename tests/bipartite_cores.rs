@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::bipartite_cores::BipartiteCores;
+use lib_dachshund::dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use rand::prelude::*;
+
+#[test]
+fn test_bipartite_core() -> CLQResult<()> {
+    let ids = |x: Vec<i64>| -> Vec<NodeId> { x.into_iter().map(NodeId::from).collect() };
+    // authors 1-3 all published at venues 10 and 11; author 4 at 11 and 12; author 5 at 12.
+    let graph = BipartiteGraphBuilder {}.from_vector(vec![
+        (1, 10),
+        (2, 10),
+        (3, 10),
+        (1, 11),
+        (2, 11),
+        (3, 11),
+        (4, 11),
+        (4, 12),
+        (5, 12),
+    ])?;
+    assert_eq!(
+        graph.get_bipartite_core(1, 1),
+        (ids(vec![1, 2, 3, 4, 5]), ids(vec![10, 11, 12]))
+    );
+    assert_eq!(
+        graph.get_bipartite_core(2, 1),
+        (ids(vec![1, 2, 3, 4]), ids(vec![10, 11, 12]))
+    );
+    // venue 12 is left with author 4 only, who then has a single venue.
+    assert_eq!(
+        graph.get_bipartite_core(2, 2),
+        (ids(vec![1, 2, 3]), ids(vec![10, 11]))
+    );
+    assert_eq!(
+        graph.get_bipartite_core(2, 3),
+        (ids(vec![1, 2, 3]), ids(vec![10, 11]))
+    );
+    assert_eq!(graph.get_bipartite_core(2, 4), (vec![], vec![]));
+    assert_eq!(graph.get_bipartite_core(3, 1), (vec![], vec![]));
+
+    let core_numbers = graph.get_bipartite_core_numbers(2);
+    assert_eq!(core_numbers.len(), 7);
+    assert_eq!(core_numbers[&NodeId::from(1)], 3);
+    assert_eq!(core_numbers[&NodeId::from(11)], 3);
+    assert_eq!(core_numbers[&NodeId::from(4)], 1);
+    assert_eq!(core_numbers[&NodeId::from(12)], 1);
+    assert!(!core_numbers.contains_key(&NodeId::from(5)));
+    Ok(())
+}
+
+#[test]
+fn test_bipartite_core_numbers_random() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let edges: Vec<(i64, i64)> = (0..80)
+            .map(|_| (rng.gen_range(0..15), rng.gen_range(100..112)))
+            .collect();
+        let graph = BipartiteGraphBuilder {}.from_vector(edges)?;
+        for alpha in 1..5 {
+            let core_numbers = graph.get_bipartite_core_numbers(alpha);
+            // each node's number is the largest beta for which it is in the core.
+            for beta in 0..8 {
+                let (core_ids, non_core_ids) = graph.get_bipartite_core(alpha, beta);
+                for id in core_ids.iter().chain(non_core_ids.iter()) {
+                    assert!(core_numbers[id] >= beta);
+                }
+                let in_core = core_numbers.values().filter(|x| **x >= beta).count();
+                assert_eq!(in_core, core_ids.len() + non_core_ids.len());
+            }
+        }
+    }
+    Ok(())
+}
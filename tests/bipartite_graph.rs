@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::bipartite_graph::{ProjectionWeighting, Shore};
+use lib_dachshund::dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::WeightedNodeEdgeBase;
+use lib_dachshund::dachshund::node::{NodeBase, NodeEdgeBase};
+use lib_dachshund::dachshund::test_utils::gen_authorship_graph;
+use lib_dachshund::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+
+fn get_weight(graph: &WeightedUndirectedGraph, id1: i64, id2: i64) -> Option<f64> {
+    graph
+        .get_node(NodeId::from(id1))
+        .get_edges()
+        .find(|e| e.get_neighbor_id() == NodeId::from(id2))
+        .map(|e| e.get_weight())
+}
+
+#[test]
+fn test_bipartite_graph() -> CLQResult<()> {
+    let id = NodeId::from;
+    let graph = gen_authorship_graph()?;
+    assert_eq!(graph.count_nodes(), 7);
+    assert_eq!(graph.count_edges(), 6);
+    assert_eq!(graph.get_core_ids(), &[id(1), id(2), id(3), id(4)]);
+    assert_eq!(graph.get_shore_ids(Shore::Right), &[id(10), id(11), id(12)]);
+    assert_eq!(graph.get_shore(id(3)), Some(Shore::Left));
+    assert_eq!(graph.get_shore(id(11)), Some(Shore::Right));
+    assert_eq!(graph.get_shore(id(5)), None);
+    assert_eq!(graph.get_node(id(10)).degree(), 3);
+
+    assert!(BipartiteGraphBuilder {}
+        .from_vector(vec![(1, 2), (2, 3)])
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_bipartite_projections() -> CLQResult<()> {
+    let id = NodeId::from;
+    let graph = gen_authorship_graph()?;
+
+    let simple = graph.get_projection(Shore::Left, ProjectionWeighting::Simple);
+    assert_eq!(
+        simple.get_ordered_node_ids(),
+        vec![id(1), id(2), id(3), id(4)]
+    );
+    assert_eq!(simple.count_edges(), 3);
+    assert_eq!(get_weight(&simple, 1, 2), Some(1.0));
+    assert_eq!(get_weight(&simple, 3, 1), Some(1.0));
+    assert_eq!(simple.get_node(id(4)).degree(), 0);
+
+    let shared = graph.get_projection(Shore::Left, ProjectionWeighting::SharedNeighbors);
+    assert_eq!(get_weight(&shared, 1, 2), Some(2.0));
+    assert_eq!(get_weight(&shared, 2, 1), Some(2.0));
+    assert_eq!(get_weight(&shared, 2, 3), Some(1.0));
+    assert_eq!(get_weight(&shared, 3, 4), None);
+
+    // 1 and 2 wrote paper 10 with one other author and paper 11 alone: 1/2 + 1.
+    let collaboration = graph.get_projection(Shore::Left, ProjectionWeighting::Collaboration);
+    assert_eq!(get_weight(&collaboration, 1, 2), Some(1.5));
+    assert_eq!(get_weight(&collaboration, 1, 3), Some(0.5));
+    assert_eq!(collaboration.get_node_weight(id(1)), 2.0);
+
+    // papers 10 and 11 share authors 1 and 2.
+    let papers = graph.get_projection(Shore::Right, ProjectionWeighting::SharedNeighbors);
+    assert_eq!(papers.count_nodes(), 3);
+    assert_eq!(papers.count_edges(), 1);
+    assert_eq!(get_weight(&papers, 10, 11), Some(2.0));
+    Ok(())
+}
@@ -0,0 +1,96 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::bipartite_matching::BipartiteMatching;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::node::NodeEdgeBase;
+use lib_dachshund::dachshund::test_utils::{
+    gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use std::collections::HashSet;
+
+fn build_graph(edges: Vec<(i64, i64)>) -> CLQResult<TypedGraph> {
+    let mut typespec = gen_test_typespec();
+    typespec.push(vec![
+        "author".to_string(),
+        "reviewed_for".into(),
+        "journal".into(),
+    ]);
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let raw: Vec<String> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, (core, non_core))| {
+            let edge_type = if i % 2 == 0 {
+                "published_at"
+            } else {
+                "reviewed_for"
+            };
+            format!("0\t{}\t{}\tauthor\t{}\tjournal", core, non_core, edge_type)
+        })
+        .collect();
+    let rows = process_raw_vector(&transformer, raw)?;
+    transformer.build_pruned_graph(GraphId::from(0), rows)
+}
+
+fn get_ids(graph: &TypedGraph, labels: Vec<i64>) -> Vec<u32> {
+    labels
+        .into_iter()
+        .map(|label| graph.get_node_by_label(label.into()).node_id)
+        .collect()
+}
+
+fn assert_is_cover(graph: &TypedGraph, cover: &(Vec<u32>, Vec<u32>)) {
+    let cover_ids: HashSet<u32> = cover.0.iter().chain(cover.1.iter()).copied().collect();
+    for id in graph.get_core_ids() {
+        for e in graph.get_node(*id).edges.iter() {
+            assert!(cover_ids.contains(id) || cover_ids.contains(&e.get_neighbor_id()));
+        }
+    }
+}
+
+#[test]
+fn test_maximum_matching() -> CLQResult<()> {
+    // authors 2 and 3 only published in journal 10, so one of them is left unmatched.
+    let graph = build_graph(vec![(1, 10), (1, 11), (2, 10), (3, 10)])?;
+    let matching = graph.get_maximum_matching();
+    assert_eq!(matching.len(), 2);
+    let matched_cores: HashSet<u32> = matching.iter().map(|(core, _)| *core).collect();
+    let matched_non_cores: HashSet<u32> = matching.iter().map(|(_, non_core)| *non_core).collect();
+    assert_eq!(matched_cores.len(), 2);
+    assert_eq!(matched_non_cores.len(), 2);
+    assert!(matching.contains(&(get_ids(&graph, vec![1])[0], get_ids(&graph, vec![11])[0])));
+
+    let cover = graph.get_minimum_vertex_cover();
+    assert_eq!(cover, (get_ids(&graph, vec![1]), get_ids(&graph, vec![10])));
+    assert_is_cover(&graph, &cover);
+    Ok(())
+}
+
+#[test]
+fn test_perfect_matching() -> CLQResult<()> {
+    // a path 10 - 1 - 11 - 2 - 12 - 3, with 1 and 11 joined by edges of both types;
+    // a greedy match of 1 to 11 has to be undone.
+    let graph = build_graph(vec![(1, 11), (1, 11), (1, 10), (2, 11), (2, 12), (3, 12)])?;
+    let mut matching = graph.get_maximum_matching();
+    matching.sort();
+    let mut expected: Vec<(u32, u32)> = get_ids(&graph, vec![1, 2, 3])
+        .into_iter()
+        .zip(get_ids(&graph, vec![10, 11, 12]))
+        .collect();
+    expected.sort();
+    assert_eq!(matching, expected);
+
+    let cover = graph.get_minimum_vertex_cover();
+    assert_eq!(cover.0.len() + cover.1.len(), 3);
+    assert_is_cover(&graph, &cover);
+    Ok(())
+}
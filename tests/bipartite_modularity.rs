@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::bipartite_modularity::{BipartiteModularity, CoCluster};
+use lib_dachshund::dachshund::bipartite_graph::BipartiteGraph;
+use lib_dachshund::dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::test_utils::{gen_test_transformer, process_raw_vector};
+use lib_dachshund::dachshund::typed_graph::LabeledGraph;
+use rand::prelude::*;
+
+fn co_cluster(core_ids: Vec<i64>, non_core_ids: Vec<i64>) -> CoCluster<NodeId> {
+    CoCluster {
+        core_ids: core_ids.into_iter().map(NodeId::from).collect(),
+        non_core_ids: non_core_ids.into_iter().map(NodeId::from).collect(),
+    }
+}
+
+// complete bipartite graphs between each group of core nodes and group of non-core nodes,
+// plus the given edges.
+fn get_graph(
+    groups: Vec<(Vec<i64>, Vec<i64>)>,
+    extra: Vec<(i64, i64)>,
+) -> CLQResult<BipartiteGraph> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for (core_ids, non_core_ids) in groups {
+        for i in &core_ids {
+            for j in &non_core_ids {
+                edges.push((*i, *j));
+            }
+        }
+    }
+    edges.extend(extra);
+    BipartiteGraphBuilder {}.from_vector(edges)
+}
+
+#[test]
+fn test_bipartite_modularity() -> CLQResult<()> {
+    let graph = get_graph(
+        vec![(vec![1, 2], vec![10, 11]), (vec![3, 4], vec![12, 13])],
+        vec![],
+    )?;
+    // each co-cluster has 4 of the 8 edges, and core and non-core degrees of 4.
+    let co_clusters = vec![
+        co_cluster(vec![1, 2], vec![10, 11]),
+        co_cluster(vec![3, 4], vec![12, 13]),
+    ];
+    assert!((graph.get_bipartite_modularity(&co_clusters) - 0.5).abs() < 1e-9);
+    let everything = vec![co_cluster(vec![1, 2, 3, 4], vec![10, 11, 12, 13])];
+    assert!(graph.get_bipartite_modularity(&everything).abs() < 1e-9);
+    // nodes on their own: no co-cluster has both core and non-core nodes.
+    assert!(graph.get_bipartite_modularity(&[]).abs() < 1e-9);
+
+    let (found, modularity) = graph.get_bilouvain_co_clusters();
+    assert_eq!(found, co_clusters);
+    assert!((modularity - 0.5).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_bilouvain_bridged_bicliques() -> CLQResult<()> {
+    let graph = get_graph(
+        vec![
+            (vec![1, 2, 3], vec![10, 11, 12]),
+            (vec![4, 5, 6], vec![13, 14, 15]),
+            (vec![7, 8], vec![16, 17]),
+        ],
+        vec![(3, 13), (6, 16)],
+    )?;
+    let (co_clusters, modularity) = graph.get_bilouvain_co_clusters();
+    assert_eq!(
+        co_clusters,
+        vec![
+            co_cluster(vec![1, 2, 3], vec![10, 11, 12]),
+            co_cluster(vec![4, 5, 6], vec![13, 14, 15]),
+            co_cluster(vec![7, 8], vec![16, 17]),
+        ]
+    );
+    assert!((graph.get_bipartite_modularity(&co_clusters) - modularity).abs() < 1e-9);
+    assert!(modularity > 0.5);
+
+    let (co_clusters, modularity) = get_graph(vec![], vec![])?.get_bilouvain_co_clusters();
+    assert!(co_clusters.is_empty());
+    assert_eq!(modularity, 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_bilouvain_random() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10 {
+        // two planted co-clusters, plus noise.
+        let edges: Vec<(i64, i64)> = (0..120)
+            .map(|_| {
+                let block = rng.gen_range(0..2);
+                if rng.gen::<f64>() < 0.8 {
+                    (
+                        block * 10 + rng.gen_range(0..10),
+                        100 + block * 10 + rng.gen_range(0..10),
+                    )
+                } else {
+                    (rng.gen_range(0..20), rng.gen_range(100..120))
+                }
+            })
+            .collect();
+        let graph = BipartiteGraphBuilder {}.from_vector(edges)?;
+        let (co_clusters, modularity) = graph.get_bilouvain_co_clusters();
+        assert!((graph.get_bipartite_modularity(&co_clusters) - modularity).abs() < 1e-9);
+        let num_nodes: usize = co_clusters
+            .iter()
+            .map(|c| c.core_ids.len() + c.non_core_ids.len())
+            .sum();
+        assert_eq!(num_nodes, graph.left_ids.len() + graph.right_ids.len());
+        assert!(modularity > 0.3);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bilouvain_typed_graph() -> CLQResult<()> {
+    let typespec = vec![vec![
+        "author".into(),
+        "published_at".into(),
+        "conference".into(),
+    ]];
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let mut raw: Vec<String> = Vec::new();
+    for (authors, conferences) in [(vec![1, 2], vec![5, 6]), (vec![3, 4], vec![7, 8])] {
+        for author in &authors {
+            for conference in &conferences {
+                raw.push(format!(
+                    "0\t{}\t{}\tauthor\tpublished_at\tconference",
+                    author, conference
+                ));
+            }
+        }
+    }
+    let rows = process_raw_vector(&transformer, raw)?;
+    let graph = transformer.build_pruned_graph(GraphId::from(0), rows)?;
+    let (co_clusters, modularity) = graph.get_bilouvain_co_clusters();
+    let get_labels = |ids: &[u32]| -> Vec<i64> {
+        let mut labels: Vec<i64> = ids
+            .iter()
+            .map(|id| graph.get_node_label(*id).unwrap().value())
+            .collect();
+        labels.sort_unstable();
+        labels
+    };
+    let mut labels: Vec<(Vec<i64>, Vec<i64>)> = co_clusters
+        .iter()
+        .map(|c| (get_labels(&c.core_ids), get_labels(&c.non_core_ids)))
+        .collect();
+    labels.sort();
+    assert_eq!(
+        labels,
+        vec![(vec![1, 2], vec![5, 6]), (vec![3, 4], vec![7, 8])]
+    );
+    assert!((modularity - 0.5).abs() < 1e-9);
+    Ok(())
+}
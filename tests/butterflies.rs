@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::butterflies::Butterflies;
+use lib_dachshund::dachshund::bipartite_graph::BipartiteGraph;
+use lib_dachshund::dachshund::bipartite_graph_builder::BipartiteGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::test_utils::{
+    gen_authorship_graph, gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use rand::prelude::*;
+
+fn build_typed_graph(edges: Vec<(i64, i64)>) -> CLQResult<TypedGraph> {
+    let mut typespec = gen_test_typespec();
+    typespec.push(vec![
+        "author".to_string(),
+        "reviewed_for".into(),
+        "journal".into(),
+    ]);
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let raw: Vec<String> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, (core, non_core))| {
+            let edge_type = if i % 2 == 0 {
+                "published_at"
+            } else {
+                "reviewed_for"
+            };
+            format!("0\t{}\t{}\tauthor\t{}\tjournal", core, non_core, edge_type)
+        })
+        .collect();
+    let rows = process_raw_vector(&transformer, raw)?;
+    transformer.build_pruned_graph(GraphId::from(0), rows)
+}
+
+// counts butterflies by checking every pair of left and every pair of right nodes.
+fn get_brute_force_count(graph: &BipartiteGraph) -> usize {
+    let mut count = 0;
+    for (i, u) in graph.left_ids.iter().enumerate() {
+        for w in &graph.left_ids[i + 1..] {
+            let shared = graph
+                .get_node(*u)
+                .neighbors
+                .iter()
+                .filter(|v| graph.get_node(*w).has_neighbor(**v))
+                .count();
+            count += shared * shared.saturating_sub(1) / 2;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_butterflies_complete_bipartite() -> CLQResult<()> {
+    let id = NodeId::from;
+    // K2,3 with a pendant edge: 3 butterflies.
+    let graph = BipartiteGraphBuilder {}.from_vector(vec![
+        (1, 10),
+        (1, 11),
+        (1, 12),
+        (2, 10),
+        (2, 11),
+        (2, 12),
+        (3, 12),
+    ])?;
+    assert_eq!(graph.get_butterfly_count(), 3);
+    let counts = graph.get_butterfly_counts();
+    assert_eq!(counts.len(), 6);
+    assert_eq!(counts[&id(1)], 3);
+    assert_eq!(counts[&id(2)], 3);
+    assert_eq!(counts[&id(3)], 0);
+    assert_eq!(counts[&id(10)], 2);
+    assert_eq!(counts[&id(12)], 2);
+
+    let support = graph.get_edge_butterfly_counts();
+    assert_eq!(support.len(), 7);
+    assert_eq!(support[&(id(1), id(10))], 2);
+    assert_eq!(support[&(id(2), id(12))], 2);
+    assert_eq!(support[&(id(3), id(12))], 0);
+
+    let empty = BipartiteGraphBuilder {}.from_vector(vec![(1, 10), (2, 11)])?;
+    assert_eq!(empty.get_butterfly_count(), 0);
+    // authors 1 and 2 share papers 10 and 11, whichever way their edges are listed.
+    assert_eq!(gen_authorship_graph()?.get_butterfly_count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_butterflies_random() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let edges: Vec<(i64, i64)> = (0..60)
+            .map(|_| (rng.gen_range(0..10), rng.gen_range(100..115)))
+            .collect();
+        let graph = BipartiteGraphBuilder {}.from_vector(edges)?;
+        let count = graph.get_butterfly_count();
+        assert_eq!(count, get_brute_force_count(&graph));
+
+        // each butterfly has four nodes and four edges.
+        let counts = graph.get_butterfly_counts();
+        assert_eq!(counts.values().sum::<usize>(), 4 * count);
+        let support = graph.get_edge_butterfly_counts();
+        assert_eq!(support.len(), graph.count_edges());
+        assert_eq!(support.values().sum::<usize>(), 4 * count);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_butterflies_typed_graph() -> CLQResult<()> {
+    // authors 1 and 2 both published at and reviewed for journals 10 and 11: edges of
+    // either type count once.
+    let graph = build_typed_graph(vec![(1, 10), (1, 10), (1, 11), (2, 10), (2, 11), (3, 11)])?;
+    assert_eq!(graph.get_butterfly_count(), 1);
+    let counts = graph.get_butterfly_counts();
+    let get_count = |label: i64| counts[&graph.get_node_by_label(label.into()).node_id];
+    assert_eq!(get_count(1), 1);
+    assert_eq!(get_count(3), 0);
+    assert_eq!(get_count(11), 1);
+    assert_eq!(graph.get_edge_butterfly_counts().values().sum::<usize>(), 4);
+    Ok(())
+}
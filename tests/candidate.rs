@@ -6,18 +6,19 @@
  */
 extern crate lib_dachshund;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use lib_dachshund::dachshund::candidate::{Candidate, Recipe};
+use lib_dachshund::dachshund::candidate::{Candidate, NeigbhorhoodMap, Recipe};
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
-use lib_dachshund::dachshund::node::Node;
+use lib_dachshund::dachshund::node::{Node, NodeEdges};
 use lib_dachshund::dachshund::row::CliqueRow;
 use lib_dachshund::dachshund::row::EdgeRow;
 use lib_dachshund::dachshund::scorer::Scorer;
 use lib_dachshund::dachshund::test_utils::{gen_test_transformer, process_raw_vector};
 use lib_dachshund::dachshund::transformer::Transformer;
 use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use lib_dachshund::dachshund::typed_graph_builder::{TypedGraphBuilder, TypedGraphBuilderBase};
 
 extern crate fxhash;
 use fxhash::FxHashMap;
@@ -27,22 +28,23 @@ use fxhash::FxHashMap;
 fn test_output_simple_candidate() -> CLQResult<()> {
     let node_id = NodeId::from(0);
     let node_idx = 0;
-    let node: Node = Node::new(node_idx, true, None, Vec::new(), HashMap::new());
+    let node: Node = Node::new(node_idx, true, None, NodeEdges::new(), HashMap::new());
     let mut graph: TypedGraph = TypedGraph {
         nodes: FxHashMap::default(),
         core_ids: vec![],
         non_core_ids: vec![],
         labels_map: FxHashMap::default(),
+        labels: vec![],
     };
     graph.nodes.insert(node_idx, node);
     graph.core_ids.push(node_idx);
     graph.labels_map.insert(node_id, node_idx);
+    graph.labels.push(node_id);
 
     let mut candidate: Candidate<TypedGraph> = Candidate::init_blank(&graph, 1);
     candidate.add_node(node_idx)?;
     let graph_id: GraphId = 1.into();
-    let output_rows: Vec<CliqueRow> =
-        candidate.get_output_rows(graph_id, graph.get_reverse_labels_map())?;
+    let output_rows: Vec<CliqueRow> = candidate.get_output_rows(graph_id)?;
     assert_eq!(output_rows.len(), 1);
     assert_eq!(output_rows[0].graph_id, graph_id);
     assert_eq!(output_rows[0].node_id, NodeId::from(0));
@@ -74,8 +76,7 @@ fn test_rebuild_candidate() -> CLQResult<()> {
     candidate.set_score(score)?;
 
     let graph_id: GraphId = 1.into();
-    let output_rows: Vec<CliqueRow> =
-        candidate.get_output_rows(graph_id, graph.get_reverse_labels_map())?;
+    let output_rows: Vec<CliqueRow> = candidate.get_output_rows(graph_id)?;
     assert_eq!(output_rows.len(), 2);
     assert_eq!(output_rows[0].graph_id, graph_id);
     assert_eq!(output_rows[0].node_id, NodeId::from(1));
@@ -118,6 +119,58 @@ fn build_sample_graph() -> (TypedGraph, Transformer) {
     )
 }
 
+#[test]
+fn test_labeled_graph_lookups() -> CLQResult<()> {
+    let (graph, _transformer) = build_sample_graph();
+    let labels: HashSet<NodeId> = graph.get_labels_iter().cloned().collect();
+    assert_eq!(labels, (1..7).map(NodeId::from).collect());
+    for label in labels {
+        assert!(graph.has_label(label));
+        let node_id: u32 = graph.get_node_by_label(label).node_id;
+        assert_eq!(graph.get_node_label(node_id), Some(label));
+    }
+    assert!(!graph.has_label(NodeId::from(7)));
+    assert_eq!(graph.get_node_label(6), None);
+
+    // internal ids that don't run from 0 are reported rather than looked up.
+    let labels_map: FxHashMap<NodeId, u32> = vec![(NodeId::from(1), 0), (NodeId::from(2), 2)]
+        .into_iter()
+        .collect();
+    assert!(
+        TypedGraphBuilder::create_graph(FxHashMap::default(), vec![], vec![], labels_map).is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_candidate_from_labels() -> CLQResult<()> {
+    let (graph, transformer) = build_sample_graph();
+    let scorer: Scorer = Scorer::new(1, &transformer.search_problem);
+
+    let candidate: Candidate<TypedGraph> = Candidate::from_labels(
+        &[1.into(), 3.into()],
+        &[4.into(), 4.into(), 100.into()],
+        &graph,
+        &scorer,
+    )?
+    .unwrap();
+    assert_eq!(candidate.sorted_core_labels(), vec![1, 3]);
+    assert_eq!(candidate.sorted_non_core_labels(), vec![4]);
+    assert!(candidate.get_score().is_ok());
+
+    let mut expected: Candidate<TypedGraph> =
+        Candidate::new(graph.get_node_by_label(1.into()).node_id, &graph, &scorer)?;
+    expected.add_node(graph.get_node_by_label(3.into()).node_id)?;
+    expected.add_node(graph.get_node_by_label(4.into()).node_id)?;
+    assert_eq!(candidate.checksum, expected.checksum);
+
+    // no overlap with the graph.
+    assert!(Candidate::from_labels(&[100.into()], &[], &graph, &scorer)?.is_none());
+    // 2 is an article, not an author.
+    assert!(Candidate::from_labels(&[2.into()], &[], &graph, &scorer).is_err());
+    Ok(())
+}
+
 /// Test that a candidate correctly tracks its neighborhood.
 ///
 ///  1 - 2
@@ -144,7 +197,7 @@ fn test_neighborhood() -> CLQResult<()> {
     let node_6: u32 = graph.get_node_by_label(6.into()).node_id;
 
     let neighborhood = candidate.get_neighborhood();
-    let mut expected_neighborhood: HashMap<u32, u32> = HashMap::new();
+    let mut expected_neighborhood = NeigbhorhoodMap::default();
     expected_neighborhood.insert(node_2, 1);
     expected_neighborhood.insert(node_4, 2);
     assert_eq!(neighborhood, expected_neighborhood);
@@ -153,7 +206,7 @@ fn test_neighborhood() -> CLQResult<()> {
     // be added with value 1.
     candidate.add_node(node_4)?;
     let neighborhood = candidate.get_neighborhood();
-    let mut expected_neighborhood: HashMap<u32, u32> = HashMap::new();
+    let mut expected_neighborhood = NeigbhorhoodMap::default();
     expected_neighborhood.insert(node_2, 1);
     expected_neighborhood.insert(node_3, 1);
     assert_eq!(neighborhood, expected_neighborhood);
@@ -162,7 +215,7 @@ fn test_neighborhood() -> CLQResult<()> {
     // be added with value 1.
     candidate.add_node(node_3)?;
     let neighborhood = candidate.get_neighborhood();
-    let mut expected_neighborhood: HashMap<u32, u32> = HashMap::new();
+    let mut expected_neighborhood = NeigbhorhoodMap::default();
     expected_neighborhood.insert(node_2, 1);
     expected_neighborhood.insert(node_6, 1);
     assert_eq!(neighborhood, expected_neighborhood);
@@ -233,6 +286,135 @@ fn test_incremental() -> CLQResult<()> {
     Ok(())
 }
 
+/// Test that a candidate's incremental statistics are undone by removing nodes.
+///
+///  1 - 2
+///    \\
+///  3 - 4
+///    \
+///  5 - 6
+///
+/// Start with {1, 3, 4, 6}, then remove 6, then remove 3, then remove 4.
+#[test]
+fn test_decremental() -> CLQResult<()> {
+    let (graph, transformer) = build_sample_graph();
+    let scorer: Scorer = Scorer::new(2, &transformer.search_problem);
+
+    let node_1 = graph.get_node_by_label(1.into());
+    let node_3 = graph.get_node_by_label(3.into());
+    let node_4 = graph.get_node_by_label(4.into());
+    let node_6 = graph.get_node_by_label(6.into());
+
+    let mut candidate: Candidate<TypedGraph> = Candidate::new(node_1.node_id, &graph, &scorer)?;
+    candidate.add_node(node_4.node_id)?;
+    let recipe_14 = candidate.as_recipe();
+    candidate.add_node(node_3.node_id)?;
+    let recipe_134 = candidate.as_recipe();
+    candidate.add_node(node_6.node_id)?;
+
+    // Removing 6 from the clique. Expected local densities: {1: 1.0, 3: 0.5}
+    let new_size = candidate.get_size_without_node(node_6)?;
+    let new_cliqueness = candidate.get_cliqueness_without_node(node_6)?;
+    assert!(candidate.local_thresh_score_without_node_at_least(0.5, node_6));
+    assert!(!candidate.local_thresh_score_without_node_at_least(0.51, node_6));
+    candidate.remove_node(node_6.node_id)?;
+    assert_eq!(new_size, candidate.get_size()?);
+    assert_eq!(new_cliqueness, candidate.get_cliqueness()?);
+    assert_eq!(candidate.as_recipe().checksum, recipe_134.checksum);
+
+    // Removing 3 from the clique, so both of the possible edges should exist.
+    let new_size = candidate.get_size_without_node(node_3)?;
+    let new_cliqueness = candidate.get_cliqueness_without_node(node_3)?;
+    assert!(candidate.local_thresh_score_without_node_at_least(1.0, node_3));
+    candidate.remove_node(node_3.node_id)?;
+    assert_eq!(new_size, candidate.get_size()?);
+    assert_eq!(new_cliqueness, candidate.get_cliqueness()?);
+    assert_eq!(candidate.as_recipe().checksum, recipe_14.checksum);
+    assert!(candidate.local_thresh_score_at_least(1.0));
+
+    // Removing 4 from the clique leaves only 1, with 2 and 4 in its neighborhood.
+    assert_eq!(candidate.get_size_without_node(node_4)?, 0);
+    assert_eq!(candidate.get_cliqueness_without_node(node_4)?, 1.0);
+    candidate.remove_node(node_4.node_id)?;
+    assert_eq!(candidate.count_ties_between_nodes()?, 0);
+    assert_eq!(candidate.get_node_counts(), vec![1, 0, 0]);
+    let neighborhood = candidate.get_neighborhood();
+    let node_2: u32 = graph.get_node_by_label(2.into()).node_id;
+    assert_eq!(
+        neighborhood,
+        NeigbhorhoodMap::from_iter([(node_2, 1), (node_4.node_id, 2)])
+    );
+
+    // 4 is no longer part of the clique.
+    assert!(candidate.remove_node(node_4.node_id).is_err());
+    Ok(())
+}
+
+/// Test that removing any node from any candidate of the graph above gives the same
+/// statistics as building the smaller candidate from scratch.
+#[test]
+fn test_decremental_exhaustive() -> CLQResult<()> {
+    let (graph, _transformer) = build_sample_graph();
+    let mut node_ids: Vec<u32> = graph.nodes.keys().cloned().collect();
+    node_ids.sort_unstable();
+    let build = |ids: &[u32]| -> CLQResult<Candidate<TypedGraph>> {
+        let mut candidate: Candidate<TypedGraph> = Candidate::init_blank(&graph, 1);
+        for node_id in ids {
+            candidate.add_node(*node_id)?;
+        }
+        Ok(candidate)
+    };
+    let thresholds: Vec<f32> = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+
+    for subset in 1..(1 << node_ids.len()) {
+        let ids: Vec<u32> = (0..node_ids.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| node_ids[i])
+            .collect();
+        for removed_id in &ids {
+            let removed: &Node = &graph.nodes[removed_id];
+            let remaining: Vec<u32> = ids.iter().filter(|x| *x != removed_id).cloned().collect();
+            let expected = build(&remaining)?;
+
+            let mut candidate = build(&ids)?;
+            // build up a local guarantee, to check it is kept sound by the removal.
+            candidate.local_thresh_score_at_least(0.5);
+            let new_size = candidate.get_size_without_node(removed)?;
+            let new_cliqueness = candidate.get_cliqueness_without_node(removed)?;
+            let new_local_thresh_scores: Vec<bool> = thresholds
+                .iter()
+                .map(|t| candidate.local_thresh_score_without_node_at_least(*t, removed))
+                .collect();
+            candidate.remove_node(*removed_id)?;
+
+            assert_eq!(
+                candidate.as_recipe().checksum,
+                expected.as_recipe().checksum
+            );
+            assert_eq!(candidate.get_node_counts(), expected.get_node_counts());
+            assert_eq!(
+                candidate.count_ties_between_nodes()?,
+                expected.count_ties_between_nodes()?
+            );
+            assert_eq!(candidate.get_size()?, expected.get_size()?);
+            assert_eq!(new_size, expected.get_size()?);
+            assert_eq!(candidate.get_cliqueness()?, expected.get_cliqueness()?);
+            assert_eq!(new_cliqueness, expected.get_cliqueness()?);
+            assert_eq!(candidate.get_neighborhood(), expected.get_neighborhood());
+            let mut expected = expected;
+            for (thresh, new_local_thresh_score) in thresholds.iter().zip(new_local_thresh_scores) {
+                let expected_score = expected.local_thresh_score_at_least(*thresh);
+                assert_eq!(new_local_thresh_score, expected_score);
+                assert_eq!(
+                    candidate.local_thresh_score_at_least(*thresh),
+                    expected_score
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Test that a candidate's appropriately calculates its local density.
 /// (Does not inspect the density guarantee itself.)
 ///
@@ -289,6 +471,45 @@ fn test_local_density() -> CLQResult<()> {
     Ok(())
 }
 
+/// Test that a candidate checks the local density of non-core nodes against the local
+/// threshold of their type, with the same graph as above.
+///
+/// Start with {1, 4}, then add 3, then add 6.
+#[test]
+fn test_non_core_local_density() -> CLQResult<()> {
+    let (graph, transformer) = build_sample_graph();
+    let scorer: Scorer = Scorer::new(1, &transformer.search_problem);
+    let node_3 = graph.get_node_by_label(3.into());
+    let node_4 = graph.get_node_by_label(4.into());
+    let node_6 = graph.get_node_by_label(6.into());
+    let article_type_id = node_4.non_core_type.unwrap().value();
+    let thresh = |x: f32| BTreeMap::from([(article_type_id, x)]);
+
+    let mut candidate: Candidate<TypedGraph> =
+        Candidate::new(graph.get_node_by_label(1.into()).node_id, &graph, &scorer)?;
+    candidate.add_node(node_4.node_id)?;
+    // 4 has both possible ties with 1.
+    assert!(candidate.non_core_local_thresh_score_at_least(&thresh(1.0)));
+
+    // Adding 3 gives 4 another possible tie, and 3 of its 4 possible ties.
+    assert!(candidate.non_core_local_thresh_score_with_node_at_least(&thresh(0.75), node_3));
+    assert!(!candidate.non_core_local_thresh_score_with_node_at_least(&thresh(0.76), node_3));
+    candidate.add_node(node_3.node_id)?;
+    assert!(candidate.non_core_local_thresh_score_at_least(&thresh(0.75)));
+    assert!(!candidate.non_core_local_thresh_score_at_least(&thresh(0.76)));
+
+    // 6 has 1 of its 4 possible ties.
+    assert!(candidate.non_core_local_thresh_score_with_node_at_least(&thresh(0.25), node_6));
+    assert!(!candidate.non_core_local_thresh_score_with_node_at_least(&thresh(0.26), node_6));
+    candidate.add_node(node_6.node_id)?;
+    assert!(candidate.non_core_local_thresh_score_at_least(&thresh(0.25)));
+    assert!(!candidate.non_core_local_thresh_score_at_least(&thresh(0.26)));
+
+    // types without a threshold aren't checked.
+    assert!(candidate.non_core_local_thresh_score_at_least(&BTreeMap::new()));
+    Ok(())
+}
+
 /// Test that a candidate is appropriately calculating / updating its local density guarantee.
 /// (Note that this test is a bit of an abstraction violation: Candidate doesn't technically
 /// specify any contract about how it will choose to update its local guarantee, only that
@@ -414,3 +635,39 @@ fn test_one_step_search() -> CLQResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_local_densities() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let raw: Vec<String> = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t1\t3\tauthor\tcited\tarticle".into(),
+        "0\t1\t4\tauthor\tpublished\tarticle".into(),
+        "0\t2\t3\tauthor\tpublished\tarticle".into(),
+    ];
+    let graph_id: GraphId = 0.into();
+    let transformer: Transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let rows: Vec<EdgeRow> = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+    let ids: Vec<u32> = vec![1, 2, 3, 4]
+        .into_iter()
+        .map(|label| graph.get_node_by_label(NodeId::from(label)).node_id)
+        .collect();
+
+    let scorer: Scorer = Scorer::new(2, &transformer.search_problem);
+    let mut candidate: Candidate<TypedGraph> = Candidate::new(ids[0], &graph, &scorer)?;
+    // no non-core nodes yet, so nothing is missing.
+    assert_eq!(candidate.get_local_densities()[&ids[0]], 1.0);
+    for id in &ids[1..] {
+        candidate.add_node(*id)?;
+    }
+    // each article allows for 2 edges (one of each type) with each author.
+    let densities = candidate.get_local_densities();
+    assert_eq!(densities.len(), 2);
+    assert_eq!(densities[&ids[0]], 0.75);
+    assert_eq!(densities[&ids[1]], 0.25);
+    Ok(())
+}
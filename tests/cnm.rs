@@ -146,3 +146,32 @@ fn test_modularity_changes() -> CLQResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_cnm_dendrogram() -> CLQResult<()> {
+    let g = get_graph(3)?;
+    let dendrogram = g.get_cnm_dendrogram();
+    // the graph is connected, so every node ends up in one community.
+    assert_eq!(dendrogram.merges.len(), 6);
+    assert_eq!(dendrogram.cut_at(1).len(), 1);
+    let expected = get_expected_modularity_changes(3).unwrap();
+    for (merge, expected_change) in dendrogram.merges.iter().zip(expected) {
+        assert_eq!(merge.modularity_change, expected_change);
+    }
+    // the first 4 merges raise modularity, from 7 communities to 3, and the last 2 lower it.
+    let best = dendrogram.cut_at_modularity();
+    assert_eq!(best.len(), 3);
+    assert_eq!(best, dendrogram.cut_at(3));
+    assert_eq!(dendrogram.cut_at(7).len(), 7);
+    assert_eq!(dendrogram.cut_at(10).len(), 7);
+
+    // disconnected triads never merge.
+    let g = get_graph(1)?;
+    let dendrogram = g.get_cnm_dendrogram();
+    assert_eq!(dendrogram.cut_at(1).len(), 2);
+    assert_eq!(dendrogram.cut_at_modularity(), g.get_cnm_communities().0);
+    let modularities = dendrogram.get_modularities();
+    assert!((modularities[0] + 6.0 * 4.0 / 144.0).abs() < 1e-12);
+    assert!((modularities[4] - 0.5).abs() < 1e-12);
+    Ok(())
+}
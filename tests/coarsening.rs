@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::coarsening::Coarsening;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::WeightedNodeBase;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn test_heavy_edge_matching() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // heavy edges at either end of a path, whatever order nodes are matched in.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 10.0),
+        (1, 2, 1.0),
+        (2, 3, 10.0),
+    ])?;
+    let hierarchy = graph.get_coarsening_hierarchy_with_rng(2, &mut rng);
+    assert_eq!(hierarchy.len(), 1);
+    let level = &hierarchy[0];
+    let id = |x: i64| NodeId::from(x);
+    assert_eq!(level.projection[&id(0)], id(0));
+    assert_eq!(level.projection[&id(1)], id(0));
+    assert_eq!(level.projection[&id(2)], id(1));
+    assert_eq!(level.projection[&id(3)], id(1));
+    assert_eq!(level.graph.count_nodes(), 2);
+    assert_eq!(level.graph.get_node(id(0)).weight(), 1.0);
+    assert_eq!(level.node_weights[&id(1)], 2.0);
+    assert_eq!(level.internal_weights[&id(1)], 10.0);
+
+    // nothing to do on graphs that are already small enough.
+    assert!(graph
+        .get_coarsening_hierarchy_with_rng(4, &mut rng)
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_coarsening_hierarchy() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graph = SimpleUndirectedGraphBuilder {}.get_cycle_graph(16)?;
+    let hierarchy = graph.get_coarsening_hierarchy_with_rng(1, &mut rng);
+    assert!(!hierarchy.is_empty());
+    let mut num_nodes = graph.count_nodes();
+    for level in &hierarchy {
+        let num_coarse_nodes = level.graph.count_nodes();
+        assert!(num_coarse_nodes < num_nodes);
+        assert_eq!(level.projection.len(), num_nodes);
+        // weights are only ever moved around, never lost.
+        assert_eq!(level.node_weights.values().sum::<f64>(), 16.0);
+        let external: f64 = level
+            .graph
+            .get_nodes_iter()
+            .map(|node| node.weight())
+            .sum::<f64>()
+            / 2.0;
+        assert_eq!(
+            external + level.internal_weights.values().sum::<f64>(),
+            16.0
+        );
+        num_nodes = num_coarse_nodes;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_contract_communities() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // two triangles joined by two edges, and an isolated edge.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 2.0),
+        (0, 2, 1.0),
+        (3, 4, 1.0),
+        (4, 5, 1.0),
+        (3, 5, 1.0),
+        (2, 3, 0.5),
+        (1, 4, 0.25),
+        (6, 7, 3.0),
+    ])?;
+    let communities: HashMap<usize, HashSet<NodeId>> = HashMap::from([
+        (0, HashSet::from([id(0), id(1), id(2)])),
+        (1, HashSet::from([id(3), id(4), id(5)])),
+        (5, HashSet::from([id(6), id(7)])),
+    ]);
+    let contracted = graph.contract_communities(&communities)?;
+    assert_eq!(contracted.graph.count_nodes(), 3);
+    assert_eq!(contracted.projection[&id(4)], id(1));
+    assert_eq!(contracted.projection[&id(7)], id(5));
+    assert_eq!(contracted.node_weights[&id(0)], 3.0);
+    assert_eq!(contracted.node_weights[&id(5)], 2.0);
+    assert_eq!(contracted.internal_weights[&id(0)], 4.0);
+    assert_eq!(contracted.internal_weights[&id(1)], 3.0);
+    assert_eq!(contracted.internal_weights[&id(5)], 3.0);
+    let node = contracted.graph.get_node(id(0));
+    assert_eq!(node.edges.len(), 1);
+    assert_eq!(node.edges[0].target_id, id(1));
+    assert_eq!(node.edges[0].weight, 0.75);
+    assert_eq!(contracted.graph.get_node(id(5)).weight(), 0.0);
+
+    // unweighted graphs count edges.
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(6)?;
+    let partition: HashMap<NodeId, NodeId> = (0..6).map(|x| (id(x), id(x / 3))).collect();
+    let contracted = cycle.contract_partition(&partition)?;
+    assert_eq!(contracted.graph.get_node(id(0)).weight(), 2.0);
+    assert_eq!(contracted.internal_weights[&id(1)], 2.0);
+
+    // every node must be in exactly one part.
+    let mut missing = communities.clone();
+    missing.get_mut(&5).unwrap().remove(&id(7));
+    assert!(graph.contract_communities(&missing).is_err());
+    let mut overlapping = communities;
+    overlapping.get_mut(&5).unwrap().insert(id(0));
+    assert!(graph.contract_communities(&overlapping).is_err());
+    let mut unknown = partition;
+    unknown.insert(id(10), id(0));
+    assert!(cycle.contract_partition(&unknown).is_err());
+    Ok(())
+}
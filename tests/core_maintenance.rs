@@ -0,0 +1,112 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::core_maintenance::CoreMaintenance;
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use rand::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+
+// coreness of the graph with the given edges, computed from scratch, with nodes left without
+// edges at 0.
+fn get_expected_coreness(
+    edges: &BTreeSet<(i64, i64)>,
+    num_nodes: i64,
+) -> CLQResult<HashMap<NodeId, usize>> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.iter().cloned().collect())?;
+    let mut coreness = graph.get_coreness_values();
+    for i in 0..num_nodes {
+        coreness.entry(NodeId::from(i)).or_insert(0);
+    }
+    Ok(coreness)
+}
+
+#[test]
+fn test_core_maintenance() {
+    let id = NodeId::from;
+    let mut cores = CoreMaintenance::new();
+    // a triangle, with a tail.
+    assert!(cores.add_edge(id(0), id(1)));
+    assert!(cores.add_edge(id(1), id(2)));
+    assert_eq!(cores.get_coreness(id(0)), Some(1));
+    assert!(cores.add_edge(id(2), id(0)));
+    assert!(cores.add_edge(id(2), id(3)));
+    assert!(!cores.add_edge(id(3), id(2)));
+    assert!(!cores.add_edge(id(3), id(3)));
+    assert_eq!(cores.count_nodes(), 4);
+    assert_eq!(cores.count_edges(), 4);
+    let expected: Vec<usize> = vec![2, 2, 2, 1];
+    for (i, k) in expected.iter().enumerate() {
+        assert_eq!(cores.get_coreness(id(i as i64)), Some(*k));
+    }
+    assert_eq!(cores.get_coreness(id(4)), None);
+
+    // closing the tail into a 4-clique raises all of it.
+    cores.add_edge(id(3), id(0));
+    cores.add_edge(id(3), id(1));
+    assert!((0..4).all(|i| cores.get_coreness(id(i)) == Some(3)));
+
+    // and breaking one of its edges brings it back down.
+    assert!(cores.remove_edge(id(0), id(1)));
+    assert!(!cores.remove_edge(id(0), id(1)));
+    assert!((0..4).all(|i| cores.get_coreness(id(i)) == Some(2)));
+    assert!(cores.remove_edge(id(2), id(3)));
+    assert!(cores.remove_edge(id(3), id(0)));
+    assert!(cores.remove_edge(id(3), id(1)));
+    assert_eq!(cores.get_coreness(id(3)), Some(0));
+    assert_eq!(cores.count_edges(), 2);
+    assert_eq!(cores.get_coreness(id(1)), Some(1));
+}
+
+#[test]
+fn test_core_maintenance_matches_coreness() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let num_nodes: i64 = 40;
+    let mut edges: BTreeSet<(i64, i64)> = BTreeSet::new();
+    for i in 0..num_nodes {
+        for j in i + 1..num_nodes {
+            if rng.gen::<f64>() < 0.1 {
+                edges.insert((i, j));
+            }
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.iter().cloned().collect())?;
+    let mut cores = CoreMaintenance::from_graph(&graph);
+    for i in 0..num_nodes {
+        cores.add_node(NodeId::from(i));
+    }
+    assert_eq!(cores.count_edges(), edges.len());
+    assert_eq!(
+        cores.get_coreness_values(),
+        get_expected_coreness(&edges, num_nodes)?
+    );
+
+    // a stream of updates, biased towards insertions at first so that deep cores form, and
+    // towards deletions later so that they are taken apart.
+    for step in 0..2000 {
+        let p_insert = if step < 1000 { 0.7 } else { 0.3 };
+        if rng.gen::<f64>() < p_insert {
+            let i = rng.gen_range(0..num_nodes);
+            let j = rng.gen_range(0..num_nodes);
+            let edge = (i.min(j), i.max(j));
+            let added = cores.add_edge(NodeId::from(edge.0), NodeId::from(edge.1));
+            assert_eq!(added, i != j && edges.insert(edge));
+        } else if let Some(edge) = edges.iter().nth(rng.gen_range(0..edges.len().max(1))) {
+            let edge = *edge;
+            edges.remove(&edge);
+            assert!(cores.remove_edge(NodeId::from(edge.1), NodeId::from(edge.0)));
+        }
+        assert_eq!(
+            cores.get_coreness_values(),
+            get_expected_coreness(&edges, num_nodes)?
+        );
+    }
+    Ok(())
+}
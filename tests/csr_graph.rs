@@ -0,0 +1,88 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::pagerank::PageRank;
+use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
+use lib_dachshund::dachshund::algorithms::triangles::Triangles;
+use lib_dachshund::dachshund::csr_graph::CsrGraph;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::NodeBase;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::{gen_bridged_triangles_graph, gen_two_triangles_graph};
+
+#[test]
+fn test_csr_graph_layout() -> CLQResult<()> {
+    let graph = CsrGraph::from_graph(&gen_bridged_triangles_graph()?);
+    assert_eq!(graph.count_nodes(), 6);
+    assert_eq!(graph.count_edges(), 7);
+    assert_eq!(graph.ids, (0..6).map(NodeId::from).collect::<Vec<_>>());
+    let position = graph.get_position(NodeId::from(3)).unwrap();
+    let neighbors: Vec<NodeId> = vec![2, 4, 5].into_iter().map(NodeId::from).collect();
+    assert_eq!(graph.get_neighbors_at(position), neighbors.as_slice());
+    assert_eq!(
+        graph.get_node(NodeId::from(3)).get_neighbors(),
+        neighbors.as_slice()
+    );
+    assert_eq!(graph.get_node_degree(NodeId::from(0)), 2);
+    assert_eq!(graph.get_position(NodeId::from(10)), None);
+    assert!(CsrGraph::create_empty().get_ids_iter().next().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_csr_graph_from_directed_graph() -> CLQResult<()> {
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 1), (2, 3)])?;
+    let graph = CsrGraph::from_graph(&directed);
+    assert_eq!(graph.count_edges(), 2);
+    let node = graph.get_node(NodeId::from(2));
+    assert_eq!(node.degree(), 2);
+    assert!(node.has_neighbor(NodeId::from(1)) && node.has_neighbor(NodeId::from(3)));
+    assert_eq!(graph.get_node(NodeId::from(3)).degree(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_csr_graph_algorithms_match() -> CLQResult<()> {
+    // two components, to compare the algorithms across them.
+    let simple = gen_two_triangles_graph()?;
+    let graph = CsrGraph::from_graph(&simple);
+
+    let mut components = graph.get_connected_components();
+    components.iter_mut().for_each(|x| x.sort());
+    components.sort();
+    let mut simple_components = simple.get_connected_components();
+    simple_components.iter_mut().for_each(|x| x.sort());
+    simple_components.sort();
+    assert_eq!(components, simple_components);
+
+    assert_eq!(graph.get_coreness_values(), simple.get_coreness_values());
+    assert_eq!(graph.get_triangle_counts(), simple.get_triangle_counts());
+    assert!((graph.get_transitivity() - simple.get_transitivity()).abs() <= 1e-12);
+
+    // betweenness is only defined on connected graphs.
+    let connected = gen_bridged_triangles_graph()?;
+    let betweenness = CsrGraph::from_graph(&connected)
+        .get_node_betweenness_brandes()
+        .unwrap();
+    let simple_betweenness = connected.get_node_betweenness_brandes().unwrap();
+    for (id, value) in &simple_betweenness {
+        assert!((betweenness[id] - value).abs() <= 1e-9);
+    }
+
+    let pagerank = graph.get_pagerank(0.85, 1e-9, 100);
+    let simple_pagerank = simple.get_pagerank(0.85, 1e-9, 100);
+    for (id, value) in &simple_pagerank {
+        assert!((pagerank[id] - value).abs() <= 1e-9);
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::dot::Dot;
+use lib_dachshund::dachshund::csr_graph::CsrGraph;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::test_utils::gen_triangle_with_pendant_graph;
+use lib_dachshund::dachshund::transformer::{OutputFormat, Transformer};
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use std::collections::HashSet;
+
+#[test]
+fn test_graph_to_dot() -> CLQResult<()> {
+    let graph = gen_triangle_with_pendant_graph()?;
+    let clique: HashSet<NodeId> = vec![0, 1, 2].into_iter().map(NodeId::from).collect();
+    let expected = "graph \"triangle\" {\n  \
+                    node [shape=circle];\n  \
+                    0 [style=filled, fillcolor=lightblue];\n  \
+                    1 [style=filled, fillcolor=lightblue];\n  \
+                    2 [style=filled, fillcolor=lightblue];\n  \
+                    3;\n  \
+                    0 -- 1 [color=red, penwidth=2];\n  \
+                    0 -- 2 [color=red, penwidth=2];\n  \
+                    1 -- 2 [color=red, penwidth=2];\n  \
+                    2 -- 3;\n\
+                    }";
+    assert_eq!(graph.to_dot("triangle", &clique), expected);
+    assert_eq!(
+        CsrGraph::from_graph(&graph).to_dot("triangle", &clique),
+        expected
+    );
+
+    let dot = graph.to_dot("say \"hi\"", &HashSet::new());
+    assert!(dot.starts_with("graph \"say \\\"hi\\\"\" {\n"));
+    assert!(dot.contains("  0;\n") && dot.contains("  0 -- 1;\n"));
+    Ok(())
+}
+
+#[test]
+fn test_candidate_to_dot() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle",
+        "0\t1\t3\tauthor\tcited\tarticle",
+        "0\t2\t3\tauthor\tpublished\tarticle",
+        "0\t1\t4\tauthor\tpublished\tarticle",
+        "0\t2\t4\tauthor\tpublished\tarticle",
+        "0\t2\t5\tauthor\tpublished\tarticle",
+        "0\t1\tauthor\t\t\t",
+        "0\t3\tarticle\t\t\t",
+        "0\t4\tarticle\t\t\t",
+    ];
+    // with no epochs, the top candidate is the clique given as input.
+    let mut transformer = Transformer::new(
+        typespec,
+        20,
+        1.0,
+        Some(0.5),
+        Some(0.5),
+        20,
+        0,
+        3,
+        // no epochs are only allowed in debug mode.
+        true,
+        0,
+        "author".to_string(),
+        false,
+    )?
+    .with_output_format(OutputFormat::Dot);
+    let text = raw.join("\n");
+    let mut buffer: Vec<u8> = Vec::new();
+    transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let expected = "graph \"0\" {\n  \
+                    node [shape=circle];\n  \
+                    1 [style=filled, fillcolor=lightblue];\n  \
+                    2;\n  \
+                    3 [style=filled, fillcolor=orange];\n  \
+                    4 [style=filled, fillcolor=orange];\n  \
+                    5;\n  \
+                    1 -- 3 [color=red, penwidth=2];\n  \
+                    1 -- 4 [color=red, penwidth=2];\n  \
+                    2 -- 3;\n  \
+                    2 -- 4;\n  \
+                    2 -- 5;\n\
+                    }\n";
+    assert_eq!(String::from_utf8(buffer)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_parse_output_format() {
+    assert_eq!("dot".parse::<OutputFormat>().unwrap(), OutputFormat::Dot);
+    assert_eq!("long".parse::<OutputFormat>().unwrap(), OutputFormat::Long);
+    assert_eq!("wide".parse::<OutputFormat>().unwrap(), OutputFormat::Wide);
+    assert_eq!(
+        "stats".parse::<OutputFormat>().unwrap(),
+        OutputFormat::Stats
+    );
+    assert!("svg".parse::<OutputFormat>().is_err());
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::embedding_index::{
+    read_word2vec_embeddings, EmbeddingIndex,
+};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::NodeId;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+// num_clusters groups of cluster_size nodes, whose embeddings are small perturbations of
+// a random center. Nodes are numbered cluster by cluster.
+fn get_clustered_embeddings<R: Rng>(
+    num_clusters: usize,
+    cluster_size: usize,
+    dimension: usize,
+    rng: &mut R,
+) -> HashMap<NodeId, Vec<f32>> {
+    let mut embeddings: HashMap<NodeId, Vec<f32>> = HashMap::new();
+    for c in 0..num_clusters {
+        let center: Vec<f32> = (0..dimension).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        for i in 0..cluster_size {
+            let vector = center
+                .iter()
+                .map(|x| x + rng.gen_range(-0.05..0.05))
+                .collect();
+            embeddings.insert(NodeId::from((c * cluster_size + i) as i64), vector);
+        }
+    }
+    embeddings
+}
+
+#[test]
+fn test_suggest_similar_nodes() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let embeddings = get_clustered_embeddings(10, 20, 16, &mut rng);
+    let index = EmbeddingIndex::new_with_rng(embeddings, 4, 8, &mut rng)?;
+    assert_eq!(index.len(), 200);
+    for node in [0, 45, 199] {
+        let suggestions = index.suggest_similar_nodes(NodeId::from(node), 10)?;
+        assert_eq!(suggestions.len(), 10);
+        for (i, (id, similarity)) in suggestions.iter().enumerate() {
+            assert_ne!(id.value(), node);
+            assert_eq!(id.value() / 20, node / 20);
+            assert_eq!(
+                index.get_similarity(NodeId::from(node), *id),
+                Some(*similarity)
+            );
+            if i > 0 {
+                assert!(suggestions[i - 1].1 >= *similarity);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_suggest_similar_to_nodes() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let embeddings = get_clustered_embeddings(10, 20, 16, &mut rng);
+    let index = EmbeddingIndex::new_with_rng(embeddings, 4, 8, &mut rng)?;
+    let nodes: Vec<NodeId> = (20..30).map(NodeId::from).collect();
+    let suggestions = index.suggest_similar_to_nodes(&nodes, 100)?;
+    assert!(!suggestions.is_empty());
+    for (id, similarity) in &suggestions {
+        if (30..40).contains(&id.value()) {
+            assert!(*similarity > 0.9);
+        }
+        assert!(!nodes.contains(id));
+    }
+    // the rest of the cluster comes first.
+    let found = suggestions
+        .iter()
+        .take(10)
+        .filter(|(id, _)| (30..40).contains(&id.value()))
+        .count();
+    assert_eq!(found, 10);
+    assert!(index
+        .suggest_similar_to_nodes(&[NodeId::from(200)], 5)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_embedding_index_errors() {
+    let mut embeddings: HashMap<NodeId, Vec<f32>> = HashMap::new();
+    embeddings.insert(NodeId::from(0), vec![1.0, 0.0]);
+    embeddings.insert(NodeId::from(1), vec![0.0, 1.0]);
+    assert!(EmbeddingIndex::new(embeddings.clone(), 0, 8).is_err());
+    assert!(EmbeddingIndex::new(embeddings.clone(), 1, 65).is_err());
+    let index = EmbeddingIndex::new(embeddings.clone(), 1, 8).unwrap();
+    assert_eq!(
+        index.get_similarity(NodeId::from(0), NodeId::from(1)),
+        Some(0.0)
+    );
+    assert_eq!(index.get_similarity(NodeId::from(0), NodeId::from(2)), None);
+    assert!(index.suggest_similar_nodes(NodeId::from(2), 1).is_err());
+    embeddings.insert(NodeId::from(2), vec![0.0, 0.0]);
+    assert!(EmbeddingIndex::new(embeddings.clone(), 1, 8).is_err());
+    embeddings.insert(NodeId::from(2), vec![1.0, 0.0, 1.0]);
+    assert!(EmbeddingIndex::new(embeddings, 1, 8).is_err());
+}
+
+#[test]
+fn test_read_word2vec_embeddings() -> CLQResult<()> {
+    let text = "4 3\n</s> 0.1 0.1 0.1\n1 1.0 0.0 0.0\n2 0.9 0.1 0.0\n3 -1.0 0.0 0.5\n";
+    let embeddings = read_word2vec_embeddings(text.as_bytes())?;
+    assert_eq!(embeddings.len(), 3);
+    assert_eq!(embeddings[&NodeId::from(3)], vec![-1.0, 0.0, 0.5]);
+    let index = EmbeddingIndex::new_with_rng(embeddings, 8, 1, &mut StdRng::seed_from_u64(0))?;
+    let suggestions = index.suggest_similar_nodes(NodeId::from(1), 1)?;
+    assert_eq!(suggestions[0].0, NodeId::from(2));
+
+    assert!(read_word2vec_embeddings("2 3\n1 1.0 0.0\n".as_bytes()).is_err());
+    assert!(read_word2vec_embeddings("2 3\nx 1.0 0.0 0.0\n".as_bytes()).is_err());
+    assert!(read_word2vec_embeddings("".as_bytes()).is_err());
+    Ok(())
+}
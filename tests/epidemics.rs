@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::epidemics::{
+    EpidemicConfig, EpidemicCounts, EpidemicModel, Epidemics,
+};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::gen_planted_clique_undirected_graph;
+use rand::prelude::*;
+
+fn config(model: EpidemicModel, infection_rate: f64, recovery_rate: f64) -> EpidemicConfig {
+    EpidemicConfig {
+        model,
+        infection_rate,
+        recovery_rate,
+        max_steps: 100,
+    }
+}
+
+fn counts(susceptible: usize, infected: usize, recovered: usize) -> EpidemicCounts {
+    EpidemicCounts {
+        susceptible,
+        infected,
+        recovered,
+    }
+}
+
+#[test]
+fn test_sir_path() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a path 0 - 1 - 2 - 3 - 4.
+    let graph =
+        SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3), (3, 4)])?;
+
+    // infections always spread, and infected nodes recover after a step.
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIR, 1.0, 1.0), &[id(0)], 0)?;
+    assert_eq!(
+        history,
+        vec![
+            counts(4, 1, 0),
+            counts(3, 1, 1),
+            counts(2, 1, 2),
+            counts(1, 1, 3),
+            counts(0, 1, 4),
+            counts(0, 0, 5),
+        ]
+    );
+    // from the middle, both ways at once.
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIR, 1.0, 1.0), &[id(2)], 0)?;
+    assert_eq!(history.len(), 4);
+    assert_eq!(history[1], counts(2, 2, 1));
+
+    // without recoveries, everyone ends up infected, until steps run out.
+    let mut no_recoveries = config(EpidemicModel::SIR, 1.0, 0.0);
+    no_recoveries.max_steps = 6;
+    let history = graph.simulate_epidemic(&no_recoveries, &[id(0), id(0)], 0)?;
+    assert_eq!(history.len(), 7);
+    assert_eq!(history[0], counts(4, 1, 0));
+    assert_eq!(history[6], counts(0, 5, 0));
+
+    // without infections, seeds are all that ever get infected.
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIR, 0.0, 0.5), &[id(0)], 0)?;
+    assert_eq!(*history.last().unwrap(), counts(4, 0, 1));
+
+    assert!(graph
+        .simulate_epidemic(&config(EpidemicModel::SIR, 1.5, 0.5), &[id(0)], 0)
+        .is_err());
+    assert!(graph
+        .simulate_epidemic(&config(EpidemicModel::SIS, 0.5, -0.1), &[id(0)], 0)
+        .is_err());
+    assert!(graph
+        .simulate_epidemic(&config(EpidemicModel::SIR, 0.5, 0.5), &[id(5)], 0)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sis_path() -> CLQResult<()> {
+    let id = NodeId::from;
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2)])?;
+    // nodes recover into susceptible ones, so that infections bounce back and forth.
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIS, 1.0, 1.0), &[id(1)], 0)?;
+    assert_eq!(history.len(), 101);
+    for (step, step_counts) in history.iter().enumerate() {
+        assert_eq!(step_counts.recovered, 0);
+        assert_eq!(step_counts.infected, if step % 2 == 0 { 1 } else { 2 });
+    }
+    Ok(())
+}
+
+#[test]
+fn test_epidemics_directed() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a path 0 -> 1 -> 2, with 3 -> 2 on the side.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (3, 2)])?;
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIR, 1.0, 1.0), &[id(0)], 0)?;
+    assert_eq!(*history.last().unwrap(), counts(1, 0, 3));
+    let history = graph.simulate_epidemic(&config(EpidemicModel::SIR, 1.0, 1.0), &[id(2)], 0)?;
+    assert_eq!(*history.last().unwrap(), counts(3, 0, 1));
+    Ok(())
+}
+
+#[test]
+fn test_epidemics_seeded() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..20).collect();
+    let graph = gen_planted_clique_undirected_graph(200, 0.05, &clique, &mut rng)?;
+    let seeds = vec![NodeId::from(0)];
+    let sir = config(EpidemicModel::SIR, 0.3, 0.2);
+
+    // the same seed gives the same run.
+    let history = graph.simulate_epidemic(&sir, &seeds, 7)?;
+    assert_eq!(history, graph.simulate_epidemic(&sir, &seeds, 7)?);
+    for step_counts in &history {
+        assert_eq!(
+            step_counts.susceptible + step_counts.infected + step_counts.recovered,
+            graph.count_nodes()
+        );
+    }
+    // recoveries are for good.
+    assert!(history.windows(2).all(|w| w[0].recovered <= w[1].recovered));
+    assert!(history
+        .windows(2)
+        .all(|w| w[0].susceptible >= w[1].susceptible));
+
+    // an outbreak which isn't over before max_steps carries on in an SIS model.
+    let sis = config(EpidemicModel::SIS, 0.3, 0.2);
+    let history = graph.simulate_epidemic_with_rng(&sis, &seeds, &mut rng)?;
+    assert_eq!(history.len(), 101);
+    assert!(history.iter().all(|x| x.recovered == 0));
+    assert!(history[100].infected > graph.count_nodes() / 2);
+    Ok(())
+}
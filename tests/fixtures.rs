@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::DirectedNodeBase;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::{
+    assert_node_sets_have_labels, assert_nodes_have_labels, gen_golden_candidates,
+    gen_planted_clique_directed_graph, gen_planted_clique_typed_rows,
+    gen_planted_clique_undirected_graph, gen_planted_clique_weighted_graph, gen_test_transformer,
+    gen_test_typespec,
+};
+use rand::prelude::*;
+
+#[test]
+fn test_golden_candidates() -> CLQResult<()> {
+    for golden in gen_golden_candidates() {
+        golden.assert_found(&gen_test_transformer(
+            gen_test_typespec(),
+            "author".to_string(),
+        )?)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_planted_clique_typed_rows() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let golden = gen_planted_clique_typed_rows(8, vec![5, 5], 20, 0.2, &mut rng);
+    assert_eq!(golden.core_labels, (0..8).collect::<Vec<i64>>());
+    assert_eq!(golden.non_core_labels, (8..18).collect::<Vec<i64>>());
+    assert!(golden.rows.len() > 80);
+    golden.assert_found(&gen_test_transformer(
+        gen_test_typespec(),
+        "author".to_string(),
+    )?)
+}
+
+#[test]
+fn test_planted_clique_graphs() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = vec![3, 10, 20, 30, 47];
+    let graph = gen_planted_clique_undirected_graph(50, 0.05, &clique, &mut rng)?;
+    for a in &clique {
+        for b in &clique {
+            assert!(
+                a == b
+                    || graph.nodes[&NodeId::from(*a)]
+                        .neighbors
+                        .contains(&NodeId::from(*b))
+            );
+        }
+    }
+    let graph = gen_planted_clique_directed_graph(50, 0.05, &clique, &mut rng)?;
+    for a in &clique {
+        let node = graph.get_node(NodeId::from(*a));
+        for b in &clique {
+            assert!(a == b || node.has_out_neighbor(NodeId::from(*b)));
+        }
+    }
+    let graph = gen_planted_clique_weighted_graph(50, 0.05, &clique, 3.0, &mut rng)?;
+    let node = graph.get_node(NodeId::from(3));
+    let clique_weight: f64 = node
+        .edges
+        .iter()
+        .filter(|e| clique.contains(&e.target_id.value()))
+        .map(|e| e.weight)
+        .sum();
+    assert_eq!(clique_weight, 12.0);
+    Ok(())
+}
+
+#[test]
+fn test_assert_nodes_have_labels() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (3, 4)])?;
+    let components = graph.get_connected_components();
+    assert_node_sets_have_labels(&components, vec![vec![4, 3], vec![2, 0, 1]]);
+    assert_nodes_have_labels(&graph.get_ordered_node_ids(), vec![4, 3, 2, 1, 0]);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_assert_nodes_have_labels_fails() {
+    assert_nodes_have_labels(&[NodeId::from(1), NodeId::from(1)], vec![1]);
+}
@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::flow::{FlowNetwork, MaxFlow};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+#[test]
+fn test_flow_network() {
+    // the example network from CLRS, with a maximum flow of 23.
+    let arcs = vec![
+        (0, 1, 16.0),
+        (0, 2, 13.0),
+        (1, 2, 10.0),
+        (2, 1, 4.0),
+        (1, 3, 12.0),
+        (3, 2, 9.0),
+        (2, 4, 14.0),
+        (4, 3, 7.0),
+        (3, 5, 20.0),
+        (4, 5, 4.0),
+    ];
+    let mut network = FlowNetwork::new(6);
+    for (from, to, capacity) in arcs {
+        network.add_arc(from, to, capacity);
+    }
+    assert_eq!(network.get_max_flow(0, 5), 23.0);
+
+    // flows respect capacities, and are conserved everywhere but at the source and sink.
+    let mut net_flows = vec![0.0; 6];
+    for (from, to, capacity, flow) in network.get_arc_flows() {
+        assert!(flow >= 0.0 && flow <= capacity);
+        net_flows[from] -= flow;
+        net_flows[to] += flow;
+    }
+    assert_eq!(net_flows, vec![-23.0, 0.0, 0.0, 0.0, 0.0, 23.0]);
+
+    let source_side = network.get_source_side(0);
+    assert_eq!(source_side, vec![true, true, true, false, true, false]);
+}
+
+#[test]
+fn test_min_cut_weighted() -> CLQResult<()> {
+    // two heavy pairs joined by a light edge.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (1, 2, 1.0),
+        (2, 3, 5.0),
+        (0, 3, 0.5),
+    ])?;
+    assert_eq!(graph.get_max_flow(NodeId::from(0), NodeId::from(3))?, 1.5);
+    let (flow, cut) = graph.get_min_cut(NodeId::from(0), NodeId::from(2))?;
+    assert_eq!(flow, 1.5);
+    assert_eq!(
+        cut,
+        vec![
+            (NodeId::from(0), NodeId::from(3), 0.5),
+            (NodeId::from(1), NodeId::from(2), 1.0),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_max_flow_directed() -> CLQResult<()> {
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (0, 2)])?;
+    assert_eq!(graph.get_max_flow(NodeId::from(0), NodeId::from(2))?, 2.0);
+    // nothing flows against the edges.
+    let (flow, cut) = graph.get_min_cut(NodeId::from(2), NodeId::from(0))?;
+    assert_eq!(flow, 0.0);
+    assert!(cut.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_max_flow_undirected() -> CLQResult<()> {
+    // any two nodes of a complete graph on 5 nodes are 4-edge-connected.
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let (flow, cut) = graph.get_min_cut(NodeId::from(1), NodeId::from(4))?;
+    assert_eq!(flow, 4.0);
+    assert_eq!(cut.len(), 4);
+
+    assert!(graph
+        .get_max_flow(NodeId::from(1), NodeId::from(1))
+        .is_err());
+    assert!(graph
+        .get_max_flow(NodeId::from(1), NodeId::from(10))
+        .is_err());
+    Ok(())
+}
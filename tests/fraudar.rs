@@ -0,0 +1,112 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::fraudar::Fraudar;
+use lib_dachshund::dachshund::candidate::Candidate;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::row::CliqueRow;
+use lib_dachshund::dachshund::scorer::Scorer;
+use lib_dachshund::dachshund::test_utils::{
+    gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::transformer::Transformer;
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+
+// 4 authors who all published at journals 101 to 103, and also at the popular journal 110,
+// as did 10 other authors, each of whom also published at a journal of their own.
+fn build_graph(transformer: &Transformer) -> CLQResult<TypedGraph> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for core in 1..=4 {
+        for non_core in [101, 102, 103, 110] {
+            edges.push((core, non_core));
+        }
+    }
+    for core in 5..=14 {
+        edges.push((core, 110));
+        edges.push((core, core + 200));
+    }
+    let raw: Vec<String> = edges
+        .iter()
+        .map(|(core, non_core)| format!("0\t{core}\t{non_core}\tauthor\tpublished_at\tjournal"))
+        .collect();
+    let rows = process_raw_vector(transformer, raw)?;
+    transformer.build_pruned_graph(GraphId::from(0), rows)
+}
+
+fn get_ids(graph: &TypedGraph, labels: Vec<i64>) -> Vec<u32> {
+    let mut ids: Vec<u32> = labels
+        .into_iter()
+        .map(|label| graph.get_node_by_label(label.into()).node_id)
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn test_fraudar_block() -> CLQResult<()> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let graph = build_graph(&transformer)?;
+
+    let block = graph.get_fraudar_block(false);
+    assert_eq!(block.core_ids, get_ids(&graph, vec![1, 2, 3, 4]));
+    assert_eq!(
+        block.non_core_ids,
+        get_ids(&graph, vec![101, 102, 103, 110])
+    );
+    assert_eq!(block.score, 2.0);
+
+    // journal 110 weighs 1 / ln(19), the others 1 / ln(9).
+    let block = graph.get_fraudar_block(true);
+    assert_eq!(block.core_ids, get_ids(&graph, vec![1, 2, 3, 4]));
+    assert_eq!(
+        block.non_core_ids,
+        get_ids(&graph, vec![101, 102, 103, 110])
+    );
+    let expected_score = (12.0 / 9_f64.ln() + 4.0 / 19_f64.ln()) / 8.0;
+    assert!((block.score - expected_score).abs() < 1e-9);
+
+    let empty = TypedGraph {
+        nodes: Default::default(),
+        core_ids: Vec::new(),
+        non_core_ids: Vec::new(),
+        labels_map: Default::default(),
+        labels: Vec::new(),
+    };
+    let block = empty.get_fraudar_block(true);
+    assert!(block.core_ids.is_empty() && block.non_core_ids.is_empty());
+    assert_eq!(block.score, 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_fraudar_seed() -> CLQResult<()> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let graph = build_graph(&transformer)?;
+    let block = graph.get_fraudar_block(true);
+    let rows = block.to_clique_rows(&graph, GraphId::from(0));
+    assert_eq!(rows.len(), 8);
+
+    let scorer = Scorer::new(2, &transformer.search_problem);
+    let candidate = Candidate::from_clique_rows(&rows, &graph, &scorer)?.unwrap();
+    assert_eq!(
+        candidate.core_ids.iter().collect::<Vec<u32>>(),
+        block.core_ids
+    );
+    assert_eq!(
+        candidate.non_core_ids.iter().collect::<Vec<u32>>(),
+        block.non_core_ids
+    );
+    assert!(candidate.get_score()? > 0.0);
+
+    let transformer = transformer.with_dense_block_seed();
+    let no_rows: Vec<CliqueRow> = Vec::new();
+    let result = transformer.process_graph(&graph, &no_rows, GraphId::from(0), false)?;
+    assert!(result.top_candidate.get_score()? >= candidate.get_score()?);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::cnm_communities::{Community, CommunityId};
+use lib_dachshund::dachshund::algorithms::gexf::Gexf;
+use lib_dachshund::dachshund::csr_graph::CsrGraph;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::test_utils::gen_triangle_with_pendant_graph;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use std::collections::HashMap;
+
+#[test]
+fn test_to_gexf() -> CLQResult<()> {
+    let graph = gen_triangle_with_pendant_graph()?;
+    let mut communities: HashMap<CommunityId, Community> = HashMap::new();
+    communities.insert(5, vec![0, 1, 2].into_iter().map(NodeId::from).collect());
+    let node = |id: usize, degree: usize, coreness: usize, community: Option<usize>| {
+        let community = community.map_or(String::new(), |x| {
+            format!("          <attvalue for=\"2\" value=\"{}\"/>\n", x)
+        });
+        format!(
+            "      <node id=\"{0}\" label=\"{0}\">\n        <attvalues>\n          \
+             <attvalue for=\"0\" value=\"{1}\"/>\n          \
+             <attvalue for=\"1\" value=\"{2}\"/>\n{3}        </attvalues>\n      </node>\n",
+            id, degree, coreness, community
+        )
+    };
+    let expected = [
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n",
+        "  <graph defaultedgetype=\"undirected\">\n",
+        "    <attributes class=\"node\">\n",
+        "      <attribute id=\"0\" title=\"degree\" type=\"integer\"/>\n",
+        "      <attribute id=\"1\" title=\"coreness\" type=\"integer\"/>\n",
+        "      <attribute id=\"2\" title=\"community\" type=\"integer\"/>\n",
+        "    </attributes>\n",
+        "    <nodes>\n",
+        &node(0, 2, 2, Some(5)),
+        &node(1, 2, 2, Some(5)),
+        &node(2, 3, 2, Some(5)),
+        &node(3, 1, 1, None),
+        "    </nodes>\n",
+        "    <edges>\n",
+        "      <edge id=\"0\" source=\"0\" target=\"1\" weight=\"1\"/>\n",
+        "      <edge id=\"1\" source=\"0\" target=\"2\" weight=\"1\"/>\n",
+        "      <edge id=\"2\" source=\"1\" target=\"2\" weight=\"1\"/>\n",
+        "      <edge id=\"3\" source=\"2\" target=\"3\" weight=\"1\"/>\n",
+        "    </edges>\n",
+        "  </graph>\n",
+        "</gexf>\n",
+    ]
+    .concat();
+    assert_eq!(graph.to_gexf(&communities), expected);
+    assert_eq!(CsrGraph::from_graph(&graph).to_gexf(&communities), expected);
+    Ok(())
+}
+
+#[test]
+fn test_weighted_to_gexf() -> CLQResult<()> {
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(1, 2, 0.5), (2, 3, 2.0)])?;
+    let gexf = graph.to_gexf(&HashMap::new());
+    assert!(gexf.contains("<edge id=\"0\" source=\"1\" target=\"2\" weight=\"0.5\"/>"));
+    assert!(gexf.contains("<edge id=\"1\" source=\"2\" target=\"3\" weight=\"2\"/>"));
+    assert!(!gexf.contains("<attvalue for=\"2\""));
+    Ok(())
+}
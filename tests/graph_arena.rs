@@ -0,0 +1,65 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_arena::GraphArena;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::gen_planted_clique_undirected_graph;
+use rand::prelude::*;
+
+#[test]
+fn test_arena_coreness() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // one arena for graphs of decreasing and increasing size.
+    let mut arena = GraphArena::new();
+    for (n, p) in [(200, 0.05), (30, 0.2), (500, 0.01), (5, 0.0)] {
+        let graph = gen_planted_clique_undirected_graph(n, p, &[0, 1, 2, 3, 4], &mut rng)?;
+        arena.load(&graph);
+        assert_eq!(arena.len(), graph.nodes.len());
+        assert!(arena.get_coreness_values().is_empty());
+        arena.compute_coreness_values();
+        let expected = graph.get_coreness_values();
+        for (i, id) in arena.get_ids().iter().enumerate() {
+            assert_eq!(arena.get_coreness_values()[i], expected[id]);
+            assert_eq!(arena.get_coreness(*id), Some(expected[id]));
+        }
+    }
+    assert_eq!(arena.get_coreness(NodeId::from(5)), None);
+    arena.reset();
+    assert!(arena.is_empty());
+    arena.compute_coreness_values();
+    assert!(arena.get_coreness_values().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_arena_distances() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut arena = GraphArena::new();
+    for _ in 0..3 {
+        let graph = gen_planted_clique_undirected_graph(300, 0.005, &[0, 1, 2], &mut rng)?;
+        arena.load(&graph);
+        let source = NodeId::from(0);
+        arena.compute_distances(source)?;
+        let (expected, _) = graph.get_shortest_paths(source, &None);
+        for id in arena.get_ids() {
+            assert_eq!(arena.get_distance(*id).map(|d| d as usize), expected[id],);
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (3, 4)])?;
+    arena.load(&graph);
+    assert!(arena.compute_distances(NodeId::from(5)).is_err());
+    arena.compute_distances(NodeId::from(2))?;
+    assert_eq!(arena.get_distance(NodeId::from(0)), Some(2));
+    assert_eq!(arena.get_distance(NodeId::from(3)), None);
+    assert_eq!(arena.get_distances().len(), 5);
+    Ok(())
+}
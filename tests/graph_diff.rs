@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::graph_diff::GraphDiff;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+
+fn to_edges(pairs: Vec<(i64, i64)>) -> Vec<(NodeId, NodeId)> {
+    pairs
+        .into_iter()
+        .map(|(i, j)| (NodeId::from(i), NodeId::from(j)))
+        .collect()
+}
+
+#[test]
+fn test_undirected_diff() -> CLQResult<()> {
+    let before = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3)])?;
+    // the same edges, listed the other way around.
+    let same = SimpleUndirectedGraphBuilder {}.from_vector(vec![(3, 2), (1, 0), (2, 1)])?;
+    assert!(before.diff(&same).is_empty());
+
+    let after = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 0), (2, 1), (4, 1)])?;
+    let delta = before.diff(&after);
+    assert_eq!(delta.added_nodes, vec![NodeId::from(4)]);
+    assert_eq!(delta.removed_nodes, vec![NodeId::from(3)]);
+    assert_eq!(delta.added_edges, to_edges(vec![(1, 4)]));
+    assert_eq!(delta.removed_edges, to_edges(vec![(2, 3)]));
+
+    let reverse = after.diff(&before);
+    assert_eq!(reverse.added_nodes, delta.removed_nodes);
+    assert_eq!(reverse.removed_edges, delta.added_edges);
+    Ok(())
+}
+
+#[test]
+fn test_directed_diff() -> CLQResult<()> {
+    let before = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2)])?;
+    let after = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 0), (1, 2)])?;
+    let delta = before.diff(&after);
+    assert!(delta.added_nodes.is_empty() && delta.removed_nodes.is_empty());
+    assert_eq!(delta.added_edges, to_edges(vec![(1, 0)]));
+    assert_eq!(delta.removed_edges, to_edges(vec![(0, 1)]));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::graphlets::{Graphlets, NUM_GRAPHLETS, NUM_ORBITS};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::gen_triangle_with_pendant_graph;
+
+// graphlet of each orbit, and number of nodes of each graphlet.
+const ORBIT_GRAPHLETS: [usize; NUM_ORBITS] = [0, 1, 1, 2, 3, 3, 4, 4, 5, 6, 6, 6, 7, 7, 8];
+const GRAPHLET_SIZES: [usize; NUM_GRAPHLETS] = [2, 3, 3, 4, 4, 4, 4, 4, 4];
+
+// graphlet counts, as derived from per-node orbit counts.
+fn get_graphlet_counts_from_orbits(graph: &SimpleUndirectedGraph) -> [usize; NUM_GRAPHLETS] {
+    let mut counts = [0; NUM_GRAPHLETS];
+    for orbit_counts in graph.get_orbit_counts().values() {
+        for (orbit, count) in orbit_counts.iter().enumerate() {
+            counts[ORBIT_GRAPHLETS[orbit]] += count;
+        }
+    }
+    for (count, size) in counts.iter_mut().zip(GRAPHLET_SIZES.iter()) {
+        *count /= size;
+    }
+    counts
+}
+
+#[test]
+fn test_orbit_counts() -> CLQResult<()> {
+    // a triangle 0-1-2 with a pendant edge 2-3: a single paw.
+    let paw = gen_triangle_with_pendant_graph()?;
+    let counts = paw.get_orbit_counts();
+    assert_eq!(
+        counts[&NodeId::from(0)],
+        [2, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        counts[&NodeId::from(2)],
+        [3, 0, 2, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]
+    );
+    assert_eq!(
+        counts[&NodeId::from(3)],
+        [1, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(paw.get_graphlet_counts(), [4, 2, 1, 0, 0, 0, 1, 0, 0]);
+
+    let k4 = SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
+    for orbit_counts in k4.get_orbit_counts().values() {
+        assert_eq!(*orbit_counts, [3, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+    assert_eq!(k4.get_graphlet_counts(), [6, 0, 4, 0, 0, 0, 0, 0, 1]);
+
+    let c4 = SimpleUndirectedGraphBuilder {}.get_cycle_graph(4)?;
+    assert_eq!(c4.get_graphlet_counts(), [4, 4, 0, 0, 0, 1, 0, 0, 0]);
+    Ok(())
+}
+
+#[test]
+fn test_graphlet_counts() -> CLQResult<()> {
+    let mut builder = SimpleUndirectedGraphBuilder {};
+    let graphs = vec![
+        builder.get_path_graph(6)?,
+        builder.get_complete_graph(6)?,
+        builder.from_vector(vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (3, 4), (4, 5)])?,
+        builder.get_er_graph(30, 0.2)?,
+        builder.get_er_graph(20, 0.5)?,
+    ];
+    for graph in &graphs {
+        assert_eq!(
+            graph.get_graphlet_counts(),
+            get_graphlet_counts_from_orbits(graph)
+        );
+    }
+    Ok(())
+}
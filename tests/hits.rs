@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::hits::Hits;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+
+fn assert_close(expected: f64, actual: f64) {
+    assert!(
+        (expected - actual).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_hits_star() -> CLQResult<()> {
+    // node 0 points at every other node: it is the only hub, and they share authority.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (0, 3)])?;
+    let (hubs, authorities) = graph.get_hits(1e-10, 100);
+    assert_close(1.0, hubs[&NodeId::from(0)]);
+    assert_close(0.0, authorities[&NodeId::from(0)]);
+    for i in 1..4 {
+        assert_close(0.0, hubs[&NodeId::from(i)]);
+        assert_close(1.0 / 3.0, authorities[&NodeId::from(i)]);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_hits_bipartite() -> CLQResult<()> {
+    // 0 and 1 both point at 2, only 1 points at 3: 1 is the better hub, 2 the better authority.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 2), (1, 2), (1, 3)])?;
+    let (hubs, authorities) = graph.get_hits(1e-10, 1000);
+    assert_close(1.0, hubs.values().sum());
+    assert_close(1.0, authorities.values().sum());
+    assert!(hubs[&NodeId::from(1)] > hubs[&NodeId::from(0)]);
+    assert!(authorities[&NodeId::from(2)] > authorities[&NodeId::from(3)]);
+    assert_close(0.0, authorities[&NodeId::from(0)]);
+    assert_close(0.0, hubs[&NodeId::from(2)]);
+    Ok(())
+}
+
+#[test]
+fn test_hits_cycle() -> CLQResult<()> {
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0)])?;
+    let (hubs, authorities) = graph.get_hits(1e-10, 100);
+    for i in 0..3 {
+        assert_close(1.0 / 3.0, hubs[&NodeId::from(i)]);
+        assert_close(1.0 / 3.0, authorities[&NodeId::from(i)]);
+    }
+    Ok(())
+}
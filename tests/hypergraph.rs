@@ -0,0 +1,86 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use lib_dachshund::dachshund::hypergraph::Hypergraph;
+use lib_dachshund::dachshund::id_types::{HyperedgeId, NodeId};
+use lib_dachshund::dachshund::node::NodeBase;
+
+// papers (hyperedges) 100-102 and their authors: 100 by 1, 2 and 3, 101 by 3 and 4, and
+// 102 by 5 alone.
+fn get_hypergraph() -> Hypergraph {
+    Hypergraph::from_memberships(vec![
+        (100, 1),
+        (100, 2),
+        (100, 3),
+        (101, 3),
+        (101, 4),
+        (101, 4),
+        (102, 5),
+    ])
+}
+
+#[test]
+fn test_hypergraph() {
+    let id = NodeId::from;
+    let hypergraph = get_hypergraph();
+    assert_eq!(hypergraph.count_nodes(), 5);
+    assert_eq!(hypergraph.count_hyperedges(), 3);
+    assert_eq!(
+        hypergraph.get_hyperedge(HyperedgeId::from(101)),
+        Some(&[id(3), id(4)][..])
+    );
+    assert_eq!(hypergraph.get_hyperedge(HyperedgeId::from(103)), None);
+    assert_eq!(
+        hypergraph.get_node_hyperedges(id(3)),
+        Some(&[HyperedgeId::from(100), HyperedgeId::from(101)][..])
+    );
+    assert_eq!(hypergraph.get_hyperdegree(id(3)), 2);
+    assert_eq!(hypergraph.get_hyperdegree(id(6)), 0);
+
+    let stats = hypergraph.get_hyperdegree_stats();
+    assert_eq!(stats.mean_hyperdegree, 6.0 / 5.0);
+    assert_eq!(stats.max_hyperdegree, 2);
+    assert_eq!(stats.mean_hyperedge_size, 2.0);
+    assert_eq!(stats.max_hyperedge_size, 3);
+    assert_eq!(
+        stats.hyperdegree_histogram,
+        vec![(1, 4), (2, 1)].into_iter().collect()
+    );
+    assert_eq!(
+        stats.hyperedge_size_histogram,
+        vec![(1, 1), (2, 1), (3, 1)].into_iter().collect()
+    );
+    let empty = Hypergraph::from_memberships(vec![]).get_hyperdegree_stats();
+    assert_eq!(empty.mean_hyperdegree, 0.0);
+    assert_eq!(empty.max_hyperedge_size, 0);
+}
+
+#[test]
+fn test_hypergraph_expansions() {
+    let id = NodeId::from;
+    let hypergraph = get_hypergraph();
+
+    let clique = hypergraph.get_clique_expansion();
+    assert_eq!(clique.count_nodes(), 5);
+    // a triangle and an edge, sharing node 3.
+    assert_eq!(clique.count_edges(), 4);
+    assert_eq!(clique.get_node(id(3)).degree(), 3);
+    assert!(clique.get_node(id(2)).has_neighbor(id(1)));
+    assert!(!clique.get_node(id(2)).has_neighbor(id(4)));
+    assert_eq!(clique.get_node(id(5)).degree(), 0);
+
+    let (star, hyperedge_ids) = hypergraph.get_star_expansion();
+    assert_eq!(star.count_nodes(), 8);
+    assert_eq!(star.count_edges(), 6);
+    assert_eq!(star.get_core_ids(), &[id(1), id(2), id(3), id(4), id(5)]);
+    assert_eq!(star.get_non_core_ids(), &[id(6), id(7), id(8)]);
+    assert_eq!(hyperedge_ids[&id(7)], HyperedgeId::from(101));
+    let star_node = star.get_node(id(7));
+    assert_eq!(star_node.neighbors.to_vec(), vec![id(3), id(4)]);
+    assert_eq!(star.get_node(id(3)).degree(), 2);
+}
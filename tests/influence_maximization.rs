@@ -0,0 +1,89 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::influence_maximization::InfluenceMaximization;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::gen_planted_clique_undirected_graph;
+use rand::prelude::*;
+
+#[test]
+fn test_influence_maximization_stars() -> CLQResult<()> {
+    let id = NodeId::from;
+    // two stars, on 0 with 10 leaves and on 100 with 5.
+    let mut edges: Vec<(i64, i64)> = (1..=10).map(|x| (0, x)).collect();
+    edges.extend((101..=105).map(|x| (100, x)));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+
+    // cascades in which every edge activates reach whole stars.
+    let estimate = graph.get_influence_maximizing_seeds(2, 1.0, 10)?;
+    assert_eq!(estimate.seeds, vec![id(0), id(100)]);
+    assert_eq!(estimate.gains, vec![11.0, 6.0]);
+    assert_eq!(estimate.spread, 17.0);
+    // a third seed brings nothing, and goes to the smallest id left.
+    let estimate = graph.get_influence_maximizing_seeds(3, 1.0, 10)?;
+    assert_eq!(estimate.seeds[2], id(1));
+    assert_eq!(estimate.spread, 17.0);
+
+    // without any activations, seeds only reach themselves.
+    let estimate = graph.get_influence_maximizing_seeds(3, 0.0, 10)?;
+    assert_eq!(estimate.spread, 3.0);
+    // and there are no more seeds than nodes.
+    let estimate = graph.get_influence_maximizing_seeds(100, 0.5, 10)?;
+    assert_eq!(estimate.seeds.len(), 17);
+
+    assert!(graph.get_influence_maximizing_seeds(1, 1.5, 10).is_err());
+    let mut rng = StdRng::seed_from_u64(0);
+    assert!(graph
+        .estimate_influence_spread_with_rng(&[id(50)], 0.5, 10, &mut rng)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_influence_maximization_directed() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a path 0 -> 1 -> 2 -> 3, with 4 -> 3 on the side.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3), (4, 3)])?;
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(
+        graph.estimate_influence_spread_with_rng(&[id(0)], 1.0, 10, &mut rng)?,
+        4.0
+    );
+    assert_eq!(
+        graph.estimate_influence_spread_with_rng(&[id(3)], 1.0, 10, &mut rng)?,
+        1.0
+    );
+    let estimate = graph.get_influence_maximizing_seeds(2, 1.0, 10)?;
+    assert_eq!(estimate.seeds, vec![id(0), id(4)]);
+    assert_eq!(estimate.spread, 5.0);
+    Ok(())
+}
+
+#[test]
+fn test_influence_maximization_estimates() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..10).collect();
+    let graph = gen_planted_clique_undirected_graph(200, 0.02, &clique, &mut rng)?;
+    let estimate = graph.get_influence_maximizing_seeds_with_rng(5, 0.1, 500, &mut rng)?;
+    assert_eq!(estimate.seeds.len(), 5);
+    // gains shrink (up to sampling noise) as seeds are added, and add up to the spread.
+    assert!(estimate.gains.windows(2).all(|x| x[1] <= x[0] + 0.5));
+    assert!((estimate.gains.iter().sum::<f64>() - estimate.spread).abs() < 1e-9);
+    let spread = graph.estimate_influence_spread_with_rng(&estimate.seeds, 0.1, 2000, &mut rng)?;
+    assert!((spread - estimate.spread).abs() < 0.1 * spread);
+    // the greedy seeds do better than arbitrary ones.
+    let arbitrary: Vec<NodeId> = graph.get_ordered_node_ids()[100..105].to_vec();
+    let arbitrary_spread =
+        graph.estimate_influence_spread_with_rng(&arbitrary, 0.1, 2000, &mut rng)?;
+    assert!(spread > arbitrary_spread);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+// The interrupt flag is global to the process, so this lives in its own test binary.
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_transformer::SimpleTransformer;
+use lib_dachshund::dachshund::transformer_base::{request_interrupt, TransformerBase};
+
+#[test]
+fn test_interrupted_run() -> CLQResult<()> {
+    let mut transformer = SimpleTransformer::new();
+    request_interrupt();
+    let text = "0\t1\t2\n0\t2\t3\n1\t1\t2\n";
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    assert!(summary.interrupted);
+    assert_eq!(summary.num_graphs, 0);
+    assert_eq!(summary.num_rows, 0);
+    assert!(buffer.is_empty());
+    assert!(summary.to_string().contains("interrupted"));
+    Ok(())
+}
@@ -4,10 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-#![feature(test)]
-
 extern crate lib_dachshund;
-extern crate test;
 use lib_dachshund::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use lib_dachshund::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
 use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
@@ -23,6 +20,7 @@ use lib_dachshund::dachshund::algorithms::connectivity::{
 use lib_dachshund::dachshund::algorithms::coreness::Coreness;
 use lib_dachshund::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
 use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
+use lib_dachshund::dachshund::algorithms::leiden::Leiden;
 use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
 use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
 use lib_dachshund::dachshund::error::CLQResult;
@@ -37,7 +35,6 @@ use lib_dachshund::dachshund::simple_undirected_graph_builder::{
     SimpleUndirectedGraphBuilder, SimpleUndirectedGraphBuilderWithCliques,
 };
 use std::collections::{BTreeSet, HashMap, HashSet};
-use test::Bencher;
 
 fn get_karate_club_edges() -> Vec<(usize, usize)> {
     vec![
@@ -338,23 +335,33 @@ fn test_shortest_paths() -> CLQResult<()> {
     Ok(())
 }
 
-#[bench]
-fn bench_shortest_paths(b: &mut Bencher) -> CLQResult<()> {
-    b.iter(|| {
-        let graph = get_karate_club_graph().unwrap();
-        let source = NodeId::from(1_i64);
-        let (_dist, _parents) = graph.get_shortest_paths(source, &None);
-    });
-    Ok(())
-}
-
-#[bench]
-fn bench_shortest_paths_bfs(b: &mut Bencher) -> CLQResult<()> {
-    b.iter(|| {
-        let graph = get_karate_club_graph().unwrap();
-        let source = NodeId::from(1_i64);
-        let (_ordered_students, _dist, _preds) = graph.get_shortest_paths_bfs(source);
-    });
+#[test]
+fn test_distance() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    // bidirectional search agrees with single-source distances.
+    for source in [1_i64, 10, 30] {
+        let (dist, _parents) = graph.get_shortest_paths(NodeId::from(source), &None);
+        for target in 1..35_i64 {
+            assert_eq!(
+                graph.get_distance(NodeId::from(source), NodeId::from(target))?,
+                dist[&NodeId::from(target)]
+            );
+        }
+    }
+    let graph = get_two_karate_clubs()?;
+    // the other club can't be reached.
+    let (dist, _parents) = graph.get_shortest_paths(NodeId::from(1_i64), &None);
+    assert_eq!(dist[&NodeId::from(30_i64)], Some(3));
+    assert_eq!(dist[&NodeId::from(36_i64)], None);
+    let pairs = vec![
+        (NodeId::from(1_i64), NodeId::from(30_i64)),
+        (NodeId::from(36_i64), NodeId::from(65_i64)),
+        (NodeId::from(1_i64), NodeId::from(36_i64)),
+    ];
+    assert_eq!(graph.get_distances(&pairs)?, vec![Some(3), Some(3), None]);
+    assert!(graph
+        .get_distance(NodeId::from(1_i64), NodeId::from(35_i64))
+        .is_err());
     Ok(())
 }
 
@@ -403,21 +410,15 @@ fn test_betweenness_brandes() -> CLQResult<()> {
     Ok(())
 }
 
-#[bench]
-fn bench_betweenness(b: &mut Bencher) -> CLQResult<()> {
-    b.iter(|| {
-        let graph = get_karate_club_graph().unwrap();
-        let _bet = graph.get_node_betweenness();
-    });
-    Ok(())
-}
-
-#[bench]
-fn bench_betweenness_brandes(b: &mut Bencher) -> CLQResult<()> {
-    b.iter(|| {
-        let graph = get_karate_club_graph().unwrap();
-        let _bet = graph.get_node_betweenness_brandes();
-    });
+#[test]
+fn test_betweenness_brandes_weighted() -> CLQResult<()> {
+    // every edge has a length of 1 in an unweighted graph.
+    let graph = get_karate_club_graph()?;
+    let bet = graph.get_node_betweenness_brandes_weighted()?;
+    let unweighted_bet = graph.get_node_betweenness_brandes().unwrap();
+    for (id, value) in unweighted_bet {
+        assert!((bet[&id] - value).abs() <= 0.000001);
+    }
     Ok(())
 }
 
@@ -443,6 +444,50 @@ fn test_matrices() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_laplacian_eigenpairs() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let (laplacian, ids) = graph.get_laplacian_matrix();
+    let sparse_laplacian = graph.get_sparse_laplacian_matrix();
+    assert_eq!(sparse_laplacian.ids, ids);
+    for i in 0..ids.len() {
+        for j in 0..ids.len() {
+            assert_eq!(sparse_laplacian.get(i, j), laplacian[(i, j)]);
+        }
+    }
+
+    let mut expected: Vec<f64> = laplacian.clone().symmetric_eigen().eigenvalues.data.into();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (values, vectors) = graph.get_laplacian_eigenpairs(3);
+    assert_eq!(values.len(), 3);
+    for (i, value) in values.iter().enumerate() {
+        assert!((value - expected[i + 1]).abs() <= 1e-8);
+        let x = nalgebra::DVector::from_iterator(ids.len(), ids.iter().map(|id| vectors[i][id]));
+        assert!((x.norm() - 1.0).abs() <= 1e-8);
+        assert!((&laplacian * &x - *value * &x).norm() <= 1e-6);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sparse_algebraic_connectivity() -> CLQResult<()> {
+    // the Laplacian of a star has eigenvalues 0, 1 (n - 2 times) and n.
+    let edges: Vec<(i64, i64)> = (1..1500).map(|i| (0, i)).collect();
+    let star = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert!((star.get_algebraic_connectivity() - 1.0).abs() <= 1e-8);
+
+    // two karate clubs: the second eigenvalue 0 belongs to a nontrivial eigenvector.
+    let rows = get_two_karate_clubs_edges()
+        .into_iter()
+        .map(|(i, j)| (i as i64, j as i64))
+        .collect();
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+    let (values, _vectors) = graph.get_laplacian_eigenpairs(2);
+    assert!(values[0].abs() <= 1e-8);
+    assert!((values[1] - 0.469).abs() <= 0.001);
+    Ok(())
+}
+
 #[test]
 fn test_eigen() -> CLQResult<()> {
     let graph = get_karate_club_graph()?;
@@ -561,6 +606,23 @@ fn test_cnm_community() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_leiden_community() -> CLQResult<()> {
+    let g = get_karate_club_graph()?;
+    let (communities, modularity_changes) = g.get_leiden_communities();
+    let modularity = g.get_modularity(&communities);
+    println!(
+        "Leiden: {} communities, modularity {}",
+        communities.len(),
+        modularity
+    );
+    // the best known partition of the karate club has a modularity of ~0.4198.
+    assert!(modularity > 0.41);
+    assert!(modularity > g.get_modularity(&g.get_cnm_communities().0));
+    assert!(modularity_changes.iter().all(|x| *x >= 0.0));
+    Ok(())
+}
+
 #[test]
 fn test_brokerage() -> CLQResult<()> {
     let expected_counts = vec![
@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::layout::ForceLayout;
+use lib_dachshund::dachshund::algorithms::leiden::Leiden;
+use lib_dachshund::dachshund::algorithms::visualization::Visualization;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::test_utils::gen_bridged_triangles_graph;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+
+fn get_distance(layout: &HashMap<NodeId, (f64, f64)>, id1: i64, id2: i64) -> f64 {
+    let (x1, y1) = layout[&NodeId::from(id1)];
+    let (x2, y2) = layout[&NodeId::from(id2)];
+    ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
+}
+
+#[test]
+fn test_force_layout() -> CLQResult<()> {
+    let graph = gen_bridged_triangles_graph()?;
+    let mut rng = StdRng::seed_from_u64(0);
+    let layout = graph.get_layout_with_rng(200, &mut rng);
+    assert_eq!(layout.len(), 6);
+    for (x, y) in layout.values() {
+        assert!((0.0..=1.0).contains(x) && (0.0..=1.0).contains(y));
+    }
+    // nodes of the same triangle end up closer together than nodes of different ones.
+    let within = get_distance(&layout, 0, 1).max(get_distance(&layout, 4, 5));
+    let across = get_distance(&layout, 0, 4).min(get_distance(&layout, 1, 5));
+    assert!(within < across);
+
+    // the layout is determined by the random number generator.
+    let same_layout = graph.get_layout_with_rng(200, &mut StdRng::seed_from_u64(0));
+    assert_eq!(layout, same_layout);
+    Ok(())
+}
+
+#[test]
+fn test_visualization_json() -> CLQResult<()> {
+    let graph = gen_bridged_triangles_graph()?;
+    let (communities, _modularity_changes) = graph.get_leiden_communities();
+    let clique: HashSet<NodeId> = (0..3).map(NodeId::from).collect();
+    let json = graph.to_visualization_json_with_rng(
+        &communities,
+        &clique,
+        50,
+        &mut StdRng::seed_from_u64(0),
+    );
+    let nodes = json["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 6);
+    for (i, node) in nodes.iter().enumerate() {
+        assert_eq!(node["id"], i as i64);
+        assert!(node["x"].is_f64() && node["y"].is_f64());
+        assert_eq!(node["in_clique"], i < 3);
+        // each triangle is a community.
+        assert_eq!(
+            node["community"],
+            nodes[if i < 3 { 0 } else { 5 }]["community"]
+        );
+    }
+    assert_ne!(nodes[0]["community"], nodes[5]["community"]);
+    let edges = json["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 7);
+    assert_eq!(edges[0]["source"], 0);
+    assert_eq!(edges[0]["target"], 1);
+
+    // nodes outside any community are null.
+    let json = graph.to_visualization_json(&HashMap::new(), &HashSet::new());
+    assert!(json["nodes"][0]["community"].is_null());
+    Ok(())
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::cnm_communities::CNMCommunities;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::algorithms::leiden::Leiden;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::{gen_bridged_triangles_graph, gen_two_triangles_graph};
+use std::collections::{BTreeSet, HashSet};
+
+// three 4-cliques joined in a ring.
+fn get_ring_of_cliques() -> CLQResult<SimpleUndirectedGraph> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for c in 0..3 {
+        for i in 0..4 {
+            for j in i + 1..4 {
+                edges.push((4 * c + i, 4 * c + j));
+            }
+        }
+        edges.push((4 * c + 3, (4 * c + 4) % 12));
+    }
+    SimpleUndirectedGraphBuilder {}.from_vector(edges)
+}
+
+fn as_sorted_sets(communities: Vec<HashSet<NodeId>>) -> BTreeSet<BTreeSet<i64>> {
+    communities
+        .into_iter()
+        .map(|c| c.into_iter().map(|x| x.value()).collect())
+        .collect()
+}
+
+#[test]
+fn test_leiden_two_triangles() -> CLQResult<()> {
+    for g in [gen_two_triangles_graph()?, gen_bridged_triangles_graph()?] {
+        let (communities, _) = g.get_leiden_communities();
+        let expected: BTreeSet<BTreeSet<i64>> = vec![vec![0, 1, 2], vec![3, 4, 5]]
+            .into_iter()
+            .map(|c| c.into_iter().collect())
+            .collect();
+        assert_eq!(
+            as_sorted_sets(communities.into_values().collect()),
+            expected
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_leiden_ring_of_cliques() -> CLQResult<()> {
+    let g = get_ring_of_cliques()?;
+    let (communities, modularity_changes) = g.get_leiden_communities();
+    assert_eq!(communities.len(), 3);
+    // communities are numbered by their smallest node.
+    for (i, community) in communities.iter() {
+        assert_eq!(community.len(), 4);
+        assert!(community.contains(&NodeId::from(4 * *i as i64)));
+    }
+    let modularity = g.get_modularity(&communities);
+    let total: f64 = modularity_changes.iter().sum();
+    // starts from singletons, whose modularity is -sum(k_i^2) / (2m)^2 = -(6 * 9 + 6 * 16) / 42^2.
+    assert!((modularity - (total - 150.0 / (42.0 * 42.0))).abs() < 1e-9);
+    assert!(modularity >= g.get_modularity(&g.get_cnm_communities().0) - 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_leiden_resolution() -> CLQResult<()> {
+    let g = get_ring_of_cliques()?;
+    // a very low resolution merges everything.
+    let (communities, _) = g.get_leiden_communities_with_resolution(0.01);
+    assert_eq!(communities.len(), 1);
+    // a very high resolution keeps every node on its own.
+    let (communities, _) = g.get_leiden_communities_with_resolution(100.0);
+    assert_eq!(communities.len(), 12);
+    Ok(())
+}
+
+#[test]
+fn test_leiden_connected_communities() -> CLQResult<()> {
+    let g = SimpleUndirectedGraphBuilder {}.get_er_graph(200, 0.03)?;
+    let (communities, _) = g.get_leiden_communities();
+    assert_eq!(
+        communities.values().map(|c| c.len()).sum::<usize>(),
+        g.nodes.len()
+    );
+    for community in communities.values() {
+        let edges: Vec<(i64, i64)> = community
+            .iter()
+            .flat_map(|id| {
+                g.nodes[id]
+                    .neighbors
+                    .iter()
+                    .filter(|x| community.contains(x))
+                    .map(move |x| (id.value(), x.value()))
+            })
+            .collect();
+        if community.len() > 1 {
+            let subgraph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+            assert_eq!(subgraph.nodes.len(), community.len());
+            assert_eq!(subgraph.get_connected_components().len(), 1);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_leiden_dendrogram() -> CLQResult<()> {
+    let g = get_ring_of_cliques()?;
+    let dendrogram = g.get_leiden_dendrogram();
+    let (communities, _) = g.get_leiden_communities();
+    assert_eq!(dendrogram.leaves.len(), 12);
+    assert_eq!(dendrogram.merges.len(), 12 - communities.len());
+    let best = dendrogram.cut_at_modularity();
+    assert_eq!(
+        as_sorted_sets(best.into_values().collect()),
+        as_sorted_sets(communities.clone().into_values().collect())
+    );
+    let modularities = dendrogram.get_modularities();
+    assert!((modularities[0] + 150.0 / (42.0 * 42.0)).abs() < 1e-9);
+    assert!((modularities.last().unwrap() - g.get_modularity(&communities)).abs() < 1e-9);
+    for k in 3..=12 {
+        let cut = dendrogram.cut_at(k);
+        assert_eq!(cut.len(), k);
+        assert_eq!(cut.values().map(|c| c.len()).sum::<usize>(), 12);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_leiden_dendrogram_modularity() -> CLQResult<()> {
+    let g = SimpleUndirectedGraphBuilder {}.get_er_graph(200, 0.03)?;
+    let dendrogram = g.get_leiden_dendrogram();
+    let modularities = dendrogram.get_modularities();
+    // modularity changes along the dendrogram are exact.
+    for num_merges in [0, 50, 100, dendrogram.merges.len()] {
+        let communities = dendrogram.cut_after(num_merges);
+        assert!((modularities[num_merges] - g.get_modularity(&communities)).abs() < 1e-9);
+    }
+    Ok(())
+}
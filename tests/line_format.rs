@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_format::{DelimitedLineProcessor, LineFormat};
+use lib_dachshund::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_transformer::SimpleTransformer;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use std::sync::Arc;
+
+fn run_simple_transformer(text: &str, line_format: LineFormat) -> CLQResult<String> {
+    let mut buffer: Vec<u8> = Vec::new();
+    SimpleTransformer::new().run(
+        Input::string(text.as_bytes()).with_line_format(line_format),
+        Output::string(&mut buffer),
+    )?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[test]
+fn test_parse_delimiter() -> CLQResult<()> {
+    assert_eq!(LineFormat::parse_delimiter("tab")?, '\t');
+    assert_eq!(LineFormat::parse_delimiter("comma")?, ',');
+    assert_eq!(LineFormat::parse_delimiter("space")?, ' ');
+    assert_eq!(LineFormat::parse_delimiter("|")?, '|');
+    assert!(LineFormat::parse_delimiter("||").is_err());
+    assert!(LineFormat::parse_delimiter("\"").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_split() -> CLQResult<()> {
+    let format = LineFormat::new(',');
+    assert_eq!(format.split("0,1,\"a,b\"")?, vec!["0", "1", "\"a", "b\""]);
+    let format = format.with_quoted_fields();
+    assert_eq!(format.split("0,1,\"a,b\"")?, vec!["0", "1", "a,b"]);
+    assert_eq!(
+        format.split("\"say \"\"hi\"\"\",,x")?,
+        vec!["say \"hi\"", "", "x"]
+    );
+    // quotes only delimit fields they start.
+    assert_eq!(format.split("a\"b,c")?, vec!["a\"b", "c"]);
+    assert!(format.split("0,\"1").is_err());
+    assert!(format.split("0,\"1\"2").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_header_columns() -> CLQResult<()> {
+    let format = LineFormat::new(',').with_header();
+    assert_eq!(
+        format.get_columns(
+            "weight, target_id,graph_id,source_id",
+            &["graph_id", "source_id"]
+        )?,
+        vec![2, 3]
+    );
+    let error = format
+        .get_columns("graph_id,src,dst", &["graph_id", "source_id"])
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Header has no column named source_id (columns: graph_id, src, dst)"
+    );
+
+    let line_processor = DelimitedLineProcessor::new(format, Arc::new(LineProcessor::new()))
+        .with_header("ts,target_id,source_id,graph_id")?;
+    assert_eq!(line_processor.to_tab_separated("9,2,1,0")?, "0\t1\t2");
+    assert_eq!(line_processor.get_graph_key("9,2,1,7"), "7");
+    assert!(line_processor.to_tab_separated("9,2,1").is_err());
+    assert!(line_processor.process_line("9,2,1,0".to_owned()).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_delimited_input() -> CLQResult<()> {
+    let expected = run_simple_transformer(
+        "0\t1\t2\n0\t2\t3\n0\t3\t1\n1\t4\t5\n",
+        LineFormat::default(),
+    )?;
+    assert_eq!(
+        run_simple_transformer("0,1,2\n0,2,3\n0,3,1\n1,4,5\n", LineFormat::new(','))?,
+        expected
+    );
+    assert_eq!(
+        run_simple_transformer("0 1 2\n0 2 3\n0 3 1\n1 4 5\n", LineFormat::new(' '))?,
+        expected
+    );
+    let text = "target_id,\"graph_id\",comment,source_id\n\
+                2,0,\"first, of three\",1\n\
+                3,0,,2\n\
+                1,0,,3\n\
+                5,1,\"\"\"last\"\"\",4\n";
+    let format = LineFormat::new(',').with_quoted_fields().with_header();
+    assert_eq!(run_simple_transformer(text, format)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_delimited_input_errors() {
+    let format = LineFormat::new(',').with_header();
+    let error =
+        run_simple_transformer("graph_id,source_id,target_id\n0,1,2\n0,2,x\n", format).unwrap_err();
+    // lines are numbered as in input, header included.
+    match &error {
+        CLQError::Line { line_num, .. } => assert_eq!(*line_num, 3),
+        _ => panic!("unexpected error: {}", error),
+    }
+
+    let format = LineFormat::new(',').with_header();
+    assert!(run_simple_transformer("", format.clone()).is_err());
+    assert!(run_simple_transformer("graph_id,source\n0,1\n", format.clone()).is_err());
+
+    let report = SimpleTransformer::new().dry_run(
+        Input::string(b"graph_id,source_id,target_id\n0,1,2\n0,1\n").with_line_format(format),
+        10,
+    );
+    assert_eq!(report.num_rows, 1);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].0, 3);
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::link_prediction::{LinkPrediction, LinkPredictionScore};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::test_utils::gen_square_with_pendant_graph;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+fn get_pairs(pairs: Vec<(i64, i64)>) -> Vec<(NodeId, NodeId)> {
+    pairs
+        .into_iter()
+        .map(|(x, y)| (NodeId::from(x), NodeId::from(y)))
+        .collect()
+}
+
+#[test]
+fn test_link_prediction_scores() -> CLQResult<()> {
+    let graph = gen_square_with_pendant_graph()?;
+    let pairs = get_pairs(vec![(0, 3), (1, 2), (0, 4)]);
+    assert_eq!(
+        graph.get_link_prediction_scores(LinkPredictionScore::CommonNeighbors, &pairs)?,
+        vec![2.0, 2.0, 0.0]
+    );
+    assert_eq!(
+        graph.get_link_prediction_scores(LinkPredictionScore::Jaccard, &pairs)?,
+        vec![2.0 / 3.0, 1.0, 0.0]
+    );
+    assert_eq!(
+        graph.get_link_prediction_scores(LinkPredictionScore::AdamicAdar, &pairs)?,
+        vec![2.0 / 2f64.ln(), 1.0 / 2f64.ln() + 1.0 / 3f64.ln(), 0.0]
+    );
+    assert_eq!(
+        graph.get_link_prediction_scores(LinkPredictionScore::PreferentialAttachment, &pairs)?,
+        vec![6.0, 4.0, 2.0]
+    );
+    assert!(graph
+        .get_link_prediction_scores(LinkPredictionScore::Jaccard, &get_pairs(vec![(0, 5)]))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_predicted_links() -> CLQResult<()> {
+    let graph = gen_square_with_pendant_graph()?;
+    let get_links = |score, min_score| -> Vec<(i64, i64)> {
+        graph
+            .get_predicted_links(score, min_score)
+            .into_iter()
+            .map(|(x, y, _score)| (x.value(), y.value()))
+            .collect()
+    };
+    assert_eq!(
+        get_links(LinkPredictionScore::CommonNeighbors, 2.0),
+        vec![(0, 3), (1, 2)]
+    );
+    // every non-edge scores at least 0.
+    assert_eq!(
+        get_links(LinkPredictionScore::CommonNeighbors, 0.0),
+        vec![(0, 3), (0, 4), (1, 2), (1, 4), (2, 4)]
+    );
+    assert_eq!(
+        get_links(LinkPredictionScore::Jaccard, 0.5),
+        vec![(0, 3), (1, 2), (1, 4), (2, 4)]
+    );
+    assert_eq!(
+        graph.get_predicted_links(LinkPredictionScore::PreferentialAttachment, 4.0),
+        vec![
+            (NodeId::from(0), NodeId::from(3), 6.0),
+            (NodeId::from(1), NodeId::from(2), 4.0)
+        ]
+    );
+
+    // weights are ignored.
+    let weighted = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 0.5),
+        (1, 2, 2.0),
+        (2, 3, 1.0),
+    ])?;
+    assert_eq!(
+        weighted.get_predicted_links(LinkPredictionScore::CommonNeighbors, 1.0),
+        vec![
+            (NodeId::from(0), NodeId::from(2), 1.0),
+            (NodeId::from(1), NodeId::from(3), 1.0)
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::max_clique::MaxClique;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use rand::prelude::*;
+
+fn is_clique(graph: &SimpleUndirectedGraph, nodes: &[NodeId]) -> bool {
+    nodes.iter().enumerate().all(|(i, a)| {
+        nodes[i + 1..]
+            .iter()
+            .all(|b| graph.get_node(*a).neighbors.contains(b))
+    })
+}
+
+fn get_random_edges<R: Rng>(n: i64, p: f64, rng: &mut R) -> Vec<(i64, i64)> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges
+}
+
+#[test]
+fn test_approximate_max_clique_complete_graph() -> CLQResult<()> {
+    // a 6-clique with a pendant path: the degeneracy bound is met.
+    let mut edges: Vec<(i64, i64)> = vec![(5, 6), (6, 7)];
+    for i in 0..6 {
+        for j in (i + 1)..6 {
+            edges.push((i, j));
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let estimate = graph.get_approximate_max_clique_with_rng(10, &mut StdRng::seed_from_u64(0));
+    assert_eq!(
+        estimate.clique,
+        (0..6).map(NodeId::from).collect::<Vec<_>>()
+    );
+    assert_eq!(estimate.lower_bound, 6);
+    assert_eq!(estimate.upper_bound, 6);
+
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![])?;
+    let estimate = graph.get_approximate_max_clique(10);
+    assert!(estimate.clique.is_empty());
+    assert_eq!(estimate.upper_bound, 0);
+    Ok(())
+}
+
+#[test]
+fn test_approximate_max_clique_planted() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut edges = get_random_edges(300, 0.05, &mut rng);
+    let planted: Vec<i64> = (0..300).step_by(25).collect();
+    for (i, a) in planted.iter().enumerate() {
+        for b in &planted[i + 1..] {
+            edges.push((*a, *b));
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let estimate = graph.get_approximate_max_clique_with_rng(100, &mut rng);
+    assert!(is_clique(&graph, &estimate.clique));
+    assert_eq!(estimate.lower_bound, estimate.clique.len());
+    assert!(estimate.lower_bound >= planted.len());
+    assert!(estimate.upper_bound >= estimate.lower_bound);
+    Ok(())
+}
+
+#[test]
+fn test_approximate_max_clique_small_graphs() -> CLQResult<()> {
+    // compared with the maximum clique found by exhaustive search.
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..20 {
+        let n: usize = 12;
+        let graph = SimpleUndirectedGraphBuilder {}
+            .from_vector(get_random_edges(n as i64, 0.5, &mut rng))?;
+        let ids: Vec<NodeId> = graph.get_ordered_node_ids();
+        let max_size = (0..1_u32 << ids.len())
+            .map(|mask| {
+                (0..ids.len())
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| ids[i])
+                    .collect::<Vec<NodeId>>()
+            })
+            .filter(|nodes| is_clique(&graph, nodes))
+            .map(|nodes| nodes.len())
+            .max()
+            .unwrap();
+        let estimate = graph.get_approximate_max_clique_with_rng(100, &mut rng);
+        assert!(is_clique(&graph, &estimate.clique));
+        assert_eq!(estimate.lower_bound, max_size);
+        assert!(estimate.upper_bound >= max_size);
+    }
+    Ok(())
+}
@@ -0,0 +1,286 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::core_transformer::CoreTransformer;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_processor::{
+    AttributedLineProcessor, FieldValue, LineProcessor, LineProcessorBase, WeightedLineProcessor,
+};
+use lib_dachshund::dachshund::node_labels::{GraphNodeLabels, LabeledGraphBuilder, NodeLabels};
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::row::{AttributedRow, Row, SimpleEdgeRow};
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::transformer::{OutputFormat, Transformer};
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_node_labels() {
+    let labels = NodeLabels::new();
+    assert!(labels.is_empty());
+    assert_eq!(labels.intern("alice"), NodeId::from(0));
+    assert_eq!(labels.intern("bob"), NodeId::from(1));
+    assert_eq!(labels.intern("alice"), NodeId::from(0));
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels.get_id("bob"), Some(NodeId::from(1)));
+    assert_eq!(labels.get_id("carol"), None);
+    assert_eq!(labels.get_label(NodeId::from(1)), Some("bob".to_owned()));
+    assert_eq!(labels.get_label(NodeId::from(2)), None);
+    assert_eq!(labels.get_label(NodeId::from(-1)), None);
+    let reverse_labels = labels.get_reverse_labels_map();
+    assert_eq!(reverse_labels.len(), 2);
+    assert_eq!(reverse_labels[&NodeId::from(0)], "alice");
+}
+
+#[test]
+fn test_node_labels_across_threads() {
+    let labels = Arc::new(NodeLabels::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let labels = labels.clone();
+            thread::spawn(move || {
+                (0..100)
+                    .map(|i| labels.intern(&format!("user{}", i)))
+                    .collect::<Vec<NodeId>>()
+            })
+        })
+        .collect();
+    let ids: Vec<Vec<NodeId>> = handles.into_iter().map(|x| x.join().unwrap()).collect();
+    assert!(ids.iter().all(|x| *x == ids[0]));
+    assert_eq!(labels.len(), 100);
+    for (i, id) in ids[0].iter().enumerate() {
+        assert_eq!(labels.get_label(*id), Some(format!("user{}", i)));
+    }
+}
+
+#[test]
+fn test_labeled_graph_builders() -> CLQResult<()> {
+    let labels = NodeLabels::new();
+    let edges = vec![("alice", "bob"), ("bob", "carol"), ("carol", "alice")];
+    let graph = SimpleUndirectedGraphBuilder {}.from_labeled_vector(&edges, &labels)?;
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+    let id = labels.get_id("carol").unwrap();
+    assert_eq!(graph.get_node_degree(id), 2);
+
+    // labels keep their ids across graphs built with the same table.
+    let edges = vec![("carol".to_owned(), "dave".to_owned())];
+    let graph = SimpleDirectedGraphBuilder {}.from_labeled_vector(&edges, &labels)?;
+    assert!(graph.has_node(id));
+    assert_eq!(labels.get_id("dave"), Some(NodeId::from(3)));
+    Ok(())
+}
+
+#[test]
+fn test_line_processors_with_string_labels() -> CLQResult<()> {
+    let line_processor = LineProcessor::new().with_string_labels();
+    let row = line_processor.process_line("g\talice\tbob".to_owned())?;
+    let row: SimpleEdgeRow = row.as_simple_edge_row().unwrap();
+    assert_eq!(row.as_tuple(), (0, 1));
+    let row = line_processor.process_line("g\t42\talice".to_owned())?;
+    assert_eq!(row.as_simple_edge_row().unwrap().as_tuple(), (2, 0));
+    // graphs have tables of their own.
+    let row = line_processor.process_line("h\tbob\t42".to_owned())?;
+    assert_eq!(row.as_simple_edge_row().unwrap().as_tuple(), (0, 1));
+    let labels = line_processor.get_graph_labels(GraphId::from(0)).unwrap();
+    assert_eq!(labels.get_label(NodeId::from(2)), Some("42".to_owned()));
+    assert_eq!(line_processor.get_node_labels().unwrap().len(), 2);
+    line_processor.release_graph(GraphId::from(0));
+    assert_eq!(line_processor.get_node_labels().unwrap().len(), 1);
+    // labels handed out stay valid once released.
+    assert_eq!(labels.len(), 3);
+
+    // missing node ids are errors rather than empty labels.
+    assert!(line_processor.process_line("g\t\tbob".to_owned()).is_err());
+    let values = [
+        FieldValue::Str("g"),
+        FieldValue::Null,
+        FieldValue::Str("bob"),
+    ];
+    assert!(line_processor.process_values(&values).is_err());
+    let values = [
+        FieldValue::Str("g"),
+        FieldValue::Int(7),
+        FieldValue::Str("bob"),
+    ];
+    assert!(line_processor.process_values(&values).is_ok());
+
+    let line_processor = WeightedLineProcessor::new().with_string_labels();
+    let row = line_processor.process_line("g\talice\tbob\t0.5".to_owned())?;
+    assert_eq!(row.as_weighted_edge_row().unwrap().as_tuple(), (0, 1, 0.5));
+
+    let line_processor = AttributedLineProcessor::new().with_string_labels();
+    let row = line_processor.process_line("g\tbob\t{\"age\": 25}".to_owned())?;
+    let row = row.downcast_ref::<AttributedRow>().unwrap();
+    assert_eq!((row.source_id, row.target_id), (NodeId::from(0), None));
+
+    // without string labels, node ids must still be integers.
+    assert!(LineProcessor::new().get_node_labels().is_none());
+    assert!(LineProcessor::new()
+        .process_line("g\talice\tbob".to_owned())
+        .is_err());
+    Ok(())
+}
+
+// runs the typed clique search on edges and clique rows between string labels, with no
+// epochs, so that the top candidate is the clique given as input.
+fn run_labeled_transformer(output_format: OutputFormat) -> CLQResult<String> {
+    let typespec: Vec<Vec<String>> = vec![vec![
+        "author".to_string(),
+        "published".into(),
+        "article".into(),
+    ]];
+    let raw = [
+        "0\tann\tp1\tauthor\tpublished\tarticle",
+        "0\tann\tp2\tauthor\tpublished\tarticle",
+        "0\tbob\tp1\tauthor\tpublished\tarticle",
+        "0\tann\tauthor\t\t\t",
+        "0\tp1\tarticle\t\t\t",
+        "0\tp2\tarticle\t\t\t",
+    ];
+    let mut transformer = Transformer::new(
+        typespec,
+        20,
+        1.0,
+        Some(0.5),
+        Some(0.5),
+        20,
+        0,
+        3,
+        // no epochs are only allowed in debug mode.
+        true,
+        0,
+        "author".to_string(),
+        false,
+    )?
+    .with_string_labels()
+    .with_output_format(output_format);
+    let text = raw.join("\n");
+    let mut buffer: Vec<u8> = Vec::new();
+    transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[test]
+fn test_transformer_with_string_labels() -> CLQResult<()> {
+    let wide = run_labeled_transformer(OutputFormat::Wide)?;
+    assert!(wide.starts_with("0\t1\t2\t[\"ann\"]\t[\"p1\",\"p2\"]\t"));
+    assert_eq!(
+        run_labeled_transformer(OutputFormat::Long)?,
+        "0\tann\tauthor\n0\tp1\tarticle\n0\tp2\tarticle\n"
+    );
+    let dot = run_labeled_transformer(OutputFormat::Dot)?;
+    assert!(dot.contains("  \"ann\" [style=filled, fillcolor=lightblue];\n"));
+    assert!(dot.contains("  \"bob\";\n"));
+    assert!(dot.contains("  \"ann\" -- \"p2\" [color=red, penwidth=2];\n"));
+    assert!(dot.contains("  \"bob\" -- \"p1\";\n"));
+    Ok(())
+}
+
+#[test]
+fn test_graph_node_labels() {
+    let graph_labels = GraphNodeLabels::new();
+    assert!(graph_labels.is_empty());
+    let labels = graph_labels.get_or_insert(GraphId::from(0));
+    assert_eq!(labels.intern("alice"), NodeId::from(0));
+    assert!(Arc::ptr_eq(
+        &labels,
+        &graph_labels.get_or_insert(GraphId::from(0))
+    ));
+    assert_eq!(
+        graph_labels.get_or_insert(GraphId::from(1)).intern("bob"),
+        NodeId::from(0)
+    );
+    assert_eq!(graph_labels.len(), 2);
+    graph_labels.release(GraphId::from(0));
+    assert!(graph_labels.get(GraphId::from(0)).is_none());
+    assert_eq!(labels.get_label(NodeId::from(0)), Some("alice".to_owned()));
+    assert_eq!(graph_labels.len(), 1);
+}
+
+// records the number of labels of each graph, with a line processor shared with the test.
+struct LabelCountTransformer {
+    line_processor: Arc<LineProcessor>,
+    num_labels: Vec<usize>,
+}
+impl TransformerBase for LabelCountTransformer {
+    fn get_line_processor(&self) -> Arc<dyn LineProcessorBase> {
+        self.line_processor.clone()
+    }
+    fn process_row(&mut self, _row: Box<dyn Row>) -> CLQResult<()> {
+        Ok(())
+    }
+    fn process_batch(
+        &mut self,
+        graph_id: GraphId,
+        output: &Sender<(Option<String>, bool)>,
+    ) -> CLQResult<()> {
+        let labels = self.line_processor.get_graph_labels(graph_id).unwrap();
+        self.num_labels.push(labels.len());
+        output.send((None, false)).unwrap();
+        Ok(())
+    }
+    fn reset(&mut self) -> CLQResult<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_labels_released_after_each_graph() -> CLQResult<()> {
+    let line_processor = Arc::new(LineProcessor::new().with_string_labels());
+    let mut transformer = LabelCountTransformer {
+        line_processor: line_processor.clone(),
+        num_labels: Vec::new(),
+    };
+    let text = "a\tx\ty\na\ty\tz\nb\tx\tw\n";
+    let mut buffer: Vec<u8> = Vec::new();
+    transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    assert_eq!(transformer.num_labels, vec![3, 2]);
+    assert!(line_processor.get_node_labels().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_simple_transformers_with_string_labels() -> CLQResult<()> {
+    let text = "g\talice\tbob\ng\tbob\tcarol\ng\tcarol\talice\nh\tdave\terin\n";
+    let mut buffer: Vec<u8> = Vec::new();
+    CoreTransformer::new()
+        .with_string_labels()
+        .run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let mut lines: Vec<String> = String::from_utf8(buffer)?
+        .lines()
+        .map(|x| x.split('\t').take(3).collect::<Vec<&str>>().join("\t"))
+        .collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        vec![
+            "g\talice\t2",
+            "g\tbob\t2",
+            "g\tcarol\t2",
+            "h\tdave\t1",
+            "h\terin\t1"
+        ]
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    ConnectedComponentsTransformer::new()
+        .with_string_labels()
+        .run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    assert_eq!(
+        String::from_utf8(buffer)?,
+        "g\t0\talice\ng\t0\tbob\ng\t0\tcarol\nh\t0\tdave\nh\t0\terin\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,247 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::local_community::LocalCommunity;
+use lib_dachshund::dachshund::algorithms::pagerank::PageRank;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::{NodeBase, NodeEdgeBase};
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use std::collections::HashMap;
+
+fn assert_close(expected: f64, actual: f64) {
+    assert!(
+        (expected - actual).abs() < 1e-4,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_pagerank_complete_graph() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let pr = graph.get_pagerank(0.85, 1e-8, 100);
+    assert_eq!(pr.len(), 5);
+    for score in pr.values() {
+        assert_close(0.2, *score);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_pagerank_star_graph() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (0, 3)])?;
+    let pr = graph.get_pagerank(0.85, 1e-10, 1000);
+    assert_close(1.0, pr.values().sum());
+    // hub: x = 0.15 / 4 + 0.85 * 3 * y, leaves: y = 0.15 / 4 + 0.85 * x / 3
+    let hub = (0.15 / 4.0) * (1.0 + 3.0 * 0.85) / (1.0 - 0.85 * 0.85);
+    assert_close(hub, pr[&NodeId::from(0)]);
+    assert_close((1.0 - hub) / 3.0, pr[&NodeId::from(1)]);
+    Ok(())
+}
+
+#[test]
+fn test_pagerank_weighted() -> CLQResult<()> {
+    // node 1 gets most of node 0's rank.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 9.0), (0, 2, 1.0)])?;
+    let pr = graph.get_pagerank(0.85, 1e-10, 1000);
+    assert_close(1.0, pr.values().sum());
+    assert!(pr[&NodeId::from(1)] > pr[&NodeId::from(2)]);
+
+    let unweighted =
+        WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (0, 2, 1.0)])?;
+    let pr = unweighted.get_pagerank(0.85, 1e-10, 1000);
+    assert_close(pr[&NodeId::from(1)], pr[&NodeId::from(2)]);
+    Ok(())
+}
+
+#[test]
+fn test_pagerank_directed() -> CLQResult<()> {
+    // a directed cycle is uniform.
+    let cycle = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0)])?;
+    let pr = cycle.get_pagerank(0.85, 1e-10, 1000);
+    for score in pr.values() {
+        assert_close(1.0 / 3.0, *score);
+    }
+
+    // node 2 is a sink: its rank is redistributed uniformly, and it outranks
+    // its in-neighbors.
+    let sink = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 2), (1, 2)])?;
+    let pr = sink.get_pagerank(0.85, 1e-10, 1000);
+    assert_close(1.0, pr.values().sum());
+    assert_close(pr[&NodeId::from(0)], pr[&NodeId::from(1)]);
+    assert!(pr[&NodeId::from(2)] > pr[&NodeId::from(0)]);
+    Ok(())
+}
+
+#[test]
+fn test_pagerank_max_iter() -> CLQResult<()> {
+    // no iterations leaves the uniform starting vector.
+    let graph = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    let pr = graph.get_pagerank(0.85, 1e-10, 0);
+    for score in pr.values() {
+        assert_close(0.25, *score);
+    }
+    Ok(())
+}
+
+// Personalized PageRank by power iteration, restarting at the seeds (and at the seeds
+// from nodes without outgoing edges).
+fn get_exact_personalized_pagerank<G: PageRank + GraphBase<NodeType = N>, N>(
+    graph: &G,
+    seeds: &[NodeId],
+    damping: f64,
+) -> HashMap<NodeId, f64>
+where
+    N: NodeBase<NodeIdType = NodeId>,
+    <N as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    let restart = 1.0 / seeds.len() as f64;
+    let mut ranks: HashMap<NodeId, f64> = seeds.iter().map(|id| (*id, restart)).collect();
+    for _ in 0..200 {
+        let mut next: HashMap<NodeId, f64> = HashMap::new();
+        let mut to_seeds: f64 = 1.0 - damping;
+        for (id, rank) in &ranks {
+            let edges = graph.get_pagerank_out_edges(graph.get_node(*id));
+            let total: f64 = edges.iter().map(|(_, w)| w).sum();
+            if edges.is_empty() {
+                to_seeds += damping * rank;
+            }
+            for (target, w) in edges {
+                *next.entry(target).or_insert(0.0) += damping * rank * w / total;
+            }
+        }
+        for id in seeds {
+            *next.entry(*id).or_insert(0.0) += to_seeds * restart;
+        }
+        ranks = next;
+    }
+    ranks
+}
+
+#[test]
+fn test_personalized_pagerank() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (0, 2),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (4, 6),
+        (5, 6),
+    ])?;
+    for seeds in [vec![0], vec![0, 6], vec![3, 3]] {
+        let seeds: Vec<NodeId> = seeds.into_iter().map(NodeId::from).collect();
+        let exact = get_exact_personalized_pagerank(&graph, &seeds, 0.85);
+        let ppr = graph.get_personalized_pagerank(&seeds, 0.85, 1e-10)?;
+        assert_eq!(ppr.len(), 7);
+        for (id, score) in &exact {
+            assert_close(*score, ppr[id]);
+        }
+    }
+
+    // the seed's side of the graph ranks higher.
+    let ppr = graph.get_personalized_pagerank(&[NodeId::from(0)], 0.85, 1e-6)?;
+    assert!(ppr[&NodeId::from(1)] > ppr[&NodeId::from(5)]);
+
+    assert!(graph
+        .get_personalized_pagerank(&[NodeId::from(7)], 0.85, 1e-6)
+        .is_err());
+    assert!(graph
+        .get_personalized_pagerank(&[NodeId::from(0)], 0.85, 0.0)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_personalized_pagerank_is_local() -> CLQResult<()> {
+    // only nodes close to the seed get a score.
+    let graph = SimpleUndirectedGraphBuilder {}.get_path_graph(10000)?;
+    let epsilon = 1e-4;
+    let ppr = graph.get_personalized_pagerank(&[NodeId::from(0)], 0.85, epsilon)?;
+    assert!(ppr.len() < 100);
+    assert!(!ppr.contains_key(&NodeId::from(9999)));
+    let total: f64 = ppr.values().sum();
+    assert!(total <= 1.0 && total > 0.9);
+    // scores are within epsilon times the degree of the exact ones.
+    let exact = get_exact_personalized_pagerank(&graph, &[NodeId::from(0)], 0.85);
+    for (id, score) in &exact {
+        let approximate = ppr.get(id).cloned().unwrap_or(0.0);
+        assert!(*score - approximate <= 2.0 * epsilon + 1e-12);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_personalized_pagerank_directed() -> CLQResult<()> {
+    // node 2 is a sink, from which walks restart at the seed.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (3, 0)])?;
+    let seeds = vec![NodeId::from(0)];
+    let ppr = graph.get_personalized_pagerank(&seeds, 0.85, 1e-12)?;
+    let exact = get_exact_personalized_pagerank(&graph, &seeds, 0.85);
+    assert!(!ppr.contains_key(&NodeId::from(3)));
+    for (id, score) in &exact {
+        assert_close(*score, ppr[id]);
+    }
+    assert_close(1.0, ppr.values().sum());
+    Ok(())
+}
+
+#[test]
+fn test_local_community() -> CLQResult<()> {
+    // three 5-cliques in a row, joined by single edges.
+    let mut edges: Vec<(i64, i64)> = vec![(4, 5), (9, 10)];
+    for clique in 0..3 {
+        for i in 0..5 {
+            for j in (i + 1)..5 {
+                edges.push((5 * clique + i, 5 * clique + j));
+            }
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let ids = |range: std::ops::Range<i64>| range.map(NodeId::from).collect::<Vec<NodeId>>();
+
+    let (community, conductance) = graph.get_local_community(&ids(0..1), 0.85, 1e-6)?;
+    assert_eq!(community, ids(0..5));
+    assert_close(1.0 / 21.0, conductance);
+    assert_close(graph.get_conductance(&community)?, conductance);
+
+    // the community contains every seed.
+    let (community, conductance) = graph.get_local_community(&ids(6..8), 0.85, 1e-6)?;
+    assert_eq!(community, ids(5..10));
+    assert_close(2.0 / 22.0, conductance);
+    let (community, _) =
+        graph.get_local_community(&[NodeId::from(0), NodeId::from(5)], 0.85, 1e-6)?;
+    assert_eq!(community, ids(0..10));
+
+    assert!(graph.get_local_community(&[], 0.85, 1e-6).is_err());
+    assert!(graph.get_conductance(&ids(0..15)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_local_community_weighted() -> CLQResult<()> {
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (1, 2, 0.1),
+        (2, 3, 5.0),
+        (3, 4, 5.0),
+    ])?;
+    let (community, conductance) = graph.get_local_community(&[NodeId::from(0)], 0.85, 1e-8)?;
+    assert_eq!(community, vec![NodeId::from(0), NodeId::from(1)]);
+    assert_close(0.1 / 10.1, conductance);
+    Ok(())
+}
@@ -0,0 +1,173 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+#![cfg(feature = "parquet")]
+extern crate arrow_array;
+extern crate lib_dachshund;
+extern crate parquet;
+use arrow_array::{ArrayRef, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::parquet_input::ParquetInput;
+use lib_dachshund::dachshund::simple_transformer::SimpleTransformer;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn write_parquet(name: &str, columns: Vec<(&str, ArrayRef)>) -> CLQResult<PathBuf> {
+    let path = std::env::temp_dir().join(format!("dachshund_{}_{}", name, std::process::id()));
+    let batch = RecordBatch::try_from_iter(columns).map_err(|x| x.to_string())?;
+    let mut writer = ArrowWriter::try_new(File::create(&path)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(path)
+}
+
+#[test]
+fn test_parquet_input() -> CLQResult<()> {
+    let text = "0\t1\t2\n0\t2\t3\n0\t3\t1\n1\t4\t5\n";
+    let mut expected: Vec<u8> = Vec::new();
+    SimpleTransformer::new().run(
+        Input::string(text.as_bytes()),
+        Output::string(&mut expected),
+    )?;
+    let expected = String::from_utf8(expected)?;
+
+    // columns in any order, of any integer type, with extra ones ignored.
+    let path = write_parquet(
+        "parquet_input",
+        vec![
+            (
+                "target_id",
+                Arc::new(Int32Array::from(vec![2, 3, 1, 5])) as ArrayRef,
+            ),
+            (
+                "weight",
+                Arc::new(Float64Array::from(vec![0.5, 1.0, 1.5, 2.0])) as ArrayRef,
+            ),
+            (
+                "graph_id",
+                Arc::new(StringArray::from(vec!["0", "0", "0", "1"])) as ArrayRef,
+            ),
+            (
+                "source_id",
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+        ],
+    )?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = SimpleTransformer::new().run_parquet(
+        ParquetInput::file(path.to_str().unwrap())?,
+        Output::string(&mut buffer),
+        None,
+        None,
+    )?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(String::from_utf8(buffer)?, expected);
+    assert_eq!(summary.num_graphs, 2);
+    assert_eq!(summary.num_rows, 4);
+
+    // values are read as typed, so that strings may contain tabs.
+    let path = write_parquet(
+        "parquet_input_tabs",
+        vec![
+            (
+                "graph_id",
+                Arc::new(StringArray::from(vec!["a\tb", "a\tb", "a\tb", "c"])) as ArrayRef,
+            ),
+            (
+                "source_id",
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+            (
+                "target_id",
+                Arc::new(Int64Array::from(vec![2, 3, 1, 5])) as ArrayRef,
+            ),
+        ],
+    )?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = SimpleTransformer::new().run_parquet(
+        ParquetInput::file(path.to_str().unwrap())?,
+        Output::string(&mut buffer),
+        None,
+        None,
+    )?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(summary.num_graphs, 2);
+    assert_eq!(summary.num_rows_rejected, 0);
+    let output = String::from_utf8(buffer)?;
+    assert_eq!(output.lines().count(), 2);
+    assert_eq!(
+        output.lines().next().unwrap(),
+        expected.lines().next().unwrap().replacen('0', "a\tb", 1)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parquet_input_errors() -> CLQResult<()> {
+    let path = write_parquet(
+        "parquet_input_missing",
+        vec![
+            (
+                "graph_id",
+                Arc::new(Int64Array::from(vec![0, 0])) as ArrayRef,
+            ),
+            ("src", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            (
+                "target_id",
+                Arc::new(Int64Array::from(vec![2, 3])) as ArrayRef,
+            ),
+        ],
+    )?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let error = SimpleTransformer::new()
+        .run_parquet(
+            ParquetInput::file(path.to_str().unwrap())?,
+            Output::string(&mut buffer),
+            None,
+            None,
+        )
+        .unwrap_err();
+    std::fs::remove_file(&path)?;
+    assert!(error
+        .to_string()
+        .starts_with("Parquet input has no column named source_id"));
+
+    // rows that cannot be parsed are rejected as lines would be.
+    let path = write_parquet(
+        "parquet_input_rejects",
+        vec![
+            (
+                "graph_id",
+                Arc::new(Int64Array::from(vec![0, 0, 0])) as ArrayRef,
+            ),
+            (
+                "source_id",
+                Arc::new(StringArray::from(vec!["1", "x", "3"])) as ArrayRef,
+            ),
+            (
+                "target_id",
+                Arc::new(Int64Array::from(vec![2, 3, 1])) as ArrayRef,
+            ),
+        ],
+    )?;
+    let mut rejects: Vec<u8> = Vec::new();
+    let summary = SimpleTransformer::new().run_parquet(
+        ParquetInput::file(path.to_str().unwrap())?,
+        Output::string(&mut buffer),
+        Some(&mut rejects),
+        None,
+    )?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(summary.num_rows, 2);
+    assert_eq!(summary.num_rows_rejected, 1);
+    assert!(String::from_utf8(rejects)?.starts_with("2\t"));
+    Ok(())
+}
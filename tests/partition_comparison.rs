@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::leiden::Leiden;
+use lib_dachshund::dachshund::algorithms::partition_comparison::{
+    get_adjusted_rand_index, get_community_labels, get_normalized_mutual_information,
+    get_variation_of_information,
+};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::test_utils::gen_bridged_triangles_graph;
+use std::collections::HashMap;
+
+fn get_labels(labels: Vec<usize>) -> HashMap<NodeId, usize> {
+    labels
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| (NodeId::from(i as i64), label))
+        .collect()
+}
+
+#[test]
+fn test_identical_partitions() -> CLQResult<()> {
+    let a = get_labels(vec![0, 0, 0, 1, 1, 2]);
+    // labels are arbitrary.
+    let b = get_labels(vec![5, 5, 5, 3, 3, 4]);
+    assert!((get_normalized_mutual_information(&a, &b)? - 1.0).abs() < 1e-12);
+    assert!((get_adjusted_rand_index(&a, &b)? - 1.0).abs() < 1e-12);
+    assert!(get_variation_of_information(&a, &b)?.abs() < 1e-12);
+
+    let together = get_labels(vec![0; 6]);
+    assert_eq!(
+        get_normalized_mutual_information(&together, &together)?,
+        1.0
+    );
+    assert_eq!(get_adjusted_rand_index(&together, &together)?, 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_partition_comparison() -> CLQResult<()> {
+    let a = get_labels(vec![0, 0, 0, 1, 1, 1]);
+    let b = get_labels(vec![0, 0, 1, 1, 2, 2]);
+    assert!((get_normalized_mutual_information(&a, &b)? - 0.5158037429793889).abs() < 1e-12);
+    assert!((get_adjusted_rand_index(&a, &b)? - 0.24242424242424243).abs() < 1e-12);
+    // H(b | a) + H(a | b), with H(b | a) = ln 3 - 2/3 ln 2 and H(a | b) = 1/3 ln 2.
+    let vi = 3.0_f64.ln() - 2.0_f64.ln() / 3.0;
+    assert!((get_variation_of_information(&a, &b)? - vi).abs() < 1e-12);
+    assert!((get_variation_of_information(&b, &a)? - vi).abs() < 1e-12);
+
+    // every node together against every node apart.
+    let together = get_labels(vec![0; 6]);
+    let apart = get_labels((0..6).collect());
+    assert_eq!(get_normalized_mutual_information(&together, &apart)?, 0.0);
+    assert_eq!(get_adjusted_rand_index(&together, &apart)?, 0.0);
+    assert!((get_variation_of_information(&together, &apart)? - 6.0_f64.ln()).abs() < 1e-12);
+
+    assert!(get_adjusted_rand_index(&a, &get_labels(vec![0; 5])).is_err());
+    let mut c = get_labels(vec![0; 5]);
+    c.insert(NodeId::from(7), 0);
+    assert!(get_normalized_mutual_information(&a, &c).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_compare_communities() -> CLQResult<()> {
+    // two triangles joined by a bridge.
+    let g = gen_bridged_triangles_graph()?;
+    let (communities, _) = g.get_leiden_communities();
+    let found = get_community_labels(&communities);
+    let truth = get_labels(vec![1, 1, 1, 0, 0, 0]);
+    assert!((get_normalized_mutual_information(&found, &truth)? - 1.0).abs() < 1e-12);
+    assert!((get_adjusted_rand_index(&found, &truth)? - 1.0).abs() < 1e-12);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::partitioning::Partitioning;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// num_cliques cliques of clique_size nodes, each joined to the next by a single edge.
+fn get_ring_of_cliques(num_cliques: i64, clique_size: i64) -> Vec<(i64, i64)> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for c in 0..num_cliques {
+        let first = c * clique_size;
+        for i in first..first + clique_size {
+            for j in i + 1..first + clique_size {
+                edges.push((i, j));
+            }
+        }
+        edges.push((first, (first + clique_size) % (num_cliques * clique_size)));
+    }
+    edges
+}
+
+#[test]
+fn test_partition_of_cliques() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_ring_of_cliques(4, 30))?;
+    let partition = graph.get_partition_with_rng(4, 0.0, &mut rng)?;
+    assert_eq!(partition.part_sizes, vec![30, 30, 30, 30]);
+    assert_eq!(partition.edge_cut, 4.0);
+    // each clique ends up in a part of its own.
+    for c in 0..4 {
+        let part = partition.parts[&NodeId::from(c * 30)];
+        assert!((c * 30..(c + 1) * 30).all(|i| partition.parts[&NodeId::from(i)] == part));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_partition_balance() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut edges: Vec<(i64, i64, f64)> = Vec::new();
+    for i in 0..300 {
+        for _ in 0..3 {
+            let j = rng.gen_range(0..300);
+            if i != j {
+                edges.push((i, j, rng.gen_range(1.0..10.0)));
+            }
+        }
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let num_nodes = graph.count_nodes();
+    let total_weight: f64 = graph
+        .get_nodes_iter()
+        .flat_map(|node| node.edges.iter().map(|e| e.weight))
+        .sum::<f64>()
+        / 2.0;
+    for k in [1, 2, 3, 8] {
+        let partition = graph.get_partition_with_rng(k, 0.05, &mut rng)?;
+        let max_size = (1.05 * num_nodes as f64 / k as f64).max((num_nodes / k) as f64);
+        assert_eq!(partition.parts.len(), num_nodes);
+        assert_eq!(partition.part_sizes.iter().sum::<usize>(), num_nodes);
+        assert!(partition.part_sizes.iter().all(|x| *x as f64 <= max_size));
+        if k == 1 {
+            assert_eq!(partition.edge_cut, 0.0);
+        } else {
+            // far better than a random split, which would cut most of the weight.
+            assert!(partition.edge_cut > 0.0);
+            assert!(partition.edge_cut < total_weight * 0.75 * (k - 1) as f64 / k as f64);
+        }
+    }
+    assert!(graph.get_partition_with_rng(0, 0.05, &mut rng).is_err());
+    assert!(graph.get_partition_with_rng(2, -0.1, &mut rng).is_err());
+    Ok(())
+}
@@ -8,14 +8,14 @@ extern crate lib_dachshund;
 use crate::lib_dachshund::dachshund::typed_graph_builder::TypedGraphBuilderBase;
 use lib_dachshund::dachshund::candidate::Candidate;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
-use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::id_types::{EdgeTypeId, GraphId};
 use lib_dachshund::dachshund::test_utils::{
     assert_nodes_have_ids, gen_test_transformer, process_raw_vector,
 };
 use lib_dachshund::dachshund::transformer::Transformer;
-use lib_dachshund::dachshund::typed_graph::TypedGraph;
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
 use lib_dachshund::dachshund::typed_graph_builder::TypedGraphBuilder;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::mpsc::channel;
 
 pub fn gen_test_typespec() -> Vec<Vec<String>> {
@@ -193,3 +193,118 @@ fn test_full_prune_small_clique() -> CLQResult<()> {
     assert!(result_prune.num_steps < result.num_steps);
     Ok(())
 }
+
+#[test]
+fn test_prune_by_edge_type() -> CLQResult<()> {
+    let typespec = vec![
+        vec!["author".into(), "published_at".into(), "conference".into()],
+        vec!["author".into(), "reviewed_for".into(), "conference".into()],
+    ];
+    // author 2 published at a single conference, and author 7 twice at the same one, so
+    // both are pruned, whatever their other ties.
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t1\t4\tauthor\tpublished_at\tconference".into(),
+        "0\t1\t3\tauthor\treviewed_for\tconference".into(),
+        "0\t2\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t2\t3\tauthor\treviewed_for\tconference".into(),
+        "0\t2\t4\tauthor\treviewed_for\tconference".into(),
+        "0\t5\t4\tauthor\tpublished_at\tconference".into(),
+        "0\t5\t6\tauthor\tpublished_at\tconference".into(),
+        "0\t7\t6\tauthor\tpublished_at\tconference".into(),
+        "0\t7\t6\tauthor\tpublished_at\tconference".into(),
+        "0\t7\t4\tauthor\treviewed_for\tconference".into(),
+    ];
+    let graph_id: GraphId = 0.into();
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let rows = process_raw_vector(&transformer, raw)?;
+    let mut graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows.clone())?;
+    let published_at = EdgeTypeId::from(
+        transformer
+            .edge_types
+            .iter()
+            .position(|x| x == "published_at")
+            .unwrap(),
+    );
+    let edge_type_min_degree: BTreeMap<EdgeTypeId, usize> =
+        vec![(published_at, 2)].into_iter().collect();
+    let exclude_nodes =
+        TypedGraphBuilder::trim_edges_by_type(&mut graph.nodes, 0, &edge_type_min_degree);
+    let excluded_labels: HashSet<i64> = exclude_nodes
+        .iter()
+        .map(|id| graph.get_node_label(*id).unwrap().value())
+        .collect();
+    assert_eq!(excluded_labels, vec![2, 7].into_iter().collect());
+
+    let transformer = transformer
+        .with_edge_type_min_degree(vec![("published_at".to_string(), 2)].into_iter().collect())?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+    let mut core_labels: Vec<i64> = graph.get_core_labels().iter().map(|x| x.value()).collect();
+    core_labels.sort_unstable();
+    assert_eq!(core_labels, vec![1, 5]);
+
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    assert!(transformer
+        .with_edge_type_min_degree(vec![("cited".to_string(), 2)].into_iter().collect())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_prune_bipartite() -> CLQResult<()> {
+    let typespec = vec![vec![
+        "author".into(),
+        "published_at".into(),
+        "conference".into(),
+    ]];
+    // authors 1 and 2 published at conferences 3 and 4, and author 5 at 3, 4 and 6.
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t1\t4\tauthor\tpublished_at\tconference".into(),
+        "0\t2\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t2\t4\tauthor\tpublished_at\tconference".into(),
+        "0\t5\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t5\t4\tauthor\tpublished_at\tconference".into(),
+        "0\t5\t6\tauthor\tpublished_at\tconference".into(),
+    ];
+    let graph_id: GraphId = 0.into();
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let rows = process_raw_vector(&transformer, raw)?;
+    let get_excluded_labels = |core_min_degree: usize, non_core_min_degree: usize| {
+        let mut graph: TypedGraph = transformer
+            .build_pruned_graph(graph_id, rows.clone())
+            .unwrap();
+        let exclude_nodes = TypedGraphBuilder::trim_edges_bipartite(
+            &mut graph.nodes,
+            core_min_degree,
+            non_core_min_degree,
+            &BTreeMap::new(),
+        );
+        let mut excluded_labels: Vec<i64> = exclude_nodes
+            .iter()
+            .map(|id| graph.get_node_label(*id).unwrap().value())
+            .collect();
+        excluded_labels.sort_unstable();
+        excluded_labels
+    };
+    assert_eq!(get_excluded_labels(2, 2), vec![6]);
+    assert_eq!(get_excluded_labels(2, 3), vec![6]);
+    assert_eq!(get_excluded_labels(2, 4), vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(get_excluded_labels(3, 1), vec![1, 2]);
+
+    let graph: TypedGraph = transformer
+        .with_non_core_min_degree(3)
+        .build_pruned_graph(graph_id, rows)?;
+    let mut core_labels: Vec<i64> = graph.get_core_labels().iter().map(|x| x.value()).collect();
+    core_labels.sort_unstable();
+    assert_eq!(core_labels, vec![1, 2, 5]);
+    let mut non_core_labels: Vec<i64> = graph
+        .get_non_core_labels()
+        .unwrap()
+        .iter()
+        .map(|x| x.value())
+        .collect();
+    non_core_labels.sort_unstable();
+    assert_eq!(non_core_labels, vec![3, 4]);
+    Ok(())
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use lib_dachshund::dachshund::beam::{Beam, BeamCandidate};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::quasi_clique::{
+    QuasiCliqueCandidate, QuasiCliqueGraph, QuasiCliqueScorer,
+};
+use lib_dachshund::dachshund::quasi_clique_transformer::QuasiCliqueTransformer;
+use lib_dachshund::dachshund::search_problem::SearchProblem;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use rand::prelude::*;
+
+fn get_search_problem(global_thresh: f32, local_thresh: f32, min_degree: usize) -> SearchProblem {
+    SearchProblem::new(
+        20,
+        1.0,
+        Some(global_thresh),
+        Some(local_thresh),
+        10,
+        100,
+        3,
+        min_degree,
+    )
+}
+
+#[test]
+fn test_quasi_clique_candidate() -> CLQResult<()> {
+    // a 4-cycle 10-11-12-13 with a chord 10-12, and a pendant node 14 (and a self-loop).
+    let edges = vec![
+        (10, 11),
+        (11, 12),
+        (12, 13),
+        (13, 10),
+        (10, 12),
+        (13, 14),
+        (14, 14),
+    ];
+    let graph = QuasiCliqueGraph::new(&SimpleUndirectedGraphBuilder {}.from_vector(edges)?);
+    assert_eq!(graph.num_nodes(), 5);
+    assert!(!graph.neighbors[4].contains(4));
+    let scorer = QuasiCliqueScorer::new(&get_search_problem(0.5, 0.5, 1));
+
+    let mut candidate = QuasiCliqueCandidate::new(0, &graph, &scorer);
+    assert_eq!(candidate.get_score()?, -1.0);
+    let mut visited: HashSet<u64> = HashSet::new();
+    let recipes = candidate.one_step_search(10, &mut visited, &scorer)?;
+    assert_eq!(recipes.len(), 3);
+    for recipe in &recipes {
+        let expanded = candidate.expand_from_recipe(recipe)?;
+        let mut rescored = expanded.replicate(false);
+        rescored.set_score(scorer.score(&rescored))?;
+        assert_eq!(expanded.get_score()?, rescored.get_score()?);
+    }
+
+    for node_id in [1, 2, 3] {
+        candidate.add_node(node_id)?;
+    }
+    assert!(candidate.add_node(2).is_err());
+    assert_eq!(candidate.get_size(), 4);
+    assert_eq!(candidate.count_ties(), 5);
+    assert_eq!(candidate.get_min_degree(), 2);
+    assert_eq!(candidate.get_degrees()[&0], 3);
+    assert_eq!(candidate.get_neighborhood().len(), 1);
+    assert_eq!(candidate.get_neighborhood()[&4], 1);
+    assert_eq!(
+        candidate.sorted_ids(),
+        (10..14).map(NodeId::from).collect::<Vec<_>>()
+    );
+    // 5 of 6 pairs are tied, and every node is tied to at least 2 of the 3 others.
+    assert!(scorer.score(&candidate) > 0.0);
+    assert!(QuasiCliqueScorer::new(&get_search_problem(0.9, 0.5, 1)).score(&candidate) == 0.0);
+    assert!(QuasiCliqueScorer::new(&get_search_problem(0.5, 0.9, 1)).score(&candidate) == 0.0);
+    let row = candidate.to_printable_row()?;
+    let fields: Vec<&str> = row.split('\t').collect();
+    assert_eq!(fields[0], "4");
+    assert_eq!(fields[1], "[10,11,12,13]");
+    assert_eq!(fields[2].parse::<f32>().unwrap(), 5.0 / 6.0);
+    Ok(())
+}
+
+#[test]
+fn test_quasi_clique_search_planted() -> CLQResult<()> {
+    // 12 nodes with all but a perfect matching of their ties, in a sparse random graph.
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..100 {
+        for j in (i + 1)..100 {
+            let planted = i < 12 && j < 12;
+            if (planted && !(i % 2 == 0 && j == i + 1)) || (!planted && rng.gen::<f64>() < 0.03) {
+                edges.push((i, j));
+            }
+        }
+    }
+    let graph = QuasiCliqueGraph::new(&SimpleUndirectedGraphBuilder {}.from_vector(edges)?);
+    let search_problem = Rc::new(get_search_problem(0.8, 0.8, 1));
+    let mut beam = Beam::new_quasi_clique(&graph, false, search_problem, GraphId::from(0))?;
+    let top_candidate = beam.run_search()?.top_candidate;
+    assert!(top_candidate.get_score()? > 0.0);
+    assert!(top_candidate.get_size() >= 12);
+    assert!(top_candidate.get_density() >= 0.8);
+    assert!(top_candidate.get_min_degree() as f32 >= 0.8 * (top_candidate.get_size() - 1) as f32);
+    Ok(())
+}
+
+#[test]
+fn test_quasi_clique_transformer() -> CLQResult<()> {
+    // graph a: a 5-clique with a pendant node; graph b: a path, pruned away at min_degree 2.
+    let mut rows: Vec<String> = Vec::new();
+    for i in 0..5 {
+        for j in (i + 1)..5 {
+            rows.push(format!("a\t{i}\t{j}"));
+        }
+    }
+    rows.push("a\t4\t5".into());
+    rows.push("b\t1\t2".into());
+    rows.push("b\t2\t3".into());
+    let text = rows.join("\n");
+    let mut transformer = QuasiCliqueTransformer::new(get_search_problem(1.0, 1.0, 2), false)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let output_str: String = String::from_utf8(buffer).unwrap();
+    let lines: Vec<&str> = output_str.lines().collect();
+    assert_eq!(lines.len(), 1);
+    let fields: Vec<&str> = lines[0].split('\t').collect();
+    assert_eq!(fields[..3], ["a", "5", "[0,1,2,3,4]"]);
+    assert_eq!(fields[3].parse::<f32>().unwrap(), 1.0);
+    assert_eq!(summary.num_graphs, 2);
+    assert_eq!(summary.num_lines_emitted, 1);
+    Ok(())
+}
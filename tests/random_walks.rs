@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::random_walks::{RandomWalkParams, RandomWalks};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_random_walk_params() {
+    assert!(RandomWalkParams::new(0, 1, 1.0, 1.0).is_err());
+    assert!(RandomWalkParams::new(5, 1, 0.0, 1.0).is_err());
+    assert!(RandomWalkParams::new(5, 1, 1.0, -1.0).is_err());
+    assert!(RandomWalkParams::new(5, 1, 1.0, 1.0).is_ok());
+}
+
+#[test]
+fn test_random_walks() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graph = SimpleUndirectedGraphBuilder {}.get_cycle_graph(10)?;
+    let start = NodeId::from(0);
+
+    let params = RandomWalkParams::new(20, 1, 1.0, 1.0)?;
+    let walk = graph.get_random_walk_with_rng(start, &params, &mut rng)?;
+    assert_eq!(walk.len(), 20);
+    assert_eq!(walk[0], start);
+    for step in walk.windows(2) {
+        assert!(graph.get_node(step[0]).neighbors.contains(&step[1]));
+    }
+
+    // a tiny return parameter makes walks go back and forth.
+    let params = RandomWalkParams::new(20, 1, 1e-9, 1.0)?;
+    let walk = graph.get_random_walk_with_rng(start, &params, &mut rng)?;
+    assert!(walk.windows(3).all(|x| x[0] == x[2]));
+    // a tiny in-out parameter makes them go around the cycle.
+    let params = RandomWalkParams::new(20, 1, 1.0, 1e-9)?;
+    let walk = graph.get_random_walk_with_rng(start, &params, &mut rng)?;
+    assert!(walk.windows(3).all(|x| x[0] != x[2]));
+
+    assert!(graph
+        .get_random_walk_with_rng(NodeId::from(100), &params, &mut rng)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_directed_random_walks() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // walks can only leave 0 and 1 towards 2, which has no out-edges.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (0, 2)])?;
+    let params = RandomWalkParams::new(10, 1, 0.5, 2.0)?;
+    for _ in 0..10 {
+        let walk = graph.get_random_walk_with_rng(NodeId::from(0), &params, &mut rng)?;
+        assert_eq!(walk[walk.len() - 1], NodeId::from(2));
+        assert!(walk.len() <= 3);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_write_random_walks() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let params = RandomWalkParams::new(4, 3, 2.0, 0.5)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut output = Output::string(&mut buffer);
+    assert_eq!(
+        graph.write_random_walks_with_rng(&params, &mut output, &mut rng)?,
+        15
+    );
+    let text = String::from_utf8(buffer).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 15);
+    for line in lines {
+        let ids: Vec<i64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+        assert_eq!(ids.len(), 4);
+        assert!(ids.iter().all(|x| (1..6).contains(x)));
+    }
+    Ok(())
+}
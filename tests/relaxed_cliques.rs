@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use std::rc::Rc;
+
+use lib_dachshund::dachshund::algorithms::relaxed_cliques::{
+    is_k_club_within, is_k_plex_within, RelaxedCliques,
+};
+use lib_dachshund::dachshund::beam::Beam;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::quasi_clique::QuasiCliqueGraph;
+use lib_dachshund::dachshund::search_problem::{CliqueRelaxation, SearchProblem};
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use rand::prelude::*;
+
+fn to_ids(ids: Vec<i64>) -> Vec<NodeId> {
+    ids.into_iter().map(NodeId::from).collect()
+}
+
+#[test]
+fn test_max_k_plex() -> CLQResult<()> {
+    // a 5-clique missing the tie 0-1, with a pendant node 5.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..5 {
+        for j in (i + 1)..5 {
+            if (i, j) != (0, 1) {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges.push((4, 5));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert_eq!(graph.get_max_k_plex(1)?, to_ids(vec![0, 2, 3, 4]));
+    assert_eq!(graph.get_max_k_plex(2)?, to_ids(vec![0, 1, 2, 3, 4]));
+    assert!(graph.is_k_plex(&to_ids(vec![0, 1, 2, 3, 4]), 2)?);
+    assert!(!graph.is_k_plex(&to_ids(vec![0, 1, 2, 3, 4]), 1)?);
+    // the pendant node is tied to 1 of the 6.
+    assert!(!graph.is_k_plex(&to_ids(vec![0, 1, 2, 3, 4, 5]), 4)?);
+    assert!(graph.is_k_plex(&to_ids(vec![0, 1, 2, 3, 4, 5]), 5)?);
+    assert!(graph.is_k_plex(&to_ids(vec![7]), 1).is_err());
+    assert!(graph.get_max_k_plex(0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_max_k_club() -> CLQResult<()> {
+    // a star with center 0 and leaves 1 to 5, a path 5-6-7, and a 5-cycle 10 to 14.
+    let mut edges: Vec<(i64, i64)> = (1..6).map(|i| (0, i)).collect();
+    edges.push((5, 6));
+    edges.push((6, 7));
+    for i in 0..5 {
+        edges.push((10 + i, 10 + (i + 1) % 5));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert_eq!(graph.get_max_k_club(1)?.len(), 2);
+    assert_eq!(graph.get_max_k_club(2)?, to_ids(vec![0, 1, 2, 3, 4, 5]));
+    assert_eq!(graph.get_max_k_club(3)?, to_ids(vec![0, 1, 2, 3, 4, 5, 6]));
+    // 2-clubs aren't hereditary: the 5-cycle is one, but not a path of 4 of its nodes.
+    assert!(graph.is_k_club(&to_ids(vec![10, 11, 12, 13, 14]), 2)?);
+    assert!(!graph.is_k_club(&to_ids(vec![10, 11, 12, 13]), 2)?);
+    assert!(graph.get_max_k_club(0).is_err());
+    Ok(())
+}
+
+fn get_search_problem(clique_relaxation: CliqueRelaxation) -> SearchProblem {
+    SearchProblem::new(20, 1.0, Some(1.0), Some(1.0), 10, 100, 3, 1)
+        .with_clique_relaxation(clique_relaxation)
+}
+
+#[test]
+fn test_relaxed_clique_search() -> CLQResult<()> {
+    // 12 nodes with all but a perfect matching of their ties (a 2-plex), in a sparse random
+    // graph.
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..100 {
+        for j in (i + 1)..100 {
+            let planted = i < 12 && j < 12;
+            if (planted && !(i % 2 == 0 && j == i + 1)) || (!planted && rng.gen::<f64>() < 0.03) {
+                edges.push((i, j));
+            }
+        }
+    }
+    let graph = QuasiCliqueGraph::new(&SimpleUndirectedGraphBuilder {}.from_vector(edges)?);
+
+    let search_problem = Rc::new(get_search_problem(CliqueRelaxation::KPlex(2)));
+    let mut beam = Beam::new_quasi_clique(&graph, false, search_problem, GraphId::from(0))?;
+    let top_candidate = beam.run_search()?.top_candidate;
+    assert!(top_candidate.get_score()? > 0.0);
+    assert!(top_candidate.get_size() >= 12);
+    assert!(is_k_plex_within(
+        &graph.neighbors,
+        &top_candidate.node_ids,
+        2
+    ));
+
+    let search_problem = Rc::new(get_search_problem(CliqueRelaxation::KClub(2)));
+    let mut beam = Beam::new_quasi_clique(&graph, false, search_problem, GraphId::from(0))?;
+    let top_candidate = beam.run_search()?.top_candidate;
+    assert!(top_candidate.get_score()? > 0.0);
+    assert!(top_candidate.get_size() >= 12);
+    assert!(is_k_club_within(
+        &graph.neighbors,
+        &top_candidate.node_ids,
+        2
+    ));
+
+    assert!(get_search_problem(CliqueRelaxation::KClub(0))
+        .validate(false)
+        .is_err());
+    Ok(())
+}
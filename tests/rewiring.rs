@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::algorithms::rewiring::{rewire_edges_connected, Rewiring};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::gen_two_triangles_graph;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_rewired_graph() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // a cycle with a self-loop on 0.
+    let mut edges: Vec<(i64, i64)> = (0..20).map(|i| (i, (i + 1) % 20)).collect();
+    edges.push((0, 0));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let rewired = graph.get_rewired_graph_with_rng(10, &mut rng);
+    assert_eq!(rewired.get_ordered_node_ids(), graph.get_ordered_node_ids());
+    assert_eq!(rewired.count_edges(), graph.count_edges());
+    for node in rewired.get_nodes_iter() {
+        let id = node.node_id;
+        assert_eq!(node.neighbors.len(), graph.get_node(id).neighbors.len());
+        assert_eq!(node.neighbors.contains(&id), id == NodeId::from(0));
+    }
+    // a rewired cycle is very unlikely to still be the same cycle.
+    assert!(graph
+        .get_nodes_iter()
+        .any(|node| node.neighbors != rewired.get_node(node.node_id).neighbors));
+
+    // no swaps are possible on a complete graph.
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let rewired = graph.get_rewired_graph_with_rng(10, &mut rng);
+    for node in graph.get_nodes_iter() {
+        assert_eq!(node.neighbors, rewired.get_node(node.node_id).neighbors);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_connected_rewired_graph() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // a sparse graph (a cycle with a few chords), which random swaps easily disconnect.
+    let mut edges: Vec<(i64, i64)> = (0..30).map(|i| (i, (i + 1) % 30)).collect();
+    edges.extend(vec![(0, 15), (5, 20), (10, 25)]);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    for _ in 0..10 {
+        let rewired = graph.get_connected_rewired_graph_with_rng(10, &mut rng)?;
+        assert_eq!(rewired.get_connected_components().len(), 1);
+        assert_eq!(rewired.count_edges(), graph.count_edges());
+        for node in graph.get_nodes_iter() {
+            let rewired_node = rewired.get_node(node.node_id);
+            assert_eq!(rewired_node.neighbors.len(), node.neighbors.len());
+        }
+        assert!(graph
+            .get_nodes_iter()
+            .any(|node| node.neighbors != rewired.get_node(node.node_id).neighbors));
+    }
+
+    // two triangles can't be rewired into a connected graph.
+    let graph = gen_two_triangles_graph()?;
+    assert!(graph
+        .get_connected_rewired_graph_with_rng(10, &mut rng)
+        .is_err());
+    let mut edges: Vec<(NodeId, NodeId)> = vec![
+        (NodeId::from(0), NodeId::from(1)),
+        (NodeId::from(2), NodeId::from(3)),
+    ];
+    assert!(rewire_edges_connected(&mut edges, 10, &mut rng).is_err());
+    Ok(())
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::rich_club::RichClub;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_rich_club_coefficient() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    assert_eq!(graph.get_rich_club_coefficient(3), Some(1.0));
+    assert_eq!(graph.get_rich_club_coefficient(4), None);
+
+    // the 3 inner nodes of a path on 5 nodes (4 edges) are joined by 2 of 3 possible edges.
+    let graph = SimpleUndirectedGraphBuilder {}.get_path_graph(4)?;
+    assert_eq!(graph.get_rich_club_coefficient(1), Some(2.0 / 3.0));
+    assert_eq!(graph.get_rich_club_coefficient(0), Some(0.4));
+    Ok(())
+}
+
+#[test]
+fn test_normalized_rich_club_coefficient() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // no swaps are possible in a complete graph.
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    assert_eq!(
+        graph.get_normalized_rich_club_coefficient_with_rng(2, 10, &mut rng),
+        Some(1.0)
+    );
+
+    // a clique of 4 hubs, each with 4 leaves of its own, and a cycle through the leaves.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for hub in 0..4 {
+        for other in hub + 1..4 {
+            edges.push((hub, other));
+        }
+        for leaf in 0..4 {
+            edges.push((hub, 4 + 4 * hub + leaf));
+        }
+    }
+    for leaf in 4..20 {
+        edges.push((leaf, if leaf == 19 { 4 } else { leaf + 1 }));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert_eq!(graph.get_rich_club_coefficient(3), Some(1.0));
+    // rewiring scatters the hubs' edges among the leaves.
+    let normalized = graph
+        .get_normalized_rich_club_coefficient_with_rng(3, 10, &mut rng)
+        .unwrap();
+    assert!(normalized > 1.0);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::line_processor::{
+    LineProcessor, LineProcessorBase, LineProcessorRegistry, LineSchema, SIMPLE_EDGE_SCHEMA,
+};
+use lib_dachshund::dachshund::row::{Row, SimpleEdgeRow};
+use std::any::Any;
+use std::sync::Arc;
+
+const TIMESTAMPED_EDGE_SCHEMA: LineSchema = LineSchema {
+    fields: &["graph_id", "source_id", "target_id", "timestamp"],
+};
+
+// a kind of row the library knows nothing about.
+#[derive(Copy, Clone)]
+struct TimestampedEdgeRow {
+    graph_id: GraphId,
+    source_id: NodeId,
+    target_id: NodeId,
+    timestamp: u64,
+}
+impl Row for TimestampedEdgeRow {
+    fn get_graph_id(&self) -> GraphId {
+        self.graph_id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct TimestampedLineProcessor {}
+impl LineProcessorBase for TimestampedLineProcessor {
+    fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
+        let schema = &TIMESTAMPED_EDGE_SCHEMA;
+        let vec: Vec<&str> = schema.split(&line)?;
+        Ok(Box::new(TimestampedEdgeRow {
+            graph_id: schema.parse::<i64>(&vec, 0)?.into(),
+            source_id: schema.parse::<i64>(&vec, 1)?.into(),
+            target_id: schema.parse::<i64>(&vec, 2)?.into(),
+            timestamp: schema.parse::<u64>(&vec, 3)?,
+        }))
+    }
+}
+
+#[test]
+fn test_line_processor_registry() -> CLQResult<()> {
+    let mut registry = LineProcessorRegistry::new();
+    registry.register(&SIMPLE_EDGE_SCHEMA, Arc::new(LineProcessor::new()))?;
+    registry.register(
+        &TIMESTAMPED_EDGE_SCHEMA,
+        Arc::new(TimestampedLineProcessor {}),
+    )?;
+    assert!(registry
+        .register(&SIMPLE_EDGE_SCHEMA, Arc::new(LineProcessor::new()))
+        .is_err());
+
+    let row = registry.process_line("0\t1\t2".to_string())?;
+    assert_eq!(row.as_simple_edge_row().unwrap().as_tuple(), (1, 2));
+    assert!(row.downcast_ref::<SimpleEdgeRow>().is_some());
+    assert!(row.downcast_ref::<TimestampedEdgeRow>().is_none());
+
+    let row = registry.process_line("3\t1\t2\t1600000000".to_string())?;
+    assert!(row.as_simple_edge_row().is_none());
+    let timestamped = row.downcast_ref::<TimestampedEdgeRow>().unwrap();
+    assert_eq!(timestamped.graph_id, GraphId::from(3));
+    assert_eq!(timestamped.source_id, NodeId::from(1));
+    assert_eq!(timestamped.target_id, NodeId::from(2));
+    assert_eq!(timestamped.timestamp, 1600000000);
+
+    let error = registry.process_line("0\t1".to_string()).err().unwrap();
+    assert!(error.to_string().contains("registered: 3, 4"));
+    let error = registry
+        .process_line("3\t1\t2\tnow".to_string())
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("timestamp"));
+    Ok(())
+}
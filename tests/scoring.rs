@@ -10,12 +10,12 @@ use std::rc::Rc;
 
 use lib_dachshund::dachshund::candidate::Candidate;
 use lib_dachshund::dachshund::error::CLQResult;
-use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
 use lib_dachshund::dachshund::row::EdgeRow;
 use lib_dachshund::dachshund::scorer::Scorer;
 use lib_dachshund::dachshund::search_problem::SearchProblem;
 use lib_dachshund::dachshund::transformer::Transformer;
-use lib_dachshund::dachshund::typed_graph::TypedGraph;
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
 
 use lib_dachshund::dachshund::test_utils::{gen_test_transformer, process_raw_vector};
 
@@ -89,3 +89,87 @@ fn test_score_trivial_graph() -> CLQResult<()> {
     assert_eq!(score, expected_score);
     Ok(())
 }
+
+#[test]
+fn test_explain_score() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec![
+            "author".to_string(),
+            "published_at".into(),
+            "conference".into(),
+        ],
+        vec!["author".to_string(), "attended".into(), "conference".into()],
+    ];
+    let graph_id: GraphId = 0.into();
+    let raw: Vec<String> = vec!["0\t1\t2\tauthor\tpublished_at\tconference".to_string()];
+    let transformer: Transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let rows: Vec<EdgeRow> = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+
+    // the candidate has a cliqueness of 0.5, so fails a global threshold of 0.75.
+    let alpha = 2.0;
+    let search_problem = Rc::new(SearchProblem::new(
+        20,
+        alpha,
+        Some(0.75),
+        Some(0.5),
+        20,
+        100,
+        3,
+        1,
+    ));
+    let scorer: Scorer = Scorer::new(2, &search_problem);
+    let core_node_id: u32 = *graph.core_ids.first().unwrap();
+    let mut candidate: Candidate<TypedGraph> = Candidate::new(core_node_id, &graph, &scorer)?;
+    let explanation = scorer.explain(&mut candidate)?;
+    assert_eq!(explanation.score, -1.0);
+
+    candidate.add_node(*graph.non_core_ids.first().unwrap())?;
+    let explanation = scorer.explain(&mut candidate)?;
+    assert_eq!(explanation.size_term, 2.0 * 2.0_f32.ln());
+    assert_eq!(explanation.cliqueness, 0.5);
+    assert_eq!(explanation.cliqueness_term, 1.0);
+    assert_eq!(explanation.global_thresh_factor, 0.0);
+    assert_eq!(explanation.local_thresh_factor, 1.0);
+    assert_eq!(explanation.min_local_density, Some(0.5));
+    assert_eq!(explanation.score, 0.0);
+    assert_eq!(explanation.score, scorer.score(&mut candidate)?);
+    assert!(explanation.to_string().contains("global thresh 0"));
+    Ok(())
+}
+
+#[test]
+fn test_score_with_edge_type_weights() -> CLQResult<()> {
+    let typespec_str = r#"[["author", "published", "article", 1.0],
+                           ["author", "cited", "article", 0.25]]"#;
+    let typespec: Vec<Vec<String>> = Transformer::parse_typespec(typespec_str)?;
+    assert_eq!(typespec[1], vec!["author", "cited", "article", "0.25"]);
+    let graph_id: GraphId = 0.into();
+    let raw: Vec<String> = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t2\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t1\t3\tauthor\tcited\tarticle".to_string(),
+    ];
+    let transformer: Transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let rows: Vec<EdgeRow> = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows)?;
+    let node_id = |label: i64| graph.get_node_by_label(NodeId::from(label)).node_id;
+
+    let scorer: Scorer = Scorer::new(1, &transformer.search_problem);
+    let mut candidate: Candidate<TypedGraph> = Candidate::new(node_id(1), &graph, &scorer)?;
+    candidate.add_node(node_id(3))?;
+    let cliqueness_with_node =
+        candidate.get_cliqueness_with_node(graph.get_node_by_label(NodeId::from(2)))?;
+    candidate.add_node(node_id(2))?;
+
+    // 3 of the 4 possible ties, but the missing one is a citation, which only counts 0.25:
+    // (1.0 + 1.0 + 0.25) / (2 * 1.25).
+    assert_eq!(candidate.count_ties_between_nodes()?, 3);
+    assert_eq!(candidate.get_size()?, 4);
+    assert_eq!(candidate.get_cliqueness()?, 0.9);
+    assert_eq!(cliqueness_with_node, 0.9);
+    assert_eq!(scorer.explain(&mut candidate)?.cliqueness, 0.9);
+    // local thresholds still count ties.
+    assert_eq!(candidate.get_local_densities()[&node_id(2)], 0.5);
+    Ok(())
+}
@@ -5,12 +5,22 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::clustering::Clustering;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsDirected;
+use lib_dachshund::dachshund::algorithms::coreness::DirectedCoreness;
+use lib_dachshund::dachshund::algorithms::directed_transitivity::DirectedTransitivity;
+use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
-use lib_dachshund::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph::{DirectedGraph, SimpleDirectedGraph};
 use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
-use std::collections::HashSet;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
 fn get_rows(idx: usize) -> CLQResult<Vec<(usize, usize)>> {
     match idx {
         0 => Ok(vec![
@@ -153,3 +163,258 @@ fn test_build_graph() -> CLQResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_directed_graph_exports() -> CLQResult<()> {
+    use lib_dachshund::{
+        ConnectedComponentsDirected, ConnectivityDirected, DirectedGraph, GraphBuilderBase,
+        SimpleDirectedGraph, SimpleDirectedGraphBuilder,
+    };
+    // a cycle 0 -> 1 -> 2 -> 0, with 2 -> 3 out of it; the repeated edge is ignored.
+    let graph: SimpleDirectedGraph =
+        SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0), (2, 3), (2, 3)])?;
+    assert_eq!(graph.count_edges(), 4);
+    assert!(!graph.is_acyclic());
+    assert_eq!(graph.get_is_weakly_connected(), Ok(true));
+    assert_eq!(
+        as_sorted_sets(graph.get_strongly_connected_components()),
+        as_sorted_sets(vec![
+            vec![NodeId::from(0), NodeId::from(1), NodeId::from(2)],
+            vec![NodeId::from(3)],
+        ])
+    );
+    let dag = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (0, 2)])?;
+    assert!(dag.is_acyclic());
+    Ok(())
+}
+
+#[test]
+fn test_reciprocity() -> CLQResult<()> {
+    // a directed cycle has no mutual edges.
+    assert_eq!(get_graph(1)?.get_reciprocity(), 0.0);
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 0), (1, 2)])?;
+    assert_eq!(graph.get_reciprocity(), 2.0 / 3.0);
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 0)])?;
+    assert_eq!(graph.get_reciprocity(), 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_directed_clustering() -> CLQResult<()> {
+    // a directed cycle closes half the triangles its nodes could be part of.
+    let graph = get_graph(1)?;
+    for i in 0..3 {
+        assert_eq!(
+            graph.get_directed_clustering_coefficient(NodeId::from(i)),
+            Some(0.5)
+        );
+    }
+    assert_eq!(graph.get_directed_transitivity(), 0.5);
+
+    // 0 has 3 neighbors, with 2 triangles: 0 -> 1 -> 2 -> 0 and 0 -> 1 -> 3 -> 0.
+    let graph = get_graph(2)?;
+    assert_eq!(
+        graph.get_directed_clustering_coefficient(NodeId::from(0)),
+        Some(2.0 / 6.0)
+    );
+
+    // a path can't close any triangle, and its ends couldn't be part of one.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 0), (1, 2)])?;
+    assert_eq!(
+        graph.get_directed_clustering_coefficient(NodeId::from(1)),
+        Some(0.0)
+    );
+    assert_eq!(
+        graph.get_directed_clustering_coefficient(NodeId::from(2)),
+        None
+    );
+    assert_eq!(graph.get_avg_directed_clustering(), 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_directed_clustering_of_mutual_edges() -> CLQResult<()> {
+    // with every edge reciprocated, directed and undirected clustering agree.
+    let undirected = SimpleUndirectedGraphBuilder {}.get_er_graph(50, 0.2)?;
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for (id, node) in &undirected.nodes {
+        edges.extend(node.neighbors.iter().map(|x| (id.value(), x.value())));
+    }
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(edges)?;
+    assert_eq!(directed.get_reciprocity(), 1.0);
+    for id in undirected.nodes.keys() {
+        match undirected.get_clustering_coefficient(*id) {
+            Some(coef) => assert!(
+                (directed.get_directed_clustering_coefficient(*id).unwrap() - coef).abs() < 1e-12
+            ),
+            None => assert_eq!(directed.get_directed_clustering_coefficient(*id), None),
+        }
+    }
+    assert!((directed.get_directed_transitivity() - undirected.get_transitivity()).abs() < 1e-12);
+    Ok(())
+}
+
+fn as_sorted_sets(components: Vec<Vec<NodeId>>) -> BTreeSet<BTreeSet<NodeId>> {
+    components
+        .into_iter()
+        .map(|c| c.into_iter().collect())
+        .collect()
+}
+
+#[test]
+fn test_tarjan_strongly_connected_components() -> CLQResult<()> {
+    for i in 0..7 {
+        let graph = get_graph(i)?;
+        let components = graph.get_strongly_connected_components_tarjan();
+        assert_eq!(
+            as_sorted_sets(components.clone()),
+            as_sorted_sets(graph.get_strongly_connected_components())
+        );
+        // components are in topological order.
+        let position: HashMap<NodeId, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, c)| c.iter().map(move |id| (*id, i)))
+            .collect();
+        for (id, node) in &graph.nodes {
+            for target in &node.out_neighbors {
+                assert!(position[target] >= position[id]);
+            }
+        }
+    }
+
+    // a long path, which a recursive search could overflow the stack on.
+    let path: Vec<(i64, i64)> = (0..100000).map(|i| (i, i + 1)).collect();
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(path)?;
+    let components = graph.get_strongly_connected_components_tarjan();
+    assert_eq!(components.len(), 100001);
+    assert_eq!(components[0], vec![NodeId::from(0)]);
+    Ok(())
+}
+
+#[test]
+fn test_condensation() -> CLQResult<()> {
+    // two directed triangles, with an edge from the first to the second.
+    let graph = get_graph(4)?;
+    let (condensation, membership) = graph.get_condensation();
+    assert_eq!(condensation.count_nodes(), 2);
+    assert_eq!(condensation.count_edges(), 1);
+    assert!(condensation.is_acyclic());
+    for i in 0..3 {
+        assert_eq!(membership[&NodeId::from(i)], NodeId::from(0));
+        assert_eq!(membership[&NodeId::from(i + 3)], NodeId::from(1));
+    }
+    assert!(condensation.nodes[&NodeId::from(0)]
+        .out_neighbors
+        .contains(&NodeId::from(1)));
+
+    let graph = get_graph(0)?;
+    let (condensation, membership) = graph.get_condensation();
+    assert!(condensation.is_acyclic());
+    assert_eq!(membership.len(), graph.count_nodes());
+    assert_eq!(
+        condensation.count_nodes(),
+        graph.get_strongly_connected_components().len()
+    );
+    Ok(())
+}
+
+// (k, l)-core by repeatedly removing nodes with too few in- or out-neighbors left.
+fn get_naive_dcore(graph: &SimpleDirectedGraph, k: usize, l: usize) -> Vec<NodeId> {
+    let mut core: BTreeSet<NodeId> = graph.nodes.keys().cloned().collect();
+    loop {
+        let removed: Vec<NodeId> = core
+            .iter()
+            .filter(|id| {
+                let node = &graph.nodes[*id];
+                let in_degree = node
+                    .in_neighbors
+                    .iter()
+                    .filter(|x| *x != *id && core.contains(x))
+                    .count();
+                let out_degree = node
+                    .out_neighbors
+                    .iter()
+                    .filter(|x| *x != *id && core.contains(x))
+                    .count();
+                in_degree < k || out_degree < l
+            })
+            .cloned()
+            .collect();
+        if removed.is_empty() {
+            return core.into_iter().collect();
+        }
+        for id in removed {
+            core.remove(&id);
+        }
+    }
+}
+
+#[test]
+fn test_dcores() -> CLQResult<()> {
+    // a complete directed triangle, a node pointing to all of it, and one pointed to by all
+    // of it.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 0),
+        (0, 2),
+        (3, 0),
+        (3, 1),
+        (3, 2),
+        (0, 4),
+        (1, 4),
+        (2, 4),
+    ])?;
+    let dcores = graph.get_dcores();
+    assert_eq!(dcores.out_coreness[&NodeId::from(0)], vec![2, 2, 2]);
+    assert_eq!(dcores.out_coreness[&NodeId::from(3)], vec![2]);
+    assert_eq!(dcores.out_coreness[&NodeId::from(4)], vec![0, 0, 0]);
+    assert_eq!(
+        dcores.get_matrix(),
+        vec![vec![5, 4, 4], vec![4, 3, 3], vec![4, 3, 3]]
+    );
+    assert_eq!(dcores.get_skyline(), vec![(2, 2)]);
+    let ids = |v: Vec<i64>| v.into_iter().map(NodeId::from).collect::<Vec<NodeId>>();
+    assert_eq!(dcores.get_core(2, 2), ids(vec![0, 1, 2]));
+    assert_eq!(dcores.get_core(0, 2), ids(vec![0, 1, 2, 3]));
+    assert_eq!(dcores.get_core(1, 0), ids(vec![0, 1, 2, 4]));
+    assert_eq!(dcores.get_core(3, 0), ids(vec![]));
+    assert_eq!(graph.get_dcore(0, 2), ids(vec![0, 1, 2, 3]));
+
+    // in a directed cycle, every node is in the (1, 1)-core.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0)])?;
+    let dcores = graph.get_dcores();
+    assert_eq!(dcores.get_matrix(), vec![vec![3, 3], vec![3, 3]]);
+    assert_eq!(dcores.get_skyline(), vec![(1, 1)]);
+    Ok(())
+}
+
+#[test]
+fn test_dcores_match_naive_peeling() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..5 {
+        let edges: Vec<(i64, i64)> = (0..300)
+            .map(|_| (rng.gen_range(0..40), rng.gen_range(0..40)))
+            .collect();
+        let graph = SimpleDirectedGraphBuilder {}.from_vector(edges)?;
+        let dcores = graph.get_dcores();
+        let matrix = dcores.get_matrix();
+        for k in 0..=matrix.len() {
+            for l in 0..=12 {
+                let expected = get_naive_dcore(&graph, k, l);
+                assert_eq!(dcores.get_core(k, l), expected);
+                let size = matrix.get(k).and_then(|row| row.get(l)).cloned();
+                assert_eq!(size.unwrap_or(0), expected.len());
+            }
+        }
+        for (k, l) in dcores.get_skyline() {
+            assert!(!dcores.get_core(k, l).is_empty());
+            assert!(dcores.get_core(k + 1, l).is_empty());
+            assert!(dcores.get_core(k, l + 1).is_empty());
+        }
+    }
+    Ok(())
+}
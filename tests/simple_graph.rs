@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate lib_dachshund;
+extern crate rand;
 
 use crate::lib_dachshund::TransformerBase;
 use lib_dachshund::dachshund::algorithms::cnm_communities::CNMCommunities;
@@ -13,19 +14,36 @@ use lib_dachshund::dachshund::algorithms::connected_components::{
 };
 use lib_dachshund::dachshund::algorithms::coreness::averaged_ties_ranking;
 use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::degree_distribution::{
+    get_power_law_fit, DegreeDistribution,
+};
 use lib_dachshund::dachshund::algorithms::k_peaks::KPeaks;
+use lib_dachshund::dachshund::algorithms::label_propagation::LabelPropagation;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::graph_cache::GraphCache;
 use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::input::Input;
-use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::node::{SimpleNeighbors, SimpleNode, NODE_INLINE_EDGES};
+use lib_dachshund::dachshund::ordered_output::OrderedOutput;
+use lib_dachshund::dachshund::output::{check_graph_markers, Output};
 use lib_dachshund::dachshund::simple_transformer::{
-    GraphStatsTransformerBase, SimpleParallelTransformer, SimpleTransformer,
+    GraphStatsTransformerBase, Parallelism, SimpleParallelTransformer, SimpleTransformer,
 };
 use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
-use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::{
+    gen_bridged_triangles_graph, gen_triangle_with_pendant_graph,
+};
+use lib_dachshund::dachshund::transformer_base::resolve_num_threads;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
+use std::sync::mpsc::channel;
 
 fn get_graph(idx: usize) -> CLQResult<SimpleUndirectedGraph> {
     let v = match idx {
@@ -383,9 +401,158 @@ fn test_simple_transformer() {
     let input = Input::string(bytes);
     let mut buffer: Vec<u8> = Vec::new();
     let output = Output::string(&mut buffer);
-    transformer.run(input, output).unwrap();
+    let summary = transformer.run(input, output).unwrap();
     let output_str: String = String::from_utf8(buffer).unwrap();
     assert_eq!(output_str, expected + "\n");
+    assert_eq!(summary.num_graphs, graphs.len());
+    assert_eq!(summary.num_graphs_skipped, 0);
+    assert_eq!(summary.num_rows, text.lines().count());
+    assert_eq!(summary.num_rows_rejected, 0);
+    assert_eq!(summary.num_lines_emitted, graphs.len());
+}
+
+#[test]
+fn test_parse_errors() {
+    let text = "0\t1\t2\n0\t2\tx\n0\t1\n1\t1\t2\n";
+    let mut transformer = SimpleTransformer::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let error = transformer
+        .run(Input::string(text.as_bytes()), Output::string(&mut buffer))
+        .unwrap_err();
+    match &error {
+        CLQError::Line { line_num, error } => {
+            assert_eq!(*line_num, 2);
+            match error.as_ref() {
+                CLQError::FieldParse { field, value, .. } => {
+                    assert_eq!(field, "target_id");
+                    assert_eq!(value, "x");
+                }
+                _ => panic!("unexpected error: {}", error),
+            }
+        }
+        _ => panic!("unexpected error: {}", error),
+    }
+    assert!(error
+        .to_string()
+        .contains("graph_id\\tsource_id\\ttarget_id"));
+
+    // with a rejects stream, bad lines are written to it and skipped.
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut rejects: Vec<u8> = Vec::new();
+    let summary = transformer
+        .run_with_rejects(
+            Input::string(text.as_bytes()),
+            Output::string(&mut buffer),
+            Some(&mut rejects),
+        )
+        .unwrap();
+    assert_eq!(summary.num_rows, 2);
+    assert_eq!(summary.num_rows_rejected, 2);
+    assert_eq!(summary.num_graphs, 2);
+    let rejects_str = String::from_utf8(rejects).unwrap();
+    let rejected: Vec<Vec<&str>> = rejects_str
+        .lines()
+        .map(|x| x.splitn(3, '\t').collect())
+        .collect();
+    assert_eq!(rejected.len(), 2);
+    assert_eq!(rejected[0][0], "2");
+    assert!(rejected[0][1].contains("target_id"));
+    assert_eq!(rejected[1][0], "3");
+    assert!(rejected[1][1].contains("Expected 3 tab-separated fields"));
+    assert!(rejected[1][1].contains("found 2"));
+    assert_eq!(rejected[1][2], "0\t1");
+}
+
+#[test]
+fn test_graph_cache() -> CLQResult<()> {
+    let path = std::env::temp_dir().join(format!("dachshund_cache_{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let run = |text: &str, config_key: &str| -> CLQResult<(String, usize)> {
+        let mut cache = GraphCache::open(&path, config_key)?;
+        let mut buffer: Vec<u8> = Vec::new();
+        let summary = SimpleParallelTransformer::with_threads(2, Parallelism::PerGraph, None)?
+            .run_with_cache(
+                Input::string(text.as_bytes()),
+                Output::string(&mut buffer),
+                None,
+                Some(&mut cache),
+            )?;
+        cache.save()?;
+        assert_eq!(summary.num_graphs, 3);
+        assert_eq!(cache.num_hits + cache.num_misses, 3);
+        Ok((String::from_utf8(buffer)?, summary.num_graphs_cached))
+    };
+    let text = "7\t1\t2\n7\t2\t3\n5\t1\t2\n9\t1\t2\n9\t1\t3\n9\t2\t3\n";
+    let mut expected: Vec<u8> = Vec::new();
+    SimpleTransformer::new().run(
+        Input::string(text.as_bytes()),
+        Output::string(&mut expected),
+    )?;
+    let expected = String::from_utf8(expected)?;
+    let (output, num_cached) = run(text, "a")?;
+    assert_eq!(output, expected);
+    assert_eq!(num_cached, 0);
+    let (output, num_cached) = run(text, "a")?;
+    assert_eq!(output, expected);
+    assert_eq!(num_cached, 3);
+
+    // only graph 5, whose lines changed, is featurized again.
+    let changed_text = text.replace("5\t1\t2\n", "5\t1\t2\n5\t1\t3\n");
+    let mut changed_expected: Vec<u8> = Vec::new();
+    SimpleTransformer::new().run(
+        Input::string(changed_text.as_bytes()),
+        Output::string(&mut changed_expected),
+    )?;
+    let (output, num_cached) = run(&changed_text, "a")?;
+    assert_eq!(output, String::from_utf8(changed_expected)?);
+    assert_eq!(num_cached, 2);
+
+    // a cache saved with other options is discarded.
+    let (_output, num_cached) = run(&changed_text, "b")?;
+    assert_eq!(num_cached, 0);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_graph_markers() -> CLQResult<()> {
+    let text = "7\t1\t2\n7\t2\t3\n5\t1\t2\n9\t1\t2\n9\t1\t3\n9\t2\t3\n";
+    let mut expected: Vec<u8> = Vec::new();
+    SimpleTransformer::new().run(
+        Input::string(text.as_bytes()),
+        Output::string(&mut expected),
+    )?;
+    let expected = String::from_utf8(expected)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = SimpleParallelTransformer::with_threads(2, Parallelism::PerGraph, None)?.run(
+        Input::string(text.as_bytes()),
+        Output::string(&mut buffer).with_graph_markers(),
+    )?;
+    let output = String::from_utf8(buffer)?;
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(check_graph_markers(lines.iter().cloned())?, 3);
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[0], "#begin\t7");
+    assert_eq!(lines[2], "#end\t7\t1");
+    assert_eq!(lines[8], "#end\t9\t1");
+    // markers aside, output is unchanged, and marker rows aren't counted as output.
+    let unmarked: Vec<&str> = lines
+        .iter()
+        .cloned()
+        .filter(|x| !x.starts_with('#'))
+        .collect();
+    assert_eq!(unmarked, expected.lines().collect::<Vec<&str>>());
+    assert_eq!(summary.num_graphs, 3);
+    assert_eq!(summary.num_lines_emitted, 3);
+
+    // output cut short within a graph, or miscounted, is caught.
+    assert!(check_graph_markers(lines[..7].iter().cloned()).is_err());
+    assert!(check_graph_markers(lines[1..].iter().cloned()).is_err());
+    let miscounted = ["#begin\t7", "7\t{}", "7\t{}", "#end\t7\t1"];
+    assert!(check_graph_markers(miscounted).is_err());
+    let unterminated = ["#begin\t7", "7\t{}", "#begin\t5", "5\t{}", "#end\t5\t1"];
+    assert!(check_graph_markers(unterminated).is_err());
+    Ok(())
 }
 
 #[test]
@@ -528,3 +695,355 @@ fn test_k_peaks() {
         true
     );
 }
+
+#[test]
+fn test_stats_quantiles() -> CLQResult<()> {
+    let mut values = vec![5, 1, 4, 2, 3, 6, 7, 8, 9, 10];
+    assert_eq!(
+        SimpleTransformer::compute_quantiles(&mut values, &[0.0, 0.1, 0.5, 0.9, 1.0]),
+        vec![Some(1), Some(1), Some(5), Some(9), Some(10)]
+    );
+    let mut empty: Vec<f64> = Vec::new();
+    assert_eq!(
+        SimpleTransformer::compute_quantiles(&mut empty, &[0.5]),
+        vec![None]
+    );
+
+    // a triangle with a pendant node: degrees 1, 2, 2, 3, clustering 1, 1, 1/3.
+    let graph = gen_triangle_with_pendant_graph()?;
+    let stats: serde_json::Value =
+        serde_json::from_str(&SimpleTransformer::compute_graph_stats_json(&graph))?;
+    assert_eq!(stats["degree_p10"], 1);
+    assert_eq!(stats["degree_p50"], 2);
+    assert_eq!(stats["degree_p90"], 3);
+    assert_eq!(stats["clust_coef_p10"], 0.333);
+    assert_eq!(stats["clust_coef_p50"], 1.0);
+    assert_eq!(stats["clust_coef_p90"], 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_degree_distribution() -> CLQResult<()> {
+    // a triangle with a pendant node: degrees 1, 2, 2, 3.
+    let graph = gen_triangle_with_pendant_graph()?;
+    let histogram: Vec<(usize, usize)> = graph.get_degree_histogram().into_iter().collect();
+    assert_eq!(histogram, vec![(1, 1), (2, 2), (3, 1)]);
+    assert_eq!(
+        graph.get_degree_ccdf(),
+        vec![(1, 1.0), (2, 0.75), (3, 0.25)]
+    );
+    // too few nodes to fit a power law to.
+    assert_eq!(graph.get_degree_power_law_fit(), None);
+    let stats: serde_json::Value =
+        serde_json::from_str(&SimpleTransformer::compute_graph_stats_json(&graph))?;
+    assert_eq!(
+        stats["degree_histogram"],
+        serde_json::json!([[1, 1], [2, 2], [3, 1]])
+    );
+    assert!(stats["degree_power_law_alpha"].is_null());
+
+    // degrees drawn from a discrete power law with exponent 2.5 from degree 5 on, below
+    // which degrees are uniform.
+    let mut rng = StdRng::seed_from_u64(0);
+    let degrees: Vec<usize> = (0..20000)
+        .map(|_| {
+            let u: f64 = rng.gen();
+            if u < 0.5 {
+                1 + (u * 8.0) as usize
+            } else {
+                (4.5 * (2.0_f64 * (1.0 - u)).powf(-1.0 / 1.5) + 0.5).floor() as usize
+            }
+        })
+        .collect();
+    let fit = get_power_law_fit(&degrees).unwrap();
+    // the approximate likelihood is biased for small degrees, so the tail may start later.
+    assert!((5..=10).contains(&fit.x_min));
+    assert!((fit.alpha - 2.5).abs() < 0.1);
+    assert!(fit.ks_statistic < 0.02);
+    Ok(())
+}
+
+#[test]
+fn test_null_model_zscores() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // a ring of 5-cliques, each joined to the next by a single edge: far more clustered and
+    // modular than a random graph with the same degrees.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for c in 0..6 {
+        for i in 0..5 {
+            for j in i + 1..5 {
+                edges.push((5 * c + i, 5 * c + j));
+            }
+        }
+        edges.push((5 * c, (5 * c + 6) % 30));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let zscores = SimpleTransformer::compute_null_model_zscores(&graph, 10, &mut rng);
+    assert!(zscores["clust_coef_zscore"].as_f64().unwrap() > 2.0);
+    assert!(zscores["modularity_zscore"].as_f64().unwrap() > 2.0);
+    // not enough samples for a standard deviation.
+    let zscores = SimpleTransformer::compute_null_model_zscores(&graph, 1, &mut rng);
+    assert!(zscores["clust_coef_zscore"].is_null());
+
+    // complete graphs can't be rewired, so all the random graphs are the same.
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let zscores = SimpleTransformer::compute_null_model_zscores(&graph, 5, &mut rng);
+    assert!(zscores["clust_coef_zscore"].is_null());
+    assert!(zscores["modularity_zscore"].is_null());
+
+    // z-scores are only reported if asked for.
+    let stats: serde_json::Value =
+        serde_json::from_str(&SimpleTransformer::compute_graph_stats_json(&graph))?;
+    assert!(stats.get("clust_coef_zscore").is_none());
+    let stats: serde_json::Value = serde_json::from_str(
+        &SimpleTransformer::compute_graph_stats_json_with_null_models(&graph, false, 3),
+    )?;
+    assert!(stats["modularity_zscore"].is_null());
+    assert_eq!(stats["num_edges"], 10);
+    Ok(())
+}
+
+#[test]
+fn test_label_propagation() -> CLQResult<()> {
+    // two triangles joined by an edge.
+    let graph = gen_bridged_triangles_graph()?;
+    let communities = graph.get_label_propagation_communities(0, 100);
+    // same seed, same communities.
+    assert_eq!(communities, graph.get_label_propagation_communities(0, 100));
+    assert_eq!(
+        communities.values().map(|c| c.len()).sum::<usize>(),
+        graph.nodes.len()
+    );
+    // no iterations: every node is on its own.
+    assert_eq!(graph.get_label_propagation_communities(0, 0).len(), 6);
+
+    // disconnected components never share a label.
+    let graph = get_graph(3)?;
+    let communities = graph.get_label_propagation_communities(42, 100);
+    let components = graph.get_connected_components();
+    assert!(communities.len() >= components.len());
+    for community in communities.values() {
+        assert!(components
+            .iter()
+            .any(|component| community.iter().all(|id| component.contains(id))));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parallel_transformer_per_algorithm() -> CLQResult<()> {
+    // one graph at a time, so the output is in input order.
+    let mut transformer = SimpleParallelTransformer::with_threads(2, "algorithm".parse()?, None)?;
+    let graphs = (0..3)
+        .map(|x| get_graph(x as usize).unwrap())
+        .collect::<Vec<SimpleUndirectedGraph>>();
+    let text = graphs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x.as_input_rows(i))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let expected = graphs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| format!("{}\t{}", i, SimpleTransformer::compute_graph_stats_json(x)))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let input = Input::string(text.as_bytes());
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    transformer.run(input, output)?;
+    assert_eq!(String::from_utf8(buffer)?, expected + "\n");
+
+    assert_eq!(resolve_num_threads(Some("4"))?, Some(4));
+    assert!(resolve_num_threads(Some("four")).is_err());
+    assert!("everything".parse::<Parallelism>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parallel_transformer_ordered_output() -> CLQResult<()> {
+    let mut transformer =
+        SimpleParallelTransformer::with_threads(4, Parallelism::PerGraph, Some(2))?;
+    // graphs of decreasing size, so that later graphs tend to finish first.
+    let graphs = (0..8)
+        .map(|x| SimpleUndirectedGraphBuilder {}.get_complete_graph(30 - 3 * x))
+        .collect::<CLQResult<Vec<SimpleUndirectedGraph>>>()?;
+    let text = graphs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x.as_input_rows(i))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let expected = graphs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| format!("{}\t{}", i, SimpleTransformer::compute_graph_stats_json(x)))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let input = Input::string(text.as_bytes());
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    transformer.run(input, output)?;
+    assert_eq!(String::from_utf8(buffer)?, expected + "\n");
+
+    // output is held back until its turn comes.
+    let ordered_output = OrderedOutput::new(3)?;
+    let seqs: Vec<usize> = (0..3).map(|_| ordered_output.reserve()).collect();
+    assert_eq!(seqs, vec![0, 1, 2]);
+    let (sender, receiver) = channel();
+    ordered_output.send(2, Some("c".to_string()), &sender);
+    ordered_output.send(1, None, &sender);
+    assert!(receiver.try_recv().is_err());
+    ordered_output.send(0, Some("a".to_string()), &sender);
+    let received: Vec<Option<String>> = receiver.try_iter().map(|(line, _)| line).collect();
+    assert_eq!(
+        received,
+        vec![Some("a".to_string()), None, Some("c".to_string())]
+    );
+    assert!(OrderedOutput::new(0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_simple_node_neighbors() -> CLQResult<()> {
+    let ids = |v: &[i64]| {
+        v.iter()
+            .map(|x| NodeId::from(*x))
+            .collect::<SimpleNeighbors>()
+    };
+    let mut node = SimpleNode::new(NodeId::from(0), ids(&[3, 1, 2, 3, 1]));
+    assert_eq!(node.neighbors, ids(&[1, 2, 3]));
+    assert!(node.has_neighbor(NodeId::from(2)));
+    assert!(!node.has_neighbor(NodeId::from(0)));
+    assert!(node.add_neighbor(NodeId::from(0)));
+    assert!(!node.add_neighbor(NodeId::from(2)));
+    assert_eq!(node.neighbors, ids(&[0, 1, 2, 3]));
+    assert!(!node.neighbors.spilled());
+
+    // neighbors built from repeated edges are sorted and deduplicated, and only nodes with
+    // more than NODE_INLINE_EDGES of them spill onto the heap.
+    let n = NODE_INLINE_EDGES as i64 + 1;
+    let mut rows: Vec<(i64, i64)> = (1..=n).map(|x| (0, x)).collect();
+    rows.extend((1..=n).map(|x| (x, 0)));
+    rows.push((1, 2));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+    let hub = graph.get_node(NodeId::from(0));
+    assert_eq!(hub.neighbors, ids(&(1..=n).collect::<Vec<i64>>()));
+    assert!(hub.neighbors.spilled());
+    assert_eq!(graph.get_node(NodeId::from(1)).neighbors, ids(&[0, 2]));
+    assert!(!graph.get_node(NodeId::from(1)).neighbors.spilled());
+    Ok(())
+}
+
+// (graph_id, number of edges) for each line of output, rejected lines, rows and graphs.
+type ParsingThreadsRun = (Vec<(String, serde_json::Value)>, String, usize, usize);
+
+#[test]
+fn test_parsing_threads() -> CLQResult<()> {
+    // graphs spanning several chunks of lines, with bad lines in some of them.
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut lines: Vec<String> = Vec::new();
+    for graph_id in 0..40 {
+        for _ in 0..rng.gen_range(1..200) {
+            let (source_id, target_id) = (rng.gen_range(0..30), rng.gen_range(0..30));
+            lines.push(format!(
+                "{}\t{}\t{}",
+                graph_id * 7 % 40,
+                source_id,
+                target_id
+            ));
+        }
+        if graph_id % 9 == 0 {
+            lines.push(format!("{}\t1", graph_id * 7 % 40));
+        }
+    }
+    let text = lines.join("\n") + "\n";
+    let run = |num_parsing_threads: usize| -> CLQResult<ParsingThreadsRun> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut rejects: Vec<u8> = Vec::new();
+        let summary = SimpleTransformer::new().run_with_parsing_threads(
+            Input::string(text.as_bytes()),
+            Output::string(&mut buffer),
+            Some(&mut rejects),
+            None,
+            num_parsing_threads,
+        )?;
+        // features are summarized by the number of edges, since some (e.g. betweenness)
+        // can differ in their last digit from one run to the next.
+        let output: Vec<(String, serde_json::Value)> = String::from_utf8(buffer)?
+            .lines()
+            .map(|x| {
+                let (graph_id, features) = x.split_once('\t').unwrap();
+                let features: serde_json::Value = serde_json::from_str(features).unwrap();
+                (graph_id.to_owned(), features["num_edges"].clone())
+            })
+            .collect();
+        Ok((
+            output,
+            String::from_utf8(rejects)?,
+            summary.num_rows,
+            summary.num_graphs,
+        ))
+    };
+    let expected = run(0)?;
+    assert_eq!(expected.0.len(), 40);
+    assert_eq!(expected.3, 40);
+    assert_eq!(expected.1.lines().count(), 5);
+    for num_parsing_threads in [1, 3] {
+        assert_eq!(run(num_parsing_threads)?, expected);
+    }
+
+    // without rejects, the first bad line still ends the run.
+    let mut buffer: Vec<u8> = Vec::new();
+    let error = SimpleTransformer::new()
+        .run_with_parsing_threads(
+            Input::string(text.as_bytes()),
+            Output::string(&mut buffer),
+            None,
+            None,
+            2,
+        )
+        .unwrap_err();
+    let first_bad_line = lines.iter().position(|x| x.split('\t').count() != 3);
+    match error {
+        CLQError::Line { line_num, .. } => assert_eq!(Some(line_num - 1), first_bad_line),
+        _ => panic!("unexpected error: {}", error),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_from_sorted_edges() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // edges in either direction, with repeats and a self-loop.
+    let mut edges: Vec<(i64, i64)> = (0..300)
+        .map(|_| (rng.gen_range(0..50), rng.gen_range(0..50)))
+        .collect();
+    edges.push((7, 7));
+    edges.sort_unstable();
+    let graph = SimpleUndirectedGraphBuilder {}.from_sorted_edges(&edges)?;
+    let expected = SimpleUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+    assert_eq!(graph.count_nodes(), expected.count_nodes());
+    assert_eq!(graph.count_edges(), expected.count_edges());
+    for id in expected.get_ids_iter() {
+        assert_eq!(
+            graph.get_node(*id).neighbors,
+            expected.get_node(*id).neighbors
+        );
+    }
+    assert_eq!(graph.ids.len(), graph.count_nodes());
+
+    edges.swap(0, 1);
+    edges.swap(10, 20);
+    assert!(SimpleUndirectedGraphBuilder {}
+        .from_sorted_edges(&edges)
+        .is_err());
+    assert_eq!(
+        SimpleUndirectedGraphBuilder {}
+            .from_sorted_edges(&[])?
+            .count_nodes(),
+        0
+    );
+    Ok(())
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::algorithms::spanning_tree::UniformSpanningTree;
+use lib_dachshund::dachshund::algorithms::sparsification::Sparsification;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::gen_planted_clique_edges;
+use nalgebra::DMatrix;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_effective_resistance() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a 6-cycle, with a tail and a separate edge.
+    let mut edges: Vec<(i64, i64)> = (0..6).map(|i| (i, (i + 1) % 6)).collect();
+    edges.push((5, 6));
+    edges.push((7, 8));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    // i and j hops apart on the cycle are joined by parallel paths of i and 6 - i edges.
+    assert!((graph.get_effective_resistance(id(0), id(1)) - 5.0 / 6.0).abs() < 1e-6);
+    assert!((graph.get_effective_resistance(id(0), id(3)) - 1.5).abs() < 1e-6);
+    assert!((graph.get_effective_resistance(id(0), id(6)) - 11.0 / 6.0).abs() < 1e-6);
+    assert_eq!(graph.get_effective_resistance(id(2), id(2)), 0.0);
+    assert_eq!(graph.get_effective_resistance(id(0), id(7)), f64::INFINITY);
+
+    let resistances = graph.get_effective_resistances(400);
+    assert_eq!(resistances.len(), 8);
+    for (a, _b, resistance) in resistances {
+        let expected = if a.value() >= 5 { 1.0 } else { 5.0 / 6.0 };
+        assert!((resistance - expected).abs() < 0.25 * expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_spectral_sparsifier() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..40).collect();
+    let graph = SimpleUndirectedGraphBuilder {}
+        .from_vector(gen_planted_clique_edges(200, 0.5, &clique, &mut rng))?;
+    let edges = graph.get_spectral_sparsifier_edges_with_rng(1.0, &mut rng)?;
+    assert!(edges.len() < graph.count_edges());
+
+    // the weighted Laplacian of the sparsifier is close to the graph's, on random vectors.
+    let (laplacian, ids) = graph.get_laplacian_matrix();
+    let position = |id: NodeId| ids.iter().position(|x| *x == id).unwrap();
+    let mut sparsified = DMatrix::<f64>::zeros(ids.len(), ids.len());
+    for (a, b, weight) in &edges {
+        let (i, j) = (position(*a), position(*b));
+        sparsified[(i, i)] += weight;
+        sparsified[(j, j)] += weight;
+        sparsified[(i, j)] -= weight;
+        sparsified[(j, i)] -= weight;
+    }
+    for _ in 0..20 {
+        let x = nalgebra::DVector::<f64>::from_fn(ids.len(), |_, _| rng.gen::<f64>());
+        let expected = (x.transpose() * &laplacian * &x)[(0, 0)];
+        let actual = (x.transpose() * &sparsified * &x)[(0, 0)];
+        assert!((actual - expected).abs() < 0.5 * expected);
+    }
+
+    let sparsifier = graph.get_spectral_sparsifier(1.0)?;
+    assert_eq!(sparsifier.count_nodes(), graph.count_nodes());
+    assert!(sparsifier.count_edges() < graph.count_edges());
+    assert!(graph.get_spectral_sparsifier(0.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_spanner() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let clique: Vec<i64> = (0..30).collect();
+    let graph = SimpleUndirectedGraphBuilder {}
+        .from_vector(gen_planted_clique_edges(100, 0.2, &clique, &mut rng))?;
+    let spanner = graph.get_spanner(3.0)?;
+    assert_eq!(spanner.count_nodes(), graph.count_nodes());
+    assert!(spanner.count_edges() < graph.count_edges());
+    for id in graph.get_ordered_node_ids() {
+        let (distances, _parents) = graph.get_shortest_paths(id, &None);
+        let (spanner_distances, _parents) = spanner.get_shortest_paths(id, &None);
+        for (other, distance) in distances {
+            if let Some(distance) = distance {
+                assert!(spanner_distances[&other].unwrap() <= 3 * distance);
+            }
+        }
+    }
+
+    // a stretch of 1 keeps every edge.
+    assert_eq!(graph.get_spanner(1.0)?.count_edges(), graph.count_edges());
+    assert!(graph.get_spanner(0.5).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_uniform_spanning_forest() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a square with a diagonal has 8 spanning trees (all but the 2 triangles among the
+    // C(5, 3) = 10 sets of 3 edges), each of which should be drawn about as often.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 2),
+    ])?;
+    let mut rng = StdRng::seed_from_u64(0);
+    let num_samples = 8000;
+    let mut counts: HashMap<Vec<(NodeId, NodeId)>, usize> = HashMap::new();
+    for _ in 0..num_samples {
+        let mut tree = graph.get_uniform_spanning_forest_with_rng(&mut rng);
+        assert_eq!(tree.len(), 3);
+        let subgraph = graph.get_subgraph_with_edges(&tree);
+        let (distances, _) = subgraph.get_shortest_paths(id(0), &None);
+        assert!(distances.values().all(|x| x.is_some()));
+        tree.sort();
+        *counts.entry(tree).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 8);
+    for count in counts.values() {
+        assert!((*count as f64 - 1000.0).abs() < 150.0);
+    }
+
+    // one tree per connected component, and none for isolated nodes' self-loops.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (5, 6),
+        (6, 7),
+        (9, 9),
+    ])?;
+    let forest = graph.get_uniform_spanning_forest(1);
+    assert_eq!(forest.len(), graph.count_nodes() - 3);
+    assert!(forest.iter().all(|(a, b)| a < b));
+    assert_eq!(forest, graph.get_uniform_spanning_forest(1));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::stochastic_block_model::StochasticBlockModel;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// num_groups groups of group_size nodes, with ties within groups present with probability
+// p_in, and between groups with probability p_out.
+fn get_planted_partition(
+    num_groups: i64,
+    group_size: i64,
+    p_in: f64,
+    p_out: f64,
+    rng: &mut StdRng,
+) -> Vec<(i64, i64)> {
+    let n = num_groups * group_size;
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            let p = if i / group_size == j / group_size {
+                p_in
+            } else {
+                p_out
+            };
+            if rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges
+}
+
+#[test]
+fn test_sbm_planted_partition() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let edges = get_planted_partition(3, 20, 0.6, 0.02, &mut rng);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let model = graph.get_sbm_blocks_with_rng(3, &mut rng)?;
+    assert_eq!(model.block_sizes, vec![20, 20, 20]);
+    // each group ends up in a block of its own.
+    for g in 0..3 {
+        let block = model.blocks[&NodeId::from(g * 20)];
+        assert!((g * 20..(g + 1) * 20).all(|i| model.blocks[&NodeId::from(i)] == block));
+    }
+
+    // the planted number of blocks has the shortest description.
+    let description_lengths: Vec<f64> = (1..=6)
+        .map(|b| {
+            Ok(graph
+                .get_sbm_blocks_with_rng(b, &mut rng)?
+                .description_length)
+        })
+        .collect::<CLQResult<Vec<f64>>>()?;
+    for (b, description_length) in description_lengths.iter().enumerate() {
+        if b != 2 {
+            assert!(description_lengths[2] < *description_length);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sbm_errors() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2)])?;
+    assert!(graph.get_sbm_blocks(0).is_err());
+    assert!(graph.get_sbm_blocks(4).is_err());
+    let model = graph.get_sbm_blocks(3)?;
+    assert_eq!(model.block_sizes, vec![1, 1, 1]);
+
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 0)])?;
+    assert!(graph.get_sbm_blocks(1).is_err());
+    Ok(())
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::streaming_triangles::TriestEstimator;
+use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
+use lib_dachshund::dachshund::algorithms::triangles::Triangles;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::streaming_triangles_transformer::StreamingTrianglesTransformer;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use rand::prelude::*;
+
+fn gen_edges(num_nodes: i64, p: f64, rng: &mut StdRng) -> Vec<(i64, i64)> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..num_nodes {
+        for j in i + 1..num_nodes {
+            if rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges.shuffle(rng);
+    edges
+}
+
+fn estimate(edges: &[(i64, i64)], reservoir_size: usize, seed: u64) -> CLQResult<TriestEstimator> {
+    let mut estimator = TriestEstimator::new_with_rng(reservoir_size, StdRng::seed_from_u64(seed))?;
+    for (a, b) in edges {
+        estimator.add_edge(NodeId::from(*a), NodeId::from(*b));
+    }
+    Ok(estimator)
+}
+
+#[test]
+fn test_triest_exact() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let edges = gen_edges(60, 0.2, &mut rng);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+    let counts = graph.get_triangle_counts();
+    let num_triangles: usize = counts.values().sum::<usize>() / 3;
+    assert!(num_triangles > 0);
+
+    // with all edges in the reservoir, estimates are exact.
+    let mut estimator = estimate(&edges, edges.len(), 0)?;
+    assert!(estimator.is_exact());
+    assert_eq!(estimator.count_edges(), edges.len());
+    assert_eq!(estimator.get_triangle_estimate(), num_triangles as f64);
+    for (id, count) in &counts {
+        assert_eq!(estimator.get_local_triangle_estimate(*id), *count as f64);
+    }
+    assert!((estimator.get_transitivity_estimate() - graph.get_transitivity()).abs() < 1e-9);
+
+    // self-loops are left out.
+    estimator.add_edge(NodeId::from(0), NodeId::from(0));
+    assert_eq!(estimator.count_edges(), edges.len());
+    estimator.reset();
+    assert_eq!(estimator.count_edges(), 0);
+    assert_eq!(estimator.get_triangle_estimate(), 0.0);
+    assert!(estimator.get_transitivity_estimate().is_nan());
+
+    assert!(TriestEstimator::new(5).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_triest_estimates() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let edges = gen_edges(80, 0.3, &mut rng);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+    let num_triangles = graph.get_triangle_counts().values().sum::<usize>() as f64 / 3.0;
+
+    // estimates from a quarter of the edges are unbiased, so average out to the count.
+    let reservoir_size = edges.len() / 4;
+    let num_runs = 40;
+    let mut total: f64 = 0.0;
+    for seed in 0..num_runs {
+        let estimator = estimate(&edges, reservoir_size, seed)?;
+        assert!(!estimator.is_exact());
+        total += estimator.get_triangle_estimate();
+    }
+    let mean = total / num_runs as f64;
+    assert!((mean - num_triangles).abs() < 0.05 * num_triangles);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_triangles_transformer() -> CLQResult<()> {
+    // a 4-clique, and a triangle with a tail.
+    let text = "a\t1\t2\na\t1\t3\na\t1\t4\na\t2\t3\na\t2\t4\na\t3\t4\n\
+                b\t1\t2\nb\t2\t3\nb\t3\t1\nb\t3\t4\n";
+    let mut transformer = StreamingTrianglesTransformer::with_seed(6, 0)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    assert_eq!(summary.num_graphs, 2);
+    let lines: Vec<(String, serde_json::Value)> = String::from_utf8(buffer)?
+        .lines()
+        .map(|x| {
+            let (graph_id, stats) = x.split_once('\t').unwrap();
+            (graph_id.to_owned(), serde_json::from_str(stats).unwrap())
+        })
+        .collect();
+    assert_eq!(lines[0].0, "a");
+    assert_eq!(lines[0].1["exact"], true);
+    assert_eq!(lines[0].1["num_edges"], 6);
+    assert_eq!(lines[0].1["num_triangles"], 4.0);
+    assert_eq!(lines[0].1["transitivity"], 1.0);
+    assert_eq!(lines[1].0, "b");
+    assert_eq!(lines[1].1["num_triangles"], 1.0);
+    assert_eq!(lines[1].1["transitivity"], 0.6);
+    Ok(())
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate lib_dachshund;
+use fxhash::FxHashSet;
+use lib_dachshund::dachshund::algorithms::subgraphs::Subgraphs;
+use lib_dachshund::dachshund::csr_graph::CsrGraph;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::{BipartiteGraphBase, GraphBase};
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::{DirectedNodeBase, NodeBase, WeightedNodeEdgeBase};
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::{gen_authorship_graph, gen_square_with_pendant_graph};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+fn get_ids(ids: Vec<i64>) -> FxHashSet<NodeId> {
+    ids.into_iter().map(NodeId::from).collect()
+}
+
+#[test]
+fn test_induced_subgraph() -> CLQResult<()> {
+    let graph = gen_square_with_pendant_graph()?;
+    let subgraph = graph.subgraph(&get_ids(vec![0, 1, 3, 4, 10]));
+    assert_eq!(subgraph.count_nodes(), 4);
+    assert_eq!(subgraph.count_edges(), 3);
+    assert_eq!(subgraph.get_node_degree(NodeId::from(0)), 1);
+    assert_eq!(subgraph.get_node_degree(NodeId::from(3)), 2);
+    assert!(!subgraph.has_node(NodeId::from(2)));
+    assert_eq!(subgraph.ids.len(), 4);
+    // the original graph is left as it was.
+    assert_eq!(graph.count_nodes(), 5);
+    assert!(graph.subgraph(&FxHashSet::default()).ids.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_ego_net() -> CLQResult<()> {
+    let graph = gen_square_with_pendant_graph()?;
+    let ego_net = graph.ego_net(NodeId::from(1), 1);
+    assert_eq!(
+        ego_net.get_ordered_node_ids(),
+        vec![0, 1, 3]
+            .into_iter()
+            .map(NodeId::from)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(ego_net.count_edges(), 2);
+
+    let ego_net = graph.ego_net(NodeId::from(1), 2);
+    assert_eq!(ego_net.count_nodes(), 5);
+    assert_eq!(ego_net.count_edges(), 5);
+
+    assert_eq!(graph.ego_net(NodeId::from(1), 0).count_nodes(), 1);
+    assert_eq!(graph.ego_net(NodeId::from(4), 10).count_nodes(), 5);
+    assert_eq!(graph.ego_net(NodeId::from(10), 1).count_nodes(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_subgraphs_of_other_graphs() -> CLQResult<()> {
+    let ids = get_ids(vec![1, 2, 3]);
+
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3), (3, 4)])?;
+    let subgraph = directed.subgraph(&ids);
+    assert_eq!(subgraph.count_edges(), 2);
+    let node = subgraph.get_node(NodeId::from(3));
+    assert_eq!((node.get_in_degree(), node.get_out_degree()), (1, 0));
+
+    let weighted = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (1, 2, 0.5),
+        (2, 3, 2.0),
+        (3, 4, 1.0),
+    ])?;
+    let subgraph = weighted.subgraph(&ids);
+    assert_eq!(subgraph.count_edges(), 2);
+    let weights: Vec<f64> = subgraph
+        .get_node(NodeId::from(3))
+        .get_edges()
+        .map(|e| e.get_weight())
+        .collect();
+    assert_eq!(weights, vec![2.0]);
+
+    let csr = CsrGraph::from_graph(&gen_square_with_pendant_graph()?);
+    let subgraph = csr.ego_net(NodeId::from(3), 1);
+    assert_eq!(subgraph.count_nodes(), 4);
+    assert_eq!(subgraph.count_edges(), 3);
+    assert_eq!(
+        subgraph.get_node(NodeId::from(3)).get_neighbors(),
+        vec![1, 2, 4]
+            .into_iter()
+            .map(NodeId::from)
+            .collect::<Vec<_>>()
+            .as_slice()
+    );
+
+    let subgraph = gen_authorship_graph()?.subgraph(&get_ids(vec![1, 3, 10, 12]));
+    assert_eq!(subgraph.get_core_ids(), &[NodeId::from(1), NodeId::from(3)]);
+    assert_eq!(
+        subgraph.get_non_core_ids(),
+        &[NodeId::from(10), NodeId::from(12)]
+    );
+    assert_eq!(subgraph.count_edges(), 2);
+    Ok(())
+}
@@ -6,17 +6,25 @@
  */
 extern crate lib_dachshund;
 
+use lib_dachshund::dachshund::batch_transformer::BatchTransformer;
 use lib_dachshund::dachshund::candidate::Candidate;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
-use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::id_types::{EdgeTypeId, GraphId, NodeId};
+use lib_dachshund::dachshund::input::Input;
 use lib_dachshund::dachshund::line_processor::LineProcessorBase;
+use lib_dachshund::dachshund::node::{NodeBase, TypedNodeBase};
+use lib_dachshund::dachshund::output::Output;
 use lib_dachshund::dachshund::row::{CliqueRow, EdgeRow};
+use lib_dachshund::dachshund::search_problem::SearchProblem;
 use lib_dachshund::dachshund::test_utils::{
     assert_nodes_have_ids, gen_single_clique, gen_test_transformer, gen_test_typespec,
     process_raw_vector,
 };
 use lib_dachshund::dachshund::transformer::Transformer;
-use lib_dachshund::dachshund::typed_graph::TypedGraph;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::typed_graph::{LabeledGraph, TypedGraph};
+use roaring::RoaringBitmap;
+use std::collections::HashMap;
 use std::sync::mpsc::channel;
 
 #[cfg(test)]
@@ -62,6 +70,20 @@ fn test_process_typespec() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_invalid_edge_type_weights() -> CLQResult<()> {
+    let typespecs = [
+        r#"[["author", "cited", "article", 0]]"#,
+        r#"[["author", "cited", "article", "often"]]"#,
+        r#"[["author", "cited", "article", 0.5], ["author", "cited", "journal"]]"#,
+    ];
+    for typespec_str in typespecs {
+        let typespec = Transformer::parse_typespec(typespec_str)?;
+        assert!(gen_test_transformer(typespec, "author".to_string()).is_err());
+    }
+    Ok(())
+}
+
 #[test]
 fn test_process_single_line() -> CLQResult<()> {
     let ts = gen_test_typespec();
@@ -296,3 +318,248 @@ fn test_process_another_small_clique_with_two_kinds_of_rows() -> CLQResult<()> {
         },
     )
 }
+
+#[test]
+fn test_non_core_local_thresh() -> CLQResult<()> {
+    let thresh = |non_core_type: &str, x: f32| HashMap::from([(non_core_type.to_string(), x)]);
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?
+        .with_non_core_local_thresh(thresh("journal", 0.5))?;
+    let journal_type_id = transformer.non_core_type_ids.require("journal")?.value();
+    assert_eq!(
+        transformer.search_problem.non_core_local_thresh[&journal_type_id],
+        0.5
+    );
+
+    for (non_core_type, x) in [("author", 0.5), ("book", 0.5), ("journal", 1.5)] {
+        let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+        assert!(transformer
+            .with_non_core_local_thresh(thresh(non_core_type, x))
+            .is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_search_problem_validation() -> CLQResult<()> {
+    let valid = SearchProblem::new(20, 1.0, Some(1.0), Some(0.5), 20, 10, 3, 0);
+    valid.validate(false)?;
+
+    // no epochs is only allowed in debug mode.
+    let no_epochs = SearchProblem::new(20, 1.0, None, None, 20, 0, 3, 0);
+    no_epochs.validate(true)?;
+    assert!(matches!(
+        no_epochs.validate(false),
+        Err(CLQError::InvalidSearchProblem(_))
+    ));
+
+    // every problem is reported.
+    let invalid = SearchProblem::new(0, 1.0, Some(-0.1), Some(1.5), 0, 10, 3, 0);
+    match invalid.validate(false) {
+        Err(CLQError::InvalidSearchProblem(msg)) => {
+            assert!(msg.contains("beam_size"));
+            assert!(msg.contains("num_to_search"));
+            assert!(msg.contains("global_thresh must be between 0 and 1, got -0.1"));
+            assert!(msg.contains("local_thresh must be between 0 and 1, got 1.5"));
+            assert!(!msg.contains("epochs"));
+        }
+        _ => panic!("expected an invalid search problem"),
+    }
+
+    // the transformer refuses to be built from an invalid search problem.
+    let transformer = Transformer::new(
+        gen_test_typespec(),
+        20,
+        1.0,
+        Some(1.0),
+        Some(2.0),
+        20,
+        10,
+        3,
+        false,
+        0,
+        "author".to_string(),
+        false,
+    );
+    assert!(matches!(
+        transformer,
+        Err(CLQError::InvalidSearchProblem(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_dry_run() -> CLQResult<()> {
+    let ts = gen_test_typespec();
+    let transformer = gen_test_transformer(ts, "author".to_string())?;
+    let raw = "0\t1\t2\tauthor\tpublished_at\tjournal\n\
+               0\t1\t3\tauthor\tpublished_at\tconference\n\
+               0\t1\tauthor\t\t\t\n\
+               1\t1\t2\tauthor\tpublished_at\tjournal\n\
+               1\t1\t2\tauthor\tcited\tjournal\n\
+               0\tx\t2\tauthor\tpublished_at\tjournal\n\
+               0\t1\t2\n\
+               0\t1\t4\tauthor\tpublished_at\tjournal\n\
+               2\t1\t2\tauthor\tpublished_at\tjournal\n";
+    let report = transformer.dry_run(Input::string(raw.as_bytes()), 8);
+    assert_eq!(report.num_lines, 8);
+    assert_eq!(report.num_rows, 5);
+    let rows_per_graph: Vec<(i64, usize)> = report
+        .rows_per_graph
+        .iter()
+        .map(|(graph_id, count)| (graph_id.value(), *count))
+        .collect();
+    assert_eq!(rows_per_graph, vec![(0, 3), (1, 1), (0, 1)]);
+    assert_eq!(report.rows_per_type["edge author published_at journal"], 3);
+    assert_eq!(
+        report.rows_per_type["edge author published_at conference"],
+        1
+    );
+    assert_eq!(report.rows_per_type["clique author"], 1);
+    let issue_lines: Vec<usize> = report.issues.iter().map(|(line, _)| *line).collect();
+    assert_eq!(issue_lines, vec![5, 6, 7, 8]);
+    assert!(report.issues[0].1.contains("cited"));
+    assert!(report.issues[3].1.contains("not contiguous"));
+    Ok(())
+}
+
+#[test]
+fn test_comments_and_blank_lines() -> CLQResult<()> {
+    let ts = gen_test_typespec();
+    let mut transformer = gen_test_transformer(ts, "author".to_string())?;
+    let raw = "# graph_id\tcore_id\tnon_core_id\tcore_type\tedge_type\tnon_core_type\n\
+               0\t1\t2\tauthor\tpublished_at\tjournal\n\
+               \n\
+               0\t1\t3\tauthor\tpublished_at\tconference\n\
+               \t\n\
+               # end of graph 0\n";
+    let report = transformer.dry_run(Input::string(raw.as_bytes()), 10);
+    assert_eq!(report.num_lines, 6);
+    assert_eq!(report.num_lines_skipped, 4);
+    assert_eq!(report.num_rows, 2);
+    assert!(report.issues.is_empty());
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = transformer.run(Input::string(raw.as_bytes()), Output::string(&mut buffer))?;
+    assert_eq!(summary.num_lines_skipped, 4);
+    assert_eq!(summary.num_rows, 2);
+    assert_eq!(summary.num_rows_rejected, 0);
+    assert_eq!(summary.num_graphs, 1);
+    Ok(())
+}
+
+#[test]
+fn test_batch_typespecs() -> CLQResult<()> {
+    let typespecs = BatchTransformer::parse_typespecs(
+        r#"{"published": [["author", "published_at", "journal"]],
+            "cited": [["author", "cited", "journal", 0.5]]}"#,
+    )?;
+    let names: Vec<&str> = typespecs.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["cited", "published"]);
+    let transformers = typespecs
+        .into_iter()
+        .map(|(name, typespec)| Ok((name, gen_test_transformer(typespec, "author".to_string())?)))
+        .collect::<CLQResult<Vec<(String, Transformer)>>>()?;
+    let mut transformer = BatchTransformer::new(transformers)?;
+
+    // authors 1 and 2 published in journals 3 and 4, and cited journals 5 and 6.
+    let raw = "0\t1\t3\tauthor\tpublished_at\tjournal\n\
+               0\t1\t4\tauthor\tpublished_at\tjournal\n\
+               0\t2\t3\tauthor\tpublished_at\tjournal\n\
+               0\t2\t4\tauthor\tpublished_at\tjournal\n\
+               0\t1\t5\tauthor\tcited\tjournal\n\
+               0\t1\t6\tauthor\tcited\tjournal\n\
+               0\t2\t5\tauthor\tcited\tjournal\n\
+               0\t2\t6\tauthor\tcited\tjournal\n\
+               1\t1\t3\tauthor\tpublished_at\tjournal\n";
+    let report = transformer.dry_run(Input::string(raw.as_bytes()), 10);
+    assert_eq!(report.num_rows, 9);
+    assert!(report.issues.is_empty());
+    assert_eq!(
+        report.rows_per_type["published: edge author published_at journal"],
+        5
+    );
+    assert_eq!(report.rows_per_type["cited: edge author cited journal"], 4);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let summary = transformer.run(Input::string(raw.as_bytes()), Output::string(&mut buffer))?;
+    assert_eq!(summary.num_graphs, 2);
+    assert_eq!(summary.num_lines_emitted, 3);
+    let output = String::from_utf8(buffer)?;
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_unstable();
+    assert!(lines[0].starts_with("cited\t0\t"));
+    assert!(lines[0].contains("[5,6]"));
+    assert!(lines[1].starts_with("published\t0\t"));
+    assert!(lines[1].contains("[3,4]"));
+    assert!(lines[2].starts_with("published\t1\t"));
+
+    // lines must be valid for at least one typespec.
+    let line_processor = transformer.get_line_processor();
+    assert!(line_processor
+        .process_line("0\t1\t3\tauthor\treviewed\tjournal".to_string())
+        .is_err());
+
+    let duplicates = vec![
+        (
+            "a".to_string(),
+            gen_test_transformer(gen_test_typespec(), "author".into())?,
+        ),
+        (
+            "a".to_string(),
+            gen_test_transformer(gen_test_typespec(), "author".into())?,
+        ),
+    ];
+    assert!(BatchTransformer::new(duplicates).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_count_ties_by_type() -> CLQResult<()> {
+    let typespec = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let transformer = gen_test_transformer(typespec, "author".to_string())?;
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "0\t1\t4\tauthor\tpublished\tarticle".into(),
+        "0\t1\t4\tauthor\tcited\tarticle".into(),
+        "0\t1\t5\tauthor\tcited\tarticle".into(),
+        "0\t1\t5\tauthor\tcited\tarticle".into(),
+        "0\t2\t3\tauthor\tcited\tarticle".into(),
+    ];
+    let rows = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(GraphId::from(0), rows)?;
+    let edge_type_id = |name: &str| {
+        EdgeTypeId::from(
+            transformer
+                .edge_types
+                .iter()
+                .position(|x| x == name)
+                .unwrap(),
+        )
+    };
+    let (published, cited) = (edge_type_id("published"), edge_type_id("cited"));
+    let ids: RoaringBitmap = [3, 4]
+        .iter()
+        .map(|label: &i64| graph.get_node_by_label(NodeId::from(*label)).node_id)
+        .collect();
+
+    let node = graph.get_node_by_label(NodeId::from(1));
+    assert_eq!(node.count_neighbors_of_type(published), 2);
+    // the repeated tie to article 5 counts once.
+    assert_eq!(node.count_neighbors_of_type(cited), 2);
+    assert_eq!(node.count_ties_with_ids_of_type(&ids, published), 2);
+    assert_eq!(node.count_ties_with_ids_of_type(&ids, cited), 1);
+    assert_eq!(node.count_ties_with_ids(&ids), 3);
+    let by_type: HashMap<EdgeTypeId, usize> = node.count_ties_with_ids_by_type(&ids);
+    assert_eq!(
+        by_type,
+        vec![(published, 2), (cited, 1)].into_iter().collect()
+    );
+
+    let node = graph.get_node_by_label(NodeId::from(2));
+    assert_eq!(node.count_neighbors_of_type(published), 0);
+    assert_eq!(node.count_ties_with_ids_by_type(&ids).len(), 1);
+    Ok(())
+}
@@ -4,22 +4,23 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-#![feature(test)]
-
 extern crate lib_dachshund;
-extern crate test;
 
+use lib_dachshund::dachshund::algorithms::bitset_adjacency::BitsetAdjacency;
 use lib_dachshund::dachshund::algorithms::clustering::Clustering;
 use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
+use lib_dachshund::dachshund::algorithms::triangles::Triangles;
 use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use lib_dachshund::dachshund::simple_undirected_graph_builder::{
     SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
 };
-
-use test::Bencher;
+use lib_dachshund::dachshund::test_utils::gen_planted_clique_undirected_graph;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 // The complete graph on 4 nodes with one edge removed.
 // This is the minimal counterexample where T(G) != C(G).
@@ -44,17 +45,6 @@ fn test_triangle_count() -> CLQResult<()> {
     Ok(())
 }
 
-#[bench]
-fn bench_triangle_count(b: &mut Bencher) -> CLQResult<()> {
-    let k100 = SimpleUndirectedGraphBuilder {}.get_complete_graph(100)?;
-    b.iter(|| {
-        for node_id in k100.nodes.keys() {
-            k100.triangle_count(*node_id);
-        }
-    });
-    Ok(())
-}
-
 #[test]
 fn test_clustering_coefficient() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
@@ -90,6 +80,42 @@ fn test_approx_avg_clustering() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_approx_clustering_num_samples() {
+    // matches the ~26,000 samples quoted by Schank & Wagner for 1% error w/ 99% confidence.
+    assert_eq!(
+        26492,
+        SimpleUndirectedGraph::get_approx_clustering_num_samples(0.01, 0.01)
+    );
+    assert_eq!(
+        3,
+        SimpleUndirectedGraph::get_approx_clustering_num_samples(0.5, 0.5)
+    );
+}
+
+#[test]
+fn test_avg_clustering_with_accuracy() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    // few wedges: computed exactly.
+    let almost_k4 = &get_almost_k4_graph()?;
+    assert_eq!(
+        almost_k4.get_avg_clustering(),
+        almost_k4.get_avg_clustering_with_accuracy(0.01, 0.01, &mut rng)
+    );
+
+    // many more wedges than samples: estimated by sampling.
+    let k20 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(20)?;
+    assert_eq!(
+        1.0,
+        k20.get_avg_clustering_with_accuracy(0.5, 0.5, &mut rng)
+    );
+
+    // no node has two neighbors.
+    let edge = &SimpleUndirectedGraphBuilder {}.get_path_graph(1)?;
+    assert!(edge.get_approx_avg_clustering(10).is_nan());
+    Ok(())
+}
+
 #[test]
 fn test_approx_transitivity() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
@@ -103,3 +129,83 @@ fn test_approx_transitivity() -> CLQResult<()> {
     assert!((0.75 - approx_transitivity).abs() <= 0.01);
     Ok(())
 }
+
+#[test]
+fn test_triangle_counts() -> CLQResult<()> {
+    let k5 = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let counts = k5.get_triangle_counts();
+    assert_eq!(counts.len(), 5);
+    assert!(counts.values().all(|count| *count == 6));
+    assert_eq!(k5.get_triangle_count(), 10);
+
+    let almost_k4 = get_almost_k4_graph()?;
+    let counts = almost_k4.get_triangle_counts();
+    for i in 0..4 {
+        let id = NodeId::from(i as i64);
+        assert_eq!(counts[&id], almost_k4.triangle_count(id));
+    }
+    assert_eq!(almost_k4.get_triangle_count(), 2);
+
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    assert_eq!(path.get_triangle_count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_approx_triangle_counts() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let k4 = SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
+    assert_eq!(k4.get_approx_triangle_count(10, &mut rng), 4.0);
+
+    // every node of k20 has 171 wedges, more than are sampled, all of them closed.
+    let k20 = SimpleUndirectedGraphBuilder {}.get_complete_graph(20)?;
+    let counts = k20.get_approx_triangle_counts(100, &mut rng);
+    assert!(counts.values().all(|count| *count == 171.0));
+
+    // nodes with few wedges are counted exactly.
+    let almost_k4 = get_almost_k4_graph()?;
+    let counts = almost_k4.get_approx_triangle_counts(100, &mut rng);
+    assert_eq!(counts[&NodeId::from(0)], 2.0);
+    assert_eq!(counts[&NodeId::from(3)], 1.0);
+
+    let approx_count = almost_k4.get_approx_triangle_count(100000, &mut rng);
+    assert!((2.0 - approx_count).abs() <= 0.05);
+
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(1)?;
+    assert_eq!(path.get_approx_triangle_count(10, &mut rng), 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_bitset_adjacency() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let clique: Vec<i64> = (0..20).collect();
+    let graph = gen_planted_clique_undirected_graph(300, 0.2, &clique, &mut rng)?;
+    let adjacency = BitsetAdjacency::from_dense_graph(&graph).unwrap();
+    assert_eq!(adjacency.count_nodes(), graph.count_nodes());
+    for (i, id) in adjacency.get_ids().iter().enumerate() {
+        assert_eq!(adjacency.degree(i), graph.get_node_degree(*id));
+        assert_eq!(adjacency.count_triangles(i), graph.triangle_count(*id));
+        assert_eq!(
+            adjacency.get_clustering_coefficient(i),
+            graph.get_clustering_coefficient(*id)
+        );
+    }
+    let ids: Vec<NodeId> = clique.iter().map(|x| NodeId::from(*x)).collect();
+    assert!(adjacency.is_clique(&ids));
+    let not_clique: Vec<NodeId> = (0..300)
+        .map(NodeId::from)
+        .filter(|id| graph.has_node(*id))
+        .take(25)
+        .collect();
+    assert!(!adjacency.is_clique(&not_clique));
+    assert!(!adjacency.is_clique(&[NodeId::from(0), NodeId::from(1000)]));
+
+    // sparse graphs, and graphs with self-loops, get no bitset.
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(200)?;
+    assert!(BitsetAdjacency::from_dense_graph(&cycle).is_none());
+    assert!(BitsetAdjacency::from_graph(&cycle).is_some());
+    let looped = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 2)])?;
+    assert!(BitsetAdjacency::from_graph(&looped).is_none());
+    Ok(())
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::algorithms::union_find::UnionFind;
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::assert_node_sets_have_labels;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use rand::prelude::*;
+use std::collections::BTreeSet;
+
+#[test]
+fn test_union_find() {
+    let mut components = UnionFind::new();
+    let id = NodeId::from;
+    assert!(components.add_edge(id(0), id(1)));
+    assert!(components.add_edge(id(2), id(3)));
+    assert!(!components.add_edge(id(1), id(0)));
+    components.add_node(id(4));
+    assert_eq!(components.count_nodes(), 5);
+    assert_eq!(components.count_components(), 3);
+    assert!(components.is_connected(id(0), id(1)));
+    assert!(!components.is_connected(id(0), id(2)));
+    assert!(!components.is_connected(id(0), id(5)));
+    assert!(components.add_edge(id(1), id(3)));
+    assert!(components.is_connected(id(0), id(2)));
+    assert_eq!(components.get_component_size(id(2)), 4);
+    assert_eq!(components.get_component_size(id(5)), 0);
+    assert_eq!(
+        components.get_representative(id(0)),
+        components.get_representative(id(3))
+    );
+    assert_eq!(components.get_representative(id(5)), None);
+    assert_eq!(
+        components.get_components(),
+        vec![vec![id(0), id(1), id(2), id(3)], vec![id(4)]]
+    );
+    components.reset();
+    assert_eq!(components.count_nodes(), 0);
+    assert_eq!(components.count_components(), 0);
+    assert!(components.get_components().is_empty());
+}
+
+#[test]
+fn test_union_find_matches_connected_components() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut components = UnionFind::new();
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    // components are updated as edges stream in, and match those of the graph so far.
+    for batch in 0..5 {
+        for _ in 0..40 {
+            let edge = (rng.gen_range(0..200), rng.gen_range(0..200));
+            components.add_edge(NodeId::from(edge.0), NodeId::from(edge.1));
+            edges.push(edge);
+        }
+        let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+        let expected: Vec<Vec<i64>> = graph
+            .get_connected_components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|x| x.value()).collect())
+            .collect();
+        assert_node_sets_have_labels(&components.get_components(), expected);
+        assert_eq!(
+            components.count_components(),
+            graph.get_connected_components().len()
+        );
+        assert_eq!(
+            components.count_nodes(),
+            graph.nodes.len(),
+            "batch {}",
+            batch
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_connected_components_transformer() -> CLQResult<()> {
+    let text = "a\t1\t2\na\t3\t4\na\t2\t5\nb\t7\t8\n";
+    let mut transformer = ConnectedComponentsTransformer::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let output_str: String = String::from_utf8(buffer).unwrap();
+    let lines: BTreeSet<&str> = output_str.lines().collect();
+    let expected: BTreeSet<&str> = vec![
+        "a\t0\t1", "a\t0\t2", "a\t0\t5", "a\t1\t3", "a\t1\t4", "b\t0\t7", "b\t0\t8",
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(lines, expected);
+    Ok(())
+}
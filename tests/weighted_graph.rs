@@ -6,14 +6,24 @@
  */
 extern crate lib_dachshund;
 
+use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
 use lib_dachshund::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
+use lib_dachshund::dachshund::algorithms::label_propagation::LabelPropagation;
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::algorithms::spanning_tree::SpanningTree;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::input::Input;
 use lib_dachshund::dachshund::node::WeightedNodeBase;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use lib_dachshund::dachshund::weighted_core_transformer::WeightedCoreTransformer;
 use lib_dachshund::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
-use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::{
+    DecayedWeightedUndirectedGraphBuilder, TemporalDecay, WeightedUndirectedGraphBuilder,
+};
 
 fn get_graph(idx: usize) -> CLQResult<WeightedUndirectedGraph> {
     let v = match idx {
@@ -131,3 +141,132 @@ fn test_fractional_coreness() {
         );
     }
 }
+
+#[test]
+fn test_label_propagation() -> CLQResult<()> {
+    // Two heavy triangles, attached to hubs 0 and 4 by light edges.
+    let graph = get_graph(6)?;
+    for seed in 0..10 {
+        let communities = graph.get_label_propagation_communities(seed, 100);
+        assert_eq!(communities.len(), 2);
+        let expected: Vec<NodeId> = (0..4).map(|x| NodeId::from(x as i64)).collect();
+        assert_eq!(communities[&0], expected.into_iter().collect());
+        let expected: Vec<NodeId> = (4..8).map(|x| NodeId::from(x as i64)).collect();
+        assert_eq!(communities[&1], expected.into_iter().collect());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_minimum_spanning_forest() -> CLQResult<()> {
+    // uneven square: the heaviest edge is left out.
+    let (tree, total_weight) = get_graph(3)?.get_minimum_spanning_forest();
+    assert_eq!(
+        tree,
+        vec![
+            (NodeId::from(0), NodeId::from(1), 1.0),
+            (NodeId::from(1), NodeId::from(2), 2.0),
+            (NodeId::from(2), NodeId::from(3), 3.0),
+        ]
+    );
+    assert_eq!(total_weight, 6.0);
+
+    // the light spokes of hubs 0 and 4 span the graph, leaving out the triangles.
+    let (tree, total_weight) = get_graph(6)?.get_minimum_spanning_forest();
+    assert_eq!(tree.len(), 7);
+    assert!(tree.iter().all(|(_, _, weight)| *weight < 2.0));
+    assert!((total_weight - 7.3).abs() < 1e-9);
+
+    // two components make for a forest with one edge fewer than a tree would have.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (1, 2, 1.0),
+        (0, 2, 2.0),
+        (3, 4, 7.0),
+    ])?;
+    let (forest, total_weight) = graph.get_minimum_spanning_forest();
+    assert_eq!(forest.len(), 3);
+    assert_eq!(total_weight, 10.0);
+    assert!(forest.contains(&(NodeId::from(3), NodeId::from(4), 7.0)));
+    Ok(())
+}
+
+#[test]
+fn test_weighted_shortest_paths() -> CLQResult<()> {
+    // going around 0-1-2-3 is shorter than the direct edge 0-3; 2 is reached two ways.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 2.0),
+        (0, 2, 3.0),
+        (2, 3, 0.5),
+        (0, 3, 10.0),
+        (4, 5, 1.0),
+    ])?;
+    let (dists, preds) = graph.get_weighted_shortest_paths(NodeId::from(0))?;
+    assert_eq!(dists.len(), 4);
+    assert_eq!(dists[&NodeId::from(2)], 3.0);
+    assert_eq!(dists[&NodeId::from(3)], 3.5);
+    assert!(!dists.contains_key(&NodeId::from(4)));
+    assert!(preds[&NodeId::from(0)].is_empty());
+    let mut two_preds = preds[&NodeId::from(2)].clone();
+    two_preds.sort();
+    assert_eq!(two_preds, vec![NodeId::from(0), NodeId::from(1)]);
+    assert_eq!(preds[&NodeId::from(3)], vec![NodeId::from(2)]);
+
+    assert!(graph.get_weighted_shortest_paths(NodeId::from(6)).is_err());
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, -1.0)])?;
+    assert!(graph.get_weighted_shortest_paths(NodeId::from(0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_betweenness() -> CLQResult<()> {
+    // the graph of test_weighted_shortest_paths: 1 is on one of the two shortest paths
+    // from 0 to 2 and from 0 to 3, and 2 is on all paths from 0 and 1 to 3.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 2.0),
+        (0, 2, 3.0),
+        (2, 3, 0.5),
+        (0, 3, 10.0),
+        (4, 5, 1.0),
+    ])?;
+    let bet = graph.get_node_betweenness_brandes_weighted()?;
+    let expected: Vec<f64> = vec![0.0, 1.0, 2.0, 0.0, 0.0, 0.0];
+    for (id, value) in expected.into_iter().enumerate() {
+        assert_eq!(bet[&NodeId::from(id as i64)], value);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_temporal_decay() -> CLQResult<()> {
+    let decay = TemporalDecay::new(100.0, 10.0)?;
+    assert_eq!(decay.get_weight(100.0), 1.0);
+    assert_eq!(decay.get_weight(80.0), 0.25);
+    assert_eq!(decay.get_weight(200.0), 1.0);
+    assert!(TemporalDecay::new(100.0, 0.0).is_err());
+
+    // interactions between the same nodes add up, in either direction.
+    let graph = DecayedWeightedUndirectedGraphBuilder { decay }.from_vector(vec![
+        (0, 1, 100.0),
+        (1, 0, 90.0),
+        (1, 2, 80.0),
+        (0, 2, 200.0),
+    ])?;
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.get_node_weight(NodeId::from(0)), 2.5);
+    assert_eq!(graph.get_node_weight(NodeId::from(1)), 1.75);
+    assert_eq!(graph.get_node_weight(NodeId::from(2)), 1.25);
+
+    let text = "g\t0\t1\t100\ng\t1\t0\t90\ng\t1\t2\t80\ng\t0\t2\t200\n";
+    let mut buffer: Vec<u8> = Vec::new();
+    WeightedCoreTransformer::with_temporal_decay(decay)
+        .run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+    let output = String::from_utf8(buffer)?;
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|x| x.starts_with("g\t")));
+    Ok(())
+}